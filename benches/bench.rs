@@ -9,16 +9,60 @@ fn readme(c: &mut Criterion) {
     });
 }
 
-// fn one_and_a_half_mb(c: &mut Criterion) {
-//     let doc = fs::read_to_string("../a-dump-of-markdown/markdown.md").unwrap();
-//     let mut group = c.benchmark_group("giant");
-//     group.sample_size(10);
-//     group.bench_with_input(BenchmarkId::new("giant", "1.5 mb"), &doc, |b, s| {
-//         b.iter(|| markdown::to_html(s));
-//     });
-//     group.finish();
-// }
-// , one_and_a_half_mb
-
-criterion_group!(benches, readme);
+fn snippets(c: &mut Criterion) {
+    let doc = fs::read_to_string("readme.md").unwrap();
+    let snippets = doc.split("\n\n").collect::<Vec<_>>();
+
+    c.bench_with_input(
+        BenchmarkId::new("snippets", "readme paragraphs"),
+        &snippets,
+        |b, s| {
+            b.iter(|| {
+                for snippet in s {
+                    markdown::to_html(snippet);
+                }
+            });
+        },
+    );
+}
+
+fn one_mb(c: &mut Criterion) {
+    let readme = fs::read_to_string("readme.md").unwrap();
+    let mut doc = String::new();
+
+    // Repeat the readme (which has most constructs) up to 1mb.
+    while doc.len() < 1024 * 1024 {
+        doc.push_str(&readme);
+        doc.push('\n');
+    }
+
+    let mut group = c.benchmark_group("giant");
+    group.sample_size(10);
+    group.bench_with_input(BenchmarkId::new("giant", "1 mb"), &doc, |b, s| {
+        b.iter(|| markdown::to_html(s));
+    });
+    group.finish();
+}
+
+fn pathological(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pathological");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("pathological", "brackets"),
+        &"[".repeat(50000),
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("pathological", "attention"),
+        &"*a".repeat(50000),
+        |b, s| {
+            b.iter(|| markdown::to_html(s));
+        },
+    );
+    group.finish();
+}
+
+criterion_group!(benches, readme, snippets, one_mb, pathological);
 criterion_main!(benches);
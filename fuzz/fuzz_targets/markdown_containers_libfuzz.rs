@@ -0,0 +1,48 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+/// Parts that form containers (block quotes, list items, footnote
+/// definitions, directives) and the flow that is interrupted by or continued
+/// in them.
+const PARTS: [&str; 32] = [
+    ">", "> ", "*", "* ", "-", "- ", "+ ", "1.", "1) ", "2. ", " ", "  ", "   ", "    ", "\t",
+    "\n", "\r\n", "\r", "a", "```", "~~~", "$$", "<div>", "---", "===", ":::", ":::a", "[^a]:",
+    "[a]:", "[x] ", "|", "\\",
+];
+
+fuzz_target!(|data: &[u8]| {
+    // Each byte picks a part, so that deep and pathological nesting is found
+    // quickly.
+    let mut value = String::new();
+    for byte in data {
+        value.push_str(PARTS[usize::from(*byte) % PARTS.len()]);
+    }
+
+    let mut constructs = markdown::Constructs::gfm();
+    constructs.directive_container = true;
+    constructs.directive_leaf = true;
+    constructs.directive_text = true;
+    constructs.frontmatter = true;
+    constructs.math_flow = true;
+    constructs.math_text = true;
+    let parse = markdown::ParseOptions {
+        constructs,
+        ..markdown::ParseOptions::gfm()
+    };
+    let strict = markdown::ParseOptions {
+        constructs: parse.constructs.clone(),
+        block_quote_lazy: false,
+        ..markdown::ParseOptions::gfm()
+    };
+
+    let _ = markdown::to_html(&value);
+    let _ = markdown::to_html_with_options(
+        &value,
+        &markdown::Options {
+            parse,
+            ..markdown::Options::gfm()
+        },
+    );
+    let _ = markdown::to_mdast(&value, &strict);
+    let _ = markdown::to_mdast(&value, &markdown::ParseOptions::mdx());
+});
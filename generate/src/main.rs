@@ -11,6 +11,7 @@ use std::fs;
 async fn main() {
     commonmark().await;
     punctuation().await;
+    emoji().await;
 }
 
 async fn commonmark() {
@@ -140,6 +141,8 @@ async fn punctuation() {
 
 /// List of characters that are considered punctuation.
 ///
+/// Sorted by code point, so that it can be searched quickly.
+///
 /// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
 /// > It is generate from the latest Unicode data.
 ///
@@ -153,7 +156,7 @@ async fn punctuation() {
 /// ## References
 ///
 /// *   [*§ 2.1 Characters and lines* in `CommonMark`](https://spec.commonmark.org/0.31.2/#unicode-punctuation-character)
-pub const PUNCTUATION: [char; {}] = [
+pub static PUNCTUATION: [char; {}] = [
 {}
 ];
 ",
@@ -163,3 +166,66 @@ pub const PUNCTUATION: [char; {}] = [
 
     fs::write(code_url, doc).unwrap();
 }
+
+async fn emoji() {
+    let url = "https://raw.githubusercontent.com/github/gemoji/master/db/emoji.json";
+    let data_url = "emoji-data.json";
+    let code_url = "src/util/emoji.rs";
+
+    let value = if let Ok(value) = fs::read_to_string(data_url) {
+        value
+    } else {
+        let value = reqwest::get(url).await.unwrap().text().await.unwrap();
+
+        fs::write(data_url, value.clone()).unwrap();
+
+        value
+    };
+
+    let re = Regex::new(r#""emoji":\s*"([^"]+)"[\s\S]*?"aliases":\s*\[([^\]]*)\]"#).unwrap();
+    let re_alias = Regex::new(r#""([^"]+)""#).unwrap();
+    let re_shortcode = Regex::new(r"^[+\-0-9_a-z]+$").unwrap();
+    let mut found = vec![];
+
+    for captures in re.captures_iter(&value) {
+        let emoji = captures[1]
+            .chars()
+            .map(|d| format!("\\u{{{:x}}}", d as u32))
+            .collect::<String>();
+
+        for alias in re_alias.captures_iter(&captures[2]) {
+            let alias = alias[1].to_string();
+
+            if re_shortcode.is_match(&alias) {
+                found.push((alias, emoji.clone()));
+            }
+        }
+    }
+
+    found.sort();
+    found.dedup_by(|a, b| a.0 == b.0);
+
+    let doc = format!(
+        "//! Info on emoji.
+
+/// List of emoji shortcodes (without colons) and the emoji they stand for,
+/// sorted by shortcode.
+///
+/// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
+/// > It is generate from the emoji data of `gemoji`.
+///
+/// Only included with the `emoji` feature.
+pub static EMOJI: [(&str, &str); {}] = [
+{}
+];
+",
+        found.len(),
+        found
+            .iter()
+            .map(|d| format!("    (\"{}\", \"{}\"),", d.0, d.1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+
+    fs::write(code_url, doc).unwrap();
+}
@@ -1,8 +1,16 @@
+use crate::event::Name;
 use crate::util::{
+    code_highlight::Highlighter,
+    code_language::Transform as CodeLanguageTransform,
+    constant::{HARD_BREAK_PREFIX_SIZE_MIN, TAB_SIZE},
+    directive::Handler as DirectiveHandler,
+    flow_extension::{FlowExtension, Handler as FlowExtensionHandler},
     line_ending::LineEnding,
     mdx::{EsmParse as MdxEsmParse, ExpressionParse as MdxExpressionParse},
+    text_extension::{Handler as TextExtensionHandler, TextExtension},
+    url_transform::{IsExternal as IsExternalLink, Transform as UrlTransform},
 };
-use alloc::{boxed::Box, fmt, string::String};
+use alloc::{boxed::Box, collections::BTreeMap, fmt, rc::Rc, string::String, vec, vec::Vec};
 
 /// Control which constructs are enabled.
 ///
@@ -32,6 +40,18 @@ use alloc::{boxed::Box, fmt, string::String};
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Constructs {
+    /// Abbreviation extension.
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// > | a HTML b
+    ///       ^^^^
+    /// ```
+    ///
+    /// Abbreviations are expanded in text, see
+    /// [`abbreviation_ignore_case`][ParseOptions::abbreviation_ignore_case].
+    pub abbreviation: bool,
     /// Attention.
     ///
     /// ```markdown
@@ -39,6 +59,22 @@ pub struct Constructs {
     ///       ^^^   ^^^^^
     /// ```
     pub attention: bool,
+    /// Attributes extension.
+    ///
+    /// ```markdown
+    /// > | ## a {#b .c}
+    ///          ^^^^^^
+    /// > | d {e=f}
+    ///       ^^^^^
+    /// ```
+    ///
+    /// Attribute blocks are only recognized at the end of headings and
+    /// paragraphs, where they add attributes to the element.
+    /// Event handlers (`on*`) and `style` are never added, and other
+    /// attributes than `id`, `class`, `dir`, `lang`, and `title` are only
+    /// added when [`allow_dangerous_html`][CompileOptions::allow_dangerous_html]
+    /// is on.
+    pub attributes: bool,
     /// Autolink.
     ///
     /// ```markdown
@@ -99,6 +135,51 @@ pub struct Constructs {
     ///     ^^^^^^^^^^
     /// ```
     pub definition: bool,
+    /// Definition list extension.
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    /// > | : b
+    ///     ^^^
+    /// ```
+    pub definition_list: bool,
+    /// Directive extension: container.
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    pub directive_container: bool,
+    /// Directive extension: leaf.
+    ///
+    /// ```markdown
+    /// > | ::a[b]{c}
+    ///     ^^^^^^^^^
+    /// ```
+    pub directive_leaf: bool,
+    /// Directive extension: text.
+    ///
+    /// ```markdown
+    /// > | a :b[c]{d} e
+    ///       ^^^^^^^^
+    /// ```
+    pub directive_text: bool,
+    /// Emoji shortcode.
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///       ^^^^^^^
+    /// ```
+    ///
+    /// Shortcodes are looked up in
+    /// [`emoji_shortcodes`][ParseOptions::emoji_shortcodes], and, with the
+    /// `emoji` feature, in the shortcodes used on GitHub.
+    pub emoji: bool,
     /// Frontmatter.
     ///
     /// ````markdown
@@ -116,7 +197,51 @@ pub struct Constructs {
     /// > | https://example.com
     ///     ^^^^^^^^^^^^^^^^^^^
     /// ```
+    ///
+    /// This turns all kinds of literals on or off.
+    /// Each kind can also be turned off on its own, with
+    /// [`gfm_autolink_literal_email`][Constructs::gfm_autolink_literal_email],
+    /// [`gfm_autolink_literal_protocol`][Constructs::gfm_autolink_literal_protocol],
+    /// and [`gfm_autolink_literal_www`][Constructs::gfm_autolink_literal_www].
     pub gfm_autolink_literal: bool,
+    /// GFM: autolink literal (email).
+    ///
+    /// ```markdown
+    /// > | contact@example.com
+    ///     ^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Only used when
+    /// [`gfm_autolink_literal`][Constructs::gfm_autolink_literal] is on,
+    /// which is why this is on by default.
+    ///
+    /// This includes literals with a `mailto:` or `xmpp:` protocol, such as
+    /// `mailto:contact@example.com`.
+    /// Autolinks in angle brackets, such as `<mailto:contact@example.com>`,
+    /// are [`autolink`][Constructs::autolink] instead.
+    pub gfm_autolink_literal_email: bool,
+    /// GFM: autolink literal (protocol).
+    ///
+    /// ```markdown
+    /// > | https://example.com
+    ///     ^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Only used when
+    /// [`gfm_autolink_literal`][Constructs::gfm_autolink_literal] is on,
+    /// which is why this is on by default.
+    pub gfm_autolink_literal_protocol: bool,
+    /// GFM: autolink literal (www).
+    ///
+    /// ```markdown
+    /// > | www.example.com
+    ///     ^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Only used when
+    /// [`gfm_autolink_literal`][Constructs::gfm_autolink_literal] is on,
+    /// which is why this is on by default.
+    pub gfm_autolink_literal_www: bool,
     /// GFM: footnote definition.
     ///
     /// ```markdown
@@ -186,7 +311,18 @@ pub struct Constructs {
     /// > | ==
     ///     ^^
     /// ```
+    ///
+    /// When off, `===` after a paragraph is part of that paragraph, while
+    /// `---` after a paragraph is a thematic break (if
+    /// [`thematic_break`][Constructs::thematic_break] is on).
     pub heading_setext: bool,
+    /// Highlight.
+    ///
+    /// ```markdown
+    /// > | a ==b== c.
+    ///       ^^^^^
+    /// ```
+    pub highlight: bool,
     /// HTML (flow).
     ///
     /// ```markdown
@@ -320,6 +456,34 @@ pub struct Constructs {
     /// > Otherwise, expressions are parsed with a basic algorithm that only
     /// > cares about braces.
     pub mdx_jsx_text: bool,
+    /// Subscript.
+    ///
+    /// ```markdown
+    /// > | H~2~O
+    ///      ^^^
+    /// ```
+    ///
+    /// > 👉 **Note**: subscript uses one tilde and GFM strikethrough two
+    /// > tildes.
+    /// > When both are on, one tilde is always subscript, so
+    /// > [`gfm_strikethrough_single_tilde`][ParseOptions::gfm_strikethrough_single_tilde]
+    /// > does nothing.
+    ///
+    /// > 👉 **Note**: like in Pandoc, subscript cannot contain whitespace
+    /// > (`~a b~` is not subscript).
+    /// > Use a character reference (`~a&#32;b~`) instead.
+    pub subscript: bool,
+    /// Superscript.
+    ///
+    /// ```markdown
+    /// > | 2^10^
+    ///      ^^^^
+    /// ```
+    ///
+    /// > 👉 **Note**: like in Pandoc, superscript cannot contain whitespace
+    /// > (`^a b^` is not superscript).
+    /// > Use a character reference (`^a&#32;b^`) instead.
+    pub superscript: bool,
     /// Thematic break.
     ///
     /// ```markdown
@@ -340,7 +504,9 @@ impl Default for Constructs {
     /// <https://spec.commonmark.org>.
     fn default() -> Self {
         Self {
+            abbreviation: false,
             attention: true,
+            attributes: false,
             autolink: true,
             block_quote: true,
             character_escape: true,
@@ -349,8 +515,16 @@ impl Default for Constructs {
             code_fenced: true,
             code_text: true,
             definition: true,
+            definition_list: false,
+            directive_container: false,
+            directive_leaf: false,
+            directive_text: false,
+            emoji: false,
             frontmatter: false,
             gfm_autolink_literal: false,
+            gfm_autolink_literal_email: true,
+            gfm_autolink_literal_protocol: true,
+            gfm_autolink_literal_www: true,
             gfm_label_start_footnote: false,
             gfm_footnote_definition: false,
             gfm_strikethrough: false,
@@ -360,6 +534,7 @@ impl Default for Constructs {
             hard_break_trailing: true,
             heading_atx: true,
             heading_setext: true,
+            highlight: false,
             html_flow: true,
             html_text: true,
             label_start_image: true,
@@ -373,6 +548,8 @@ impl Default for Constructs {
             mdx_expression_text: false,
             mdx_jsx_flow: false,
             mdx_jsx_text: false,
+            subscript: false,
+            superscript: false,
             thematic_break: true,
         }
     }
@@ -434,719 +611,3911 @@ impl Constructs {
     }
 }
 
-/// Configuration that describes how to compile to HTML.
+/// What to use as the caption of a figure.
 ///
-/// You likely either want to turn on the dangerous options
-/// (`allow_dangerous_html`, `allow_dangerous_protocol`) when dealing with
-/// input you trust, or want to customize how GFM footnotes are compiled
-/// (typically because the input markdown is not in English).
+/// Only used for [`image_figure`][CompileOptions::image_figure].
 ///
 /// ## Examples
 ///
 /// ```
-/// use markdown::CompileOptions;
+/// use markdown::FigureCaption;
 /// # fn main() {
 ///
-/// // Use the default trait to get safe defaults:
-/// let safe = CompileOptions::default();
+/// // Use the alternative text of the image:
+/// let alt = FigureCaption::Alt;
 ///
-/// // Live dangerously / trust the author:
-/// let danger = CompileOptions {
-///   allow_dangerous_html: true,
-///   allow_dangerous_protocol: true,
-///   ..CompileOptions::default()
-/// };
+/// // Or, use its title:
+/// let title = FigureCaption::Title;
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FigureCaption {
+    /// The alternative text of the image (`a` in `![a](b "c")`).
+    Alt,
+    /// The title of the image (`c` in `![a](b "c")`).
+    Title,
+}
+
+/// Where to place GFM footnotes.
 ///
-/// // In French:
-/// let enFrançais = CompileOptions {
-///   gfm_footnote_label: Some("Notes de bas de page".into()),
-///   gfm_footnote_back_label: Some("Arrière".into()),
-///   ..CompileOptions::default()
-/// };
+/// Only used for [`gfm_footnote_style`][CompileOptions::gfm_footnote_style].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::FootnoteStyle;
+/// # fn main() {
+///
+/// // Use the default trait to place footnotes in a section at the end:
+/// let section = FootnoteStyle::default();
+///
+/// // Or, place them after where they are first referenced:
+/// let inline = FootnoteStyle::Inline;
 /// # }
 /// ```
-#[allow(clippy::struct_excessive_bools)]
-#[derive(Clone, Debug, Default)]
-pub struct CompileOptions {
-    /// Whether to allow (dangerous) HTML.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FootnoteStyle {
+    /// Footnotes in a section at the end of the document, in an ordered
+    /// list.
     ///
-    /// The default is `false`, which still parses the HTML according to
-    /// `CommonMark` but shows the HTML as text instead of as elements.
+    /// ```html
+    /// <p>a<sup><a href="#user-content-fn-1" …>1</a></sup></p>
+    /// <section data-footnotes="" class="footnotes">…
+    /// <ol>
+    /// <li id="user-content-fn-1">
+    /// <p>b <a href="#user-content-fnref-1" …>↩</a></p>
+    /// </li>
+    /// </ol>
+    /// </section>
+    /// ```
+    Section,
+    /// Each footnote in an `<aside>`, right after the block where it is first
+    /// referenced, such as for sidenotes or popovers.
     ///
-    /// Pass `true` for trusted content to get actual HTML elements.
+    /// ```html
+    /// <p>a<sup><a href="#user-content-fn-1" …>1</a></sup></p>
+    /// <aside id="user-content-fn-1" class="footnote" data-footnote-number="1">
+    /// <p>b <a href="#user-content-fnref-1" …>↩</a></p>
+    /// </aside>
+    /// ```
+    Inline,
+}
+
+impl Default for FootnoteStyle {
+    /// Section.
+    fn default() -> Self {
+        Self::Section
+    }
+}
+
+/// Kinds of frontmatter fences to support.
+///
+/// Only used when [`frontmatter`][Constructs::frontmatter] is turned on.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::FrontmatterKind;
+/// # fn main() {
+///
+/// // Use the default trait to support both YAML (`---`) and TOML (`+++`):
+/// let both = FrontmatterKind::default();
+///
+/// // Or, only support TOML:
+/// let toml = FrontmatterKind::Toml;
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FrontmatterKind {
+    /// YAML frontmatter, fenced with `---`.
     ///
-    /// When using GFM, make sure to also turn off `gfm_tagfilter`.
-    /// Otherwise, some dangerous HTML is still ignored.
+    /// ```markdown
+    /// > | ---
+    ///     ^^^
+    /// > | title: Neptune
+    ///     ^^^^^^^^^^^^^^
+    /// > | ---
+    ///     ^^^
+    /// ```
+    Yaml,
+    /// TOML frontmatter, fenced with `+++`.
     ///
-    /// ## Examples
+    /// ```markdown
+    /// > | +++
+    ///     ^^^
+    /// > | title = "Neptune"
+    ///     ^^^^^^^^^^^^^^^^^
+    /// > | +++
+    ///     ^^^
+    /// ```
+    Toml,
+    /// Both YAML and TOML frontmatter.
+    Both,
+}
+
+impl Default for FrontmatterKind {
+    /// Both YAML and TOML.
+    fn default() -> Self {
+        Self::Both
+    }
+}
+
+impl FrontmatterKind {
+    /// Whether `marker` (`-` or `+`) can start a fence.
+    pub(crate) fn allows(&self, marker: u8) -> bool {
+        match self {
+            Self::Yaml => marker == b'-',
+            Self::Toml => marker == b'+',
+            Self::Both => matches!(marker, b'-' | b'+'),
+        }
+    }
+}
+
+/// Configuration for [heading (atx)][crate::construct::heading_atx].
+///
+/// The default follows `CommonMark`.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::HeadingAtxOptions;
+/// # fn main() {
+///
+/// // Use the default trait to follow `CommonMark`:
+/// let commonmark = HeadingAtxOptions::default();
+///
+/// // Or, allow headings without whitespace after the opening sequence, as
+/// // older markdown did:
+/// let relaxed = HeadingAtxOptions {
+///   space_required: false,
+///   ..HeadingAtxOptions::default()
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeadingAtxOptions {
+    /// Whether a closing sequence is allowed.
     ///
+    /// When off, the hashes at the end are part of the text.
+    ///
+    /// ```markdown
+    /// > | ## a ##
+    ///          ^^
     /// ```
-    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
-    /// # fn main() -> Result<(), markdown::message::Message> {
+    pub closing_sequence: bool,
+    /// Whether whitespace is required after the opening sequence.
     ///
-    /// // `markdown-rs` is safe by default:
-    /// assert_eq!(
-    ///     to_html("Hi, <i>venus</i>!"),
-    ///     "<p>Hi, &lt;i&gt;venus&lt;/i&gt;!</p>"
-    /// );
+    /// When off, the text can start right after the hashes.
     ///
-    /// // Turn `allow_dangerous_html` on to allow potentially dangerous HTML:
-    /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "Hi, <i>venus</i>!",
-    ///         &Options {
-    ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               ..CompileOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<p>Hi, <i>venus</i>!</p>"
-    /// );
-    /// # Ok(())
-    /// # }
+    /// ```markdown
+    /// > | ##a
+    ///       ^
     /// ```
-    pub allow_dangerous_html: bool,
+    pub space_required: bool,
+}
 
-    /// Whether to allow dangerous protocols in links and images.
-    ///
-    /// The default is `false`, which drops URLs in links and images that use
-    /// dangerous protocols.
+impl Default for HeadingAtxOptions {
+    /// `CommonMark` defaults.
+    fn default() -> Self {
+        Self {
+            closing_sequence: true,
+            space_required: true,
+        }
+    }
+}
+
+/// Control which kinds of HTML are allowed.
+///
+/// Only used when [`allow_dangerous_html`][CompileOptions::allow_dangerous_html]
+/// is turned on.
+/// HTML of a kind that is not allowed is shown as text, as if
+/// `allow_dangerous_html` was off.
+///
+/// The kinds are those of [HTML (flow)][crate::construct::html_flow].
+/// HTML (text) is classified in the same way, so for example `<script>` in a
+/// paragraph is also [`raw`][HtmlFilter::raw].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::HtmlFilter;
+/// # fn main() {
+///
+/// // Use the default trait to allow all HTML:
+/// let all = HtmlFilter::default();
+///
+/// // Or, only allow comments:
+/// let comments = HtmlFilter {
+///   comment: true,
+///   ..HtmlFilter::none()
+/// };
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HtmlFilter {
+    /// Raw HTML: the opening tags `<pre>`, `<script>`, `<style>`, and
+    /// `<textarea>`.
     ///
-    /// Pass `true` for trusted content to support all protocols.
+    /// ```markdown
+    /// > | <script>alert(1)</script>
+    ///     ^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    pub raw: bool,
+    /// Comments.
     ///
-    /// URLs that have no protocol (which means it’s relative to the current
-    /// page, such as `./some/page.html`) and URLs that have a safe protocol
-    /// (for images: `http`, `https`; for links: `http`, `https`, `irc`,
-    /// `ircs`, `mailto`, `xmpp`), are safe.
-    /// All other URLs are dangerous and dropped.
+    /// ```markdown
+    /// > | <!-- a -->
+    ///     ^^^^^^^^^^
+    /// ```
+    pub comment: bool,
+    /// Processing instructions.
     ///
-    /// ## Examples
+    /// ```markdown
+    /// > | <?php echo 1 ?>
+    ///     ^^^^^^^^^^^^^^^
+    /// ```
+    pub instruction: bool,
+    /// Declarations.
     ///
+    /// ```markdown
+    /// > | <!DOCTYPE html>
+    ///     ^^^^^^^^^^^^^^^
     /// ```
-    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
-    /// # fn main() -> Result<(), markdown::message::Message> {
+    pub declaration: bool,
+    /// CDATA.
     ///
-    /// // `markdown-rs` is safe by default:
-    /// assert_eq!(
-    ///     to_html("<javascript:alert(1)>"),
-    ///     "<p><a href=\"\">javascript:alert(1)</a></p>"
-    /// );
+    /// ```markdown
+    /// > | <![CDATA[a]]>
+    ///     ^^^^^^^^^^^^^
+    /// ```
+    pub cdata: bool,
+    /// Basic HTML: opening and closing tags of known block elements, such as
+    /// `<div>` or `</p>`.
     ///
-    /// // Turn `allow_dangerous_protocol` on to allow potentially dangerous protocols:
-    /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "<javascript:alert(1)>",
-    ///         &Options {
-    ///             compile: CompileOptions {
-    ///               allow_dangerous_protocol: true,
-    ///               ..CompileOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<p><a href=\"javascript:alert(1)\">javascript:alert(1)</a></p>"
-    /// );
-    /// # Ok(())
-    /// # }
+    /// ```markdown
+    /// > | <div>
+    ///     ^^^^^
     /// ```
-    pub allow_dangerous_protocol: bool,
+    pub basic: bool,
+    /// Complete HTML: all other opening and closing tags, such as `<x-y>`
+    /// or `</script>`.
+    ///
+    /// ```markdown
+    /// > | <x-y>
+    ///     ^^^^^
+    /// ```
+    pub complete: bool,
+}
+
+impl Default for HtmlFilter {
+    /// Allow all HTML.
+    fn default() -> Self {
+        Self {
+            raw: true,
+            comment: true,
+            instruction: true,
+            declaration: true,
+            cdata: true,
+            basic: true,
+            complete: true,
+        }
+    }
+}
+
+impl HtmlFilter {
+    /// Allow no HTML.
+    pub fn none() -> Self {
+        Self {
+            raw: false,
+            comment: false,
+            instruction: false,
+            declaration: false,
+            cdata: false,
+            basic: false,
+            complete: false,
+        }
+    }
+}
+
+/// How browsers should load images.
+///
+/// Only used for [`image_loading`][CompileOptions::image_loading].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ImageLoading;
+/// # fn main() {
+///
+/// // Load images when they are almost visible:
+/// let lazy = ImageLoading::Lazy;
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ImageLoading {
+    /// Load images when they are almost visible (`loading="lazy"`).
+    Lazy,
+    /// Load images right away (`loading="eager"`).
+    Eager,
+}
+
+/// What to do with invisible characters in text.
+///
+/// Only used for [`invisibles`][CompileOptions::invisibles].
+///
+/// Invisible characters are zero width spaces (U+200B), word joiners
+/// (U+2060), and zero width no-break spaces, also known as byte order marks
+/// (U+FEFF), when they are not at the start of the document.
+/// Zero width joiners and non-joiners (U+200D, U+200C) are not included, as
+/// they are meaningful in emoji and in several scripts.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::Invisibles;
+/// # fn main() {
+///
+/// // Use the default trait to keep invisible characters:
+/// let keep = Invisibles::default();
+///
+/// // Or, remove them:
+/// let strip = Invisibles::Strip;
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Invisibles {
+    /// Keep invisible characters as they are.
+    Keep,
+    /// Remove invisible characters.
+    Strip,
+    /// Replace invisible characters with character references, such as
+    /// `&#x200B;`, which render the same but are visible in the HTML.
+    Replace,
+}
+
+impl Default for Invisibles {
+    /// Keep.
+    fn default() -> Self {
+        Self::Keep
+    }
+}
+
+/// Configuration for [list item][crate::construct::list_item].
+///
+/// The default follows `CommonMark`, which allows all bullets and
+/// delimiters.
+/// Markers that are not allowed do not start list items: such lines are
+/// typically paragraphs instead.
+/// Thematic breaks (such as `***` and `---`) are not affected.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ListItemOptions;
+/// # fn main() {
+///
+/// // Use the default trait to follow `CommonMark`:
+/// let commonmark = ListItemOptions::default();
+///
+/// // Or, only allow `-` for unordered lists:
+/// let dash = ListItemOptions {
+///   bullet_asterisk: false,
+///   bullet_plus: false,
+///   ..ListItemOptions::default()
+/// };
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListItemOptions {
+    /// Whether an asterisk can start an unordered list item.
+    ///
+    /// ```markdown
+    /// > | * a
+    ///     ^
+    /// ```
+    pub bullet_asterisk: bool,
+    /// Whether a dash can start an unordered list item.
+    ///
+    /// ```markdown
+    /// > | - a
+    ///     ^
+    /// ```
+    pub bullet_dash: bool,
+    /// Whether a plus sign can start an unordered list item.
+    ///
+    /// ```markdown
+    /// > | + a
+    ///     ^
+    /// ```
+    pub bullet_plus: bool,
+    /// Whether a period can follow the value of an ordered list item.
+    ///
+    /// ```markdown
+    /// > | 1. a
+    ///      ^
+    /// ```
+    pub delimiter_period: bool,
+    /// Whether a right parenthesis can follow the value of an ordered list
+    /// item.
+    ///
+    /// ```markdown
+    /// > | 1) a
+    ///      ^
+    /// ```
+    pub delimiter_parenthesis: bool,
+}
+
+impl Default for ListItemOptions {
+    /// `CommonMark` defaults.
+    fn default() -> Self {
+        Self {
+            bullet_asterisk: true,
+            bullet_dash: true,
+            bullet_plus: true,
+            delimiter_period: true,
+            delimiter_parenthesis: true,
+        }
+    }
+}
+
+/// How to wrap prose when formatting markdown.
+///
+/// Only used for [`prose_wrap`][FormatOptions::prose_wrap].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ProseWrap;
+/// # fn main() {
+///
+/// // Use the default trait to keep line endings as they are:
+/// let preserve = ProseWrap::default();
+///
+/// // Or, wrap prose at the print width:
+/// let always = ProseWrap::Always;
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProseWrap {
+    /// Keep line endings in prose as they are.
+    Preserve,
+    /// Wrap prose so that lines fit in
+    /// [`print_width`][FormatOptions::print_width], where possible.
+    Always,
+    /// Put each paragraph on one line, except for hard breaks.
+    Never,
+}
+
+impl Default for ProseWrap {
+    /// Preserve.
+    fn default() -> Self {
+        Self::Preserve
+    }
+}
+
+/// Configuration that describes how to compile to HTML.
+///
+/// You likely either want to turn on the dangerous options
+/// (`allow_dangerous_html`, `allow_dangerous_protocol`) when dealing with
+/// input you trust, or want to customize how GFM footnotes are compiled
+/// (typically because the input markdown is not in English).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::CompileOptions;
+/// # fn main() {
+///
+/// // Use the default trait to get safe defaults:
+/// let safe = CompileOptions::default();
+///
+/// // Live dangerously / trust the author:
+/// let danger = CompileOptions {
+///   allow_dangerous_html: true,
+///   allow_dangerous_protocol: true,
+///   ..CompileOptions::default()
+/// };
+///
+/// // In French:
+/// let enFrançais = CompileOptions {
+///   gfm_footnote_label: Some("Notes de bas de page".into()),
+///   gfm_footnote_back_label: Some("Arrière".into()),
+///   ..CompileOptions::default()
+/// };
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone)]
+pub struct CompileOptions {
+    /// Whether to allow (dangerous) HTML.
+    ///
+    /// The default is `false`, which still parses the HTML according to
+    /// `CommonMark` but shows the HTML as text instead of as elements.
+    ///
+    /// Pass `true` for trusted content to get actual HTML elements.
+    ///
+    /// When using GFM, make sure to also turn off `gfm_tagfilter`.
+    /// Otherwise, some dangerous HTML is still ignored.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` is safe by default:
+    /// assert_eq!(
+    ///     to_html("Hi, <i>venus</i>!"),
+    ///     "<p>Hi, &lt;i&gt;venus&lt;/i&gt;!</p>"
+    /// );
+    ///
+    /// // Turn `allow_dangerous_html` on to allow potentially dangerous HTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "Hi, <i>venus</i>!",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>Hi, <i>venus</i>!</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub allow_dangerous_html: bool,
+
+    /// Which kinds of HTML to allow.
+    ///
+    /// The default is to allow all kinds.
+    /// Only used when `allow_dangerous_html` is on: HTML of kinds that are
+    /// not allowed is shown as text instead of as elements.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, HtmlFilter, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Allow comments, but not other HTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<!-- a -->\n\n<script>alert(1)</script>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               html_filter: HtmlFilter {
+    ///                 comment: true,
+    ///                 ..HtmlFilter::none()
+    ///               },
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<!-- a -->\n&lt;script&gt;alert(1)&lt;/script&gt;"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub html_filter: HtmlFilter,
+
+    /// Whether to allow dangerous protocols in links and images.
+    ///
+    /// The default is `false`, which drops URLs in links and images that use
+    /// dangerous protocols.
+    ///
+    /// Pass `true` for trusted content to support all protocols.
+    ///
+    /// URLs that have no protocol (which means it’s relative to the current
+    /// page, such as `./some/page.html`) and URLs that have a safe protocol
+    /// (for images: `http`, `https`; for links: `http`, `https`, `irc`,
+    /// `ircs`, `mailto`, `xmpp`), are safe.
+    /// For images, `data:` URLs of GIF, JPEG, PNG, and WebP images are safe
+    /// too.
+    /// All other URLs (such as `javascript:`, `vbscript:`, `file:`, and other
+    /// `data:` URLs) are dangerous and dropped.
+    /// This check happens after character references are decoded, so
+    /// `&#x6A;avascript:` is dropped too.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` is safe by default:
+    /// assert_eq!(
+    ///     to_html("<javascript:alert(1)>"),
+    ///     "<p><a href=\"\">javascript:alert(1)</a></p>"
+    /// );
+    ///
+    /// // Turn `allow_dangerous_protocol` on to allow potentially dangerous protocols:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<javascript:alert(1)>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_protocol: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"javascript:alert(1)\">javascript:alert(1)</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub allow_dangerous_protocol: bool,
+
+    /// Base URL to resolve relative URLs in links and images against.
+    ///
+    /// The default is `None`, which leaves URLs as they are.
+    ///
+    /// Pass a URL, such as `https://example.com/docs/`, to turn relative
+    /// URLs into absolute ones, like a browser would.
+    /// This applies to links and images, including references to
+    /// definitions.
+    /// Autolinks and URLs that already have a protocol (such as `https:` or
+    /// `mailto:`) are left as they are.
+    /// Protocol-relative URLs (`//example.com`) get the protocol of the base,
+    /// and fragment-only URLs (`#a`) resolve to the base itself, with that
+    /// fragment.
+    ///
+    /// Note that, like in browsers, the last path segment of the base is
+    /// dropped, unless it ends in a slash: `a` resolved against
+    /// `https://example.com/docs` is `https://example.com/a`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` leaves URLs alone by default:
+    /// assert_eq!(
+    ///     to_html("[a](b.html)"),
+    ///     "<p><a href=\"b.html\">a</a></p>"
+    /// );
+    ///
+    /// // Pass `base_url` to resolve them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](b.html)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               base_url: Some("https://example.com/docs/".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://example.com/docs/b.html\">a</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub base_url: Option<String>,
+
+    /// Function to change the URLs of links and images.
+    ///
+    /// The default is `None`, which leaves URLs as they are.
+    ///
+    /// The function is called for each link (including autolinks) and image
+    /// with a [`UrlContext`][crate::UrlContext], which has the destination
+    /// after definitions are resolved and character references are decoded,
+    /// but before [`base_url`][CompileOptions::base_url] is applied and the
+    /// URL is encoded.
+    /// Return `Some` with a new destination, or `None` to drop the `href`
+    /// or `src` attribute.
+    ///
+    /// What is returned is still resolved against `base_url`, and still
+    /// checked for dangerous protocols, unless `allow_dangerous_protocol` is
+    /// on.
+    /// So, to allow a different set of protocols, turn
+    /// `allow_dangerous_protocol` on and check them in this function.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, UrlContext};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Only allow `https:` and `mailto:`, and drop other URLs:
+    /// let options = Options {
+    ///     compile: CompileOptions {
+    ///       allow_dangerous_protocol: true,
+    ///       url_transform: Some(Rc::new(|url: UrlContext| {
+    ///           if url.destination.starts_with("https:") || url.destination.starts_with("mailto:") {
+    ///               Some(url.destination)
+    ///           } else {
+    ///               None
+    ///           }
+    ///       })),
+    ///       ..CompileOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("[a](https://b.c) [d](http://e.f)", &options)?,
+    ///     "<p><a href=\"https://b.c\">a</a> <a>d</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub url_transform: Option<Rc<UrlTransform>>,
+
+    /// Whether to lowercase the protocol and host of URLs.
+    ///
+    /// The default is `false`, which leaves URLs as they are.
+    /// Pass `true` to lowercase the protocol (`HTTP:`) and host
+    /// (`//Example.COM`) of links (including autolinks) and images, as those
+    /// are case-insensitive.
+    /// The user info, path, query, and fragment are case-sensitive, and left
+    /// as they are.
+    ///
+    /// This happens after [`url_transform`][CompileOptions::url_transform]
+    /// and [`base_url`][CompileOptions::base_url] are applied.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` leaves the case of URLs alone by default:
+    /// assert_eq!(
+    ///     to_html("<HTTP://Example.COM/Path>"),
+    ///     "<p><a href=\"HTTP://Example.COM/Path\">HTTP://Example.COM/Path</a></p>"
+    /// );
+    ///
+    /// // Pass `normalize_url_case` to lowercase protocols and hosts:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<HTTP://Example.COM/Path>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               normalize_url_case: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"http://example.com/Path\">HTTP://Example.COM/Path</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub normalize_url_case: bool,
+
+    /// Value of a `rel` attribute to add to links to other sites.
+    ///
+    /// The default is `None`, which adds no `rel` attribute.
+    /// Pass something like `Some("nofollow noopener".into())`, which is
+    /// typical for user content, to add it to links (including autolinks)
+    /// that are external.
+    ///
+    /// A link is external if its URL has a host (such as
+    /// `https://example.com/a` or `//example.com/a`) that differs from the
+    /// host of [`base_url`][CompileOptions::base_url].
+    /// Without `base_url`, all links with a host are external.
+    /// Links without a host (such as `a`, `/a`, `#a`, or `mailto:a@b.c`) are
+    /// not.
+    /// Pass [`is_external_link`][CompileOptions::is_external_link] to decide
+    /// yourself.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` adds no `rel` to links by default:
+    /// assert_eq!(
+    ///     to_html("[a](https://b.c) [d](/e)"),
+    ///     "<p><a href=\"https://b.c\">a</a> <a href=\"/e\">d</a></p>"
+    /// );
+    ///
+    /// // Pass `external_link_rel` to add one to external links:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](https://b.c) [d](/e)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               external_link_rel: Some("nofollow noopener".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://b.c\" rel=\"nofollow noopener\">a</a> <a href=\"/e\">d</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub external_link_rel: Option<String>,
+
+    /// Value of a `target` attribute to add to links to other sites.
+    ///
+    /// The default is `None`, which adds no `target` attribute.
+    /// Pass something like `Some("_blank".into())` to add it to links
+    /// (including autolinks) that are external.
+    /// See [`external_link_rel`][CompileOptions::external_link_rel] for
+    /// which links are external.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Pass `external_link_target` to open external links in a new tab:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[a](https://b.c) [d](https://e.f/g)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               base_url: Some("https://e.f/".into()),
+    ///               external_link_rel: Some("noopener".into()),
+    ///               external_link_target: Some("_blank".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://b.c\" rel=\"noopener\" target=\"_blank\">a</a> <a href=\"https://e.f/g\">d</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub external_link_target: Option<String>,
+
+    /// Function to decide whether a link goes to another site.
+    ///
+    /// The default is `None`, which compares hosts (see
+    /// [`external_link_rel`][CompileOptions::external_link_rel]).
+    /// Only used when `external_link_rel` or
+    /// [`external_link_target`][CompileOptions::external_link_target] is
+    /// given.
+    ///
+    /// Pass a function to decide yourself.
+    /// It gets the final URL of a link (after
+    /// [`url_transform`][CompileOptions::url_transform],
+    /// [`base_url`][CompileOptions::base_url], and
+    /// [`normalize_url_case`][CompileOptions::normalize_url_case]), and
+    /// returns whether it is external.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Treat subdomains as internal:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<https://docs.example.com> <https://example.org>",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               external_link_rel: Some("nofollow".into()),
+    ///               is_external_link: Some(Rc::new(|url: &str| {
+    ///                   !url.starts_with("https://example.com") && !url.contains(".example.com")
+    ///               })),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"https://docs.example.com\">https://docs.example.com</a> <a href=\"https://example.org\" rel=\"nofollow\">https://example.org</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub is_external_link: Option<Rc<IsExternalLink>>,
+
+    /// Whether to compile autolinks in links as text.
+    ///
+    /// The default is `false`.
+    /// `CommonMark` does not allow links in links, but it does allow
+    /// [autolinks][crate::construct::autolink] (`<https://a.b>`) in the text
+    /// of links, which results in nested `<a>` elements: invalid HTML.
+    /// GFM autolink literals (`https://a.b`) in links are always text.
+    ///
+    /// Pass `true` to compile autolinks in links as text too, so that links
+    /// are never nested.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` nests autolinks in links by default, like `CommonMark`:
+    /// assert_eq!(
+    ///     to_html("[<https://a.b>](c)"),
+    ///     "<p><a href=\"c\"><a href=\"https://a.b\">https://a.b</a></a></p>"
+    /// );
+    ///
+    /// // Pass `flatten_autolinks` to compile them as text:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[<https://a.b>](c)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               flatten_autolinks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><a href=\"c\">https://a.b</a></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub flatten_autolinks: bool,
+
+    /// How browsers should load images, as a `loading` attribute.
+    ///
+    /// The default is `None`, which adds no attribute: browsers then load
+    /// images right away.
+    /// Pass [`ImageLoading::Lazy`][] to load images when they are almost
+    /// visible, which is useful for long documents with many images.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, ImageLoading, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` adds no `loading` attribute by default:
+    /// assert_eq!(
+    ///     to_html("![a](b)"),
+    ///     "<p><img src=\"b\" alt=\"a\" /></p>"
+    /// );
+    ///
+    /// // Pass `image_loading` to add one:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "![a](b)",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               image_loading: Some(ImageLoading::Lazy),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><img src=\"b\" alt=\"a\" loading=\"lazy\" /></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub image_loading: Option<ImageLoading>,
+
+    /// Whether to turn a `=WxH` suffix in titles of images into `width` and
+    /// `height` attributes.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to support `![a](b "c =100x50")`.
+    /// The suffix must be at the end of the title, after whitespace (or be
+    /// the whole title), and consist of `=`, optional digits for the width,
+    /// `x`, and optional digits for the height.
+    /// At least one of the width or the height must be given: `=100x` sets
+    /// only the width, `=x50` only the height.
+    /// Titles without a valid suffix, such as `c =100` or `c=100x50`, are
+    /// used as they are.
+    ///
+    /// This works on titles of images in resources (`![a](b "c =1x2")`) and
+    /// in definitions used by image references.
+    /// Titles of links are never changed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps the suffix in the title by default:
+    /// assert_eq!(
+    ///     to_html("![a](b \"c =100x50\")"),
+    ///     "<p><img src=\"b\" alt=\"a\" title=\"c =100x50\" /></p>"
+    /// );
+    ///
+    /// // Pass `image_dimensions` to turn it into attributes:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "![a](b \"c =100x50\")",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               image_dimensions: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><img src=\"b\" alt=\"a\" title=\"c\" width=\"100\" height=\"50\" /></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub image_dimensions: bool,
+
+    /// Whether to turn paragraphs that contain only an image into figures,
+    /// and what to use as their caption.
+    ///
+    /// The default is `None`, which keeps such paragraphs as paragraphs.
+    /// Pass [`FigureCaption::Alt`][] or [`FigureCaption::Title`][] to compile
+    /// them as `<figure>` elements, with a `<figcaption>` containing the
+    /// alternative text or title of the image.
+    /// When that is missing or empty, no `<figcaption>` is added.
+    ///
+    /// Only paragraphs with exactly one image, and nothing else except for
+    /// trailing whitespace and attributes, are turned into figures.
+    /// Images next to text, or wrapped in links, are not affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, FigureCaption, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps images in paragraphs by default:
+    /// assert_eq!(
+    ///     to_html("![a](b \"c\")"),
+    ///     "<p><img src=\"b\" alt=\"a\" title=\"c\" /></p>"
+    /// );
+    ///
+    /// // Pass `image_figure` to turn them into figures:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "![a](b \"c\")",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               image_figure: Some(FigureCaption::Alt),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<figure><img src=\"b\" alt=\"a\" title=\"c\" /><figcaption>a</figcaption></figure>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub image_figure: Option<FigureCaption>,
+
+    // To do: `doc_markdown` is broken.
+    #[allow(clippy::doc_markdown)]
+    /// Default line ending to use when compiling to HTML, for line endings not
+    /// in `value`.
+    ///
+    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
+    /// the markdown document over to the compiled HTML.
+    /// In some cases, such as `> a`, CommonMark requires that extra line
+    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
+    ///
+    /// To create that line ending, the document is checked for the first line
+    /// ending that is used.
+    /// If there is no line ending, `default_line_ending` is used.
+    /// If that isn’t configured, `\n` is used.
+    ///
+    /// So, the line ending used for such extra line endings is, in order:
+    /// `line_ending` (if passed), the first line ending in the document (if
+    /// any), `default_line_ending` (if passed), and `\n`.
+    /// That means that documents that use `\r\n` compile to HTML that uses
+    /// `\r\n` too.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `\n` by default:
+    /// assert_eq!(
+    ///     to_html("> a"),
+    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Define `default_line_ending` to configure the default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "> a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               default_line_ending: LineEnding::CarriageReturnLineFeed,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<blockquote>\r\n<p>a</p>\r\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub default_line_ending: LineEnding,
+
+    /// Line ending to use for all line endings in the output.
+    ///
+    /// The default is `None`, which keeps the line endings that are used in
+    /// the document, and uses the first one (or `default_line_ending`) for
+    /// line endings that are not in the document, such as between elements.
+    ///
+    /// Pass a line ending to get only that line ending in the output,
+    /// regardless of which (mix of) line endings the document uses.
+    /// This includes line endings after hard breaks, in code, and in HTML.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps line endings by default:
+    /// assert_eq!(
+    ///     to_html("a\r\nb\rc\n\nd"),
+    ///     "<p>a\r\nb\rc</p>\n<p>d</p>"
+    /// );
+    ///
+    /// // Pass `line_ending` to use the same line ending everywhere:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\r\nb\rc\n\nd",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               line_ending: Some(LineEnding::LineFeed),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a\nb\nc</p>\n<p>d</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub line_ending: Option<LineEnding>,
+
+    /// Whether to normalize the whitespace between blocks.
+    ///
+    /// The default is `false`, which keeps the line endings between blocks
+    /// that are in the document, and only ends the output with a line ending
+    /// if the document ends with one.
+    ///
+    /// Pass `true` to always put exactly one line ending between blocks and
+    /// at the end of the output, which makes the output stable for snapshot
+    /// tests.
+    /// These line endings are `line_ending` if given, or the first line
+    /// ending in the document, or `default_line_ending`.
+    /// Whitespace inside blocks, such as in code, in HTML, and in
+    /// paragraphs, is not changed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows the document by default:
+    /// assert_eq!(
+    ///     to_html("a\n\nb\r\n\r\nc"),
+    ///     "<p>a</p>\n<p>b</p>\r\n<p>c</p>"
+    /// );
+    ///
+    /// // Pass `normalize_block_whitespace` to normalize:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\n\nb\r\n\r\nc",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               normalize_block_whitespace: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a</p>\n<p>b</p>\n<p>c</p>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub normalize_block_whitespace: bool,
+
+    /// Function to turn directives into HTML with.
+    ///
+    /// The function is called with each directive (container, leaf, text).
+    /// When it returns `Some`, that HTML is used for the directive.
+    /// When it returns `None`, or when no function is passed, the label (leaf,
+    /// text) or content (container) of the directive is rendered on its own,
+    /// where the label of a leaf is in a paragraph.
+    ///
+    /// > 👉 **Note**: the returned HTML is not sanitized.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Directive, Options, ParseOptions};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let mut options = Options {
+    ///     parse: ParseOptions {
+    ///         constructs: Constructs {
+    ///             directive_text: true,
+    ///             ..Constructs::default()
+    ///         },
+    ///         ..ParseOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// // Without a function, directives render their label:
+    /// assert_eq!(
+    ///     to_html_with_options("Hi, :abbr[Venus]{title=\"Planet\"}!", &options)?,
+    ///     "<p>Hi, Venus!</p>"
+    /// );
+    ///
+    /// // Pass `directive_handler` to turn directives into HTML:
+    /// options.compile.directive_handler = Some(Rc::new(|directive: &Directive| {
+    ///     if directive.name == "abbr" {
+    ///         Some(format!(
+    ///             "<abbr>{}</abbr>",
+    ///             directive.label.as_deref().unwrap_or_default()
+    ///         ))
+    ///     } else {
+    ///         None
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("Hi, :abbr[Venus]{title=\"Planet\"}!", &options)?,
+    ///     "<p>Hi, <abbr>Venus</abbr>!</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub directive_handler: Option<Rc<DirectiveHandler>>,
+
+    /// Function to turn custom flow constructs into HTML with.
+    ///
+    /// The function is called with the name and the value of each custom
+    /// flow construct (see `flow_extensions` in [`ParseOptions`][]).
+    /// The value is the lines of the construct, without container prefixes
+    /// (such as `> ` of block quotes), joined by their line endings.
+    /// When it returns `Some`, that HTML is used for the construct.
+    /// When it returns `None`, or when no function is passed, the value is
+    /// rendered as code.
+    ///
+    /// > 👉 **Note**: the returned HTML is not sanitized.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, FlowExtension, FlowExtensionPriority, Options, ParseOptions};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let mut options = Options {
+    ///     parse: ParseOptions {
+    ///         flow_extensions: vec![FlowExtension {
+    ///             name: "note".into(),
+    ///             priority: FlowExtensionPriority::First,
+    ///             interrupt: false,
+    ///             start: Box::new(|line| line == "%%%"),
+    ///             close: Some(Box::new(|_opening, line| line == "%%%")),
+    ///         }],
+    ///         ..ParseOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// // Without a function, custom flow constructs are rendered as code:
+    /// assert_eq!(
+    ///     to_html_with_options("%%%\nVenus\n%%%", &options)?,
+    ///     "<pre><code>%%%\nVenus\n%%%\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `flow_extension_handler` to turn them into HTML:
+    /// options.compile.flow_extension_handler = Some(Rc::new(|name, value| {
+    ///     if name == "note" {
+    ///         let lines: Vec<&str> = value.lines().collect();
+    ///         Some(format!("<aside>{}</aside>", lines[1..lines.len() - 1].join("\n")))
+    ///     } else {
+    ///         None
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("%%%\nVenus\n%%%", &options)?,
+    ///     "<aside>Venus</aside>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub flow_extension_handler: Option<Rc<FlowExtensionHandler>>,
+
+    /// Function to turn custom text constructs into HTML with.
+    ///
+    /// The function is called with the name and the value of each custom
+    /// text construct (see `text_extensions` in [`ParseOptions`][]).
+    /// When it returns `Some`, that HTML is used for the construct.
+    /// When it returns `None`, or when no function is passed, the value is
+    /// rendered as text.
+    ///
+    /// > 👉 **Note**: the returned HTML is not sanitized.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options, ParseOptions, TextExtension};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// let mut options = Options {
+    ///     parse: ParseOptions {
+    ///         text_extensions: vec![TextExtension {
+    ///             name: "mention".into(),
+    ///             marker: b'@',
+    ///             start: Box::new(|rest| {
+    ///                 let size = rest[1..]
+    ///                     .find(|d: char| !d.is_ascii_alphanumeric())
+    ///                     .unwrap_or(rest.len() - 1);
+    ///                 if size > 0 { Some(size + 1) } else { None }
+    ///             }),
+    ///         }],
+    ///         ..ParseOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// // Without a function, custom text constructs are rendered as text:
+    /// assert_eq!(
+    ///     to_html_with_options("Hi, @venus!", &options)?,
+    ///     "<p>Hi, @venus!</p>"
+    /// );
+    ///
+    /// // Pass `text_extension_handler` to turn them into HTML:
+    /// options.compile.text_extension_handler = Some(Rc::new(|name, value| {
+    ///     if name == "mention" {
+    ///         Some(format!("<a href=\"/{}\">{}</a>", &value[1..], value))
+    ///     } else {
+    ///         None
+    ///     }
+    /// }));
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("Hi, @venus!", &options)?,
+    ///     "<p>Hi, <a href=\"/venus\">@venus</a>!</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub text_extension_handler: Option<Rc<TextExtensionHandler>>,
+
+    /// Textual label to use for the footnotes section.
+    ///
+    /// The default value is `"Footnotes"`.
+    /// Change it when the markdown is not in English.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"Footnotes"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label: Option<String>,
+
+    /// HTML tag name to use for the footnote label element.
+    ///
+    /// The default value is `"h2"`.
+    /// Change it to match your document structure.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass different attributes with the `gfm_footnote_label_attributes`
+    /// option.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"h2"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_tag_name: Some("h1".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_tag_name: Option<String>,
+
+    /// Attributes to use on the footnote label.
+    ///
+    /// The default value is `"class=\"sr-only\""`.
+    /// Change it to show the label and add other attributes.
+    ///
+    /// This label is typically hidden visually (assuming a `sr-only` CSS class
+    /// is defined that does that), and thus affects screen readers only.
+    /// If you do have such a class, but want to show this section to everyone,
+    /// pass an empty string.
+    /// You can also add different attributes.
+    ///
+    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
+    /// > calls use it with `aria-describedby` to provide an accessible label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"class=\"sr-only\""` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_label_attributes: Option<String>,
+
+    /// Textual label to describe the backreference back to footnote calls.
+    ///
+    /// The default value is `"Back to content"`.
+    /// Change it when the markdown is not in English.
+    ///
+    /// This label is used in the `aria-label` attribute on each backreference
+    /// (the `↩` links).
+    /// It affects users of assistive technology.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"Back to content"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_back_label: Some("Arrière".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_back_label: Option<String>,
+
+    /// Prefix to use before the `id` attribute on footnotes to prevent them
+    /// from *clobbering*.
+    ///
+    /// The default is `"user-content-"`.
+    /// Pass `Some("".into())` for trusted markdown and when you are careful
+    /// with polyfilling.
+    /// You could pass a different prefix.
+    ///
+    /// DOM clobbering is this:
+    ///
+    /// ```html
+    /// <p id="x"></p>
+    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
+    /// ```
+    ///
+    /// The above example shows that elements are made available by browsers,
+    /// by their ID, on the `window` object.
+    /// This is a security risk because you might be expecting some other
+    /// variable at that place.
+    /// It can also break polyfills.
+    /// Using a prefix solves these problems.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"user-content-"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "[^a]\n\n[^a]: b",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_clobber_prefix: Some("".into()),
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_clobber_prefix: Option<String>,
+
+    /// Where to place GFM footnotes.
+    ///
+    /// The default is a section at the end of the document.
+    /// With [`FootnoteStyle::Inline`][], each footnote is placed in an
+    /// `<aside>` right after the block (such as a paragraph or heading)
+    /// where it is first referenced, which is useful for sidenotes in print
+    /// layouts or for popovers.
+    /// Footnotes first referenced in other footnotes are placed after the
+    /// next block instead, or at the end of the document.
+    ///
+    /// Footnotes are numbered the same in both styles.
+    /// A footnote referenced several times is placed once, with a
+    /// backreference to each reference.
+    /// References describe the footnote itself (with `aria-describedby`),
+    /// as there is no footnote label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, FootnoteStyle, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // A section at the end is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a[^b]\n\nc\n\n[^b]: d",
+    ///         &Options::gfm()
+    ///     )?,
+    ///     "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<p>c</p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>d <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    /// );
+    ///
+    /// // Pass `gfm_footnote_style` to place them inline:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a[^b]\n\nc\n\n[^b]: d",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               gfm_footnote_style: FootnoteStyle::Inline,
+    ///               ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup></p>\n<aside id=\"user-content-fn-b\" class=\"footnote\" data-footnote-number=\"1\">\n<p>d <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</aside>\n<p>c</p>\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_footnote_style: FootnoteStyle,
+
+    /// Whether or not GFM task list html `<input>` items are enabled.
+    ///
+    /// This determines whether or not the user of the browser is able
+    /// to click and toggle generated checkbox items. The default is false.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // With `gfm_task_list_item_checkable`, generated `<input type="checkbox" />`
+    /// // tags do not contain the attribute `disabled=""` and are thus toggleable by
+    /// // browser users.
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "* [x] y.",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///                 gfm_task_list_item_checkable: true,
+    ///                 ..CompileOptions::gfm()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_task_list_item_checkable: bool,
+
+    /// Whether to support the GFM tagfilter.
+    ///
+    /// This option does nothing if `allow_dangerous_html` is not turned on.
+    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
+    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    ///
+    /// The tagfilter is kinda weird and kinda useless.
+    /// The tag filter is a naïve attempt at XSS protection.
+    /// You should use a proper HTML sanitizing algorithm instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<iframe>"
+    /// );
+    ///
+    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "<iframe>",
+    ///         &Options {
+    ///             parse: ParseOptions::gfm(),
+    ///             compile: CompileOptions {
+    ///               allow_dangerous_html: true,
+    ///               gfm_tagfilter: true,
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "&lt;iframe>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
+    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
+    pub gfm_tagfilter: bool,
+
+    /// Whether to add `id` attributes to headings.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to generate an `id` for each heading from its text, as
+    /// done on GitHub.
+    /// The plain text of the heading is used (so the text of code, emphasis,
+    /// and links, but not of images or HTML), lowercased, with everything
+    /// other than letters, numbers, `-`, `_`, and spaces removed, and spaces
+    /// replaced by `-`.
+    /// When a slug is used more than once, `-1`, `-2`, etc are appended to
+    /// the later ones.
+    ///
+    /// > 👉 **Note**: the ids are not prefixed (unlike footnotes, see
+    /// > `gfm_footnote_clobber_prefix`), so they could clobber other things on
+    /// > the page.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add ids to headings by default:
+    /// assert_eq!(
+    ///     to_html_with_options("# Hello, *world*!", &Options::default())?,
+    ///     "<h1>Hello, <em>world</em>!</h1>"
+    /// );
+    ///
+    /// // Pass `heading_ids: true` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# Hello, *world*!\n\n## Hello, world",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               heading_ids: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 id=\"hello-world\">Hello, <em>world</em>!</h1>\n<h2 id=\"hello-world-1\">Hello, world</h2>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## References
+    ///
+    /// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+    pub heading_ids: bool,
+
+    /// Whether to turn straight quotes, dashes, and dots into their
+    /// typographic forms.
+    ///
+    /// The default is `false`, as `CommonMark` does not do this.
+    ///
+    /// Pass `true` to turn `"` and `'` into curly quotes (`“”` and `‘’`,
+    /// or an apostrophe `’` after a letter or number), `--` into an en dash
+    /// (`–`), `---` into an em dash (`—`), and `...` into an ellipsis (`…`).
+    /// This only affects text: code, HTML, autolinks, character escapes,
+    /// and character references are left as they are.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` leaves punctuation alone by default:
+    /// assert_eq!(
+    ///     to_html("\"Don't\" -- `'a'`..."),
+    ///     "<p>&quot;Don't&quot; -- <code>'a'</code>...</p>"
+    /// );
+    ///
+    /// // Turn `smart_punctuation` on to get typographic punctuation:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "\"Don't\" -- `'a'`...",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               smart_punctuation: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>“Don’t” – <code>'a'</code>…</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub smart_punctuation: bool,
+
+    /// What to do with invisible characters (such as zero width spaces) in
+    /// text.
+    ///
+    /// The default is `Invisibles::Keep`.
+    /// A byte order mark at the start of the document is always dropped, but
+    /// invisible characters elsewhere (typically from pasted content) are
+    /// kept, which can cause differences in the HTML that you cannot see.
+    ///
+    /// Pass `Invisibles::Strip` to remove them, or `Invisibles::Replace` to
+    /// turn them into character references (such as `&#x200B;`).
+    /// This only affects text: code, HTML, and character references are
+    /// left as they are.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Invisibles, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps invisible characters by default:
+    /// assert_eq!(
+    ///     to_html("a\u{200B}b"),
+    ///     "<p>a\u{200B}b</p>"
+    /// );
+    ///
+    /// // Pass `invisibles: Invisibles::Strip` to remove them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\u{200B}b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               invisibles: Invisibles::Strip,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>ab</p>"
+    /// );
+    ///
+    /// // Or `Invisibles::Replace` to make them visible in the HTML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\u{200B}b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               invisibles: Invisibles::Replace,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a&#x200B;b</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub invisibles: Invisibles,
+
+    /// Whether to keep character references in text as they are written.
+    ///
+    /// The default is `false`, which decodes character references (such as
+    /// `&copy;`) to the characters they represent, as `CommonMark` describes.
+    ///
+    /// Pass `true` to keep them as they are written instead, which is useful
+    /// if something else handles them afterwards.
+    /// This applies to named, decimal (`&#169;`), and hexadecimal (`&#xA9;`)
+    /// character references alike, and keeps their casing.
+    /// Only valid character references are recognized: other ampersands are
+    /// still encoded (`&amp;`).
+    /// Character references in link destinations and titles, and in heading
+    /// IDs, are still decoded.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` decodes character references by default:
+    /// assert_eq!(
+    ///     to_html("&copy; &#169;"),
+    ///     "<p>© ©</p>"
+    /// );
+    ///
+    /// // Pass `preserve_character_references` to keep them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "&copy; &#169;",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               preserve_character_references: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>&copy; &#169;</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub preserve_character_references: bool,
+
+    /// Whether to compile all lists as loose or tight.
+    ///
+    /// The default is `None`, which infers whether each list is loose or
+    /// tight from blank lines, as described in
+    /// [list item][crate::construct::list_item] (and exposed in
+    /// [`list_info()`][crate::list_info()]).
+    /// The items of loose lists are wrapped in paragraphs (`<li><p>a</p></li>`)
+    /// and those of tight lists are not (`<li>a</li>`).
+    ///
+    /// Pass `Some(true)` to compile all lists as loose, or `Some(false)` to
+    /// compile all lists as tight.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` infers whether lists are loose by default:
+    /// assert_eq!(
+    ///     to_html("* a\n\n* b"),
+    ///     "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ul>"
+    /// );
+    ///
+    /// // Pass `list_loose` to compile all lists as tight:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "* a\n\n* b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               list_loose: Some(false),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<ul>\n<li>a</li>\n<li>b</li>\n</ul>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub list_loose: Option<bool>,
+
+    /// Whether to drop empty paragraphs and list items.
+    ///
+    /// The default is `false`, which, like `CommonMark`, keeps them: `- ` is
+    /// compiled as an empty list item (`<li></li>`).
+    ///
+    /// Pass `true` to drop paragraphs and list items that are empty (or only
+    /// whitespace) when compiled, which is useful for sloppily written
+    /// content.
+    /// Lists of which all items are dropped are dropped too.
+    /// Paragraphs can end up empty when what is in them is not compiled
+    /// (such as invisible characters with
+    /// [`invisibles`][CompileOptions::invisibles]).
+    /// The numbers of ordered list items are not changed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps empty list items by default:
+    /// assert_eq!(
+    ///     to_html("- a\n-\n- b"),
+    ///     "<ul>\n<li>a</li>\n<li></li>\n<li>b</li>\n</ul>"
+    /// );
+    ///
+    /// // Pass `drop_empty_blocks` to drop them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "- a\n-\n- b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               drop_empty_blocks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<ul>\n<li>a</li>\n<li>b</li>\n</ul>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub drop_empty_blocks: bool,
+
+    /// Prefix to use before the language of fenced code, in the `class` of
+    /// `<code>`.
+    ///
+    /// The default is `None`, which uses `"language-"`, as `CommonMark` does.
+    /// Some syntax highlighters expect a different prefix, such as `"lang-"`,
+    /// or none at all (`""`).
+    ///
+    /// This does not affect math (flow), which always uses
+    /// `language-math math-display`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `language-` by default:
+    /// assert_eq!(
+    ///     to_html("```rust\na\n```"),
+    ///     "<pre><code class=\"language-rust\">a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_language_prefix` to use a different prefix:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "```rust\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_language_prefix: Some("lang-".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code class=\"lang-rust\">a\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_language_prefix: Option<String>,
+
+    /// Function to choose the language of fenced code.
+    ///
+    /// The default is `None`, which uses the first word of the info string
+    /// as the language, as `CommonMark` does.
+    ///
+    /// The function is called for each fenced code with an info string,
+    /// with a [`CodeInfo`][crate::CodeInfo], which has the first word of the
+    /// info string and the rest of it (the meta).
+    /// Return `Some` with a language, which is prefixed with
+    /// [`code_language_prefix`][CompileOptions::code_language_prefix], or
+    /// `None` to drop the `class` attribute.
+    /// What is returned is encoded.
+    ///
+    /// Fenced code without info string is always compiled to a bare `<code>`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CodeInfo, CompileOptions, Options};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Use the whole info string:
+    /// let options = Options {
+    ///     compile: CompileOptions {
+    ///       code_language_transform: Some(Rc::new(|info: CodeInfo| {
+    ///           Some(if let Some(meta) = info.meta {
+    ///               format!("{} {}", info.language, meta)
+    ///           } else {
+    ///               info.language
+    ///           })
+    ///       })),
+    ///       ..CompileOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("```rust no_run\na\n```", &options)?,
+    ///     "<pre><code class=\"language-rust no_run\">a\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_language_transform: Option<Rc<CodeLanguageTransform>>,
+
+    /// Function to highlight fenced code.
+    ///
+    /// The default is `None`, which encodes the code.
+    ///
+    /// The function is called for each fenced code with a
+    /// [`HighlightContext`][crate::HighlightContext], which has the language,
+    /// meta, and the code itself, which is not encoded and has its line
+    /// endings.
+    /// Return `Some` with [`Highlight::Body`][crate::Highlight::Body] to
+    /// replace what is between `<pre><code>` and `</code></pre>`,
+    /// [`Highlight::Whole`][crate::Highlight::Whole] to replace those
+    /// elements too, or `None` to compile the code as normal.
+    ///
+    /// What is returned is HTML, which is not encoded: make sure it is safe.
+    ///
+    /// This does not affect code (indented) or math (flow).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Highlight, HighlightContext, Options};
+    /// use std::rc::Rc;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Wrap each line of Rust in a `<span>`:
+    /// let options = Options {
+    ///     compile: CompileOptions {
+    ///       code_highlight: Some(Rc::new(|code: HighlightContext| {
+    ///           if code.language.as_deref() == Some("rust") {
+    ///               Some(Highlight::Body(
+    ///                   code.value
+    ///                       .lines()
+    ///                       .map(|line| format!("<span class=\"line\">{}</span>\n", line))
+    ///                       .collect(),
+    ///               ))
+    ///           } else {
+    ///               None
+    ///           }
+    ///       })),
+    ///       ..CompileOptions::default()
+    ///     },
+    ///     ..Options::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options("```rust\nfn\n```", &options)?,
+    ///     "<pre><code class=\"language-rust\"><span class=\"line\">fn</span>\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_highlight: Option<Rc<Highlighter>>,
+
+    /// Whether to drop the final line ending of code.
+    ///
+    /// The default is `false`, which keeps it, as `CommonMark` does: the
+    /// content of `<code>` in code (fenced), code (indented), and math
+    /// (flow), ends in a line ending if there is content.
+    ///
+    /// Pass `true` to drop that final line ending.
+    /// Only one line ending is dropped, so blank lines at the end of code
+    /// (fenced) are still kept (minus one line ending).
+    /// This also affects the value passed to
+    /// [`code_highlight`][CompileOptions::code_highlight].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps the final line ending by default:
+    /// assert_eq!(
+    ///     to_html("```\na\n```"),
+    ///     "<pre><code>a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `code_trim_final_line_ending` to drop it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "```\na\n```",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               code_trim_final_line_ending: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code>a</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_trim_final_line_ending: bool,
+
+    /// HTML tag name to use for the element that wraps math.
+    ///
+    /// The default value is `"code"`.
+    /// Math (text) is compiled to this element, and math (flow) to this
+    /// element in a `<pre>`.
+    /// Change it to match what your client side math renderer (such as
+    /// `KaTeX` or `MathJax`) looks for.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"code"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_tag_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               math_tag_name: Some("span".into()),
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<p><span class=\"language-math math-inline\">a</span></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_tag_name: Option<String>,
+
+    /// Class name to use for the element that wraps math.
+    ///
+    /// The default value is `"language-math"`.
+    /// A second class, `math-inline` for math (text) or `math-display` for
+    /// math (flow), is always added after it, so that the two can be told
+    /// apart.
+    /// Pass an empty string to only add that second class.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"language-math"` is used by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$$\na\n$$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_flow: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code class=\"language-math math-display\">a\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `math_class_name` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$$\na\n$$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_flow: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             compile: CompileOptions {
+    ///               math_class_name: Some("math".into()),
+    ///               ..CompileOptions::default()
+    ///             }
+    ///         }
+    ///     )?,
+    ///     "<pre><code class=\"math math-display\">a\n</code></pre>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_class_name: Option<String>,
+
+    /// Whether to add the positions of block elements in the markdown
+    /// document to them, as `data-sourcepos` attributes.
+    ///
+    /// The default is `false`, which does not add them.
+    ///
+    /// Pass `true` to add `data-sourcepos="startLine:startColumn-endLine:endColumn"`
+    /// to paragraphs, headings, thematic breaks, block quotes, lists, list
+    /// items, code (on `<pre>`), math (flow), the tables, rows, and cells
+    /// of GFM tables, and the lists, terms, and descriptions of definition
+    /// lists.
+    /// Lines and columns are 1-indexed, like in
+    /// [`Point`][crate::unist::Point], and the end is exclusive (it is the
+    /// place after the construct).
+    /// Inline elements (such as emphasis or links) do not get positions.
+    ///
+    /// This can be used to sync scrolling between a markdown editor and a
+    /// preview.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add positions by default:
+    /// assert_eq!(
+    ///     to_html("> a\n> *b*"),
+    ///     "<blockquote>\n<p>a\n<em>b</em></p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `source_positions` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "> a\n> *b*",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               source_positions: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<blockquote data-sourcepos=\"1:1-2:6\">\n<p data-sourcepos=\"1:3-2:6\">a\n<em>b</em></p>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub source_positions: bool,
+
+    /// HTML tag name of an element to wrap the whole output in.
+    ///
+    /// The default is `None`, which does not wrap the output.
+    ///
+    /// Pass a tag name (such as `"article"`) to wrap everything, including
+    /// the GFM footnote section, in that element.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not wrap the output by default:
+    /// assert_eq!(
+    ///     to_html("# a\n\nb"),
+    ///     "<h1>a</h1>\n<p>b</p>"
+    /// );
+    ///
+    /// // Pass `wrap` to wrap it:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# a\n\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               wrap: Some("article".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<article>\n<h1>a</h1>\n<p>b</p>\n</article>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub wrap: Option<String>,
+
+    /// HTML tag name to use for paragraphs.
+    ///
+    /// The default value is `"p"`.
+    ///
+    /// This is used for all paragraphs that are shown, such as those in
+    /// block quotes, loose list items, and GFM footnote definitions.
+    /// Paragraphs in tight list items are not shown, so they are not
+    /// wrapped either.
+    /// Pass `paragraph_attributes` to also add attributes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `"p"` is used by default:
+    /// assert_eq!(
+    ///     to_html("> a"),
+    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `paragraph_wrapper` to use something else:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "> a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               paragraph_wrapper: Some("div".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<blockquote>\n<div>a</div>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub paragraph_wrapper: Option<String>,
+
+    /// Attributes to use on paragraphs.
+    ///
+    /// The default is `None`, which does not add attributes.
+    ///
+    /// The value is added as-is, so it must be valid HTML attributes (such
+    /// as `class="prose-paragraph"`).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add attributes by default:
+    /// assert_eq!(
+    ///     to_html("a"),
+    ///     "<p>a</p>"
+    /// );
+    ///
+    /// // Pass `paragraph_attributes` to add them:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               paragraph_attributes: Some("class=\"prose-paragraph\"".into()),
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p class=\"prose-paragraph\">a</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub paragraph_attributes: Option<String>,
+
+    /// Classes to add to block elements, by the name of the event that
+    /// forms them.
+    ///
+    /// The default is an empty map, which does not add classes.
+    ///
+    /// Classes can be added to the elements that can get source positions
+    /// (see `source_positions`):
+    /// [`Paragraph`][Name::Paragraph],
+    /// [`HeadingAtx`][Name::HeadingAtx],
+    /// [`HeadingSetext`][Name::HeadingSetext],
+    /// [`ThematicBreak`][Name::ThematicBreak],
+    /// [`BlockQuote`][Name::BlockQuote],
+    /// [`ListOrdered`][Name::ListOrdered],
+    /// [`ListUnordered`][Name::ListUnordered],
+    /// [`ListItem`][Name::ListItem],
+    /// [`CodeIndented`][Name::CodeIndented] and
+    /// [`CodeFenced`][Name::CodeFenced] (on `<pre>`),
+    /// [`MathFlow`][Name::MathFlow],
+    /// [`GfmTable`][Name::GfmTable],
+    /// [`GfmTableRow`][Name::GfmTableRow],
+    /// [`GfmTableCell`][Name::GfmTableCell],
+    /// and [`DefinitionList`][Name::DefinitionList],
+    /// [`DefinitionListTerm`][Name::DefinitionListTerm], and
+    /// [`DefinitionListDescription`][Name::DefinitionListDescription].
+    /// Other names are ignored.
+    ///
+    /// Classes are encoded.
+    /// An empty string does not add a `class` attribute.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, EventName, Options};
+    /// use std::collections::BTreeMap;
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` does not add classes by default:
+    /// assert_eq!(
+    ///     to_html("# a\n> b"),
+    ///     "<h1>a</h1>\n<blockquote>\n<p>b</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `class_map` to add them:
+    /// let mut class_map = BTreeMap::new();
+    /// class_map.insert(EventName::HeadingAtx, "title".into());
+    /// class_map.insert(EventName::BlockQuote, "quote".into());
+    ///
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "# a\n> b",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               class_map,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h1 class=\"title\">a</h1>\n<blockquote class=\"quote\">\n<p>b</p>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub class_map: BTreeMap<Name, String>,
+
+    /// Whether to turn soft breaks (line endings in text) into hard breaks.
+    ///
+    /// The default is `false`: as required by `CommonMark`, line endings in
+    /// paragraphs are kept as line endings, which browsers show as spaces.
+    ///
+    /// Pass `true` to add `<br />` before each line ending in paragraphs and
+    /// heading (setext) text, which is how many chat apps and comment
+    /// sections show markdown.
+    /// Existing hard breaks (such as trailing spaces) are not doubled.
+    /// Line endings in code (text), HTML (text), and image alt text, and
+    /// line endings between or in other blocks (such as code or HTML (flow)),
+    /// are not affected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps soft breaks by default:
+    /// assert_eq!(
+    ///     to_html("a\nb"),
+    ///     "<p>a\nb</p>"
+    /// );
+    ///
+    /// // Pass `hard_line_breaks` to turn them into hard breaks:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\nb",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               hard_line_breaks: true,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub hard_line_breaks: bool,
+
+    /// Whether to self-close void elements, as in XHTML.
+    ///
+    /// The default is `true`: `<br />`, `<hr />`, `<img />`, and
+    /// `<input />` are generated, which works in both HTML and XHTML.
+    ///
+    /// Pass `false` to generate HTML void elements without a slash (`<br>`,
+    /// `<hr>`, `<img>`, `<input>`).
+    /// Void elements in embedded HTML are passed through as-is.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` self-closes void elements by default:
+    /// assert_eq!(
+    ///     to_html("a\\\nb\n\n***"),
+    ///     "<p>a<br />\nb</p>\n<hr />"
+    /// );
+    ///
+    /// // Turn `xhtml` off to generate HTML void elements:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a\\\nb\n\n***",
+    ///         &Options {
+    ///             compile: CompileOptions {
+    ///               xhtml: false,
+    ///               ..CompileOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a<br>\nb</p>\n<hr>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub xhtml: bool,
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+}
+
+impl fmt::Debug for CompileOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompileOptions")
+            .field("allow_dangerous_html", &self.allow_dangerous_html)
+            .field("html_filter", &self.html_filter)
+            .field("allow_dangerous_protocol", &self.allow_dangerous_protocol)
+            .field("base_url", &self.base_url)
+            .field(
+                "url_transform",
+                &self.url_transform.as_ref().map(|_d| "[Function]"),
+            )
+            .field("normalize_url_case", &self.normalize_url_case)
+            .field("external_link_rel", &self.external_link_rel)
+            .field("external_link_target", &self.external_link_target)
+            .field(
+                "is_external_link",
+                &self.is_external_link.as_ref().map(|_d| "[Function]"),
+            )
+            .field("flatten_autolinks", &self.flatten_autolinks)
+            .field("image_loading", &self.image_loading)
+            .field("image_dimensions", &self.image_dimensions)
+            .field("image_figure", &self.image_figure)
+            .field("default_line_ending", &self.default_line_ending)
+            .field("line_ending", &self.line_ending)
+            .field(
+                "normalize_block_whitespace",
+                &self.normalize_block_whitespace,
+            )
+            .field(
+                "directive_handler",
+                &self.directive_handler.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "flow_extension_handler",
+                &self.flow_extension_handler.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "text_extension_handler",
+                &self.text_extension_handler.as_ref().map(|_d| "[Function]"),
+            )
+            .field("gfm_footnote_label", &self.gfm_footnote_label)
+            .field(
+                "gfm_footnote_label_tag_name",
+                &self.gfm_footnote_label_tag_name,
+            )
+            .field(
+                "gfm_footnote_label_attributes",
+                &self.gfm_footnote_label_attributes,
+            )
+            .field("gfm_footnote_back_label", &self.gfm_footnote_back_label)
+            .field(
+                "gfm_footnote_clobber_prefix",
+                &self.gfm_footnote_clobber_prefix,
+            )
+            .field("gfm_footnote_style", &self.gfm_footnote_style)
+            .field(
+                "gfm_task_list_item_checkable",
+                &self.gfm_task_list_item_checkable,
+            )
+            .field("gfm_tagfilter", &self.gfm_tagfilter)
+            .field("heading_ids", &self.heading_ids)
+            .field("smart_punctuation", &self.smart_punctuation)
+            .field("invisibles", &self.invisibles)
+            .field(
+                "preserve_character_references",
+                &self.preserve_character_references,
+            )
+            .field("list_loose", &self.list_loose)
+            .field("drop_empty_blocks", &self.drop_empty_blocks)
+            .field("code_language_prefix", &self.code_language_prefix)
+            .field(
+                "code_language_transform",
+                &self.code_language_transform.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "code_highlight",
+                &self.code_highlight.as_ref().map(|_d| "[Function]"),
+            )
+            .field(
+                "code_trim_final_line_ending",
+                &self.code_trim_final_line_ending,
+            )
+            .field("math_tag_name", &self.math_tag_name)
+            .field("math_class_name", &self.math_class_name)
+            .field("source_positions", &self.source_positions)
+            .field("wrap", &self.wrap)
+            .field("paragraph_wrapper", &self.paragraph_wrapper)
+            .field("paragraph_attributes", &self.paragraph_attributes)
+            .field("class_map", &self.class_map)
+            .field("hard_line_breaks", &self.hard_line_breaks)
+            .field("xhtml", &self.xhtml)
+            .finish()
+    }
+}
+
+impl Default for CompileOptions {
+    /// Safe `CommonMark` defaults.
+    fn default() -> Self {
+        Self {
+            allow_dangerous_html: false,
+            html_filter: HtmlFilter::default(),
+            allow_dangerous_protocol: false,
+            base_url: None,
+            url_transform: None,
+            normalize_url_case: false,
+            external_link_rel: None,
+            external_link_target: None,
+            is_external_link: None,
+            flatten_autolinks: false,
+            image_loading: None,
+            image_dimensions: false,
+            image_figure: None,
+            default_line_ending: LineEnding::default(),
+            line_ending: None,
+            normalize_block_whitespace: false,
+            directive_handler: None,
+            flow_extension_handler: None,
+            text_extension_handler: None,
+            gfm_footnote_label: None,
+            gfm_footnote_label_tag_name: None,
+            gfm_footnote_label_attributes: None,
+            gfm_footnote_back_label: None,
+            gfm_footnote_clobber_prefix: None,
+            gfm_footnote_style: FootnoteStyle::default(),
+            gfm_task_list_item_checkable: false,
+            gfm_tagfilter: false,
+            heading_ids: false,
+            smart_punctuation: false,
+            invisibles: Invisibles::default(),
+            preserve_character_references: false,
+            list_loose: None,
+            drop_empty_blocks: false,
+            code_language_prefix: None,
+            code_language_transform: None,
+            code_highlight: None,
+            code_trim_final_line_ending: false,
+            math_tag_name: None,
+            math_class_name: None,
+            source_positions: false,
+            wrap: None,
+            paragraph_wrapper: None,
+            paragraph_attributes: None,
+            class_map: BTreeMap::new(),
+            hard_line_breaks: false,
+            xhtml: true,
+        }
+    }
+}
+
+impl CompileOptions {
+    /// GFM.
+    ///
+    /// GFM stands for **GitHub flavored markdown**.
+    /// On the compilation side, GFM turns on the GFM tag filter.
+    /// The tagfilter is useless, but it’s included here for consistency, and
+    /// this method exists for parity to parse options.
+    ///
+    /// For more information, see the GFM specification:
+    /// <https://github.github.com/gfm/>.
+    pub fn gfm() -> Self {
+        Self {
+            gfm_tagfilter: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Configuration that describes how to parse from markdown.
+///
+/// You can use this:
+///
+/// *   To control what markdown constructs are turned on and off
+/// *   To control some of those constructs
+/// *   To add support for certain programming languages when parsing MDX
+///
+/// In most cases, you will want to use the default trait or `gfm` method.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::ParseOptions;
+/// # fn main() {
+///
+/// // Use the default trait to parse markdown according to `CommonMark`:
+/// let commonmark = ParseOptions::default();
+///
+/// // Use the `gfm` method to parse markdown according to GFM:
+/// let gfm = ParseOptions::gfm();
+/// # }
+/// ```
+#[allow(clippy::struct_excessive_bools)]
+pub struct ParseOptions {
+    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
+    /// Which constructs to enable and disable.
+    ///
+    /// The default is to follow `CommonMark`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("    indented code?"),
+    ///     "<pre><code>indented code?\n</code></pre>"
+    /// );
+    ///
+    /// // Pass `constructs` to choose what to enable and disable:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "    indented code?",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 code_indented: false,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>indented code?</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub constructs: Constructs,
+
+    /// Whether to support GFM strikethrough with a single tilde
+    ///
+    /// This option does nothing if `gfm_strikethrough` is not turned on in
+    /// `constructs`, or if `subscript` is, as then single tildes form
+    /// subscript.
+    /// This option does not affect strikethrough with double tildes.
+    ///
+    /// The default is `true`, which follows how markdown on `github.com`
+    /// works, as strikethrough with single tildes is supported.
+    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
+    /// strikethrough with single tildes.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single tildes by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><del>a</del></p>"
+    /// );
+    ///
+    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "~a~",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs::gfm(),
+    ///               gfm_strikethrough_single_tilde: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>~a~</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub gfm_strikethrough_single_tilde: bool,
+
+    /// Whether to support math (text) with a single dollar
+    ///
+    /// This option does nothing if `math_text` is not turned on in
+    /// `constructs`.
+    /// This option does not affect math (text) with two or more dollars.
+    ///
+    /// The default is `true`, which is more close to how code (text) and
+    /// Pandoc work, as it allows math with a single dollar to form.
+    /// However, single dollars can interfere with “normal” dollars in text.
+    /// Pass `false`, to only allow math (text) to form when two or more
+    /// dollars are used.
+    /// If you pass `false`, you can still use two or more dollars for text
+    /// math.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports single dollars by default:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    /// );
+    ///
+    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "$a$",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 math_text: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               math_text_single_dollar: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>$a$</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub math_text_single_dollar: bool,
+
+    /// Whether to support emphasis and strong with underscores.
+    ///
+    /// This option does nothing if `attention` is not turned on in
+    /// `constructs`.
+    ///
+    /// The default is `true`, which follows `CommonMark`: `_a_` is emphasis
+    /// and `__a__` is strong.
+    /// Underscores inside words (as in `snake_case_words`) never form
+    /// emphasis or strong, but `_snake_ case` at the edges of words does.
+    /// Pass `false` to never form emphasis or strong with underscores: they
+    /// are always literal.
+    /// Asterisks still work.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports underscores by default:
+    /// assert_eq!(
+    ///     to_html("_a_ __b__ *c*"),
+    ///     "<p><em>a</em> <strong>b</strong> <em>c</em></p>"
+    /// );
+    ///
+    /// // Pass `attention_underscore: false` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "_a_ __b__ *c*",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               attention_underscore: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>_a_ __b__ <em>c</em></p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub attention_underscore: bool,
 
-    // To do: `doc_markdown` is broken.
-    #[allow(clippy::doc_markdown)]
-    /// Default line ending to use when compiling to HTML, for line endings not
-    /// in `value`.
+    /// Whether to support emphasis and strong with underscores inside words.
     ///
-    /// Generally, `markdown-rs` copies line endings (`\r`, `\n`, `\r\n`) in
-    /// the markdown document over to the compiled HTML.
-    /// In some cases, such as `> a`, CommonMark requires that extra line
-    /// endings are added: `<blockquote>\n<p>a</p>\n</blockquote>`.
+    /// This option does nothing if `attention` is not turned on in
+    /// `constructs`, or if `attention_underscore` is `false`.
     ///
-    /// To create that line ending, the document is checked for the first line
-    /// ending that is used.
-    /// If there is no line ending, `default_line_ending` is used.
-    /// If that isn’t configured, `\n` is used.
+    /// The default is `false`, which follows `CommonMark` and GFM (and so
+    /// matches `github.com`): underscores inside words, as in
+    /// `snake_case_words`, are literal, while `_a_` at the edges of words is
+    /// emphasis.
+    /// Pass `true` to let underscores open and close inside words too, the
+    /// same as asterisks, so that `foo_bar_baz` is `foo<em>bar</em>baz`.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` uses `\n` by default:
+    /// // `markdown-rs` does not support underscores inside words by default:
     /// assert_eq!(
-    ///     to_html("> a"),
-    ///     "<blockquote>\n<p>a</p>\n</blockquote>"
+    ///     to_html("foo_bar_baz foo*bar*baz"),
+    ///     "<p>foo_bar_baz foo<em>bar</em>baz</p>"
     /// );
     ///
-    /// // Define `default_line_ending` to configure the default:
+    /// // Pass `attention_underscore_intraword: true` to turn that on:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "> a",
+    ///         "foo_bar_baz foo*bar*baz",
     ///         &Options {
-    ///             compile: CompileOptions {
-    ///               default_line_ending: LineEnding::CarriageReturnLineFeed,
-    ///               ..CompileOptions::default()
+    ///             parse: ParseOptions {
+    ///               attention_underscore_intraword: true,
+    ///               ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<blockquote>\r\n<p>a</p>\r\n</blockquote>"
+    ///     "<p>foo<em>bar</em>baz foo<em>bar</em>baz</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub default_line_ending: LineEnding,
+    pub attention_underscore_intraword: bool,
 
-    /// Textual label to use for the footnotes section.
+    /// Whether to treat text as plain text, without phrasing constructs.
+    ///
+    /// The default is `false`: text in paragraphs, headings, and table cells
+    /// can contain emphasis, links, code (text), and so on.
+    /// Pass `true` to not parse phrasing at all, which is faster and which is
+    /// useful for user content where only the block structure matters.
+    /// Text then only contains literal characters (which are still encoded
+    /// when compiling to HTML) and line endings.
+    /// This turns off attention, autolinks, character escapes, character
+    /// references, code (text), directives (text), GFM autolink literals,
+    /// GFM footnote calls, GFM task list item checks, hard breaks, HTML
+    /// (text), labels (links and images), math (text), and MDX (text),
+    /// regardless of `constructs`.
+    ///
+    /// Block constructs (such as block quotes, lists, and headings) and
+    /// strings (such as the info of fenced code and definitions) are not
+    /// affected.
     ///
-    /// The default value is `"Footnotes"`.
-    /// Change it when the markdown is not in English.
+    /// ## Examples
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` parses phrasing by default:
+    /// assert_eq!(
+    ///     to_html("> *a* [b](c) &amp; <i>"),
+    ///     "<blockquote>\n<p><em>a</em> <a href=\"c\">b</a> &amp; &lt;i&gt;</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `plain_text: true` to turn that off:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "> *a* [b](c) &amp; <i>",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               plain_text: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<blockquote>\n<p>*a* [b](c) &amp;amp; &lt;i&gt;</p>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub plain_text: bool,
+
+    /// Whether to strip one space from both sides of code (text) and math
+    /// (text).
+    ///
+    /// The default is `true`, as required by `CommonMark`: when the content
+    /// of code (text) both starts and ends with a space, and does not consist
+    /// of only spaces, one space is removed from each side.
+    /// That allows code (text) to start or end with a backtick: `` `` `a` `` ``
+    /// is `` `a` ``.
+    /// Line endings in code (text) are turned into spaces before that
+    /// happens, but other whitespace (such as tabs) is not stripped.
+    /// Pass `false` to keep all spaces.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"Footnotes"` is used by default:
+    /// // `markdown-rs` strips a space from both sides by default:
+    /// assert_eq!(
+    ///     to_html("`` `a` `` and ` `"),
+    ///     "<p><code>`a`</code> and <code> </code></p>"
+    /// );
+    ///
+    /// // Pass `code_text_strip_space: false` to keep them:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "`` `a` `` and ` `",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               code_text_strip_space: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p><code> `a` </code> and <code> </code></p>"
     /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub code_text_strip_space: bool,
+
+    /// Which kinds of frontmatter to support.
     ///
-    /// // Pass `gfm_footnote_label` to use something else:
+    /// This option does nothing if `frontmatter` is not turned on in
+    /// `constructs`.
+    ///
+    /// The default is [`FrontmatterKind::Both`][], which supports YAML
+    /// (`---`) and TOML (`+++`) fences.
+    /// The closing fence must always use the same marker as the opening
+    /// fence.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Constructs, FrontmatterKind, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` supports TOML frontmatter by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "+++\ntitle = \"Neptune\"\n+++",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               gfm_footnote_label: Some("Notes de bas de page".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 frontmatter: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Notes de bas de page</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     ""
+    /// );
+    ///
+    /// // Pass `frontmatter_kind: FrontmatterKind::Yaml` to only support YAML:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "+++\ntitle = \"Neptune\"\n+++",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 frontmatter: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               frontmatter_kind: FrontmatterKind::Yaml,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>+++\ntitle = &quot;Neptune&quot;\n+++</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label: Option<String>,
+    pub frontmatter_kind: FrontmatterKind,
 
-    /// HTML tag name to use for the footnote label element.
+    /// Configuration for heading (atx).
     ///
-    /// The default value is `"h2"`.
-    /// Change it to match your document structure.
+    /// The default follows `CommonMark`: a closing sequence is allowed, and
+    /// whitespace is required after the opening sequence.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass different attributes with the `gfm_footnote_label_attributes`
-    /// option.
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, HeadingAtxOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` follows CommonMark by default:
+    /// assert_eq!(
+    ///     to_html("##a ##\n\n## b ##"),
+    ///     "<p>##a ##</p>\n<h2>b</h2>"
+    /// );
+    ///
+    /// // Pass `heading_atx` to change that:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "##a ##\n\n## b ##",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               heading_atx: HeadingAtxOptions {
+    ///                 closing_sequence: false,
+    ///                 space_required: false,
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<h2>a ##</h2>\n<h2>b ##</h2>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub heading_atx: HeadingAtxOptions,
+
+    /// Max rank of headings.
+    ///
+    /// The default is `6`, which allows `<h1>` through `<h6>`.
+    /// Pass a lower number to allow fewer ranks, for example `3` when only
+    /// `<h1>` through `<h3>` are allowed.
+    ///
+    /// Heading (atx) with more hashes than allowed, and heading (setext) with
+    /// an underline of dashes (`<h2>`) when only `1` is allowed, are not
+    /// headings: they are parsed as if headings of that rank did not exist,
+    /// which typically makes them part of a paragraph.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"h2"` is used by default:
+    /// // `markdown-rs` supports six ranks by default:
+    /// assert_eq!(
+    ///     to_html("### a\n\n#### b"),
+    ///     "<h3>a</h3>\n<h4>b</h4>"
+    /// );
+    ///
+    /// // Pass `max_heading_depth` to allow fewer:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "### a\n\n#### b",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               max_heading_depth: 3,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<h3>a</h3>\n<p>#### b</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub max_heading_depth: u8,
+
+    /// Which markers can start list items.
+    ///
+    /// The default follows `CommonMark`, which allows `*`, `+`, and `-` as
+    /// bullets, and `.` and `)` as delimiters after the value of ordered
+    /// list items.
+    /// Some flavors of markdown only allow some of them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, ListItemOptions, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` allows all markers by default:
+    /// assert_eq!(
+    ///     to_html("+ a\n\n1) b"),
+    ///     "<ul>\n<li>a</li>\n</ul>\n<ol>\n<li>b</li>\n</ol>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_tag_name` to use something else:
+    /// // Pass `list_item` to allow fewer:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "+ a\n\n1) b",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               gfm_footnote_label_tag_name: Some("h1".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               list_item: ListItemOptions {
+    ///                 bullet_plus: false,
+    ///                 delimiter_parenthesis: false,
+    ///                 ..ListItemOptions::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h1 id=\"footnote-label\" class=\"sr-only\">Footnotes</h1>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>+ a</p>\n<p>1) b</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_tag_name: Option<String>,
+    pub list_item: ListItemOptions,
 
-    /// Attributes to use on the footnote label.
+    /// Number of spaces needed before a line ending to form a hard break
+    /// (trailing).
     ///
-    /// The default value is `"class=\"sr-only\""`.
-    /// Change it to show the label and add other attributes.
+    /// The default is `2`, as in `CommonMark`.
+    /// Pass a higher number to make it less likely that accidental trailing
+    /// whitespace forms hard breaks.
+    /// Numbers below `1` are treated as `1`.
     ///
-    /// This label is typically hidden visually (assuming a `sr-only` CSS class
-    /// is defined that does that), and thus affects screen readers only.
-    /// If you do have such a class, but want to show this section to everyone,
-    /// pass an empty string.
-    /// You can also add different attributes.
+    /// To never turn trailing whitespace into hard breaks, and only support
+    /// hard breaks with a backslash (`\`), turn off the
+    /// [`hard_break_trailing`][Constructs::hard_break_trailing] construct
+    /// instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` needs two spaces by default:
+    /// assert_eq!(
+    ///     to_html("a  \nb"),
+    ///     "<p>a<br />\nb</p>"
+    /// );
+    ///
+    /// // Pass `hard_break_trailing_size_min` to change that:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "a  \nb",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               hard_break_trailing_size_min: 3,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<p>a\nb</p>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub hard_break_trailing_size_min: usize,
+
+    /// Size of a tab stop.
+    ///
+    /// Tabs are expanded to the next multiple of this size, and it is the
+    /// amount of indentation needed to form
+    /// [code (indented)][Constructs::code_indented] (and one less is the most
+    /// indentation allowed before other flow constructs, such as headings,
+    /// and before container prefixes, such as `>`).
+    ///
+    /// The default is `4`, as in `CommonMark`.
+    /// Numbers below `1` are treated as `1`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses a tab size of 4 by default:
+    /// assert_eq!(
+    ///     to_html("  a\n\n>\t\tb"),
+    ///     "<p>a</p>\n<blockquote>\n<pre><code>  b\n</code></pre>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `tab_size` to change that:
+    /// assert_eq!(
+    ///     to_html_with_options(
+    ///         "  a\n\n>\t\tb",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               tab_size: 2,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
+    ///     )?,
+    ///     "<pre><code>a\n</code></pre>\n<blockquote>\n<pre><code>b\n</code></pre>\n</blockquote>"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub tab_size: usize,
+
+    /// Protocols (schemes) to allow in autolinks.
     ///
-    /// > 👉 **Note**: `id="footnote-label"` is always added, because footnote
-    /// > calls use it with `aria-describedby` to provide an accessible label.
+    /// The default is `None`, which allows all protocols, as in `CommonMark`.
+    /// Pass a list to only allow those protocols (compared case-insensitively,
+    /// without the `:`): autolinks with other protocols are not autolinks but
+    /// text.
+    ///
+    /// Email autolinks (such as `<a@b.c>`) are not affected by this:
+    /// turn them off with the [`autolink`][Constructs::autolink] construct.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"class=\"sr-only\""` is used by default:
+    /// // `markdown-rs` allows all protocols by default (though not in `href`s,
+    /// // see `allow_dangerous_protocol`):
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     to_html("<tel:123>"),
+    ///     "<p><a href=\"\">tel:123</a></p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_label_attributes` to use something else:
+    /// // Pass `autolink_protocols` to only allow some:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "<tel:123>, <https://example.com>",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               gfm_footnote_label_attributes: Some("class=\"footnote-heading\"".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               autolink_protocols: Some(vec!["http".into(), "https".into(), "mailto".into()]),
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"footnote-heading\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>&lt;tel:123&gt;, <a href=\"https://example.com\">https://example.com</a></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_label_attributes: Option<String>,
+    pub autolink_protocols: Option<Vec<String>>,
 
-    /// Textual label to describe the backreference back to footnote calls.
+    /// Emoji shortcodes (without colons) and the emoji they stand for.
     ///
-    /// The default value is `"Back to content"`.
-    /// Change it when the markdown is not in English.
+    /// Only used when [`emoji`][Constructs::emoji] is turned on.
     ///
-    /// This label is used in the `aria-label` attribute on each backreference
-    /// (the `↩` links).
-    /// It affects users of assistive technology.
+    /// The default is `vec![]`.
+    /// With the `emoji` feature, the shortcodes used on GitHub are supported
+    /// too; these go before those, so they can be used to override them.
+    /// Without that feature, only these are supported.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"Back to content"` is used by default:
-    /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
-    ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
-    /// );
-    ///
-    /// // Pass `gfm_footnote_back_label` to use something else:
+    /// // Pass `emoji_shortcodes` to add shortcodes:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         ":ferris: says hi, :unknown:",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               gfm_footnote_back_label: Some("Arrière".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 emoji: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               emoji_shortcodes: vec![("ferris".into(), "🦀".into())],
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Arrière\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p>🦀 says hi, :unknown:</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_back_label: Option<String>,
+    pub emoji_shortcodes: Vec<(String, String)>,
 
-    /// Prefix to use before the `id` attribute on footnotes to prevent them
-    /// from *clobbering*.
-    ///
-    /// The default is `"user-content-"`.
-    /// Pass `Some("".into())` for trusted markdown and when you are careful
-    /// with polyfilling.
-    /// You could pass a different prefix.
+    /// Whether to match abbreviations regardless of case.
     ///
-    /// DOM clobbering is this:
-    ///
-    /// ```html
-    /// <p id="x"></p>
-    /// <script>alert(x) // `x` now refers to the `p#x` DOM element</script>
-    /// ```
+    /// Only used when [`abbreviation`][Constructs::abbreviation] is turned
+    /// on.
     ///
-    /// The above example shows that elements are made available by browsers,
-    /// by their ID, on the `window` object.
-    /// This is a security risk because you might be expecting some other
-    /// variable at that place.
-    /// It can also break polyfills.
-    /// Using a prefix solves these problems.
+    /// The default is `false`: `*[HTML]: …` does not match `html` in text.
+    /// Pass `true` to match it too.
+    /// Either way, abbreviations only match whole words, and do not match in
+    /// code, links, images, or other abbreviations.
+    /// When several abbreviations match at a place, the longest is used.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `"user-content-"` is used by default:
+    /// // Abbreviations match case-sensitively by default:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
-    ///         &Options::gfm()
+    ///         "*[HTML]: Hyper Text Markup Language\n\nHTML and html",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 abbreviation: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
+    ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p><abbr title=\"Hyper Text Markup Language\">HTML</abbr> and html</p>"
     /// );
     ///
-    /// // Pass `gfm_footnote_clobber_prefix` to use something else:
+    /// // Pass `abbreviation_ignore_case` to match regardless of case:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "[^a]\n\n[^a]: b",
+    ///         "*[HTML]: Hyper Text Markup Language\n\nHTML and html",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               gfm_footnote_clobber_prefix: Some("".into()),
-    ///               ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               constructs: Constructs {
+    ///                 abbreviation: true,
+    ///                 ..Constructs::default()
+    ///               },
+    ///               abbreviation_ignore_case: true,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p><sup><a href=\"#fn-a\" id=\"fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"fn-a\">\n<p>b <a href=\"#fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n"
+    ///     "<p><abbr title=\"Hyper Text Markup Language\">HTML</abbr> and <abbr title=\"Hyper Text Markup Language\">html</abbr></p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_footnote_clobber_prefix: Option<String>,
+    pub abbreviation_ignore_case: bool,
 
-    /// Whether or not GFM task list html `<input>` items are enabled.
+    /// Whether to support lazy paragraph continuation in block quotes.
     ///
-    /// This determines whether or not the user of the browser is able
-    /// to click and toggle generated checkbox items. The default is false.
+    /// The default is `true`, which follows `CommonMark`: a line without `>`
+    /// can continue a paragraph in a block quote.
+    ///
+    /// Pass `false` to require `>` on every line of a block quote: a line
+    /// without it closes the block quote, and everything in it, before that
+    /// line.
+    /// Lazy lines in list items are still supported, unless they are in a
+    /// block quote.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // With `gfm_task_list_item_checkable`, generated `<input type="checkbox" />`
-    /// // tags do not contain the attribute `disabled=""` and are thus toggleable by
-    /// // browser users.
+    /// // `markdown-rs` supports lazy lines in block quotes by default:
+    /// assert_eq!(
+    ///     to_html("> a\nb"),
+    ///     "<blockquote>\n<p>a\nb</p>\n</blockquote>"
+    /// );
+    ///
+    /// // Pass `block_quote_lazy: false` to turn that off:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "* [x] y.",
+    ///         "> a\nb",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///                 gfm_task_list_item_checkable: true,
-    ///                 ..CompileOptions::gfm()
-    ///             }
+    ///             parse: ParseOptions {
+    ///               block_quote_lazy: false,
+    ///               ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<ul>\n<li><input type=\"checkbox\" checked=\"\" /> y.</li>\n</ul>"
+    ///     "<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_task_list_item_checkable: bool,
+    pub block_quote_lazy: bool,
 
-    /// Whether to support the GFM tagfilter.
+    /// Function to parse expressions with.
     ///
-    /// This option does nothing if `allow_dangerous_html` is not turned on.
-    /// The default is `false`, which does not apply the GFM tagfilter to HTML.
-    /// Pass `true` for output that is a bit closer to GitHub’s actual output.
+    /// This function can be used to add support for arbitrary programming
+    /// languages within expressions.
     ///
-    /// The tagfilter is kinda weird and kinda useless.
-    /// The tag filter is a naïve attempt at XSS protection.
-    /// You should use a proper HTML sanitizing algorithm instead.
+    /// It only makes sense to pass this when compiling to a syntax tree
+    /// with [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// For an example that adds support for JavaScript with SWC, see
+    /// `tests/test_utils/mod.rs`.
+    pub mdx_expression_parse: Option<Box<MdxExpressionParse>>,
+
+    /// Function to parse ESM with.
+    ///
+    /// This function can be used to add support for arbitrary programming
+    /// languages within ESM blocks, however, the keywords (`export`,
+    /// `import`) are currently hardcoded JavaScript-specific.
+    ///
+    /// > 👉 **Note**: please raise an issue if you’re interested in working on
+    /// > MDX that is aware of, say, Rust, or other programming languages.
+    ///
+    /// It only makes sense to pass this when compiling to a syntax tree
+    /// with [`to_mdast()`][crate::to_mdast()].
+    ///
+    /// For an example that adds support for JavaScript with SWC, see
+    /// `tests/test_utils/mod.rs`.
+    pub mdx_esm_parse: Option<Box<MdxEsmParse>>,
+
+    /// Custom flow constructs.
+    ///
+    /// The default is to not have any.
+    /// Pass extensions to add constructs that are not maintained in this
+    /// project, such as custom fences, without forking.
+    /// Each extension is line based: its `start` function decides whether a
+    /// line starts it, and its optional `close` function decides whether a
+    /// later line closes it.
+    /// Depending on its `priority`, an extension is tried before or after the
+    /// built-in flow constructs.
+    ///
+    /// To turn them into HTML, see `flow_extension_handler` in
+    /// [`CompileOptions`][].
+    /// They are not included in mdast.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, CompileOptions, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, FlowExtension, FlowExtensionPriority, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // With `allow_dangerous_html`, `markdown-rs` passes HTML through untouched:
+    /// // `markdown-rs` has no custom flow constructs by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "<iframe>",
-    ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               ..CompileOptions::default()
-    ///             }
-    ///         }
-    ///     )?,
-    ///     "<iframe>"
+    ///     to_html("%%%\n*a*\n%%%"),
+    ///     "<p>%%%\n<em>a</em>\n%%%</p>"
     /// );
     ///
-    /// // Pass `gfm_tagfilter: true` to make some of that safe:
+    /// // Pass `flow_extensions` to add some:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "<iframe>",
+    ///         "%%%\n*a*\n%%%",
     ///         &Options {
-    ///             parse: ParseOptions::gfm(),
-    ///             compile: CompileOptions {
-    ///               allow_dangerous_html: true,
-    ///               gfm_tagfilter: true,
-    ///               ..CompileOptions::default()
-    ///             }
+    ///             parse: ParseOptions {
+    ///                 flow_extensions: vec![FlowExtension {
+    ///                     name: "note".into(),
+    ///                     priority: FlowExtensionPriority::First,
+    ///                     interrupt: false,
+    ///                     start: Box::new(|line| line == "%%%"),
+    ///                     close: Some(Box::new(|_opening, line| line == "%%%")),
+    ///                 }],
+    ///                 ..ParseOptions::default()
+    ///             },
+    ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "&lt;iframe>"
+    ///     "<pre><code>%%%\n*a*\n%%%\n</code></pre>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// ## References
-    ///
-    /// *   [*§ 6.1 Disallowed Raw HTML (extension)* in GFM](https://github.github.com/gfm/#disallowed-raw-html-extension-)
-    /// *   [`cmark-gfm#extensions/tagfilter.c`](https://github.com/github/cmark-gfm/blob/master/extensions/tagfilter.c)
-    pub gfm_tagfilter: bool,
-}
+    pub flow_extensions: Vec<FlowExtension>,
 
-impl CompileOptions {
-    /// GFM.
-    ///
-    /// GFM stands for **GitHub flavored markdown**.
-    /// On the compilation side, GFM turns on the GFM tag filter.
-    /// The tagfilter is useless, but it’s included here for consistency, and
-    /// this method exists for parity to parse options.
+    /// Custom text constructs.
     ///
-    /// For more information, see the GFM specification:
-    /// <https://github.github.com/gfm/>.
-    pub fn gfm() -> Self {
-        Self {
-            gfm_tagfilter: true,
-            ..Self::default()
-        }
-    }
-}
-
-/// Configuration that describes how to parse from markdown.
-///
-/// You can use this:
-///
-/// *   To control what markdown constructs are turned on and off
-/// *   To control some of those constructs
-/// *   To add support for certain programming languages when parsing MDX
-///
-/// In most cases, you will want to use the default trait or `gfm` method.
-///
-/// ## Examples
-///
-/// ```
-/// use markdown::ParseOptions;
-/// # fn main() {
-///
-/// // Use the default trait to parse markdown according to `CommonMark`:
-/// let commonmark = ParseOptions::default();
-///
-/// // Use the `gfm` method to parse markdown according to GFM:
-/// let gfm = ParseOptions::gfm();
-/// # }
-/// ```
-#[allow(clippy::struct_excessive_bools)]
-pub struct ParseOptions {
-    // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
-    /// Which constructs to enable and disable.
+    /// The default is to not have any.
+    /// Pass extensions to add constructs that are not maintained in this
+    /// project, such as mentions, hashtags, or emoji shortcodes, without
+    /// forking.
+    /// Each extension is tried at its `marker`, before the built-in text
+    /// constructs, and its `start` function decides whether the construct
+    /// starts there, and how big it is.
     ///
-    /// The default is to follow `CommonMark`.
+    /// To turn them into HTML, see `text_extension_handler` in
+    /// [`CompileOptions`][].
+    /// They are included in mdast as text.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html, to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, CompileOptions, Options, ParseOptions, TextExtension};
+    /// use std::rc::Rc;
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` follows CommonMark by default:
+    /// // `markdown-rs` has no custom text constructs by default:
     /// assert_eq!(
-    ///     to_html("    indented code?"),
-    ///     "<pre><code>indented code?\n</code></pre>"
+    ///     to_html("Hi, :wave:!"),
+    ///     "<p>Hi, :wave:!</p>"
     /// );
     ///
-    /// // Pass `constructs` to choose what to enable and disable:
+    /// // Pass `text_extensions` to add some:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "    indented code?",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 code_indented: false,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               ..ParseOptions::default()
+    ///     to_html_with_options(
+    ///         "Hi, :wave:!",
+    ///         &Options {
+    ///             parse: ParseOptions {
+    ///                 text_extensions: vec![TextExtension {
+    ///                     name: "emoji".into(),
+    ///                     marker: b':',
+    ///                     start: Box::new(|rest| {
+    ///                         let size = rest[1..].find(':')?;
+    ///                         if size > 0 && rest[1..=size].bytes().all(|d| d.is_ascii_lowercase()) {
+    ///                             Some(size + 2)
+    ///                         } else {
+    ///                             None
+    ///                         }
+    ///                     }),
+    ///                 }],
+    ///                 ..ParseOptions::default()
     ///             },
-    ///             ..Options::default()
+    ///             compile: CompileOptions {
+    ///                 text_extension_handler: Some(Rc::new(|_name, value| {
+    ///                     if value == ":wave:" { Some("👋".into()) } else { None }
+    ///                 })),
+    ///                 ..CompileOptions::default()
+    ///             }
     ///         }
     ///     )?,
-    ///     "<p>indented code?</p>"
+    ///     "<p>Hi, 👋!</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub constructs: Constructs,
+    pub text_extensions: Vec<TextExtension>,
 
-    /// Whether to support GFM strikethrough with a single tilde
+    /// How deep containers and brackets can be nested.
     ///
-    /// This option does nothing if `gfm_strikethrough` is not turned on in
-    /// `constructs`.
-    /// This option does not affect strikethrough with double tildes.
+    /// The default is `None`, which means there is no limit.
+    /// Pass a number to guard against input that is nested very deeply, such
+    /// as thousands of `>` or `[`, which takes a lot of time and memory to
+    /// parse and compile.
     ///
-    /// The default is `true`, which follows how markdown on `github.com`
-    /// works, as strikethrough with single tildes is supported.
-    /// Pass `false`, to follow the GFM spec more strictly, by not allowing
-    /// strikethrough with single tildes.
+    /// The limit applies to block containers (block quotes, list items,
+    /// footnote definitions, directives) and, separately, to the open labels
+    /// of links, images, and footnote calls (`[`, `![`, `[^`).
+    /// When the limit is reached, further container markers are text.
+    /// For labels, the outermost open label turns into text instead, so that
+    /// stray brackets do not affect later links.
+    /// Other nesting, such as of emphasis, is not limited.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single tildes by default:
+    /// // `markdown-rs` has no limit by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "~a~",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               ..ParseOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<p><del>a</del></p>"
+    ///     to_html(">>> a"),
+    ///     "<blockquote>\n<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n</blockquote>"
     /// );
     ///
-    /// // Pass `gfm_strikethrough_single_tilde: false` to turn that off:
+    /// // Pass `max_nesting_depth` to limit nesting:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "~a~",
+    ///         ">>> a",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs::gfm(),
-    ///               gfm_strikethrough_single_tilde: false,
-    ///               ..ParseOptions::default()
+    ///                 max_nesting_depth: Some(2),
+    ///                 ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>~a~</p>"
+    ///     "<blockquote>\n<blockquote>\n<p>&gt; a</p>\n</blockquote>\n</blockquote>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub gfm_strikethrough_single_tilde: bool,
+    pub max_nesting_depth: Option<usize>,
 
-    /// Whether to support math (text) with a single dollar
+    /// How big the input can be, in bytes.
     ///
-    /// This option does nothing if `math_text` is not turned on in
-    /// `constructs`.
-    /// This option does not affect math (text) with two or more dollars.
+    /// The default is `None`, which means there is no limit.
+    /// Pass a number to guard against very big input, which takes a lot of
+    /// time and memory to parse and compile.
+    /// The size is checked before anything is parsed.
     ///
-    /// The default is `true`, which is more close to how code (text) and
-    /// Pandoc work, as it allows math with a single dollar to form.
-    /// However, single dollars can interfere with “normal” dollars in text.
-    /// Pass `false`, to only allow math (text) to form when two or more
-    /// dollars are used.
-    /// If you pass `false`, you can still use two or more dollars for text
-    /// math.
+    /// By default, input that is too big results in an error.
+    /// Turn [`truncate_input`][ParseOptions::truncate_input] on to parse the
+    /// start of the input instead.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use markdown::{to_html_with_options, Constructs, Options, ParseOptions};
+    /// use markdown::{to_html, to_html_with_options, Options, ParseOptions};
     /// # fn main() -> Result<(), markdown::message::Message> {
     ///
-    /// // `markdown-rs` supports single dollars by default:
+    /// // `markdown-rs` has no limit by default:
     /// assert_eq!(
-    ///     to_html_with_options(
-    ///         "$a$",
-    ///         &Options {
-    ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               ..ParseOptions::default()
-    ///             },
-    ///             ..Options::default()
-    ///         }
-    ///     )?,
-    ///     "<p><code class=\"language-math math-inline\">a</code></p>"
+    ///     to_html("*abcdef*"),
+    ///     "<p><em>abcdef</em></p>"
     /// );
     ///
-    /// // Pass `math_text_single_dollar: false` to turn that off:
+    /// // Pass `max_input_bytes` to limit the size of the input:
+    /// let result = to_html_with_options(
+    ///     "*abcdef*",
+    ///     &Options {
+    ///         parse: ParseOptions {
+    ///             max_input_bytes: Some(4),
+    ///             ..ParseOptions::default()
+    ///         },
+    ///         ..Options::default()
+    ///     }
+    /// );
+    ///
+    /// assert_eq!(
+    ///     result.unwrap_err().reason,
+    ///     "Unexpected input of 8 bytes, expected at most 4 bytes"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub max_input_bytes: Option<usize>,
+
+    /// Whether to parse the start of input that is bigger than
+    /// [`max_input_bytes`][ParseOptions::max_input_bytes], instead of
+    /// erroring.
+    ///
+    /// The default is `false`.
+    /// Pass `true` to drop everything after the limit.
+    /// When the limit is in the middle of a character, that character is
+    /// dropped too.
+    /// What is left is parsed as a whole document, so constructs that are
+    /// cut off (such as an unclosed fenced code block) are handled like any
+    /// other unclosed construct.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_html_with_options, Options, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // Pass `truncate_input` to parse the first `max_input_bytes`:
     /// assert_eq!(
     ///     to_html_with_options(
-    ///         "$a$",
+    ///         "*abcdef*",
     ///         &Options {
     ///             parse: ParseOptions {
-    ///               constructs: Constructs {
-    ///                 math_text: true,
-    ///                 ..Constructs::default()
-    ///               },
-    ///               math_text_single_dollar: false,
-    ///               ..ParseOptions::default()
+    ///                 max_input_bytes: Some(4),
+    ///                 truncate_input: true,
+    ///                 ..ParseOptions::default()
     ///             },
     ///             ..Options::default()
     ///         }
     ///     )?,
-    ///     "<p>$a$</p>"
+    ///     "<p>*abc</p>"
     /// );
     /// # Ok(())
     /// # }
     /// ```
-    pub math_text_single_dollar: bool,
-
-    /// Function to parse expressions with.
-    ///
-    /// This function can be used to add support for arbitrary programming
-    /// languages within expressions.
-    ///
-    /// It only makes sense to pass this when compiling to a syntax tree
-    /// with [`to_mdast()`][crate::to_mdast()].
-    ///
-    /// For an example that adds support for JavaScript with SWC, see
-    /// `tests/test_utils/mod.rs`.
-    pub mdx_expression_parse: Option<Box<MdxExpressionParse>>,
-
-    /// Function to parse ESM with.
-    ///
-    /// This function can be used to add support for arbitrary programming
-    /// languages within ESM blocks, however, the keywords (`export`,
-    /// `import`) are currently hardcoded JavaScript-specific.
-    ///
-    /// > 👉 **Note**: please raise an issue if you’re interested in working on
-    /// > MDX that is aware of, say, Rust, or other programming languages.
-    ///
-    /// It only makes sense to pass this when compiling to a syntax tree
-    /// with [`to_mdast()`][crate::to_mdast()].
-    ///
-    /// For an example that adds support for JavaScript with SWC, see
-    /// `tests/test_utils/mod.rs`.
-    pub mdx_esm_parse: Option<Box<MdxEsmParse>>,
+    pub truncate_input: bool,
     // Note: when adding fields, don’t forget to add them to `fmt::Debug` below.
 }
 
@@ -1159,6 +4528,26 @@ impl fmt::Debug for ParseOptions {
                 &self.gfm_strikethrough_single_tilde,
             )
             .field("math_text_single_dollar", &self.math_text_single_dollar)
+            .field("attention_underscore", &self.attention_underscore)
+            .field(
+                "attention_underscore_intraword",
+                &self.attention_underscore_intraword,
+            )
+            .field("plain_text", &self.plain_text)
+            .field("code_text_strip_space", &self.code_text_strip_space)
+            .field("frontmatter_kind", &self.frontmatter_kind)
+            .field("heading_atx", &self.heading_atx)
+            .field("max_heading_depth", &self.max_heading_depth)
+            .field("list_item", &self.list_item)
+            .field(
+                "hard_break_trailing_size_min",
+                &self.hard_break_trailing_size_min,
+            )
+            .field("tab_size", &self.tab_size)
+            .field("autolink_protocols", &self.autolink_protocols)
+            .field("emoji_shortcodes", &self.emoji_shortcodes)
+            .field("abbreviation_ignore_case", &self.abbreviation_ignore_case)
+            .field("block_quote_lazy", &self.block_quote_lazy)
             .field(
                 "mdx_expression_parse",
                 &self.mdx_expression_parse.as_ref().map(|_d| "[Function]"),
@@ -1167,6 +4556,11 @@ impl fmt::Debug for ParseOptions {
                 "mdx_esm_parse",
                 &self.mdx_esm_parse.as_ref().map(|_d| "[Function]"),
             )
+            .field("flow_extensions", &self.flow_extensions)
+            .field("text_extensions", &self.text_extensions)
+            .field("max_nesting_depth", &self.max_nesting_depth)
+            .field("max_input_bytes", &self.max_input_bytes)
+            .field("truncate_input", &self.truncate_input)
             .finish()
     }
 }
@@ -1178,8 +4572,27 @@ impl Default for ParseOptions {
             constructs: Constructs::default(),
             gfm_strikethrough_single_tilde: true,
             math_text_single_dollar: true,
+            attention_underscore: true,
+            attention_underscore_intraword: false,
+            plain_text: false,
+            code_text_strip_space: true,
+            frontmatter_kind: FrontmatterKind::default(),
+            heading_atx: HeadingAtxOptions::default(),
+            max_heading_depth: 6,
+            list_item: ListItemOptions::default(),
+            hard_break_trailing_size_min: HARD_BREAK_PREFIX_SIZE_MIN,
+            tab_size: TAB_SIZE,
+            autolink_protocols: None,
+            emoji_shortcodes: vec![],
+            abbreviation_ignore_case: false,
+            block_quote_lazy: true,
             mdx_expression_parse: None,
             mdx_esm_parse: None,
+            flow_extensions: vec![],
+            text_extensions: vec![],
+            max_nesting_depth: None,
+            max_input_bytes: None,
+            truncate_input: false,
         }
     }
 }
@@ -1227,6 +4640,251 @@ impl ParseOptions {
     }
 }
 
+/// Configuration that describes how to format markdown.
+///
+/// Only used by [`to_markdown()`][crate::to_markdown()].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{FormatOptions, ProseWrap};
+/// # fn main() {
+///
+/// // Use the default trait to get `-` bullets, `*` emphasis, and prose
+/// // line endings as they are:
+/// let default = FormatOptions::default();
+///
+/// // Wrap prose at 60 characters, with `_` for emphasis:
+/// let custom = FormatOptions {
+///   emphasis: '_',
+///   prose_wrap: ProseWrap::Always,
+///   print_width: 60,
+///   ..FormatOptions::default()
+/// };
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    /// Marker to use for bullets of unordered list items.
+    ///
+    /// The default is `'-'`.
+    /// Pass `'*'` or `'+'` to use those instead.
+    /// A list directly after another unordered list uses another marker, as
+    /// they would otherwise form one list.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `-` by default:
+    /// assert_eq!(
+    ///     to_markdown("* a\n+ b", &ParseOptions::default(), &FormatOptions::default())?,
+    ///     "- a\n\n* b\n"
+    /// );
+    ///
+    /// // Pass `bullet` to use another marker:
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "- a",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           bullet: '*',
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "* a\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub bullet: char,
+
+    /// Marker to use for emphasis.
+    ///
+    /// The default is `'*'`.
+    /// Pass `'_'` to use underscores instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `*` by default:
+    /// assert_eq!(
+    ///     to_markdown("_a_", &ParseOptions::default(), &FormatOptions::default())?,
+    ///     "*a*\n"
+    /// );
+    ///
+    /// // Pass `emphasis` to use `_`:
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "*a*",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           emphasis: '_',
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "_a_\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub emphasis: char,
+
+    /// Marker to use for strong.
+    ///
+    /// The default is `'*'`.
+    /// Pass `'_'` to use underscores instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` uses `**` by default:
+    /// assert_eq!(
+    ///     to_markdown("__a__", &ParseOptions::default(), &FormatOptions::default())?,
+    ///     "**a**\n"
+    /// );
+    ///
+    /// // Pass `strong` to use `__`:
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "**a**",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           strong: '_',
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "__a__\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub strong: char,
+
+    /// How to wrap prose.
+    ///
+    /// The default is [`ProseWrap::Preserve`][], which keeps line endings in
+    /// paragraphs as they are.
+    /// Code, hard breaks, and headings are never wrapped.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions, ProseWrap};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps line endings by default:
+    /// assert_eq!(
+    ///     to_markdown("a\nb", &ParseOptions::default(), &FormatOptions::default())?,
+    ///     "a\nb\n"
+    /// );
+    ///
+    /// // Pass `ProseWrap::Never` to join lines:
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "a\nb",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           prose_wrap: ProseWrap::Never,
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "a b\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub prose_wrap: ProseWrap,
+
+    /// Number of characters that fit on a line, when wrapping prose.
+    ///
+    /// The default is `80`.
+    /// Only used when [`prose_wrap`][FormatOptions::prose_wrap] is
+    /// [`ProseWrap::Always`][].
+    /// Words longer than this are not broken.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions, ProseWrap};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "aaa bbb ccc",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           prose_wrap: ProseWrap::Always,
+    ///           print_width: 7,
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "aaa bbb\nccc\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub print_width: usize,
+
+    /// Whether to turn references into inline links.
+    ///
+    /// The default is `false`, which keeps references (such as `[a][b]`) and
+    /// definitions (such as `[b]: c`) as they are.
+    /// Pass `true` to turn references that have a definition into resources
+    /// (such as `[a](c)`), and to drop definitions.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use markdown::{to_markdown, FormatOptions, ParseOptions};
+    /// # fn main() -> Result<(), markdown::message::Message> {
+    ///
+    /// // `markdown-rs` keeps references by default:
+    /// assert_eq!(
+    ///     to_markdown("[a][b]\n\n[b]: c", &ParseOptions::default(), &FormatOptions::default())?,
+    ///     "[a][b]\n\n[b]: c\n"
+    /// );
+    ///
+    /// // Pass `inline_links: true` to turn them into resources:
+    /// assert_eq!(
+    ///     to_markdown(
+    ///         "[a][b]\n\n[b]: c",
+    ///         &ParseOptions::default(),
+    ///         &FormatOptions {
+    ///           inline_links: true,
+    ///           ..FormatOptions::default()
+    ///         }
+    ///     )?,
+    ///     "[a](c)\n"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub inline_links: bool,
+}
+
+impl Default for FormatOptions {
+    /// `-` bullets, `*` emphasis, and prose line endings as they are.
+    fn default() -> Self {
+        Self {
+            bullet: '-',
+            emphasis: '*',
+            strong: '*',
+            prose_wrap: ProseWrap::default(),
+            print_width: 80,
+            inline_links: false,
+        }
+    }
+}
+
 /// Configuration that describes how to parse from markdown and compile to
 /// HTML.
 ///
@@ -1320,6 +4978,28 @@ mod tests {
         );
         assert!(constructs.mdx_jsx_flow, "should support `mdx` shortcut (3)");
         assert!(!constructs.frontmatter, "should support `mdx` shortcut (4)");
+
+        let constructs = Constructs::default();
+        assert!(
+            constructs.gfm_autolink_literal_email
+                && constructs.gfm_autolink_literal_protocol
+                && constructs.gfm_autolink_literal_www,
+            "should default to all kinds of autolink literals when they are turned on"
+        );
+
+        let constructs = Constructs::gfm();
+        assert!(
+            constructs.gfm_autolink_literal_email,
+            "should support `gfm` shortcut (autolink literal email)"
+        );
+        assert!(
+            constructs.gfm_autolink_literal_protocol,
+            "should support `gfm` shortcut (autolink literal protocol)"
+        );
+        assert!(
+            constructs.gfm_autolink_literal_www,
+            "should support `gfm` shortcut (autolink literal www)"
+        );
     }
 
     #[test]
@@ -1372,7 +5052,7 @@ mod tests {
 
         assert_eq!(
             format!("{:?}", ParseOptions::default()),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: None, mdx_esm_parse: None }",
+            "ParseOptions { constructs: Constructs { abbreviation: false, attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, definition_list: false, directive_container: false, directive_leaf: false, directive_text: false, emoji: false, frontmatter: false, gfm_autolink_literal: false, gfm_autolink_literal_email: true, gfm_autolink_literal_protocol: true, gfm_autolink_literal_www: true, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, highlight: false, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, subscript: false, superscript: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, attention_underscore: true, attention_underscore_intraword: false, plain_text: false, code_text_strip_space: true, frontmatter_kind: Both, heading_atx: HeadingAtxOptions { closing_sequence: true, space_required: true }, max_heading_depth: 6, list_item: ListItemOptions { bullet_asterisk: true, bullet_dash: true, bullet_plus: true, delimiter_period: true, delimiter_parenthesis: true }, hard_break_trailing_size_min: 2, tab_size: 4, autolink_protocols: None, emoji_shortcodes: [], abbreviation_ignore_case: false, block_quote_lazy: true, mdx_expression_parse: None, mdx_esm_parse: None, flow_extensions: [], text_extensions: [], max_nesting_depth: None, max_input_bytes: None, truncate_input: false }",
             "should support `Debug` trait"
         );
         assert_eq!(
@@ -1385,7 +5065,7 @@ mod tests {
                 })),
                 ..Default::default()
             }),
-            "ParseOptions { constructs: Constructs { attention: true, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, frontmatter: false, gfm_autolink_literal: false, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\") }",
+            "ParseOptions { constructs: Constructs { abbreviation: false, attention: true, attributes: false, autolink: true, block_quote: true, character_escape: true, character_reference: true, code_indented: true, code_fenced: true, code_text: true, definition: true, definition_list: false, directive_container: false, directive_leaf: false, directive_text: false, emoji: false, frontmatter: false, gfm_autolink_literal: false, gfm_autolink_literal_email: true, gfm_autolink_literal_protocol: true, gfm_autolink_literal_www: true, gfm_footnote_definition: false, gfm_label_start_footnote: false, gfm_strikethrough: false, gfm_table: false, gfm_task_list_item: false, hard_break_escape: true, hard_break_trailing: true, heading_atx: true, heading_setext: true, highlight: false, html_flow: true, html_text: true, label_start_image: true, label_start_link: true, label_end: true, list_item: true, math_flow: false, math_text: false, mdx_esm: false, mdx_expression_flow: false, mdx_expression_text: false, mdx_jsx_flow: false, mdx_jsx_text: false, subscript: false, superscript: false, thematic_break: true }, gfm_strikethrough_single_tilde: true, math_text_single_dollar: true, attention_underscore: true, attention_underscore_intraword: false, plain_text: false, code_text_strip_space: true, frontmatter_kind: Both, heading_atx: HeadingAtxOptions { closing_sequence: true, space_required: true }, max_heading_depth: 6, list_item: ListItemOptions { bullet_asterisk: true, bullet_dash: true, bullet_plus: true, delimiter_period: true, delimiter_parenthesis: true }, hard_break_trailing_size_min: 2, tab_size: 4, autolink_protocols: None, emoji_shortcodes: [], abbreviation_ignore_case: false, block_quote_lazy: true, mdx_expression_parse: Some(\"[Function]\"), mdx_esm_parse: Some(\"[Function]\"), flow_extensions: [], text_extensions: [], max_nesting_depth: None, max_input_bytes: None, truncate_input: false }",
             "should support `Debug` trait on mdx functions"
         );
     }
@@ -1404,6 +5084,10 @@ mod tests {
             !options.gfm_tagfilter,
             "should default to safe `CommonMark` (2)"
         );
+        assert!(
+            options.xhtml,
+            "should default to self-closing void elements"
+        );
 
         let options = CompileOptions::gfm();
         assert!(
@@ -0,0 +1,263 @@
+//! Abbreviation occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Abbreviation forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: `label` must be the label of an abbreviation definition.
+//! ; Restriction: if `label` starts with a word character, the code before must
+//! ; not be a word character.
+//! ; Restriction: if `label` ends with a word character, the code after must not
+//! ; be a word character.
+//! abbreviation ::= label
+//!
+//! ; Note: `_` is not a word character, as it is used for emphasis.
+//! word ::= unicode_alphanumeric
+//! ```
+//!
+//! Abbreviations are defined with
+//! [abbreviation definitions][abbreviation_definition].
+//! They match whole words: `HTML` does not match in `XHTML` or `HTML5`.
+//! Labels of definitions match case-sensitively, unless
+//! [`abbreviation_ignore_case`][abbreviation_ignore_case] is on.
+//! When several labels match at a place, the longest one is used, so with
+//! both `HTML` and `HTML 5` defined, `HTML 5` matches as one abbreviation.
+//!
+//! Like [emoji][], abbreviations are handled afterwards: when everything is
+//! parsed, we look back at data to figure out if there were abbreviations.
+//! That means that abbreviations are not found in code (text) or HTML, and
+//! that character escapes and character references cannot be used in them.
+//! Abbreviations are also not found in links, images, GFM footnote calls, or
+//! other abbreviations.
+//!
+//! ## HTML
+//!
+//! Abbreviation relates to the `<abbr>` element in HTML.
+//! See [*§ 4.5.9 The `abbr` element*][html_abbr] in the HTML spec for more
+//! info.
+//! The title of the first definition with the label is used as the `title`
+//! attribute.
+//!
+//! mdast has no node for abbreviations: when turning markdown into mdast,
+//! they are text.
+//!
+//! ## Tokens
+//!
+//! *   [`Abbreviation`][Name::Abbreviation]
+//!
+//! ## References
+//!
+//! *   [*Abbreviations* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#abbr)
+//!
+//! [text]: crate::construct::text
+//! [abbreviation_definition]: crate::construct::abbreviation_definition
+//! [abbreviation_ignore_case]: crate::ParseOptions::abbreviation_ignore_case
+//! [emoji]: crate::construct::emoji
+//! [html_abbr]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-abbr-element
+
+use crate::event::{Event, Kind, Name};
+use crate::tokenizer::Tokenizer;
+use crate::util::{
+    char::{after_index, before_index},
+    slice::{Position, Slice},
+};
+use alloc::{string::String, vec::Vec};
+
+/// Resolve: postprocess text to find abbreviations.
+pub fn resolve(tokenizer: &mut Tokenizer) {
+    tokenizer.map.consume(&mut tokenizer.events);
+
+    let labels = &tokenizer.parse_state.abbreviations;
+    let ignore_case = tokenizer.parse_state.options.abbreviation_ignore_case;
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if matches!(
+            event.name,
+            Name::Abbreviation | Name::GfmFootnoteCall | Name::Image | Name::Link
+        ) {
+            if event.kind == Kind::Enter {
+                depth += 1;
+            } else {
+                depth -= 1;
+            }
+        } else if depth == 0 && event.kind == Kind::Exit && event.name == Name::Data {
+            let slice = Slice::from_position(
+                tokenizer.parse_state.bytes,
+                &Position::from_exit_event(&tokenizer.events, index),
+                tokenizer.parse_state.tab_size,
+            );
+            let value = slice.as_str();
+            let mut byte_index = 0;
+            let mut replace = Vec::new();
+            let mut point = tokenizer.events[index - 1].point.clone();
+            let start_index = point.index;
+            let mut min = 0;
+
+            while byte_index < value.len() {
+                if let Some(end) = find(
+                    tokenizer.parse_state.bytes,
+                    labels,
+                    value,
+                    start_index,
+                    byte_index,
+                    ignore_case,
+                ) {
+                    // If there is something between the last abbreviation
+                    // (or `min`) and this abbreviation.
+                    if min != byte_index {
+                        replace.push(Event {
+                            kind: Kind::Enter,
+                            name: Name::Data,
+                            point: point.clone(),
+                            link: None,
+                        });
+                        point = point.shift_to(
+                            tokenizer.parse_state.bytes,
+                            start_index + byte_index,
+                            tokenizer.parse_state.tab_size,
+                        );
+                        replace.push(Event {
+                            kind: Kind::Exit,
+                            name: Name::Data,
+                            point: point.clone(),
+                            link: None,
+                        });
+                    }
+
+                    let end_point = point.shift_to(
+                        tokenizer.parse_state.bytes,
+                        start_index + end,
+                        tokenizer.parse_state.tab_size,
+                    );
+
+                    // Add the abbreviation.
+                    replace.push(Event {
+                        kind: Kind::Enter,
+                        name: Name::Abbreviation,
+                        point: point.clone(),
+                        link: None,
+                    });
+                    replace.push(Event {
+                        kind: Kind::Enter,
+                        name: Name::Data,
+                        point: point.clone(),
+                        link: None,
+                    });
+                    replace.push(Event {
+                        kind: Kind::Exit,
+                        name: Name::Data,
+                        point: end_point.clone(),
+                        link: None,
+                    });
+                    replace.push(Event {
+                        kind: Kind::Exit,
+                        name: Name::Abbreviation,
+                        point: end_point.clone(),
+                        link: None,
+                    });
+                    point = end_point;
+                    min = end;
+                    byte_index = end;
+                    continue;
+                }
+
+                byte_index += value[byte_index..].chars().next().unwrap().len_utf8();
+            }
+
+            // If there was an abbreviation, and we have more bytes left.
+            if min != 0 && min < value.len() {
+                replace.push(Event {
+                    kind: Kind::Enter,
+                    name: Name::Data,
+                    point,
+                    link: None,
+                });
+                replace.push(Event {
+                    kind: Kind::Exit,
+                    name: Name::Data,
+                    point: event.point.clone(),
+                    link: None,
+                });
+            }
+
+            // If there were abbreviations.
+            if !replace.is_empty() {
+                tokenizer.map.add(index - 1, 2, replace);
+            }
+        }
+
+        index += 1;
+    }
+}
+
+/// Find the end of the longest abbreviation in `value` at `at`.
+///
+/// `offset` is where `value` starts in `bytes`, which is used to check what
+/// comes before and after.
+fn find(
+    bytes: &[u8],
+    labels: &[String],
+    value: &str,
+    offset: usize,
+    at: usize,
+    ignore_case: bool,
+) -> Option<usize> {
+    let before_is_word = is_word(before_index(bytes, offset + at));
+    let mut result = None;
+    let mut index = 0;
+
+    while index < labels.len() {
+        let label = &labels[index];
+
+        if !(before_is_word && is_word(label.chars().next())) {
+            if let Some(size) = match_size(&value[at..], label, ignore_case) {
+                let end = at + size;
+
+                if Some(end) > result
+                    && !(is_word(label.chars().last()) && is_word(after_index(bytes, offset + end)))
+                {
+                    result = Some(end);
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+/// Get the size of `label` at the start of `value`, if it is there.
+fn match_size(value: &str, label: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return if value.starts_with(label) {
+            Some(label.len())
+        } else {
+            None
+        };
+    }
+
+    let mut chars = value.char_indices();
+    let mut label_chars = label.chars();
+
+    loop {
+        match label_chars.next() {
+            None => return Some(chars.next().map_or(value.len(), |d| d.0)),
+            Some(expected) => match chars.next() {
+                Some((_, char)) if char.to_lowercase().eq(expected.to_lowercase()) => {}
+                _ => return None,
+            },
+        }
+    }
+}
+
+/// Check whether a char is a word character.
+fn is_word(char: Option<char>) -> bool {
+    char.map_or(false, char::is_alphanumeric)
+}
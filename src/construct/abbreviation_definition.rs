@@ -0,0 +1,339 @@
+//! Abbreviation definition occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Abbreviation definitions form with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! abbreviation_definition ::= '*' '[' label ']' ':' *space_or_tab [title] *space_or_tab
+//!
+//! ; Restriction: maximum `999` bytes.
+//! ; Restriction: must not start or end with `space_or_tab`.
+//! label ::= 1*(byte - eol - ']')
+//! title ::= 1*(byte - eol)
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//! It cannot interrupt a paragraph, so `*[HTML]: …` right after a line of a
+//! paragraph is part of that paragraph.
+//!
+//! The `label` part is literal: it is used as it is when looking for
+//! [abbreviations][abbreviation] in text, without parsing
+//! [character escapes][character_escape] or
+//! [character references][character_reference].
+//! The `title` part is interpreted as the [string][] content type, so those
+//! are allowed there.
+//!
+//! Definitions apply to the whole document, also to text before them.
+//! Multiple definitions with the same label are ignored: the first definition
+//! is preferred.
+//! A definition without title is allowed: abbreviations it matches are
+//! marked, but get no title.
+//!
+//! ## HTML
+//!
+//! Abbreviation definitions do not, on their own, relate to anything in HTML.
+//! When matched with an [abbreviation][], they together relate to the
+//! `<abbr>` element in HTML.
+//! See [*§ 4.5.9 The `abbr` element*][html_abbr] in the HTML spec for more
+//! info.
+//!
+//! ## Recommendation
+//!
+//! Place definitions at the end of a document.
+//!
+//! ## Tokens
+//!
+//! *   [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+//! *   [`AbbreviationDefinitionLabel`][Name::AbbreviationDefinitionLabel]
+//! *   [`AbbreviationDefinitionLabelMarker`][Name::AbbreviationDefinitionLabelMarker]
+//! *   [`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString]
+//! *   [`AbbreviationDefinitionMarker`][Name::AbbreviationDefinitionMarker]
+//! *   [`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle]
+//! *   [`DefinitionMarker`][Name::DefinitionMarker]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//!
+//! ## References
+//!
+//! *   [*Abbreviations* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#abbr)
+//!
+//! [flow]: crate::construct::flow
+//! [string]: crate::construct::string
+//! [abbreviation]: crate::construct::abbreviation
+//! [character_escape]: crate::construct::character_escape
+//! [character_reference]: crate::construct::character_reference
+//! [html_abbr]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-abbr-element
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{
+    constant::LINK_REFERENCE_SIZE_MAX,
+    slice::{Position, Slice},
+};
+
+/// At start of abbreviation definition.
+///
+/// ```markdown
+/// > | *[a]: b
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    // Do not interrupt paragraphs.
+    if tokenizer.parse_state.options.constructs.abbreviation && !tokenizer.interrupt {
+        tokenizer.enter(Name::AbbreviationDefinition);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::AbbreviationDefinitionBefore),
+                State::Nok,
+            );
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::AbbreviationDefinitionBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at `*`.
+///
+/// ```markdown
+/// > | *[a]: b
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'*') {
+        tokenizer.enter(Name::AbbreviationDefinitionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AbbreviationDefinitionMarker);
+        State::Next(StateName::AbbreviationDefinitionLabelBefore)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `*`, at `[`.
+///
+/// ```markdown
+/// > | *[a]: b
+///      ^
+/// ```
+pub fn label_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.enter(Name::AbbreviationDefinitionLabel);
+        tokenizer.enter(Name::AbbreviationDefinitionLabelMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::AbbreviationDefinitionLabelMarker);
+        State::Next(StateName::AbbreviationDefinitionLabelStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After `[`, at label.
+///
+/// ```markdown
+/// > | *[a]: b
+///       ^
+/// ```
+pub fn label_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\t' | b'\n' | b' ' | b']') => State::Nok,
+        Some(_) => {
+            tokenizer.enter(Name::AbbreviationDefinitionLabelString);
+            State::Retry(StateName::AbbreviationDefinitionLabelInside)
+        }
+    }
+}
+
+/// In label.
+///
+/// ```markdown
+/// > | *[a]: b
+///       ^
+/// ```
+pub fn label_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b']') if !matches!(tokenizer.previous, Some(b'\t' | b' ')) => {
+            tokenizer.tokenize_state.size = 0;
+            tokenizer.exit(Name::AbbreviationDefinitionLabelString);
+            tokenizer.tokenize_state.end = tokenizer.events.len() - 1;
+            tokenizer.enter(Name::AbbreviationDefinitionLabelMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::AbbreviationDefinitionLabelMarker);
+            tokenizer.exit(Name::AbbreviationDefinitionLabel);
+            State::Next(StateName::AbbreviationDefinitionLabelAfter)
+        }
+        None | Some(b'\n' | b']') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(_) => {
+            if tokenizer.tokenize_state.size < LINK_REFERENCE_SIZE_MAX {
+                tokenizer.tokenize_state.size += 1;
+                tokenizer.consume();
+                State::Next(StateName::AbbreviationDefinitionLabelInside)
+            } else {
+                tokenizer.tokenize_state.size = 0;
+                State::Nok
+            }
+        }
+    }
+}
+
+/// After label, at `:`.
+///
+/// ```markdown
+/// > | *[a]: b
+///         ^
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DefinitionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DefinitionMarker);
+        State::Next(StateName::AbbreviationDefinitionMarkerAfter)
+    } else {
+        tokenizer.tokenize_state.end = 0;
+        State::Nok
+    }
+}
+
+/// After `:`, at optional whitespace.
+///
+/// ```markdown
+/// > | *[a]: b
+///          ^
+/// ```
+pub fn marker_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::AbbreviationDefinitionTitleBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::AbbreviationDefinitionTitleBefore)
+    }
+}
+
+/// After whitespace, at optional title.
+///
+/// ```markdown
+/// > | *[a]: b
+///           ^
+/// ```
+pub fn title_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Retry(StateName::AbbreviationDefinitionAfter),
+        Some(_) => {
+            tokenizer.enter(Name::AbbreviationDefinitionTitle);
+            tokenizer.enter_link(
+                Name::Data,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::String,
+                },
+            );
+            State::Retry(StateName::AbbreviationDefinitionTitleInside)
+        }
+    }
+}
+
+/// In title.
+///
+/// ```markdown
+/// > | *[a]: b
+///           ^
+/// ```
+pub fn title_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::Data);
+            tokenizer.exit(Name::AbbreviationDefinitionTitle);
+            State::Retry(StateName::AbbreviationDefinitionAfter)
+        }
+        // Whitespace at the end is not part of the title.
+        Some(b'\t' | b' ') => {
+            tokenizer.check(
+                State::Next(StateName::AbbreviationDefinitionTitleEnd),
+                State::Next(StateName::AbbreviationDefinitionTitleWhitespace),
+            );
+            State::Retry(StateName::BlankLineStart)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::AbbreviationDefinitionTitleInside)
+        }
+    }
+}
+
+/// In whitespace in title, followed by more title.
+///
+/// ```markdown
+/// > | *[a]: b c
+///            ^
+/// ```
+pub fn title_whitespace(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.consume();
+        State::Next(StateName::AbbreviationDefinitionTitleWhitespace)
+    } else {
+        State::Retry(StateName::AbbreviationDefinitionTitleInside)
+    }
+}
+
+/// At whitespace after title.
+///
+/// ```markdown
+/// > | *[a]: b␠␠
+///            ^
+/// ```
+pub fn title_end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::Data);
+    tokenizer.exit(Name::AbbreviationDefinitionTitle);
+    tokenizer.attempt(
+        State::Next(StateName::AbbreviationDefinitionAfter),
+        State::Nok,
+    );
+    State::Retry(space_or_tab(tokenizer))
+}
+
+/// After abbreviation definition.
+///
+/// ```markdown
+/// > | *[a]: b
+///            ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::AbbreviationDefinition);
+
+    // Note: like with definitions, we don’t care about uniqueness.
+    tokenizer.tokenize_state.abbreviations.push(
+        Slice::from_position(
+            tokenizer.parse_state.bytes,
+            &Position::from_exit_event(&tokenizer.events, tokenizer.tokenize_state.end),
+            tokenizer.parse_state.tab_size,
+        )
+        .as_str()
+        .into(),
+    );
+
+    tokenizer.tokenize_state.end = 0;
+    tokenizer.interrupt = false;
+    State::Ok
+}
@@ -1,5 +1,5 @@
-//! Attention (emphasis, strong, optionally GFM strikethrough) occurs in the
-//! [text][] content type.
+//! Attention (emphasis, strong, optionally GFM strikethrough, highlight,
+//! subscript, and superscript) occurs in the [text][] content type.
 //!
 //! ## Grammar
 //!
@@ -9,6 +9,8 @@
 //! ```bnf
 //! attention_sequence ::= 1*'*' | 1*'_'
 //! gfm_attention_sequence ::= 1*'~'
+//! highlight_sequence ::= 1*'='
+//! superscript_sequence ::= 1*'^'
 //! ```
 //!
 //! Sequences are matched together to form attention based on which character
@@ -28,6 +30,16 @@
 //! When tilde sequences match, they together relate to the `<del>` element in
 //! HTML.
 //! See [*§ 4.7.2 The `del` element*][html-del] in the HTML spec for more info.
+//! When subscript is on, tilde sequences of one marker instead relate to the
+//! `<sub>` element.
+//! When superscript is on, caret sequences relate to the `<sup>` element.
+//! See [*§ 4.5.19 The `sub` and `sup` elements*][html-sub-sup] in the HTML
+//! spec for more info.
+//!
+//! When equals sequences match, they together relate to the `<mark>` element
+//! in HTML.
+//! See [*§ 4.5.23 The `mark` element*][html-mark] in the HTML spec for more
+//! info.
 //!
 //! ## Recommendation
 //!
@@ -39,6 +51,11 @@
 //! Because underscores also frequently occur in natural language inside words,
 //! while asterisks typically never do, `CommonMark` prohibits underscore
 //! sequences from opening or closing when *inside* a word.
+//! So, `snake_case_words` never forms emphasis, but `_snake_ case` does.
+//! To never form emphasis or strong with underscores, pass
+//! [`attention_underscore: false`][crate::ParseOptions::attention_underscore].
+//! To let underscores form them inside words too, like asterisks, pass
+//! [`attention_underscore_intraword: true`][crate::ParseOptions::attention_underscore_intraword].
 //!
 //! Because asterisks can be used to form the most markdown constructs, using
 //! them has the added benefit of making it easier to gloss over markdown: you
@@ -49,6 +66,13 @@
 //! While `github.com` allows single tildes too, it technically prohibits it in
 //! their spec.
 //!
+//! Highlight only forms with two markers (`==a==`): other sizes are data.
+//!
+//! Subscript and superscript only form with one marker (`~a~`, `^a^`), and,
+//! like in Pandoc, when there is no whitespace between them.
+//! When both subscript and strikethrough are on, one tilde is subscript and
+//! two tildes are strikethrough.
+//!
 //! ## Tokens
 //!
 //! *   [`Emphasis`][Name::Emphasis]
@@ -57,9 +81,18 @@
 //! *   [`GfmStrikethrough`][Name::GfmStrikethrough]
 //! *   [`GfmStrikethroughSequence`][Name::GfmStrikethroughSequence]
 //! *   [`GfmStrikethroughText`][Name::GfmStrikethroughText]
+//! *   [`Highlight`][Name::Highlight]
+//! *   [`HighlightSequence`][Name::HighlightSequence]
+//! *   [`HighlightText`][Name::HighlightText]
 //! *   [`Strong`][Name::Strong]
 //! *   [`StrongSequence`][Name::StrongSequence]
 //! *   [`StrongText`][Name::StrongText]
+//! *   [`Subscript`][Name::Subscript]
+//! *   [`SubscriptSequence`][Name::SubscriptSequence]
+//! *   [`SubscriptText`][Name::SubscriptText]
+//! *   [`Superscript`][Name::Superscript]
+//! *   [`SuperscriptSequence`][Name::SuperscriptSequence]
+//! *   [`SuperscriptText`][Name::SuperscriptText]
 //!
 //! > 👉 **Note**: while parsing, [`AttentionSequence`][Name::AttentionSequence]
 //! > is used, which is later compiled away.
@@ -70,11 +103,14 @@
 //! *   [`micromark-extension-gfm-strikethrough`](https://github.com/micromark/micromark-extension-gfm-strikethrough)
 //! *   [*§ 6.2 Emphasis and strong emphasis* in `CommonMark`](https://spec.commonmark.org/0.31/#emphasis-and-strong-emphasis)
 //! *   [*§ 6.5 Strikethrough (extension)* in `GFM`](https://github.github.com/gfm/#strikethrough-extension-)
+//! *   [*Superscripts and subscripts* in Pandoc](https://pandoc.org/MANUAL.html#superscripts-and-subscripts)
 //!
 //! [text]: crate::construct::text
 //! [html-em]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-em-element
 //! [html-strong]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-strong-element
 //! [html-del]: https://html.spec.whatwg.org/multipage/edits.html#the-del-element
+//! [html-mark]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-mark-element
+//! [html-sub-sup]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-sub-and-sup-elements
 
 use crate::event::{Event, Kind, Name, Point};
 use crate::resolve::Name as ResolveName;
@@ -85,7 +121,15 @@ use crate::util::char::{
     after_index as char_after_index, before_index as char_before_index, classify_opt,
     Kind as CharacterKind,
 };
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+/// Kind of closing sequence, used to remember where to stop looking for
+/// openers (see [`resolve`]).
+///
+/// Closers of the same kind match the same openers: they have the same
+/// marker, are in the same balanced events, and the rules that use their size
+/// and whether they can open give the same results.
+type CloserKind = (Option<usize>, u8, bool, usize);
 
 /// Attentention sequence that we can take markers from.
 #[derive(Debug)]
@@ -108,6 +152,11 @@ struct Sequence {
     open: bool,
     /// Whether this sequence can close attention.
     close: bool,
+    /// Index of the sequence before this one that could still open.
+    ///
+    /// Sequences between a matched opener and closer can no longer open, so
+    /// they are skipped.
+    previous: Option<usize>,
 }
 
 /// At start of attention.
@@ -119,9 +168,15 @@ struct Sequence {
 pub fn start(tokenizer: &mut Tokenizer) -> State {
     // Emphasis/strong:
     if (tokenizer.parse_state.options.constructs.attention
-        && matches!(tokenizer.current, Some(b'*' | b'_')))
-        // GFM strikethrough:
-        || (tokenizer.parse_state.options.constructs.gfm_strikethrough && tokenizer.current == Some(b'~'))
+        && (tokenizer.current == Some(b'*')
+            || (tokenizer.parse_state.options.attention_underscore
+                && tokenizer.current == Some(b'_'))))
+        // GFM strikethrough, subscript:
+        || ((tokenizer.parse_state.options.constructs.gfm_strikethrough || tokenizer.parse_state.options.constructs.subscript) && tokenizer.current == Some(b'~'))
+        // Highlight:
+        || (tokenizer.parse_state.options.constructs.highlight && tokenizer.current == Some(b'='))
+        // Superscript:
+        || (tokenizer.parse_state.options.constructs.superscript && tokenizer.current == Some(b'^'))
     {
         tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
         tokenizer.enter(Name::AttentionSequence);
@@ -153,6 +208,12 @@ pub fn inside(tokenizer: &mut Tokenizer) -> State {
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     // Find all sequences, gather info about them.
     let mut sequences = get_sequences(tokenizer);
+    // Like the “openers bottom” in the `CommonMark` reference parser: for
+    // each kind of closer, the index of the last closer of that kind that
+    // did not find an opener.
+    // Openers before it were tried, and do not match, so later closers of
+    // the same kind don’t walk back over them again.
+    let mut bottoms: BTreeMap<CloserKind, usize> = BTreeMap::new();
 
     // Now walk through them and match them.
     let mut close = 0;
@@ -163,14 +224,23 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
 
         // Find a sequence that can close.
         if sequence_close.close {
-            let mut open = close;
+            let kind = closer_kind(&sequences[close]);
+            let bottom = bottoms.get(&kind).copied().unwrap_or(0);
+            let mut previous = sequence_close.previous;
+            let mut found = None;
 
             // Now walk back to find an opener.
-            while open > 0 {
-                open -= 1;
-
+            while let Some(open) = previous {
                 let sequence_open = &sequences[open];
 
+                // Openers here, and before here, were already tried, or are
+                // outside the balanced events the closer is in.
+                if open < bottom || Some(sequence_open.index) < kind.0 {
+                    break;
+                }
+
+                previous = sequence_open.previous;
+
                 // An opener matching our closer:
                 if sequence_open.open
                     && sequence_close.marker == sequence_open.marker
@@ -187,25 +257,76 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                         continue;
                     }
 
-                    // For GFM strikethrough:
+                    // For GFM strikethrough and subscript:
                     // * both sequences must have the same size
                     // * more than 2 markers don’t work
-                    // * one marker is prohibited by the spec, but supported by GH
+                    // * one marker is subscript, if on; otherwise, it’s
+                    //   prohibited by the spec, but supported by GH
                     if sequence_close.marker == b'~'
                         && (sequence_close.size != sequence_open.size
                             || sequence_close.size > 2
-                            || sequence_close.size == 1
-                                && !tokenizer.parse_state.options.gfm_strikethrough_single_tilde)
+                            || (sequence_close.size == 2
+                                && !tokenizer.parse_state.options.constructs.gfm_strikethrough)
+                            || (sequence_close.size == 1
+                                && !tokenizer.parse_state.options.constructs.subscript
+                                && !(tokenizer.parse_state.options.constructs.gfm_strikethrough
+                                    && tokenizer
+                                        .parse_state
+                                        .options
+                                        .gfm_strikethrough_single_tilde)))
                     {
                         continue;
                     }
 
-                    // We found a match!
-                    next_index = match_sequences(tokenizer, &mut sequences, open, close);
+                    // For superscript, both sequences must have one marker.
+                    if sequence_close.marker == b'^'
+                        && (sequence_close.size != 1 || sequence_open.size != 1)
+                    {
+                        continue;
+                    }
 
+                    // Subscript and superscript cannot contain whitespace.
+                    if (sequence_close.marker == b'^'
+                        || (sequence_close.marker == b'~'
+                            && sequence_close.size == 1
+                            && tokenizer.parse_state.options.constructs.subscript))
+                        && tokenizer.parse_state.bytes
+                            [sequence_open.end_point.index..sequence_close.start_point.index]
+                            .iter()
+                            .any(|byte| matches!(byte, b'\t' | b'\n' | b'\r' | b' '))
+                    {
+                        continue;
+                    }
+
+                    // For highlight, both sequences must have two markers.
+                    if sequence_close.marker == b'='
+                        && (sequence_close.size != 2 || sequence_open.size != 2)
+                    {
+                        continue;
+                    }
+
+                    // We found a match!
+                    found = Some(open);
                     break;
                 }
             }
+
+            if let Some(open) = found {
+                next_index = match_sequences(tokenizer, &mut sequences, open, close);
+
+                // If the opener remains, it is smaller now, so it might
+                // match closers that it did not match before.
+                if sequences[open].size > 0 {
+                    let entries = bottoms
+                        .range_mut((kind.0, 0, false, 0)..=(kind.0, u8::MAX, true, usize::MAX));
+
+                    for (_, bottom) in entries {
+                        *bottom = (*bottom).min(open);
+                    }
+                }
+            } else {
+                bottoms.insert(kind, close);
+            }
         }
 
         close = next_index;
@@ -215,8 +336,10 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut index = 0;
     while index < sequences.len() {
         let sequence = &sequences[index];
-        tokenizer.events[sequence.index].name = Name::Data;
-        tokenizer.events[sequence.index + 1].name = Name::Data;
+        if sequence.size > 0 {
+            tokenizer.events[sequence.index].name = Name::Data;
+            tokenizer.events[sequence.index + 1].name = Name::Data;
+        }
         index += 1;
     }
 
@@ -243,18 +366,20 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                 let before = classify_opt(before_char);
                 let after_char = char_after_index(tokenizer.parse_state.bytes, exit.point.index);
                 let after = classify_opt(after_char);
+                // For regular attention markers (not strikethrough,
+                // highlight, subscript, or superscript), the other attention
+                // markers can be used around them.
+                let regular = marker == b'*' || marker == b'_';
                 let open = after == CharacterKind::Other
                     || (after == CharacterKind::Punctuation && before != CharacterKind::Other)
-                    // For regular attention markers (not strikethrough), the
-                    // other attention markers can be used around them
-                    || (marker != b'~' && matches!(after_char, Some('*' | '_')))
-                    || (marker != b'~' && tokenizer.parse_state.options.constructs.gfm_strikethrough && matches!(after_char, Some('~')));
+                    || (regular && is_marker(tokenizer, after_char));
                 let close = before == CharacterKind::Other
                     || (before == CharacterKind::Punctuation && after != CharacterKind::Other)
-                    || (marker != b'~' && matches!(before_char, Some('*' | '_')))
-                    || (marker != b'~'
-                        && tokenizer.parse_state.options.constructs.gfm_strikethrough
-                        && matches!(before_char, Some('~')));
+                    || (regular && is_marker(tokenizer, before_char));
+                // Underscores can’t open or close inside words, unless turned
+                // on.
+                let intraword =
+                    marker != b'_' || tokenizer.parse_state.options.attention_underscore_intraword;
 
                 sequences.push(Sequence {
                     index,
@@ -262,17 +387,22 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
                     start_point: enter.point.clone(),
                     end_point: exit.point.clone(),
                     size: exit.point.index - enter.point.index,
-                    open: if marker == b'_' {
-                        open && (before != CharacterKind::Other || !close)
-                    } else {
+                    open: if intraword {
                         open
-                    },
-                    close: if marker == b'_' {
-                        close && (after != CharacterKind::Other || !open)
                     } else {
+                        open && (before != CharacterKind::Other || !close)
+                    },
+                    close: if intraword {
                         close
+                    } else {
+                        close && (after != CharacterKind::Other || !open)
                     },
                     marker,
+                    previous: if sequences.is_empty() {
+                        None
+                    } else {
+                        Some(sequences.len() - 1)
+                    },
                 });
             }
         } else if enter.kind == Kind::Enter {
@@ -287,18 +417,47 @@ fn get_sequences(tokenizer: &mut Tokenizer) -> Vec<Sequence> {
     sequences
 }
 
+/// Get the kind of a closing sequence.
+fn closer_kind(sequence: &Sequence) -> CloserKind {
+    (
+        sequence.stack.last().copied(),
+        sequence.marker,
+        sequence.open,
+        // Regular attention uses the size in the “rule of 3”, the others
+        // only match one or two markers.
+        if sequence.marker == b'*' || sequence.marker == b'_' {
+            sequence.size % 3
+        } else {
+            sequence.size.min(3)
+        },
+    )
+}
+
+/// Check whether `char` is an attention marker that is turned on.
+fn is_marker(tokenizer: &Tokenizer, char: Option<char>) -> bool {
+    let constructs = &tokenizer.parse_state.options.constructs;
+
+    match char {
+        Some('*' | '_') => true,
+        Some('~') => constructs.gfm_strikethrough || constructs.subscript,
+        Some('=') => constructs.highlight,
+        Some('^') => constructs.superscript,
+        _ => false,
+    }
+}
+
 /// Match two sequences.
 #[allow(clippy::too_many_lines)]
 fn match_sequences(
     tokenizer: &mut Tokenizer,
-    sequences: &mut Vec<Sequence>,
+    sequences: &mut [Sequence],
     open: usize,
     close: usize,
 ) -> usize {
     // Where to move to next.
     // Stay on this closing sequence for the next iteration: it
     // might close more things.
-    // It’s changed if sequences are removed.
+    // It’s changed if it is fully used.
     let mut next = close;
 
     // Number of markers to use from the sequence.
@@ -316,23 +475,38 @@ fn match_sequences(
     // <em>a <strong>b</em> c</strong>
     // ```
     //
-    // Do that by marking everything between it as no longer
-    // possible to open anything.
-    // Theoretically we should mark as `close: false` too, but
+    // Do that by skipping everything between when looking for openers
+    // (see `previous` below).
+    // Theoretically we should prevent them from closing too, but
     // we don’t look for closers backwards, so it’s not needed.
-    let mut between = open + 1;
-
-    while between < close {
-        sequences[between].open = false;
-        between += 1;
-    }
 
-    let (group_name, seq_name, text_name) = if sequences[open].marker == b'~' {
+    let (group_name, seq_name, text_name) = if sequences[open].marker == b'~'
+        && take == 1
+        && tokenizer.parse_state.options.constructs.subscript
+    {
+        (
+            Name::Subscript,
+            Name::SubscriptSequence,
+            Name::SubscriptText,
+        )
+    } else if sequences[open].marker == b'^' {
+        (
+            Name::Superscript,
+            Name::SuperscriptSequence,
+            Name::SuperscriptText,
+        )
+    } else if sequences[open].marker == b'~' {
         (
             Name::GfmStrikethrough,
             Name::GfmStrikethroughSequence,
             Name::GfmStrikethroughText,
         )
+    } else if sequences[open].marker == b'=' {
+        (
+            Name::Highlight,
+            Name::HighlightSequence,
+            Name::HighlightText,
+        )
     } else if take == 1 {
         (Name::Emphasis, Name::EmphasisSequence, Name::EmphasisText)
     } else {
@@ -415,23 +589,33 @@ fn match_sequences(
         ],
     );
 
+    // Where to look for openers from after the match.
+    let previous = if sequences[open].size == 0 {
+        sequences[open].previous
+    } else {
+        Some(open)
+    };
+
     // Remove closing sequence if fully used.
     if sequences[close].size == 0 {
-        sequences.remove(close);
         tokenizer.map.add(close_index, 2, vec![]);
+        next += 1;
+
+        if next < sequences.len() {
+            sequences[next].previous = previous;
+        }
     } else {
         // Shift remaining closing sequence forward.
         // Do it here because a sequence can open and close different
         // other sequences, and the remainder can be on any side or
         // somewhere in the middle.
         tokenizer.events[close_index].point = sequences[close].start_point.clone();
+        sequences[close].previous = previous;
     }
 
+    // Remove opening sequence if fully used.
     if sequences[open].size == 0 {
-        sequences.remove(open);
         tokenizer.map.add(open_index, 2, vec![]);
-        // Everything shifts one to the left, account for it in next iteration.
-        next -= 1;
     } else {
         tokenizer.events[open_index + 1].point = sequences[open].end_point.clone();
     }
@@ -25,6 +25,10 @@
 //! The maximum allowed size of a domain is `63` (inclusive), which is defined
 //! in [`AUTOLINK_DOMAIN_SIZE_MAX`][].
 //!
+//! Which schemes are allowed can be configured with
+//! [`autolink_protocols`][crate::ParseOptions::autolink_protocols]: by
+//! default, all are.
+//!
 //! The grammar for autolinks is quite strict and prohibits the use of ASCII control
 //! characters or spaces.
 //! To use non-ascii characters and otherwise impossible characters in URLs,
@@ -84,6 +88,9 @@
 //! <p><a href="#"></a><a href="https://example.com">https://example.com</a></p>
 //! ```
 //!
+//! Pass [`flatten_autolinks`][flatten_autolinks] to compile autolinks in
+//! links as text instead.
+//!
 //! ## HTML
 //!
 //! Autolinks relate to the `<a>` element in HTML.
@@ -119,6 +126,7 @@
 //! [autolink_scheme_size_max]: crate::util::constant::AUTOLINK_SCHEME_SIZE_MAX
 //! [autolink_domain_size_max]: crate::util::constant::AUTOLINK_DOMAIN_SIZE_MAX
 //! [sanitize_uri]: crate::util::sanitize_uri
+//! [flatten_autolinks]: crate::CompileOptions::flatten_autolinks
 //! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
 
 use crate::event::Name;
@@ -198,9 +206,14 @@ pub fn scheme_or_email_atext(tokenizer: &mut Tokenizer) -> State {
 pub fn scheme_inside_or_email_atext(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         Some(b':') => {
-            tokenizer.consume();
             tokenizer.tokenize_state.size = 0;
-            State::Next(StateName::AutolinkUrlInside)
+
+            if protocol_allowed(tokenizer) {
+                tokenizer.consume();
+                State::Next(StateName::AutolinkUrlInside)
+            } else {
+                State::Nok
+            }
         }
         // ASCII alphanumeric and `+`, `-`, and `.`.
         Some(b'+' | b'-' | b'.' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
@@ -356,3 +369,29 @@ pub fn email_value(tokenizer: &mut Tokenizer) -> State {
         }
     }
 }
+
+/// Check whether the protocol before the current `:` is allowed.
+///
+/// ```markdown
+/// > | a<https://example.com>b
+///            ^
+/// ```
+fn protocol_allowed(tokenizer: &Tokenizer) -> bool {
+    if let Some(ref protocols) = tokenizer.parse_state.options.autolink_protocols {
+        let start = tokenizer.events[tokenizer.events.len() - 1].point.index;
+        let protocol = &tokenizer.parse_state.bytes[start..tokenizer.point.index];
+        let mut index = 0;
+
+        while index < protocols.len() {
+            if protocols[index].as_bytes().eq_ignore_ascii_case(protocol) {
+                return true;
+            }
+
+            index += 1;
+        }
+
+        false
+    } else {
+        true
+    }
+}
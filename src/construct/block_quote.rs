@@ -12,6 +12,8 @@
 //!
 //! Further lines that are not prefixed with `block_quote_cont` cause the block
 //! quote to be exited, except when those lines are lazy continuation.
+//! Lazy continuation in block quotes can be turned off with
+//! [`block_quote_lazy`][crate::ParseOptions::block_quote_lazy].
 //! Like so many things in markdown, block quotes too are complex.
 //! See [*§ Phase 1: block structure* in `CommonMark`][commonmark-block] for
 //! more on parsing details.
@@ -50,7 +52,6 @@ use crate::construct::partial_space_or_tab::space_or_tab_min_max;
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::TAB_SIZE;
 
 /// Start of block quote.
 ///
@@ -83,7 +84,7 @@ pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
             tokenizer,
             1,
             if tokenizer.parse_state.options.constructs.code_indented {
-                TAB_SIZE - 1
+                tokenizer.parse_state.tab_size - 1
             } else {
                 usize::MAX
             },
@@ -23,6 +23,9 @@
 //! fine but are not rendered as their corresponding character.
 //! They are instead replaced by a U+FFFD REPLACEMENT CHARACTER (`�`).
 //! See [`decode_numeric`][decode_numeric] for more info.
+//! Such references are reported as `invalid-character-reference` warnings,
+//! which you can get with
+//! [`to_html_with_diagnostics()`][crate::to_html_with_diagnostics].
 //!
 //! To escape ASCII punctuation characters, use the terser
 //! [character escape][character_escape] construct instead (as in, `\&`).
@@ -68,13 +71,16 @@
 //! [character_references]: crate::util::constant::CHARACTER_REFERENCES
 //! [html]: https://html.spec.whatwg.org/multipage/parsing.html#character-reference-state
 
-use crate::event::Name;
+use crate::event::{Kind, Name};
+use crate::message;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
+use crate::unist;
 use crate::util::{
-    character_reference::{decode_named, value_max, value_test},
+    character_reference::{decode_named, numeric_allowed, value_max, value_test},
     slice::Slice,
 };
+use alloc::{boxed::Box, format};
 
 /// Start of character reference.
 ///
@@ -162,27 +168,62 @@ pub fn numeric(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn value(tokenizer: &mut Tokenizer) -> State {
     if matches!(tokenizer.current, Some(b';')) && tokenizer.tokenize_state.size > 0 {
-        // Named.
-        if tokenizer.tokenize_state.marker == b'&' {
-            // Guaranteed to be valid ASCII bytes.
-            let slice = Slice::from_indices(
-                tokenizer.parse_state.bytes,
-                tokenizer.point.index - tokenizer.tokenize_state.size,
-                tokenizer.point.index,
-            );
+        // Guaranteed to be valid ASCII bytes.
+        let slice = Slice::from_indices(
+            tokenizer.parse_state.bytes,
+            tokenizer.point.index - tokenizer.tokenize_state.size,
+            tokenizer.point.index,
+        );
+        let marker = tokenizer.tokenize_state.marker;
 
-            if decode_named(slice.as_str(), true).is_none() {
-                tokenizer.tokenize_state.marker = 0;
-                tokenizer.tokenize_state.size = 0;
-                return State::Nok;
-            }
+        // Named.
+        if marker == b'&' && decode_named(slice.as_str(), true).is_none() {
+            tokenizer.tokenize_state.marker = 0;
+            tokenizer.tokenize_state.size = 0;
+            return State::Nok;
         }
 
+        // Numeric: sizes are limited, so these fit.
+        let allowed = marker == b'&'
+            || numeric_allowed(
+                u32::from_str_radix(slice.as_str(), if marker == b'x' { 16 } else { 10 }).unwrap(),
+            );
+
         tokenizer.exit(Name::CharacterReferenceValue);
         tokenizer.enter(Name::CharacterReferenceMarkerSemi);
         tokenizer.consume();
         tokenizer.exit(Name::CharacterReferenceMarkerSemi);
         tokenizer.exit(Name::CharacterReference);
+
+        if !allowed {
+            let mut index = tokenizer.events.len() - 1;
+            while !(tokenizer.events[index].kind == Kind::Enter
+                && tokenizer.events[index].name == Name::CharacterReference)
+            {
+                index -= 1;
+            }
+
+            let start = &tokenizer.events[index].point;
+            let source = Slice::from_indices(
+                tokenizer.parse_state.bytes,
+                start.index,
+                tokenizer.point.index,
+            );
+
+            tokenizer.tokenize_state.messages.push(message::Message {
+                place: Some(Box::new(message::Place::Position(unist::Position {
+                    start: start.to_unist(),
+                    end: tokenizer.point.to_unist(),
+                }))),
+                reason: format!(
+                    "Unexpected character reference `{}` to a disallowed code point, expected an allowed character (it is replaced by U+FFFD REPLACEMENT CHARACTER)",
+                    source.as_str()
+                ),
+                rule_id: Box::new("invalid-character-reference".into()),
+                source: Box::new("markdown-rs".into()),
+            });
+        }
+
         tokenizer.tokenize_state.marker = 0;
         tokenizer.tokenize_state.size = 0;
         return State::Ok;
@@ -16,6 +16,14 @@
 //! As this construct occurs in flow, like all flow constructs, it must be
 //! followed by an eol (line ending) or eof (end of file).
 //!
+//! The `4` is the tab size, which can be configured with
+//! [`tab_size`][crate::ParseOptions::tab_size].
+//!
+//! When this construct is turned off, indented lines are not dropped: the
+//! indentation is ignored, so the line forms a paragraph (or continues one),
+//! or another construct (such as a heading), also in list items, no matter
+//! how deep the indent.
+//!
 //! In markdown, it is also possible to use [code (text)][raw_text] in the
 //! [text][] content type.
 //! It is also possible to create code with the [code (fenced)][raw_flow]
@@ -57,7 +65,6 @@ use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max}
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::TAB_SIZE;
 
 /// Start of code (indented).
 ///
@@ -77,7 +84,11 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
     {
         tokenizer.enter(Name::CodeIndented);
         tokenizer.attempt(State::Next(StateName::CodeIndentedAtBreak), State::Nok);
-        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            tokenizer.parse_state.tab_size,
+            tokenizer.parse_state.tab_size,
+        ))
     } else {
         State::Nok
     }
@@ -157,7 +168,11 @@ pub fn further_start(tokenizer: &mut Tokenizer) -> State {
         State::Next(StateName::CodeIndentedFurtherStart)
     } else {
         tokenizer.attempt(State::Ok, State::Next(StateName::CodeIndentedFurtherBegin));
-        State::Retry(space_or_tab_min_max(tokenizer, TAB_SIZE, TAB_SIZE))
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            tokenizer.parse_state.tab_size,
+            tokenizer.parse_state.tab_size,
+        ))
     }
 }
 
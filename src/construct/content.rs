@@ -64,7 +64,7 @@ pub fn chunk_inside(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         None | Some(b'\n') => {
             tokenizer.exit(Name::Content);
-            tokenizer.register_resolver_before(ResolveName::Content);
+            tokenizer.register_resolver(ResolveName::Content);
             // You’d be interrupting.
             tokenizer.interrupt = true;
             State::Ok
@@ -182,7 +182,7 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Result<Option<Subresult>, message::
     let result = subtokenize(
         &mut tokenizer.events,
         tokenizer.parse_state,
-        &Some(Content::Content),
+        Some(&Content::Content),
     )?;
 
     Ok(Some(result))
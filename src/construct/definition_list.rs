@@ -0,0 +1,470 @@
+//! Definition list occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Definition list forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: must come after a paragraph (the term) or another description.
+//! ; Restriction: the first line after the marker must not be blank.
+//! ; Restriction: if the first line after the marker starts with `5(space_or_tab)`,
+//! ; only the first `space_or_tab` is part of the start.
+//! description_start ::= ':' 1*4 space_or_tab
+//!
+//! ; Restriction: blank line allowed.
+//! ; Restriction: if not blank, the line must be indented, exactly `n` times.
+//! description_cont ::= [n(space_or_tab)]
+//! ```
+//!
+//! A description is a container, like a [list item][list_item], and is
+//! continued like one: further lines must be indented to where the content of
+//! its first line starts, except when those lines are lazy continuation or
+//! blank.
+//!
+//! The paragraph right before a description is its term.
+//! That paragraph can be followed by a blank line.
+//! A term can have several descriptions, each starting with a marker:
+//!
+//! ```markdown
+//! Mercury
+//! : The first planet.
+//! : Named after a Roman god.
+//!
+//! Venus
+//! : The second planet.
+//! ```
+//!
+//! Terms and descriptions next to each other, with only blank lines between
+//! them, form one definition list.
+//! As a description can be continued lazily, a term must be preceded by a
+//! blank line to not be part of the description before it.
+//!
+//! Definition lists are either loose or tight, like lists.
+//! A definition list is loose if there is a blank line before one of its
+//! descriptions, or between two things directly in one of its descriptions.
+//! Blank lines before terms do not make a list loose.
+//! When loose, the content of descriptions is wrapped in paragraphs in HTML.
+//!
+//! ## HTML
+//!
+//! Definition list relates to the `<dl>`, `<dt>`, and `<dd>` elements in
+//! HTML.
+//! See [*§ 4.4.9 The `dl` element*][html_dl],
+//! [*§ 4.4.10 The `dt` element*][html_dt], and
+//! [*§ 4.4.11 The `dd` element*][html_dd] in the HTML spec for more info.
+//!
+//! mdast has no nodes for definition lists: when turning markdown into
+//! mdast, terms become paragraphs, and the content of descriptions is added
+//! to the parent of the list.
+//!
+//! ## Recommendation
+//!
+//! Use a single space after a marker.
+//! Never use lazy continuation.
+//!
+//! ## Tokens
+//!
+//! *   [`DefinitionList`][Name::DefinitionList]
+//! *   [`DefinitionListDescription`][Name::DefinitionListDescription]
+//! *   [`DefinitionListDescriptionMarker`][Name::DefinitionListDescriptionMarker]
+//! *   [`DefinitionListDescriptionPrefix`][Name::DefinitionListDescriptionPrefix]
+//! *   [`DefinitionListTerm`][Name::DefinitionListTerm]
+//!
+//! ## References
+//!
+//! *   [*Definition lists* in Pandoc](https://pandoc.org/MANUAL.html#definition-lists)
+//! *   [*Definition lists* in PHP Markdown Extra](https://michelf.ca/projects/php-markdown/extra/#def-list)
+//!
+//! [document]: crate::construct::document
+//! [list_item]: crate::construct::list_item
+//! [html_dl]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dl-element
+//! [html_dt]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dt-element
+//! [html_dd]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-dd-element
+
+use crate::construct::partial_space_or_tab::space_or_tab_min_max;
+use crate::event::{Kind, Name};
+use crate::resolve::Name as ResolveName;
+use crate::state::{Name as StateName, State};
+use crate::subtokenize::Subresult;
+use crate::tokenizer::{Container, Tokenizer};
+use crate::util::{
+    skip,
+    slice::{Position, Slice},
+};
+use alloc::{vec, vec::Vec};
+
+/// Things that can occur between a term and descriptions.
+const BETWEEN: [Name; 4] = [
+    Name::BlankLineEnding,
+    Name::BlockQuotePrefix,
+    Name::LineEnding,
+    Name::SpaceOrTab,
+];
+
+/// Start of definition list description.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.definition_list
+        && after_term_or_description(tokenizer)
+    {
+        tokenizer.enter(Name::DefinitionListDescription);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DefinitionListBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DefinitionListBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at definition list description prefix.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DefinitionListDescriptionPrefix);
+        tokenizer.enter(Name::DefinitionListDescriptionMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DefinitionListDescriptionMarker);
+        State::Next(StateName::DefinitionListMarkerAfter)
+    } else {
+        State::Nok
+    }
+}
+
+/// After definition list description marker.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///      ^
+/// ```
+pub fn marker_after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.check(
+        State::Nok,
+        State::Next(StateName::DefinitionListMarkerAfterFilled),
+    );
+    State::Retry(StateName::BlankLineStart)
+}
+
+/// After definition list description marker.
+///
+/// The marker is not followed by a blank line.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///      ^
+/// ```
+pub fn marker_after_filled(tokenizer: &mut Tokenizer) -> State {
+    // Attempt to parse up to the largest allowed indent, `nok` if there is more whitespace.
+    tokenizer.attempt(
+        State::Next(StateName::DefinitionListAfter),
+        State::Next(StateName::DefinitionListPrefixOther),
+    );
+    State::Retry(StateName::DefinitionListWhitespace)
+}
+
+/// After marker, at whitespace.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///      ^
+/// ```
+pub fn whitespace(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::DefinitionListWhitespaceAfter),
+        State::Nok,
+    );
+    State::Retry(space_or_tab_min_max(
+        tokenizer,
+        1,
+        tokenizer.parse_state.tab_size,
+    ))
+}
+
+/// After acceptable whitespace.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///       ^
+/// ```
+pub fn whitespace_after(tokenizer: &mut Tokenizer) -> State {
+    if let Some(b'\t' | b' ') = tokenizer.current {
+        State::Nok
+    } else {
+        State::Ok
+    }
+}
+
+/// After marker, followed by more indent than needed.
+///
+/// ```markdown
+///   | a
+/// > | :      b
+///      ^
+/// ```
+pub fn prefix_other(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'\t' | b' ') => {
+            tokenizer.enter(Name::SpaceOrTab);
+            tokenizer.consume();
+            tokenizer.exit(Name::SpaceOrTab);
+            State::Next(StateName::DefinitionListAfter)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// After definition list description prefix.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///       ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    let start = skip::to_back(
+        &tokenizer.events,
+        tokenizer.events.len() - 1,
+        &[Name::DefinitionListDescription],
+    );
+    let prefix = Slice::from_position(
+        tokenizer.parse_state.bytes,
+        &Position {
+            start: &tokenizer.events[start].point,
+            end: &tokenizer.point,
+        },
+        tokenizer.parse_state.tab_size,
+    )
+    .len();
+
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued]
+        .size = prefix;
+
+    tokenizer.exit(Name::DefinitionListDescriptionPrefix);
+    tokenizer.register_resolver(ResolveName::DefinitionList);
+    State::Ok
+}
+
+/// Check whether a description can start here: after a paragraph (its
+/// term), or after another description.
+///
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// > | : c
+///     ^
+/// ```
+fn after_term_or_description(tokenizer: &Tokenizer) -> bool {
+    let stack = &tokenizer.tokenize_state.document_container_stack;
+    let continued = tokenizer.tokenize_state.document_continued;
+
+    // Not all existing containers were continued: the first of those is what
+    // comes before, which was swapped to the end of the stack.
+    if continued + 1 < stack.len() {
+        return stack[stack.len() - 1].kind == Container::DefinitionListDescription;
+    }
+
+    // Otherwise, look at what came before in flow, which must be a
+    // paragraph, optionally followed by blank lines.
+    let child = tokenizer.tokenize_state.document_child.as_ref().unwrap();
+    let events = &child.events;
+
+    if events.is_empty() {
+        return false;
+    }
+
+    let mut index = events.len() - 1;
+    let mut lines = 0;
+
+    while index > 0
+        && matches!(
+            events[index].name,
+            Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+        )
+    {
+        if events[index].kind == Kind::Exit && events[index].name == Name::BlankLineEnding {
+            lines += 1;
+        }
+
+        index -= 1;
+    }
+
+    if events[index].kind != Kind::Exit {
+        return false;
+    }
+
+    if events[index].name == Name::GfmTableRow {
+        // With GFM tables, a line could be the head row of a table, which is
+        // still being attempted: as descriptions cannot be delimiter rows,
+        // that attempt fails and the line is a paragraph.
+        let mut enter = index - 1;
+
+        while events[enter].name != Name::GfmTableRow {
+            enter -= 1;
+        }
+
+        if enter == 0
+            || events[enter - 1].kind != Kind::Enter
+            || events[enter - 1].name != Name::GfmTableHead
+        {
+            return false;
+        }
+    } else if events[index].name != Name::Content {
+        return false;
+    }
+
+    // The paragraph must not be in a container that was closed since.
+    let exits = &tokenizer.tokenize_state.document_exits;
+    let mut line = 0;
+
+    while line <= lines && line < exits.len() {
+        if exits[exits.len() - 1 - line].is_some() {
+            return false;
+        }
+
+        line += 1;
+    }
+
+    true
+}
+
+/// Turn the paragraphs before descriptions into terms, and wrap adjacent
+/// terms and descriptions in definition lists.
+pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
+    let mut lists_wip: Vec<(usize, usize)> = vec![];
+    let mut lists: Vec<(usize, usize)> = vec![];
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.kind == Kind::Enter && event.name == Name::DefinitionListDescription {
+            // Find the exit of this description.
+            let mut end = index + 1;
+            let mut depth = 1;
+            while end < tokenizer.events.len() {
+                if tokenizer.events[end].name == Name::DefinitionListDescription {
+                    if tokenizer.events[end].kind == Kind::Enter {
+                        depth += 1;
+                    } else {
+                        depth -= 1;
+
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                }
+
+                end += 1;
+            }
+
+            let mut start = index;
+
+            // A paragraph before is the term.
+            if index > 0 {
+                let before = skip::opt_back(&tokenizer.events, index - 1, &BETWEEN);
+
+                if tokenizer.events[before].kind == Kind::Exit
+                    && tokenizer.events[before].name == Name::Paragraph
+                {
+                    let enter = skip::to_back(&tokenizer.events, before - 1, &[Name::Paragraph]);
+                    tokenizer.events[enter].name = Name::DefinitionListTerm;
+                    tokenizer.events[before].name = Name::DefinitionListTerm;
+                    start = enter;
+                }
+            }
+
+            let before = if start > 0 {
+                Some(skip::opt_back(&tokenizer.events, start - 1, &BETWEEN))
+            } else {
+                None
+            };
+            let mut list_index = lists_wip.len();
+            let mut matched = false;
+
+            while list_index > 0 {
+                list_index -= 1;
+
+                // Directly after the last description of this list.
+                if before == Some(lists_wip[list_index].1) {
+                    lists_wip[list_index].1 = end;
+                    lists.append(&mut lists_wip.split_off(list_index + 1));
+                    matched = true;
+                    break;
+                }
+            }
+
+            if !matched {
+                let mut list_index = lists_wip.len();
+                let mut exit = None;
+
+                while list_index > 0 {
+                    list_index -= 1;
+
+                    // If the current (new) list starts after where this list
+                    // on the stack ends, we can remove it from the stack.
+                    if start > lists_wip[list_index].1 {
+                        exit = Some(list_index);
+                    } else {
+                        break;
+                    }
+                }
+
+                if let Some(exit) = exit {
+                    lists.append(&mut lists_wip.split_off(exit));
+                }
+
+                lists_wip.push((start, end));
+            }
+        }
+
+        index += 1;
+    }
+
+    lists.append(&mut lists_wip);
+
+    // Inject events.
+    let mut index = 0;
+    while index < lists.len() {
+        let (start, end) = lists[index];
+        let mut list_start = tokenizer.events[start].clone();
+        let mut list_end = tokenizer.events[end].clone();
+        list_start.name = Name::DefinitionList;
+        list_end.name = Name::DefinitionList;
+
+        tokenizer.map.add(start, 0, vec![list_start]);
+        tokenizer.map.add(end + 1, 0, vec![list_end]);
+
+        index += 1;
+    }
+
+    tokenizer.map.consume(&mut tokenizer.events);
+    None
+}
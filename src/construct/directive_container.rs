@@ -0,0 +1,337 @@
+//! Directive (container) occurs in the [document][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (container) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_container ::= fence_open *( eol *line ) [ eol fence_close ]
+//!
+//! ; Restriction: the number of markers in the closing fence sequence must be
+//! ; equal to or greater than the number of markers in the opening fence
+//! ; sequence.
+//! fence_open ::= sequence directive *space_or_tab
+//! fence_close ::= sequence *space_or_tab
+//! sequence ::= 3*':'
+//!
+//! ; See the `partial_directive` construct for the BNF of that part.
+//! ```
+//!
+//! As this construct occurs in document, it is a container, like
+//! [block quote][block_quote]: the lines between the fences are parsed as
+//! [flow][], which can include further containers.
+//! Unlike other containers, directive (container) does not need a prefix on
+//! each line: it continues until a closing fence, or until its parent
+//! container ends.
+//!
+//! The opening fence cannot contain line endings, not in its label and not in
+//! its attributes.
+//! The closing fence sequence must be at least as long as the opening fence
+//! sequence.
+//! To nest directives (container), use more markers for the outer one:
+//!
+//! ```markdown
+//! ::::a
+//! :::b
+//! c
+//! :::
+//! ::::
+//! ```
+//!
+//! When compiling to HTML, a container directive is passed to
+//! [`directive_handler`][directive_handler] if given.
+//! Otherwise, or if that function does not handle the directive, only its
+//! content is rendered.
+//!
+//! See [`partial_directive`][partial_directive] for more info.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveContainer`][Name::DirectiveContainer]
+//! *   [`DirectiveContainerFence`][Name::DirectiveContainerFence]
+//! *   [`DirectiveContainerSequence`][Name::DirectiveContainerSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   see [`partial_directive`][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [document]: crate::construct::document
+//! [flow]: crate::construct::flow
+//! [block_quote]: crate::construct::block_quote
+//! [partial_directive]: crate::construct::partial_directive
+//! [directive_handler]: crate::CompileOptions::directive_handler
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::constant::DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN;
+
+/// Start of directive (container).
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_container {
+        tokenizer.enter(Name::DirectiveContainer);
+        tokenizer.enter(Name::DirectiveContainerFence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DirectiveContainerBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DirectiveContainerBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at opening fence sequence.
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveContainerSequence);
+        State::Retry(StateName::DirectiveContainerSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In opening fence sequence.
+///
+/// ```markdown
+/// > | :::a
+///     ^
+///   | b
+///   | :::
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.consume();
+        tokenizer.tokenize_state.size += 1;
+        State::Next(StateName::DirectiveContainerSequence)
+    } else if tokenizer.tokenize_state.size >= DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN {
+        tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size = tokenizer.tokenize_state.size;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.tokenize_state.token_1 = Name::DirectiveContainer;
+        tokenizer.exit(Name::DirectiveContainerSequence);
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerAfter),
+            State::Next(StateName::DirectiveContainerNok),
+        );
+        State::Retry(StateName::DirectiveStart)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// After directive in opening fence, at optional whitespace.
+///
+/// ```markdown
+/// > | :::a
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::DirectiveContainerEnd), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveContainerEnd)
+    }
+}
+
+/// After opening fence, at eol.
+///
+/// ```markdown
+/// > | :::a
+///         ^
+///   | b
+///   | :::
+/// ```
+pub fn end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+
+    if matches!(tokenizer.current, None | Some(b'\n')) {
+        tokenizer.exit(Name::DirectiveContainerFence);
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// At something that wasn’t a directive (container).
+///
+/// ```markdown
+/// > | :::1
+///        ^
+/// ```
+pub fn nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    State::Nok
+}
+
+/// Start of directive (container) continuation.
+///
+/// A directive (container) always continues, but when this line is a closing
+/// fence, it is consumed and the directive is marked as closed.
+///
+/// ```markdown
+///   | :::a
+/// > | b
+///     ^
+/// > | :::
+///     ^
+/// ```
+pub fn cont_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::DirectiveContainerContAfter),
+        State::Next(StateName::DirectiveContainerContAfter),
+    );
+    State::Retry(StateName::DirectiveContainerCloseStart)
+}
+
+/// After directive (container) continuation.
+///
+/// ```markdown
+///   | :::a
+/// > | b
+///     ^
+/// > | :::
+///        ^
+/// ```
+pub fn cont_after(_tokenizer: &mut Tokenizer) -> State {
+    State::Ok
+}
+
+/// Start of closing fence, at optional whitespace.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::DirectiveContainerFence);
+
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveContainerCloseBefore),
+            State::Nok,
+        );
+        State::Retry(space_or_tab_min_max(
+            tokenizer,
+            0,
+            if tokenizer.parse_state.options.constructs.code_indented {
+                tokenizer.parse_state.tab_size - 1
+            } else {
+                usize::MAX
+            },
+        ))
+    } else {
+        State::Retry(StateName::DirectiveContainerCloseBefore)
+    }
+}
+
+/// In closing fence, after optional whitespace, at sequence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveContainerSequence);
+        State::Retry(StateName::DirectiveContainerCloseSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In closing fence sequence.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///     ^
+/// ```
+pub fn close_sequence(tokenizer: &mut Tokenizer) -> State {
+    let size = tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued]
+        .size;
+
+    if tokenizer.current == Some(b':') {
+        tokenizer.tokenize_state.size_b += 1;
+        tokenizer.consume();
+        State::Next(StateName::DirectiveContainerCloseSequence)
+    } else if tokenizer.tokenize_state.size_b >= size {
+        tokenizer.tokenize_state.size_b = 0;
+        tokenizer.exit(Name::DirectiveContainerSequence);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveContainerCloseSequenceAfter),
+                State::Nok,
+            );
+            State::Retry(space_or_tab(tokenizer))
+        } else {
+            State::Retry(StateName::DirectiveContainerCloseSequenceAfter)
+        }
+    } else {
+        tokenizer.tokenize_state.size_b = 0;
+        State::Nok
+    }
+}
+
+/// After closing fence sequence, after optional whitespace.
+///
+/// ```markdown
+///   | :::a
+///   | b
+/// > | :::
+///        ^
+/// ```
+pub fn close_sequence_after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, None | Some(b'\n')) {
+        tokenizer.exit(Name::DirectiveContainerFence);
+        // Mark as closed: the document closes it, and everything in it, before
+        // the flow on this line.
+        tokenizer.tokenize_state.document_container_stack
+            [tokenizer.tokenize_state.document_continued]
+            .size = 0;
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
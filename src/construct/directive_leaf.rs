@@ -0,0 +1,161 @@
+//! Directive (leaf) occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (leaf) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! directive_leaf ::= '::' directive *space_or_tab
+//!
+//! ; See the `partial_directive` construct for the BNF of that part.
+//! ```
+//!
+//! As this construct occurs in flow, like all flow constructs, it must be
+//! followed by an eol (line ending) or eof (end of file).
+//! It cannot contain line endings, not in its label and not in its
+//! attributes.
+//!
+//! When compiling to HTML, a leaf directive is passed to
+//! [`directive_handler`][directive_handler] if given.
+//! Otherwise, or if that function does not handle the directive, only its
+//! label is rendered, in a paragraph.
+//!
+//! See [`partial_directive`][partial_directive] for more info.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveLeaf`][Name::DirectiveLeaf]
+//! *   [`DirectiveLeafSequence`][Name::DirectiveLeafSequence]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//! *   see [`partial_directive`][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [flow]: crate::construct::flow
+//! [partial_directive]: crate::construct::partial_directive
+//! [directive_handler]: crate::CompileOptions::directive_handler
+
+use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max};
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::constant::DIRECTIVE_LEAF_SEQUENCE_SIZE;
+
+/// Start of directive (leaf).
+///
+/// ```markdown
+/// > | ::a
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_leaf {
+        tokenizer.enter(Name::DirectiveLeaf);
+
+        if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+            tokenizer.attempt(State::Next(StateName::DirectiveLeafBefore), State::Nok);
+            State::Retry(space_or_tab_min_max(
+                tokenizer,
+                0,
+                if tokenizer.parse_state.options.constructs.code_indented {
+                    tokenizer.parse_state.tab_size - 1
+                } else {
+                    usize::MAX
+                },
+            ))
+        } else {
+            State::Retry(StateName::DirectiveLeafBefore)
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After optional whitespace, at sequence.
+///
+/// ```markdown
+/// > | ::a
+///     ^
+/// ```
+pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.enter(Name::DirectiveLeafSequence);
+        State::Retry(StateName::DirectiveLeafSequence)
+    } else {
+        State::Nok
+    }
+}
+
+/// In sequence.
+///
+/// ```markdown
+/// > | ::a
+///     ^
+/// ```
+pub fn sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b':') {
+        tokenizer.consume();
+        tokenizer.tokenize_state.size += 1;
+        State::Next(StateName::DirectiveLeafSequence)
+    } else if tokenizer.tokenize_state.size == DIRECTIVE_LEAF_SEQUENCE_SIZE {
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.tokenize_state.token_1 = Name::DirectiveLeaf;
+        tokenizer.exit(Name::DirectiveLeafSequence);
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveLeafAfter),
+            State::Next(StateName::DirectiveLeafNok),
+        );
+        State::Retry(StateName::DirectiveStart)
+    } else {
+        tokenizer.tokenize_state.size = 0;
+        State::Nok
+    }
+}
+
+/// After directive, at optional whitespace.
+///
+/// ```markdown
+/// > | ::a[b]
+///           ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::DirectiveLeafEnd), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::DirectiveLeafEnd)
+    }
+}
+
+/// After directive and optional whitespace, at eol.
+///
+/// ```markdown
+/// > | ::a[b]
+///           ^
+/// ```
+pub fn end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+
+    if matches!(tokenizer.current, None | Some(b'\n')) {
+        tokenizer.exit(Name::DirectiveLeaf);
+        // Feel free to interrupt.
+        tokenizer.interrupt = false;
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// At something that wasn’t a directive (leaf).
+///
+/// ```markdown
+/// > | ::1
+///       ^
+/// ```
+pub fn nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    State::Nok
+}
@@ -0,0 +1,93 @@
+//! Directive (text) occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Directive (text) forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the byte before must not be `:`.
+//! directive_text ::= ':' directive
+//!
+//! ; See the `partial_directive` construct for the BNF of that part.
+//! ```
+//!
+//! Directives are a proposed generic syntax for extensions to markdown.
+//! The name (`a` in `:a`) says what the directive is, the optional label
+//! (`[b]`) holds its content, and the optional attributes (`{c}`) configure
+//! it.
+//!
+//! When compiling to HTML, a text directive is passed to
+//! [`directive_handler`][directive_handler] if given.
+//! Otherwise, or if that function does not handle the directive, only its
+//! label is rendered.
+//!
+//! See [`partial_directive`][partial_directive] for more info.
+//!
+//! ## Tokens
+//!
+//! *   [`DirectiveText`][Name::DirectiveText]
+//! *   [`DirectiveTextMarker`][Name::DirectiveTextMarker]
+//! *   see [`partial_directive`][partial_directive] for more
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [text]: crate::construct::text
+//! [partial_directive]: crate::construct::partial_directive
+//! [directive_handler]: crate::CompileOptions::directive_handler
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive (text).
+///
+/// ```markdown
+/// > | a :b c
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.directive_text
+        && tokenizer.current == Some(b':')
+        && tokenizer.previous != Some(b':')
+    {
+        tokenizer.tokenize_state.token_1 = Name::DirectiveText;
+        tokenizer.enter(Name::DirectiveText);
+        tokenizer.enter(Name::DirectiveTextMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveTextMarker);
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveTextAfter),
+            State::Next(StateName::DirectiveTextNok),
+        );
+        State::Next(StateName::DirectiveStart)
+    } else {
+        State::Nok
+    }
+}
+
+/// After directive (text).
+///
+/// ```markdown
+/// > | a :b c
+///         ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    tokenizer.exit(Name::DirectiveText);
+    State::Ok
+}
+
+/// At something that wasn’t a directive (text).
+///
+/// ```markdown
+/// > | a :1
+///        ^
+/// ```
+pub fn nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    State::Nok
+}
@@ -8,6 +8,8 @@
 //! *   [Block quote][crate::construct::block_quote]
 //! *   [List item][crate::construct::list_item]
 //! *   [GFM: Footnote definition][crate::construct::gfm_footnote_definition]
+//! *   [Directive (container)][crate::construct::directive_container]
+//! *   [Definition list][crate::construct::definition_list] (description)
 
 use crate::event::{Content, Event, Kind, Link, Name};
 use crate::message;
@@ -15,7 +17,7 @@ use crate::state::{Name as StateName, State};
 use crate::subtokenize::divide_events;
 use crate::tokenizer::{Container, ContainerState, Tokenizer};
 use crate::util::skip;
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{boxed::Box, format, vec::Vec};
 
 /// Phases where we can exit containers.
 #[derive(Debug, PartialEq)]
@@ -31,7 +33,8 @@ enum Phase {
     ///   | ```
     /// ```
     After,
-    /// When a new container replaces an existing container.
+    /// When a new container replaces an existing container, or when a block
+    /// quote is not continued and lazy block quotes are off.
     ///
     /// ```markdown
     ///   | * a
@@ -39,6 +42,16 @@ enum Phase {
     ///     ^
     /// ```
     Prefix,
+    /// When a container was closed by the current line, such as by the
+    /// closing fence of a directive (container).
+    ///
+    /// ```markdown
+    ///   | :::a
+    ///   | b
+    /// > | :::
+    ///        ^
+    /// ```
+    Close,
     /// After everything.
     ///
     /// ```markdown
@@ -101,8 +114,12 @@ pub fn container_existing_before(tokenizer: &mut Tokenizer) -> State {
 
         let name = match container.kind {
             Container::BlockQuote => StateName::BlockQuoteContStart,
+            Container::Directive => StateName::DirectiveContainerContStart,
             Container::GfmFootnoteDefinition => StateName::GfmFootnoteDefinitionContStart,
-            Container::ListItem => StateName::ListItemContStart,
+            // Descriptions are continued like list items.
+            Container::DefinitionListDescription | Container::ListItem => {
+                StateName::ListItemContStart
+            }
         };
 
         tokenizer.attempt(
@@ -155,6 +172,13 @@ pub fn container_new_before(tokenizer: &mut Tokenizer) -> State {
         }
     }
 
+    // Do not nest containers deeper than allowed.
+    if let Some(max) = tokenizer.parse_state.options.max_nesting_depth {
+        if tokenizer.tokenize_state.document_continued >= max {
+            return State::Retry(StateName::DocumentContainersAfter);
+        }
+    }
+
     // Check for a new container.
     // Block quote?
     // Add a new container at the end of the stack.
@@ -230,11 +254,62 @@ pub fn container_new_before_not_list(tokenizer: &mut Tokenizer) -> State {
 /// At new container, but not a block quote, list item, or footnote definition.
 //
 /// ```markdown
-/// > | a
+/// > | :::a
 ///     ^
 /// ```
 pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -> State {
-    // It wasn’t a new block quote, list item, or footnote definition.
+    // Directive (container)?
+    // We replace the empty footnote definition container for this new
+    // directive one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::Directive,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotDirectiveContainer),
+    );
+    State::Retry(StateName::DirectiveContainerStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// or directive.
+//
+/// ```markdown
+///   | a
+/// > | : b
+///     ^
+/// ```
+pub fn container_new_before_not_directive_container(tokenizer: &mut Tokenizer) -> State {
+    // Definition list description?
+    // We replace the empty directive container for this new description one.
+    tokenizer.tokenize_state.document_container_stack
+        [tokenizer.tokenize_state.document_continued] = ContainerState {
+        kind: Container::DefinitionListDescription,
+        blank_initial: false,
+        size: 0,
+    };
+
+    tokenizer.attempt(
+        State::Next(StateName::DocumentContainerNewAfter),
+        State::Next(StateName::DocumentContainerNewBeforeNotDefinitionListDescription),
+    );
+    State::Retry(StateName::DefinitionListStart)
+}
+
+/// At new container, but not a block quote, list item, footnote definition,
+/// directive, or definition list description.
+//
+/// ```markdown
+/// > | a
+///     ^
+/// ```
+pub fn container_new_before_not_definition_list_description(tokenizer: &mut Tokenizer) -> State {
+    // It wasn’t a new block quote, list item, footnote definition, directive,
+    // or definition list description.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Drop what was in the middle.
     tokenizer
@@ -254,7 +329,8 @@ pub fn container_new_before_not_footnote_definition(tokenizer: &mut Tokenizer) -
 ///       ^
 /// ```
 pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
-    // It was a new block quote, list item, or footnote definition.
+    // It was a new block quote, list item, footnote definition, directive, or
+    // definition list description.
     // Swap the new container (in the middle) with the existing one (at the end).
     // Take the new container.
     let container = tokenizer
@@ -298,32 +374,58 @@ pub fn container_new_after(tokenizer: &mut Tokenizer) -> State {
 ///       ^
 /// ```
 pub fn containers_after(tokenizer: &mut Tokenizer) -> State {
+    // Without lazy block quotes, close block quotes that were not continued,
+    // and the containers after them, before this line is fed to flow.
+    if !tokenizer.parse_state.options.block_quote_lazy && tokenizer.current.is_some() {
+        let stack = &tokenizer.tokenize_state.document_container_stack;
+        let mut index = tokenizer.tokenize_state.document_continued;
+
+        while index < stack.len() {
+            if stack[index].kind == Container::BlockQuote {
+                if let Err(message) = exit_containers(tokenizer, &Phase::Prefix) {
+                    return State::Error(message);
+                }
+
+                // Like new containers, what comes after cannot continue the
+                // flow before.
+                tokenizer
+                    .tokenize_state
+                    .document_child
+                    .as_mut()
+                    .unwrap()
+                    .pierce = true;
+                break;
+            }
+
+            index += 1;
+        }
+    }
+
     let child = tokenizer.tokenize_state.document_child.as_mut().unwrap();
 
     child.lazy = tokenizer.tokenize_state.document_continued
         != tokenizer.tokenize_state.document_container_stack.len();
     child.define_skip(tokenizer.point.clone());
 
-    match tokenizer.current {
-        // Note: EOL is part of data.
-        None => State::Retry(StateName::DocumentFlowEnd),
-        Some(_) => {
-            let current = tokenizer.events.len();
-            let previous = tokenizer.tokenize_state.document_data_index;
-            if let Some(previous) = previous {
-                tokenizer.events[previous].link.as_mut().unwrap().next = Some(current);
-            }
-            tokenizer.tokenize_state.document_data_index = Some(current);
-            tokenizer.enter_link(
-                Name::Data,
-                Link {
-                    previous,
-                    next: None,
-                    content: Content::Flow,
-                },
-            );
-            State::Retry(StateName::DocumentFlowInside)
+    // Note: EOL is part of data.
+    if tokenizer.current.is_none() {
+        State::Retry(StateName::DocumentFlowEnd)
+    } else {
+        let current = tokenizer.events.len();
+        let previous = tokenizer.tokenize_state.document_data_index;
+        if let Some(previous) = previous {
+            tokenizer.events[previous].link.as_mut().unwrap().next = Some(current);
         }
+        tokenizer.tokenize_state.document_data_index = Some(current);
+        tokenizer.enter_link(
+            Name::Data,
+            Link {
+                previous,
+                next: None,
+                content: Content::Flow,
+            },
+        );
+        State::Retry(StateName::DocumentFlowInside)
     }
 }
 
@@ -360,6 +462,28 @@ pub fn flow_inside(tokenizer: &mut Tokenizer) -> State {
 ///     ^  ^
 /// ```
 pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.document_exits.push(None);
+
+    // If a container was closed on this line, close the flow and it (and
+    // everything in it) before this line is fed to flow.
+    let mut index = 0;
+
+    while index < tokenizer.tokenize_state.document_container_stack.len() {
+        let container = &tokenizer.tokenize_state.document_container_stack[index];
+
+        if container.kind == Container::Directive && container.size == 0 {
+            tokenizer.tokenize_state.document_continued = index;
+
+            if let Err(message) = exit_containers(tokenizer, &Phase::Close) {
+                return State::Error(message);
+            }
+
+            break;
+        }
+
+        index += 1;
+    }
+
     let child = tokenizer.tokenize_state.document_child.as_mut().unwrap();
     let state = tokenizer
         .tokenize_state
@@ -367,8 +491,6 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
         .take()
         .unwrap_or(State::Next(StateName::FlowStart));
 
-    tokenizer.tokenize_state.document_exits.push(None);
-
     let state = child.push(
         (child.point.index, child.point.vs),
         (tokenizer.point.index, tokenizer.point.vs),
@@ -443,30 +565,28 @@ pub fn flow_end(tokenizer: &mut Tokenizer) -> State {
     if tokenizer.tokenize_state.document_continued
         != tokenizer.tokenize_state.document_container_stack.len()
     {
-        let result = exit_containers(tokenizer, &Phase::After);
         // `Phase::After` doesn’t deal with flow: it only generates exits for
         // containers.
-        // And that never errors.
-        debug_assert!(result.is_ok(), "did not expect error when exiting");
+        // That only errors if there is a bug.
+        if let Err(message) = exit_containers(tokenizer, &Phase::After) {
+            return State::Error(message);
+        }
     }
 
-    match tokenizer.current {
-        None => {
-            tokenizer.tokenize_state.document_continued = 0;
-            if let Err(message) = exit_containers(tokenizer, &Phase::Eof) {
-                return State::Error(message);
-            }
-            resolve(tokenizer);
-            State::Ok
-        }
-        Some(_) => {
-            tokenizer.tokenize_state.document_continued = 0;
-            tokenizer.tokenize_state.document_lazy_accepting_before =
-                document_lazy_continuation_current;
-            // Containers would only be interrupting if we’ve continued.
-            tokenizer.interrupt = false;
-            State::Retry(StateName::DocumentContainerExistingBefore)
+    if tokenizer.current.is_none() {
+        tokenizer.tokenize_state.document_continued = 0;
+        if let Err(message) = exit_containers(tokenizer, &Phase::Eof) {
+            return State::Error(message);
         }
+        resolve(tokenizer);
+        State::Ok
+    } else {
+        tokenizer.tokenize_state.document_continued = 0;
+        tokenizer.tokenize_state.document_lazy_accepting_before =
+            document_lazy_continuation_current;
+        // Containers would only be interrupting if we’ve continued.
+        tokenizer.interrupt = false;
+        State::Retry(StateName::DocumentContainerExistingBefore)
     }
 }
 
@@ -487,7 +607,13 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
             .take()
             .unwrap_or(State::Next(StateName::FlowStart));
 
-        child.flush(state, false)?;
+        let mut result = child.flush(state, false)?;
+        // Abbreviation definitions are flow, so they are only known to the
+        // child.
+        tokenizer
+            .tokenize_state
+            .abbreviations
+            .append(&mut result.abbreviations);
     }
 
     if !stack_close.is_empty() {
@@ -496,10 +622,22 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
         let mut exits = Vec::with_capacity(stack_close.len());
 
         while let Some(container) = stack_close.pop() {
+            // The line that closes a container belongs to it, but the
+            // containers inside it are closed before that line.
+            if *phase == Phase::Close && stack_close.is_empty() && !exits.is_empty() {
+                add_exits(
+                    &mut tokenizer.tokenize_state.document_exits,
+                    index - 1,
+                    exits.split_off(0),
+                );
+            }
+
             let name = match container.kind {
                 Container::BlockQuote => Name::BlockQuote,
+                Container::Directive => Name::DirectiveContainer,
                 Container::GfmFootnoteDefinition => Name::GfmFootnoteDefinition,
                 Container::ListItem => Name::ListItem,
+                Container::DefinitionListDescription => Name::DefinitionListDescription,
             };
 
             exits.push(Event {
@@ -522,14 +660,19 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
                 }
             }
 
-            debug_assert!(found, "expected to find container event to exit");
+            // Containers are always on the stack, so this only happens if
+            // there is a bug, which is not a reason to panic.
+            if !found {
+                return Err(message::Message {
+                    place: Some(Box::new(message::Place::Point(tokenizer.point.to_unist()))),
+                    reason: format!("Unexpected missing `{:?}` when closing containers", name),
+                    rule_id: Box::new("unexpected-container-exit".into()),
+                    source: Box::new("markdown-rs".into()),
+                });
+            }
         }
 
-        debug_assert!(
-            tokenizer.tokenize_state.document_exits[index].is_none(),
-            "expected no exits yet"
-        );
-        tokenizer.tokenize_state.document_exits[index] = Some(exits);
+        add_exits(&mut tokenizer.tokenize_state.document_exits, index, exits);
     }
 
     child.interrupt = false;
@@ -537,6 +680,17 @@ fn exit_containers(tokenizer: &mut Tokenizer, phase: &Phase) -> Result<(), messa
     Ok(())
 }
 
+/// Add exits of containers to a line.
+fn add_exits(document_exits: &mut [Option<Vec<Event>>], index: usize, mut exits: Vec<Event>) {
+    // Containers closed by a line (`Phase::Close`) can be followed by their
+    // parents being closed after that line.
+    if let Some(existing) = document_exits[index].as_mut() {
+        existing.append(&mut exits);
+    } else {
+        document_exits[index] = Some(exits);
+    }
+}
+
 // Inject everything together.
 fn resolve(tokenizer: &mut Tokenizer) {
     let child = tokenizer.tokenize_state.document_child.as_mut().unwrap();
@@ -621,13 +775,21 @@ fn resolve(tokenizer: &mut Tokenizer) {
         }
     }
 
-    // Add the resolvers from child.
-    tokenizer
-        .resolvers
-        .append(&mut child.resolvers.split_off(0));
+    let resolvers = child.resolvers.split_off(0);
 
     tokenizer
         .tokenize_state
         .definitions
         .append(&mut child.tokenize_state.definitions.split_off(0));
+    tokenizer
+        .tokenize_state
+        .abbreviations
+        .append(&mut child.tokenize_state.abbreviations.split_off(0));
+
+    // Add the resolvers from child.
+    let mut index = 0;
+    while index < resolvers.len() {
+        tokenizer.register_resolver(resolvers[index]);
+        index += 1;
+    }
 }
@@ -0,0 +1,205 @@
+//! Emoji occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Emoji forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: the code before must be `emoji_before`.
+//! ; Restriction: the code after must be `emoji_after`.
+//! ; Restriction: `shortcode` must be a known shortcode.
+//! emoji ::= ':' shortcode ':'
+//! emoji_before ::= byte - ascii_alphanumeric
+//! emoji_after ::= byte - ascii_alphanumeric
+//!
+//! shortcode ::= 1*('+' | '-' | '_' | ascii_digit | ascii_lowercase)
+//! ```
+//!
+//! Known shortcodes are those passed as
+//! [`emoji_shortcodes`][emoji_shortcodes] in parse options.
+//! With the `emoji` feature, the shortcodes used on GitHub (such as
+//! `:smile:`, `:tada:`, and `:+1:`) are known too.
+//! The table of those is big, which is why it is behind a feature.
+//! Shortcodes passed in options go before the bundled ones.
+//!
+//! Unknown shortcodes (`:unknown:`) are left as they are.
+//! The restrictions on what comes before and after prevent matching colons
+//! used as normal punctuation, such as in `10:30:00`.
+//!
+//! Like email [GFM autolink literals][gfm_autolink_literal], emoji are
+//! handled afterwards: when everything is parsed, we look back at data to
+//! figure out if there were shortcodes.
+//! That means that emoji are not found in code (text) or HTML, and that
+//! character escapes and character references cannot be used in them.
+//! It also means that other constructs that start with `:`, such as
+//! [directive (text)][directive_text], go first.
+//!
+//! ## HTML
+//!
+//! Emoji do not relate to an element in HTML.
+//! When compiling, the emoji a shortcode stands for is used instead of it.
+//!
+//! ## Tokens
+//!
+//! *   [`Emoji`][Name::Emoji]
+//!
+//! ## References
+//!
+//! *   [`gemoji`](https://github.com/github/gemoji)
+//!
+//! [text]: crate::construct::text
+//! [emoji_shortcodes]: crate::ParseOptions::emoji_shortcodes
+//! [gfm_autolink_literal]: crate::construct::gfm_autolink_literal
+//! [directive_text]: crate::construct::directive_text
+
+use crate::event::{Event, Kind, Name};
+use crate::tokenizer::Tokenizer;
+#[cfg(feature = "emoji")]
+use crate::util::emoji::EMOJI;
+use crate::util::slice::{Position, Slice};
+use crate::ParseOptions;
+use alloc::{string::String, vec::Vec};
+
+/// Resolve: postprocess text to find emoji shortcodes.
+pub fn resolve(tokenizer: &mut Tokenizer) {
+    tokenizer.map.consume(&mut tokenizer.events);
+
+    let mut index = 0;
+
+    while index < tokenizer.events.len() {
+        let event = &tokenizer.events[index];
+
+        if event.kind == Kind::Exit && event.name == Name::Data {
+            let slice = Slice::from_position(
+                tokenizer.parse_state.bytes,
+                &Position::from_exit_event(&tokenizer.events, index),
+                tokenizer.parse_state.tab_size,
+            );
+            let bytes = slice.bytes;
+            let mut byte_index = 0;
+            let mut replace = Vec::new();
+            let mut point = tokenizer.events[index - 1].point.clone();
+            let start_index = point.index;
+            let mut min = 0;
+
+            while byte_index < bytes.len() {
+                if bytes[byte_index] == b':'
+                    && (byte_index == 0 || !bytes[byte_index - 1].is_ascii_alphanumeric())
+                {
+                    let mut end = byte_index + 1;
+
+                    while end < bytes.len()
+                        && matches!(bytes[end], b'+' | b'-' | b'_' | b'0'..=b'9' | b'a'..=b'z')
+                    {
+                        end += 1;
+                    }
+
+                    if end > byte_index + 1
+                        && end < bytes.len()
+                        && bytes[end] == b':'
+                        && (end + 1 == bytes.len() || !bytes[end + 1].is_ascii_alphanumeric())
+                    {
+                        // Shortcodes are ASCII, so this is fine.
+                        let shortcode = core::str::from_utf8(&bytes[byte_index + 1..end]).unwrap();
+
+                        if let Some(value) = find(tokenizer.parse_state.options, shortcode) {
+                            end += 1;
+
+                            // If there is something between the last emoji
+                            // (or `min`) and this emoji.
+                            if min != byte_index {
+                                replace.push(Event {
+                                    kind: Kind::Enter,
+                                    name: Name::Data,
+                                    point: point.clone(),
+                                    link: None,
+                                });
+                                point = point.shift_to(
+                                    tokenizer.parse_state.bytes,
+                                    start_index + byte_index,
+                                    tokenizer.parse_state.tab_size,
+                                );
+                                replace.push(Event {
+                                    kind: Kind::Exit,
+                                    name: Name::Data,
+                                    point: point.clone(),
+                                    link: None,
+                                });
+                            }
+
+                            // Add the emoji.
+                            replace.push(Event {
+                                kind: Kind::Enter,
+                                name: Name::Emoji(value.clone()),
+                                point: point.clone(),
+                                link: None,
+                            });
+                            point = point.shift_to(
+                                tokenizer.parse_state.bytes,
+                                start_index + end,
+                                tokenizer.parse_state.tab_size,
+                            );
+                            replace.push(Event {
+                                kind: Kind::Exit,
+                                name: Name::Emoji(value),
+                                point: point.clone(),
+                                link: None,
+                            });
+                            min = end;
+                            byte_index = end;
+                            continue;
+                        }
+                    }
+                }
+
+                byte_index += 1;
+            }
+
+            // If there was an emoji, and we have more bytes left.
+            if min != 0 && min < bytes.len() {
+                replace.push(Event {
+                    kind: Kind::Enter,
+                    name: Name::Data,
+                    point: point.clone(),
+                    link: None,
+                });
+                replace.push(Event {
+                    kind: Kind::Exit,
+                    name: Name::Data,
+                    point: event.point.clone(),
+                    link: None,
+                });
+            }
+
+            // If there were emoji.
+            if !replace.is_empty() {
+                tokenizer.map.add(index - 1, 2, replace);
+            }
+        }
+
+        index += 1;
+    }
+}
+
+/// Find the emoji that a shortcode (without colons) stands for.
+fn find(options: &ParseOptions, shortcode: &str) -> Option<String> {
+    let mut index = 0;
+
+    while index < options.emoji_shortcodes.len() {
+        let (name, value) = &options.emoji_shortcodes[index];
+
+        if name == shortcode {
+            return Some(value.clone());
+        }
+
+        index += 1;
+    }
+
+    #[cfg(feature = "emoji")]
+    if let Ok(index) = EMOJI.binary_search_by(|d| d.0.cmp(shortcode)) {
+        return Some(EMOJI[index].1.into());
+    }
+
+    None
+}
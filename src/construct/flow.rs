@@ -10,8 +10,11 @@
 //!
 //! The constructs found in flow are:
 //!
+//! *   [Abbreviation definition][crate::construct::abbreviation_definition]
 //! *   [Blank line][crate::construct::blank_line]
 //! *   [Code (indented)][crate::construct::code_indented]
+//! *   [Directive (leaf)][crate::construct::directive_leaf]
+//! *   [Flow extension][crate::construct::flow_extension] (custom flow constructs)
 //! *   [Heading (atx)][crate::construct::heading_atx]
 //! *   [Heading (setext)][crate::construct::heading_setext]
 //! *   [HTML (flow)][crate::construct::html_flow]
@@ -22,8 +25,11 @@
 //! *   [Thematic break][crate::construct::thematic_break]
 
 use crate::event::Name;
+use crate::message;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
+use crate::util::flow_extension::Priority;
+use alloc::boxed::Box;
 
 /// Start of flow.
 //
@@ -36,18 +42,40 @@ use crate::tokenizer::Tokenizer;
 ///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if has_flow_extensions(tokenizer, Priority::First) {
+        tokenizer.attempt(
+            State::Next(StateName::FlowAfter),
+            State::Next(StateName::FlowBeforeBuiltIn),
+        );
+        State::Retry(StateName::FlowExtensionStartFirst)
+    } else {
+        State::Retry(StateName::FlowBeforeBuiltIn)
+    }
+}
+
+/// At built-in constructs, after flow extensions that go first.
+///
+/// ```markdown
+/// > | ## alpha
+///     ^
+/// > |     bravo
+///     ^
+/// > | ***
+///     ^
+/// ```
+pub fn before_built_in(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         Some(b'#') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeFlowExtensionNotGfmTable),
             );
             State::Retry(StateName::HeadingAtxStart)
         }
         Some(b'$' | b'`' | b'~') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeFlowExtensionNotGfmTable),
             );
             State::Retry(StateName::RawFlowStart)
         }
@@ -56,7 +84,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         Some(b'*' | b'_') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeAbbreviationDefinitionNotGfmTable),
             );
             State::Retry(StateName::ThematicBreakStart)
         }
@@ -70,17 +98,24 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         Some(b'e' | b'i') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeFlowExtensionNotGfmTable),
             );
             State::Retry(StateName::MdxEsmStart)
         }
         Some(b'{') => {
             tokenizer.attempt(
                 State::Next(StateName::FlowAfter),
-                State::Next(StateName::FlowBeforeContent),
+                State::Next(StateName::FlowBeforeFlowExtensionNotGfmTable),
             );
             State::Retry(StateName::MdxExpressionFlowStart)
         }
+        Some(b':') => {
+            tokenizer.attempt(
+                State::Next(StateName::FlowAfter),
+                State::Next(StateName::FlowBeforeFlowExtension),
+            );
+            State::Retry(StateName::DirectiveLeafStart)
+        }
         // Actual parsing: blank line? Indented code? Indented anything?
         // Tables, setext heading underlines, definitions, and Contents are
         // particularly weird.
@@ -196,11 +231,39 @@ pub fn before_heading_setext(tokenizer: &mut Tokenizer) -> State {
 pub fn before_thematic_break(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(
         State::Next(StateName::FlowAfter),
-        State::Next(StateName::FlowBeforeMdxExpression),
+        State::Next(StateName::FlowBeforeAbbreviationDefinition),
     );
     State::Retry(StateName::ThematicBreakStart)
 }
 
+/// At abbreviation definition.
+///
+/// ```markdown
+/// > | *[a]: b
+///     ^
+/// ```
+pub fn before_abbreviation_definition(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeMdxExpression),
+    );
+    State::Retry(StateName::AbbreviationDefinitionStart)
+}
+
+/// At abbreviation definition, from a line that cannot be a GFM table.
+///
+/// ```markdown
+/// > | *[a]: b
+///     ^
+/// ```
+pub fn before_abbreviation_definition_not_gfm_table(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeFlowExtensionNotGfmTable),
+    );
+    State::Retry(StateName::AbbreviationDefinitionStart)
+}
+
 /// At MDX expression (flow).
 ///
 /// ```markdown
@@ -210,11 +273,48 @@ pub fn before_thematic_break(tokenizer: &mut Tokenizer) -> State {
 pub fn before_mdx_expression(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(
         State::Next(StateName::FlowAfter),
-        State::Next(StateName::FlowBeforeGfmTable),
+        State::Next(StateName::FlowBeforeDirectiveLeaf),
     );
     State::Retry(StateName::MdxExpressionFlowStart)
 }
 
+/// At directive (leaf).
+///
+/// ```markdown
+/// > | ::a
+///     ^
+/// ```
+pub fn before_directive_leaf(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.attempt(
+        State::Next(StateName::FlowAfter),
+        State::Next(StateName::FlowBeforeFlowExtension),
+    );
+    State::Retry(StateName::DirectiveLeafStart)
+}
+
+/// At flow extensions that go last.
+///
+/// Note: GFM tables look at the next line, which means that containers on
+/// that line are already parsed, so flow extensions go before them.
+///
+/// ```markdown
+/// > | %%%
+///     ^
+/// ```
+pub fn before_flow_extension(tokenizer: &mut Tokenizer) -> State {
+    before_flow_extension_then(tokenizer, StateName::FlowBeforeGfmTable)
+}
+
+/// At flow extensions that go last, from a line that cannot be a GFM table.
+///
+/// ```markdown
+/// > | #%%%
+///     ^
+/// ```
+pub fn before_flow_extension_not_gfm_table(tokenizer: &mut Tokenizer) -> State {
+    before_flow_extension_then(tokenizer, StateName::FlowBeforeContent)
+}
+
 /// At GFM table.
 ///
 /// ```markdown
@@ -257,7 +357,7 @@ pub fn blank_line_after(tokenizer: &mut Tokenizer) -> State {
             tokenizer.interrupt = false;
             State::Next(StateName::FlowStart)
         }
-        _ => unreachable!("expected eol/eof"),
+        _ => crash(tokenizer),
     }
 }
 
@@ -276,6 +376,48 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
             tokenizer.exit(Name::LineEnding);
             State::Next(StateName::FlowStart)
         }
-        _ => unreachable!("expected eol/eof"),
+        _ => crash(tokenizer),
     }
 }
+
+/// Check whether there are flow extensions with a certain priority.
+fn has_flow_extensions(tokenizer: &Tokenizer, priority: Priority) -> bool {
+    let extensions = &tokenizer.parse_state.options.flow_extensions;
+    let mut index = 0;
+
+    while index < extensions.len() {
+        if extensions[index].priority == priority {
+            return true;
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
+/// At flow extensions that go last, going to `next` if there are none, or if
+/// none of them start here.
+fn before_flow_extension_then(tokenizer: &mut Tokenizer, next: StateName) -> State {
+    if has_flow_extensions(tokenizer, Priority::Last) {
+        tokenizer.attempt(State::Next(StateName::FlowAfter), State::Next(next));
+        State::Retry(StateName::FlowExtensionStartLast)
+    } else {
+        State::Retry(next)
+    }
+}
+
+/// Crash because flow ended somewhere other than at a line ending or the end
+/// of the file.
+///
+/// This cannot happen in valid `markdown-rs` code: it is an internal error,
+/// but it’s not a reason to panic.
+fn crash(tokenizer: &Tokenizer) -> State {
+    State::Error(message::Message {
+        place: Some(Box::new(message::Place::Point(tokenizer.point.to_unist()))),
+        reason: "Unexpected character after flow, expected a line ending or the end of the file"
+            .into(),
+        rule_id: Box::new("unexpected-flow-end".into()),
+        source: Box::new("markdown-rs".into()),
+    })
+}
@@ -0,0 +1,276 @@
+//! Flow extension occurs in the [flow][] content type.
+//!
+//! ## Grammar
+//!
+//! Flow extension forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: `start` of the extension must accept `line_open`.
+//! ; Restriction: `close` of the extension must accept `line_close`, and must
+//! ; not accept the lines before it.
+//! ; Restriction: if the extension has no `close`, there are no more lines.
+//! flow_extension ::= line_open *( eol *line ) [ eol line_close ]
+//!
+//! line_open ::= 1*line
+//! line_close ::= *line
+//! ```
+//!
+//! Flow extensions are custom flow constructs, passed by users as
+//! [`flow_extensions`][flow_extensions] in parse options.
+//! They are line based: the `start` function of an extension decides whether
+//! a line starts it, and the `close` function decides whether a later line
+//! closes it.
+//! An extension without `close` is one line.
+//! Empty lines never start an extension.
+//!
+//! Extensions with the [`First`][Priority::First] priority are tried before
+//! all built-in flow constructs, at the start of each line, so they see lines
+//! that could also be, for example, code (indented).
+//! Extensions with the [`Last`][Priority::Last] priority are tried after all
+//! built-in flow constructs, right before content.
+//! Extensions with the same priority are tried in order.
+//!
+//! When a line follows a paragraph, only extensions that set `interrupt` are
+//! tried.
+//! Like [raw (flow)][raw_flow], the lines after the first line of an
+//! extension with `close` are concrete: new containers cannot start in them,
+//! and lazy lines are not part of it.
+//! Without a closing line, the extension continues until its containers end,
+//! or until the end of the document.
+//!
+//! ## HTML
+//!
+//! When compiling to HTML, a flow extension is passed to
+//! [`flow_extension_handler`][flow_extension_handler] if given.
+//! Otherwise, or if that function does not handle the construct, its lines
+//! are rendered as code.
+//!
+//! ## Tokens
+//!
+//! *   [`FlowExtension`][Name::FlowExtension]
+//! *   [`FlowExtensionChunk`][Name::FlowExtensionChunk]
+//! *   [`LineEnding`][Name::LineEnding]
+//!
+//! [flow]: crate::construct::flow
+//! [raw_flow]: crate::construct::raw_flow
+//! [flow_extensions]: crate::ParseOptions::flow_extensions
+//! [flow_extension_handler]: crate::CompileOptions::flow_extension_handler
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::{flow_extension::Priority, slice::Slice};
+
+/// Start of flow extension, trying extensions that go before built-in
+/// constructs.
+///
+/// ```markdown
+/// > | %%%
+///     ^
+///   | a
+///   | %%%
+/// ```
+pub fn start_first(tokenizer: &mut Tokenizer) -> State {
+    start(tokenizer, Priority::First)
+}
+
+/// Start of flow extension, trying extensions that go after built-in
+/// constructs.
+///
+/// ```markdown
+/// > | %%%
+///     ^
+///   | a
+///   | %%%
+/// ```
+pub fn start_last(tokenizer: &mut Tokenizer) -> State {
+    start(tokenizer, Priority::Last)
+}
+
+/// Start of flow extension, with a certain priority.
+///
+/// ```markdown
+/// > | %%%
+///     ^
+///   | a
+///   | %%%
+/// ```
+fn start(tokenizer: &mut Tokenizer, priority: Priority) -> State {
+    let extensions = &tokenizer.parse_state.options.flow_extensions;
+    let start = tokenizer.point.index;
+    let end = line_end(tokenizer);
+    let line = Slice::from_indices(tokenizer.parse_state.bytes, start, end);
+    let mut index = 0;
+
+    while index < extensions.len() {
+        let extension = &extensions[index];
+
+        if extension.priority == priority
+            && start < end
+            && (!tokenizer.interrupt || extension.interrupt)
+            && (extension.start)(line.as_str())
+        {
+            tokenizer.tokenize_state.size = index;
+            tokenizer.tokenize_state.start = start;
+            tokenizer.tokenize_state.end = end;
+            tokenizer.tokenize_state.token_1 = Name::FlowExtension(extension.name.clone());
+            tokenizer.enter(tokenizer.tokenize_state.token_1.clone());
+            return State::Retry(StateName::FlowExtensionLineStart);
+        }
+
+        index += 1;
+    }
+
+    State::Nok
+}
+
+/// At start of a line in flow extension.
+///
+/// ```markdown
+/// > | %%%
+///     ^
+/// > | a
+///     ^
+/// > | %%%
+///     ^
+/// ```
+pub fn line_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Retry(StateName::FlowExtensionLineAfter),
+        _ => {
+            tokenizer.enter(Name::FlowExtensionChunk);
+            State::Retry(StateName::FlowExtensionChunk)
+        }
+    }
+}
+
+/// In chunk.
+///
+/// ```markdown
+/// > | %%%
+///     ^^^
+/// > | a
+///     ^
+/// > | %%%
+///     ^^^
+/// ```
+pub fn chunk(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::FlowExtensionChunk);
+            State::Retry(StateName::FlowExtensionLineAfter)
+        }
+        _ => {
+            tokenizer.consume();
+            State::Next(StateName::FlowExtensionChunk)
+        }
+    }
+}
+
+/// At eol/eof, after a line.
+///
+/// ```markdown
+/// > | %%%
+///        ^
+/// > | a
+///      ^
+/// > | %%%
+///        ^
+/// ```
+pub fn line_after(tokenizer: &mut Tokenizer) -> State {
+    let extension = &tokenizer.parse_state.options.flow_extensions[tokenizer.tokenize_state.size];
+
+    if tokenizer.tokenize_state.seen || extension.close.is_none() {
+        State::Retry(StateName::FlowExtensionAfter)
+    } else {
+        match tokenizer.current {
+            None => State::Retry(StateName::FlowExtensionAfter),
+            Some(b'\n') => {
+                tokenizer.concrete = true;
+                tokenizer.check(
+                    State::Next(StateName::FlowExtensionAtNonLazyBreak),
+                    State::Next(StateName::FlowExtensionAfter),
+                );
+                State::Retry(StateName::NonLazyContinuationStart)
+            }
+            _ => unreachable!("expected eol/eof"),
+        }
+    }
+}
+
+/// At eol, before a non-lazy line.
+///
+/// ```markdown
+/// > | %%%
+///        ^
+///   | a
+///   | %%%
+/// ```
+pub fn at_non_lazy_break(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter(Name::LineEnding);
+    tokenizer.consume();
+    tokenizer.exit(Name::LineEnding);
+    State::Next(StateName::FlowExtensionContinuationStart)
+}
+
+/// At start of a line after the first line.
+///
+/// ```markdown
+///   | %%%
+/// > | a
+///     ^
+/// > | %%%
+///     ^
+/// ```
+pub fn continuation_start(tokenizer: &mut Tokenizer) -> State {
+    let bytes = tokenizer.parse_state.bytes;
+    let extension = &tokenizer.parse_state.options.flow_extensions[tokenizer.tokenize_state.size];
+    let close = extension
+        .close
+        .as_ref()
+        .expect("expected `close` on extensions with more lines");
+    let opening = Slice::from_indices(
+        bytes,
+        tokenizer.tokenize_state.start,
+        tokenizer.tokenize_state.end,
+    );
+    let line = Slice::from_indices(bytes, tokenizer.point.index, line_end(tokenizer));
+
+    tokenizer.tokenize_state.seen = close(opening.as_str(), line.as_str());
+    State::Retry(StateName::FlowExtensionLineStart)
+}
+
+/// After flow extension.
+///
+/// ```markdown
+///   | %%%
+///   | a
+/// > | %%%
+///        ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    tokenizer.tokenize_state.seen = false;
+    tokenizer.tokenize_state.size = 0;
+    tokenizer.tokenize_state.start = 0;
+    tokenizer.tokenize_state.end = 0;
+    // Feel free to interrupt.
+    tokenizer.interrupt = false;
+    // No longer concrete.
+    tokenizer.concrete = false;
+    State::Ok
+}
+
+/// Get the index of the end of the current line (its eol or eof).
+fn line_end(tokenizer: &Tokenizer) -> usize {
+    let bytes = tokenizer.parse_state.bytes;
+    let mut index = tokenizer.point.index;
+
+    while index < bytes.len() && !matches!(bytes[index], b'\n' | b'\r') {
+        index += 1;
+    }
+
+    index
+}
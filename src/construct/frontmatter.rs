@@ -29,6 +29,8 @@
 //! As there is no spec for frontmatter in markdown, this extension follows how
 //! YAML frontmatter works on `github.com`.
 //! It also parses TOML frontmatter, just like YAML except that it uses a `+`.
+//! Which of the two are supported can be configured with
+//! [`frontmatter_kind`][frontmatter_kind].
 //!
 //! ## Recommendation
 //!
@@ -55,6 +57,7 @@
 //! *   [`micromark-extension-frontmatter`](https://github.com/micromark/micromark-extension-frontmatter)
 //!
 //! [constructs]: crate::Constructs
+//! [frontmatter_kind]: crate::ParseOptions::frontmatter_kind
 
 use crate::construct::partial_space_or_tab::space_or_tab;
 use crate::event::Name;
@@ -74,6 +77,11 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
     // Indent not allowed.
     if tokenizer.parse_state.options.constructs.frontmatter
         && matches!(tokenizer.current, Some(b'+' | b'-'))
+        && tokenizer
+            .parse_state
+            .options
+            .frontmatter_kind
+            .allows(tokenizer.current.unwrap())
     {
         tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
         tokenizer.enter(Name::Frontmatter);
@@ -91,6 +91,26 @@
 //! This particularly affects how they interleave with character escapes and
 //! character references.
 //!
+//! All kinds are turned on and off with
+//! [`gfm_autolink_literal`][crate::Constructs::gfm_autolink_literal].
+//! Each kind can also be turned off on its own, with
+//! [`gfm_autolink_literal_email`][crate::Constructs::gfm_autolink_literal_email],
+//! [`gfm_autolink_literal_protocol`][crate::Constructs::gfm_autolink_literal_protocol],
+//! and [`gfm_autolink_literal_www`][crate::Constructs::gfm_autolink_literal_www].
+//! The email kind includes literals with a `mailto:` or `xmpp:` protocol.
+//!
+//! Autolinks ([autolink][]) go before autolink literals: they start at `<`,
+//! and what is in them is not text, so no literals are found in
+//! `<https://a.b/https://c.d>`.
+//! Literals can directly follow autolinks, as `>` is not an ASCII
+//! alphabetical: `<https://a.b>https://c.d` is two links, and so is
+//! `https://a.b<https://c.d>`.
+//! They never overlap.
+//! www literals can not follow autolinks, as they have to follow whitespace
+//! or certain punctuation.
+//! Literals in links (`[https://a.b](c)`) are compiled as text, so links are
+//! not nested in HTML.
+//!
 //! ## HTML
 //!
 //! GFM autolink literals relate to the `<a>` element in HTML.
@@ -137,6 +157,7 @@
 //! > added in `cmark-gfm@0.29.0.gfm.5` and are as of yet undocumented.
 //!
 //! [text]: crate::construct::text
+//! [autolink]: crate::construct::autolink
 //! [definition]: crate::construct::definition
 //! [attention]: crate::construct::attention
 //! [label_start_link]: crate::construct::label_start_link
@@ -160,12 +181,11 @@ use alloc::vec::Vec;
 ///     ^
 /// ```
 pub fn protocol_start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer
-        .parse_state
-        .options
-        .constructs
-        .gfm_autolink_literal &&
-        matches!(tokenizer.current, Some(b'H' | b'h'))
+    let constructs = &tokenizer.parse_state.options.constructs;
+
+    if constructs.gfm_autolink_literal
+        && constructs.gfm_autolink_literal_protocol
+        && matches!(tokenizer.current, Some(b'H' | b'h'))
             // Source: <https://github.com/github/cmark-gfm/blob/ef1cfcb/extensions/autolink.c#L214>.
             && !matches!(tokenizer.previous, Some(b'A'..=b'Z' | b'a'..=b'z'))
     {
@@ -264,12 +284,11 @@ pub fn protocol_slashes_inside(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn www_start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer
-        .parse_state
-        .options
-        .constructs
-        .gfm_autolink_literal &&
-        matches!(tokenizer.current, Some(b'W' | b'w'))
+    let constructs = &tokenizer.parse_state.options.constructs;
+
+    if constructs.gfm_autolink_literal
+        && constructs.gfm_autolink_literal_www
+        && matches!(tokenizer.current, Some(b'W' | b'w'))
             // Source: <https://github.com/github/cmark-gfm/blob/ef1cfcb/extensions/autolink.c#L156>.
             && matches!(tokenizer.previous, None | Some(b'\t' | b'\n' | b' ' | b'(' | b'*' | b'_' | b'[' | b']' | b'~'))
     {
@@ -631,6 +650,7 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                 let slice = Slice::from_position(
                     tokenizer.parse_state.bytes,
                     &Position::from_exit_event(&tokenizer.events, index),
+                    tokenizer.parse_state.tab_size,
                 );
                 let bytes = slice.bytes;
                 let mut byte_index = 0;
@@ -675,8 +695,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                     point: point.clone(),
                                     link: None,
                                 });
-                                point = point
-                                    .shift_to(tokenizer.parse_state.bytes, start_index + range.0);
+                                point = point.shift_to(
+                                    tokenizer.parse_state.bytes,
+                                    start_index + range.0,
+                                    tokenizer.parse_state.tab_size,
+                                );
                                 replace.push(Event {
                                     kind: Kind::Exit,
                                     name: Name::Data,
@@ -692,8 +715,11 @@ pub fn resolve(tokenizer: &mut Tokenizer) {
                                 point: point.clone(),
                                 link: None,
                             });
-                            point =
-                                point.shift_to(tokenizer.parse_state.bytes, start_index + range.1);
+                            point = point.shift_to(
+                                tokenizer.parse_state.bytes,
+                                start_index + range.1,
+                                tokenizer.parse_state.tab_size,
+                            );
                             replace.push(Event {
                                 kind: Kind::Exit,
                                 name: range.2.clone(),
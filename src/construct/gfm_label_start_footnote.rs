@@ -37,6 +37,7 @@
 //! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
 //! [html_sup]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-sub-and-sup-elements
 
+use crate::construct::label_end::push_start;
 use crate::event::Name;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
@@ -79,12 +80,15 @@ pub fn open(tokenizer: &mut Tokenizer) -> State {
             tokenizer.consume();
             tokenizer.exit(Name::GfmFootnoteCallMarker);
             tokenizer.exit(Name::GfmFootnoteCallLabel);
-            tokenizer.tokenize_state.label_starts.push(LabelStart {
-                kind: LabelKind::GfmFootnote,
-                start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
-                inactive: false,
-            });
-            tokenizer.register_resolver_before(ResolveName::Label);
+            push_start(
+                tokenizer,
+                LabelStart {
+                    kind: LabelKind::GfmFootnote,
+                    start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
+                    inactive: false,
+                },
+            );
+            tokenizer.register_resolver(ResolveName::Label);
             State::Ok
         }
         _ => State::Nok,
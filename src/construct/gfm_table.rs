@@ -231,7 +231,7 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip::opt_back as skip_opt_back};
+use crate::util::skip::opt_back as skip_opt_back;
 use alloc::vec;
 
 /// Start of a GFM table.
@@ -287,7 +287,7 @@ pub fn head_row_before(tokenizer: &mut Tokenizer) -> State {
             tokenizer,
             0,
             if tokenizer.parse_state.options.constructs.code_indented {
-                TAB_SIZE - 1
+                tokenizer.parse_state.tab_size - 1
             } else {
                 usize::MAX
             },
@@ -460,7 +460,7 @@ pub fn head_delimiter_start(tokenizer: &mut Tokenizer) -> State {
                     tokenizer,
                     0,
                     if tokenizer.parse_state.options.constructs.code_indented {
-                        TAB_SIZE - 1
+                        tokenizer.parse_state.tab_size - 1
                     } else {
                         usize::MAX
                     },
@@ -6,9 +6,16 @@
 //! (<small>see [construct][crate::construct] for character groups</small>):
 //!
 //! ```bnf
-//! heading_atx ::= 1*6'#' [ 1*space_or_tab line [ 1*space_or_tab 1*'#' ] ] *space_or_tab
+//! heading_atx ::= 1*6'#' [ 1*space_or_tab line [ 1*space_or_tab attributes ] [ 1*space_or_tab 1*'#' ] ] *space_or_tab
 //! ```
 //!
+//! The `attributes` part (such as `{#a .b}`) is only used when
+//! [`attributes`][crate::Constructs::attributes] is on, and only when it is
+//! at the end of the heading: otherwise it is part of `line`.
+//! It can also be used without `line`, resulting in an empty heading.
+//! See [`partial_directive`][crate::construct::partial_directive] for its
+//! grammar.
+//!
 //! As this construct occurs in flow, like all flow constructs, it must be
 //! followed by an eol (line ending) or eof (end of file).
 //!
@@ -16,6 +23,10 @@
 //! opening sequence and before text.
 //! In older markdown versions, this was not required, and headings would form
 //! without it.
+//! That behavior can be turned back on with
+//! [`space_required`][crate::HeadingAtxOptions::space_required], and the
+//! closing sequence can be turned off with
+//! [`closing_sequence`][crate::HeadingAtxOptions::closing_sequence].
 //!
 //! In markdown, it is also possible to create headings with a
 //! [heading (setext)][heading_setext] construct.
@@ -38,6 +49,11 @@
 //! Headings in markdown relate to the `<h1>` through `<h6>` elements in HTML.
 //! See [*§ 4.3.6 The `h1`, `h2`, `h3`, `h4`, `h5`, and `h6` elements* in the
 //! HTML spec][html] for more info.
+//! Attributes are added to the heading element, where an `id` in them is
+//! used instead of one generated with
+//! [`heading_ids`][crate::CompileOptions::heading_ids].
+//! Unsafe attributes are dropped, as with
+//! [paragraphs][crate::construct::paragraph].
 //!
 //! ## Recommendation
 //!
@@ -45,6 +61,7 @@
 //!
 //! ## Tokens
 //!
+//! *   [`Attributes`][Name::Attributes]
 //! *   [`HeadingAtx`][Name::HeadingAtx]
 //! *   [`HeadingAtxSequence`][Name::HeadingAtxSequence]
 //! *   [`HeadingAtxText`][Name::HeadingAtxText]
@@ -68,7 +85,7 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::{HEADING_ATX_OPENING_FENCE_SIZE_MAX, TAB_SIZE};
+use crate::util::constant::HEADING_ATX_OPENING_FENCE_SIZE_MAX;
 use alloc::vec;
 
 /// Start of a heading (atx).
@@ -86,7 +103,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -122,14 +139,20 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn sequence_open(tokenizer: &mut Tokenizer) -> State {
     if tokenizer.current == Some(b'#')
-        && tokenizer.tokenize_state.size < HEADING_ATX_OPENING_FENCE_SIZE_MAX
+        && tokenizer.tokenize_state.size
+            < HEADING_ATX_OPENING_FENCE_SIZE_MAX
+                .min(tokenizer.parse_state.options.max_heading_depth.into())
     {
         tokenizer.tokenize_state.size += 1;
         tokenizer.consume();
         State::Next(StateName::HeadingAtxSequenceOpen)
     }
     // Always at least one `#`.
-    else if matches!(tokenizer.current, None | Some(b'\t' | b'\n' | b' ')) {
+    // Whitespace is required after it, unless configured otherwise.
+    else if matches!(tokenizer.current, None | Some(b'\t' | b'\n' | b' '))
+        || (!tokenizer.parse_state.options.heading_atx.space_required
+            && tokenizer.current != Some(b'#'))
+    {
         tokenizer.tokenize_state.size = 0;
         tokenizer.exit(Name::HeadingAtxSequence);
         State::Retry(StateName::HeadingAtxAtBreak)
@@ -158,24 +181,44 @@ pub fn at_break(tokenizer: &mut Tokenizer) -> State {
             tokenizer.attempt(State::Next(StateName::HeadingAtxAtBreak), State::Nok);
             State::Retry(space_or_tab(tokenizer))
         }
-        Some(b'#') => {
+        Some(b'#') if tokenizer.parse_state.options.heading_atx.closing_sequence => {
             tokenizer.enter(Name::HeadingAtxSequence);
             State::Retry(StateName::HeadingAtxSequenceFurther)
         }
-        Some(_) => {
-            tokenizer.enter_link(
-                Name::Data,
-                Link {
-                    previous: None,
-                    next: None,
-                    content: Content::Text,
-                },
+        // Note: attributes must be preceded by whitespace, otherwise they’re
+        // just text.
+        Some(b'{')
+            if tokenizer.parse_state.options.constructs.attributes
+                && matches!(tokenizer.previous, Some(b'\t' | b' ')) =>
+        {
+            tokenizer.attempt(
+                State::Next(StateName::HeadingAtxAtBreak),
+                State::Next(StateName::HeadingAtxDataStart),
             );
-            State::Retry(StateName::HeadingAtxData)
+            State::Retry(StateName::HeadingAtxAttributesStart)
         }
+        Some(_) => State::Retry(StateName::HeadingAtxDataStart),
     }
 }
 
+/// At start of text.
+///
+/// ```markdown
+/// > | ## aa
+///        ^
+/// ```
+pub fn data_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.enter_link(
+        Name::Data,
+        Link {
+            previous: None,
+            next: None,
+            content: Content::Text,
+        },
+    );
+    State::Retry(StateName::HeadingAtxData)
+}
+
 /// In further sequence (after whitespace).
 ///
 /// Could be normal “visible” hashes in the heading or a final sequence.
@@ -214,6 +257,85 @@ pub fn data(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// At `{`, before attributes.
+///
+/// ```markdown
+/// > | ## aa {#b}
+///           ^
+/// ```
+pub fn attributes_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::HeadingAtx;
+    tokenizer.attempt(
+        State::Next(StateName::HeadingAtxAttributesAfter),
+        State::Next(StateName::HeadingAtxAttributesNok),
+    );
+    State::Retry(StateName::DirectiveAttributesStart)
+}
+
+/// After attributes.
+///
+/// Attributes are only used if they are at the end of the heading, that is,
+/// if only whitespace and a closing sequence follow.
+///
+/// ```markdown
+/// > | ## aa {#b}
+///               ^
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    tokenizer.check(State::Ok, State::Nok);
+    State::Retry(StateName::HeadingAtxAttributesEnd)
+}
+
+/// After attributes, at whitespace, closing sequence, or eol.
+///
+/// ```markdown
+/// > | ## aa {#b} ##
+///               ^^^
+/// ```
+pub fn attributes_end(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => State::Ok,
+        Some(b'\t' | b' ') => {
+            tokenizer.consume();
+            State::Next(StateName::HeadingAtxAttributesEnd)
+        }
+        Some(b'#')
+            if tokenizer.parse_state.options.heading_atx.closing_sequence
+                && matches!(tokenizer.previous, Some(b'\t' | b' ')) =>
+        {
+            State::Retry(StateName::HeadingAtxAttributesSequence)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// In closing sequence after attributes.
+///
+/// ```markdown
+/// > | ## aa {#b} ##
+///                ^^
+/// ```
+pub fn attributes_sequence(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'#') {
+        tokenizer.consume();
+        State::Next(StateName::HeadingAtxAttributesSequence)
+    } else {
+        State::Retry(StateName::BlankLineStart)
+    }
+}
+
+/// At something that wasn’t attributes.
+///
+/// ```markdown
+/// > | ## aa {<}
+///            ^
+/// ```
+pub fn attributes_nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    State::Nok
+}
+
 /// Resolve heading (atx).
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     let mut index = 0;
@@ -27,8 +27,9 @@
 //!
 //! [Thematic breaks][thematic_break] formed with dashes and without whitespace
 //! could be interpreted as a heading (setext).
-//! Which one forms depends on whether there is text directly in fron of the
-//! sequence.
+//! Which one forms depends on whether there is text directly in front of the
+//! sequence:
+//! see [`can_underline`][] for how that is decided.
 //!
 //! > 🏛 **Background**: the word *setext* originates from a small markup
 //! > language by Ian Feldman from 1991.
@@ -76,18 +77,29 @@ use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
-use crate::util::{constant::TAB_SIZE, skip};
+use crate::util::skip;
 use alloc::vec;
 
-/// At start of heading (setext) underline.
+/// Whether a heading (setext) underline can start here.
+///
+/// This is the precedence decision between a heading (setext) underline and
+/// the other constructs that a line such as `-` or `---` can form.
+/// An underline can only form when the construct is on, directly after a
+/// paragraph (content), and when the line is not lazy or piercing into a
+/// container.
+/// In that case, it takes precedence over a
+/// [thematic break][crate::construct::thematic_break] (`a\n---` is a heading), and a lone
+/// dash (`a\n-`) is a heading too, as an empty
+/// [list item][crate::construct::list_item] cannot interrupt a paragraph.
+/// Otherwise, those other constructs are tried.
 ///
 /// ```markdown
 ///   | aa
 /// > | ==
 ///     ^
 /// ```
-pub fn start(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.parse_state.options.constructs.heading_setext
+pub fn can_underline(tokenizer: &Tokenizer) -> bool {
+    tokenizer.parse_state.options.constructs.heading_setext
         && !tokenizer.lazy
         && !tokenizer.pierce
         // Require a paragraph before.
@@ -98,7 +110,17 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 &[Name::LineEnding, Name::SpaceOrTab],
             )]
             .name, Name::Content | Name::HeadingSetextUnderline))
-    {
+}
+
+/// At start of heading (setext) underline.
+///
+/// ```markdown
+///   | aa
+/// > | ==
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if can_underline(tokenizer) {
         tokenizer.enter(Name::HeadingSetextUnderline);
 
         if matches!(tokenizer.current, Some(b'\t' | b' ')) {
@@ -107,7 +129,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -129,7 +151,15 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn before(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
-        Some(b'-' | b'=') => {
+        // Equals signs form `<h1>`, dashes `<h2>`.
+        Some(b'-' | b'=')
+            if tokenizer.parse_state.options.max_heading_depth
+                >= if tokenizer.current == Some(b'=') {
+                    1
+                } else {
+                    2
+                } =>
+        {
             tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
             tokenizer.enter(Name::HeadingSetextUnderlineSequence);
             State::Retry(StateName::HeadingSetextInside)
@@ -242,6 +272,9 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
                 // Remove *including* HeadingSetextUnderline:Exit, until the line ending.
                 tokenizer.map.add(enter + 1, exit - enter, vec![]);
                 // Remove old Paragraph:Enter.
+                // Also change its type, so that a later underline that turns
+                // this paragraph into a heading finds the new start.
+                tokenizer.events[exit + 3].name = Name::Data;
                 tokenizer.map.add(exit + 3, 1, vec![]);
             } else {
                 // Swap type.
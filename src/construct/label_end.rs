@@ -181,17 +181,19 @@
 
 use crate::construct::partial_space_or_tab_eol::space_or_tab_eol;
 use crate::event::{Event, Kind, Name};
+use crate::message;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::{Label, LabelKind, LabelStart, Tokenizer};
+use crate::unist;
 use crate::util::{
-    constant::RESOURCE_DESTINATION_BALANCE_MAX,
+    constant::{LINK_REFERENCE_SIZE_MAX, RESOURCE_DESTINATION_BALANCE_MAX},
     normalize_identifier::normalize_identifier,
     skip,
     slice::{Position, Slice},
 };
-use alloc::{string::String, vec};
+use alloc::{boxed::Box, format, string::String, vec};
 
 /// Start of label end.
 ///
@@ -258,14 +260,22 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         tokenizer.events[tokenizer.tokenize_state.end].point.index,
     );
 
+    // Labels that are too long cannot match definitions.
+    // Not normalizing them also keeps lots of nested brackets fast.
+    let too_long = indices.1 - indices.0 > LINK_REFERENCE_SIZE_MAX;
+
     // We don’t care about virtual spaces, so `indices` and `as_str` are fine.
-    let mut id = normalize_identifier(
-        Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
-    );
+    let mut id = if too_long {
+        String::new()
+    } else {
+        normalize_identifier(
+            Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
+        )
+    };
 
     // See if this matches a footnote definition.
     if start.kind == LabelKind::GfmFootnote {
-        if tokenizer.parse_state.gfm_footnote_definitions.contains(&id) {
+        if !too_long && tokenizer.parse_state.gfm_footnote_definitions.contains(&id) {
             return State::Retry(StateName::LabelEndOk);
         }
 
@@ -277,7 +287,43 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         id = new_id;
     }
 
-    let defined = tokenizer.parse_state.definitions.contains(&id);
+    let defined = !too_long && tokenizer.parse_state.definitions.contains(&id);
+
+    // Warn about shortcut (`[a]`) and collapsed (`[a][]`) references that do
+    // not match a definition.
+    // Full references (`[a][b]`) are warned about when `[b]` is seen on its
+    // own, and resources (`[a](b)`) do not need a definition.
+    if !defined
+        && !too_long
+        && !id.is_empty()
+        && match tokenizer.current {
+            Some(b'(') => false,
+            Some(b'[') => tokenizer.parse_state.bytes.get(tokenizer.point.index + 1) == Some(&b']'),
+            _ => true,
+        }
+    {
+        let start = &tokenizer.tokenize_state.label_starts[start_index];
+        let mut label = String::new();
+        if start.kind == LabelKind::GfmUndefinedFootnote {
+            label.push('^');
+        }
+        label.push_str(
+            Slice::from_indices(tokenizer.parse_state.bytes, indices.0, indices.1).as_str(),
+        );
+
+        tokenizer.tokenize_state.messages.push(message::Message {
+            place: Some(Box::new(message::Place::Position(unist::Position {
+                start: tokenizer.events[start.start.0].point.to_unist(),
+                end: tokenizer.point.to_unist(),
+            }))),
+            reason: format!(
+                "Unexpected reference to undefined definition `{}`, expected a definition (such as `[{}]: https://example.com`)",
+                label, label
+            ),
+            rule_id: Box::new("undefined-reference".into()),
+            source: Box::new("markdown-rs".into()),
+        });
+    }
 
     match tokenizer.current {
         // Resource (`[asd](fgh)`)?
@@ -368,7 +414,7 @@ pub fn ok(tokenizer: &mut Tokenizer) -> State {
         end: (tokenizer.tokenize_state.end, tokenizer.events.len() - 1),
     });
     tokenizer.tokenize_state.end = 0;
-    tokenizer.register_resolver_before(ResolveName::Label);
+    tokenizer.register_resolver(ResolveName::Label);
     State::Ok
 }
 
@@ -605,6 +651,7 @@ pub fn reference_full_after(tokenizer: &mut Tokenizer) -> State {
                         &[Name::ReferenceString],
                     ),
                 ),
+                tokenizer.parse_state.tab_size,
             )
             .as_str(),
         ))
@@ -667,6 +714,25 @@ pub fn reference_collapsed_open(tokenizer: &mut Tokenizer) -> State {
     }
 }
 
+/// Add a label start.
+///
+/// When there are more open label starts than
+/// [`max_nesting_depth`][crate::ParseOptions::max_nesting_depth], the
+/// outermost one is turned into data.
+/// The innermost ones are kept, as closing brackets match those first, so
+/// labels that are not nested too deeply still form, even after stray
+/// brackets.
+pub fn push_start(tokenizer: &mut Tokenizer, label_start: LabelStart) {
+    tokenizer.tokenize_state.label_starts.push(label_start);
+
+    if let Some(max) = tokenizer.parse_state.options.max_nesting_depth {
+        if tokenizer.tokenize_state.label_starts.len() > max {
+            let start = tokenizer.tokenize_state.label_starts.remove(0);
+            tokenizer.tokenize_state.label_starts_loose.push(start);
+        }
+    }
+}
+
 /// Resolve images, links, and footnotes.
 ///
 /// This turns matching label starts and label ends into links, images, and
@@ -32,6 +32,7 @@
 //! [label_end]: crate::construct::label_end
 //! [html_img]: https://html.spec.whatwg.org/multipage/embedded-content.html#the-img-element
 
+use crate::construct::label_end::push_start;
 use crate::event::Name;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
@@ -108,12 +109,15 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
     {
         State::Nok
     } else {
-        tokenizer.tokenize_state.label_starts.push(LabelStart {
-            kind: LabelKind::Image,
-            start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
-            inactive: false,
-        });
-        tokenizer.register_resolver_before(ResolveName::Label);
+        push_start(
+            tokenizer,
+            LabelStart {
+                kind: LabelKind::Image,
+                start: (tokenizer.events.len() - 6, tokenizer.events.len() - 1),
+                inactive: false,
+            },
+        );
+        tokenizer.register_resolver(ResolveName::Label);
         State::Ok
     }
 }
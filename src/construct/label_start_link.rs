@@ -31,6 +31,7 @@
 //! [label_end]: crate::construct::label_end
 //! [html_a]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
 
+use crate::construct::label_end::push_start;
 use crate::event::Name;
 use crate::resolve::Name as ResolveName;
 use crate::state::State;
@@ -51,12 +52,15 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
         tokenizer.consume();
         tokenizer.exit(Name::LabelMarker);
         tokenizer.exit(Name::LabelLink);
-        tokenizer.tokenize_state.label_starts.push(LabelStart {
-            kind: LabelKind::Link,
-            start: (start, tokenizer.events.len() - 1),
-            inactive: false,
-        });
-        tokenizer.register_resolver_before(ResolveName::Label);
+        push_start(
+            tokenizer,
+            LabelStart {
+                kind: LabelKind::Link,
+                start: (start, tokenizer.events.len() - 1),
+                inactive: false,
+            },
+        );
+        tokenizer.register_resolver(ResolveName::Label);
         State::Ok
     } else {
         State::Nok
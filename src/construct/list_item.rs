@@ -16,6 +16,9 @@
 //! list_item_cont ::= [n(space_or_tab)]
 //! ```
 //!
+//! Which bullets and delimiters are allowed can be configured with
+//! [`list_item`][crate::ParseOptions::list_item].
+//!
 //! Further lines that are not prefixed with `list_item_cont` cause the list
 //! item to be exited, except when those lines are lazy continuation or blank.
 //! Like so many things in markdown, list items too are complex.
@@ -25,6 +28,29 @@
 //! As list item is a container, it takes several bytes from the start of the
 //! line, while the rest of the line includes more containers or flow.
 //!
+//! Lists are either loose or tight.
+//! A list is loose if one of these has a blank line:
+//!
+//! *   between two items of the list, directly in the list
+//!     (`* a␊␊* b`)
+//! *   between two things directly in an item of the list
+//!     (`* a␊␊  b`)
+//!
+//! Other blank lines do not make a list loose:
+//!
+//! *   right after the marker of an item that starts with a blank line
+//!     (`*␊  a`)
+//! *   after an empty block quote directly in an item (`* >␊* b`)
+//! *   at the end of the list (`* a␊␊␊b`)
+//! *   deeper in an item, such as in a sublist, block quote, or fenced
+//!     code (`* a␊  * b␊␊  * c`), which might make that sublist loose
+//!     instead
+//!
+//! A blank line before or after HTML (flow) in an item counts like any
+//! other blank line.
+//! But, depending on the kind of HTML (flow), a blank line can also be part
+//! of the HTML, in which case it is not between two things.
+//!
 //! ## HTML
 //!
 //! List item relates to the `<li>`, `<ol>`, and `<ul>` elements in HTML.
@@ -65,7 +91,7 @@ use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{LIST_ITEM_VALUE_SIZE_MAX, TAB_SIZE},
+    constant::LIST_ITEM_VALUE_SIZE_MAX,
     skip,
     slice::{Position, Slice},
 };
@@ -87,7 +113,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -107,11 +133,15 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn before(tokenizer: &mut Tokenizer) -> State {
+    let options = &tokenizer.parse_state.options.list_item;
+
     // Unordered.
-    if matches!(tokenizer.current, Some(b'*' | b'-')) {
+    if (tokenizer.current == Some(b'*') && options.bullet_asterisk)
+        || (tokenizer.current == Some(b'-') && options.bullet_dash)
+    {
         tokenizer.check(State::Nok, State::Next(StateName::ListItemBeforeUnordered));
         State::Retry(StateName::ThematicBreakStart)
-    } else if tokenizer.current == Some(b'+') {
+    } else if tokenizer.current == Some(b'+') && options.bullet_plus {
         State::Retry(StateName::ListItemBeforeUnordered)
     }
     // Ordered.
@@ -156,7 +186,10 @@ pub fn before_ordered(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn value(tokenizer: &mut Tokenizer) -> State {
-    if matches!(tokenizer.current, Some(b'.' | b')'))
+    let options = &tokenizer.parse_state.options.list_item;
+
+    if ((tokenizer.current == Some(b'.') && options.delimiter_period)
+        || (tokenizer.current == Some(b')') && options.delimiter_parenthesis))
         && (!tokenizer.interrupt || tokenizer.tokenize_state.size < 2)
     {
         tokenizer.exit(Name::ListItemValue);
@@ -232,7 +265,11 @@ pub fn marker_after_filled(tokenizer: &mut Tokenizer) -> State {
 /// ```
 pub fn whitespace(tokenizer: &mut Tokenizer) -> State {
     tokenizer.attempt(State::Next(StateName::ListItemWhitespaceAfter), State::Nok);
-    State::Retry(space_or_tab_min_max(tokenizer, 1, TAB_SIZE))
+    State::Retry(space_or_tab_min_max(
+        tokenizer,
+        1,
+        tokenizer.parse_state.tab_size,
+    ))
 }
 
 /// After acceptable whitespace.
@@ -291,6 +328,7 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
                 start: &tokenizer.events[start].point,
                 end: &tokenizer.point,
             },
+            tokenizer.parse_state.tab_size,
         )
         .len();
 
@@ -305,7 +343,7 @@ pub fn after(tokenizer: &mut Tokenizer) -> State {
         container.size = prefix;
 
         tokenizer.exit(Name::ListItemPrefix);
-        tokenizer.register_resolver_before(ResolveName::ListItem);
+        tokenizer.register_resolver(ResolveName::ListItem);
         State::Ok
     }
 }
@@ -383,7 +421,26 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
 
         if event.name == Name::ListItem {
             if event.kind == Kind::Enter {
-                let end = skip::opt(&tokenizer.events, index, &[Name::ListItem]) - 1;
+                // Find the exit of this item.
+                // Not with `skip::opt`, as that would also skip other items
+                // directly after it (such as after fenced code).
+                let mut end = index + 1;
+                let mut depth = 1;
+                while end < tokenizer.events.len() {
+                    if tokenizer.events[end].name == Name::ListItem {
+                        if tokenizer.events[end].kind == Kind::Enter {
+                            depth += 1;
+                        } else {
+                            depth -= 1;
+
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                    }
+
+                    end += 1;
+                }
                 let marker = skip::to(&tokenizer.events, index, &[Name::ListItemMarker]);
                 // Guaranteed to be a valid ASCII byte.
                 let marker = tokenizer.parse_state.bytes[tokenizer.events[marker].point.index];
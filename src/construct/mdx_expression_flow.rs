@@ -38,7 +38,6 @@ use crate::construct::partial_space_or_tab::{space_or_tab, space_or_tab_min_max}
 use crate::event::Name;
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
-use crate::util::constant::TAB_SIZE;
 
 /// Start of an MDX expression (flow).
 ///
@@ -55,7 +54,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -154,6 +153,8 @@ pub fn end(tokenizer: &mut Tokenizer) -> State {
                 State::Next(StateName::MdxExpressionFlowAfter),
                 State::Next(StateName::MdxExpressionFlowNok),
             );
+            // The tag is wrapped in a JSX tag, not in an expression.
+            tokenizer.tokenize_state.token_1 = Name::MdxJsxFlowTag;
             State::Retry(StateName::MdxJsxStart)
         }
         // // An expression.
@@ -62,6 +62,14 @@
 //!
 //! The following constructs are extensions found in markdown:
 //!
+//! *   [abbreviation][]
+//! *   [abbreviation definition][abbreviation_definition]
+//! *   [definition list][definition_list]
+//! *   [directive (container)][directive_container]
+//! *   [directive (leaf)][directive_leaf]
+//! *   [directive (text)][directive_text]
+//! *   [emoji][]
+//! *   [flow extension][flow_extension] (custom flow constructs)
 //! *   [frontmatter][]
 //! *   [gfm autolink literal][gfm_autolink_literal]
 //! *   [gfm footnote definition][gfm_footnote_definition]
@@ -73,12 +81,14 @@
 //! *   [mdx expression (text)][mdx_expression_text]
 //! *   [mdx jsx (flow)][mdx_jsx_flow]
 //! *   [mdx jsx (text)][mdx_jsx_text]
+//! *   [text extension][text_extension] (custom text constructs)
 //!
 //! There are also several small subroutines typically used in different places:
 //!
 //! *   [bom][partial_bom]
 //! *   [data][partial_data]
 //! *   [destination][partial_destination]
+//! *   [directive][partial_directive] (also attributes)
 //! *   [label][partial_label]
 //! *   [mdx expression][partial_mdx_expression]
 //! *   [mdx jsx][partial_mdx_jsx]
@@ -88,6 +98,94 @@
 //! *   [title][partial_title]
 //! *   [whitespace][partial_whitespace]
 //!
+//! ## Writing a construct
+//!
+//! Constructs are written as state machines: functions that get a
+//! [`Tokenizer`][crate::tokenizer::Tokenizer], look at its current byte
+//! (`tokenizer.current`, `None` at the end), and return a
+//! [`State`][crate::state::State]:
+//!
+//! *   `State::Next(name)` — the byte was consumed (`tokenizer.consume()`),
+//!     continue in `name` with the next byte
+//! *   `State::Retry(name)` — the byte was not consumed, continue in `name`
+//!     with the same byte
+//! *   `State::Ok` — the construct was found
+//! *   `State::Nok` — the construct was not found
+//!
+//! State functions are not passed around as closures: each one has a
+//! [`Name`][crate::state::Name], and is called through
+//! [`call`][crate::state::call].
+//! So adding a state means adding it to both of those.
+//!
+//! Whether something is a construct often is not known until later bytes.
+//! [`attempt`][crate::tokenizer::Tokenizer::attempt] stacks two states:
+//! when the next construct ends in `State::Ok`, its events are kept and the
+//! tokenizer moves to the first; when it ends in `State::Nok`, the tokenizer
+//! reverts to where the attempt started and moves to the second.
+//! [`check`][crate::tokenizer::Tokenizer::check] is the same, but reverts in
+//! both cases, to look ahead without keeping events.
+//! Content types (such as [text][]) use this to try each construct that can
+//! start at a byte, falling back to data.
+//!
+//! Some things can only be decided once everything is parsed, such as which
+//! attention sequences match.
+//! For that, a construct registers a [resolver][crate::resolve::Name] with
+//! [`register_resolver`][crate::tokenizer::Tokenizer::register_resolver],
+//! which is called with the tokenizer, and its events, when done.
+//!
+//! As an example, a (hypothetical) `==highlight==` construct in text, with
+//! states `HighlightStart`, `HighlightInside`, and `HighlightAfter`, and a
+//! `Highlight` event:
+//!
+//! ```rust ignore
+//! /// At `=`.
+//! pub fn start(tokenizer: &mut Tokenizer) -> State {
+//!     if tokenizer.current == Some(b'=') {
+//!         tokenizer.enter(Name::Highlight);
+//!         tokenizer.consume();
+//!         State::Next(StateName::HighlightInside)
+//!     } else {
+//!         State::Nok
+//!     }
+//! }
+//!
+//! /// After `=`, in the highlight.
+//! pub fn inside(tokenizer: &mut Tokenizer) -> State {
+//!     match tokenizer.current {
+//!         // Not closed.
+//!         None | Some(b'\n') => State::Nok,
+//!         Some(b'=') => {
+//!             tokenizer.consume();
+//!             State::Next(StateName::HighlightAfter)
+//!         }
+//!         _ => {
+//!             tokenizer.consume();
+//!             State::Next(StateName::HighlightInside)
+//!         }
+//!     }
+//! }
+//!
+//! /// After the closing `=`.
+//! pub fn after(tokenizer: &mut Tokenizer) -> State {
+//!     tokenizer.exit(Name::Highlight);
+//!     State::Ok
+//! }
+//!
+//! // In `text::before`, try it, and fall back to data if it fails:
+//! Some(b'=') => {
+//!     tokenizer.attempt(
+//!         State::Next(StateName::TextBefore),
+//!         State::Next(StateName::TextBeforeData),
+//!     );
+//!     State::Retry(StateName::HighlightStart)
+//! }
+//! ```
+//!
+//! > 👉 **Note**: these are internals, which change between versions.
+//! > To add custom constructs without changing `markdown-rs`, see
+//! > `flow_extensions` and `text_extensions` in
+//! > [`ParseOptions`][crate::configuration::ParseOptions].
+//!
 //! ## Grammar
 //!
 //! Each construct maintained here is explained with a BNF diagram.
@@ -148,6 +246,8 @@
 //!
 //! [bnf]: http://trevorjim.com/a-specification-for-markdown/
 
+pub mod abbreviation;
+pub mod abbreviation_definition;
 pub mod attention;
 pub mod autolink;
 pub mod blank_line;
@@ -157,8 +257,14 @@ pub mod character_reference;
 pub mod code_indented;
 pub mod content;
 pub mod definition;
+pub mod definition_list;
+pub mod directive_container;
+pub mod directive_leaf;
+pub mod directive_text;
 pub mod document;
+pub mod emoji;
 pub mod flow;
+pub mod flow_extension;
 pub mod frontmatter;
 pub mod gfm_autolink_literal;
 pub mod gfm_footnote_definition;
@@ -183,6 +289,7 @@ pub mod paragraph;
 pub mod partial_bom;
 pub mod partial_data;
 pub mod partial_destination;
+pub mod partial_directive;
 pub mod partial_label;
 pub mod partial_mdx_expression;
 pub mod partial_mdx_jsx;
@@ -195,4 +302,5 @@ pub mod raw_flow;
 pub mod raw_text;
 pub mod string;
 pub mod text;
+pub mod text_extension;
 pub mod thematic_break;
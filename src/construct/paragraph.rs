@@ -8,7 +8,7 @@
 //! ```bnf
 //! ; Restriction: lines cannot start other flow constructs.
 //! ; Restriction: lines cannot be blank.
-//! paragraph ::= 1*line *(eol 1*line)
+//! paragraph ::= 1*line *(eol 1*line) [ 1*space_or_tab attributes *space_or_tab ]
 //! ```
 //!
 //! This construct must be followed by an eol (line ending) or eof (end of
@@ -21,14 +21,28 @@
 //! That means that [autolinks][autolink], [code (text)][raw_text], etc are
 //! allowed.
 //!
+//! The `attributes` part (such as `{.a}`) is only used when
+//! [`attributes`][crate::Constructs::attributes] is on, and only when it is
+//! at the end of the last line of the paragraph: otherwise it is part of
+//! `line`.
+//! See [`partial_directive`][crate::construct::partial_directive] for its
+//! grammar.
+//!
 //! ## HTML
 //!
 //! Paragraphs in markdown relate to the `<p>` element in HTML.
 //! See [*§ 4.4.1 The `p` element* in the HTML spec][html] for more info.
+//! Attributes are added to the `<p>` element.
+//! Event handlers (`on*`) and `style` are always dropped; other attributes
+//! than `id`, `class`, `dir`, `lang`, and `title` are dropped unless
+//! [`allow_dangerous_html`][crate::CompileOptions::allow_dangerous_html] is on.
+//! Paragraphs in tight lists have no element, so their attributes are dropped.
 //!
 //! ## Tokens
 //!
+//! *   [`Attributes`][Name::Attributes]
 //! *   [`Paragraph`][Name::Paragraph]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
 //!
 //! ## References
 //!
@@ -41,6 +55,7 @@
 //! [raw_text]: crate::construct::raw_text
 //! [html]: https://html.spec.whatwg.org/multipage/grouping-content.html#the-p-element
 
+use crate::construct::partial_space_or_tab::space_or_tab;
 use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::link;
@@ -107,9 +122,115 @@ pub fn inside(tokenizer: &mut Tokenizer) -> State {
             tokenizer.exit(Name::Data);
             State::Next(StateName::ParagraphLineStart)
         }
+        // Note: attributes must be preceded by whitespace, otherwise they’re
+        // just text.
+        Some(b'{')
+            if tokenizer.parse_state.options.constructs.attributes
+                && matches!(tokenizer.previous, Some(b'\t' | b' ')) =>
+        {
+            tokenizer.check(
+                State::Next(StateName::ParagraphAttributesStart),
+                State::Next(StateName::ParagraphData),
+            );
+            State::Retry(StateName::ParagraphAttributesBefore)
+        }
         _ => {
             tokenizer.consume();
             State::Next(StateName::ParagraphInside)
         }
     }
 }
+
+/// In paragraph, at data.
+///
+/// ```markdown
+/// > | abc {<}
+///         ^
+/// ```
+pub fn data(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.consume();
+    State::Next(StateName::ParagraphInside)
+}
+
+/// At `{`, known to start attributes at the end of the paragraph.
+///
+/// ```markdown
+/// > | abc {.d}
+///         ^
+/// ```
+pub fn attributes_start(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.exit(Name::Data);
+    tokenizer.attempt(State::Next(StateName::ParagraphAfter), State::Nok);
+    State::Retry(StateName::ParagraphAttributesBefore)
+}
+
+/// At `{`, before attributes.
+///
+/// ```markdown
+/// > | abc {.d}
+///         ^
+/// ```
+pub fn attributes_before(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Paragraph;
+    tokenizer.attempt(
+        State::Next(StateName::ParagraphAttributesAfter),
+        State::Next(StateName::ParagraphAttributesNok),
+    );
+    State::Retry(StateName::DirectiveAttributesStart)
+}
+
+/// After attributes, at optional whitespace.
+///
+/// ```markdown
+/// > | abc {.d}
+///             ^
+/// ```
+pub fn attributes_after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+
+    if matches!(tokenizer.current, Some(b'\t' | b' ')) {
+        tokenizer.attempt(State::Next(StateName::ParagraphAttributesEnd), State::Nok);
+        State::Retry(space_or_tab(tokenizer))
+    } else {
+        State::Retry(StateName::ParagraphAttributesEnd)
+    }
+}
+
+/// After attributes and optional whitespace.
+///
+/// Attributes are only used if they are at the end of the paragraph.
+///
+/// ```markdown
+/// > | abc {.d}
+///             ^
+/// ```
+pub fn attributes_end(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current.is_none() {
+        State::Ok
+    } else {
+        State::Nok
+    }
+}
+
+/// At something that wasn’t attributes.
+///
+/// ```markdown
+/// > | abc {<}
+///          ^
+/// ```
+pub fn attributes_nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.token_1 = Name::Data;
+    State::Nok
+}
+
+/// After attributes, at the end of the paragraph.
+///
+/// ```markdown
+/// > | abc {.d}
+///             ^
+/// ```
+pub fn after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.connect = false;
+    tokenizer.exit(Name::Paragraph);
+    State::Ok
+}
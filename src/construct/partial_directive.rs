@@ -0,0 +1,711 @@
+//! Directive occurs in [directive (container)][directive_container],
+//! [directive (leaf)][directive_leaf], and [directive (text)][directive_text].
+//! Its attributes also occur on their own at the end of
+//! [heading (atx)][heading_atx] and [paragraph][paragraph].
+//!
+//! ## Grammar
+//!
+//! Directive forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: line endings are only allowed in directive (text).
+//! directive ::= name [label] [attributes]
+//!
+//! ; Restriction: cannot end in `-` or `_`.
+//! name ::= ascii_alphabetic *(ascii_alphanumeric | '-' | '_')
+//!
+//! ; Restriction: brackets must be balanced.
+//! label ::= '[' *(label_byte | label_escape) ']'
+//! label_byte ::= code - '\\'
+//! label_escape ::= '\\' ['[' | '\\' | ']']
+//!
+//! attributes ::= '{' *(whitespace | attribute) '}'
+//! attribute ::= id | class | key [*whitespace '=' *whitespace value]
+//! id ::= '#' 1*shortcut_byte
+//! class ::= '.' 1*shortcut_byte
+//! key ::= (ascii_alphabetic | ':' | '_') *(ascii_alphanumeric | ':' | '_' | '.' | '-')
+//! value ::= '"' *(byte - '"') '"' | "'" *(byte - "'") "'" | 1*unquoted_byte
+//! unquoted_byte ::= byte - '"' - "'" - '<' - '=' - '>' - '`' - '}' - space_or_tab - eol
+//! shortcut_byte ::= unquoted_byte - '#' - '.'
+//! whitespace ::= space_or_tab | eol
+//! ```
+//!
+//! The label is interpreted as the [text][] content type.
+//! That means that other phrasing constructs, such as
+//! [attention][crate::construct::attention], are allowed.
+//!
+//! Attribute values are not parsed further, but character references in them
+//! are decoded when compiling.
+//!
+//! Attributes at the end of headings and paragraphs use the same grammar, but
+//! their tokens are named `Attribute*` instead of `DirectiveAttribute*`.
+//! So that braces in prose (such as `Hello {name}` or `f(x) {return}`) stay
+//! text, they must also contain at least one attribute, and keys there must
+//! have a value.
+//!
+//! ## Tokens
+//!
+//! *   [`Attribute`][Name::Attribute]
+//! *   [`AttributeClassMarker`][Name::AttributeClassMarker]
+//! *   [`AttributeIdMarker`][Name::AttributeIdMarker]
+//! *   [`AttributeInitializerMarker`][Name::AttributeInitializerMarker]
+//! *   [`AttributeName`][Name::AttributeName]
+//! *   [`AttributeValue`][Name::AttributeValue]
+//! *   [`AttributeValueMarker`][Name::AttributeValueMarker]
+//! *   [`Attributes`][Name::Attributes]
+//! *   [`AttributesMarker`][Name::AttributesMarker]
+//! *   [`DirectiveAttribute`][Name::DirectiveAttribute]
+//! *   [`DirectiveAttributeClassMarker`][Name::DirectiveAttributeClassMarker]
+//! *   [`DirectiveAttributeIdMarker`][Name::DirectiveAttributeIdMarker]
+//! *   [`DirectiveAttributeInitializerMarker`][Name::DirectiveAttributeInitializerMarker]
+//! *   [`DirectiveAttributeName`][Name::DirectiveAttributeName]
+//! *   [`DirectiveAttributeValue`][Name::DirectiveAttributeValue]
+//! *   [`DirectiveAttributeValueMarker`][Name::DirectiveAttributeValueMarker]
+//! *   [`DirectiveAttributes`][Name::DirectiveAttributes]
+//! *   [`DirectiveAttributesMarker`][Name::DirectiveAttributesMarker]
+//! *   [`DirectiveLabel`][Name::DirectiveLabel]
+//! *   [`DirectiveLabelMarker`][Name::DirectiveLabelMarker]
+//! *   [`DirectiveLabelString`][Name::DirectiveLabelString]
+//! *   [`DirectiveName`][Name::DirectiveName]
+//! *   [`LineEnding`][Name::LineEnding]
+//! *   [`SpaceOrTab`][Name::SpaceOrTab]
+//!
+//! ## References
+//!
+//! *   [`micromark-extension-directive`](https://github.com/micromark/micromark-extension-directive)
+//! *   [Generic directives/plugins syntax](https://talk.commonmark.org/t/generic-directives-plugins-syntax/444)
+//!
+//! [directive_container]: crate::construct::directive_container
+//! [directive_leaf]: crate::construct::directive_leaf
+//! [directive_text]: crate::construct::directive_text
+//! [heading_atx]: crate::construct::heading_atx
+//! [paragraph]: crate::construct::paragraph
+//! [text]: crate::construct::text
+
+use crate::construct::partial_space_or_tab::space_or_tab;
+use crate::construct::partial_space_or_tab_eol::{
+    space_or_tab_eol, space_or_tab_eol_with_options, Options,
+};
+use crate::event::{Content, Link, Name};
+use crate::state::{Name as StateName, State};
+use crate::subtokenize::link;
+use crate::tokenizer::Tokenizer;
+
+/// Start of directive, at name.
+///
+/// ```markdown
+/// > | :a[b]{c}
+///      ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'A'..=b'Z' | b'a'..=b'z') => {
+            tokenizer.enter(Name::DirectiveName);
+            tokenizer.consume();
+            State::Next(StateName::DirectiveName)
+        }
+        _ => State::Nok,
+    }
+}
+
+/// In name.
+///
+/// ```markdown
+/// > | :ab[c]{d}
+///       ^
+/// ```
+pub fn name(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveName)
+        }
+        _ => {
+            if matches!(tokenizer.previous, Some(b'-' | b'_')) {
+                State::Nok
+            } else {
+                tokenizer.exit(Name::DirectiveName);
+                State::Retry(StateName::DirectiveNameAfter)
+            }
+        }
+    }
+}
+
+/// After name, at optional label.
+///
+/// ```markdown
+/// > | :a[b]{c}
+///       ^
+/// ```
+pub fn name_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'[') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveLabelAfter),
+            State::Next(StateName::DirectiveLabelAfter),
+        );
+        State::Retry(StateName::DirectiveLabelStart)
+    } else {
+        State::Retry(StateName::DirectiveLabelAfter)
+    }
+}
+
+/// After optional label, at optional attributes.
+///
+/// ```markdown
+/// > | :a[b]{c}
+///          ^
+/// ```
+pub fn label_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'{') {
+        tokenizer.attempt(
+            State::Next(StateName::DirectiveAfter),
+            State::Next(StateName::DirectiveAfter),
+        );
+        State::Retry(StateName::DirectiveAttributesStart)
+    } else {
+        State::Retry(StateName::DirectiveAfter)
+    }
+}
+
+/// After directive.
+///
+/// ```markdown
+/// > | :a[b]{c}
+///             ^
+/// ```
+pub fn after(_tokenizer: &mut Tokenizer) -> State {
+    State::Ok
+}
+
+/// Start of label.
+///
+/// ```markdown
+/// > | :a[b]
+///       ^
+/// ```
+pub fn label_start(tokenizer: &mut Tokenizer) -> State {
+    debug_assert_eq!(tokenizer.current, Some(b'['), "expected `[`");
+    tokenizer.enter(Name::DirectiveLabel);
+    tokenizer.enter(Name::DirectiveLabelMarker);
+    tokenizer.consume();
+    tokenizer.exit(Name::DirectiveLabelMarker);
+    State::Next(StateName::DirectiveLabelBefore)
+}
+
+/// After label start, at label string or the end of an empty label.
+///
+/// ```markdown
+/// > | :a[b]
+///        ^
+/// ```
+pub fn label_before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b']') {
+        tokenizer.enter(Name::DirectiveLabelMarker);
+        tokenizer.consume();
+        tokenizer.exit(Name::DirectiveLabelMarker);
+        tokenizer.exit(Name::DirectiveLabel);
+        State::Ok
+    } else {
+        tokenizer.enter(Name::DirectiveLabelString);
+        State::Retry(StateName::DirectiveLabelAtBreak)
+    }
+}
+
+/// In label, at something, before something else.
+///
+/// ```markdown
+/// > | :a[b]
+///        ^
+/// ```
+pub fn label_at_break(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None => State::Retry(StateName::DirectiveLabelNok),
+        Some(b'\n') => {
+            if tokenizer.tokenize_state.token_1 == Name::DirectiveText {
+                tokenizer.attempt(
+                    State::Next(StateName::DirectiveLabelEolAfter),
+                    State::Next(StateName::DirectiveLabelNok),
+                );
+                State::Retry(space_or_tab_eol_with_options(
+                    tokenizer,
+                    Options {
+                        content: Some(Content::Text),
+                        connect: tokenizer.tokenize_state.connect,
+                    },
+                ))
+            } else {
+                State::Retry(StateName::DirectiveLabelNok)
+            }
+        }
+        Some(b']') if tokenizer.tokenize_state.size == 0 => {
+            tokenizer.exit(Name::DirectiveLabelString);
+            tokenizer.enter(Name::DirectiveLabelMarker);
+            tokenizer.consume();
+            tokenizer.exit(Name::DirectiveLabelMarker);
+            tokenizer.exit(Name::DirectiveLabel);
+            tokenizer.tokenize_state.connect = false;
+            State::Ok
+        }
+        _ => {
+            tokenizer.enter_link(
+                Name::Data,
+                Link {
+                    previous: None,
+                    next: None,
+                    content: Content::Text,
+                },
+            );
+
+            if tokenizer.tokenize_state.connect {
+                let index = tokenizer.events.len() - 1;
+                link(&mut tokenizer.events, index);
+            } else {
+                tokenizer.tokenize_state.connect = true;
+            }
+
+            State::Retry(StateName::DirectiveLabelInside)
+        }
+    }
+}
+
+/// In label, after whitespace.
+///
+/// ```markdown
+///   | :a[b␊
+/// > | c]
+///     ^
+/// ```
+pub fn label_eol_after(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.connect = true;
+    State::Retry(StateName::DirectiveLabelAtBreak)
+}
+
+/// In label, on something disallowed.
+///
+/// ```markdown
+/// > | :a[b
+///         ^
+/// ```
+pub fn label_nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.connect = false;
+    tokenizer.tokenize_state.size = 0;
+    State::Nok
+}
+
+/// In label, in text.
+///
+/// ```markdown
+/// > | :a[b]
+///        ^
+/// ```
+pub fn label_inside(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.exit(Name::Data);
+            State::Retry(StateName::DirectiveLabelAtBreak)
+        }
+        Some(b']') if tokenizer.tokenize_state.size == 0 => {
+            tokenizer.exit(Name::Data);
+            State::Retry(StateName::DirectiveLabelAtBreak)
+        }
+        Some(byte) => {
+            if byte == b'[' {
+                tokenizer.tokenize_state.size += 1;
+            } else if byte == b']' {
+                tokenizer.tokenize_state.size -= 1;
+            }
+
+            tokenizer.consume();
+            State::Next(if byte == b'\\' {
+                StateName::DirectiveLabelEscape
+            } else {
+                StateName::DirectiveLabelInside
+            })
+        }
+    }
+}
+
+/// After `\`, at a special character.
+///
+/// ```markdown
+/// > | :a[b\]c]
+///          ^
+/// ```
+pub fn label_escape(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[' | b'\\' | b']') => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveLabelInside)
+        }
+        _ => State::Retry(StateName::DirectiveLabelInside),
+    }
+}
+
+/// Start of attributes.
+///
+/// ```markdown
+/// > | :a{b}
+///       ^
+/// ```
+pub fn attributes_start(tokenizer: &mut Tokenizer) -> State {
+    debug_assert_eq!(tokenizer.current, Some(b'{'), "expected `{{`");
+    tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributes));
+    tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributesMarker));
+    tokenizer.consume();
+    tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributesMarker));
+    State::Next(StateName::DirectiveAttributesBetween)
+}
+
+/// In attributes, before an attribute, whitespace, or the end.
+///
+/// ```markdown
+/// > | :a{b c}
+///        ^^ ^
+/// ```
+pub fn attributes_between(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'#' | b'.') => {
+            let name = attribute_name(
+                tokenizer,
+                if tokenizer.current == Some(b'#') {
+                    Name::DirectiveAttributeIdMarker
+                } else {
+                    Name::DirectiveAttributeClassMarker
+                },
+            );
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttribute));
+            tokenizer.enter(name.clone());
+            tokenizer.consume();
+            tokenizer.exit(name);
+            State::Next(StateName::DirectiveAttributesShortcutStart)
+        }
+        Some(b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z') => {
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttribute));
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributeName));
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesName)
+        }
+        // Attributes at the end of headings and paragraphs cannot be empty.
+        Some(b'}') if !trailing(tokenizer) || has_attribute(tokenizer) => {
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributesMarker));
+            tokenizer.consume();
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributesMarker));
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributes));
+            State::Ok
+        }
+        Some(b'\t' | b'\n' | b' ') if whitespace_allowed(tokenizer) => {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveAttributesBetween),
+                State::Next(StateName::DirectiveAttributesNok),
+            );
+            State::Retry(whitespace(tokenizer))
+        }
+        _ => State::Retry(StateName::DirectiveAttributesNok),
+    }
+}
+
+/// After `#` or `.`, at shortcut value.
+///
+/// ```markdown
+/// > | :a{#b}
+///         ^
+/// ```
+pub fn attributes_shortcut_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None
+        | Some(
+            b'\t' | b'\n' | b' ' | b'"' | b'#' | b'\'' | b'.' | b'<' | b'=' | b'>' | b'`' | b'}',
+        ) => State::Retry(StateName::DirectiveAttributesNok),
+        Some(_) => {
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesShortcut)
+        }
+    }
+}
+
+/// In shortcut value.
+///
+/// ```markdown
+/// > | :a{#bc}
+///          ^
+/// ```
+pub fn attributes_shortcut(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'"' | b'\'' | b'<' | b'=' | b'>' | b'`') => {
+            State::Retry(StateName::DirectiveAttributesNok)
+        }
+        Some(b'\t' | b'\n' | b' ' | b'#' | b'.' | b'}') => {
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttribute));
+            State::Retry(StateName::DirectiveAttributesBetween)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesShortcut)
+        }
+    }
+}
+
+/// In attribute name.
+///
+/// ```markdown
+/// > | :a{bc=d}
+///         ^
+/// ```
+pub fn attributes_name(tokenizer: &mut Tokenizer) -> State {
+    if matches!(
+        tokenizer.current,
+        Some(b'-' | b'.' | b'0'..=b'9' | b':' | b'A'..=b'Z' | b'_' | b'a'..=b'z')
+    ) {
+        tokenizer.consume();
+        State::Next(StateName::DirectiveAttributesName)
+    } else {
+        tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributeName));
+
+        if matches!(tokenizer.current, Some(b'\t' | b'\n' | b' ')) && whitespace_allowed(tokenizer)
+        {
+            tokenizer.attempt(
+                State::Next(StateName::DirectiveAttributesNameAfter),
+                State::Next(StateName::DirectiveAttributesNok),
+            );
+            State::Retry(whitespace(tokenizer))
+        } else {
+            State::Retry(StateName::DirectiveAttributesNameAfter)
+        }
+    }
+}
+
+/// After attribute name, at optional initializer.
+///
+/// ```markdown
+/// > | :a{b=c}
+///         ^
+/// ```
+pub fn attributes_name_after(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(b'=') {
+        tokenizer.enter(attribute_name(
+            tokenizer,
+            Name::DirectiveAttributeInitializerMarker,
+        ));
+        tokenizer.consume();
+        tokenizer.exit(attribute_name(
+            tokenizer,
+            Name::DirectiveAttributeInitializerMarker,
+        ));
+        State::Next(StateName::DirectiveAttributesValueBefore)
+    } else if trailing(tokenizer) {
+        // Keys w/o value are not allowed at the end of headings and paragraphs.
+        State::Retry(StateName::DirectiveAttributesNok)
+    } else {
+        tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttribute));
+        State::Retry(StateName::DirectiveAttributesBetween)
+    }
+}
+
+/// After initializer, at attribute value.
+///
+/// ```markdown
+/// > | :a{b=c}
+///          ^
+/// ```
+pub fn attributes_value_before(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'<' | b'=' | b'>' | b'`' | b'}') => {
+            State::Retry(StateName::DirectiveAttributesNok)
+        }
+        Some(b'"' | b'\'') => {
+            tokenizer.tokenize_state.marker = tokenizer.current.unwrap();
+            tokenizer.enter(attribute_name(
+                tokenizer,
+                Name::DirectiveAttributeValueMarker,
+            ));
+            tokenizer.consume();
+            tokenizer.exit(attribute_name(
+                tokenizer,
+                Name::DirectiveAttributeValueMarker,
+            ));
+            State::Next(StateName::DirectiveAttributesValueQuotedStart)
+        }
+        Some(b'\t' | b'\n' | b' ') => {
+            if whitespace_allowed(tokenizer) {
+                tokenizer.attempt(
+                    State::Next(StateName::DirectiveAttributesValueBefore),
+                    State::Next(StateName::DirectiveAttributesNok),
+                );
+                State::Retry(whitespace(tokenizer))
+            } else {
+                State::Retry(StateName::DirectiveAttributesNok)
+            }
+        }
+        Some(_) => {
+            tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueUnquoted)
+        }
+    }
+}
+
+/// In unquoted attribute value.
+///
+/// ```markdown
+/// > | :a{b=cd}
+///           ^
+/// ```
+pub fn attributes_value_unquoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'"' | b'\'' | b'<' | b'=' | b'>' | b'`') => {
+            State::Retry(StateName::DirectiveAttributesNok)
+        }
+        Some(b'\t' | b'\n' | b' ' | b'}') => {
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttribute));
+            State::Retry(StateName::DirectiveAttributesBetween)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueUnquoted)
+        }
+    }
+}
+
+/// After opening quote or at closing quote of attribute value.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///           ^
+/// ```
+pub fn attributes_value_quoted_start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.current == Some(tokenizer.tokenize_state.marker) {
+        tokenizer.tokenize_state.marker = 0;
+        tokenizer.enter(attribute_name(
+            tokenizer,
+            Name::DirectiveAttributeValueMarker,
+        ));
+        tokenizer.consume();
+        tokenizer.exit(attribute_name(
+            tokenizer,
+            Name::DirectiveAttributeValueMarker,
+        ));
+        tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttribute));
+        State::Next(StateName::DirectiveAttributesValueQuotedAfter)
+    } else {
+        tokenizer.enter(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+        State::Retry(StateName::DirectiveAttributesValueQuoted)
+    }
+}
+
+/// In quoted attribute value.
+///
+/// ```markdown
+/// > | :a{b="cd"}
+///            ^
+/// ```
+pub fn attributes_value_quoted(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None => State::Retry(StateName::DirectiveAttributesNok),
+        Some(b'\n') if !whitespace_allowed(tokenizer) => {
+            State::Retry(StateName::DirectiveAttributesNok)
+        }
+        Some(byte) if byte == tokenizer.tokenize_state.marker => {
+            tokenizer.exit(attribute_name(tokenizer, Name::DirectiveAttributeValue));
+            State::Retry(StateName::DirectiveAttributesValueQuotedStart)
+        }
+        Some(_) => {
+            tokenizer.consume();
+            State::Next(StateName::DirectiveAttributesValueQuoted)
+        }
+    }
+}
+
+/// After quoted attribute value.
+///
+/// ```markdown
+/// > | :a{b="c"}
+///             ^
+/// ```
+pub fn attributes_value_quoted_after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'\t' | b'\n' | b' ' | b'}') => State::Retry(StateName::DirectiveAttributesBetween),
+        _ => State::Retry(StateName::DirectiveAttributesNok),
+    }
+}
+
+/// In attributes, on something disallowed.
+///
+/// ```markdown
+/// > | :a{b<c}
+///         ^
+/// ```
+pub fn attributes_nok(tokenizer: &mut Tokenizer) -> State {
+    tokenizer.tokenize_state.marker = 0;
+    State::Nok
+}
+
+/// Whether whitespace, including line endings, can occur in attributes.
+///
+/// Line endings are only allowed in directive (text), so this returns
+/// `false` for line endings in directive (container) and directive (leaf).
+fn whitespace_allowed(tokenizer: &Tokenizer) -> bool {
+    tokenizer.current != Some(b'\n') || tokenizer.tokenize_state.token_1 == Name::DirectiveText
+}
+
+/// Whitespace in attributes.
+fn whitespace(tokenizer: &mut Tokenizer) -> StateName {
+    if tokenizer.tokenize_state.token_1 == Name::DirectiveText {
+        space_or_tab_eol(tokenizer)
+    } else {
+        space_or_tab(tokenizer)
+    }
+}
+
+/// Whether these are attributes at the end of a heading or paragraph, instead
+/// of those of a directive.
+fn trailing(tokenizer: &Tokenizer) -> bool {
+    !matches!(
+        tokenizer.tokenize_state.token_1,
+        Name::DirectiveContainer | Name::DirectiveLeaf | Name::DirectiveText
+    )
+}
+
+/// Whether an attribute was found in the current attributes.
+fn has_attribute(tokenizer: &Tokenizer) -> bool {
+    let mut index = tokenizer.events.len();
+
+    while index > 0 {
+        index -= 1;
+
+        match tokenizer.events[index].name {
+            Name::Attribute => return true,
+            Name::Attributes => return false,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Get the name to use for a token in attributes.
+///
+/// Directives use the `Directive*` names, attributes at the end of
+/// [heading (atx)][crate::construct::heading_atx] and
+/// [paragraph][crate::construct::paragraph] use the `Attribute*` names.
+fn attribute_name(tokenizer: &Tokenizer, name: Name) -> Name {
+    if !trailing(tokenizer) {
+        return name;
+    }
+
+    match name {
+        Name::DirectiveAttribute => Name::Attribute,
+        Name::DirectiveAttributeClassMarker => Name::AttributeClassMarker,
+        Name::DirectiveAttributeIdMarker => Name::AttributeIdMarker,
+        Name::DirectiveAttributeInitializerMarker => Name::AttributeInitializerMarker,
+        Name::DirectiveAttributeName => Name::AttributeName,
+        Name::DirectiveAttributeValue => Name::AttributeValue,
+        Name::DirectiveAttributeValueMarker => Name::AttributeValueMarker,
+        Name::DirectiveAttributes => Name::Attributes,
+        Name::DirectiveAttributesMarker => Name::AttributesMarker,
+        _ => unreachable!("expected attributes name"),
+    }
+}
@@ -14,11 +14,18 @@
 //! occurs at the start or end of the whole.
 //!
 //! Normally this whitespace is ignored.
+//! It is not dropped from the events though: it is turned into
+//! [`SpaceOrTab`][Name::SpaceOrTab] (or
+//! [`HardBreakTrailing`][Name::HardBreakTrailing]) events, with exact
+//! positions (where tabs are expanded in columns), so tools that need the
+//! original whitespace can get it from the source.
 //! In the case of text content, whitespace before a line ending that
 //! consistents solely of spaces, at least 2, forms a hard break (trailing).
 //!
-//! The minimum number of those spaces is defined in
-//! [`HARD_BREAK_PREFIX_SIZE_MIN`][].
+//! The minimum number of those spaces defaults to
+//! [`HARD_BREAK_PREFIX_SIZE_MIN`][hard_break_prefix_size_min], and can be
+//! configured with
+//! [`hard_break_trailing_size_min`][crate::ParseOptions::hard_break_trailing_size_min].
 //!
 //! It is also possible to create a hard break with a similar construct: a
 //! [hard break (escape)][hard_break_escape] is a backslash followed
@@ -57,22 +64,27 @@
 //! [hard_break_prefix_size_min]: crate::util::constant::HARD_BREAK_PREFIX_SIZE_MIN
 //! [html]: https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-br-element
 
-use crate::event::{Event, Kind, Name};
+use crate::event::{Event, Kind, Name, Point};
 use crate::tokenizer::Tokenizer;
-use crate::util::{
-    constant::HARD_BREAK_PREFIX_SIZE_MIN,
-    slice::{Position, Slice},
-};
+use crate::util::slice::{Position, Slice};
 use alloc::vec;
 
 /// Resolve whitespace.
-pub fn resolve_whitespace(tokenizer: &mut Tokenizer, hard_break: bool, trim_whole: bool) {
+///
+/// `hard_break` is the number of spaces needed to form a hard break
+/// (trailing), or `None` if they cannot form.
+pub fn resolve_whitespace(tokenizer: &mut Tokenizer, hard_break: Option<usize>, trim_whole: bool) {
     let mut index = 0;
 
     while index < tokenizer.events.len() {
         let event = &tokenizer.events[index];
 
-        if event.kind == Kind::Exit && event.name == Name::Data {
+        // Linked data (such as a directive label) is subcontent: it is
+        // trimmed when it is tokenized itself.
+        if event.kind == Kind::Exit
+            && event.name == Name::Data
+            && tokenizer.events[index - 1].link.is_none()
+        {
             let trim_start = (trim_whole && index == 1)
                 || (index > 1 && tokenizer.events[index - 2].name == Name::LineEnding);
             let trim_end = (trim_whole && index == tokenizer.events.len() - 1)
@@ -94,12 +106,15 @@ fn trim_data(
     exit_index: usize,
     trim_start: bool,
     trim_end: bool,
-    hard_break: bool,
+    hard_break: Option<usize>,
 ) {
     let mut slice = Slice::from_position(
         tokenizer.parse_state.bytes,
         &Position::from_exit_event(&tokenizer.events, exit_index),
+        tokenizer.parse_state.tab_size,
     );
+    // Index of the first byte in `slice`.
+    let start = tokenizer.events[exit_index - 1].point.index + usize::from(slice.before > 0);
 
     if trim_end {
         let mut index = slice.bytes.len();
@@ -115,9 +130,8 @@ fn trim_data(
         }
 
         let diff = slice.bytes.len() - index;
-        let name = if hard_break
+        let name = if hard_break.map_or(false, |size| diff >= size.max(1))
             && spaces_only
-            && diff >= HARD_BREAK_PREFIX_SIZE_MIN
             && exit_index + 1 < tokenizer.events.len()
         {
             Name::HardBreakTrailing
@@ -135,10 +149,12 @@ fn trim_data(
 
         if diff > 0 || slice.after > 0 {
             let exit_point = tokenizer.events[exit_index].point.clone();
-            let mut enter_point = exit_point.clone();
-            enter_point.index -= diff;
-            enter_point.column -= diff;
-            enter_point.vs = 0;
+            let enter_point = point_at(
+                tokenizer.parse_state.bytes,
+                &tokenizer.events[exit_index - 1].point,
+                start + index,
+                tokenizer.parse_state.tab_size,
+            );
 
             tokenizer.map.add(
                 exit_index + 1,
@@ -183,10 +199,12 @@ fn trim_data(
 
         if index > 0 || slice.before > 0 {
             let enter_point = tokenizer.events[exit_index - 1].point.clone();
-            let mut exit_point = enter_point.clone();
-            exit_point.index += index;
-            exit_point.column += index;
-            exit_point.vs = 0;
+            let exit_point = point_at(
+                tokenizer.parse_state.bytes,
+                &enter_point,
+                start + index,
+                tokenizer.parse_state.tab_size,
+            );
 
             tokenizer.map.add(
                 exit_index - 1,
@@ -211,3 +229,20 @@ fn trim_data(
         }
     }
 }
+
+/// Get the point at `index`, from an earlier `point` on the same line.
+///
+/// Tabs are expanded, like they are when tokenizing, so that the columns of
+/// whitespace are exact.
+fn point_at(bytes: &[u8], point: &Point, index: usize, tab_size: usize) -> Point {
+    let mut start = point.clone();
+    // Go back to the start of a partially used tab.
+    start.column -= start.vs;
+    start.vs = 0;
+
+    if index > start.index {
+        start.shift_to(bytes, index, tab_size)
+    } else {
+        start
+    }
+}
@@ -71,6 +71,9 @@
 //! For that, the math is compiled as a `<pre>`, and a `<code>` element with two
 //! classes: `language-math` and `math-display`.
 //! Client side JavaScript can look for these classes to process them further.
+//! The element and the first class can be changed with
+//! [`math_tag_name`][crate::CompileOptions::math_tag_name] and
+//! [`math_class_name`][crate::CompileOptions::math_class_name].
 //!
 //! The `info` is, when rendering to HTML, typically exposed as a class.
 //! This behavior stems from the HTML spec ([*§ 4.5.15 The `code`
@@ -143,7 +146,7 @@ use crate::event::{Content, Link, Name};
 use crate::state::{Name as StateName, State};
 use crate::tokenizer::Tokenizer;
 use crate::util::{
-    constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN, TAB_SIZE},
+    constant::{CODE_FENCED_SEQUENCE_SIZE_MIN, MATH_FLOW_SEQUENCE_SIZE_MIN},
     slice::{Position, Slice},
 };
 
@@ -168,7 +171,7 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
                 tokenizer,
                 0,
                 if tokenizer.parse_state.options.constructs.code_indented {
-                    TAB_SIZE - 1
+                    tokenizer.parse_state.tab_size - 1
                 } else {
                     usize::MAX
                 },
@@ -200,6 +203,7 @@ pub fn before_sequence_open(tokenizer: &mut Tokenizer) -> State {
             prefix = Slice::from_position(
                 tokenizer.parse_state.bytes,
                 &Position::from_exit_event(&tokenizer.events, tokenizer.events.len() - 1),
+                tokenizer.parse_state.tab_size,
             )
             .len();
         }
@@ -475,7 +479,7 @@ pub fn close_start(tokenizer: &mut Tokenizer) -> State {
             tokenizer,
             0,
             if tokenizer.parse_state.options.constructs.code_indented {
-                TAB_SIZE - 1
+                tokenizer.parse_state.tab_size - 1
             } else {
                 usize::MAX
             },
@@ -75,6 +75,9 @@
 //! For that, the math is compiled as a `<code>` element with two classes:
 //! `language-math` and `math-inline`.
 //! Client side JavaScript can look for these classes to process them further.
+//! The element and the first class can be changed with
+//! [`math_tag_name`][crate::CompileOptions::math_tag_name] and
+//! [`math_class_name`][crate::CompileOptions::math_class_name].
 //!
 //! When turning markdown into HTML, each line ending in raw (text) is turned
 //! into a space.
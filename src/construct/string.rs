@@ -76,6 +76,6 @@ pub fn before_data(tokenizer: &mut Tokenizer) -> State {
 
 /// Resolve whitespace in string.
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
-    resolve_whitespace(tokenizer, false, false);
+    resolve_whitespace(tokenizer, None, false);
     None
 }
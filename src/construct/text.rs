@@ -6,10 +6,13 @@
 //!
 //! The constructs found in text are:
 //!
+//! *   [Abbreviation][crate::construct::abbreviation]
 //! *   [Attention][crate::construct::attention] (emphasis, gfm strikethrough, strong)
 //! *   [Autolink][crate::construct::autolink]
 //! *   [Character escape][crate::construct::character_escape]
 //! *   [Character reference][crate::construct::character_reference]
+//! *   [Directive (text)][crate::construct::directive_text]
+//! *   [Emoji][crate::construct::emoji]
 //! *   [Raw (text)][crate::construct::raw_text] (code (text), math (text))
 //! *   [GFM: Label start (footnote)][crate::construct::gfm_label_start_footnote]
 //! *   [GFM: Task list item check][crate::construct::gfm_task_list_item_check]
@@ -20,35 +23,42 @@
 //! *   [Label end][crate::construct::label_end]
 //! *   [MDX: expression (text)][crate::construct::mdx_expression_text]
 //! *   [MDX: JSX (text)][crate::construct::mdx_jsx_text]
+//! *   [Text extension][crate::construct::text_extension] (custom text constructs)
 //!
 //! > 👉 **Note**: for performance reasons, hard break (trailing) is formed by
 //! > [whitespace][crate::construct::partial_whitespace].
 
+use crate::construct::abbreviation::resolve as resolve_abbreviation;
+use crate::construct::emoji::resolve as resolve_emoji;
 use crate::construct::gfm_autolink_literal::resolve as resolve_gfm_autolink_literal;
 use crate::construct::partial_whitespace::resolve_whitespace;
+use crate::construct::text_extension::is_marker as is_text_extension_marker;
 use crate::resolve::Name as ResolveName;
 use crate::state::{Name as StateName, State};
 use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 
 /// Characters that can start something in text.
-const MARKERS: [u8; 16] = [
+pub const MARKERS: [u8; 19] = [
     b'!',  // `label_start_image`
     b'$',  // `raw_text` (math (text))
     b'&',  // `character_reference`
     b'*',  // `attention` (emphasis, strong)
+    b':',  // `directive_text`
     b'<',  // `autolink`, `html_text`, `mdx_jsx_text`
+    b'=',  // `attention` (highlight)
     b'H',  // `gfm_autolink_literal` (`protocol` kind)
     b'W',  // `gfm_autolink_literal` (`www.` kind)
     b'[',  // `label_start_link`
     b'\\', // `character_escape`, `hard_break_escape`
     b']',  // `label_end`, `gfm_label_start_footnote`
+    b'^',  // `attention` (superscript)
     b'_',  // `attention` (emphasis, strong)
     b'`',  // `raw_text` (code (text))
     b'h',  // `gfm_autolink_literal` (`protocol` kind)
     b'w',  // `gfm_autolink_literal` (`www.` kind)
     b'{',  // `mdx_expression_text`
-    b'~',  // `attention` (gfm strikethrough)
+    b'~',  // `attention` (gfm strikethrough, subscript)
 ];
 
 /// Start of text.
@@ -62,7 +72,17 @@ const MARKERS: [u8; 16] = [
 ///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    tokenizer.tokenize_state.markers = &MARKERS;
+    // Plain text: there are no markers, so data eats everything.
+    if tokenizer.parse_state.options.plain_text {
+        tokenizer.tokenize_state.markers = &[];
+        return State::Retry(StateName::TextBeforeData);
+    }
+
+    tokenizer.tokenize_state.markers = if tokenizer.parse_state.text_markers.is_empty() {
+        &MARKERS
+    } else {
+        &tokenizer.parse_state.text_markers
+    };
     tokenizer.attempt(
         State::Next(StateName::TextBefore),
         State::Next(StateName::TextBefore),
@@ -77,6 +97,27 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///     ^
 /// ```
 pub fn before(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer
+        .current
+        .map_or(false, |byte| is_text_extension_marker(tokenizer, byte))
+    {
+        tokenizer.attempt(
+            State::Next(StateName::TextBefore),
+            State::Next(StateName::TextBeforeBuiltIn),
+        );
+        State::Retry(StateName::TextExtensionStart)
+    } else {
+        State::Retry(StateName::TextBeforeBuiltIn)
+    }
+}
+
+/// At built-in constructs, after text extensions.
+///
+/// ```markdown
+/// > | abc
+///     ^
+/// ```
+pub fn before_built_in(tokenizer: &mut Tokenizer) -> State {
     match tokenizer.current {
         None => {
             tokenizer.register_resolver(ResolveName::Data);
@@ -105,14 +146,22 @@ pub fn before(tokenizer: &mut Tokenizer) -> State {
             );
             State::Retry(StateName::CharacterReferenceStart)
         }
-        // attention (emphasis, gfm strikethrough, strong)
-        Some(b'*' | b'_' | b'~') => {
+        // attention (emphasis, gfm strikethrough, highlight, strong,
+        // subscript, superscript)
+        Some(b'*' | b'=' | b'^' | b'_' | b'~') => {
             tokenizer.attempt(
                 State::Next(StateName::TextBefore),
                 State::Next(StateName::TextBeforeData),
             );
             State::Retry(StateName::AttentionStart)
         }
+        Some(b':') => {
+            tokenizer.attempt(
+                State::Next(StateName::TextBefore),
+                State::Next(StateName::TextBeforeData),
+            );
+            State::Retry(StateName::DirectiveTextStart)
+        }
         // `autolink`, `html_text` (order does not matter), `mdx_jsx_text` (order matters).
         Some(b'<') => {
             tokenizer.attempt(
@@ -246,7 +295,13 @@ pub fn before_data(tokenizer: &mut Tokenizer) -> State {
 pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
     resolve_whitespace(
         tokenizer,
-        tokenizer.parse_state.options.constructs.hard_break_trailing,
+        if tokenizer.parse_state.options.constructs.hard_break_trailing
+            && !tokenizer.parse_state.options.plain_text
+        {
+            Some(tokenizer.parse_state.options.hard_break_trailing_size_min)
+        } else {
+            None
+        },
         true,
     );
 
@@ -255,10 +310,27 @@ pub fn resolve(tokenizer: &mut Tokenizer) -> Option<Subresult> {
         .options
         .constructs
         .gfm_autolink_literal
+        && tokenizer
+            .parse_state
+            .options
+            .constructs
+            .gfm_autolink_literal_email
+        && !tokenizer.parse_state.options.plain_text
     {
         resolve_gfm_autolink_literal(tokenizer);
     }
 
+    if tokenizer.parse_state.options.constructs.emoji && !tokenizer.parse_state.options.plain_text {
+        resolve_emoji(tokenizer);
+    }
+
+    if tokenizer.parse_state.options.constructs.abbreviation
+        && !tokenizer.parse_state.options.plain_text
+        && !tokenizer.parse_state.abbreviations.is_empty()
+    {
+        resolve_abbreviation(tokenizer);
+    }
+
     tokenizer.map.consume(&mut tokenizer.events);
     None
 }
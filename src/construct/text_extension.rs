@@ -0,0 +1,138 @@
+//! Text extension occurs in the [text][] content type.
+//!
+//! ## Grammar
+//!
+//! Text extension forms with the following BNF
+//! (<small>see [construct][crate::construct] for character groups</small>):
+//!
+//! ```bnf
+//! ; Restriction: `start` of the extension must accept `marker *line`, and
+//! ; return its size.
+//! text_extension ::= marker *line
+//! ```
+//!
+//! Text extensions are custom text constructs, passed by users as
+//! [`text_extensions`][text_extensions] in parse options.
+//! The `start` function of an extension gets the rest of the line at its
+//! marker, and decides whether the construct starts there, and how many bytes
+//! it spans.
+//! The construct cannot span lines.
+//!
+//! Extensions are tried before the built-in constructs that start with the
+//! same byte, so an extension with `:` as its marker goes before
+//! [directive (text)][directive_text].
+//! Extensions with the same marker are tried in order.
+//! Like other constructs, an extension cannot start inside a construct that
+//! started earlier, such as a code (text) or a GFM autolink literal with a
+//! protocol (`https://a.b/@c`).
+//! Extensions are not tried in [`plain_text`][plain_text].
+//!
+//! A text extension is one event, whose value is not parsed further.
+//! Because it is formed while tokenizing, before anything is resolved, it
+//! hides its bytes from the resolvers that run after: attention sequences,
+//! label starts and ends, and GFM autolink literals in it are not seen.
+//! Attention (such as emphasis) and links can contain text extensions.
+//!
+//! ## HTML
+//!
+//! When compiling to HTML, a text extension is passed to
+//! [`text_extension_handler`][text_extension_handler] if given.
+//! Otherwise, or if that function does not handle the construct, its value is
+//! rendered as text.
+//!
+//! ## Tokens
+//!
+//! *   [`TextExtension`][Name::TextExtension]
+//!
+//! [text]: crate::construct::text
+//! [directive_text]: crate::construct::directive_text
+//! [text_extensions]: crate::ParseOptions::text_extensions
+//! [plain_text]: crate::ParseOptions::plain_text
+//! [text_extension_handler]: crate::CompileOptions::text_extension_handler
+
+use crate::event::Name;
+use crate::state::{Name as StateName, State};
+use crate::tokenizer::Tokenizer;
+use crate::util::slice::Slice;
+
+/// Start of text extension.
+///
+/// ```markdown
+/// > | a :tada: b
+///       ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    let extensions = &tokenizer.parse_state.options.text_extensions;
+    let bytes = tokenizer.parse_state.bytes;
+    let start = tokenizer.point.index;
+    let mut end = start;
+
+    while end < bytes.len() && !matches!(bytes[end], b'\n' | b'\r') {
+        end += 1;
+    }
+
+    let rest = Slice::from_indices(bytes, start, end);
+    let rest = rest.as_str();
+    let mut index = 0;
+
+    while index < extensions.len() {
+        let extension = &extensions[index];
+
+        if tokenizer.current == Some(extension.marker) {
+            if let Some(size) = (extension.start)(rest) {
+                if size > 0 && size <= rest.len() && rest.is_char_boundary(size) {
+                    tokenizer.tokenize_state.end = start + size;
+                    tokenizer.tokenize_state.token_1 = Name::TextExtension(extension.name.clone());
+                    tokenizer.enter(tokenizer.tokenize_state.token_1.clone());
+                    return State::Retry(StateName::TextExtensionInside);
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    State::Nok
+}
+
+/// In text extension.
+///
+/// ```markdown
+/// > | a :tada: b
+///       ^^^^^^
+/// ```
+pub fn inside(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.point.index < tokenizer.tokenize_state.end {
+        // The text ends before the construct, such as at the closing
+        // sequence of a heading (atx).
+        if matches!(tokenizer.current, None | Some(b'\n')) {
+            tokenizer.tokenize_state.token_1 = Name::Data;
+            tokenizer.tokenize_state.end = 0;
+            State::Nok
+        } else {
+            tokenizer.consume();
+            State::Next(StateName::TextExtensionInside)
+        }
+    } else {
+        tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
+        tokenizer.tokenize_state.token_1 = Name::Data;
+        tokenizer.tokenize_state.end = 0;
+        State::Ok
+    }
+}
+
+/// Check whether there is a text extension with `byte` as its marker.
+pub fn is_marker(tokenizer: &Tokenizer, byte: u8) -> bool {
+    let extensions = &tokenizer.parse_state.options.text_extensions;
+    let mut index = 0;
+
+    while index < extensions.len() {
+        if extensions[index].marker == byte {
+            return true;
+        }
+
+        index += 1;
+    }
+
+    false
+}
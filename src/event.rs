@@ -1,15 +1,322 @@
 //! Semantic labels of things happening.
 
 use crate::unist;
-use crate::util::constant::TAB_SIZE;
+use alloc::string::String;
 
 /// Semantic label of a span.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Name {
+    /// Abbreviation extension: use of an abbreviation.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`Data`][Name::Data]
+    /// *   **Construct**:
+    ///     [`abbreviation`][crate::construct::abbreviation]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | *[HTML]: Hyper Text Markup Language
+    /// > | a HTML b
+    ///       ^^^^
+    /// ```
+    Abbreviation,
+    /// Abbreviation extension: whole definition.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`AbbreviationDefinitionLabel`][Name::AbbreviationDefinitionLabel],
+    ///     [`AbbreviationDefinitionMarker`][Name::AbbreviationDefinitionMarker],
+    ///     [`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle],
+    ///     [`DefinitionMarker`][Name::DefinitionMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    AbbreviationDefinition,
+    /// Abbreviation extension: definition label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     [`AbbreviationDefinitionLabelMarker`][Name::AbbreviationDefinitionLabelMarker],
+    ///     [`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString]
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///      ^^^^^^
+    /// ```
+    AbbreviationDefinitionLabel,
+    /// Abbreviation extension: definition label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinitionLabel`][Name::AbbreviationDefinitionLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///      ^    ^
+    /// ```
+    AbbreviationDefinitionLabelMarker,
+    /// Abbreviation extension: definition label string.
+    ///
+    /// The label is literal: it is matched in text as it is, without parsing
+    /// character escapes or references.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinitionLabel`][Name::AbbreviationDefinitionLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///       ^^^^
+    /// ```
+    AbbreviationDefinitionLabelString,
+    /// Abbreviation extension: definition marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///     ^
+    /// ```
+    AbbreviationDefinitionMarker,
+    /// Abbreviation extension: definition title.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition]
+    /// *   **Content model**:
+    ///     [string content][crate::construct::string]
+    /// *   **Construct**:
+    ///     [`abbreviation_definition`][crate::construct::abbreviation_definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | *[HTML]: Hyper Text Markup Language
+    ///              ^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    AbbreviationDefinitionTitle,
     /// Attention sequence.
     ///
     /// > 👉 **Note**: this is used while parsing but compiled away.
     AttentionSequence,
+    /// Attributes extension: attribute.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attributes`][Name::Attributes]
+    /// *   **Content model**:
+    ///     [`AttributeClassMarker`][Name::AttributeClassMarker],
+    ///     [`AttributeIdMarker`][Name::AttributeIdMarker],
+    ///     [`AttributeInitializerMarker`][Name::AttributeInitializerMarker],
+    ///     [`AttributeName`][Name::AttributeName],
+    ///     [`AttributeValue`][Name::AttributeValue],
+    ///     [`AttributeValueMarker`][Name::AttributeValueMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {#b .c d="e"}
+    ///           ^^ ^^ ^^^^^
+    /// ```
+    Attribute,
+    /// Attributes extension: attribute class marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {.b}
+    ///           ^
+    /// ```
+    AttributeClassMarker,
+    /// Attributes extension: attribute id marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {#b}
+    ///           ^
+    /// ```
+    AttributeIdMarker,
+    /// Attributes extension: attribute initializer marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {b="c"}
+    ///            ^
+    /// ```
+    AttributeInitializerMarker,
+    /// Attributes extension: attribute name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {b="c"}
+    ///           ^
+    /// ```
+    #[allow(clippy::enum_variant_names)]
+    AttributeName,
+    /// Attributes extension: attribute value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {#b .c d="e" f=g}
+    ///            ^   ^   ^    ^
+    /// ```
+    AttributeValue,
+    /// Attributes extension: attribute value marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attribute`][Name::Attribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {b="c"}
+    ///             ^ ^
+    /// ```
+    AttributeValueMarker,
+    /// Attributes extension: whole attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`HeadingAtx`][Name::HeadingAtx],
+    ///     [`Paragraph`][Name::Paragraph]
+    /// *   **Content model**:
+    ///     [`Attribute`][Name::Attribute],
+    ///     [`AttributesMarker`][Name::AttributesMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {#b}
+    ///          ^^^^
+    /// ```
+    Attributes,
+    /// Attributes extension: attributes marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Attributes`][Name::Attributes]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ## a {#b}
+    ///          ^  ^
+    /// ```
+    AttributesMarker,
     /// Whole autolink.
     ///
     /// ## Info
@@ -726,127 +1033,634 @@ pub enum Name {
     ///     [`LineEnding`][Name::LineEnding],
     ///     [`SpaceOrTab`][Name::SpaceOrTab]
     /// *   **Construct**:
-    ///     [`label`][crate::construct::partial_label]
+    ///     [`label`][crate::construct::partial_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///     ^^^
+    /// ```
+    DefinitionLabel,
+    /// Definition label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionLabel`][Name::DefinitionLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`label`][crate::construct::partial_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///     ^ ^
+    /// ```
+    DefinitionLabelMarker,
+    /// Definition label data.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionLabel`][Name::DefinitionLabel]
+    /// *   **Content model**:
+    ///     [string content][crate::construct::string]
+    /// *   **Construct**:
+    ///     [`label`][crate::construct::partial_label]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///      ^
+    /// ```
+    DefinitionLabelString,
+    /// Whole definition list.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`BlankLineEnding`][Name::BlankLineEnding],
+    ///     [`BlockQuotePrefix`][Name::BlockQuotePrefix],
+    ///     [`DefinitionListDescription`][Name::DefinitionListDescription],
+    ///     [`DefinitionListTerm`][Name::DefinitionListTerm],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    /// > | : b
+    ///     ^^^
+    /// ```
+    DefinitionList,
+    /// Definition list description.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionList`][Name::DefinitionList]
+    /// *   **Content model**:
+    ///     [`DefinitionListDescriptionPrefix`][Name::DefinitionListDescriptionPrefix],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^^^
+    /// ```
+    DefinitionListDescription,
+    /// Definition list description (marker).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionListDescriptionPrefix`][Name::DefinitionListDescriptionPrefix]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^
+    /// ```
+    DefinitionListDescriptionMarker,
+    /// Definition list description (prefix).
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionListDescription`][Name::DefinitionListDescription]
+    /// *   **Content model**:
+    ///     [`DefinitionListDescriptionMarker`][Name::DefinitionListDescriptionMarker],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    ///   | a
+    /// > | : b
+    ///     ^^
+    /// ```
+    DefinitionListDescriptionPrefix,
+    /// Definition list term.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionList`][Name::DefinitionList]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`definition_list`][crate::construct::definition_list]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a
+    ///     ^
+    ///   | : b
+    /// ```
+    DefinitionListTerm,
+    /// Definition marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`AbbreviationDefinition`][Name::AbbreviationDefinition],
+    ///     [`Definition`][Name::Definition],
+    ///     [`GfmFootnoteDefinition`][Name::GfmFootnoteDefinition]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`definition`][crate::construct::definition]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///        ^
+    /// ```
+    DefinitionMarker,
+    /// Whole definition title.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Definition`][Name::Definition]
+    /// *   **Content model**:
+    ///     [`DefinitionTitleMarker`][Name::DefinitionTitleMarker],
+    ///     [`DefinitionTitleString`][Name::DefinitionTitleString],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`title`][crate::construct::partial_title]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///            ^^^
+    /// ```
+    DefinitionTitle,
+    /// Definition title marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`title`][crate::construct::partial_title]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///            ^ ^
+    /// ```
+    DefinitionTitleMarker,
+    /// Definition title data.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    /// *   **Content model**:
+    ///     [string content][crate::construct::string]
+    /// *   **Construct**:
+    ///     [`title`][crate::construct::partial_title]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | [a]: b "c"
+    ///             ^
+    /// ```
+    DefinitionTitleString,
+    /// Directive extension: attribute.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributeClassMarker`][Name::DirectiveAttributeClassMarker],
+    ///     [`DirectiveAttributeIdMarker`][Name::DirectiveAttributeIdMarker],
+    ///     [`DirectiveAttributeInitializerMarker`][Name::DirectiveAttributeInitializerMarker],
+    ///     [`DirectiveAttributeName`][Name::DirectiveAttributeName],
+    ///     [`DirectiveAttributeValue`][Name::DirectiveAttributeValue],
+    ///     [`DirectiveAttributeValueMarker`][Name::DirectiveAttributeValueMarker],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{#b .c d="e"}
+    ///        ^^ ^^ ^^^^^
+    /// ```
+    DirectiveAttribute,
+    /// Directive extension: attribute class marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{.b}
+    ///        ^
+    /// ```
+    DirectiveAttributeClassMarker,
+    /// Directive extension: attribute id marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{#b}
+    ///        ^
+    /// ```
+    DirectiveAttributeIdMarker,
+    /// Directive extension: attribute initializer marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{b="c"}
+    ///         ^
+    /// ```
+    DirectiveAttributeInitializerMarker,
+    /// Directive extension: attribute name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{b="c"}
+    ///        ^
+    /// ```
+    #[allow(clippy::enum_variant_names)]
+    DirectiveAttributeName,
+    /// Directive extension: attribute value.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{#b .c d="e" f=g}
+    ///         ^   ^   ^    ^
+    /// ```
+    DirectiveAttributeValue,
+    /// Directive extension: attribute value marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{b="c"}
+    ///          ^ ^
+    /// ```
+    DirectiveAttributeValueMarker,
+    /// Directive extension: whole attributes.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     [`DirectiveAttribute`][Name::DirectiveAttribute],
+    ///     [`DirectiveAttributesMarker`][Name::DirectiveAttributesMarker],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{#b}
+    ///       ^^^^
+    /// ```
+    DirectiveAttributes,
+    /// Directive extension: attributes marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a{#b}
+    ///       ^  ^
+    /// ```
+    DirectiveAttributesMarker,
+    /// Directive extension: container.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [document content][crate::construct::document]
+    /// *   **Content model**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [flow content][crate::construct::flow]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainer,
+    /// Directive extension: container fence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainer`][Name::DirectiveContainer]
+    /// *   **Content model**:
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveContainerSequence`][Name::DirectiveContainerSequence],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainerFence,
+    /// Directive extension: container fence sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`directive_container`][crate::construct::directive_container]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^
+    ///   | b
+    /// > | :::
+    ///     ^^^
+    /// ```
+    DirectiveContainerSequence,
+    /// Directive extension: whole label.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
+    /// *   **Content model**:
+    ///     [`DirectiveLabelMarker`][Name::DirectiveLabelMarker],
+    ///     [`DirectiveLabelString`][Name::DirectiveLabelString],
+    ///     [`LineEnding`][Name::LineEnding],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | :a[b]
+    ///       ^^^
+    /// ```
+    DirectiveLabel,
+    /// Directive extension: label marker.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`partial_directive`][crate::construct::partial_directive]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///     ^^^
+    /// > | :a[b]
+    ///       ^ ^
     /// ```
-    DefinitionLabel,
-    /// Definition label marker.
+    DirectiveLabelMarker,
+    /// Directive extension: label data.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`DefinitionLabel`][Name::DefinitionLabel]
+    ///     [`DirectiveLabel`][Name::DirectiveLabel]
     /// *   **Content model**:
-    ///     void
+    ///     [text content][crate::construct::text]
     /// *   **Construct**:
-    ///     [`label`][crate::construct::partial_label]
+    ///     [`partial_directive`][crate::construct::partial_directive]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///     ^ ^
+    /// > | :a[b]
+    ///        ^
     /// ```
-    DefinitionLabelMarker,
-    /// Definition label data.
+    DirectiveLabelString,
+    /// Directive extension: leaf.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`DefinitionLabel`][Name::DefinitionLabel]
+    ///     [flow content][crate::construct::flow]
     /// *   **Content model**:
-    ///     [string content][crate::construct::string]
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveLeafSequence`][Name::DirectiveLeafSequence],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
     /// *   **Construct**:
-    ///     [`label`][crate::construct::partial_label]
+    ///     [`directive_leaf`][crate::construct::directive_leaf]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///      ^
+    /// > | ::a[b]{c}
+    ///     ^^^^^^^^^
     /// ```
-    DefinitionLabelString,
-    /// Definition marker.
+    DirectiveLeaf,
+    /// Directive extension: leaf sequence.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`Definition`][Name::Definition],
-    ///     [`GfmFootnoteDefinition`][Name::GfmFootnoteDefinition]
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf]
     /// *   **Content model**:
     ///     void
     /// *   **Construct**:
-    ///     [`definition`][crate::construct::definition]
+    ///     [`directive_leaf`][crate::construct::directive_leaf]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///        ^
+    /// > | ::a
+    ///     ^^
     /// ```
-    DefinitionMarker,
-    /// Whole definition title.
+    DirectiveLeafSequence,
+    /// Directive extension: name.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`Definition`][Name::Definition]
+    ///     [`DirectiveContainerFence`][Name::DirectiveContainerFence],
+    ///     [`DirectiveLeaf`][Name::DirectiveLeaf],
+    ///     [`DirectiveText`][Name::DirectiveText]
     /// *   **Content model**:
-    ///     [`DefinitionTitleMarker`][Name::DefinitionTitleMarker],
-    ///     [`DefinitionTitleString`][Name::DefinitionTitleString],
-    ///     [`LineEnding`][Name::LineEnding],
-    ///     [`SpaceOrTab`][Name::SpaceOrTab]
+    ///     void
     /// *   **Construct**:
-    ///     [`title`][crate::construct::partial_title]
+    ///     [`partial_directive`][crate::construct::partial_directive]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///            ^^^
+    /// > | :abc
+    ///      ^^^
     /// ```
-    DefinitionTitle,
-    /// Definition title marker.
+    #[allow(clippy::enum_variant_names)]
+    DirectiveName,
+    /// Directive extension: text.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    ///     [text content][crate::construct::text]
     /// *   **Content model**:
-    ///     void
+    ///     [`DirectiveAttributes`][Name::DirectiveAttributes],
+    ///     [`DirectiveLabel`][Name::DirectiveLabel],
+    ///     [`DirectiveName`][Name::DirectiveName],
+    ///     [`DirectiveTextMarker`][Name::DirectiveTextMarker]
     /// *   **Construct**:
-    ///     [`title`][crate::construct::partial_title]
+    ///     [`directive_text`][crate::construct::directive_text]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///            ^ ^
+    /// > | a :b[c]{d} e
+    ///       ^^^^^^^^
     /// ```
-    DefinitionTitleMarker,
-    /// Definition title data.
+    DirectiveText,
+    /// Directive extension: text marker.
     ///
     /// ## Info
     ///
     /// *   **Context**:
-    ///     [`DefinitionTitle`][Name::DefinitionTitle]
+    ///     [`DirectiveText`][Name::DirectiveText]
     /// *   **Content model**:
-    ///     [string content][crate::construct::string]
+    ///     void
     /// *   **Construct**:
-    ///     [`title`][crate::construct::partial_title]
+    ///     [`directive_text`][crate::construct::directive_text]
     ///
     /// ## Example
     ///
     /// ```markdown
-    /// > | [a]: b "c"
-    ///             ^
+    /// > | a :b c
+    ///       ^
     /// ```
-    DefinitionTitleString,
+    DirectiveTextMarker,
     /// Emphasis.
     ///
     /// ## Info
@@ -902,6 +1716,69 @@ pub enum Name {
     ///      ^
     /// ```
     EmphasisText,
+    /// Emoji shortcode, with the emoji it stands for.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`emoji`][crate::construct::emoji]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :smile: b
+    ///       ^^^^^^^
+    /// ```
+    Emoji(String),
+    /// Whole custom flow construct, with its name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [flow content][crate::construct::flow]
+    /// *   **Content model**:
+    ///     [`FlowExtensionChunk`][Name::FlowExtensionChunk],
+    ///     [`LineEnding`][Name::LineEnding]
+    /// *   **Construct**:
+    ///     [`flow_extension`][crate::construct::flow_extension]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | %%%
+    ///     ^^^
+    /// > | a
+    ///     ^
+    /// > | %%%
+    ///     ^^^
+    /// ```
+    FlowExtension(String),
+    /// Custom flow construct chunk.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`FlowExtension`][Name::FlowExtension]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`flow_extension`][crate::construct::flow_extension]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | %%%
+    ///     ^^^
+    /// > | a
+    ///     ^
+    /// > | %%%
+    ///     ^^^
+    /// ```
+    FlowExtensionChunk,
     /// Whole frontmatter.
     ///
     /// ## Info
@@ -1677,6 +2554,7 @@ pub enum Name {
     /// *   **Context**:
     ///     [flow content][crate::construct::flow]
     /// *   **Content model**:
+    ///     [`Attributes`][Name::Attributes],
     ///     [`HeadingAtxSequence`][Name::HeadingAtxSequence],
     ///     [`HeadingAtxText`][Name::HeadingAtxText],
     ///     [`SpaceOrTab`][Name::SpaceOrTab]
@@ -1807,6 +2685,61 @@ pub enum Name {
     ///     ^^^^^
     /// ```
     HeadingSetextUnderlineSequence,
+    /// Highlight.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`HighlightSequence`][Name::HighlightSequence],
+    ///     [`HighlightText`][Name::HighlightText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///     ^^^^^
+    /// ```
+    Highlight,
+    /// Highlight sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Highlight`][Name::Highlight]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///     ^^ ^^
+    /// ```
+    HighlightSequence,
+    /// Highlight text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Highlight`][Name::Highlight]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ==a==
+    ///       ^
+    /// ```
+    HighlightText,
     /// Whole html (flow).
     ///
     /// ## Info
@@ -3004,7 +3937,9 @@ pub enum Name {
     /// *   **Context**:
     ///     [content][crate::construct::content]
     /// *   **Content model**:
-    ///     [text content][crate::construct::text]
+    ///     [text content][crate::construct::text],
+    ///     [`Attributes`][Name::Attributes],
+    ///     [`SpaceOrTab`][Name::SpaceOrTab]
     /// *   **Construct**:
     ///     [`paragraph`][crate::construct::paragraph]
     ///
@@ -3339,6 +4274,134 @@ pub enum Name {
     ///       ^
     /// ```
     StrongText,
+    /// Subscript.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`SubscriptSequence`][Name::SubscriptSequence],
+    ///     [`SubscriptText`][Name::SubscriptText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ~a~
+    ///     ^^^
+    /// ```
+    Subscript,
+    /// Subscript sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Subscript`][Name::Subscript]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ~a~
+    ///     ^ ^
+    /// ```
+    SubscriptSequence,
+    /// Subscript text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Subscript`][Name::Subscript]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ~a~
+    ///      ^
+    /// ```
+    SubscriptText,
+    /// Superscript.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     [`SuperscriptSequence`][Name::SuperscriptSequence],
+    ///     [`SuperscriptText`][Name::SuperscriptText]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ^a^
+    ///     ^^^
+    /// ```
+    Superscript,
+    /// Superscript sequence.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Superscript`][Name::Superscript]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ^a^
+    ///     ^ ^
+    /// ```
+    SuperscriptSequence,
+    /// Superscript text.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [`Superscript`][Name::Superscript]
+    /// *   **Content model**:
+    ///     [text content][crate::construct::text]
+    /// *   **Construct**:
+    ///     [`attention`][crate::construct::attention]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | ^a^
+    ///      ^
+    /// ```
+    SuperscriptText,
+    /// Whole custom text construct, with its name.
+    ///
+    /// ## Info
+    ///
+    /// *   **Context**:
+    ///     [text content][crate::construct::text]
+    /// *   **Content model**:
+    ///     void
+    /// *   **Construct**:
+    ///     [`text_extension`][crate::construct::text_extension]
+    ///
+    /// ## Example
+    ///
+    /// ```markdown
+    /// > | a :tada: b
+    ///       ^^^^^^
+    /// ```
+    TextExtension(String),
     /// Whole thematic break.
     ///
     /// ## Info
@@ -3379,8 +4442,18 @@ pub enum Name {
 }
 
 /// List of void events, used to make sure everything is working well.
-pub const VOID_EVENTS: [Name; 76] = [
+pub const VOID_EVENTS: [Name; 103] = [
+    Name::AbbreviationDefinitionLabelMarker,
+    Name::AbbreviationDefinitionLabelString,
+    Name::AbbreviationDefinitionMarker,
     Name::AttentionSequence,
+    Name::AttributeClassMarker,
+    Name::AttributeIdMarker,
+    Name::AttributeInitializerMarker,
+    Name::AttributeName,
+    Name::AttributeValue,
+    Name::AttributeValueMarker,
+    Name::AttributesMarker,
     Name::AutolinkEmail,
     Name::AutolinkMarker,
     Name::AutolinkProtocol,
@@ -3401,9 +4474,23 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::Data,
     Name::DefinitionDestinationLiteralMarker,
     Name::DefinitionLabelMarker,
+    Name::DefinitionListDescriptionMarker,
     Name::DefinitionMarker,
     Name::DefinitionTitleMarker,
+    Name::DirectiveAttributeClassMarker,
+    Name::DirectiveAttributeIdMarker,
+    Name::DirectiveAttributeInitializerMarker,
+    Name::DirectiveAttributeName,
+    Name::DirectiveAttributeValue,
+    Name::DirectiveAttributeValueMarker,
+    Name::DirectiveAttributesMarker,
+    Name::DirectiveContainerSequence,
+    Name::DirectiveLabelMarker,
+    Name::DirectiveLeafSequence,
+    Name::DirectiveName,
+    Name::DirectiveTextMarker,
     Name::EmphasisSequence,
+    Name::FlowExtensionChunk,
     Name::FrontmatterChunk,
     Name::GfmAutolinkLiteralEmail,
     Name::GfmAutolinkLiteralProtocol,
@@ -3423,6 +4510,7 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::HardBreakTrailing,
     Name::HeadingAtxSequence,
     Name::HeadingSetextUnderlineSequence,
+    Name::HighlightSequence,
     Name::HtmlFlowData,
     Name::HtmlTextData,
     Name::LabelImageMarker,
@@ -3455,6 +4543,8 @@ pub const VOID_EVENTS: [Name; 76] = [
     Name::ResourceTitleMarker,
     Name::SpaceOrTab,
     Name::StrongSequence,
+    Name::SubscriptSequence,
+    Name::SuperscriptSequence,
     Name::ThematicBreakSequence,
 ];
 
@@ -3493,19 +4583,43 @@ pub struct Point {
     pub line: usize,
     /// 1-indexed column number.
     ///
-    /// This is increased up to a tab stop for tabs.
+    /// This counts bytes, not characters, and is increased up to a tab stop
+    /// for tabs (see [`vs`][Point::vs]).
     /// Some editors count tabs as 1 character, so this position is not the
     /// same as editors.
+    /// Use [`Location`][crate::Location] to get columns that count a tab as
+    /// 1, or that count UTF-16 code units.
     pub column: usize,
     /// 0-indexed position in the document.
     ///
     /// Also an `index` into `bytes`.
     pub index: usize,
     /// Virtual step on the same `index`.
+    ///
+    /// A tab is expanded to the next tab stop (a multiple of `tab_size`), so
+    /// it takes up several columns.
+    /// Constructs can use a part of those columns: for example, a list item
+    /// can take 2 columns of a tab, and the code (indented) in it the rest.
+    /// Points in the middle of a tab are all at the `index` of that tab: `vs`
+    /// is how many of its columns come before the point, and `column` already
+    /// includes them.
+    /// `vs` is `0` for points that are not in the middle of a tab.
+    ///
+    /// So, two points with the same `index` can have different columns, and
+    /// the byte at `index` is a tab when `vs` is not `0`.
     pub vs: usize,
 }
 
 impl Point {
+    /// Get the 0-indexed byte offset of this point in the document.
+    ///
+    /// Points in the middle of a tab (with a [`vs`][Point::vs] that is not
+    /// `0`) get the offset of that tab.
+    #[must_use]
+    pub fn to_byte_offset(&self) -> usize {
+        self.index
+    }
+
     /// Create a unist point.
     pub fn to_unist(&self) -> unist::Point {
         unist::Point {
@@ -3517,7 +4631,10 @@ impl Point {
 
     /// Create a new point, that is shifted from the close earlier current
     /// point, to `index`.
-    pub fn shift_to(&self, bytes: &[u8], index: usize) -> Point {
+    ///
+    /// Tabs are expanded to the next multiple of `tab_size`.
+    #[must_use]
+    pub fn shift_to(&self, bytes: &[u8], index: usize, tab_size: usize) -> Point {
         let mut next = self.clone();
         debug_assert!(index > next.index, "expected to shift forward");
 
@@ -3525,11 +4642,11 @@ impl Point {
             match bytes[next.index] {
                 b'\n' | b'\r' => unreachable!("cannot move past line endings"),
                 b'\t' => {
-                    let remainder = next.column % TAB_SIZE;
+                    let remainder = next.column % tab_size;
                     let vs = if remainder == 0 {
                         0
                     } else {
-                        TAB_SIZE - remainder
+                        tab_size - remainder
                     };
                     next.index += 1;
                     next.column += 1 + vs;
@@ -1,7 +1,8 @@
 //! Public API of `markdown-rs`.
 //!
 //! This module exposes primarily [`to_html()`][].
-//! It also exposes [`to_html_with_options()`][] and [`to_mdast()`][].
+//! It also exposes [`to_html_with_options()`][], [`to_mdast()`][], and
+//! [`to_events()`][].
 //!
 //! *   [`to_html()`][]
 //!     — safe way to transform (untrusted?) markdown into HTML
@@ -9,18 +10,33 @@
 //!     — like `to_html` but lets you configure how markdown is turned into
 //!     HTML, such as allowing dangerous HTML or turning on/off different
 //!     constructs (GFM, MDX, and the like)
+//! *   `to_html_to_writer()`
+//!     — like `to_html_with_options` but writes HTML to an `io::Write` while
+//!     it is compiled (requires the `std` feature)
 //! *   [`to_mdast()`][]
 //!     — turn markdown into a syntax tree
+//! *   [`to_markdown()`][]
+//!     — format markdown, turning it into a syntax tree and back
+//! *   [`to_events()`][]
+//!     — turn markdown into events, to build your own compiler
 //!
 //! ## Features
 //!
 //! *   **`default`**
 //!     — nothing is enabled by default
+//! *   **`emoji`**
+//!     — include the emoji shortcodes used on GitHub (such as `:smile:`),
+//!     for [`emoji`][Constructs::emoji]
 //! *   **`serde`**
 //!     — enable serde to serialize the AST (includes `dep:serde`)
 //! *   **`log`**
 //!     — enable logging (includes `dep:log`);
 //!     you can show logs with `RUST_LOG=debug`
+//! *   **`profile`**
+//!     — enable `profile()`, to see how much work each construct does
+//!     (includes `std`)
+//! *   **`std`**
+//!     — enable `to_html_to_writer()`, to write HTML to an `io::Write`
 
 #![no_std]
 #![deny(clippy::pedantic)]
@@ -29,19 +45,25 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::result_large_err)]
+// Event names link to the (private) constructs that produce them.
+#![allow(rustdoc::private_intra_doc_links)]
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/wooorm/markdown-rs/8924580/media/logo-monochromatic.svg?sanitize=true"
 )]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 mod configuration;
 mod construct;
 mod event;
 mod parser;
+mod reparse;
 mod resolve;
 mod state;
 mod subtokenize;
 mod to_html;
+mod to_markdown;
 mod to_mdast;
 mod tokenizer;
 mod util;
@@ -61,14 +83,58 @@ pub use util::location::Location;
 
 pub use util::line_ending::LineEnding;
 
+pub use util::normalize_identifier::normalize_identifier;
+
+pub use event::{
+    Content as EventContent, Event, Kind as EventKind, Link as EventLink, Name as EventName,
+    Point as EventPoint,
+};
+
+pub use util::code_highlight::{Highlight, HighlightContext, Highlighter};
+
+pub use util::code_language::{CodeInfo, Transform as CodeLanguageTransform};
+
+pub use util::directive::{Directive, Handler as DirectiveHandler, Kind as DirectiveKind};
+
+pub use util::infer::{FenceInfo, HtmlFlowKind, ListInfo, ResourceInfo, ThematicBreakInfo};
+
+pub use util::stats::DocumentStats;
+
+pub use util::toc::TocEntry;
+
+pub use util::visible_text::{TextRun, TextRuns};
+
+#[cfg(feature = "profile")]
+pub use util::profile::{Profile, ProfileEntry};
+
+pub use util::flow_extension::{
+    Close as FlowExtensionClose, FlowExtension, Handler as FlowExtensionHandler,
+    Priority as FlowExtensionPriority, Start as FlowExtensionStart,
+};
+
+pub use util::text_extension::{
+    Handler as TextExtensionHandler, Start as TextExtensionStart, TextExtension,
+};
+
+pub use util::url_transform::{
+    IsExternal as IsExternalLink, Transform as UrlTransform, UrlContext,
+};
+
 pub use util::mdx::{
     EsmParse as MdxEsmParse, ExpressionKind as MdxExpressionKind,
     ExpressionParse as MdxExpressionParse, Signal as MdxSignal,
 };
 
-pub use configuration::{CompileOptions, Constructs, Options, ParseOptions};
+pub use reparse::Edit;
 
-use alloc::string::String;
+pub use configuration::{
+    CompileOptions, Constructs, FigureCaption, FootnoteStyle, FormatOptions, FrontmatterKind,
+    HeadingAtxOptions, HtmlFilter, ImageLoading, Invisibles, ListItemOptions, Options,
+    ParseOptions, ProseWrap,
+};
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use util::slice::{Position, Slice};
 
 /// Turn markdown into HTML.
 ///
@@ -124,11 +190,119 @@ pub fn to_html(value: &str) -> String {
 /// ```
 pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, message::Message> {
     let (events, parse_state) = parser::parse(value, &options.parse)?;
-    Ok(to_html::compile(
-        &events,
-        parse_state.bytes,
-        &options.compile,
-    ))
+    Ok(to_html::compile(&events, &parse_state, &options.compile).0)
+}
+
+/// Turn markdown into HTML, with configuration, writing it to `writer`.
+///
+/// HTML is written while it is compiled, instead of building one string
+/// first, which is useful for large documents or to write directly to
+/// something like a response.
+/// Only things that need info from later in the document, such as the GFM
+/// footnote section, are kept around until they can be written.
+///
+/// It is written in many small chunks, so you probably want to pass a
+/// [`BufWriter`][std::io::BufWriter].
+///
+/// Requires the `std` feature.
+///
+/// ## Errors
+///
+/// `to_html_to_writer()` errors when `writer` does.
+/// It does not error with normal markdown because markdown does not have
+/// syntax errors.
+/// However, MDX does have syntax errors: those errors are turned into
+/// [`io::Error`][std::io::Error]s of kind
+/// [`InvalidData`][std::io::ErrorKind::InvalidData], which wrap the
+/// [`Message`][message::Message].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_to_writer, Options};
+/// # fn main() -> std::io::Result<()> {
+///
+/// let mut html = vec![];
+///
+/// to_html_to_writer("# Hello, world!", &Options::default(), &mut html)?;
+///
+/// assert_eq!(html, b"<h1>Hello, world!</h1>");
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn to_html_to_writer<W: std::io::Write>(
+    value: &str,
+    options: &Options,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let (events, parse_state) = parser::parse(value, &options.parse)
+        .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidData, message))?;
+    to_html::compile_to(&events, &parse_state, &options.compile, &mut |chunk| {
+        writer.write_all(chunk.as_bytes())
+    })?;
+    Ok(())
+}
+
+/// Turn markdown into HTML, with configuration, and get warnings about it.
+///
+/// Warnings are [`Message`][message::Message]s with a `place`, a `reason`, and
+/// a `rule_id`, sorted by where they start.
+/// Currently, there are these kinds of warnings:
+///
+/// *   `undefined-reference` — a shortcut (`[a]`) or collapsed (`[a][]`)
+///     reference, or the reference of a full reference (`[b]` in `[a][b]`),
+///     does not match a definition, so it is not a link (or image) but
+///     just text
+/// *   `invalid-character-reference` — a numeric character reference (such
+///     as `&#0;`) is to a code point that is not allowed (such as a control
+///     character, lone surrogate, or one that is out of range), so it is
+///     replaced by U+FFFD REPLACEMENT CHARACTER (`�`)
+/// *   `escaped-html` — HTML (flow or text) is not allowed (because
+///     [`allow_dangerous_html`][CompileOptions::allow_dangerous_html] is off,
+///     or [`html_filter`][CompileOptions::html_filter] does not allow it), so
+///     it is escaped and shown as text; count these to find documents with
+///     HTML in them
+///
+/// ## Errors
+///
+/// `to_html_with_diagnostics()` never errors with normal markdown because
+/// markdown does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// expressions, ESM, and JSX are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_html_with_diagnostics, Options};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let (result, messages) = to_html_with_diagnostics("[a], [b]\n\n[a]: c", &Options::default())?;
+///
+/// assert_eq!(result, "<p><a href=\"c\">a</a>, [b]</p>\n");
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].rule_id, Box::new("undefined-reference".into()));
+/// assert_eq!(
+///     messages[0].to_string(),
+///     "1:6-1:9: Unexpected reference to undefined definition `b`, expected a definition (such as `[b]: https://example.com`) (markdown-rs:undefined-reference)"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_html_with_diagnostics(
+    value: &str,
+    options: &Options,
+) -> Result<(String, Vec<message::Message>), message::Message> {
+    let (events, mut parse_state) = parser::parse(value, &options.parse)?;
+    let (html, mut messages) = to_html::compile(&events, &parse_state, &options.compile);
+    messages.append(&mut parse_state.messages);
+    messages.sort_by_key(|message| match message.place.as_deref() {
+        Some(message::Place::Position(position)) => position.start.offset,
+        Some(message::Place::Point(point)) => point.offset,
+        None => 0,
+    });
+    Ok((html, messages))
 }
 
 /// Turn markdown into a syntax tree.
@@ -156,6 +330,961 @@ pub fn to_html_with_options(value: &str, options: &Options) -> Result<String, me
 /// ```
 pub fn to_mdast(value: &str, options: &ParseOptions) -> Result<mdast::Node, message::Message> {
     let (events, parse_state) = parser::parse(value, options)?;
-    let node = to_mdast::compile(&events, parse_state.bytes)?;
+    let node = to_mdast::compile(&events, &parse_state)?;
     Ok(node)
 }
+
+/// Turn markdown into (formatted) markdown.
+///
+/// Parses markdown to a syntax tree and serializes it again, in a consistent
+/// style: atx headings, fenced code, `***` for thematic breaks, and the
+/// markers and prose wrapping configured in [`FormatOptions`][].
+/// Code is kept verbatim, and hard breaks are kept (as `\\` followed by a
+/// line ending).
+///
+/// Constructs that can not be formatted are kept as written, such as MDX,
+/// directives, highlight, subscript, superscript, definition lists,
+/// abbreviation definitions, and headings and paragraphs with attributes.
+/// Documents that are nested very deeply (such as hundreds of block quotes
+/// in each other) are kept as written.
+///
+/// ## Errors
+///
+/// `to_markdown()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{to_markdown, FormatOptions, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(
+///     to_markdown(
+///         "Hey, _you_!\n===\n\n+ a\n+ b\n    c",
+///         &ParseOptions::default(),
+///         &FormatOptions::default()
+///     )?,
+///     "# Hey, *you*!\n\n- a\n- b\n  c\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_markdown(
+    value: &str,
+    parse_options: &ParseOptions,
+    options: &FormatOptions,
+) -> Result<String, message::Message> {
+    let (events, parse_state) = parser::parse(value, parse_options)?;
+    let tree = to_mdast::compile(&events, &parse_state)?;
+    Ok(to_markdown::serialize(
+        value,
+        &tree,
+        &events,
+        parse_options,
+        options,
+    ))
+}
+
+/// Get the definitions in markdown.
+///
+/// Definitions (such as `[a]: <b> "c"`) are returned in the order they occur
+/// in, including those in containers such as block quotes and list items.
+/// Each definition has its normalized `identifier` (used to match
+/// references, case-folded and with whitespace collapsed) and its `label` (as
+/// it is written, with character escapes and references decoded), its `url`,
+/// its optional `title`, and its `position`.
+///
+/// When several definitions have the same identifier, only the first one is
+/// used, and only the first one is returned.
+///
+/// ## Errors
+///
+/// `definitions()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{definitions, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = definitions("[A  b]: /c 'd'\n[a B]: /e", &ParseOptions::default())?;
+///
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(result[0].identifier, "a b");
+/// assert_eq!(result[0].label, Some("A  b".into()));
+/// assert_eq!(result[0].url, "/c");
+/// assert_eq!(result[0].title, Some("d".into()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn definitions(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<Vec<mdast::Definition>, message::Message> {
+    let tree = to_mdast(value, options)?;
+    Ok(collect_definitions(&tree))
+}
+
+/// Get the definitions in markdown, with how often each one is referenced.
+///
+/// Returns the same definitions as [`definitions()`][], each with the
+/// number of links and images that reference it (such as `[a]`, `[b][a]`, or
+/// `![a]`), which is `0` for unused definitions.
+/// Only references that match a definition form links and images, so
+/// `[b]` without definition is text, and not counted.
+/// References in the alt of images are not counted either, as the alt is
+/// text.
+///
+/// Definitions themselves are never in the HTML output.
+/// To treat them as text, turn the [`definition`][Constructs::definition]
+/// construct off.
+///
+/// ## Errors
+///
+/// `definitions_with_usage()` never errors with normal markdown because
+/// markdown does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{definitions_with_usage, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = definitions_with_usage("[a] [A][]\n\n[a]: /a\n[b]: /b", &ParseOptions::default())?;
+///
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].0.identifier, "a");
+/// assert_eq!(result[0].1, 2);
+/// assert_eq!(result[1].0.identifier, "b");
+/// assert_eq!(result[1].1, 0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn definitions_with_usage(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<Vec<(mdast::Definition, usize)>, message::Message> {
+    let tree = to_mdast(value, options)?;
+    let mut result: Vec<(mdast::Definition, usize)> = collect_definitions(&tree)
+        .into_iter()
+        .map(|definition| (definition, 0))
+        .collect();
+    let mut stack = vec![&tree];
+
+    while let Some(node) = stack.pop() {
+        let identifier = match node {
+            mdast::Node::LinkReference(reference) => Some(&reference.identifier),
+            mdast::Node::ImageReference(reference) => Some(&reference.identifier),
+            _ => None,
+        };
+
+        if let Some(identifier) = identifier {
+            let mut index = 0;
+
+            while index < result.len() {
+                if &result[index].0.identifier == identifier {
+                    result[index].1 += 1;
+                    break;
+                }
+                index += 1;
+            }
+        }
+
+        if let Some(children) = node.children() {
+            let mut index = children.len();
+            while index > 0 {
+                index -= 1;
+                stack.push(&children[index]);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Get the labels of definitions in markdown, by their identifier.
+///
+/// Maps the normalized `identifier` of each definition (as in
+/// [`definitions()`][]) to its `label` (as it is written, with character
+/// escapes and references decoded).
+/// When several definitions have the same identifier, the label of the first
+/// one is used.
+///
+/// To look up something else (such as a term from a glossary) in this map,
+/// normalize it the same way: with
+/// [`normalize_identifier()`][normalize_identifier()], lowercased.
+///
+/// ## Errors
+///
+/// `definition_labels()` never errors with normal markdown because markdown
+/// does not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{definition_labels, normalize_identifier, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = definition_labels("[Glossary  Term]: /a\n[glossary term]: /b", &ParseOptions::default())?;
+///
+/// assert_eq!(result.len(), 1);
+/// assert_eq!(
+///     result.get(&normalize_identifier("GLOSSARY TERM").to_lowercase()),
+///     Some(&"Glossary  Term".into())
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn definition_labels(
+    value: &str,
+    options: &ParseOptions,
+) -> Result<BTreeMap<String, String>, message::Message> {
+    let tree = to_mdast(value, options)?;
+    let mut result = BTreeMap::new();
+
+    for definition in collect_definitions(&tree) {
+        let label = definition.label.unwrap_or(definition.identifier.clone());
+        result.insert(definition.identifier, label);
+    }
+
+    Ok(result)
+}
+
+/// Collect the first definition of each identifier in a tree.
+fn collect_definitions(tree: &mdast::Node) -> Vec<mdast::Definition> {
+    let mut stack = vec![tree];
+    let mut result: Vec<mdast::Definition> = vec![];
+
+    while let Some(node) = stack.pop() {
+        if let mdast::Node::Definition(definition) = node {
+            let mut index = 0;
+            let mut seen = false;
+
+            while index < result.len() {
+                if result[index].identifier == definition.identifier {
+                    seen = true;
+                    break;
+                }
+                index += 1;
+            }
+
+            if !seen {
+                result.push(definition.clone());
+            }
+        } else if let Some(children) = node.children() {
+            // Reverse, so that the first child is handled first.
+            let mut index = children.len();
+            while index > 0 {
+                index -= 1;
+                stack.push(&children[index]);
+            }
+        }
+    }
+
+    result
+}
+
+/// Get statistics of markdown, such as how many words it has.
+///
+/// Words and characters are counted in the text as it is rendered, so
+/// `**bold**` is one word, and code, HTML, and URLs are not counted.
+/// See [`DocumentStats`][] for what is counted, and how.
+///
+/// ## Errors
+///
+/// `stats()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{stats, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = stats("# Hi!\n\nSome **bold** [words](https://a.b).\n\n    code", &ParseOptions::default())?;
+///
+/// assert_eq!(result.words, 4);
+/// assert_eq!(result.characters, 17);
+/// assert_eq!(result.headings, 1);
+/// assert_eq!(result.links, 1);
+/// assert_eq!(result.code_blocks, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn stats(value: &str, options: &ParseOptions) -> Result<DocumentStats, message::Message> {
+    let (events, parse_state) = parser::parse(value, options)?;
+    Ok(util::stats::collect(
+        &events,
+        parse_state.bytes,
+        parse_state.tab_size,
+    ))
+}
+
+/// Get the table of contents of markdown: its headings, in order.
+///
+/// Returns a flat list of the atx and setext headings in the document,
+/// including those in containers such as block quotes and list items.
+/// Each entry has the `depth` of the heading (`N` in `<hN>`), its plain
+/// `text`, its `id` (the slug that [`heading_ids`][CompileOptions::heading_ids]
+/// uses), and its `position`.
+/// Use the depths to nest entries.
+///
+/// Things that look like headings in code or HTML are not headings, so they
+/// are not included.
+///
+/// ## Errors
+///
+/// `toc()` never errors with normal markdown because markdown does not have
+/// syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{toc, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = toc("# Hello, *world*!\n\nAbout\n-----", &ParseOptions::default())?;
+///
+/// assert_eq!(result.len(), 2);
+/// assert_eq!(result[0].depth, 1);
+/// assert_eq!(result[0].text, "Hello, world!");
+/// assert_eq!(result[0].id, "hello-world");
+/// assert_eq!(result[1].depth, 2);
+/// assert_eq!(result[1].text, "About");
+/// assert_eq!(result[1].id, "about");
+/// # Ok(())
+/// # }
+/// ```
+pub fn toc(value: &str, options: &ParseOptions) -> Result<Vec<TocEntry>, message::Message> {
+    let (events, parse_state) = parser::parse(value, options)?;
+    Ok(util::toc::collect(
+        &events,
+        parse_state.bytes,
+        parse_state.tab_size,
+    ))
+}
+
+/// Get the visible text of markdown, such as to index it for search.
+///
+/// The text of each block is separated by a blank line (`\n\n`), so that
+/// words in different blocks do not run together.
+/// Line endings in blocks are kept.
+/// See [`TextRuns`][] for what counts as visible text.
+///
+/// Use [`text_runs()`][] to get the text of each block, with its position,
+/// one by one.
+/// This is different from [`plain_text`][ParseOptions::plain_text], which
+/// turns off phrasing constructs when parsing.
+///
+/// ## Errors
+///
+/// `visible_text()` never errors with normal markdown because markdown does
+/// not have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{visible_text, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// assert_eq!(
+///     visible_text(
+///         "# Hi!\n\nSome **bold** [words](https://a.b) and `code`.\n\n![An image](c.png)",
+///         &ParseOptions::default()
+///     )?,
+///     "Hi!\n\nSome bold words and code.\n\nAn image"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn visible_text(value: &str, options: &ParseOptions) -> Result<String, message::Message> {
+    let mut result = String::new();
+
+    for run in text_runs(value, options)? {
+        if !result.is_empty() {
+            result.push_str("\n\n");
+        }
+
+        result.push_str(&run.text);
+    }
+
+    Ok(result)
+}
+
+/// Get the visible text of markdown, one block at a time.
+///
+/// This is the streaming variant of [`visible_text()`][]: it returns an
+/// iterator that yields a [`TextRun`][], with the visible text and the
+/// position of a block, for each block that has visible text.
+/// See [`TextRuns`][] for what counts as a block and as visible text.
+///
+/// ## Errors
+///
+/// `text_runs()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{text_runs, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let mut runs = text_runs("# Hi!\n\n> Some *text*.", &ParseOptions::default())?;
+///
+/// let run = runs.next().unwrap();
+/// assert_eq!(run.text, "Hi!");
+/// assert_eq!(run.position.start.line, 1);
+///
+/// let run = runs.next().unwrap();
+/// assert_eq!(run.text, "Some text.");
+/// assert_eq!(run.position.start.line, 3);
+/// assert_eq!(run.position.start.column, 3);
+///
+/// assert_eq!(runs.next(), None);
+/// # Ok(())
+/// # }
+/// ```
+pub fn text_runs<'a>(
+    value: &'a str,
+    options: &ParseOptions,
+) -> Result<TextRuns<'a>, message::Message> {
+    let (events, _) = parser::parse(value, options)?;
+    Ok(TextRuns::new(events, value.as_bytes(), options))
+}
+
+/// Parse markdown and report how much work each construct and resolver did.
+///
+/// This is meant to find out why some document is slow to parse.
+/// Constructs are keyed by the state they are attempted with, such as
+/// `HeadingAtxStart` or `LabelEndStart`, which start with the name of the
+/// construct.
+/// Counts are the same for the same document and options; times are not.
+/// Times include the constructs attempted inside a construct, so they add up
+/// to more than the total.
+/// Only parsing is profiled, not compiling.
+///
+/// This is only available with the `profile` feature.
+///
+/// ## Errors
+///
+/// `profile()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{profile, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let result = profile("# a\n\n*b*", &ParseOptions::default())?;
+///
+/// let heading = result
+///     .entries
+///     .iter()
+///     .find(|d| d.name == "HeadingAtxStart")
+///     .unwrap();
+/// assert_eq!(heading.count, 1);
+/// assert_eq!(heading.nok, 0);
+///
+/// // A small report, sorted by time:
+/// println!("{}", result);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "profile")]
+pub fn profile(value: &str, options: &ParseOptions) -> Result<Profile, message::Message> {
+    let (_, parse_state) = parser::parse(value, options)?;
+    let mut profile = parse_state.profile.into_inner();
+    profile.sort();
+    Ok(profile)
+}
+
+/// Turn markdown into events.
+///
+/// Events are what [`to_html()`][] and [`to_mdast()`][] are built on.
+/// Each construct is represented by an enter event and an exit event, with a
+/// [`EventName`][], and the [`EventPoint`][] where it happens.
+/// Events are flat, but they nest: an enter of something, then the enters
+/// and exits of its children, then the exit of that thing.
+/// Use [`event_source()`][] to get the source of an exit event.
+///
+/// Events are useful if you want to compile markdown to something else than
+/// HTML or mdast.
+///
+/// > 👉 **Note**: [`EventLink`][]s are used internally to connect pieces of
+/// > content, such as the lines of a paragraph, which are parsed together.
+/// > When `to_events()` returns, they are already parsed, so links can be
+/// > ignored.
+///
+/// ## Errors
+///
+/// `to_events()` never errors with normal markdown because markdown does not
+/// have syntax errors, so feel free to `unwrap()`.
+/// However, MDX does have syntax errors.
+/// When MDX is turned on, there are several errors that can occur with how
+/// JSX, expressions, or ESM are written.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event_source, to_events, EventKind, EventName, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "# Hey, *you*!";
+/// let options = ParseOptions::default();
+/// let events = to_events(value, &options)?;
+///
+/// assert_eq!(events[0].kind, EventKind::Enter);
+/// assert_eq!(events[0].name, EventName::HeadingAtx);
+///
+/// let mut emphasis = vec![];
+///
+/// for (index, event) in events.iter().enumerate() {
+///     if event.kind == EventKind::Exit && event.name == EventName::Emphasis {
+///         emphasis.push(event_source(value, &events, index, &options));
+///     }
+/// }
+///
+/// assert_eq!(emphasis, vec!["*you*"]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_events(value: &str, options: &ParseOptions) -> Result<Vec<Event>, message::Message> {
+    let (events, _) = parser::parse(value, options)?;
+    Ok(events)
+}
+
+/// Turn changed markdown into events, reusing the events of the old
+/// document where possible.
+///
+/// `value` is the new markdown, `events` are the events of the old markdown
+/// (as returned by [`to_events()`][] or an earlier `reparse()`), `edit`
+/// describes what changed, and `options` must be the same as those used for
+/// the old markdown.
+/// The result is the same as `to_events(value, options)`.
+///
+/// This is useful in editors, where small changes are made to large
+/// documents.
+/// Currently, only the paragraph that changed is parsed again, when:
+///
+/// *   the change is inside a paragraph that is not in a container (such as
+///     a block quote or list item), and that has blank lines (or the start
+///     or end of the document) before and after it
+/// *   after the change, the text is still one such paragraph, that does not
+///     start with whitespace
+/// *   the text does not include brackets (`[`, `]`), as references depend
+///     on definitions elsewhere in the document
+/// *   MDX expressions and ESM are not parsed with functions
+/// *   [definition lists][Constructs::definition_list] are off, as a
+///     paragraph turns into a term when a description follows it
+/// *   [abbreviations][Constructs::abbreviation] are off, as they depend on
+///     definitions elsewhere in the document
+///
+/// Otherwise, the whole document is parsed again.
+///
+/// ## Errors
+///
+/// `reparse()` errors in the same cases as [`to_events()`][].
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{reparse, to_events, Edit, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let options = ParseOptions::default();
+/// let events = to_events("# a\n\nb *c*\n\nd", &options)?;
+///
+/// // Change `b` to `xyz`:
+/// let value = "# a\n\nxyz *c*\n\nd";
+/// let edit = Edit { start: 5, old_end: 6, new_end: 8 };
+/// let events = reparse(value, &events, &edit, &options)?;
+///
+/// assert_eq!(
+///     format!("{:?}", events),
+///     format!("{:?}", to_events(value, &options)?)
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn reparse(
+    value: &str,
+    events: &[Event],
+    edit: &Edit,
+    options: &ParseOptions,
+) -> Result<Vec<Event>, message::Message> {
+    reparse::reparse(value, events, edit, options)
+}
+
+/// Get the source of a node.
+///
+/// `value` is what was passed to [`to_mdast()`][], and `node` is a node in
+/// the tree it returned.
+/// The source is the exact markdown of the node, such as
+/// `[text](url "title")` for a link, or `*a*` for emphasis, so that unchanged
+/// nodes can be serialized verbatim.
+///
+/// Unlike [`event_source()`][], tabs are not turned into spaces: when a node
+/// starts in the middle of a tab (such as the content of `>\t\ta`), the
+/// whole tab is included.
+///
+/// Returns `None` if the node has no position (such as when it was created
+/// by hand).
+///
+/// ## Panics
+///
+/// This function panics if the position of `node` is not in `value`, such
+/// as when it comes from another document.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{node_source, to_mdast, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "a [b *c*](d \"e\") f";
+/// let tree = to_mdast(value, &ParseOptions::default())?;
+/// let paragraph = &tree.children().unwrap()[0];
+/// let link = &paragraph.children().unwrap()[1];
+///
+/// assert_eq!(node_source(value, link), Some("[b *c*](d \"e\")"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn node_source<'a>(value: &'a str, node: &mdast::Node) -> Option<&'a str> {
+    node.position()
+        .map(|position| &value[position.start.offset..position.end.offset])
+}
+
+/// Get the source of an event.
+///
+/// `value` and `options` are what was passed to [`to_events()`][], `events`
+/// are the events it returned, and `index` points to the enter event of a
+/// construct, like [`list_info()`][] and friends.
+/// The exit event of the construct works too.
+/// The source is everything between that enter and its corresponding exit.
+///
+/// Virtual spaces (parts of tabs) at the edges are turned into spaces, using
+/// [`tab_size`][ParseOptions::tab_size].
+/// To get the original characters instead, slice `value` with the `index`es
+/// of the points of the enter and exit events, or use [`node_source()`][]
+/// on mdast nodes.
+///
+/// ## Panics
+///
+/// This function panics if `index` is out of bounds.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{event_source, to_events, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "> a";
+/// let options = ParseOptions::default();
+/// let events = to_events(value, &options)?;
+///
+/// // The first event is the enter of the block quote.
+/// assert_eq!(event_source(value, &events, 0, &options), "> a");
+/// // The last event is its exit.
+/// assert_eq!(event_source(value, &events, events.len() - 1, &options), "> a");
+/// # Ok(())
+/// # }
+/// ```
+pub fn event_source(value: &str, events: &[Event], index: usize, options: &ParseOptions) -> String {
+    let name = &events[index].name;
+    let kind = &events[index].kind;
+    let mut balance = 0;
+    let mut other = index;
+
+    // Find the corresponding enter or exit, which can contain events of the
+    // same name.
+    loop {
+        if *kind == EventKind::Enter {
+            other += 1;
+        } else {
+            other -= 1;
+        }
+
+        if events[other].name == *name {
+            if events[other].kind == *kind {
+                balance += 1;
+            } else if balance == 0 {
+                break;
+            } else {
+                balance -= 1;
+            }
+        }
+    }
+
+    let (start, end) = if *kind == EventKind::Enter {
+        (index, other)
+    } else {
+        (other, index)
+    };
+
+    Slice::from_position(
+        value.as_bytes(),
+        &Position {
+            start: &events[start].point,
+            end: &events[end].point,
+        },
+        options.tab_size.max(1),
+    )
+    .serialize()
+}
+
+/// Get info on a list: whether it is ordered, its start number, its marker,
+/// and whether it is loose or tight.
+///
+/// `value` is the markdown passed to [`to_events()`][], `events` are the
+/// events it returned, and `index` points to the enter event of a list
+/// ([`ListOrdered`][EventName::ListOrdered] or
+/// [`ListUnordered`][EventName::ListUnordered]).
+///
+/// A list interrupted by something else (such as a paragraph) and then
+/// resumed after it, is actually two lists, which each have their own info.
+/// Blank lines between items do not interrupt a list, they make it loose.
+///
+/// ## Panics
+///
+/// This function panics if `index` does not point to the enter event of a
+/// list.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{list_info, to_events, EventKind, EventName, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "5) a\n\n6) b";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// assert_eq!(events[0].kind, EventKind::Enter);
+/// assert_eq!(events[0].name, EventName::ListOrdered);
+///
+/// let info = list_info(value, &events, 0);
+///
+/// assert!(info.ordered);
+/// assert_eq!(info.start, Some(5));
+/// assert_eq!(info.marker, ')');
+/// assert!(info.loose);
+/// # Ok(())
+/// # }
+/// ```
+pub fn list_info(value: &str, events: &[Event], index: usize) -> ListInfo {
+    assert_eq!(events[index].kind, EventKind::Enter, "expected enter event");
+    assert!(
+        matches!(
+            events[index].name,
+            EventName::ListOrdered | EventName::ListUnordered
+        ),
+        "expected list"
+    );
+    util::infer::list_info(value.as_bytes(), events, index)
+}
+
+/// Get info on a thematic break: its marker, and how many markers it has.
+///
+/// `value` is the markdown passed to [`to_events()`][], `events` are the
+/// events it returned, and `index` points to the enter event of a
+/// [`ThematicBreak`][EventName::ThematicBreak].
+///
+/// This is useful to keep the style of thematic breaks when formatting
+/// markdown: HTML (`<hr />`) and mdast do not have it.
+/// Only thematic breaks have this info: in `a\n---`, `---` is the underline
+/// of a setext heading ([`HeadingSetextUnderline`][EventName::HeadingSetextUnderline]),
+/// not a thematic break.
+///
+/// ## Panics
+///
+/// This function panics if `index` does not point to the enter event of a
+/// thematic break.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{thematic_break_info, to_events, EventKind, EventName, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "* * * *";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// assert_eq!(events[0].kind, EventKind::Enter);
+/// assert_eq!(events[0].name, EventName::ThematicBreak);
+///
+/// let info = thematic_break_info(value, &events, 0);
+///
+/// assert_eq!(info.marker, '*');
+/// assert_eq!(info.size, 4);
+/// # Ok(())
+/// # }
+/// ```
+pub fn thematic_break_info(value: &str, events: &[Event], index: usize) -> ThematicBreakInfo {
+    assert_eq!(events[index].kind, EventKind::Enter, "expected enter event");
+    assert_eq!(
+        events[index].name,
+        EventName::ThematicBreak,
+        "expected thematic break"
+    );
+    util::infer::thematic_break_info(value.as_bytes(), events, index)
+}
+
+/// Get info on the fences of code (fenced) or math (flow): their marker, and
+/// how many markers the opening and closing fences have.
+///
+/// `value` is the markdown passed to [`to_events()`][], `events` are the
+/// events it returned, and `index` points to the enter event of
+/// [`CodeFenced`][EventName::CodeFenced] or [`MathFlow`][EventName::MathFlow].
+///
+/// This is useful to keep the style of fences when formatting markdown:
+/// HTML (`<pre>`) and mdast do not have it.
+///
+/// ## Panics
+///
+/// This function panics if `index` does not point to the enter event of code
+/// (fenced) or math (flow).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{fence_info, to_events, EventKind, EventName, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "~~~~js\nconsole.log(1)\n~~~~~";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// assert_eq!(events[0].kind, EventKind::Enter);
+/// assert_eq!(events[0].name, EventName::CodeFenced);
+///
+/// let info = fence_info(value, &events, 0);
+///
+/// assert_eq!(info.marker, '~');
+/// assert_eq!(info.size, 4);
+/// assert_eq!(info.closing_size, Some(5));
+/// # Ok(())
+/// # }
+/// ```
+pub fn fence_info(value: &str, events: &[Event], index: usize) -> FenceInfo {
+    assert_eq!(events[index].kind, EventKind::Enter, "expected enter event");
+    assert!(
+        matches!(
+            events[index].name,
+            EventName::CodeFenced | EventName::MathFlow
+        ),
+        "expected code (fenced) or math (flow)"
+    );
+    util::infer::fence_info(value.as_bytes(), events, index)
+}
+
+/// Get the kind of HTML (flow): which of the seven kinds of HTML blocks in
+/// `CommonMark` it is, which determines how it ends.
+///
+/// `value` is the markdown passed to [`to_events()`][], `events` are the
+/// events it returned, and `index` points to the enter event of
+/// [`HtmlFlow`][EventName::HtmlFlow].
+///
+/// ## Panics
+///
+/// This function panics if `index` does not point to the enter event of HTML
+/// (flow).
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{html_flow_kind, to_events, EventKind, EventName, HtmlFlowKind, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "<table>\n<tr><td>a</td></tr>\n</table>\n\n*b*";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// assert_eq!(events[0].kind, EventKind::Enter);
+/// assert_eq!(events[0].name, EventName::HtmlFlow);
+///
+/// // Ends at the blank line.
+/// assert_eq!(html_flow_kind(value, &events, 0), HtmlFlowKind::Basic);
+/// # Ok(())
+/// # }
+/// ```
+pub fn html_flow_kind(value: &str, events: &[Event], index: usize) -> HtmlFlowKind {
+    assert_eq!(events[index].kind, EventKind::Enter, "expected enter event");
+    assert_eq!(
+        events[index].name,
+        EventName::HtmlFlow,
+        "expected html (flow)"
+    );
+    util::infer::html_flow_kind(value.as_bytes(), events, index)
+}
+
+/// Get info on a resource: the `(…)` after the label of a link or image,
+/// with its destination and title decoded.
+///
+/// `value` is the markdown passed to [`to_events()`][], `events` are the
+/// events it returned, and `index` points to the enter event of a
+/// [`Resource`][EventName::Resource].
+///
+/// Events point into `value`, so the destination and title they span
+/// still contain character escapes (`\(`) and character references
+/// (`&amp;`).
+/// This gets them decoded, as they are in HTML and mdast.
+///
+/// ## Panics
+///
+/// This function panics if `index` does not point to the enter event of a
+/// resource.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{resource_info, to_events, EventKind, EventName, ParseOptions};
+/// # fn main() -> Result<(), markdown::message::Message> {
+///
+/// let value = "[a](\\(b\\) \"c &amp; d\")";
+/// let events = to_events(value, &ParseOptions::default())?;
+///
+/// let index = events
+///     .iter()
+///     .position(|d| d.kind == EventKind::Enter && d.name == EventName::Resource)
+///     .unwrap();
+/// let info = resource_info(value, &events, index);
+///
+/// assert_eq!(info.destination, "(b)");
+/// assert!(!info.literal);
+/// assert_eq!(info.title, Some("c & d".into()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn resource_info(value: &str, events: &[Event], index: usize) -> ResourceInfo {
+    assert_eq!(events[index].kind, EventKind::Enter, "expected enter event");
+    assert_eq!(events[index].name, EventName::Resource, "expected resource");
+    util::infer::resource_info(value.as_bytes(), events, index)
+}
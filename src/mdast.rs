@@ -98,6 +98,8 @@ pub enum Node {
     BlockQuote(BlockQuote),
     /// Footnote definition.
     FootnoteDefinition(FootnoteDefinition),
+    /// Directive (container).
+    ContainerDirective(ContainerDirective),
     /// MDX: JSX element (container).
     MdxJsxFlowElement(MdxJsxFlowElement),
     /// List.
@@ -128,6 +130,8 @@ pub enum Node {
     FootnoteReference(FootnoteReference),
     /// Html (phrasing).
     Html(Html),
+    /// Highlight.
+    Highlight(Highlight),
     /// Image.
     Image(Image),
     /// Image reference.
@@ -140,8 +144,14 @@ pub enum Node {
     LinkReference(LinkReference),
     /// Strong
     Strong(Strong),
+    /// Subscript.
+    Subscript(Subscript),
+    /// Superscript.
+    Superscript(Superscript),
     /// Text.
     Text(Text),
+    /// Directive (text).
+    TextDirective(TextDirective),
 
     // Flow:
     /// Code (flow).
@@ -152,6 +162,8 @@ pub enum Node {
     MdxFlowExpression(MdxFlowExpression),
     /// Heading.
     Heading(Heading),
+    /// Directive (leaf).
+    LeafDirective(LeafDirective),
     /// Html (flow).
     // Html(Html),
     /// Table.
@@ -185,6 +197,7 @@ impl fmt::Debug for Node {
             Node::Root(x) => x.fmt(f),
             Node::BlockQuote(x) => x.fmt(f),
             Node::FootnoteDefinition(x) => x.fmt(f),
+            Node::ContainerDirective(x) => x.fmt(f),
             Node::MdxJsxFlowElement(x) => x.fmt(f),
             Node::List(x) => x.fmt(f),
             Node::MdxjsEsm(x) => x.fmt(f),
@@ -198,17 +211,22 @@ impl fmt::Debug for Node {
             Node::MdxTextExpression(x) => x.fmt(f),
             Node::FootnoteReference(x) => x.fmt(f),
             Node::Html(x) => x.fmt(f),
+            Node::Highlight(x) => x.fmt(f),
             Node::Image(x) => x.fmt(f),
             Node::ImageReference(x) => x.fmt(f),
             Node::MdxJsxTextElement(x) => x.fmt(f),
             Node::Link(x) => x.fmt(f),
             Node::LinkReference(x) => x.fmt(f),
             Node::Strong(x) => x.fmt(f),
+            Node::Subscript(x) => x.fmt(f),
+            Node::Superscript(x) => x.fmt(f),
             Node::Text(x) => x.fmt(f),
+            Node::TextDirective(x) => x.fmt(f),
             Node::Code(x) => x.fmt(f),
             Node::Math(x) => x.fmt(f),
             Node::MdxFlowExpression(x) => x.fmt(f),
             Node::Heading(x) => x.fmt(f),
+            Node::LeafDirective(x) => x.fmt(f),
             Node::Table(x) => x.fmt(f),
             Node::ThematicBreak(x) => x.fmt(f),
             Node::TableRow(x) => x.fmt(f),
@@ -238,6 +256,12 @@ impl ToString for Node {
             Node::Delete(x) => children_to_string(&x.children),
             Node::Emphasis(x) => children_to_string(&x.children),
             Node::MdxJsxTextElement(x) => children_to_string(&x.children),
+            Node::Superscript(x) => children_to_string(&x.children),
+            Node::Subscript(x) => children_to_string(&x.children),
+            Node::Highlight(x) => children_to_string(&x.children),
+            Node::LeafDirective(x) => children_to_string(&x.children),
+            Node::TextDirective(x) => children_to_string(&x.children),
+            Node::ContainerDirective(x) => children_to_string(&x.children),
             Node::Link(x) => children_to_string(&x.children),
             Node::LinkReference(x) => children_to_string(&x.children),
             Node::Strong(x) => children_to_string(&x.children),
@@ -294,6 +318,12 @@ impl Node {
             Node::Delete(x) => Some(&x.children),
             Node::MdxJsxFlowElement(x) => Some(&x.children),
             Node::MdxJsxTextElement(x) => Some(&x.children),
+            Node::Superscript(x) => Some(&x.children),
+            Node::Subscript(x) => Some(&x.children),
+            Node::Highlight(x) => Some(&x.children),
+            Node::LeafDirective(x) => Some(&x.children),
+            Node::TextDirective(x) => Some(&x.children),
+            Node::ContainerDirective(x) => Some(&x.children),
             // Non-parent.
             _ => None,
         }
@@ -319,6 +349,12 @@ impl Node {
             Node::Delete(x) => Some(&mut x.children),
             Node::MdxJsxFlowElement(x) => Some(&mut x.children),
             Node::MdxJsxTextElement(x) => Some(&mut x.children),
+            Node::Superscript(x) => Some(&mut x.children),
+            Node::Subscript(x) => Some(&mut x.children),
+            Node::Highlight(x) => Some(&mut x.children),
+            Node::LeafDirective(x) => Some(&mut x.children),
+            Node::TextDirective(x) => Some(&mut x.children),
+            Node::ContainerDirective(x) => Some(&mut x.children),
             // Non-parent.
             _ => None,
         }
@@ -330,6 +366,7 @@ impl Node {
             Node::Root(x) => x.position.as_ref(),
             Node::BlockQuote(x) => x.position.as_ref(),
             Node::FootnoteDefinition(x) => x.position.as_ref(),
+            Node::ContainerDirective(x) => x.position.as_ref(),
             Node::MdxJsxFlowElement(x) => x.position.as_ref(),
             Node::List(x) => x.position.as_ref(),
             Node::MdxjsEsm(x) => x.position.as_ref(),
@@ -343,17 +380,22 @@ impl Node {
             Node::MdxTextExpression(x) => x.position.as_ref(),
             Node::FootnoteReference(x) => x.position.as_ref(),
             Node::Html(x) => x.position.as_ref(),
+            Node::Highlight(x) => x.position.as_ref(),
             Node::Image(x) => x.position.as_ref(),
             Node::ImageReference(x) => x.position.as_ref(),
             Node::MdxJsxTextElement(x) => x.position.as_ref(),
             Node::Link(x) => x.position.as_ref(),
             Node::LinkReference(x) => x.position.as_ref(),
             Node::Strong(x) => x.position.as_ref(),
+            Node::Subscript(x) => x.position.as_ref(),
+            Node::Superscript(x) => x.position.as_ref(),
             Node::Text(x) => x.position.as_ref(),
+            Node::TextDirective(x) => x.position.as_ref(),
             Node::Code(x) => x.position.as_ref(),
             Node::Math(x) => x.position.as_ref(),
             Node::MdxFlowExpression(x) => x.position.as_ref(),
             Node::Heading(x) => x.position.as_ref(),
+            Node::LeafDirective(x) => x.position.as_ref(),
             Node::Table(x) => x.position.as_ref(),
             Node::ThematicBreak(x) => x.position.as_ref(),
             Node::TableRow(x) => x.position.as_ref(),
@@ -369,6 +411,7 @@ impl Node {
             Node::Root(x) => x.position.as_mut(),
             Node::BlockQuote(x) => x.position.as_mut(),
             Node::FootnoteDefinition(x) => x.position.as_mut(),
+            Node::ContainerDirective(x) => x.position.as_mut(),
             Node::MdxJsxFlowElement(x) => x.position.as_mut(),
             Node::List(x) => x.position.as_mut(),
             Node::MdxjsEsm(x) => x.position.as_mut(),
@@ -382,17 +425,22 @@ impl Node {
             Node::MdxTextExpression(x) => x.position.as_mut(),
             Node::FootnoteReference(x) => x.position.as_mut(),
             Node::Html(x) => x.position.as_mut(),
+            Node::Highlight(x) => x.position.as_mut(),
             Node::Image(x) => x.position.as_mut(),
             Node::ImageReference(x) => x.position.as_mut(),
             Node::MdxJsxTextElement(x) => x.position.as_mut(),
             Node::Link(x) => x.position.as_mut(),
             Node::LinkReference(x) => x.position.as_mut(),
             Node::Strong(x) => x.position.as_mut(),
+            Node::Subscript(x) => x.position.as_mut(),
+            Node::Superscript(x) => x.position.as_mut(),
             Node::Text(x) => x.position.as_mut(),
+            Node::TextDirective(x) => x.position.as_mut(),
             Node::Code(x) => x.position.as_mut(),
             Node::Math(x) => x.position.as_mut(),
             Node::MdxFlowExpression(x) => x.position.as_mut(),
             Node::Heading(x) => x.position.as_mut(),
+            Node::LeafDirective(x) => x.position.as_mut(),
             Node::Table(x) => x.position.as_mut(),
             Node::ThematicBreak(x) => x.position.as_mut(),
             Node::TableRow(x) => x.position.as_mut(),
@@ -408,6 +456,7 @@ impl Node {
             Node::Root(x) => x.position = position,
             Node::BlockQuote(x) => x.position = position,
             Node::FootnoteDefinition(x) => x.position = position,
+            Node::ContainerDirective(x) => x.position = position,
             Node::MdxJsxFlowElement(x) => x.position = position,
             Node::List(x) => x.position = position,
             Node::MdxjsEsm(x) => x.position = position,
@@ -421,17 +470,22 @@ impl Node {
             Node::MdxTextExpression(x) => x.position = position,
             Node::FootnoteReference(x) => x.position = position,
             Node::Html(x) => x.position = position,
+            Node::Highlight(x) => x.position = position,
             Node::Image(x) => x.position = position,
             Node::ImageReference(x) => x.position = position,
             Node::MdxJsxTextElement(x) => x.position = position,
             Node::Link(x) => x.position = position,
             Node::LinkReference(x) => x.position = position,
             Node::Strong(x) => x.position = position,
+            Node::Subscript(x) => x.position = position,
+            Node::Superscript(x) => x.position = position,
             Node::Text(x) => x.position = position,
+            Node::TextDirective(x) => x.position = position,
             Node::Code(x) => x.position = position,
             Node::Math(x) => x.position = position,
             Node::MdxFlowExpression(x) => x.position = position,
             Node::Heading(x) => x.position = position,
+            Node::LeafDirective(x) => x.position = position,
             Node::Table(x) => x.position = position,
             Node::ThematicBreak(x) => x.position = position,
             Node::TableRow(x) => x.position = position,
@@ -628,6 +682,9 @@ pub struct List {
     /// Starting number of the list.
     /// `None` when unordered.
     pub start: Option<u32>,
+    /// Delimiter after the numbers of the items (`.` or `)`).
+    /// `None` when unordered.
+    pub delimiter: Option<char>,
     /// One or more of its children are separated with a blank line from its
     /// siblings (when `true`), or not (when `false`).
     pub spread: bool,
@@ -658,6 +715,11 @@ pub struct ListItem {
     /// GFM: whether the item is done (when `true`), not done (when `false`),
     /// or indeterminate or not applicable (`None`).
     pub checked: Option<bool>,
+    /// Number of the item in an ordered list: the `start` of the list plus
+    /// the index of the item, as it would be numbered when rendered (the
+    /// numbers in the source are ignored, except for the first).
+    /// `None` when in an unordered list.
+    pub number: Option<u32>,
 }
 
 /// Html (flow or phrasing).
@@ -1171,6 +1233,155 @@ pub struct Delete {
     pub position: Option<Position>,
 }
 
+/// Highlight.
+///
+/// ```markdown
+/// > | ==a==
+///     ^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "highlight")
+)]
+pub struct Highlight {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Subscript.
+///
+/// ```markdown
+/// > | ~a~
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "subscript")
+)]
+pub struct Subscript {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Superscript.
+///
+/// ```markdown
+/// > | ^a^
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "superscript")
+)]
+pub struct Superscript {
+    // Parent.
+    /// Content model.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+}
+
+/// Directive (container).
+///
+/// ```markdown
+/// > | :::a[b]{c=d}
+///     ^^^^^^^^^^^^
+/// > | e
+///     ^
+/// > | :::
+///     ^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "containerDirective")
+)]
+pub struct ContainerDirective {
+    // Parent.
+    /// Content model.
+    ///
+    /// The label (`b`), if there is one, is the first child: a paragraph with
+    /// the position of the label.
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Directive.
+    /// Name (`a`).
+    pub name: String,
+    /// Attributes (`{c=d}`), in the order they were first seen.
+    ///
+    /// Character references in values are decoded.
+    /// The `#id` shortcut sets `id`, where the last one wins.
+    /// The `.class` shortcut and `class` attributes are joined with spaces.
+    /// Attributes without a value have an empty string as value.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (leaf).
+///
+/// ```markdown
+/// > | ::a[b]{c=d}
+///     ^^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "leafDirective")
+)]
+pub struct LeafDirective {
+    // Parent.
+    /// Content model: the label (`b`).
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Directive.
+    /// Name (`a`).
+    pub name: String,
+    /// Attributes (`{c=d}`), as with
+    /// [`ContainerDirective`][ContainerDirective::attributes].
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Directive (text).
+///
+/// ```markdown
+/// > | a :b[c]{d=e} f
+///       ^^^^^^^^^^
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename = "textDirective")
+)]
+pub struct TextDirective {
+    // Parent.
+    /// Content model: the label (`c`).
+    pub children: Vec<Node>,
+    /// Positional info.
+    pub position: Option<Position>,
+    // Directive.
+    /// Name (`b`).
+    pub name: String,
+    /// Attributes (`{d=e}`), as with
+    /// [`ContainerDirective`][ContainerDirective::attributes].
+    pub attributes: Vec<(String, String)>,
+}
+
 /// Frontmatter: yaml.
 ///
 /// ```markdown
@@ -1897,6 +2108,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn highlight() {
+        let mut node = Node::Highlight(Highlight {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Highlight { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Highlight { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
     #[test]
     fn emphasis() {
         let mut node = Node::Emphasis(Emphasis {
@@ -1955,6 +2195,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn subscript() {
+        let mut node = Node::Subscript(Subscript {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Subscript { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Subscript { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn superscript() {
+        let mut node = Node::Superscript(Superscript {
+            position: None,
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "Superscript { children: [], position: None }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "Superscript { children: [], position: Some(1:1-1:2 (0-1)) }",
+            "should support `position_set`"
+        );
+    }
+
     #[test]
     fn paragraph() {
         let mut node = Node::Paragraph(Paragraph {
@@ -2108,12 +2406,13 @@ mod tests {
             position: None,
             spread: false,
             checked: None,
+            number: None,
             children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "ListItem { children: [], position: None, spread: false, checked: None }",
+            "ListItem { children: [], position: None, spread: false, checked: None, number: None }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2128,7 +2427,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "ListItem { children: [], position: Some(1:1-1:2 (0-1)), spread: false, checked: None }",
+            "ListItem { children: [], position: Some(1:1-1:2 (0-1)), spread: false, checked: None, number: None }",
             "should support `position_set`"
         );
     }
@@ -2140,12 +2439,13 @@ mod tests {
             spread: false,
             ordered: false,
             start: None,
+            delimiter: None,
             children: vec![],
         });
 
         assert_eq!(
             format!("{:?}", node),
-            "List { children: [], position: None, ordered: false, start: None, spread: false }",
+            "List { children: [], position: None, ordered: false, start: None, delimiter: None, spread: false }",
             "should support `Debug`"
         );
         assert_eq!(node.to_string(), "", "should support `ToString`");
@@ -2160,7 +2460,7 @@ mod tests {
         node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
         assert_eq!(
             format!("{:?}", node),
-            "List { children: [], position: Some(1:1-1:2 (0-1)), ordered: false, start: None, spread: false }",
+            "List { children: [], position: Some(1:1-1:2 (0-1)), ordered: false, start: None, delimiter: None, spread: false }",
             "should support `position_set`"
         );
     }
@@ -2320,4 +2620,97 @@ mod tests {
             "should support `position_set`"
         );
     }
+
+    #[test]
+    fn container_directive() {
+        let mut node = Node::ContainerDirective(ContainerDirective {
+            position: None,
+            name: "a".into(),
+            attributes: vec![],
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: None, name: \"a\", attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "ContainerDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn leaf_directive() {
+        let mut node = Node::LeafDirective(LeafDirective {
+            position: None,
+            name: "a".into(),
+            attributes: vec![],
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "LeafDirective { children: [], position: None, name: \"a\", attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "LeafDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", attributes: [] }",
+            "should support `position_set`"
+        );
+    }
+
+    #[test]
+    fn text_directive() {
+        let mut node = Node::TextDirective(TextDirective {
+            position: None,
+            name: "a".into(),
+            attributes: vec![],
+            children: vec![],
+        });
+
+        assert_eq!(
+            format!("{:?}", node),
+            "TextDirective { children: [], position: None, name: \"a\", attributes: [] }",
+            "should support `Debug`"
+        );
+        assert_eq!(node.to_string(), "", "should support `ToString`");
+        assert_eq!(
+            node.children_mut(),
+            Some(&mut vec![]),
+            "should support `children_mut`"
+        );
+        assert_eq!(node.children(), Some(&vec![]), "should support `children`");
+        assert_eq!(node.position(), None, "should support `position`");
+        assert_eq!(node.position_mut(), None, "should support `position`");
+        node.position_set(Some(Position::new(1, 1, 0, 1, 2, 1)));
+        assert_eq!(
+            format!("{:?}", node),
+            "TextDirective { children: [], position: Some(1:1-1:2 (0-1)), name: \"a\", attributes: [] }",
+            "should support `position_set`"
+        );
+    }
 }
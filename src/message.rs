@@ -17,12 +17,15 @@ impl fmt::Display for Message {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(ref place) = self.place {
             write!(f, "{}: ", place)?;
-        };
+        }
 
         write!(f, "{} ({}:{})", self.reason, self.source, self.rule_id)
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Message {}
+
 /// Somewhere.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Place {
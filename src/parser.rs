@@ -1,5 +1,6 @@
 //! Turn bytes of markdown into events.
 
+use crate::construct::text::MARKERS as TEXT_MARKERS;
 use crate::event::{Event, Point};
 use crate::message;
 use crate::state::{Name as StateName, State};
@@ -7,12 +8,18 @@ use crate::subtokenize::subtokenize;
 use crate::tokenizer::Tokenizer;
 use crate::util::location::Location;
 use crate::ParseOptions;
-use alloc::{string::String, vec, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 
 /// Info needed, in all content types, when parsing markdown.
 ///
 /// Importantly, this contains a set of known definitions.
 /// It also references the input value as bytes (`u8`).
+///
+/// A state belongs to one document: it is made by [`ParseState::new`] for
+/// every parse and never reused.
+/// The bytes are not copied, so there is no buffer worth keeping around, and
+/// not reusing it means that definitions, footnote definitions, and messages
+/// of one document cannot leak into another.
 #[derive(Debug)]
 pub struct ParseState<'a> {
     /// Configuration.
@@ -21,10 +28,67 @@ pub struct ParseState<'a> {
     pub options: &'a ParseOptions,
     /// List of chars.
     pub bytes: &'a [u8],
+    /// Size of a tab stop, at least `1`.
+    pub tab_size: usize,
     /// Set of defined definition identifiers.
     pub definitions: Vec<String>,
     /// Set of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of defined abbreviation labels.
+    ///
+    /// Abbreviation definitions are flow, so they are all known before text
+    /// is parsed.
+    pub abbreviations: Vec<String>,
+    /// List of warnings, such as for references that do not match a
+    /// definition.
+    pub messages: Vec<message::Message>,
+    /// Bytes that can start something in text, when there are text
+    /// extensions.
+    pub text_markers: Vec<u8>,
+    /// Work done by constructs and resolvers.
+    #[cfg(feature = "profile")]
+    pub profile: core::cell::RefCell<crate::util::profile::Profile>,
+}
+
+impl<'a> ParseState<'a> {
+    /// Create a new, empty, state for `value`.
+    pub fn new(value: &'a str, options: &'a ParseOptions) -> ParseState<'a> {
+        let bytes = value.as_bytes();
+        let mut text_markers = vec![];
+
+        if !options.text_extensions.is_empty() {
+            text_markers.extend_from_slice(&TEXT_MARKERS);
+            let mut index = 0;
+
+            while index < options.text_extensions.len() {
+                let marker = options.text_extensions[index].marker;
+
+                if !text_markers.contains(&marker) {
+                    text_markers.push(marker);
+                }
+
+                index += 1;
+            }
+        }
+
+        ParseState {
+            options,
+            bytes,
+            tab_size: options.tab_size.max(1),
+            location: if options.mdx_esm_parse.is_some() || options.mdx_expression_parse.is_some() {
+                Some(Location::new(bytes))
+            } else {
+                None
+            },
+            definitions: vec![],
+            gfm_footnote_definitions: vec![],
+            abbreviations: vec![],
+            messages: vec![],
+            text_markers,
+            #[cfg(feature = "profile")]
+            profile: core::cell::RefCell::default(),
+        }
+    }
 }
 
 /// Turn a string of markdown into events.
@@ -34,19 +98,8 @@ pub fn parse<'a>(
     value: &'a str,
     options: &'a ParseOptions,
 ) -> Result<(Vec<Event>, ParseState<'a>), message::Message> {
-    let bytes = value.as_bytes();
-
-    let mut parse_state = ParseState {
-        options,
-        bytes,
-        location: if options.mdx_esm_parse.is_some() || options.mdx_expression_parse.is_some() {
-            Some(Location::new(bytes))
-        } else {
-            None
-        },
-        definitions: vec![],
-        gfm_footnote_definitions: vec![],
-    };
+    let value = limit(value, options)?;
+    let mut parse_state = ParseState::new(value, options);
 
     let start = Point {
         line: 1,
@@ -69,11 +122,44 @@ pub fn parse<'a>(
         let defs = &mut parse_state.definitions;
         fn_defs.append(&mut result.gfm_footnote_definitions);
         defs.append(&mut result.definitions);
+        parse_state.abbreviations.append(&mut result.abbreviations);
+        parse_state.messages.append(&mut result.messages);
 
         if result.done {
             return Ok((events, parse_state));
         }
 
-        result = subtokenize(&mut events, &parse_state, &None)?;
+        result = subtokenize(&mut events, &parse_state, None)?;
+    }
+}
+
+/// Check `value` against `max_input_bytes`, truncating it if allowed.
+fn limit<'a>(value: &'a str, options: &ParseOptions) -> Result<&'a str, message::Message> {
+    if let Some(max) = options.max_input_bytes {
+        if value.len() > max {
+            if !options.truncate_input {
+                return Err(message::Message {
+                    place: None,
+                    reason: format!(
+                        "Unexpected input of {} bytes, expected at most {} bytes",
+                        value.len(),
+                        max
+                    ),
+                    rule_id: Box::new("max-input-bytes".into()),
+                    source: Box::new("markdown-rs".into()),
+                });
+            }
+
+            let mut end = max;
+
+            // Do not split characters.
+            while !value.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            return Ok(&value[..end]);
+        }
     }
+
+    Ok(value)
 }
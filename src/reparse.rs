@@ -0,0 +1,236 @@
+//! Turn bytes of changed markdown into events, reusing earlier events.
+
+use crate::event::{Event, Kind, Name, Point};
+use crate::parser::parse;
+use crate::{message, ParseOptions};
+use alloc::vec::Vec;
+
+/// Change to a document.
+///
+/// Bytes from `start` up to `old_end` in the old document are replaced by
+/// bytes from `start` up to `new_end` in the new document.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::Edit;
+/// # fn main() {
+///
+/// // `a b` to `a xyz b`:
+/// let insert = Edit { start: 2, old_end: 2, new_end: 6 };
+///
+/// // `a xyz b` to `a b`:
+/// let delete = Edit { start: 2, old_end: 6, new_end: 2 };
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Edit {
+    /// Index of the first changed byte (in both documents).
+    pub start: usize,
+    /// Index after the last changed byte in the old document.
+    pub old_end: usize,
+    /// Index after the last changed byte in the new document.
+    pub new_end: usize,
+}
+
+/// Turn a string of changed markdown into events, reusing the events of the
+/// old document where possible.
+pub fn reparse(
+    value: &str,
+    events: &[Event],
+    edit: &Edit,
+    options: &ParseOptions,
+) -> Result<Vec<Event>, message::Message> {
+    if let Some(events) = reparse_paragraph(value, events, edit, options) {
+        Ok(events)
+    } else {
+        Ok(parse(value, options)?.0)
+    }
+}
+
+/// Parse the paragraph that changed again, if that can be done in isolation.
+fn reparse_paragraph(
+    value: &str,
+    events: &[Event],
+    edit: &Edit,
+    options: &ParseOptions,
+) -> Option<Vec<Event>> {
+    // MDX uses the location in the whole document for errors, and the input
+    // limit applies to the whole document.
+    // A paragraph can turn into the term of a description after it, with a
+    // blank line between them.
+    // Abbreviations depend on definitions elsewhere in the document.
+    if options.constructs.abbreviation
+        || options.constructs.definition_list
+        || options.mdx_esm_parse.is_some()
+        || options.mdx_expression_parse.is_some()
+        || options.max_input_bytes.is_some()
+        || edit.start > edit.old_end
+        || edit.start > edit.new_end
+    {
+        return None;
+    }
+
+    let (open, close) = find_paragraph(events, edit)?;
+    let bytes = value.as_bytes();
+    let start = events[open].point.index;
+    let end = (events[close].point.index + edit.new_end).checked_sub(edit.old_end)?;
+
+    if end > bytes.len()
+        || start >= end
+        || events[open].point.column != 1
+        // Must not be indented, which could make it part of a list item
+        // before, or code.
+        || matches!(bytes[start], b'\t' | b' ')
+        || !matches!(bytes.get(end), None | Some(b'\n' | b'\r'))
+    {
+        return None;
+    }
+
+    let mut index = start;
+
+    while index < end {
+        // References depend on definitions elsewhere in the document.
+        if matches!(bytes[index], b'[' | b']') {
+            return None;
+        }
+
+        index += 1;
+    }
+
+    let (mut inner, _) = parse(core::str::from_utf8(&bytes[start..end]).ok()?, options).ok()?;
+
+    // Must still be one paragraph.
+    if inner.len() < 2
+        || inner[0].name != Name::Paragraph
+        || find_exit(&inner[1..]) != Some(inner.len() - 2)
+    {
+        return None;
+    }
+
+    let old_start = &events[open].point;
+    let old_exit = events[close].point.clone();
+    let new_exit = shift_point(&inner[inner.len() - 1].point, old_start);
+    let old_after = close + 1;
+    let new_after = open + inner.len();
+    let mut result = Vec::with_capacity(events.len() - old_after + new_after);
+
+    result.extend_from_slice(&events[0..open]);
+
+    index = 0;
+    while index < inner.len() {
+        let event = &mut inner[index];
+        event.point = shift_point(&event.point, old_start);
+        shift_link(event, 0, open);
+        index += 1;
+    }
+
+    result.append(&mut inner);
+
+    index = old_after;
+    while index < events.len() {
+        let mut event = events[index].clone();
+
+        // Right after the paragraph, on its last line.
+        if event.point.index == old_exit.index && event.point.vs == old_exit.vs {
+            event.point = new_exit.clone();
+        } else {
+            event.point.line = event.point.line - old_exit.line + new_exit.line;
+            event.point.index = event.point.index - old_exit.index + new_exit.index;
+        }
+
+        shift_link(&mut event, old_after, new_after);
+        result.push(event);
+        index += 1;
+    }
+
+    Some(result)
+}
+
+/// Find the enter and exit of a paragraph, not in a container and
+/// surrounded by blank lines, that contains `edit`.
+fn find_paragraph(events: &[Event], edit: &Edit) -> Option<(usize, usize)> {
+    let mut index = 0;
+    let mut depth = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            if depth == 0
+                && event.name == Name::Paragraph
+                && event.point.index <= edit.start
+                && (index == 0 || events[index - 1].name == Name::BlankLineEnding)
+            {
+                let close = index + 1 + find_exit(&events[index + 1..])?;
+
+                // Before the edit.
+                if events[close].point.index < edit.start {
+                    index = close + 1;
+                    continue;
+                }
+
+                if events[close].point.index >= edit.old_end
+                    && (close + 1 == events.len()
+                        || (events[close + 1].name == Name::LineEnding
+                            && (close + 3 == events.len()
+                                || events[close + 3].name == Name::BlankLineEnding)))
+                {
+                    return Some((index, close));
+                }
+
+                return None;
+            }
+
+            depth += 1;
+        } else {
+            depth -= 1;
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Find the exit that closes the enter right before `events`.
+fn find_exit(events: &[Event]) -> Option<usize> {
+    let mut index = 0;
+    let mut depth = 0;
+
+    while index < events.len() {
+        if events[index].kind == Kind::Enter {
+            depth += 1;
+        } else if depth == 0 {
+            return Some(index);
+        } else {
+            depth -= 1;
+        }
+
+        index += 1;
+    }
+
+    None
+}
+
+/// Move a point in a paragraph parsed on its own to where the paragraph
+/// starts (`start`) in the document.
+///
+/// Paragraphs start at column 1, so only lines and indices change.
+fn shift_point(point: &Point, start: &Point) -> Point {
+    Point {
+        line: point.line + start.line - 1,
+        column: point.column,
+        index: point.index + start.index,
+        vs: point.vs,
+    }
+}
+
+/// Move the links of an event, from events starting at `from`, to events
+/// starting at `to`.
+fn shift_link(event: &mut Event, from: usize, to: usize) {
+    if let Some(link) = &mut event.link {
+        link.previous = link.previous.map(|index| index - from + to);
+        link.next = link.next.map(|index| index - from + to);
+    }
+}
@@ -6,7 +6,14 @@ use crate::subtokenize::Subresult;
 use crate::tokenizer::Tokenizer;
 
 /// Names of resolvers.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+///
+/// Resolvers run in the order in which they are defined here, no matter in
+/// which order they are registered.
+/// The order matters: for example, labels have to be resolved before
+/// attention, as emphasis cannot cross links, and attention has to be
+/// resolved before data is merged, as unmatched attention sequences turn
+/// into data.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum Name {
     /// Resolve labels.
     ///
@@ -21,11 +28,18 @@ pub enum Name {
     /// and what occurs before and after each sequence.
     /// Otherwise they are turned into data.
     Attention,
-    /// Resolve GFM tables.
+    /// Resolve list item.
     ///
-    /// The table head, and later each row, are all parsed separately.
-    /// Resolving groups everything together, and groups cells.
-    GfmTable,
+    /// List items are parsed on their own.
+    /// They are wrapped into ordered or unordered lists based on whether items
+    /// with the same marker occur next to each other.
+    ListItem,
+    /// Resolve content.
+    ///
+    /// Content is parsed as single lines, as what remains if other flow
+    /// constructs don’t match.
+    /// But, when they occur next to each other, they need to be merged.
+    Content,
     /// Resolve heading (atx).
     ///
     /// Heading (atx) contains further sequences and data.
@@ -37,18 +51,17 @@ pub enum Name {
     /// Heading (setext) is parsed as an underline that is preceded by content,
     /// both will form the whole construct.
     HeadingSetext,
-    /// Resolve list item.
+    /// Resolve definition lists.
     ///
-    /// List items are parsed on their own.
-    /// They are wrapped into ordered or unordered lists based on whether items
-    /// with the same marker occur next to each other.
-    ListItem,
-    /// Resolve content.
+    /// Definition list descriptions are parsed on their own.
+    /// The paragraph before a description is turned into its term, and
+    /// adjacent terms and descriptions are wrapped into definition lists.
+    DefinitionList,
+    /// Resolve GFM tables.
     ///
-    /// Content is parsed as single lines, as what remains if other flow
-    /// constructs don’t match.
-    /// But, when they occur next to each other, they need to be merged.
-    Content,
+    /// The table head, and later each row, are all parsed separately.
+    /// Resolving groups everything together, and groups cells.
+    GfmTable,
     /// Resolve data.
     ///
     /// Data is parsed as many small bits, due to many punctuation characters
@@ -67,11 +80,12 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> Result<Option<Subresult>,
     let result = match name {
         Name::Label => construct::label_end::resolve(tokenizer),
         Name::Attention => construct::attention::resolve(tokenizer),
-        Name::GfmTable => construct::gfm_table::resolve(tokenizer),
-        Name::HeadingAtx => construct::heading_atx::resolve(tokenizer),
-        Name::HeadingSetext => construct::heading_setext::resolve(tokenizer),
         Name::ListItem => construct::list_item::resolve(tokenizer),
         Name::Content => construct::content::resolve(tokenizer)?,
+        Name::HeadingAtx => construct::heading_atx::resolve(tokenizer),
+        Name::HeadingSetext => construct::heading_setext::resolve(tokenizer),
+        Name::DefinitionList => construct::definition_list::resolve(tokenizer),
+        Name::GfmTable => construct::gfm_table::resolve(tokenizer),
         Name::Data => construct::partial_data::resolve(tokenizer),
         Name::String => construct::string::resolve(tokenizer),
         Name::Text => construct::text::resolve(tokenizer),
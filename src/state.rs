@@ -43,6 +43,19 @@ impl State {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[allow(clippy::enum_variant_names)]
 pub enum Name {
+    AbbreviationDefinitionStart,
+    AbbreviationDefinitionBefore,
+    AbbreviationDefinitionLabelBefore,
+    AbbreviationDefinitionLabelStart,
+    AbbreviationDefinitionLabelInside,
+    AbbreviationDefinitionLabelAfter,
+    AbbreviationDefinitionMarkerAfter,
+    AbbreviationDefinitionTitleBefore,
+    AbbreviationDefinitionTitleInside,
+    AbbreviationDefinitionTitleWhitespace,
+    AbbreviationDefinitionTitleEnd,
+    AbbreviationDefinitionAfter,
+
     AttentionStart,
     AttentionInside,
 
@@ -107,6 +120,64 @@ pub enum Name {
     DefinitionTitleAfter,
     DefinitionTitleAfterOptionalWhitespace,
 
+    DefinitionListStart,
+    DefinitionListBefore,
+    DefinitionListMarkerAfter,
+    DefinitionListMarkerAfterFilled,
+    DefinitionListWhitespace,
+    DefinitionListWhitespaceAfter,
+    DefinitionListPrefixOther,
+    DefinitionListAfter,
+
+    DirectiveStart,
+    DirectiveName,
+    DirectiveNameAfter,
+    DirectiveLabelAfter,
+    DirectiveAfter,
+    DirectiveLabelStart,
+    DirectiveLabelBefore,
+    DirectiveLabelAtBreak,
+    DirectiveLabelEolAfter,
+    DirectiveLabelNok,
+    DirectiveLabelInside,
+    DirectiveLabelEscape,
+    DirectiveAttributesStart,
+    DirectiveAttributesBetween,
+    DirectiveAttributesShortcutStart,
+    DirectiveAttributesShortcut,
+    DirectiveAttributesName,
+    DirectiveAttributesNameAfter,
+    DirectiveAttributesValueBefore,
+    DirectiveAttributesValueUnquoted,
+    DirectiveAttributesValueQuotedStart,
+    DirectiveAttributesValueQuoted,
+    DirectiveAttributesValueQuotedAfter,
+    DirectiveAttributesNok,
+
+    DirectiveContainerStart,
+    DirectiveContainerBefore,
+    DirectiveContainerSequence,
+    DirectiveContainerAfter,
+    DirectiveContainerEnd,
+    DirectiveContainerNok,
+    DirectiveContainerContStart,
+    DirectiveContainerContAfter,
+    DirectiveContainerCloseStart,
+    DirectiveContainerCloseBefore,
+    DirectiveContainerCloseSequence,
+    DirectiveContainerCloseSequenceAfter,
+
+    DirectiveLeafStart,
+    DirectiveLeafBefore,
+    DirectiveLeafSequence,
+    DirectiveLeafAfter,
+    DirectiveLeafEnd,
+    DirectiveLeafNok,
+
+    DirectiveTextStart,
+    DirectiveTextAfter,
+    DirectiveTextNok,
+
     DestinationStart,
     DestinationEnclosedBefore,
     DestinationEnclosed,
@@ -122,25 +193,42 @@ pub enum Name {
     DocumentContainerNewBeforeNotBlockQuote,
     DocumentContainerNewBeforeNotList,
     DocumentContainerNewBeforeNotGfmFootnoteDefinition,
+    DocumentContainerNewBeforeNotDirectiveContainer,
+    DocumentContainerNewBeforeNotDefinitionListDescription,
     DocumentContainerNewAfter,
     DocumentContainersAfter,
     DocumentFlowInside,
     DocumentFlowEnd,
 
     FlowStart,
+    FlowBeforeBuiltIn,
     FlowBeforeGfmTable,
     FlowBeforeCodeIndented,
     FlowBeforeRaw,
     FlowBeforeHtml,
     FlowBeforeMdxExpression,
+    FlowBeforeDirectiveLeaf,
     FlowBeforeMdxJsx,
     FlowBeforeHeadingAtx,
     FlowBeforeHeadingSetext,
     FlowBeforeThematicBreak,
+    FlowBeforeAbbreviationDefinition,
+    FlowBeforeAbbreviationDefinitionNotGfmTable,
     FlowAfter,
     FlowBlankLineBefore,
     FlowBlankLineAfter,
     FlowBeforeContent,
+    FlowBeforeFlowExtension,
+    FlowBeforeFlowExtensionNotGfmTable,
+
+    FlowExtensionStartFirst,
+    FlowExtensionStartLast,
+    FlowExtensionLineStart,
+    FlowExtensionChunk,
+    FlowExtensionLineAfter,
+    FlowExtensionAtNonLazyBreak,
+    FlowExtensionContinuationStart,
+    FlowExtensionAfter,
 
     FrontmatterStart,
     FrontmatterOpenSequence,
@@ -226,6 +314,12 @@ pub enum Name {
     HeadingAtxAtBreak,
     HeadingAtxSequenceFurther,
     HeadingAtxData,
+    HeadingAtxDataStart,
+    HeadingAtxAttributesStart,
+    HeadingAtxAttributesAfter,
+    HeadingAtxAttributesEnd,
+    HeadingAtxAttributesSequence,
+    HeadingAtxAttributesNok,
 
     HeadingSetextStart,
     HeadingSetextBefore,
@@ -407,6 +501,13 @@ pub enum Name {
     ParagraphStart,
     ParagraphLineStart,
     ParagraphInside,
+    ParagraphData,
+    ParagraphAttributesStart,
+    ParagraphAttributesBefore,
+    ParagraphAttributesAfter,
+    ParagraphAttributesEnd,
+    ParagraphAttributesNok,
+    ParagraphAfter,
 
     RawFlowStart,
     RawFlowBeforeSequenceOpen,
@@ -448,12 +549,16 @@ pub enum Name {
 
     TextStart,
     TextBefore,
+    TextBeforeBuiltIn,
     TextBeforeHtml,
     TextBeforeMdxJsx,
     TextBeforeHardBreakEscape,
     TextBeforeLabelStartLink,
     TextBeforeData,
 
+    TextExtensionStart,
+    TextExtensionInside,
+
     ThematicBreakStart,
     ThematicBreakBefore,
     ThematicBreakSequence,
@@ -472,6 +577,21 @@ pub enum Name {
 /// Call the corresponding state for a state name.
 pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
     let func = match name {
+        Name::AbbreviationDefinitionStart => construct::abbreviation_definition::start,
+        Name::AbbreviationDefinitionBefore => construct::abbreviation_definition::before,
+        Name::AbbreviationDefinitionLabelBefore => construct::abbreviation_definition::label_before,
+        Name::AbbreviationDefinitionLabelStart => construct::abbreviation_definition::label_start,
+        Name::AbbreviationDefinitionLabelInside => construct::abbreviation_definition::label_inside,
+        Name::AbbreviationDefinitionLabelAfter => construct::abbreviation_definition::label_after,
+        Name::AbbreviationDefinitionMarkerAfter => construct::abbreviation_definition::marker_after,
+        Name::AbbreviationDefinitionTitleBefore => construct::abbreviation_definition::title_before,
+        Name::AbbreviationDefinitionTitleInside => construct::abbreviation_definition::title_inside,
+        Name::AbbreviationDefinitionTitleWhitespace => {
+            construct::abbreviation_definition::title_whitespace
+        }
+        Name::AbbreviationDefinitionTitleEnd => construct::abbreviation_definition::title_end,
+        Name::AbbreviationDefinitionAfter => construct::abbreviation_definition::after,
+
         Name::AttentionStart => construct::attention::start,
         Name::AttentionInside => construct::attention::inside,
 
@@ -538,6 +658,78 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
             construct::definition::title_after_optional_whitespace
         }
 
+        Name::DefinitionListStart => construct::definition_list::start,
+        Name::DefinitionListBefore => construct::definition_list::before,
+        Name::DefinitionListMarkerAfter => construct::definition_list::marker_after,
+        Name::DefinitionListMarkerAfterFilled => construct::definition_list::marker_after_filled,
+        Name::DefinitionListWhitespace => construct::definition_list::whitespace,
+        Name::DefinitionListWhitespaceAfter => construct::definition_list::whitespace_after,
+        Name::DefinitionListPrefixOther => construct::definition_list::prefix_other,
+        Name::DefinitionListAfter => construct::definition_list::after,
+
+        Name::DirectiveStart => construct::partial_directive::start,
+        Name::DirectiveName => construct::partial_directive::name,
+        Name::DirectiveNameAfter => construct::partial_directive::name_after,
+        Name::DirectiveLabelAfter => construct::partial_directive::label_after,
+        Name::DirectiveAfter => construct::partial_directive::after,
+        Name::DirectiveLabelStart => construct::partial_directive::label_start,
+        Name::DirectiveLabelBefore => construct::partial_directive::label_before,
+        Name::DirectiveLabelAtBreak => construct::partial_directive::label_at_break,
+        Name::DirectiveLabelEolAfter => construct::partial_directive::label_eol_after,
+        Name::DirectiveLabelNok => construct::partial_directive::label_nok,
+        Name::DirectiveLabelInside => construct::partial_directive::label_inside,
+        Name::DirectiveLabelEscape => construct::partial_directive::label_escape,
+        Name::DirectiveAttributesStart => construct::partial_directive::attributes_start,
+        Name::DirectiveAttributesBetween => construct::partial_directive::attributes_between,
+        Name::DirectiveAttributesShortcutStart => {
+            construct::partial_directive::attributes_shortcut_start
+        }
+        Name::DirectiveAttributesShortcut => construct::partial_directive::attributes_shortcut,
+        Name::DirectiveAttributesName => construct::partial_directive::attributes_name,
+        Name::DirectiveAttributesNameAfter => construct::partial_directive::attributes_name_after,
+        Name::DirectiveAttributesValueBefore => {
+            construct::partial_directive::attributes_value_before
+        }
+        Name::DirectiveAttributesValueUnquoted => {
+            construct::partial_directive::attributes_value_unquoted
+        }
+        Name::DirectiveAttributesValueQuotedStart => {
+            construct::partial_directive::attributes_value_quoted_start
+        }
+        Name::DirectiveAttributesValueQuoted => {
+            construct::partial_directive::attributes_value_quoted
+        }
+        Name::DirectiveAttributesValueQuotedAfter => {
+            construct::partial_directive::attributes_value_quoted_after
+        }
+        Name::DirectiveAttributesNok => construct::partial_directive::attributes_nok,
+
+        Name::DirectiveContainerStart => construct::directive_container::start,
+        Name::DirectiveContainerBefore => construct::directive_container::before,
+        Name::DirectiveContainerSequence => construct::directive_container::sequence,
+        Name::DirectiveContainerAfter => construct::directive_container::after,
+        Name::DirectiveContainerEnd => construct::directive_container::end,
+        Name::DirectiveContainerNok => construct::directive_container::nok,
+        Name::DirectiveContainerContStart => construct::directive_container::cont_start,
+        Name::DirectiveContainerContAfter => construct::directive_container::cont_after,
+        Name::DirectiveContainerCloseStart => construct::directive_container::close_start,
+        Name::DirectiveContainerCloseBefore => construct::directive_container::close_before,
+        Name::DirectiveContainerCloseSequence => construct::directive_container::close_sequence,
+        Name::DirectiveContainerCloseSequenceAfter => {
+            construct::directive_container::close_sequence_after
+        }
+
+        Name::DirectiveLeafStart => construct::directive_leaf::start,
+        Name::DirectiveLeafBefore => construct::directive_leaf::before,
+        Name::DirectiveLeafSequence => construct::directive_leaf::sequence,
+        Name::DirectiveLeafAfter => construct::directive_leaf::after,
+        Name::DirectiveLeafEnd => construct::directive_leaf::end,
+        Name::DirectiveLeafNok => construct::directive_leaf::nok,
+
+        Name::DirectiveTextStart => construct::directive_text::start,
+        Name::DirectiveTextAfter => construct::directive_text::after,
+        Name::DirectiveTextNok => construct::directive_text::nok,
+
         Name::DestinationStart => construct::partial_destination::start,
         Name::DestinationEnclosedBefore => construct::partial_destination::enclosed_before,
         Name::DestinationEnclosed => construct::partial_destination::enclosed,
@@ -559,25 +751,50 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::DocumentContainerNewBeforeNotGfmFootnoteDefinition => {
             construct::document::container_new_before_not_footnote_definition
         }
+        Name::DocumentContainerNewBeforeNotDirectiveContainer => {
+            construct::document::container_new_before_not_directive_container
+        }
+        Name::DocumentContainerNewBeforeNotDefinitionListDescription => {
+            construct::document::container_new_before_not_definition_list_description
+        }
         Name::DocumentContainerNewAfter => construct::document::container_new_after,
         Name::DocumentContainersAfter => construct::document::containers_after,
         Name::DocumentFlowEnd => construct::document::flow_end,
         Name::DocumentFlowInside => construct::document::flow_inside,
 
         Name::FlowStart => construct::flow::start,
+        Name::FlowBeforeBuiltIn => construct::flow::before_built_in,
         Name::FlowBeforeGfmTable => construct::flow::before_gfm_table,
         Name::FlowBeforeCodeIndented => construct::flow::before_code_indented,
         Name::FlowBeforeRaw => construct::flow::before_raw,
         Name::FlowBeforeHtml => construct::flow::before_html,
         Name::FlowBeforeMdxExpression => construct::flow::before_mdx_expression,
+        Name::FlowBeforeDirectiveLeaf => construct::flow::before_directive_leaf,
         Name::FlowBeforeMdxJsx => construct::flow::before_mdx_jsx,
         Name::FlowBeforeHeadingAtx => construct::flow::before_heading_atx,
         Name::FlowBeforeHeadingSetext => construct::flow::before_heading_setext,
         Name::FlowBeforeThematicBreak => construct::flow::before_thematic_break,
+        Name::FlowBeforeAbbreviationDefinition => construct::flow::before_abbreviation_definition,
+        Name::FlowBeforeAbbreviationDefinitionNotGfmTable => {
+            construct::flow::before_abbreviation_definition_not_gfm_table
+        }
         Name::FlowAfter => construct::flow::after,
         Name::FlowBlankLineBefore => construct::flow::blank_line_before,
         Name::FlowBlankLineAfter => construct::flow::blank_line_after,
         Name::FlowBeforeContent => construct::flow::before_content,
+        Name::FlowBeforeFlowExtension => construct::flow::before_flow_extension,
+        Name::FlowBeforeFlowExtensionNotGfmTable => {
+            construct::flow::before_flow_extension_not_gfm_table
+        }
+
+        Name::FlowExtensionStartFirst => construct::flow_extension::start_first,
+        Name::FlowExtensionStartLast => construct::flow_extension::start_last,
+        Name::FlowExtensionLineStart => construct::flow_extension::line_start,
+        Name::FlowExtensionChunk => construct::flow_extension::chunk,
+        Name::FlowExtensionLineAfter => construct::flow_extension::line_after,
+        Name::FlowExtensionAtNonLazyBreak => construct::flow_extension::at_non_lazy_break,
+        Name::FlowExtensionContinuationStart => construct::flow_extension::continuation_start,
+        Name::FlowExtensionAfter => construct::flow_extension::after,
 
         Name::FrontmatterStart => construct::frontmatter::start,
         Name::FrontmatterOpenSequence => construct::frontmatter::open_sequence,
@@ -684,6 +901,12 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::HeadingAtxAtBreak => construct::heading_atx::at_break,
         Name::HeadingAtxSequenceFurther => construct::heading_atx::sequence_further,
         Name::HeadingAtxData => construct::heading_atx::data,
+        Name::HeadingAtxDataStart => construct::heading_atx::data_start,
+        Name::HeadingAtxAttributesStart => construct::heading_atx::attributes_start,
+        Name::HeadingAtxAttributesAfter => construct::heading_atx::attributes_after,
+        Name::HeadingAtxAttributesEnd => construct::heading_atx::attributes_end,
+        Name::HeadingAtxAttributesSequence => construct::heading_atx::attributes_sequence,
+        Name::HeadingAtxAttributesNok => construct::heading_atx::attributes_nok,
 
         Name::HeadingSetextStart => construct::heading_setext::start,
         Name::HeadingSetextBefore => construct::heading_setext::before,
@@ -906,6 +1129,13 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
         Name::ParagraphStart => construct::paragraph::start,
         Name::ParagraphLineStart => construct::paragraph::line_start,
         Name::ParagraphInside => construct::paragraph::inside,
+        Name::ParagraphData => construct::paragraph::data,
+        Name::ParagraphAttributesStart => construct::paragraph::attributes_start,
+        Name::ParagraphAttributesBefore => construct::paragraph::attributes_before,
+        Name::ParagraphAttributesAfter => construct::paragraph::attributes_after,
+        Name::ParagraphAttributesEnd => construct::paragraph::attributes_end,
+        Name::ParagraphAttributesNok => construct::paragraph::attributes_nok,
+        Name::ParagraphAfter => construct::paragraph::after,
 
         Name::RawFlowStart => construct::raw_flow::start,
         Name::RawFlowBeforeSequenceOpen => construct::raw_flow::before_sequence_open,
@@ -947,12 +1177,16 @@ pub fn call(tokenizer: &mut Tokenizer, name: Name) -> State {
 
         Name::TextStart => construct::text::start,
         Name::TextBefore => construct::text::before,
+        Name::TextBeforeBuiltIn => construct::text::before_built_in,
         Name::TextBeforeHtml => construct::text::before_html,
         Name::TextBeforeMdxJsx => construct::text::before_mdx_jsx,
         Name::TextBeforeHardBreakEscape => construct::text::before_hard_break_escape,
         Name::TextBeforeLabelStartLink => construct::text::before_label_start_link,
         Name::TextBeforeData => construct::text::before_data,
 
+        Name::TextExtensionStart => construct::text_extension::start,
+        Name::TextExtensionInside => construct::text_extension::inside,
+
         Name::ThematicBreakStart => construct::thematic_break::start,
         Name::ThematicBreakBefore => construct::thematic_break::before,
         Name::ThematicBreakSequence => construct::thematic_break::sequence,
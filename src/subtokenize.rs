@@ -30,6 +30,8 @@ pub struct Subresult {
     pub done: bool,
     pub gfm_footnote_definitions: Vec<String>,
     pub definitions: Vec<String>,
+    pub abbreviations: Vec<String>,
+    pub messages: Vec<message::Message>,
 }
 
 /// Link two [`Event`][]s.
@@ -78,7 +80,7 @@ pub fn link_to(events: &mut [Event], previous: usize, next: usize) {
 pub fn subtokenize(
     events: &mut Vec<Event>,
     parse_state: &ParseState,
-    filter: &Option<Content>,
+    filter: Option<&Content>,
 ) -> Result<Subresult, message::Message> {
     let mut map = EditMap::new();
     let mut index = 0;
@@ -86,6 +88,8 @@ pub fn subtokenize(
         done: true,
         gfm_footnote_definitions: vec![],
         definitions: vec![],
+        abbreviations: vec![],
+        messages: vec![],
     };
     let mut acc = (0, 0);
 
@@ -97,9 +101,7 @@ pub fn subtokenize(
             debug_assert_eq!(event.kind, Kind::Enter);
 
             // No need to enter linked events again.
-            if link.previous.is_none()
-                && (filter.is_none() || &link.content == filter.as_ref().unwrap())
-            {
+            if link.previous.is_none() && (filter.is_none() || Some(&link.content) == filter) {
                 // Index into `events` pointing to a chunk.
                 let mut link_index = Some(index);
                 // Subtokenizer.
@@ -169,6 +171,8 @@ pub fn subtokenize(
                     .gfm_footnote_definitions
                     .append(&mut result.gfm_footnote_definitions);
                 value.definitions.append(&mut result.definitions);
+                value.abbreviations.append(&mut result.abbreviations);
+                value.messages.append(&mut result.messages);
                 value.done = false;
 
                 acc = divide_events(&mut map, events, index, &mut tokenizer.events, acc);
@@ -218,14 +222,11 @@ pub fn divide_events(
                 let old_prev = old_prev.unwrap();
                 let prev_event = &mut child_events[old_prev];
                 // The `index` in `events` where the current link is,
-                // minus one to get the previous link,
                 // minus 2 events (the enter and exit) for each removed
                 // link.
-                let new_link = if slices.is_empty() {
-                    old_prev + link_index + 2
-                } else {
-                    old_prev + link_index - (slices.len() - 1) * 2
-                };
+                // There can be other events between the previous link and
+                // this one (such as trailing whitespace).
+                let new_link = child_index + link_index - slices.len() * 2;
                 prev_event.link.as_mut().unwrap().next =
                     Some(new_link + acc_before.1 - acc_before.0);
             }
@@ -1,25 +1,46 @@
 //! Turn events into a string of HTML.
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
+use crate::message;
+use crate::parser::ParseState;
+use crate::unist;
 use crate::util::{
-    character_reference::decode as decode_character_reference,
-    constant::{SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC},
+    char,
+    character_reference::{
+        decode as decode_character_reference, parse as parse_character_reference,
+    },
+    code_highlight::{Highlight, HighlightContext},
+    code_language::CodeInfo,
+    constant::{
+        HTML_BLOCK_NAMES, HTML_CDATA_PREFIX, HTML_RAW_NAMES, SAFE_ATTRIBUTE_NAMES,
+        SAFE_DATA_IMAGE_TYPES, SAFE_PROTOCOL_HREF, SAFE_PROTOCOL_SRC,
+    },
+    directive::{add_attribute, Directive, Kind as DirectiveKind},
     encode::encode,
     gfm_tagfilter::gfm_tagfilter,
-    infer::{gfm_table_align, list_loose},
+    infer::{definition_list_loose, gfm_table_align, list_info},
+    invisibles::invisibles,
     normalize_identifier::normalize_identifier,
+    resolve_uri::{is_external, normalize_case, resolve},
     sanitize_uri::{sanitize, sanitize_with_protocols},
     skip,
     slice::{Position, Slice},
+    slug::{heading_text, Slugger},
+    smart_punctuation::smart_punctuation,
+    url_transform::UrlContext,
+};
+use crate::{
+    CompileOptions, FigureCaption, FootnoteStyle, ImageLoading, Invisibles, LineEnding,
+    ParseOptions,
 };
-use crate::{CompileOptions, LineEnding};
 use alloc::{
+    boxed::Box,
     format,
     string::{String, ToString},
     vec,
     vec::Vec,
 };
-use core::str;
+use core::{convert::Infallible, str};
 
 /// Link, image, or footnote call.
 /// Resource or reference.
@@ -80,6 +101,10 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Size of a tab stop.
+    tab_size: usize,
+    /// Parse configuration.
+    parse: &'a ParseOptions,
     /// Configuration.
     options: &'a CompileOptions,
     // Fields used by handlers to track the things they need to track to
@@ -88,34 +113,63 @@ struct CompileContext<'a> {
     heading_atx_rank: Option<usize>,
     /// Buffer of heading (setext) text.
     heading_setext_buffer: Option<String>,
+    /// Id of current heading.
+    heading_id: Option<String>,
+    /// Slugs of headings, to generate unique ids.
+    heading_slugger: Slugger,
     /// Whether raw (flow) (code (fenced), math (flow)) or code (indented) contains data.
     raw_flow_seen_data: Option<bool>,
     /// Number of raw (flow) fences.
     raw_flow_fences_count: Option<usize>,
+    /// Info (language and meta) of code (fenced).
+    raw_flow_fence_info: Option<(String, Option<String>)>,
+    /// Language and meta of code (fenced) that is highlighted.
+    raw_flow_highlight: Option<(Option<String>, Option<String>)>,
+    /// Whether the content of raw (flow) or code (indented) is buffered.
+    raw_flow_body_buffered: bool,
     /// Whether we are in code (text).
     raw_text_inside: bool,
     /// Whether we are in image text.
     image_alt_inside: bool,
+    /// Whether we are in a paragraph that is compiled as a figure.
+    figure_inside: bool,
+    /// Caption of the current figure.
+    figure_caption: Option<String>,
+    /// Whether we are in text that can contain soft breaks (paragraphs,
+    /// heading (setext) text).
+    text_inside: bool,
     /// Marker of character reference.
     character_reference_marker: Option<u8>,
-    /// Whether we are expecting the first list item marker.
-    list_expect_first_marker: Option<bool>,
     /// Stack of media (link, image).
     media_stack: Vec<Media>,
     /// Stack of containers.
     tight_stack: Vec<bool>,
+    /// Stack of the sizes of the opening tags of blocks that are dropped if
+    /// they are empty.
+    droppable_stack: Vec<usize>,
     /// List of definitions.
     definitions: Vec<Definition>,
+    /// List of abbreviation definitions, as labels and titles.
+    abbreviations: Vec<(String, Option<String>)>,
     /// List of definitions.
     gfm_footnote_definitions: Vec<(String, String)>,
     gfm_footnote_definition_calls: Vec<(String, usize)>,
     gfm_footnote_definition_stack: Vec<(usize, usize)>,
+    /// Places in the root buffer where to insert footnotes, as byte offsets
+    /// and indices into calls (for `FootnoteStyle::Inline`).
+    gfm_footnote_inline: Vec<(usize, usize)>,
+    /// Number of calls that have a place in `gfm_footnote_inline`.
+    gfm_footnote_inline_placed: usize,
     /// Whether we are in a GFM table head.
     gfm_table_in_head: bool,
     /// Current GFM table alignment.
     gfm_table_align: Option<Vec<AlignKind>>,
     /// Current GFM table column.
     gfm_table_column: usize,
+    /// Stack of directives.
+    directive_stack: Vec<Directive>,
+    /// Current directive attribute, as a key and a value.
+    directive_attribute: Option<(String, String)>,
     // Fields used to influance the current compilation.
     /// Ignore the next line ending.
     slurp_one_line_ending: bool,
@@ -127,6 +181,10 @@ struct CompileContext<'a> {
     // Intermediate results.
     /// Stack of buffers.
     buffers: Vec<String>,
+    /// Last byte of what was already written out of the root buffer.
+    written_last_byte: Option<u8>,
+    /// Warnings about the document, such as HTML that is escaped.
+    messages: Vec<message::Message>,
     /// Current event index.
     index: usize,
 }
@@ -135,34 +193,51 @@ impl<'a> CompileContext<'a> {
     /// Create a new compile context.
     fn new(
         events: &'a [Event],
-        bytes: &'a [u8],
+        parse_state: &ParseState<'a>,
         options: &'a CompileOptions,
         line_ending: LineEnding,
     ) -> CompileContext<'a> {
         CompileContext {
             events,
-            bytes,
+            bytes: parse_state.bytes,
+            tab_size: parse_state.tab_size,
+            parse: parse_state.options,
             heading_atx_rank: None,
             heading_setext_buffer: None,
+            heading_id: None,
+            heading_slugger: Slugger::default(),
             raw_flow_seen_data: None,
             raw_flow_fences_count: None,
+            raw_flow_fence_info: None,
+            raw_flow_highlight: None,
+            raw_flow_body_buffered: false,
             raw_text_inside: false,
             character_reference_marker: None,
-            list_expect_first_marker: None,
             media_stack: vec![],
             definitions: vec![],
+            abbreviations: vec![],
             gfm_footnote_definitions: vec![],
             gfm_footnote_definition_calls: vec![],
             gfm_footnote_definition_stack: vec![],
+            gfm_footnote_inline: vec![],
+            gfm_footnote_inline_placed: 0,
             gfm_table_in_head: false,
             gfm_table_align: None,
             gfm_table_column: 0,
+            directive_stack: vec![],
+            directive_attribute: None,
             tight_stack: vec![],
+            droppable_stack: vec![],
             slurp_one_line_ending: false,
             image_alt_inside: false,
+            figure_inside: false,
+            figure_caption: None,
+            text_inside: false,
             encode_html: true,
             line_ending_default: line_ending,
             buffers: vec![String::new()],
+            written_last_byte: None,
+            messages: vec![],
             index: 0,
             options,
         }
@@ -195,7 +270,11 @@ impl<'a> CompileContext<'a> {
     fn line_ending_if_needed(&mut self) {
         let last_buf_opt = self.buffers.last();
         let last_buf = last_buf_opt.expect("at least one buffer should exist");
-        let last_byte = last_buf.as_bytes().last();
+        let last_byte = if self.buffers.len() == 1 && last_buf.is_empty() {
+            self.written_last_byte
+        } else {
+            last_buf.as_bytes().last().copied()
+        };
 
         if !matches!(last_byte, None | Some(b'\n' | b'\r')) {
             self.line_ending();
@@ -203,8 +282,39 @@ impl<'a> CompileContext<'a> {
     }
 }
 
-/// Turn events and bytes into a string of HTML.
-pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> String {
+/// Turn events and bytes into a string of HTML, and warnings about the
+/// document.
+pub fn compile(
+    events: &[Event],
+    parse_state: &ParseState,
+    options: &CompileOptions,
+) -> (String, Vec<message::Message>) {
+    let mut value = String::new();
+    let result: Result<Vec<message::Message>, Infallible> =
+        compile_to(events, parse_state, options, &mut |chunk| {
+            value.push_str(chunk);
+            Ok(())
+        });
+
+    match result {
+        Ok(messages) => (value, messages),
+        Err(never) => match never {},
+    }
+}
+
+/// Turn events and bytes into HTML, passing it to `write` in chunks.
+///
+/// Whatever is done is written as soon as possible: only things that need
+/// info from later in the document (such as the GFM footnote section, or
+/// the content of a heading or link while it is compiled) are buffered.
+///
+/// Returns warnings about the document.
+pub fn compile_to<E>(
+    events: &[Event],
+    parse_state: &ParseState,
+    options: &CompileOptions,
+    write: &mut impl FnMut(&str) -> Result<(), E>,
+) -> Result<Vec<message::Message>, E> {
     let mut index = 0;
     let mut line_ending_inferred = None;
 
@@ -216,7 +326,11 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         if event.kind == Kind::Exit
             && (event.name == Name::BlankLineEnding || event.name == Name::LineEnding)
         {
-            let slice = Slice::from_position(bytes, &Position::from_exit_event(events, index));
+            let slice = Slice::from_position(
+                parse_state.bytes,
+                &Position::from_exit_event(events, index),
+                parse_state.tab_size,
+            );
             line_ending_inferred = Some(slice.as_str().parse().unwrap());
             break;
         }
@@ -225,15 +339,18 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
     }
 
     // Figure out which line ending style we’ll use.
-    let line_ending_default =
-        line_ending_inferred.unwrap_or_else(|| options.default_line_ending.clone());
+    let line_ending_default = options
+        .line_ending
+        .clone()
+        .or(line_ending_inferred)
+        .unwrap_or_else(|| options.default_line_ending.clone());
 
-    let mut context = CompileContext::new(events, bytes, options, line_ending_default);
+    let mut context = CompileContext::new(events, parse_state, options, line_ending_default);
     let mut definition_indices = vec![];
     let mut index = 0;
     let mut definition_inside = false;
 
-    // Handle all definitions first.
+    // Handle all definitions (and abbreviation definitions) first.
     // We must do two passes because we need to compile the events in
     // definitions which come after references already.
     //
@@ -252,12 +369,12 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         }
 
         if event.kind == Kind::Enter {
-            if event.name == Name::Definition {
+            if event.name == Name::Definition || event.name == Name::AbbreviationDefinition {
                 handle(&mut context, index); // Also handle start.
                 definition_inside = true;
                 definition_indices.push((index, index));
             }
-        } else if event.name == Name::Definition {
+        } else if event.name == Name::Definition || event.name == Name::AbbreviationDefinition {
             definition_inside = false;
             definition_indices.last_mut().unwrap().1 = index;
         }
@@ -265,6 +382,12 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         index += 1;
     }
 
+    if let Some(ref value) = context.options.wrap {
+        context.push("<");
+        context.push(&encode(value, context.encode_html));
+        context.push(">");
+    }
+
     let mut index = 0;
     let jump_default = (events.len(), events.len());
     let mut definition_index = 0;
@@ -282,20 +405,47 @@ pub fn compile(events: &[Event], bytes: &[u8], options: &CompileOptions) -> Stri
         } else {
             handle(&mut context, index);
             index += 1;
+
+            if context.options.gfm_footnote_style == FootnoteStyle::Inline {
+                place_inline_footnotes(&mut context, index - 1);
+            }
+
+            // Write out what is done.
+            // Footnotes are inserted at the end, so nothing after where they
+            // go can be written.
+            if context.buffers.len() == 1
+                && !context.buffers[0].is_empty()
+                && context.gfm_footnote_inline.is_empty()
+            {
+                write(&context.buffers[0])?;
+                context.written_last_byte = context.buffers[0].as_bytes().last().copied();
+                context.buffers[0].clear();
+            }
         }
     }
 
+    if context.options.gfm_footnote_style == FootnoteStyle::Inline {
+        generate_inline_footnotes(&mut context);
+    }
     // No section to generate.
-    if !context.gfm_footnote_definition_calls.is_empty() {
+    else if !context.gfm_footnote_definition_calls.is_empty() {
         generate_footnote_section(&mut context);
     }
 
+    if let Some(ref value) = context.options.wrap {
+        context.line_ending_if_needed();
+        context.push("</");
+        context.push(&encode(value, context.encode_html));
+        context.push(">");
+    }
+
+    if context.options.normalize_block_whitespace {
+        context.line_ending_if_needed();
+    }
+
     debug_assert_eq!(context.buffers.len(), 1, "expected 1 final buffer");
-    context
-        .buffers
-        .first()
-        .expect("expected 1 final buffer")
-        .into()
+    write(context.buffers.first().expect("expected 1 final buffer"))?;
+    Ok(context.messages)
 }
 
 /// Handle the event at `index`.
@@ -312,30 +462,41 @@ fn handle(context: &mut CompileContext, index: usize) {
 /// Handle [`Enter`][Kind::Enter].
 fn enter(context: &mut CompileContext) {
     match context.events[context.index].name {
-        Name::CodeFencedFenceInfo
-        | Name::CodeFencedFenceMeta
+        Name::Abbreviation
+        | Name::Attributes
         | Name::MathFlowFenceMeta
         | Name::DefinitionLabelString
-        | Name::DefinitionTitleString
+        | Name::DirectiveAttributes
+        | Name::DirectiveLabel
         | Name::GfmFootnoteDefinitionPrefix
         | Name::HeadingAtxText
-        | Name::HeadingSetextText
         | Name::Label
         | Name::MdxEsm
         | Name::MdxFlowExpression
         | Name::MdxTextExpression
         | Name::MdxJsxFlowTag
         | Name::MdxJsxTextTag
-        | Name::ReferenceString
-        | Name::ResourceTitleString => on_enter_buffer(context),
+        | Name::ReferenceString => on_enter_buffer(context),
 
+        Name::AbbreviationDefinition => on_enter_abbreviation_definition(context),
+        Name::AbbreviationDefinitionTitle => on_enter_abbreviation_definition_title(context),
         Name::BlockQuote => on_enter_block_quote(context),
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeFenced | Name::MathFlow => on_enter_raw_flow(context),
+        Name::CodeFencedFenceInfo | Name::CodeFencedFenceMeta => {
+            on_enter_raw_flow_fence_info_or_meta(context);
+        }
         Name::CodeText | Name::MathText => on_enter_raw_text(context),
         Name::Definition => on_enter_definition(context),
         Name::DefinitionDestinationString => on_enter_definition_destination_string(context),
+        Name::DefinitionList => on_enter_definition_list(context),
+        Name::DefinitionListDescription => on_enter_definition_list_description(context),
+        Name::DefinitionListTerm => on_enter_definition_list_term(context),
+        Name::DirectiveContainer | Name::DirectiveLeaf | Name::DirectiveText => {
+            on_enter_directive(context);
+        }
         Name::Emphasis => on_enter_emphasis(context),
+        Name::FlowExtension(_) => on_enter_flow_extension(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmFootnoteDefinition => on_enter_gfm_footnote_definition(context),
         Name::GfmFootnoteCall => on_enter_gfm_footnote_call(context),
@@ -346,6 +507,8 @@ fn enter(context: &mut CompileContext) {
         Name::GfmTableHead => on_enter_gfm_table_head(context),
         Name::GfmTableRow => on_enter_gfm_table_row(context),
         Name::GfmTaskListItemCheck => on_enter_gfm_task_list_item_check(context),
+        Name::HeadingSetextText => on_enter_heading_setext_text(context),
+        Name::Highlight => on_enter_highlight(context),
         Name::HtmlFlow => on_enter_html_flow(context),
         Name::HtmlText => on_enter_html_text(context),
         Name::Image => on_enter_image(context),
@@ -355,7 +518,12 @@ fn enter(context: &mut CompileContext) {
         Name::Paragraph => on_enter_paragraph(context),
         Name::Resource => on_enter_resource(context),
         Name::ResourceDestinationString => on_enter_resource_destination_string(context),
+        Name::DefinitionTitleString | Name::ResourceTitleString => {
+            on_enter_title_string(context);
+        }
         Name::Strong => on_enter_strong(context),
+        Name::Subscript => on_enter_subscript(context),
+        Name::Superscript => on_enter_superscript(context),
         _ => {}
     }
 }
@@ -363,7 +531,7 @@ fn enter(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit].
 fn exit(context: &mut CompileContext) {
     match context.events[context.index].name {
-        Name::CodeFencedFenceMeta
+        Name::Attributes
         | Name::MathFlowFenceMeta
         | Name::MdxJsxTextTag
         | Name::MdxTextExpression
@@ -374,10 +542,17 @@ fn exit(context: &mut CompileContext) {
         Name::CharacterEscapeValue | Name::CodeTextData | Name::Data | Name::MathTextData => {
             on_exit_data(context);
         }
+        Name::Abbreviation => on_exit_abbreviation(context),
+        Name::AbbreviationDefinition => on_exit_abbreviation_definition(context),
+        Name::AbbreviationDefinitionLabelString => {
+            on_exit_abbreviation_definition_label_string(context);
+        }
+        Name::AbbreviationDefinitionTitle => on_exit_abbreviation_definition_title(context),
         Name::AutolinkEmail => on_exit_autolink_email(context),
         Name::AutolinkProtocol => on_exit_autolink_protocol(context),
         Name::BlankLineEnding => on_exit_blank_line_ending(context),
         Name::BlockQuote => on_exit_block_quote(context),
+        Name::CharacterReference => on_exit_character_reference(context),
         Name::CharacterReferenceMarker => on_exit_character_reference_marker(context),
         Name::CharacterReferenceMarkerNumeric => {
             on_exit_character_reference_marker_numeric(context);
@@ -389,13 +564,30 @@ fn exit(context: &mut CompileContext) {
         Name::CodeFenced | Name::CodeIndented | Name::MathFlow => on_exit_raw_flow(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
         Name::CodeFencedFenceInfo => on_exit_raw_flow_fence_info(context),
+        Name::CodeFencedFenceMeta => on_exit_raw_flow_fence_meta(context),
         Name::CodeFlowChunk | Name::MathFlowChunk => on_exit_raw_flow_chunk(context),
         Name::CodeText | Name::MathText => on_exit_raw_text(context),
         Name::Definition => on_exit_definition(context),
         Name::DefinitionDestinationString => on_exit_definition_destination_string(context),
         Name::DefinitionLabelString => on_exit_definition_label_string(context),
+        Name::DefinitionList => on_exit_definition_list(context),
+        Name::DefinitionListDescription => on_exit_definition_list_description(context),
+        Name::DefinitionListTerm => on_exit_definition_list_term(context),
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::DirectiveAttribute => on_exit_directive_attribute(context),
+        Name::DirectiveAttributeClassMarker
+        | Name::DirectiveAttributeIdMarker
+        | Name::DirectiveAttributeName => on_exit_directive_attribute_name(context),
+        Name::DirectiveAttributeValue => on_exit_directive_attribute_value(context),
+        Name::DirectiveAttributes => on_exit_directive_attributes(context),
+        Name::DirectiveContainer | Name::DirectiveLeaf | Name::DirectiveText => {
+            on_exit_directive(context);
+        }
+        Name::DirectiveLabel => on_exit_directive_label(context),
+        Name::DirectiveName => on_exit_directive_name(context),
         Name::Emphasis => on_exit_emphasis(context),
+        Name::Emoji(_) => on_exit_emoji(context),
+        Name::FlowExtension(_) => on_exit_flow_extension(context),
         Name::Frontmatter => on_exit_frontmatter(context),
         Name::GfmAutolinkLiteralEmail => on_exit_gfm_autolink_literal_email(context),
         Name::GfmAutolinkLiteralMailto => on_exit_gfm_autolink_literal_mailto(context),
@@ -422,6 +614,7 @@ fn exit(context: &mut CompileContext) {
         Name::HeadingAtxText => on_exit_heading_atx_text(context),
         Name::HeadingSetextText => on_exit_heading_setext_text(context),
         Name::HeadingSetextUnderlineSequence => on_exit_heading_setext_underline_sequence(context),
+        Name::Highlight => on_exit_highlight(context),
         Name::HtmlFlow | Name::HtmlText => on_exit_html(context),
         Name::HtmlFlowData | Name::HtmlTextData => on_exit_html_data(context),
         Name::Image | Name::Link => on_exit_media(context),
@@ -430,12 +623,14 @@ fn exit(context: &mut CompileContext) {
         Name::LineEnding => on_exit_line_ending(context),
         Name::ListOrdered | Name::ListUnordered => on_exit_list(context),
         Name::ListItem => on_exit_list_item(context),
-        Name::ListItemValue => on_exit_list_item_value(context),
         Name::Paragraph => on_exit_paragraph(context),
         Name::ReferenceString => on_exit_reference_string(context),
         Name::ResourceDestinationString => on_exit_resource_destination_string(context),
         Name::ResourceTitleString => on_exit_resource_title_string(context),
         Name::Strong => on_exit_strong(context),
+        Name::Subscript => on_exit_subscript(context),
+        Name::Superscript => on_exit_superscript(context),
+        Name::TextExtension(_) => on_exit_text_extension(context),
         Name::ThematicBreak => on_exit_thematic_break(context),
         _ => {}
     }
@@ -448,30 +643,68 @@ fn on_enter_buffer(context: &mut CompileContext) {
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`AbbreviationDefinition`][Name::AbbreviationDefinition].
+fn on_enter_abbreviation_definition(context: &mut CompileContext) {
+    context.buffer();
+    context.abbreviations.push((String::new(), None));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle].
+fn on_enter_abbreviation_definition_title(context: &mut CompileContext) {
+    context.buffer();
+    context.encode_html = false;
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`BlockQuote`][Name::BlockQuote].
 fn on_enter_block_quote(context: &mut CompileContext) {
     context.tight_stack.push(false);
     context.line_ending_if_needed();
-    context.push("<blockquote>");
+    context.push("<blockquote");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`CodeIndented`][Name::CodeIndented].
 fn on_enter_code_indented(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
-    context.push("<pre><code>");
+    context.push("<pre");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push("><code>");
+
+    // Buffer the code, to trim it.
+    if context.options.code_trim_final_line_ending {
+        context.buffer();
+        context.raw_flow_body_buffered = true;
+    }
 }
 
 /// Handle [`Enter`][Kind::Enter]:{[`CodeFenced`][Name::CodeFenced],[`MathFlow`][Name::MathFlow]}.
 fn on_enter_raw_flow(context: &mut CompileContext) {
     context.raw_flow_seen_data = Some(false);
     context.line_ending_if_needed();
+
+    // Buffer the whole, as a highlighter can replace it.
+    if context.events[context.index].name == Name::CodeFenced
+        && context.options.code_highlight.is_some()
+    {
+        context.raw_flow_highlight = Some((None, None));
+        context.buffer();
+    }
+
+    context.push("<pre");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
     // Note that no `>` is used, which is added later (due to info)
-    context.push("<pre><code");
+    context.push("><");
     context.raw_flow_fences_count = Some(0);
 
     if context.events[context.index].name == Name::MathFlow {
-        context.push(" class=\"language-math math-display\"");
+        push_math_start(context, "math-display");
+    } else {
+        context.push("code");
     }
 }
 
@@ -479,15 +712,25 @@ fn on_enter_raw_flow(context: &mut CompileContext) {
 fn on_enter_raw_text(context: &mut CompileContext) {
     context.raw_text_inside = true;
     if !context.image_alt_inside {
-        context.push("<code");
+        context.push("<");
         if context.events[context.index].name == Name::MathText {
-            context.push(" class=\"language-math math-inline\"");
+            push_math_start(context, "math-inline");
+        } else {
+            context.push("code");
         }
         context.push(">");
     }
     context.buffer();
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`CodeFencedFenceInfo`][Name::CodeFencedFenceInfo],[`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta]}.
+fn on_enter_raw_flow_fence_info_or_meta(context: &mut CompileContext) {
+    context.buffer();
+    // Ignore encoding the result, as it’s passed to `code_language_transform`
+    // and encoded manually after.
+    context.encode_html = false;
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Definition`][Name::Definition].
 fn on_enter_definition(context: &mut CompileContext) {
     context.buffer();
@@ -507,6 +750,62 @@ fn on_enter_definition_destination_string(context: &mut CompileContext) {
     context.encode_html = false;
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionList`][Name::DefinitionList].
+fn on_enter_definition_list(context: &mut CompileContext) {
+    let loose = definition_list_loose(context.events, context.index);
+    context.tight_stack.push(!loose);
+    context.line_ending_if_needed();
+    context.push("<dl");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionListDescription`][Name::DefinitionListDescription].
+fn on_enter_definition_list_description(context: &mut CompileContext) {
+    context.line_ending_if_needed();
+    context.push("<dd");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DefinitionListTerm`][Name::DefinitionListTerm].
+fn on_enter_definition_list_term(context: &mut CompileContext) {
+    context.text_inside = true;
+    context.line_ending_if_needed();
+    context.push("<dt");
+    push_source_position(context, context.index);
+    push_attributes(context, context.index, None);
+    context.push(">");
+}
+
+/// Handle [`Enter`][Kind::Enter]:{[`DirectiveContainer`][Name::DirectiveContainer],[`DirectiveLeaf`][Name::DirectiveLeaf],[`DirectiveText`][Name::DirectiveText]}.
+fn on_enter_directive(context: &mut CompileContext) {
+    let kind = match context.events[context.index].name {
+        Name::DirectiveContainer => DirectiveKind::Container,
+        Name::DirectiveLeaf => DirectiveKind::Leaf,
+        _ => DirectiveKind::Text,
+    };
+
+    if kind != DirectiveKind::Text {
+        context.line_ending_if_needed();
+    }
+
+    if kind == DirectiveKind::Container {
+        context.tight_stack.push(false);
+    }
+
+    context.directive_stack.push(Directive {
+        kind,
+        name: String::new(),
+        label: None,
+        attributes: vec![],
+        content: None,
+    });
+    context.buffer();
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -514,6 +813,11 @@ fn on_enter_emphasis(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`FlowExtension`][Name::FlowExtension].
+fn on_enter_flow_extension(context: &mut CompileContext) {
+    context.buffer();
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Frontmatter`][Name::Frontmatter].
 fn on_enter_frontmatter(context: &mut CompileContext) {
     context.buffer();
@@ -548,7 +852,10 @@ fn on_enter_gfm_table(context: &mut CompileContext) {
     let align = gfm_table_align(context.events, context.index);
     context.gfm_table_align = Some(align);
     context.line_ending_if_needed();
-    context.push("<table>");
+    context.push("<table");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableBody`][Name::GfmTableBody].
@@ -581,6 +888,8 @@ fn on_enter_gfm_table_cell(context: &mut CompileContext) {
             AlignKind::None => {}
         }
 
+        push_source_position(context, context.index);
+        push_class(context, context.index);
         context.push(">");
     }
 }
@@ -595,30 +904,46 @@ fn on_enter_gfm_table_head(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`GfmTableRow`][Name::GfmTableRow].
 fn on_enter_gfm_table_row(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<tr>");
+    context.push("<tr");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_enter_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<input type=\"checkbox\" ");
+        context.push("<input type=\"checkbox\"");
         if !context.options.gfm_task_list_item_checkable {
-            context.push("disabled=\"\" ");
+            context.push(" disabled=\"\"");
         }
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`HeadingSetextText`][Name::HeadingSetextText].
+fn on_enter_heading_setext_text(context: &mut CompileContext) {
+    context.text_inside = true;
+    context.buffer();
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Highlight`][Name::Highlight].
+fn on_enter_highlight(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<mark>");
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`HtmlFlow`][Name::HtmlFlow].
 fn on_enter_html_flow(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    if context.options.allow_dangerous_html {
+    if context.options.allow_dangerous_html && html_allowed(context) {
         context.encode_html = false;
     }
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`HtmlText`][Name::HtmlText].
 fn on_enter_html_text(context: &mut CompileContext) {
-    if context.options.allow_dangerous_html {
+    if context.options.allow_dangerous_html && html_allowed(context) {
         context.encode_html = false;
     }
 }
@@ -650,39 +975,76 @@ fn on_enter_link(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
 fn on_enter_list(context: &mut CompileContext) {
-    let loose = list_loose(context.events, context.index, true);
-    context.tight_stack.push(!loose);
-    context.line_ending_if_needed();
-
-    // Note: no `>`.
-    context.push(if context.events[context.index].name == Name::ListOrdered {
-        "<ol"
+    let info = list_info(context.bytes, context.events, context.index);
+    context
+        .tight_stack
+        .push(!context.options.list_loose.unwrap_or(info.loose));
+    enter_droppable(context, true);
+
+    if info.ordered {
+        context.push("<ol");
+
+        if let Some(start) = info.start {
+            if start != 1 {
+                context.push(" start=\"");
+                context.push(&start.to_string());
+                context.push("\"");
+            }
+        }
     } else {
-        "<ul"
-    });
-    context.list_expect_first_marker = Some(true);
+        context.push("<ul");
+    }
+
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    context.push(">");
+    opened_droppable(context);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`ListItemMarker`][Name::ListItemMarker].
 fn on_enter_list_item_marker(context: &mut CompileContext) {
-    if context.list_expect_first_marker.take().unwrap() {
-        context.push(">");
+    enter_droppable(context, true);
+    context.push("<li");
+    if context.options.source_positions || !context.options.class_map.is_empty() {
+        let mut index = context.index;
+        while !(context.events[index].kind == Kind::Enter
+            && context.events[index].name == Name::ListItem)
+        {
+            index -= 1;
+        }
+        push_source_position(context, index);
+        push_class(context, index);
     }
-
-    context.line_ending_if_needed();
-
-    context.push("<li>");
-    context.list_expect_first_marker = Some(false);
+    context.push(">");
+    opened_droppable(context);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
 fn on_enter_paragraph(context: &mut CompileContext) {
-    let tight = context.tight_stack.last().unwrap_or(&false);
-
-    if !tight {
-        context.line_ending_if_needed();
-        context.push("<p>");
+    let tight = *context.tight_stack.last().unwrap_or(&false);
+    context.text_inside = true;
+    context.figure_inside =
+        context.options.image_figure.is_some() && is_figure(context.events, context.index);
+    enter_droppable(context, !tight);
+
+    if context.figure_inside {
+        context.push("<figure");
+        push_source_position(context, context.index);
+        push_attributes(context, context.index, None);
+        context.push(">");
+    } else if !tight {
+        context.push("<");
+        context.push(&paragraph_tag_name(context));
+        push_source_position(context, context.index);
+        push_attributes(context, context.index, None);
+        if let Some(ref value) = context.options.paragraph_attributes {
+            context.push(" ");
+            context.push(value);
+        }
+        context.push(">");
     }
+
+    opened_droppable(context);
 }
 
 /// Handle [`Enter`][Kind::Enter]:[`Resource`][Name::Resource].
@@ -699,6 +1061,14 @@ fn on_enter_resource_destination_string(context: &mut CompileContext) {
     context.encode_html = false;
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`DefinitionTitleString`][Name::DefinitionTitleString],[`ResourceTitleString`][Name::ResourceTitleString]}.
+fn on_enter_title_string(context: &mut CompileContext) {
+    context.buffer();
+    // Ignore encoding the result, as it’s passed to `url_transform` and
+    // encoded manually after.
+    context.encode_html = false;
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Strong`][Name::Strong].
 fn on_enter_strong(context: &mut CompileContext) {
     if !context.image_alt_inside {
@@ -706,6 +1076,87 @@ fn on_enter_strong(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Subscript`][Name::Subscript].
+fn on_enter_subscript(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<sub>");
+    }
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Superscript`][Name::Superscript].
+fn on_enter_superscript(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("<sup>");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Abbreviation`][Name::Abbreviation].
+fn on_exit_abbreviation(context: &mut CompileContext) {
+    let value = context.resume();
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let label = slice.as_str();
+    let definition = context
+        .abbreviations
+        .iter()
+        .find(|d| d.0 == label)
+        .or_else(|| {
+            let label = label.to_lowercase();
+            context
+                .abbreviations
+                .iter()
+                .find(|d| d.0.to_lowercase() == label)
+        });
+
+    if let Some((_, Some(title))) = definition {
+        let title = encode(title, context.encode_html);
+        context.push("<abbr title=\"");
+        context.push(&title);
+        context.push("\">");
+    } else {
+        context.push("<abbr>");
+    }
+
+    context.push(&value);
+    context.push("</abbr>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinition`][Name::AbbreviationDefinition].
+fn on_exit_abbreviation_definition(context: &mut CompileContext) {
+    context.resume();
+    let (label, _) = context.abbreviations.last().unwrap();
+
+    // The first definition with a label wins.
+    if context.abbreviations[0..(context.abbreviations.len() - 1)]
+        .iter()
+        .any(|d| &d.0 == label)
+    {
+        context.abbreviations.pop();
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinitionLabelString`][Name::AbbreviationDefinitionLabelString].
+fn on_exit_abbreviation_definition_label_string(context: &mut CompileContext) {
+    let label = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    )
+    .as_str()
+    .to_string();
+    context.abbreviations.last_mut().unwrap().0 = label;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`AbbreviationDefinitionTitle`][Name::AbbreviationDefinitionTitle].
+fn on_exit_abbreviation_definition_title(context: &mut CompileContext) {
+    let buf = context.resume();
+    context.abbreviations.last_mut().unwrap().1 = Some(buf);
+    context.encode_html = true;
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`AutolinkEmail`][Name::AutolinkEmail].
 fn on_exit_autolink_email(context: &mut CompileContext) {
     generate_autolink(
@@ -714,6 +1165,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -728,6 +1180,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         false,
@@ -737,7 +1190,8 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:{[`HardBreakEscape`][Name::HardBreakEscape],[`HardBreakTrailing`][Name::HardBreakTrailing]}.
 fn on_exit_break(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("<br />");
+        context.push("<br");
+        push_void_end(context);
     }
 }
 
@@ -756,6 +1210,30 @@ fn on_exit_block_quote(context: &mut CompileContext) {
     context.push("</blockquote>");
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`CharacterReference`][Name::CharacterReference].
+fn on_exit_character_reference(context: &mut CompileContext) {
+    if preserve_character_reference(context) {
+        // Character references only contain `&`, `#`, `;`, and ASCII
+        // alphanumericals, so they do not need encoding.
+        context.push(
+            Slice::from_position(
+                context.bytes,
+                &Position::from_exit_event(context.events, context.index),
+                context.tab_size,
+            )
+            .as_str(),
+        );
+    }
+}
+
+/// Whether to keep the current character reference as it is written.
+///
+/// Buffered values that are encoded manually afterwards (such as titles) are
+/// always decoded.
+fn preserve_character_reference(context: &CompileContext) -> bool {
+    context.options.preserve_character_references && context.encode_html
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`CharacterReferenceMarker`][Name::CharacterReferenceMarker].
 fn on_exit_character_reference_marker(context: &mut CompileContext) {
     context.character_reference_marker = Some(b'&');
@@ -777,9 +1255,15 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
         .character_reference_marker
         .take()
         .expect("expected `character_reference_kind` to be set");
+
+    if preserve_character_reference(context) {
+        return;
+    }
+
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = decode_character_reference(slice.as_str(), marker, true)
         .expect("expected to parse only valid named references");
@@ -794,6 +1278,7 @@ fn on_exit_raw_flow_chunk(context: &mut CompileContext) {
         &Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         // Must serialize to get virtual spaces.
         .serialize(),
@@ -808,8 +1293,39 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
         .expect("expected `raw_flow_fences_count`");
 
     if count == 0 {
+        if let Some((language, meta)) = context.raw_flow_fence_info.take() {
+            if context.raw_flow_highlight.is_some() {
+                context.raw_flow_highlight = Some((Some(language.clone()), meta.clone()));
+            }
+
+            let language = if let Some(transform) = &context.options.code_language_transform {
+                transform(CodeInfo { language, meta })
+            } else {
+                Some(language)
+            };
+
+            if let Some(language) = language {
+                let prefix = context
+                    .options
+                    .code_language_prefix
+                    .as_deref()
+                    .unwrap_or("language-");
+                let value = encode(&format!("{}{}", prefix, language), true);
+                context.push(" class=\"");
+                context.push(&value);
+                context.push("\"");
+            }
+        }
+
         context.push(">");
         context.slurp_one_line_ending = true;
+
+        // Buffer the code, not encoded for the highlighter, or to trim it.
+        if context.raw_flow_highlight.is_some() || context.options.code_trim_final_line_ending {
+            context.buffer();
+            context.raw_flow_body_buffered = true;
+            context.encode_html = context.raw_flow_highlight.is_none();
+        }
     }
 
     context.raw_flow_fences_count = Some(count + 1);
@@ -820,9 +1336,17 @@ fn on_exit_raw_flow_fence(context: &mut CompileContext) {
 /// Note: math (flow) does not support `info`.
 fn on_exit_raw_flow_fence_info(context: &mut CompileContext) {
     let value = context.resume();
-    context.push(" class=\"language-");
-    context.push(&value);
-    context.push("\"");
+    context.raw_flow_fence_info = Some((value, None));
+    context.encode_html = true;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`CodeFencedFenceMeta`][Name::CodeFencedFenceMeta].
+fn on_exit_raw_flow_fence_meta(context: &mut CompileContext) {
+    let value = context.resume();
+    if let Some((_, meta)) = context.raw_flow_fence_info.as_mut() {
+        *meta = Some(value);
+    }
+    context.encode_html = true;
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeFenced`][Name::CodeFenced],[`CodeIndented`][Name::CodeIndented],[`MathFlow`][Name::MathFlow]}.
@@ -854,7 +1378,52 @@ fn on_exit_raw_flow(context: &mut CompileContext) {
         context.line_ending_if_needed();
     }
 
-    context.push("</code></pre>");
+    if context.raw_flow_body_buffered {
+        context.raw_flow_body_buffered = false;
+        let mut value = context.resume();
+
+        if context.options.code_trim_final_line_ending {
+            if value.ends_with('\n') {
+                value.pop();
+            }
+            if value.ends_with('\r') {
+                value.pop();
+            }
+        }
+
+        if let Some((language, meta)) = context.raw_flow_highlight.take() {
+            context.encode_html = true;
+            let shell = context.resume();
+            let highlighter = context
+                .options
+                .code_highlight
+                .as_ref()
+                .expect("expected `code_highlight`");
+
+            match highlighter(HighlightContext {
+                language,
+                meta,
+                value: value.clone(),
+            }) {
+                Some(Highlight::Body(html)) => {
+                    context.push(&shell);
+                    context.push(&html);
+                    context.push("</code></pre>");
+                }
+                Some(Highlight::Whole(html)) => context.push(&html),
+                None => {
+                    context.push(&shell);
+                    context.push(&encode(&value, true));
+                    context.push("</code></pre>");
+                }
+            }
+        } else {
+            context.push(&value);
+            push_raw_flow_end(context);
+        }
+    } else {
+        push_raw_flow_end(context);
+    }
 
     if let Some(count) = context.raw_flow_fences_count.take() {
         if count < 2 {
@@ -891,7 +1460,11 @@ fn on_exit_raw_text(context: &mut CompileContext) {
     let mut index = 0;
     let mut end = bytes.len();
 
-    if end > 2 && bytes[index] == b' ' && bytes[end - 1] == b' ' {
+    if context.parse.code_text_strip_space
+        && end > 2
+        && bytes[index] == b' '
+        && bytes[end - 1] == b' '
+    {
         index += 1;
         end -= 1;
         while index < end && !trim {
@@ -912,7 +1485,13 @@ fn on_exit_raw_text(context: &mut CompileContext) {
     context.push(str::from_utf8(&bytes).unwrap());
 
     if !context.image_alt_inside {
-        context.push("</code>");
+        context.push("</");
+        if context.events[context.index].name == Name::MathText {
+            push_math_tag_name(context);
+        } else {
+            context.push("code");
+        }
+        context.push(">");
     }
 }
 
@@ -933,14 +1512,36 @@ fn on_exit_drop_slurp(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:{[`CodeTextData`][Name::CodeTextData],[`Data`][Name::Data],[`CharacterEscapeValue`][Name::CharacterEscapeValue]}.
 fn on_exit_data(context: &mut CompileContext) {
-    context.push(&encode(
-        Slice::from_position(
-            context.bytes,
-            &Position::from_exit_event(context.events, context.index),
-        )
-        .as_str(),
-        context.encode_html,
-    ));
+    let position = Position::from_exit_event(context.events, context.index);
+    let slice = Slice::from_position(context.bytes, &position, context.tab_size);
+    let value = slice.as_str();
+
+    if context.options.smart_punctuation
+        && context.events[context.index].name == Name::Data
+        && !in_string(context)
+    {
+        let value = smart_punctuation(
+            value,
+            char::before_index(context.bytes, position.start.index),
+            char::after_index(context.bytes, position.end.index),
+        );
+        push_data(context, &value);
+    } else {
+        push_data(context, value);
+    }
+}
+
+/// Push encoded data, dealing with invisible characters in text.
+fn push_data(context: &mut CompileContext, value: &str) {
+    let value = encode(value, context.encode_html);
+
+    if context.options.invisibles != Invisibles::Keep
+        && context.events[context.index].name == Name::Data
+    {
+        context.push(&invisibles(&value, &context.options.invisibles));
+    } else {
+        context.push(&value);
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`Definition`][Name::Definition].
@@ -973,10 +1574,164 @@ fn on_exit_definition_label_string(context: &mut CompileContext) {
         Some(Position::from_exit_event(context.events, context.index).to_indices());
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionList`][Name::DefinitionList].
+fn on_exit_definition_list(context: &mut CompileContext) {
+    context.tight_stack.pop();
+    context.line_ending_if_needed();
+    context.push("</dl>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionListDescription`][Name::DefinitionListDescription].
+fn on_exit_definition_list_description(context: &mut CompileContext) {
+    let tight = context.tight_stack.last().unwrap_or(&false);
+    let before = skip::opt_back(
+        context.events,
+        context.index - 1,
+        &[
+            Name::BlankLineEnding,
+            Name::BlockQuotePrefix,
+            Name::LineEnding,
+            Name::SpaceOrTab,
+            // Also ignore things that don’t contribute to the document.
+            Name::AbbreviationDefinition,
+            Name::Definition,
+            Name::GfmFootnoteDefinition,
+        ],
+    );
+    let tight_paragraph = *tight && context.events[before].name == Name::Paragraph;
+
+    context.slurp_one_line_ending = false;
+
+    if !tight_paragraph {
+        context.line_ending_if_needed();
+    }
+
+    context.push("</dd>");
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DefinitionListTerm`][Name::DefinitionListTerm].
+fn on_exit_definition_list_term(context: &mut CompileContext) {
+    context.text_inside = false;
+    context.push("</dt>");
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`DefinitionTitleString`][Name::DefinitionTitleString].
 fn on_exit_definition_title_string(context: &mut CompileContext) {
     let buf = context.resume();
     context.media_stack.last_mut().unwrap().title = Some(buf);
+    context.encode_html = true;
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`DirectiveContainer`][Name::DirectiveContainer],[`DirectiveLeaf`][Name::DirectiveLeaf],[`DirectiveText`][Name::DirectiveText]}.
+fn on_exit_directive(context: &mut CompileContext) {
+    let html = context.resume();
+    let mut directive = context
+        .directive_stack
+        .pop()
+        .expect("expected directive on stack");
+
+    if directive.kind == DirectiveKind::Container {
+        context.tight_stack.pop();
+        context.slurp_one_line_ending = false;
+        directive.content = Some(html.trim_end_matches(&['\n', '\r'][..]).into());
+    }
+
+    let result = if let Some(handler) = &context.options.directive_handler {
+        handler(&directive)
+    } else {
+        None
+    };
+
+    let value = result.unwrap_or_else(|| match directive.kind {
+        DirectiveKind::Container => directive.content.take().unwrap_or_default(),
+        // Leaves are blocks, so their label is a paragraph.
+        DirectiveKind::Leaf => directive
+            .label
+            .take()
+            .map_or_else(String::new, |label| format!("<p>{}</p>", label)),
+        DirectiveKind::Text => directive.label.take().unwrap_or_default(),
+    });
+
+    context.push(&value);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttribute`][Name::DirectiveAttribute].
+fn on_exit_directive_attribute(context: &mut CompileContext) {
+    let (key, value) = context
+        .directive_attribute
+        .take()
+        .expect("expected directive attribute");
+    let attributes = &mut context
+        .directive_stack
+        .last_mut()
+        .expect("expected directive on stack")
+        .attributes;
+    add_attribute(attributes, key, value);
+}
+
+/// Handle [`Exit`][Kind::Exit]:{[`DirectiveAttributeClassMarker`][Name::DirectiveAttributeClassMarker],[`DirectiveAttributeIdMarker`][Name::DirectiveAttributeIdMarker],[`DirectiveAttributeName`][Name::DirectiveAttributeName]}.
+fn on_exit_directive_attribute_name(context: &mut CompileContext) {
+    let key = match context.events[context.index].name {
+        Name::DirectiveAttributeClassMarker => "class".into(),
+        Name::DirectiveAttributeIdMarker => "id".into(),
+        _ => Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
+        )
+        .serialize(),
+    };
+
+    context.directive_attribute = Some((key, String::new()));
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributeValue`][Name::DirectiveAttributeValue].
+fn on_exit_directive_attribute_value(context: &mut CompileContext) {
+    let value = parse_character_reference(
+        Slice::from_position(
+            context.bytes,
+            &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
+        )
+        .as_str(),
+        true,
+    );
+
+    context
+        .directive_attribute
+        .as_mut()
+        .expect("expected directive attribute")
+        .1 = value;
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributes`][Name::DirectiveAttributes].
+fn on_exit_directive_attributes(context: &mut CompileContext) {
+    // Drop whitespace and line endings.
+    context.resume();
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_exit_directive_label(context: &mut CompileContext) {
+    let label = context.resume();
+    context
+        .directive_stack
+        .last_mut()
+        .expect("expected directive on stack")
+        .label = Some(label);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveName`][Name::DirectiveName].
+fn on_exit_directive_name(context: &mut CompileContext) {
+    context
+        .directive_stack
+        .last_mut()
+        .expect("expected directive on stack")
+        .name = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    )
+    .serialize();
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`Emphasis`][Name::Emphasis].
@@ -986,6 +1741,78 @@ fn on_exit_emphasis(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Emoji`][Name::Emoji].
+fn on_exit_emoji(context: &mut CompileContext) {
+    if let Name::Emoji(value) = &context.events[context.index].name {
+        context.push(&encode(value, context.encode_html));
+    } else {
+        unreachable!("expected emoji")
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`FlowExtension`][Name::FlowExtension].
+fn on_exit_flow_extension(context: &mut CompileContext) {
+    // Drop the line endings that were compiled.
+    context.resume();
+
+    let mut index = context.index;
+
+    while !(context.events[index].kind == Kind::Enter
+        && matches!(context.events[index].name, Name::FlowExtension(_)))
+    {
+        index -= 1;
+    }
+
+    let mut value = String::new();
+
+    while index < context.index {
+        if context.events[index].kind == Kind::Exit
+            && context.events[index].name == Name::LineEnding
+            && context.options.line_ending.is_some()
+        {
+            value.push_str(context.line_ending_default.as_str());
+        } else if context.events[index].kind == Kind::Exit
+            && matches!(
+                context.events[index].name,
+                Name::FlowExtensionChunk | Name::LineEnding
+            )
+        {
+            value.push_str(
+                Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, index),
+                    context.tab_size,
+                )
+                .as_str(),
+            );
+        }
+
+        index += 1;
+    }
+
+    let name = if let Name::FlowExtension(name) = &context.events[context.index].name {
+        name
+    } else {
+        unreachable!("expected flow extension")
+    };
+    let result = if let Some(handler) = &context.options.flow_extension_handler {
+        handler(name, &value)
+    } else {
+        None
+    };
+
+    context.line_ending_if_needed();
+
+    if let Some(result) = result {
+        context.push(&result);
+    } else {
+        context.push("<pre><code>");
+        context.push(&encode(&value, context.encode_html));
+        context.line_ending();
+        context.push("</code></pre>");
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) {
     context.resume();
@@ -1000,6 +1827,7 @@ fn on_exit_gfm_autolink_literal_email(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1014,6 +1842,7 @@ fn on_exit_gfm_autolink_literal_mailto(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1028,6 +1857,7 @@ fn on_exit_gfm_autolink_literal_protocol(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1042,6 +1872,7 @@ fn on_exit_gfm_autolink_literal_www(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1056,6 +1887,7 @@ fn on_exit_gfm_autolink_literal_xmpp(context: &mut CompileContext) {
         Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .as_str(),
         true,
@@ -1116,7 +1948,20 @@ fn on_exit_gfm_footnote_call(context: &mut CompileContext) {
                 .to_string(),
         );
     }
-    context.push("\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">");
+    context.push("\" data-footnote-ref=\"\" aria-describedby=\"");
+    // Without section, there is no label, so point to the footnote itself.
+    if context.options.gfm_footnote_style == FootnoteStyle::Inline {
+        if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
+            context.push(&encode(value, context.encode_html));
+        } else {
+            context.push("user-content-");
+        }
+        context.push("fn-");
+        context.push(&safe_id);
+    } else {
+        context.push("footnote-label");
+    }
+    context.push("\">");
 
     context.push(&(call_index + 1).to_string());
     context.push("</a></sup>");
@@ -1214,14 +2059,14 @@ fn on_exit_gfm_table_row(context: &mut CompileContext) {
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemCheck`][Name::GfmTaskListItemCheck].
 fn on_exit_gfm_task_list_item_check(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("/>");
+        push_void_end(context);
     }
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`GfmTaskListItemValueChecked`][Name::GfmTaskListItemValueChecked].
 fn on_exit_gfm_task_list_item_value_checked(context: &mut CompileContext) {
     if !context.image_alt_inside {
-        context.push("checked=\"\" ");
+        context.push(" checked=\"\"");
     }
 }
 
@@ -1230,11 +2075,11 @@ fn on_exit_heading_atx(context: &mut CompileContext) {
     let rank = context
         .heading_atx_rank
         .take()
-        .expect("`heading_atx_rank` must be set in headings");
+        .expect("`heading_atx_rank` must be set in headings")
+        .to_string();
+    let text = context.resume();
 
-    context.push("</h");
-    context.push(&rank.to_string());
-    context.push(">");
+    generate_heading(context, context.index, &rank, &text);
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`HeadingAtxSequence`][Name::HeadingAtxSequence].
@@ -1244,13 +2089,14 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
         let rank = Slice::from_position(
             context.bytes,
             &Position::from_exit_event(context.events, context.index),
+            context.tab_size,
         )
         .len();
         context.line_ending_if_needed();
         context.heading_atx_rank = Some(rank);
-        context.push("<h");
-        context.push(&rank.to_string());
-        context.push(">");
+        // Buffer the text, as the opening tag can only be generated once the
+        // id is known.
+        context.buffer();
     }
 }
 
@@ -1258,11 +2104,14 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
 fn on_exit_heading_atx_text(context: &mut CompileContext) {
     let value = context.resume();
     context.push(&value);
+    set_heading_id(context);
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`HeadingSetextText`][Name::HeadingSetextText].
 fn on_exit_heading_setext_text(context: &mut CompileContext) {
+    context.text_inside = false;
     let buf = context.resume();
+    set_heading_id(context);
     context.heading_setext_buffer = Some(buf);
     context.slurp_one_line_ending = true;
 }
@@ -1277,18 +2126,46 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
     let head = context.bytes[position.start.index];
     let rank = if head == b'-' { "2" } else { "1" };
 
+    let mut index = context.index;
+    while !(context.events[index].kind == Kind::Enter
+        && context.events[index].name == Name::HeadingSetext)
+    {
+        index -= 1;
+    }
+
     context.line_ending_if_needed();
-    context.push("<h");
-    context.push(rank);
-    context.push(">");
-    context.push(&text);
-    context.push("</h");
-    context.push(rank);
-    context.push(">");
+    generate_heading(context, index, rank, &text);
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Highlight`][Name::Highlight].
+fn on_exit_highlight(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</mark>");
+    }
 }
 
 /// Handle [`Exit`][Kind::Exit]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_exit_html(context: &mut CompileContext) {
+    // Not passed through: warn.
+    if context.encode_html {
+        let position = Position::from_exit_event(context.events, context.index);
+        let reason = if context.options.allow_dangerous_html {
+            "Unexpected HTML that is not allowed by `html_filter`, expected markdown (it is escaped, and shown as text)"
+        } else {
+            "Unexpected HTML, expected markdown (it is escaped, and shown as text, because `allow_dangerous_html` is off)"
+        };
+
+        context.messages.push(message::Message {
+            place: Some(Box::new(message::Place::Position(unist::Position {
+                start: position.start.to_unist(),
+                end: position.end.to_unist(),
+            }))),
+            reason: reason.into(),
+            rule_id: Box::new("escaped-html".into()),
+            source: Box::new("markdown-rs".into()),
+        });
+    }
+
     context.encode_html = true;
 }
 
@@ -1297,10 +2174,11 @@ fn on_exit_html_data(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.as_str();
 
-    let encoded = if context.options.gfm_tagfilter && context.options.allow_dangerous_html {
+    let encoded = if context.options.gfm_tagfilter && !context.encode_html {
         encode(&gfm_tagfilter(value), context.encode_html)
     } else {
         encode(value, context.encode_html)
@@ -1328,26 +2206,73 @@ fn on_exit_line_ending(context: &mut CompileContext) {
     } else if context.slurp_one_line_ending
         // Ignore line endings after definitions.
         || (context.index > 1
-            && (context.events[context.index - 2].name == Name::Definition
-                || context.events[context.index - 2].name == Name::GfmFootnoteDefinition))
+            && matches!(
+                context.events[context.index - 2].name,
+                Name::AbbreviationDefinition | Name::Definition | Name::GfmFootnoteDefinition
+            ))
     {
         context.slurp_one_line_ending = false;
     } else {
-        context.push(&encode(
-            Slice::from_position(
-                context.bytes,
-                &Position::from_exit_event(context.events, context.index),
+        // Soft break, which can be turned into a hard break.
+        if context.options.hard_line_breaks
+            && context.text_inside
+            && !context.image_alt_inside
+            && !matches!(
+                context.events[context.index - 2].name,
+                // Already a hard break.
+                Name::HardBreakEscape | Name::HardBreakTrailing
+                // In HTML (text).
+                | Name::HtmlTextData
             )
-            .as_str(),
-            context.encode_html,
-        ));
+        {
+            context.push("<br");
+            push_void_end(context);
+        }
+
+        if context.options.normalize_block_whitespace && flow_line_ending(context) {
+            context.line_ending_if_needed();
+        } else if context.options.line_ending.is_some() {
+            context.line_ending();
+        } else {
+            context.push(&encode(
+                Slice::from_position(
+                    context.bytes,
+                    &Position::from_exit_event(context.events, context.index),
+                    context.tab_size,
+                )
+                .as_str(),
+                context.encode_html,
+            ));
+        }
     }
 }
 
+/// Check whether the line ending at the current event is between blocks,
+/// instead of in text or in raw content (code, HTML).
+fn flow_line_ending(context: &CompileContext) -> bool {
+    let before = &context.events[context.index - 2];
+
+    !context.text_inside
+        && context.raw_flow_seen_data.is_none()
+        && before.kind == Kind::Exit
+        && !matches!(before.name, Name::HtmlFlowData | Name::LineEnding)
+}
+
 /// Handle [`Exit`][Kind::Exit]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
 fn on_exit_list(context: &mut CompileContext) {
     context.tight_stack.pop();
-    context.line_ending();
+
+    if exit_droppable(context, true) {
+        return;
+    }
+
+    // When the last item was dropped, there can be a line ending already.
+    if context.options.drop_empty_blocks {
+        context.line_ending_if_needed();
+    } else {
+        context.line_ending();
+    }
+
     context.push(if context.events[context.index].name == Name::ListOrdered {
         "</ol>"
     } else {
@@ -1367,6 +2292,7 @@ fn on_exit_list_item(context: &mut CompileContext) {
             Name::LineEnding,
             Name::SpaceOrTab,
             // Also ignore things that don’t contribute to the document.
+            Name::AbbreviationDefinition,
             Name::Definition,
             Name::GfmFootnoteDefinition,
         ],
@@ -1377,6 +2303,10 @@ fn on_exit_list_item(context: &mut CompileContext) {
 
     context.slurp_one_line_ending = false;
 
+    if exit_droppable(context, true) {
+        return;
+    }
+
     if !tight_paragraph && !empty_item {
         context.line_ending_if_needed();
     }
@@ -1384,23 +2314,6 @@ fn on_exit_list_item(context: &mut CompileContext) {
     context.push("</li>");
 }
 
-/// Handle [`Exit`][Kind::Exit]:[`ListItemValue`][Name::ListItemValue].
-fn on_exit_list_item_value(context: &mut CompileContext) {
-    if context.list_expect_first_marker.unwrap() {
-        let slice = Slice::from_position(
-            context.bytes,
-            &Position::from_exit_event(context.events, context.index),
-        );
-        let value = slice.as_str().parse::<u32>().ok().unwrap();
-
-        if value != 1 {
-            context.push(" start=\"");
-            context.push(&value.to_string());
-            context.push("\"");
-        }
-    }
-}
-
 /// Handle [`Exit`][Kind::Exit]:{[`Image`][Name::Image],[`Link`][Name::Link]}.
 fn on_exit_media(context: &mut CompileContext) {
     let mut is_in_image = false;
@@ -1442,38 +2355,64 @@ fn on_exit_media(context: &mut CompileContext) {
         None
     };
 
-    if !is_in_image {
-        if media.image {
-            context.push("<img src=\"");
-        } else {
-            context.push("<a href=\"");
-        };
+    let (destination, title) = if let Some(index) = definition_index {
+        (
+            context.definitions[index].destination.clone(),
+            context.definitions[index].title.clone(),
+        )
+    } else {
+        (media.destination, media.title)
+    };
 
-        let destination = if let Some(index) = definition_index {
-            context.definitions[index].destination.as_ref()
-        } else {
-            media.destination.as_ref()
+    let mut dimensions = None;
+    let title = if media.image && context.options.image_dimensions {
+        title.and_then(|title| {
+            if let Some((rest, width, height)) = split_image_dimensions(&title) {
+                dimensions = Some((width.to_string(), height.to_string()));
+                if rest.is_empty() {
+                    None
+                } else {
+                    Some(rest.to_string())
+                }
+            } else {
+                Some(title)
+            }
+        })
+    } else {
+        title
+    };
+
+    if media.image && !is_in_image && context.figure_inside {
+        let caption = match context.options.image_figure {
+            Some(FigureCaption::Title) => title.as_ref().map(|title| encode(title, true)),
+            _ => Some(label.clone()),
         };
+        context.figure_caption = caption.filter(|caption| !caption.is_empty());
+    }
 
-        if let Some(destination) = destination {
-            let url = if context.options.allow_dangerous_protocol {
-                sanitize(destination)
-            } else {
-                sanitize_with_protocols(
-                    destination,
-                    if media.image {
-                        &SAFE_PROTOCOL_SRC
-                    } else {
-                        &SAFE_PROTOCOL_HREF
-                    },
-                )
-            };
-            context.push(&url);
+    let url = if is_in_image {
+        None
+    } else {
+        generate_url(
+            context,
+            destination.unwrap_or_default(),
+            media.image,
+            title.clone(),
+        )
+    };
+
+    if !is_in_image {
+        context.push(if media.image { "<img" } else { "<a" });
+
+        if let Some(url) = &url {
+            context.push(if media.image { " src=\"" } else { " href=\"" });
+            context.push(url);
+            context.push("\"");
         }
 
         if media.image {
-            context.push("\" alt=\"");
-        };
+            context.push(" alt=\"");
+        }
     }
 
     if media.image {
@@ -1481,25 +2420,50 @@ fn on_exit_media(context: &mut CompileContext) {
     }
 
     if !is_in_image {
-        context.push("\"");
-
-        let title = if let Some(index) = definition_index {
-            context.definitions[index].title.clone()
-        } else {
-            media.title
-        };
+        if media.image {
+            context.push("\"");
+        }
 
         if let Some(title) = title {
             context.push(" title=\"");
-            context.push(&title);
+            context.push(&encode(&title, true));
             context.push("\"");
-        };
+        }
+
+        if let Some((width, height)) = dimensions {
+            if !width.is_empty() {
+                context.push(" width=\"");
+                context.push(&width);
+                context.push("\"");
+            }
+
+            if !height.is_empty() {
+                context.push(" height=\"");
+                context.push(&height);
+                context.push("\"");
+            }
+        }
 
         if media.image {
-            context.push(" /");
+            if let Some(loading) = &context.options.image_loading {
+                context.push(match loading {
+                    ImageLoading::Lazy => " loading=\"lazy\"",
+                    ImageLoading::Eager => " loading=\"eager\"",
+                });
+            }
         }
 
-        context.push(">");
+        if !media.image {
+            if let Some(url) = &url {
+                push_external_link_attributes(context, url);
+            }
+        }
+
+        if media.image {
+            push_void_end(context);
+        } else {
+            context.push(">");
+        }
     }
 
     if !media.image {
@@ -1513,12 +2477,33 @@ fn on_exit_media(context: &mut CompileContext) {
 
 /// Handle [`Exit`][Kind::Exit]:[`Paragraph`][Name::Paragraph].
 fn on_exit_paragraph(context: &mut CompileContext) {
-    let tight = context.tight_stack.last().unwrap_or(&false);
+    let tight = *context.tight_stack.last().unwrap_or(&false);
+    context.text_inside = false;
+
+    if exit_droppable(context, !tight) {
+        return;
+    }
+
+    if context.figure_inside {
+        context.figure_inside = false;
+
+        if let Some(caption) = context.figure_caption.take() {
+            context.push("<figcaption>");
+            context.push(&caption);
+            context.push("</figcaption>");
+        }
+
+        context.push("</figure>");
 
-    if *tight {
+        if tight {
+            context.slurp_one_line_ending = true;
+        }
+    } else if tight {
         context.slurp_one_line_ending = true;
     } else {
-        context.push("</p>");
+        context.push("</");
+        context.push(&paragraph_tag_name(context));
+        context.push(">");
     }
 }
 
@@ -1542,6 +2527,7 @@ fn on_exit_resource_destination_string(context: &mut CompileContext) {
 fn on_exit_resource_title_string(context: &mut CompileContext) {
     let buf = context.resume();
     context.media_stack.last_mut().unwrap().title = Some(buf);
+    context.encode_html = true;
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`Strong`][Name::Strong].
@@ -1551,10 +2537,55 @@ fn on_exit_strong(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Subscript`][Name::Subscript].
+fn on_exit_subscript(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</sub>");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`Superscript`][Name::Superscript].
+fn on_exit_superscript(context: &mut CompileContext) {
+    if !context.image_alt_inside {
+        context.push("</sup>");
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`TextExtension`][Name::TextExtension].
+fn on_exit_text_extension(context: &mut CompileContext) {
+    let slice = Slice::from_position(
+        context.bytes,
+        &Position::from_exit_event(context.events, context.index),
+        context.tab_size,
+    );
+    let value = slice.as_str();
+    let name = if let Name::TextExtension(name) = &context.events[context.index].name {
+        name
+    } else {
+        unreachable!("expected text extension")
+    };
+    let result = if context.image_alt_inside {
+        None
+    } else if let Some(handler) = &context.options.text_extension_handler {
+        handler(name, value)
+    } else {
+        None
+    };
+
+    if let Some(result) = result {
+        context.push(&result);
+    } else {
+        context.push(&encode(value, context.encode_html));
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_exit_thematic_break(context: &mut CompileContext) {
     context.line_ending_if_needed();
-    context.push("<hr />");
+    context.push("<hr");
+    push_source_position(context, context.index);
+    push_class(context, context.index);
+    push_void_end(context);
 }
 
 /// Generate a footnote section.
@@ -1601,8 +2632,89 @@ fn generate_footnote_section(context: &mut CompileContext) {
     context.line_ending();
 }
 
+/// Note where footnotes go that were first referenced in the block that just
+/// ended (for `FootnoteStyle::Inline`).
+///
+/// Only blocks in the root buffer are used: footnotes first referenced in
+/// other footnotes go after the next block.
+fn place_inline_footnotes(context: &mut CompileContext, index: usize) {
+    let event = &context.events[index];
+
+    if event.kind == Kind::Exit
+        && matches!(
+            event.name,
+            Name::DefinitionList
+                | Name::GfmTable
+                | Name::HeadingAtx
+                | Name::HeadingSetext
+                | Name::Paragraph
+        )
+        && context.buffers.len() == 1
+    {
+        let offset = context.buffers[0].len();
+
+        while context.gfm_footnote_inline_placed < context.gfm_footnote_definition_calls.len() {
+            context
+                .gfm_footnote_inline
+                .push((offset, context.gfm_footnote_inline_placed));
+            context.gfm_footnote_inline_placed += 1;
+        }
+    }
+}
+
+/// Generate footnotes at their places (for `FootnoteStyle::Inline`).
+fn generate_inline_footnotes(context: &mut CompileContext) {
+    // Footnotes that have no place yet go at the end.
+    let end = context.buffers[0].len();
+
+    while context.gfm_footnote_inline_placed < context.gfm_footnote_definition_calls.len() {
+        context
+            .gfm_footnote_inline
+            .push((end, context.gfm_footnote_inline_placed));
+        context.gfm_footnote_inline_placed += 1;
+    }
+
+    let places = core::mem::take(&mut context.gfm_footnote_inline);
+    let root = core::mem::take(&mut context.buffers[0]);
+    let mut result = String::new();
+    let mut last = 0;
+    let mut index = 0;
+
+    while index < places.len() {
+        let (offset, call_index) = places[index];
+        result.push_str(&root[last..offset]);
+        last = offset;
+
+        context.buffer();
+        generate_footnote_item(context, call_index);
+        let value = context.resume();
+
+        let last_byte = if result.is_empty() {
+            context.written_last_byte
+        } else {
+            result.as_bytes().last().copied()
+        };
+
+        // Don’t add a line ending at the start, or after another.
+        if matches!(last_byte, None | Some(b'\n' | b'\r')) {
+            result.push_str(value.trim_start_matches(|d| d == '\n' || d == '\r'));
+        } else {
+            result.push_str(&value);
+        }
+
+        index += 1;
+    }
+
+    result.push_str(&root[last..]);
+    context.buffers[0] = result;
+}
+
 /// Generate a footnote item from a call.
+///
+/// This is a list item in the footnote section, or an `<aside>` for
+/// `FootnoteStyle::Inline`.
 fn generate_footnote_item(context: &mut CompileContext, index: usize) {
+    let inline = context.options.gfm_footnote_style == FootnoteStyle::Inline;
     let id = &context.gfm_footnote_definition_calls[index].0;
     let safe_id = sanitize(&id.to_lowercase());
 
@@ -1622,7 +2734,7 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     );
 
     context.line_ending();
-    context.push("<li id=\"");
+    context.push(if inline { "<aside id=\"" } else { "<li id=\"" });
     if let Some(ref value) = context.options.gfm_footnote_clobber_prefix {
         context.push(&encode(value, context.encode_html));
     } else {
@@ -1630,6 +2742,10 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     }
     context.push("fn-");
     context.push(&safe_id);
+    if inline {
+        context.push("\" class=\"footnote\" data-footnote-number=\"");
+        context.push(&(index + 1).to_string());
+    }
     context.push("\">");
     context.line_ending();
 
@@ -1676,18 +2792,14 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
     while byte_index > 0 && matches!(bytes[byte_index - 1], b'\n' | b'\r') {
         byte_index -= 1;
     }
+    let closing = format!("</{}>", paragraph_tag_name(context));
     // Check if it ends in `</p>`.
     // This is a bit funky if someone wrote a safe paragraph by hand in
     // there.
     // But in all other cases, `<` and `>` would be encoded, so we can be
     // sure that this is generated by our compiler.
-    if byte_index > 3
-        && bytes[byte_index - 4] == b'<'
-        && bytes[byte_index - 3] == b'/'
-        && bytes[byte_index - 2] == b'p'
-        && bytes[byte_index - 1] == b'>'
-    {
-        let (before, after) = bytes.split_at(byte_index - 4);
+    if bytes[..byte_index].ends_with(closing.as_bytes()) {
+        let (before, after) = bytes.split_at(byte_index - closing.len());
         let mut result = String::new();
         result.push_str(str::from_utf8(before).unwrap());
         result.push(' ');
@@ -1700,7 +2812,266 @@ fn generate_footnote_item(context: &mut CompileContext, index: usize) {
         context.push(&backreferences);
     }
     context.line_ending_if_needed();
-    context.push("</li>");
+    context.push(if inline { "</aside>" } else { "</li>" });
+}
+
+/// Get the (encoded) tag name of paragraphs.
+fn paragraph_tag_name(context: &CompileContext) -> String {
+    encode(
+        context.options.paragraph_wrapper.as_deref().unwrap_or("p"),
+        context.encode_html,
+    )
+}
+
+/// Generate a heading.
+///
+/// `index` is the index of the enter or exit event of the heading.
+fn generate_heading(context: &mut CompileContext, index: usize, rank: &str, text: &str) {
+    context.push("<h");
+    context.push(rank);
+    push_source_position(context, index);
+    let id = context.heading_id.take();
+    push_attributes(context, index, id);
+    context.push(">");
+    context.push(text);
+    context.push("</h");
+    context.push(rank);
+    context.push(">");
+}
+
+/// Add a `class` attribute, if `class_map` has a class for the construct
+/// entered or exited at `index`.
+fn push_class(context: &mut CompileContext, index: usize) {
+    let options = context.options;
+
+    if let Some(value) = options.class_map.get(&context.events[index].name) {
+        if !value.is_empty() {
+            context.push(" class=\"");
+            context.push(&encode(value, true));
+            context.push("\"");
+        }
+    }
+}
+
+/// Add the tag name and class of the element that wraps math, with `mode`
+/// (`math-inline` or `math-display`) as the last class.
+fn push_math_start(context: &mut CompileContext, mode: &str) {
+    push_math_tag_name(context);
+    context.push(" class=\"");
+
+    if let Some(ref value) = context.options.math_class_name {
+        if !value.is_empty() {
+            context.push(&encode(value, true));
+            context.push(" ");
+        }
+    } else {
+        context.push("language-math ");
+    }
+
+    context.push(mode);
+    context.push("\"");
+}
+
+/// Add the tag name of the element that wraps math.
+fn push_math_tag_name(context: &mut CompileContext) {
+    if let Some(ref value) = context.options.math_tag_name {
+        context.push(&encode(value, context.encode_html));
+    } else {
+        context.push("code");
+    }
+}
+
+/// Close the element of raw (flow) (code (fenced), code (indented), math
+/// (flow)), which is exited at the current index.
+fn push_raw_flow_end(context: &mut CompileContext) {
+    context.push("</");
+    if context.events[context.index].name == Name::MathFlow {
+        push_math_tag_name(context);
+    } else {
+        context.push("code");
+    }
+    context.push("></pre>");
+}
+
+/// Close the start tag of a void element (`<br>`, `<hr>`, `<img>`,
+/// `<input>`), self-closing it if `xhtml` is on.
+fn push_void_end(context: &mut CompileContext) {
+    context.push(if context.options.xhtml { " />" } else { ">" });
+}
+
+/// Add the attributes of the element of the construct entered or exited at
+/// `index`: a `class` from `class_map`, `id`, and whatever is in an
+/// attribute block (`{#a .b c=d}`) at the end of the construct.
+fn push_attributes(context: &mut CompileContext, index: usize, id: Option<String>) {
+    let mut attributes = vec![];
+
+    if let Some(value) = context.options.class_map.get(&context.events[index].name) {
+        if !value.is_empty() {
+            attributes.push(("class".into(), value.clone()));
+        }
+    }
+
+    if let Some(id) = id {
+        attributes.push(("id".into(), id));
+    }
+
+    let other = other_event(context.events, index);
+    let (mut start, end) = if other > index {
+        (index, other)
+    } else {
+        (other, index)
+    };
+    let mut key = String::new();
+    let mut value = String::new();
+
+    while start < end {
+        let event = &context.events[start];
+
+        if event.kind == Kind::Exit {
+            match event.name {
+                Name::Attribute => {
+                    let key = core::mem::take(&mut key);
+                    let value = core::mem::take(&mut value);
+
+                    if attribute_allowed(context, &key) {
+                        add_attribute(&mut attributes, key, value);
+                    }
+                }
+                Name::AttributeClassMarker => key = "class".into(),
+                Name::AttributeIdMarker => key = "id".into(),
+                Name::AttributeName | Name::AttributeValue => {
+                    let slice = Slice::from_position(
+                        context.bytes,
+                        &Position::from_exit_event(context.events, start),
+                        context.tab_size,
+                    );
+
+                    if event.name == Name::AttributeName {
+                        key = slice.serialize();
+                    } else {
+                        value = parse_character_reference(slice.as_str(), true);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        start += 1;
+    }
+
+    let mut index = 0;
+
+    while index < attributes.len() {
+        let (key, value) = &attributes[index];
+        context.push(" ");
+        context.push(key);
+        context.push("=\"");
+        context.push(&encode(value, true));
+        context.push("\"");
+        index += 1;
+    }
+}
+
+/// Check whether an attribute from markdown can be added to an element.
+///
+/// Event handlers (`on*`) and `style` are never allowed, other attributes
+/// only when they are safe or when `allow_dangerous_html` is on.
+fn attribute_allowed(context: &CompileContext, key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+
+    !key.starts_with("on")
+        && key != "style"
+        && (context.options.allow_dangerous_html || SAFE_ATTRIBUTE_NAMES.contains(&key.as_str()))
+}
+
+/// Add a `data-sourcepos` attribute, if `source_positions` is on, with the
+/// position of the construct entered or exited at `index`.
+fn push_source_position(context: &mut CompileContext, index: usize) {
+    if !context.options.source_positions {
+        return;
+    }
+
+    let other = other_event(context.events, index);
+    let (start, end) = if other > index {
+        (&context.events[index].point, &context.events[other].point)
+    } else {
+        (&context.events[other].point, &context.events[index].point)
+    };
+    let value = format!(
+        " data-sourcepos=\"{}:{}-{}:{}\"",
+        start.line, start.column, end.line, end.column
+    );
+
+    context.push(&value);
+}
+
+/// Check whether the paragraph at `index` contains only an image, followed by
+/// optional whitespace and attributes.
+fn is_figure(events: &[Event], index: usize) -> bool {
+    let mut index = index + 1;
+
+    if !(events[index].kind == Kind::Enter && events[index].name == Name::Image) {
+        return false;
+    }
+
+    index = other_event(events, index) + 1;
+
+    while events[index].kind == Kind::Enter
+        && matches!(events[index].name, Name::Attributes | Name::SpaceOrTab)
+    {
+        index = other_event(events, index) + 1;
+    }
+
+    events[index].kind == Kind::Exit && events[index].name == Name::Paragraph
+}
+
+/// Get the index of the exit event that matches the enter event at `index`,
+/// or of the enter event that matches the exit event at `index`.
+fn other_event(events: &[Event], index: usize) -> usize {
+    let mut other = index;
+    let mut balance = 0;
+
+    if events[index].kind == Kind::Enter {
+        loop {
+            other += 1;
+            if events[other].kind == Kind::Enter {
+                balance += 1;
+            } else if balance == 0 {
+                break;
+            } else {
+                balance -= 1;
+            }
+        }
+    } else {
+        loop {
+            other -= 1;
+            if events[other].kind == Kind::Exit {
+                balance += 1;
+            } else if balance == 0 {
+                break;
+            } else {
+                balance -= 1;
+            }
+        }
+    }
+
+    other
+}
+
+/// Set the id of the current heading, if `heading_ids` is on, from the plain
+/// text of the heading text that was just exited.
+fn set_heading_id(context: &mut CompileContext) {
+    if !context.options.heading_ids {
+        return;
+    }
+
+    let value = heading_text(
+        context.events,
+        context.bytes,
+        context.tab_size,
+        context.index,
+    );
+    context.heading_id = Some(context.heading_slugger.slug(&value));
 }
 
 /// Generate an autolink (used by unicode autolinks and GFM autolink literals).
@@ -1721,27 +3092,302 @@ fn generate_autolink(
         index += 1;
     }
 
-    if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
-        context.push("<a href=\"");
+    let as_link = !context.image_alt_inside
+        && (!is_in_link || !(is_gfm_literal || context.options.flatten_autolinks));
+
+    if as_link {
         let url = if let Some(protocol) = protocol {
             format!("{}{}", protocol, value)
         } else {
             value.into()
         };
 
-        let url = if context.options.allow_dangerous_protocol {
-            sanitize(&url)
-        } else {
-            sanitize_with_protocols(&url, &SAFE_PROTOCOL_HREF)
-        };
+        context.push("<a");
 
-        context.push(&url);
-        context.push("\">");
+        if let Some(url) = generate_url(context, url, false, None) {
+            context.push(" href=\"");
+            context.push(&url);
+            context.push("\"");
+            push_external_link_attributes(context, &url);
+        }
+
+        context.push(">");
     }
 
     context.push(&encode(value, context.encode_html));
 
-    if !context.image_alt_inside && (!is_in_link || !is_gfm_literal) {
+    if as_link {
         context.push("</a>");
     }
 }
+
+/// Start a block that is dropped if it is empty, with
+/// [`drop_empty_blocks`][CompileOptions::drop_empty_blocks].
+///
+/// Call before its opening tag, and call [`opened_droppable`][] after it.
+/// Without the option, this adds a line ending if `line_ending` is `true`.
+/// With it, the block is buffered, and the line ending is added when it is
+/// not dropped.
+fn enter_droppable(context: &mut CompileContext, line_ending: bool) {
+    if context.options.drop_empty_blocks {
+        context.buffer();
+    } else if line_ending {
+        context.line_ending_if_needed();
+    }
+}
+
+/// Mark the end of the opening tag of a block that is dropped if it is
+/// empty.
+fn opened_droppable(context: &mut CompileContext) {
+    if context.options.drop_empty_blocks {
+        let size = context.buffers.last().expect("expected buffer").len();
+        context.droppable_stack.push(size);
+    }
+}
+
+/// End a block that is dropped if it is empty, before its closing tag.
+///
+/// Returns whether the block was dropped, in which case the closing tag
+/// must not be added.
+fn exit_droppable(context: &mut CompileContext, line_ending: bool) -> bool {
+    if !context.options.drop_empty_blocks {
+        return false;
+    }
+
+    let size = context
+        .droppable_stack
+        .pop()
+        .expect("expected droppable block");
+    let value = context.resume();
+
+    if value[size..].trim().is_empty() {
+        // Also drop the line ending after it.
+        context.slurp_one_line_ending = true;
+        return true;
+    }
+
+    if line_ending {
+        context.line_ending_if_needed();
+    }
+
+    context.push(&value);
+    false
+}
+
+/// Add `rel` and `target` attributes to a link, if it is external and they
+/// are configured.
+fn push_external_link_attributes(context: &mut CompileContext, url: &str) {
+    if context.options.external_link_rel.is_none() && context.options.external_link_target.is_none()
+    {
+        return;
+    }
+
+    let external = if let Some(is_external_link) = &context.options.is_external_link {
+        is_external_link(url)
+    } else {
+        is_external(url, context.options.base_url.as_deref())
+    };
+
+    if external {
+        if let Some(rel) = &context.options.external_link_rel {
+            context.push(" rel=\"");
+            context.push(&encode(rel, true));
+            context.push("\"");
+        }
+
+        if let Some(target) = &context.options.external_link_target {
+            context.push(" target=\"");
+            context.push(&encode(target, true));
+            context.push("\"");
+        }
+    }
+}
+
+/// Generate the URL of a link or image: pass it to `url_transform`, resolve it
+/// against `base_url`, normalize its case, and make it safe.
+///
+/// Returns `None` if `url_transform` drops it.
+fn generate_url(
+    context: &CompileContext,
+    destination: String,
+    image: bool,
+    title: Option<String>,
+) -> Option<String> {
+    let destination = if let Some(transform) = &context.options.url_transform {
+        transform(UrlContext {
+            destination,
+            image,
+            title,
+        })?
+    } else {
+        destination
+    };
+
+    let destination = if let Some(base) = &context.options.base_url {
+        resolve(base, &destination)
+    } else {
+        destination
+    };
+
+    let destination = if context.options.normalize_url_case {
+        normalize_case(&destination)
+    } else {
+        destination
+    };
+
+    Some(
+        if context.options.allow_dangerous_protocol || image && safe_data_image(&destination) {
+            sanitize(&destination)
+        } else {
+            sanitize_with_protocols(
+                &destination,
+                if image {
+                    &SAFE_PROTOCOL_SRC
+                } else {
+                    &SAFE_PROTOCOL_HREF
+                },
+            )
+        },
+    )
+}
+
+/// Check whether a URL is a `data:` URL of an image of a safe type, such as
+/// `data:image/png;base64,…`.
+fn safe_data_image(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+
+    if let Some(rest) = lower.strip_prefix("data:image/") {
+        let mut index = 0;
+
+        while index < SAFE_DATA_IMAGE_TYPES.len() {
+            if let Some(after) = rest.strip_prefix(SAFE_DATA_IMAGE_TYPES[index]) {
+                if after.is_empty() || after.starts_with([';', ',']) {
+                    return true;
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    false
+}
+
+/// Split a `=WxH` suffix off the title of an image.
+///
+/// Returns the title before the suffix (without trailing whitespace), and the
+/// width and height (either, but not both, can be empty), or `None` if there
+/// is no valid suffix.
+fn split_image_dimensions(title: &str) -> Option<(&str, &str, &str)> {
+    let bytes = title.as_bytes();
+    let mut start = bytes.len();
+
+    while start > 0 && matches!(bytes[start - 1], b'0'..=b'9' | b'x' | b'=') {
+        start -= 1;
+    }
+
+    // Find the `=`, which must be at the start or after whitespace.
+    while start < bytes.len() && bytes[start] != b'=' {
+        start += 1;
+    }
+
+    if start == bytes.len()
+        || (start > 0 && !matches!(bytes[start - 1], b'\t' | b'\n' | b'\r' | b' '))
+    {
+        return None;
+    }
+
+    let (width, height) = title[start + 1..].split_once('x')?;
+
+    if (width.is_empty() && height.is_empty())
+        || !width.bytes().all(|d| d.is_ascii_digit())
+        || !height.bytes().all(|d| d.is_ascii_digit())
+    {
+        return None;
+    }
+
+    Some((title[..start].trim_end(), width, height))
+}
+
+/// Check whether the HTML (flow or text) that starts at the current event is
+/// allowed by `html_filter`.
+///
+/// Classifies HTML like [HTML (flow)][crate::construct::html_flow] does.
+fn html_allowed(context: &CompileContext) -> bool {
+    let filter = &context.options.html_filter;
+    let bytes = context.bytes;
+    let mut index = context.events[context.index].point.index;
+
+    // Skip the indent of HTML (flow).
+    while index < bytes.len() && matches!(bytes[index], b'\t' | b' ') {
+        index += 1;
+    }
+
+    let rest = &bytes[index..];
+
+    if rest.starts_with(b"<!--") {
+        filter.comment
+    } else if rest.starts_with(b"<![") && rest[3..].starts_with(&HTML_CDATA_PREFIX) {
+        filter.cdata
+    } else if rest.starts_with(b"<!") {
+        filter.declaration
+    } else if rest.starts_with(b"<?") {
+        filter.instruction
+    } else {
+        let closing = rest.get(1) == Some(&b'/');
+        let start = if closing { 2 } else { 1 };
+        let mut end = start;
+
+        while end < rest.len() && (rest[end].is_ascii_alphanumeric() || rest[end] == b'-') {
+            end += 1;
+        }
+
+        let name = String::from_utf8_lossy(&rest[start..end]).to_ascii_lowercase();
+        let slash = rest.get(end) == Some(&b'/');
+
+        if !closing && !slash && HTML_RAW_NAMES.contains(&name.as_str()) {
+            filter.raw
+        } else if HTML_BLOCK_NAMES.contains(&name.as_str()) {
+            filter.basic
+        } else {
+            filter.complete
+        }
+    }
+}
+
+/// Check whether the current data is in string content (such as the info
+/// string of code (fenced), or a destination), instead of in text.
+fn in_string(context: &CompileContext) -> bool {
+    let mut index = context.index;
+    let mut balance = 0;
+
+    // Find the parent, skipping the enter of the data itself.
+    index -= 1;
+
+    while index > 0 {
+        index -= 1;
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            balance += 1;
+        } else if balance == 0 {
+            break;
+        } else {
+            balance -= 1;
+        }
+    }
+
+    matches!(
+        context.events[index].name,
+        Name::CodeFencedFenceInfo
+            | Name::CodeFencedFenceMeta
+            | Name::DefinitionDestinationString
+            | Name::DefinitionLabelString
+            | Name::DefinitionTitleString
+            | Name::GfmFootnoteDefinitionLabelString
+            | Name::MathFlowFenceMeta
+            | Name::ReferenceString
+            | Name::ResourceDestinationString
+            | Name::ResourceTitleString
+    )
+}
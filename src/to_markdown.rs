@@ -0,0 +1,1379 @@
+//! Turn a syntax tree into markdown.
+//!
+//! The markdown is normalized: the same syntax tree always gives the same
+//! markdown, regardless of which markers and whitespace the document used.
+
+use crate::event::{Event, Kind, Name};
+use crate::mdast::{
+    AlignKind, BlockQuote, Code, Definition, FootnoteDefinition, Heading, Image, ImageReference,
+    Link, LinkReference, List, Node, ReferenceKind, Table,
+};
+use crate::unist::Position;
+use crate::util::constant::FORMAT_NESTING_DEPTH_MAX;
+use crate::{FormatOptions, ParseOptions, ProseWrap};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::Cell;
+
+/// Something between two words.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Gap {
+    /// Nothing yet.
+    None,
+    /// Whitespace, which can be turned into a line ending when wrapping.
+    Space,
+    /// Soft break (line ending in the document).
+    Soft,
+    /// Hard break, which must be a line ending.
+    Hard,
+}
+
+/// A piece of phrasing content that can not be broken.
+#[derive(Debug)]
+struct Word {
+    /// Gap before the word.
+    gap: Gap,
+    /// Value.
+    value: String,
+    /// Whether the first character comes from text (which can be escaped),
+    /// instead of from syntax (such as `*` or `` ` ``).
+    text_start: bool,
+    /// Whether the word ends in an autolink literal (GFM).
+    literal_end: bool,
+}
+
+/// Phrasing content, split into words.
+#[derive(Debug)]
+struct Words {
+    /// Words that are done.
+    done: Vec<Word>,
+    /// Gap before the current word.
+    gap: Gap,
+    /// Current word.
+    value: String,
+    /// Whether the current word starts with text.
+    text_start: bool,
+    /// Whether the current word ends in an autolink literal (GFM).
+    literal: bool,
+    /// Whether in emphasis, to use the other marker for nested emphasis.
+    emphasis: bool,
+}
+
+impl Words {
+    /// Create new words.
+    fn new() -> Words {
+        Words {
+            done: vec![],
+            gap: Gap::None,
+            value: String::new(),
+            text_start: false,
+            literal: false,
+            emphasis: false,
+        }
+    }
+
+    /// Add a gap, ending the current word.
+    fn gap(&mut self, gap: Gap) {
+        self.flush();
+        if gap > self.gap {
+            self.gap = gap;
+        }
+    }
+
+    /// Add syntax to the current word.
+    fn syntax(&mut self, value: &str) {
+        self.value.push_str(value);
+        self.literal = false;
+    }
+
+    /// Add an autolink literal (GFM), or text directly after it, to the
+    /// current word.
+    fn literal(&mut self, value: &str) {
+        self.value.push_str(value);
+        self.literal = true;
+    }
+
+    /// Add (escaped) text to the current word.
+    fn text(&mut self, value: &str) {
+        if self.value.is_empty() {
+            self.text_start = true;
+        }
+        self.value.push_str(value);
+        self.literal = false;
+    }
+
+    /// End the current word.
+    fn flush(&mut self) {
+        if !self.value.is_empty() {
+            self.done.push(Word {
+                gap: self.gap,
+                value: core::mem::take(&mut self.value),
+                text_start: self.text_start,
+                literal_end: self.literal,
+            });
+            self.gap = Gap::None;
+            self.text_start = false;
+            self.literal = false;
+        }
+    }
+}
+
+/// State needed to serialize.
+struct State<'a> {
+    /// Document, to keep nodes that are not formatted as they are.
+    value: &'a str,
+    /// Parse configuration, to know what to escape and which list item
+    /// markers are allowed.
+    parse: &'a ParseOptions,
+    /// Configuration.
+    options: &'a FormatOptions,
+    /// Definitions (identifier, URL, title), when references are turned
+    /// into inline links.
+    definitions: Vec<(String, String, Option<String>)>,
+    /// Places (start and end offsets) of constructs that are not in the
+    /// tree, which are kept as they are, in order.
+    kept: Vec<(usize, usize)>,
+    /// Index of the next construct in `kept`.
+    kept_index: Cell<usize>,
+}
+
+/// Turn a syntax tree into markdown.
+pub fn serialize(
+    value: &str,
+    tree: &Node,
+    events: &[Event],
+    parse: &ParseOptions,
+    options: &FormatOptions,
+) -> String {
+    // Formatting is recursive, so very deep trees are kept as they are.
+    if too_deep(tree, FORMAT_NESTING_DEPTH_MAX) {
+        let value = value.trim_end();
+
+        return if value.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", value)
+        };
+    }
+
+    let mut state = State {
+        value,
+        parse,
+        options,
+        definitions: vec![],
+        kept: collect_kept(events),
+        kept_index: Cell::new(0),
+    };
+
+    if options.inline_links {
+        collect_definitions(&mut state, tree);
+    }
+
+    let result = flow(
+        &state,
+        tree.children().map_or(&[], |d| d),
+        tree.position(),
+        options.print_width,
+        true,
+        None,
+    );
+
+    if result.is_empty() {
+        result
+    } else {
+        result + "\n"
+    }
+}
+
+/// Check whether nodes in `tree` are nested deeper than `max`.
+///
+/// This walks the tree without recursion.
+fn too_deep(tree: &Node, max: usize) -> bool {
+    let mut stack = vec![(tree, 0)];
+
+    while let Some((node, depth)) = stack.pop() {
+        if depth > max {
+            return true;
+        }
+
+        if let Some(children) = node.children() {
+            let mut index = 0;
+            while index < children.len() {
+                stack.push((&children[index], depth + 1));
+                index += 1;
+            }
+        }
+    }
+
+    false
+}
+
+/// Collect definitions, the first of each identifier.
+fn collect_definitions(state: &mut State, node: &Node) {
+    if let Node::Definition(definition) = node {
+        if !state
+            .definitions
+            .iter()
+            .any(|d| d.0 == definition.identifier)
+        {
+            state.definitions.push((
+                definition.identifier.clone(),
+                definition.url.clone(),
+                definition.title.clone(),
+            ));
+        }
+    }
+
+    if let Some(children) = node.children() {
+        let mut index = 0;
+        while index < children.len() {
+            collect_definitions(state, &children[index]);
+            index += 1;
+        }
+    }
+}
+
+/// Collect the places of constructs that are not in the tree: definition
+/// lists, abbreviation definitions, and headings and paragraphs with
+/// attributes.
+///
+/// Constructs in other kept constructs are not included.
+fn collect_kept(events: &[Event]) -> Vec<(usize, usize)> {
+    let mut kept: Vec<(usize, usize)> = vec![];
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            let enter = match event.name {
+                Name::AbbreviationDefinition | Name::DefinitionList => Some(index),
+                Name::Attributes => {
+                    let parent = parent(events, index);
+                    if matches!(events[parent].name, Name::HeadingAtx | Name::Paragraph) {
+                        Some(parent)
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some(enter) = enter {
+                let start = events[enter].point.index;
+                let end = events[exit(events, enter)].point.index;
+
+                if kept.last().map_or(true, |d| start >= d.1) {
+                    kept.push((start, end));
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    kept
+}
+
+/// Get the index of the enter event of the construct that the enter event
+/// at `index` is in.
+fn parent(events: &[Event], mut index: usize) -> usize {
+    let mut balance = 0;
+
+    while index > 0 {
+        index -= 1;
+
+        if events[index].kind == Kind::Exit {
+            balance += 1;
+        } else if balance == 0 {
+            break;
+        } else {
+            balance -= 1;
+        }
+    }
+
+    index
+}
+
+/// Get the index of the exit event that matches the enter event at `index`.
+fn exit(events: &[Event], mut index: usize) -> usize {
+    let mut balance = 0;
+
+    loop {
+        if events[index].kind == Kind::Enter {
+            balance += 1;
+        } else {
+            balance -= 1;
+        }
+
+        if balance == 0 {
+            return index;
+        }
+
+        index += 1;
+    }
+}
+
+/// Take the next construct that is not in the tree, if it ends at or before
+/// `end`.
+fn kept_before(state: &State, end: usize) -> Option<(usize, usize)> {
+    let index = state.kept_index.get();
+    let kept = *state.kept.get(index)?;
+
+    if kept.1 <= end {
+        state.kept_index.set(index + 1);
+        Some(kept)
+    } else {
+        None
+    }
+}
+
+/// Take the next construct that is not in the tree, if `offset` is in it.
+fn kept_at(state: &State, offset: usize) -> Option<(usize, usize)> {
+    let index = state.kept_index.get();
+    let kept = *state.kept.get(index)?;
+
+    if kept.0 <= offset && offset < kept.1 {
+        state.kept_index.set(index + 1);
+        Some(kept)
+    } else {
+        None
+    }
+}
+
+/// Serialize flow content: blocks, with a blank line between them, or with
+/// only a line ending if not `spread`.
+///
+/// `position` is the place of the parent, to keep constructs that are not in
+/// the tree after the last block.
+/// `bullet` is the bullet of the list item that the blocks are in, if any.
+fn flow(
+    state: &State,
+    children: &[Node],
+    position: Option<&Position>,
+    width: usize,
+    spread: bool,
+    bullet: Option<char>,
+) -> String {
+    let mut result = String::new();
+    // Marker of the list directly before.
+    let mut list_before = None;
+    let mut index = 0;
+
+    while index < children.len() {
+        let place = children[index]
+            .position()
+            .map(|d| (d.start.offset, d.end.offset));
+
+        if let Some((start, _)) = place {
+            // Constructs that are not in the tree, before this block.
+            while let Some(range) = kept_before(state, start) {
+                list_before = None;
+                push_block(&mut result, &source_block(state, range), spread);
+            }
+
+            // A block in a construct that is not in the tree: keep the
+            // construct, and skip the other blocks in it.
+            if let Some(range) = kept_at(state, start) {
+                list_before = None;
+                push_block(&mut result, &source_block(state, range), spread);
+                index += 1;
+
+                while index < children.len()
+                    && children[index]
+                        .position()
+                        .map_or(false, |d| d.start.offset < range.1)
+                {
+                    index += 1;
+                }
+
+                continue;
+            }
+        }
+
+        let value = if let Node::List(node) = &children[index] {
+            let marker = list_marker(state, node, list_before, bullet);
+            list_before = Some(marker);
+            Some(list(state, node, width, marker))
+        } else if index == 0
+            && bullet == Some('*')
+            && matches!(children[index], Node::ThematicBreak(_))
+        {
+            // `* ***` would be one thematic break.
+            Some("---".into())
+        } else {
+            let value = block(state, &children[index], width);
+            if value.is_some() {
+                list_before = None;
+            }
+            value
+        };
+
+        // Constructs that are not in the tree, in this block, are kept with
+        // it.
+        if let Some((_, end)) = place {
+            while kept_before(state, end).is_some() {}
+        }
+
+        if let Some(value) = value {
+            push_block(&mut result, &value, spread);
+        }
+
+        index += 1;
+    }
+
+    // Constructs that are not in the tree, after the last block.
+    if let Some(position) = position {
+        while let Some(range) = kept_before(state, position.end.offset) {
+            push_block(&mut result, &source_block(state, range), spread);
+        }
+    }
+
+    result
+}
+
+/// Add a block to serialized flow content.
+fn push_block(result: &mut String, value: &str, spread: bool) {
+    if !result.is_empty() {
+        result.push_str(if spread { "\n\n" } else { "\n" });
+    }
+
+    result.push_str(value);
+}
+
+/// Serialize a block, or `None` if it is dropped.
+fn block(state: &State, node: &Node, width: usize) -> Option<String> {
+    let value = match node {
+        Node::BlockQuote(node) => block_quote(state, node, width),
+        Node::Code(node) => code(node),
+        Node::Definition(node) => {
+            if state.options.inline_links {
+                return None;
+            }
+            definition(node)
+        }
+        Node::FootnoteDefinition(node) => footnote_definition(state, node, width),
+        Node::Heading(node) => heading(state, node),
+        // Line endings at the end are not part of the HTML.
+        Node::Html(node) => node.value.trim_end_matches(['\n', '\r']).into(),
+        Node::Math(node) => {
+            let mut value = String::from("$$");
+            if let Some(meta) = &node.meta {
+                value.push_str(meta);
+            }
+            value.push('\n');
+            if !node.value.is_empty() {
+                value.push_str(&node.value);
+                value.push('\n');
+            }
+            value.push_str("$$");
+            value
+        }
+        Node::Paragraph(node) => phrasing(
+            state,
+            &node.children,
+            width,
+            &state.options.prose_wrap,
+            false,
+        ),
+        Node::Table(node) => table(state, node),
+        Node::ThematicBreak(_) => "***".into(),
+        Node::Toml(node) => format!("+++\n{}\n+++", node.value),
+        Node::Yaml(node) => format!("---\n{}\n---", node.value),
+        // MDX, and anything else, is kept as it is.
+        _ => node.position().map_or(String::new(), |d| {
+            source_block(state, (d.start.offset, d.end.offset))
+        }),
+    };
+
+    Some(value)
+}
+
+/// Get the source at a position.
+fn source(state: &State, position: Option<&Position>) -> String {
+    position.map_or(String::new(), |position| {
+        state.value[position.start.offset..position.end.offset].to_string()
+    })
+}
+
+/// Get the source of a block, without the prefixes of the containers it is
+/// in (such as `> ` or the indent of list items) on lines after the first.
+///
+/// Lazy lines do not have those prefixes, and are kept as they are.
+fn source_block(state: &State, range: (usize, usize)) -> String {
+    let value = &state.value[range.0..range.1];
+    let line_start = state.value[..range.0].rfind('\n').map_or(0, |d| d + 1);
+    let size = state.value[line_start..range.0].chars().count();
+    let mut result = String::new();
+
+    for (index, line) in value.split('\n').enumerate() {
+        if index > 0 {
+            result.push('\n');
+        }
+
+        let mut line = line;
+        let mut stripped = 0;
+
+        while stripped < size && line.starts_with([' ', '\t', '>']) {
+            line = &line[1..];
+            stripped += 1;
+        }
+
+        result.push_str(line);
+    }
+
+    result.trim_end().into()
+}
+
+/// Serialize a block quote.
+fn block_quote(state: &State, node: &BlockQuote, width: usize) -> String {
+    let value = flow(
+        state,
+        &node.children,
+        node.position.as_ref(),
+        width.saturating_sub(2),
+        true,
+        None,
+    );
+    indent(&value, "> ", ">", "> ")
+}
+
+/// Serialize code (flow), always fenced.
+fn code(node: &Code) -> String {
+    let info = match (&node.lang, &node.meta) {
+        (Some(lang), Some(meta)) => format!("{} {}", escape_string(lang), escape_string(meta)),
+        (Some(lang), None) => escape_string(lang),
+        _ => String::new(),
+    };
+    // Grave accents can not be in the info of a grave accent fence.
+    let marker = if info.contains('`') { '~' } else { '`' };
+    let size = sequence_size(&node.value, marker).max(2) + 1;
+    let fence = marker.to_string().repeat(size);
+    let mut value = fence.clone();
+
+    // Info starting with the marker would be part of the fence.
+    if info.starts_with(marker) {
+        value.push(' ');
+    }
+
+    value.push_str(&info);
+    value.push('\n');
+
+    if !node.value.is_empty() {
+        value.push_str(&node.value);
+        value.push('\n');
+    }
+
+    value.push_str(&fence);
+    value
+}
+
+/// Serialize a definition.
+fn definition(node: &Definition) -> String {
+    let mut value = format!(
+        "[{}]: {}",
+        label(node.label.as_ref(), &node.identifier),
+        destination(&node.url)
+    );
+
+    if let Some(title) = &node.title {
+        value.push(' ');
+        value.push_str(&title_string(title));
+    }
+
+    value
+}
+
+/// Serialize a footnote definition.
+fn footnote_definition(state: &State, node: &FootnoteDefinition, width: usize) -> String {
+    let value = flow(
+        state,
+        &node.children,
+        node.position.as_ref(),
+        width.saturating_sub(4),
+        true,
+        None,
+    );
+    let first = format!("[^{}]: ", label(node.label.as_ref(), &node.identifier));
+    let value = indent(&value, &first, "", "    ");
+
+    if value.is_empty() {
+        first.trim_end().to_string()
+    } else {
+        value
+    }
+}
+
+/// Serialize a heading, atx unless it has line endings.
+fn heading(state: &State, node: &Heading) -> String {
+    let mut content = phrasing(state, &node.children, 0, &ProseWrap::Never, false);
+
+    // Hard breaks (and line endings in HTML) can only be in setext headings.
+    if content.contains('\n') && node.depth < 3 {
+        let marker = if node.depth == 1 { "===" } else { "---" };
+        return format!("{}\n{}", content, marker);
+    }
+
+    let mut value = "#".repeat(node.depth as usize);
+
+    // A closing sequence would be removed.
+    if content.ends_with('#') && !content.ends_with("\\#") {
+        content.insert(content.len() - 1, '\\');
+    }
+
+    if !content.is_empty() {
+        value.push(' ');
+        value.push_str(&content);
+    }
+
+    value
+}
+
+/// Get the marker of a list: its delimiter if ordered, or its bullet.
+///
+/// Only markers allowed by [`list_item`][crate::ParseOptions::list_item] are
+/// used.
+/// Two lists with the same marker next to each other would merge: the second
+/// one gets another marker.
+/// A list in a list item also gets another bullet than that item (`bullet`),
+/// as `- - -` would be a thematic break.
+fn list_marker(state: &State, node: &List, before: Option<char>, bullet: Option<char>) -> char {
+    let options = &state.parse.list_item;
+    let markers = if node.ordered {
+        [node.delimiter.unwrap_or('.'), '.', ')', ')']
+    } else {
+        [state.options.bullet, '-', '*', '+']
+    };
+    let mut first = None;
+    let mut other_than_before = None;
+    let mut index = 0;
+
+    while index < markers.len() {
+        let marker = markers[index];
+        let allowed = match marker {
+            '*' => options.bullet_asterisk,
+            '+' => options.bullet_plus,
+            '-' => options.bullet_dash,
+            '.' => options.delimiter_period,
+            ')' => options.delimiter_parenthesis,
+            _ => false,
+        };
+
+        if allowed {
+            if before != Some(marker) {
+                if node.ordered || bullet != Some(marker) {
+                    return marker;
+                }
+
+                if other_than_before.is_none() {
+                    other_than_before = Some(marker);
+                }
+            }
+
+            if first.is_none() {
+                first = Some(marker);
+            }
+        }
+
+        index += 1;
+    }
+
+    // Not enough markers are allowed: prefer keeping lists apart, otherwise
+    // the lists merge.
+    other_than_before.or(first).unwrap_or(markers[0])
+}
+
+/// Serialize a list.
+fn list(state: &State, node: &List, width: usize, marker: char) -> String {
+    let spread = node.spread
+        || node.children.iter().any(|d| match d {
+            Node::ListItem(item) => item.spread,
+            _ => false,
+        });
+    let mut result = String::new();
+    let mut number = node.start.unwrap_or(1);
+    let bullet = if node.ordered { None } else { Some(marker) };
+    let mut index = 0;
+
+    while index < node.children.len() {
+        if let Node::ListItem(item) = &node.children[index] {
+            let marker = if node.ordered {
+                number += 1;
+                format!("{}{}", number - 1, marker)
+            } else {
+                marker.to_string()
+            };
+            let size = marker.len() + 1;
+            let mut content = flow(
+                state,
+                &item.children,
+                item.position.as_ref(),
+                width.saturating_sub(size),
+                spread,
+                bullet,
+            );
+
+            if let Some(checked) = item.checked {
+                let check = if checked { "[x]" } else { "[ ]" };
+                content = if content.is_empty() {
+                    check.into()
+                } else {
+                    format!("{} {}", check, content)
+                };
+            }
+
+            if !result.is_empty() {
+                result.push_str(if spread { "\n\n" } else { "\n" });
+            }
+
+            if content.is_empty() {
+                result.push_str(&marker);
+            } else {
+                result.push_str(&indent(
+                    &content,
+                    &format!("{} ", marker),
+                    "",
+                    &" ".repeat(size),
+                ));
+            }
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+/// Serialize a table (GFM).
+fn table(state: &State, node: &Table) -> String {
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut widths: Vec<usize> = vec![];
+    let mut row_index = 0;
+
+    while row_index < node.children.len() {
+        let mut cells = vec![];
+
+        if let Some(children) = node.children[row_index].children() {
+            let mut cell_index = 0;
+            while cell_index < children.len() {
+                let value = phrasing(
+                    state,
+                    children[cell_index].children().map_or(&[], |d| d),
+                    0,
+                    &ProseWrap::Never,
+                    true,
+                );
+                let size = value.chars().count();
+
+                if cell_index < widths.len() {
+                    widths[cell_index] = widths[cell_index].max(size);
+                } else {
+                    widths.push(size.max(3));
+                }
+
+                cells.push(value);
+                cell_index += 1;
+            }
+        }
+
+        rows.push(cells);
+        row_index += 1;
+    }
+
+    while widths.len() < node.align.len() {
+        widths.push(3);
+    }
+
+    let mut lines = vec![];
+    let mut index = 0;
+
+    while index < rows.len() {
+        lines.push(table_row(&rows[index], &widths, &node.align));
+
+        if index == 0 {
+            let mut delimiters = vec![];
+            let mut column = 0;
+            while column < widths.len() {
+                let size = widths[column];
+                delimiters.push(match node.align.get(column) {
+                    Some(AlignKind::Left) => format!(":{}", "-".repeat(size - 1)),
+                    Some(AlignKind::Right) => format!("{}:", "-".repeat(size - 1)),
+                    Some(AlignKind::Center) => format!(":{}:", "-".repeat(size - 2)),
+                    _ => "-".repeat(size),
+                });
+                column += 1;
+            }
+            lines.push(format!("| {} |", delimiters.join(" | ")));
+        }
+
+        index += 1;
+    }
+
+    lines.join("\n")
+}
+
+/// Serialize a table row (GFM), padding cells to their column.
+fn table_row(cells: &[String], widths: &[usize], align: &[AlignKind]) -> String {
+    let mut values = vec![];
+    let mut column = 0;
+
+    while column < widths.len() {
+        let value = cells.get(column).map_or("", |d| d.as_str());
+        let padding = widths[column] - value.chars().count();
+        let before = match align.get(column) {
+            Some(AlignKind::Right) => padding,
+            Some(AlignKind::Center) => padding / 2,
+            _ => 0,
+        };
+        values.push(format!(
+            "{}{}{}",
+            " ".repeat(before),
+            value,
+            " ".repeat(padding - before)
+        ));
+        column += 1;
+    }
+
+    format!("| {} |", values.join(" | "))
+}
+
+/// Prefix the first line of `value` with `first`, other empty lines with
+/// `empty`, and other lines with `rest`.
+fn indent(value: &str, first: &str, empty: &str, rest: &str) -> String {
+    let mut result = String::new();
+    let mut start = true;
+
+    for line in value.split('\n') {
+        if start {
+            start = false;
+            result.push_str(if line.is_empty() {
+                first.trim_end()
+            } else {
+                first
+            });
+        } else {
+            result.push('\n');
+            result.push_str(if line.is_empty() { empty } else { rest });
+        }
+
+        result.push_str(line);
+    }
+
+    result
+}
+
+/// Serialize phrasing content.
+fn phrasing(
+    state: &State,
+    children: &[Node],
+    width: usize,
+    wrap: &ProseWrap,
+    in_table: bool,
+) -> String {
+    let mut words = Words::new();
+    inline(state, &mut words, children, in_table);
+    words.flush();
+    layout(&words.done, width, wrap)
+}
+
+/// Put words on lines.
+fn layout(words: &[Word], width: usize, wrap: &ProseWrap) -> String {
+    let mut result = String::new();
+    let mut size = 0;
+    let mut index = 0;
+
+    while index < words.len() {
+        let word = &words[index];
+        let count = word.value.chars().count();
+        let line_ending = match (word.gap, wrap) {
+            // Hard breaks can also start a paragraph.
+            (Gap::Hard, _) => {
+                // A backslash would become part of an autolink literal (GFM).
+                result.push_str(if index > 0 && words[index - 1].literal_end {
+                    "  "
+                } else {
+                    "\\"
+                });
+                true
+            }
+            _ if index == 0 => false,
+            (Gap::Soft, ProseWrap::Preserve) => !sticky(word),
+            (_, ProseWrap::Always) => size + 1 + count > width && !sticky(word),
+            _ => false,
+        };
+
+        if line_ending {
+            result.push('\n');
+            size = 0;
+
+            // Indent syntax that would otherwise start a block.
+            if sticky(word) {
+                result.push_str("    ");
+                size = 4;
+            }
+        } else if index != 0 && word.gap != Gap::None {
+            result.push(' ');
+            size += 1;
+        }
+
+        if size == 0 && word.text_start {
+            let value = escape_line_start(&word.value);
+            size += value.chars().count();
+            result.push_str(&value);
+        } else {
+            size += count;
+            result.push_str(&word.value);
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+/// Whether a word starts with syntax that means something else at the start
+/// of a line, so it must not be wrapped there.
+fn sticky(word: &Word) -> bool {
+    !word.text_start
+        && (word.value.starts_with("```")
+            || word.value.starts_with("~~~")
+            || word.value.starts_with("$$")
+            || word.value.starts_with('<'))
+}
+
+/// Escape a word from text at the start of a line, where `#`, `>`, `-`, `+`,
+/// `=`, `~~~`, and `1.` mean something.
+fn escape_line_start(value: &str) -> String {
+    if value.starts_with(['#', '>', '-', '+', '=']) || value.starts_with("~~~") {
+        format!("\\{}", value)
+    } else {
+        let digits = value.bytes().take_while(u8::is_ascii_digit).count();
+
+        if digits > 0 && digits + 1 == value.len() && value.ends_with(['.', ')']) {
+            format!("{}\\{}", &value[..digits], &value[digits..])
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// Serialize inline nodes into words.
+fn inline(state: &State, words: &mut Words, children: &[Node], in_table: bool) {
+    // Whether the previous node is an autolink literal (GFM).
+    let mut literal = false;
+    let mut index = 0;
+
+    while index < children.len() {
+        let next = children.get(index + 1);
+
+        match &children[index] {
+            Node::Break(_) => words.gap(Gap::Hard),
+            Node::Delete(node) => {
+                words.syntax("~~");
+                inline(state, words, &node.children, in_table);
+                words.syntax("~~");
+            }
+            Node::Emphasis(node) => {
+                let marker = if (state.options.emphasis == '_') == words.emphasis {
+                    "*"
+                } else {
+                    "_"
+                };
+                let emphasis = words.emphasis;
+                words.emphasis = !emphasis;
+                words.syntax(marker);
+                inline(state, words, &node.children, in_table);
+                words.syntax(marker);
+                words.emphasis = emphasis;
+            }
+            Node::FootnoteReference(node) => {
+                words.syntax(&format!(
+                    "[^{}]",
+                    label(node.label.as_ref(), &node.identifier)
+                ));
+            }
+            Node::Html(node) => words.syntax(&node.value),
+            Node::Image(node) => image(words, node, in_table),
+            Node::ImageReference(node) => image_reference(state, words, node, in_table),
+            Node::InlineCode(node) => {
+                words.syntax(&code_text(&node.value.replace('\n', " "), '`', in_table));
+            }
+            Node::InlineMath(node) => {
+                words.syntax(&code_text(&node.value.replace('\n', " "), '$', in_table));
+            }
+            Node::Link(node) => {
+                literal = link(state, words, node, in_table);
+                index += 1;
+                continue;
+            }
+            Node::LinkReference(node) => link_reference(state, words, node, next, in_table),
+            Node::Strong(node) => {
+                let marker = if state.options.strong == '_' {
+                    "__"
+                } else {
+                    "**"
+                };
+                words.syntax(marker);
+                inline(state, words, &node.children, in_table);
+                words.syntax(marker);
+            }
+            Node::Text(node) => {
+                let link_next = matches!(next, Some(Node::Link(_) | Node::LinkReference(_)));
+                text(state, words, &node.value, link_next, literal, in_table);
+            }
+            // MDX, and anything else, is kept as it is.
+            node => words.syntax(&source(state, node.position())),
+        }
+
+        literal = false;
+        index += 1;
+    }
+}
+
+/// Serialize text into words, escaping what would otherwise be syntax.
+///
+/// Characters directly after an autolink literal (GFM) are not escaped, as
+/// backslashes would become part of the link.
+fn text(
+    state: &State,
+    words: &mut Words,
+    value: &str,
+    link_next: bool,
+    literal_before: bool,
+    in_table: bool,
+) {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut raw = literal_before;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+        let before = if index > 0 { chars[index - 1] } else { ' ' };
+        let after = chars.get(index + 1).copied().unwrap_or(' ');
+
+        match char {
+            ' ' | '\t' => {
+                raw = false;
+                words.gap(Gap::Space);
+            }
+            '\n' | '\r' => {
+                raw = false;
+                words.gap(Gap::Soft);
+            }
+            '&' if raw => words.literal("&amp;"),
+            '<' if raw => {
+                raw = false;
+                words.text("<");
+            }
+            _ => {
+                let escape = !raw
+                    && match char {
+                        '\\' | '*' | '[' | ']' | '`' => true,
+                        '_' => !(before.is_alphanumeric() && after.is_alphanumeric()),
+                        '~' => state.parse.constructs.gfm_strikethrough,
+                        '$' => state.parse.constructs.math_text,
+                        '|' => in_table || state.parse.constructs.gfm_table,
+                        '<' => after.is_ascii_alphabetic() || matches!(after, '/' | '!' | '?'),
+                        '&' => character_reference_after(&chars[index + 1..]),
+                        '!' => link_next && index + 1 == chars.len(),
+                        _ => false,
+                    };
+
+                if escape {
+                    words.text("\\");
+                    words.syntax(&char.to_string());
+                } else if raw {
+                    words.literal(&char.to_string());
+                } else {
+                    words.text(&char.to_string());
+                }
+            }
+        }
+
+        index += 1;
+    }
+}
+
+/// Whether characters (after `&`) look like a character reference.
+fn character_reference_after(chars: &[char]) -> bool {
+    let size = chars
+        .iter()
+        .take_while(|d| d.is_ascii_alphanumeric() || **d == '#')
+        .count();
+    size > 0 && chars.get(size) == Some(&';')
+}
+
+/// Serialize a link, returning whether it is an autolink literal (GFM).
+fn link(state: &State, words: &mut Words, node: &Link, in_table: bool) -> bool {
+    if node.title.is_none() && node.children.len() == 1 {
+        if let Node::Text(text) = &node.children[0] {
+            // Autolink literal (GFM), such as `www.a.com`: there is no
+            // syntax, so keep it as it is.
+            let constructs = &state.parse.constructs;
+
+            if constructs.gfm_autolink_literal
+                && (constructs.gfm_autolink_literal_email
+                    || constructs.gfm_autolink_literal_protocol
+                    || constructs.gfm_autolink_literal_www)
+                && source(state, node.position.as_ref()) == text.value
+            {
+                words.literal(&text.value);
+                return true;
+            }
+
+            // Autolink, such as `<https://a.com>`.
+            if (text.value == node.url || format!("mailto:{}", text.value) == node.url)
+                && node.url.contains(':')
+                && !node.url.contains(['<', '>', ' ', '\n'])
+            {
+                words.syntax(&format!("<{}>", text.value));
+                return false;
+            }
+        }
+    }
+
+    words.syntax("[");
+    inline(state, words, &node.children, in_table);
+    words.syntax(&resource(&node.url, node.title.as_ref(), in_table));
+    false
+}
+
+/// Serialize a link reference.
+fn link_reference(
+    state: &State,
+    words: &mut Words,
+    node: &LinkReference,
+    next: Option<&Node>,
+    in_table: bool,
+) {
+    words.syntax("[");
+    inline(state, words, &node.children, in_table);
+
+    if let Some((_, url, title)) = definition_of(state, &node.identifier) {
+        words.syntax(&resource(url, title.as_ref(), in_table));
+    } else {
+        words.syntax(&reference(
+            node.reference_kind,
+            &label(node.label.as_ref(), &node.identifier),
+            next,
+        ));
+    }
+}
+
+/// Serialize an image.
+fn image(words: &mut Words, node: &Image, in_table: bool) {
+    words.syntax(&format!(
+        "![{}{}",
+        escape_alt(&node.alt, in_table),
+        resource(&node.url, node.title.as_ref(), in_table)
+    ));
+}
+
+/// Serialize an image reference.
+fn image_reference(state: &State, words: &mut Words, node: &ImageReference, in_table: bool) {
+    let end = if let Some((_, url, title)) = definition_of(state, &node.identifier) {
+        resource(url, title.as_ref(), in_table)
+    } else {
+        reference(
+            node.reference_kind,
+            &label(node.label.as_ref(), &node.identifier),
+            None,
+        )
+    };
+    words.syntax(&format!("![{}{}", escape_alt(&node.alt, in_table), end));
+}
+
+/// Get the definition of an identifier, when references are turned into
+/// inline links.
+fn definition_of<'a>(
+    state: &'a State,
+    identifier: &str,
+) -> Option<&'a (String, String, Option<String>)> {
+    state.definitions.iter().find(|d| d.0 == identifier)
+}
+
+/// Serialize a label (of a definition or reference).
+///
+/// Labels are decoded, so brackets, backslashes, and character references
+/// in them are escaped again.
+/// Identifiers are not decoded, so they are used as they are.
+fn label(label: Option<&String>, identifier: &str) -> String {
+    label.map_or_else(
+        || identifier.to_string(),
+        |label| escape_string(label).replace('[', "\\[").replace(']', "\\]"),
+    )
+}
+
+/// Serialize the end of a reference: `][b]`, `][]`, or `]`.
+fn reference(kind: ReferenceKind, label: &str, next: Option<&Node>) -> String {
+    match kind {
+        ReferenceKind::Full => format!("][{}]", label),
+        ReferenceKind::Collapsed => "][]".into(),
+        ReferenceKind::Shortcut => {
+            // A shortcut followed by `(` would be a resource.
+            if let Some(Node::Text(text)) = next {
+                if text.value.starts_with('(') {
+                    return "][]".into();
+                }
+            }
+
+            "]".into()
+        }
+    }
+}
+
+/// Serialize the end of a resource: `](b "c")`.
+fn resource(url: &str, title: Option<&String>, in_table: bool) -> String {
+    let mut value = format!("]({}", destination(url));
+
+    if let Some(title) = title {
+        value.push(' ');
+        value.push_str(&title_string(title));
+    }
+
+    value.push(')');
+
+    if in_table {
+        value = value.replace('|', "\\|");
+    }
+
+    value
+}
+
+/// Serialize a destination, in `<` and `>` if needed.
+fn destination(url: &str) -> String {
+    let mut balance = 0;
+    let mut balanced = true;
+
+    for char in url.chars() {
+        if char == '(' {
+            balance += 1;
+        } else if char == ')' {
+            if balance == 0 {
+                balanced = false;
+            } else {
+                balance -= 1;
+            }
+        }
+    }
+
+    if url.is_empty()
+        || balance != 0
+        || !balanced
+        || url.chars().any(|d| d.is_whitespace() || d.is_control())
+        || url.starts_with('<')
+    {
+        format!(
+            "<{}>",
+            escape_string(url).replace('<', "\\<").replace('>', "\\>")
+        )
+    } else {
+        escape_string(url)
+    }
+}
+
+/// Serialize a title, in double quotes.
+fn title_string(title: &str) -> String {
+    format!("\"{}\"", escape_string(title).replace('"', "\\\""))
+}
+
+/// Escape backslashes and character references in a string, such as a
+/// destination, title, or info.
+fn escape_string(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+
+        if char == '\\' || (char == '&' && character_reference_after(&chars[index + 1..])) {
+            result.push('\\');
+        }
+
+        result.push(char);
+        index += 1;
+    }
+
+    result
+}
+
+/// Escape alternative text of an image.
+fn escape_alt(value: &str, in_table: bool) -> String {
+    let mut result = String::new();
+
+    for char in value.chars() {
+        if matches!(char, '\\' | '[' | ']') || (in_table && char == '|') {
+            result.push('\\');
+        }
+
+        result.push(if char == '\n' { ' ' } else { char });
+    }
+
+    result
+}
+
+/// Serialize code (text) or math (text), with a sequence of `marker`s that
+/// does not occur in `value`.
+fn code_text(value: &str, marker: char, in_table: bool) -> String {
+    let mut size = 1;
+
+    while has_sequence(value, marker, size) {
+        size += 1;
+    }
+
+    let sequence = marker.to_string().repeat(size);
+    let pad = value.starts_with(marker)
+        || value.ends_with(marker)
+        || (value.starts_with(' ') && value.ends_with(' ') && value.trim() != "");
+    let value = if in_table {
+        value.replace('|', "\\|")
+    } else {
+        value.to_string()
+    };
+
+    if pad {
+        format!("{} {} {}", sequence, value, sequence)
+    } else {
+        format!("{}{}{}", sequence, value, sequence)
+    }
+}
+
+/// Whether `value` contains a sequence of exactly `size` `marker`s.
+fn has_sequence(value: &str, marker: char, size: usize) -> bool {
+    let mut count = 0;
+
+    for char in value.chars() {
+        if char == marker {
+            count += 1;
+        } else {
+            if count == size {
+                return true;
+            }
+            count = 0;
+        }
+    }
+
+    count == size
+}
+
+/// Size of the longest sequence of `marker`s at the start of a line (after
+/// whitespace).
+fn sequence_size(value: &str, marker: char) -> usize {
+    let mut max = 0;
+
+    for line in value.split('\n') {
+        let size = line
+            .trim_start()
+            .chars()
+            .take_while(|d| *d == marker)
+            .count();
+        max = max.max(size);
+    }
+
+    max
+}
@@ -3,23 +3,27 @@
 use crate::event::{Event, Kind, Name};
 use crate::mdast::{
     AttributeContent, AttributeValue, AttributeValueExpression, BlockQuote, Break, Code,
-    Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference, Heading, Html, Image,
-    ImageReference, InlineCode, InlineMath, Link, LinkReference, List, ListItem, Math,
-    MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement, MdxJsxTextElement, MdxTextExpression,
-    MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong, Table, TableCell, TableRow, Text,
-    ThematicBreak, Toml, Yaml,
+    ContainerDirective, Definition, Delete, Emphasis, FootnoteDefinition, FootnoteReference,
+    Heading, Highlight, Html, Image, ImageReference, InlineCode, InlineMath, LeafDirective, Link,
+    LinkReference, List, ListItem, Math, MdxFlowExpression, MdxJsxAttribute, MdxJsxFlowElement,
+    MdxJsxTextElement, MdxTextExpression, MdxjsEsm, Node, Paragraph, ReferenceKind, Root, Strong,
+    Subscript, Superscript, Table, TableCell, TableRow, Text, TextDirective, ThematicBreak, Toml,
+    Yaml,
 };
 use crate::message;
+use crate::parser::ParseState;
 use crate::unist::{Point, Position};
 use crate::util::{
     character_reference::{
         decode as decode_character_reference, parse as parse_character_reference,
     },
-    infer::{gfm_table_align, list_item_loose, list_loose},
+    directive::add_attribute,
+    infer::{gfm_table_align, list_info, list_item_loose, list_loose},
     mdx_collect::{collect, Result as CollectResult},
     normalize_identifier::normalize_identifier,
     slice::{Position as SlicePosition, Slice},
 };
+use crate::ParseOptions;
 use alloc::{
     boxed::Box,
     format,
@@ -27,7 +31,7 @@ use alloc::{
     vec,
     vec::Vec,
 };
-use core::str;
+use core::{convert::TryFrom, str};
 
 /// A reference to something.
 #[derive(Debug)]
@@ -90,6 +94,10 @@ struct CompileContext<'a> {
     events: &'a [Event],
     /// List of bytes.
     bytes: &'a [u8],
+    /// Size of a tab stop.
+    tab_size: usize,
+    /// Configuration.
+    options: &'a ParseOptions,
     // Fields used by handlers to track the things they need to track to
     // compile markdown.
     character_reference_marker: u8,
@@ -109,7 +117,7 @@ struct CompileContext<'a> {
 
 impl<'a> CompileContext<'a> {
     /// Create a new compile context.
-    fn new(events: &'a [Event], bytes: &'a [u8]) -> CompileContext<'a> {
+    fn new(events: &'a [Event], parse_state: &ParseState<'a>) -> CompileContext<'a> {
         let tree = Node::Root(Root {
             children: vec![],
             position: Some(Position {
@@ -128,7 +136,9 @@ impl<'a> CompileContext<'a> {
 
         CompileContext {
             events,
-            bytes,
+            bytes: parse_state.bytes,
+            tab_size: parse_state.tab_size,
+            options: parse_state.options,
             character_reference_marker: 0,
             gfm_table_inside: false,
             hard_break_after: false,
@@ -225,8 +235,8 @@ impl<'a> CompileContext<'a> {
 }
 
 /// Turn events and bytes into a syntax tree.
-pub fn compile(events: &[Event], bytes: &[u8]) -> Result<Node, message::Message> {
-    let mut context = CompileContext::new(events, bytes);
+pub fn compile(events: &[Event], parse_state: &ParseState) -> Result<Node, message::Message> {
+    let mut context = CompileContext::new(events, parse_state);
 
     let mut index = 0;
     while index < events.len() {
@@ -268,17 +278,22 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::CodeFlowChunk
         | Name::CodeTextData
         | Name::Data
+        | Name::Emoji(_)
         | Name::FrontmatterChunk
         | Name::HtmlFlowData
         | Name::HtmlTextData
         | Name::MathFlowChunk
         | Name::MathTextData
-        | Name::MdxJsxTagAttributeValueLiteralValue => on_enter_data(context),
-        Name::CodeFencedFenceInfo
+        | Name::MdxJsxTagAttributeValueLiteralValue
+        | Name::TextExtension(_) => on_enter_data(context),
+        // Abbreviations are not in mdast: drop definitions.
+        Name::AbbreviationDefinitionTitle
+        | Name::CodeFencedFenceInfo
         | Name::CodeFencedFenceMeta
         | Name::DefinitionDestinationString
         | Name::DefinitionLabelString
         | Name::DefinitionTitleString
+        | Name::DirectiveAttributes
         | Name::GfmFootnoteDefinitionLabelString
         | Name::LabelText
         | Name::MathFlowFenceMeta
@@ -292,6 +307,10 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::CodeIndented => on_enter_code_indented(context),
         Name::CodeText => on_enter_code_text(context),
         Name::Definition => on_enter_definition(context),
+        Name::DirectiveContainer | Name::DirectiveLeaf | Name::DirectiveText => {
+            on_enter_directive(context);
+        }
+        Name::DirectiveLabel => on_enter_directive_label(context),
         Name::Emphasis => on_enter_emphasis(context),
         Name::Frontmatter => on_enter_frontmatter(context),
         Name::GfmAutolinkLiteralEmail
@@ -307,6 +326,7 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::GfmTableCell => on_enter_gfm_table_cell(context),
         Name::HardBreakEscape | Name::HardBreakTrailing => on_enter_hard_break(context),
         Name::HeadingAtx | Name::HeadingSetext => on_enter_heading(context),
+        Name::Highlight => on_enter_highlight(context),
         Name::HtmlFlow | Name::HtmlText => on_enter_html(context),
         Name::Image => on_enter_image(context),
         Name::Link => on_enter_link(context),
@@ -325,10 +345,13 @@ fn enter(context: &mut CompileContext) -> Result<(), message::Message> {
             on_enter_mdx_jsx_tag_attribute_value_expression(context);
         }
         Name::MdxJsxTagSelfClosingMarker => on_enter_mdx_jsx_tag_self_closing_marker(context)?,
-        Name::Paragraph => on_enter_paragraph(context),
+        // Definition lists are not in mdast: use paragraphs for terms.
+        Name::DefinitionListTerm | Name::Paragraph => on_enter_paragraph(context),
         Name::Reference => on_enter_reference(context),
         Name::Resource => on_enter_resource(context),
         Name::Strong => on_enter_strong(context),
+        Name::Subscript => on_enter_subscript(context),
+        Name::Superscript => on_enter_superscript(context),
         Name::ThematicBreak => on_enter_thematic_break(context),
         _ => {}
     }
@@ -343,16 +366,23 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::BlockQuote
         | Name::CharacterReference
         | Name::Definition
+        | Name::DefinitionListTerm
+        | Name::DirectiveContainer
+        | Name::DirectiveLeaf
+        | Name::DirectiveText
         | Name::Emphasis
         | Name::GfmFootnoteDefinition
         | Name::GfmStrikethrough
         | Name::GfmTableRow
         | Name::GfmTableCell
         | Name::HeadingAtx
+        | Name::Highlight
         | Name::ListOrdered
         | Name::ListUnordered
         | Name::Paragraph
         | Name::Strong
+        | Name::Subscript
+        | Name::Superscript
         | Name::ThematicBreak => {
             on_exit(context)?;
         }
@@ -365,10 +395,13 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         | Name::HtmlTextData
         | Name::MathFlowChunk
         | Name::MathTextData
-        | Name::MdxJsxTagAttributeValueLiteralValue => {
+        | Name::MdxJsxTagAttributeValueLiteralValue
+        | Name::TextExtension(_) => {
             on_exit_data(context)?;
         }
-        Name::MdxJsxTagAttributeExpression | Name::MdxJsxTagAttributeValueExpression => {
+        Name::AbbreviationDefinitionTitle
+        | Name::MdxJsxTagAttributeExpression
+        | Name::MdxJsxTagAttributeValueExpression => {
             on_exit_drop(context);
         }
         Name::AutolinkProtocol => on_exit_autolink_protocol(context)?,
@@ -381,6 +414,7 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
             on_exit_character_reference_marker_hexadecimal(context);
         }
         Name::CharacterReferenceValue => on_exit_character_reference_value(context),
+        Name::Emoji(_) => on_exit_emoji(context)?,
         Name::CodeFencedFenceInfo => on_exit_code_fenced_fence_info(context),
         Name::CodeFencedFenceMeta | Name::MathFlowFenceMeta => on_exit_raw_flow_fence_meta(context),
         Name::CodeFencedFence | Name::MathFlowFence => on_exit_raw_flow_fence(context),
@@ -392,6 +426,9 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
             on_exit_definition_id(context);
         }
         Name::DefinitionTitleString => on_exit_definition_title_string(context),
+        Name::DirectiveAttributes => on_exit_directive_attributes(context),
+        Name::DirectiveLabel => on_exit_directive_label(context)?,
+        Name::DirectiveName => on_exit_directive_name(context),
         Name::Frontmatter => on_exit_frontmatter(context)?,
         Name::GfmAutolinkLiteralEmail
         | Name::GfmAutolinkLiteralMailto
@@ -409,10 +446,9 @@ fn exit(context: &mut CompileContext) -> Result<(), message::Message> {
         Name::HeadingSetextUnderlineSequence => on_exit_heading_setext_underline_sequence(context),
         Name::HeadingSetextText => on_exit_heading_setext_text(context),
         Name::HtmlFlow | Name::HtmlText => on_exit_html(context)?,
-        Name::LabelText => on_exit_label_text(context),
+        Name::LabelText => on_exit_label_text(context)?,
         Name::LineEnding => on_exit_line_ending(context)?,
         Name::ListItem => on_exit_list_item(context)?,
-        Name::ListItemValue => on_exit_list_item_value(context),
         Name::MdxEsm | Name::MdxFlowExpression | Name::MdxTextExpression => {
             on_exit_mdx_esm_or_expression(context)?;
         }
@@ -515,6 +551,7 @@ fn on_enter_mdx_esm(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.tab_size,
         context.index,
         &[Name::MdxEsmData, Name::LineEnding],
         &[Name::MdxEsm],
@@ -532,6 +569,7 @@ fn on_enter_mdx_flow_expression(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxFlowExpression],
@@ -549,6 +587,7 @@ fn on_enter_mdx_text_expression(context: &mut CompileContext) {
     let result = collect(
         context.events,
         context.bytes,
+        context.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxTextExpression],
@@ -572,6 +611,44 @@ fn on_enter_definition(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:{[`DirectiveContainer`][Name::DirectiveContainer],[`DirectiveLeaf`][Name::DirectiveLeaf],[`DirectiveText`][Name::DirectiveText]}.
+fn on_enter_directive(context: &mut CompileContext) {
+    let children = vec![];
+    let name = String::new();
+    let attributes = vec![];
+    let position = None;
+
+    context.tail_push(match context.events[context.index].name {
+        Name::DirectiveContainer => Node::ContainerDirective(ContainerDirective {
+            children,
+            position,
+            name,
+            attributes,
+        }),
+        Name::DirectiveLeaf => Node::LeafDirective(LeafDirective {
+            children,
+            position,
+            name,
+            attributes,
+        }),
+        _ => Node::TextDirective(TextDirective {
+            children,
+            position,
+            name,
+            attributes,
+        }),
+    });
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_enter_directive_label(context: &mut CompileContext) {
+    // The label of a container directive is a paragraph in it, that of leaf
+    // and text directives is their content.
+    if let Node::ContainerDirective(_) = context.tail_mut() {
+        on_enter_paragraph(context);
+    }
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`Emphasis`][Name::Emphasis].
 fn on_enter_emphasis(context: &mut CompileContext) {
     context.tail_push(Node::Emphasis(Emphasis {
@@ -695,6 +772,22 @@ fn on_enter_strong(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Subscript`][Name::Subscript].
+fn on_enter_subscript(context: &mut CompileContext) {
+    context.tail_push(Node::Subscript(Subscript {
+        children: vec![],
+        position: None,
+    }));
+}
+
+/// Handle [`Enter`][Kind::Enter]:[`Superscript`][Name::Superscript].
+fn on_enter_superscript(context: &mut CompileContext) {
+    context.tail_push(Node::Superscript(Superscript {
+        children: vec![],
+        position: None,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:[`ThematicBreak`][Name::ThematicBreak].
 fn on_enter_thematic_break(context: &mut CompileContext) {
     context.tail_push(Node::ThematicBreak(ThematicBreak { position: None }));
@@ -709,6 +802,14 @@ fn on_enter_heading(context: &mut CompileContext) {
     }));
 }
 
+/// Handle [`Enter`][Kind::Enter]:[`Highlight`][Name::Highlight].
+fn on_enter_highlight(context: &mut CompileContext) {
+    context.tail_push(Node::Highlight(Highlight {
+        children: vec![],
+        position: None,
+    }));
+}
+
 /// Handle [`Enter`][Kind::Enter]:{[`HtmlFlow`][Name::HtmlFlow],[`HtmlText`][Name::HtmlText]}.
 fn on_enter_html(context: &mut CompileContext) {
     context.tail_push(Node::Html(Html {
@@ -742,13 +843,18 @@ fn on_enter_link(context: &mut CompileContext) {
 
 /// Handle [`Enter`][Kind::Enter]:{[`ListOrdered`][Name::ListOrdered],[`ListUnordered`][Name::ListUnordered]}.
 fn on_enter_list(context: &mut CompileContext) {
-    let ordered = context.events[context.index].name == Name::ListOrdered;
+    let info = list_info(context.bytes, context.events, context.index);
     let spread = list_loose(context.events, context.index, false);
 
     context.tail_push(Node::List(List {
-        ordered,
+        ordered: info.ordered,
         spread,
-        start: None,
+        start: info.start,
+        delimiter: if info.ordered {
+            Some(info.marker)
+        } else {
+            None
+        },
         children: vec![],
         position: None,
     }));
@@ -757,10 +863,18 @@ fn on_enter_list(context: &mut CompileContext) {
 /// Handle [`Enter`][Kind::Enter]:[`ListItem`][Name::ListItem].
 fn on_enter_list_item(context: &mut CompileContext) {
     let spread = list_item_loose(context.events, context.index);
+    let number = if let Node::List(list) = context.tail_mut() {
+        list.start.map(|start| {
+            start.saturating_add(u32::try_from(list.children.len()).unwrap_or(u32::MAX))
+        })
+    } else {
+        unreachable!("expected list on stack")
+    };
 
     context.tail_push(Node::ListItem(ListItem {
         spread,
         checked: None,
+        number,
         children: vec![],
         position: None,
     }));
@@ -849,6 +963,7 @@ fn on_enter_mdx_jsx_tag_attribute_expression(
     let CollectResult { value, stops } = collect(
         context.events,
         context.bytes,
+        context.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeExpression],
@@ -870,6 +985,7 @@ fn on_enter_mdx_jsx_tag_attribute_value_expression(context: &mut CompileContext)
     let CollectResult { value, stops } = collect(
         context.events,
         context.bytes,
+        context.tab_size,
         context.index,
         &[Name::MdxExpressionData, Name::LineEnding],
         &[Name::MdxJsxTagAttributeValueExpression],
@@ -912,7 +1028,7 @@ fn on_enter_mdx_jsx_tag_self_closing_marker(
     }
 }
 
-/// Handle [`Enter`][Kind::Enter]:[`Paragraph`][Name::Paragraph].
+/// Handle [`Enter`][Kind::Enter]:{[`DefinitionListTerm`][Name::DefinitionListTerm],[`Paragraph`][Name::Paragraph]}.
 fn on_enter_paragraph(context: &mut CompileContext) {
     context.tail_push(Node::Paragraph(Paragraph {
         children: vec![],
@@ -932,6 +1048,7 @@ fn on_exit_autolink_protocol(context: &mut CompileContext) -> Result<(), message
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str(value.as_str());
@@ -947,6 +1064,7 @@ fn on_exit_autolink_email(context: &mut CompileContext) -> Result<(), message::M
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Link(link) = context.tail_mut() {
         link.url.push_str("mailto:");
@@ -977,6 +1095,7 @@ fn on_exit_character_reference_value(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value =
         decode_character_reference(slice.as_str(), context.character_reference_marker, true)
@@ -1081,7 +1200,8 @@ fn on_exit_raw_text(context: &mut CompileContext) -> Result<(), message::Message
     }
 
     let value_bytes = value.as_bytes();
-    if value.len() > 2
+    if context.options.code_text_strip_space
+        && value.len() > 2
         && value_bytes[0] == b' '
         && value_bytes[value.len() - 1] == b' '
         && !value_bytes.iter().all(|b| *b == b' ')
@@ -1105,6 +1225,7 @@ fn on_exit_data(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Node::Text(text) = context.tail_mut() {
         text.value.push_str(value.as_str());
@@ -1131,6 +1252,7 @@ fn on_exit_definition_id(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1157,11 +1279,107 @@ fn on_exit_definition_title_string(context: &mut CompileContext) {
     }
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveAttributes`][Name::DirectiveAttributes].
+fn on_exit_directive_attributes(context: &mut CompileContext) {
+    // Drop whitespace and line endings.
+    context.resume();
+
+    let mut index = context.index - 1;
+
+    while context.events[index].name != Name::DirectiveAttributes {
+        index -= 1;
+    }
+
+    let mut attributes = vec![];
+    let mut key = String::new();
+    let mut value = String::new();
+
+    while index < context.index {
+        let event = &context.events[index];
+
+        if event.kind == Kind::Exit {
+            match event.name {
+                Name::DirectiveAttribute => {
+                    add_attribute(
+                        &mut attributes,
+                        core::mem::take(&mut key),
+                        core::mem::take(&mut value),
+                    );
+                }
+                Name::DirectiveAttributeClassMarker => key = "class".into(),
+                Name::DirectiveAttributeIdMarker => key = "id".into(),
+                Name::DirectiveAttributeName | Name::DirectiveAttributeValue => {
+                    let slice = Slice::from_position(
+                        context.bytes,
+                        &SlicePosition::from_exit_event(context.events, index),
+                        context.tab_size,
+                    );
+
+                    if event.name == Name::DirectiveAttributeName {
+                        key = slice.serialize();
+                    } else {
+                        value = parse_character_reference(slice.as_str(), true);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        index += 1;
+    }
+
+    match context.tail_mut() {
+        Node::ContainerDirective(node) => node.attributes = attributes,
+        Node::LeafDirective(node) => node.attributes = attributes,
+        Node::TextDirective(node) => node.attributes = attributes,
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveLabel`][Name::DirectiveLabel].
+fn on_exit_directive_label(context: &mut CompileContext) -> Result<(), message::Message> {
+    if let Node::Paragraph(_) = context.tail_mut() {
+        on_exit(context)?;
+    }
+
+    Ok(())
+}
+
+/// Handle [`Exit`][Kind::Exit]:[`DirectiveName`][Name::DirectiveName].
+fn on_exit_directive_name(context: &mut CompileContext) {
+    let name = Slice::from_position(
+        context.bytes,
+        &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
+    )
+    .serialize();
+
+    match context.tail_mut() {
+        Node::ContainerDirective(node) => node.name = name,
+        Node::LeafDirective(node) => node.name = name,
+        Node::TextDirective(node) => node.name = name,
+        _ => unreachable!("expected directive on stack"),
+    }
+}
+
 /// Handle [`Exit`][Kind::Exit]:*, by dropping the current buffer.
 fn on_exit_drop(context: &mut CompileContext) {
     context.resume();
 }
 
+/// Handle [`Exit`][Kind::Exit]:[`Emoji`][Name::Emoji].
+fn on_exit_emoji(context: &mut CompileContext) -> Result<(), message::Message> {
+    if let (Name::Emoji(value), Node::Text(node)) =
+        (&context.events[context.index].name, context.tail_mut())
+    {
+        node.value.push_str(value);
+    } else {
+        unreachable!("expected emoji and text on stack");
+    }
+    on_exit(context)?;
+    Ok(())
+}
+
 /// Handle [`Exit`][Kind::Exit]:[`Frontmatter`][Name::Frontmatter].
 fn on_exit_frontmatter(context: &mut CompileContext) -> Result<(), message::Message> {
     let value = trim_eol(context.resume().to_string(), true, true);
@@ -1183,6 +1401,7 @@ fn on_exit_gfm_autolink_literal(context: &mut CompileContext) -> Result<(), mess
     let value = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
 
     let prefix = match &context.events[context.index].name {
@@ -1237,6 +1456,7 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
 
     if let Node::Heading(node) = context.tail_mut() {
@@ -1245,9 +1465,9 @@ fn on_exit_heading_atx_sequence(context: &mut CompileContext) {
             let depth = slice.len() as u8;
             node.depth = depth;
         }
-    } else {
-        unreachable!("expected heading on stack");
     }
+    // Otherwise, there is an unclosed JSX tag (`# a <b> #`), which is an
+    // error when exiting the heading.
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`HeadingSetext`][Name::HeadingSetext].
@@ -1270,19 +1490,27 @@ fn on_exit_heading_setext_underline_sequence(context: &mut CompileContext) {
 
     if let Node::Heading(node) = context.tail_mut() {
         node.depth = depth;
-    } else {
-        unreachable!("expected heading on stack");
     }
+    // Otherwise, there is an unclosed JSX tag (`a <b>\n=`), which is an
+    // error when exiting the heading.
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`LabelText`][Name::LabelText].
-fn on_exit_label_text(context: &mut CompileContext) {
+fn on_exit_label_text(context: &mut CompileContext) -> Result<(), message::Message> {
+    // An unclosed JSX tag in the label (`[<a>b](c)`).
+    let (_, _, event_stack) = context.trees.last().expect("Cannot get tail w/o tree");
+    if let Some(left_index) = event_stack.last() {
+        let events = context.events;
+        on_mismatch_error(context, Some(&events[context.index]), &events[*left_index])?;
+    }
+
     let mut fragment = context.resume();
     let label = fragment.to_string();
     let children = fragment.children_mut().unwrap().split_off(0);
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
 
@@ -1299,6 +1527,8 @@ fn on_exit_label_text(context: &mut CompileContext) {
         Node::FootnoteReference(_) => {}
         _ => unreachable!("expected footnote refereence, image, or link on stack"),
     }
+
+    Ok(())
 }
 
 /// Handle [`Exit`][Kind::Exit]:[`LineEnding`][Name::LineEnding].
@@ -1326,6 +1556,10 @@ fn on_exit_line_ending(context: &mut CompileContext) -> Result<(), message::Mess
             | Node::Paragraph(_)
             | Node::Strong(_)
             | Node::Delete(_)
+            | Node::Highlight(_)
+            | Node::Subscript(_)
+            | Node::Superscript(_)
+            | Node::TextDirective(_)
     ) {
         context.index -= 1;
         on_enter_data(context);
@@ -1447,26 +1681,6 @@ fn on_exit_list_item(context: &mut CompileContext) -> Result<(), message::Messag
     Ok(())
 }
 
-/// Handle [`Exit`][Kind::Exit]:[`ListItemValue`][Name::ListItemValue].
-fn on_exit_list_item_value(context: &mut CompileContext) {
-    let start = Slice::from_position(
-        context.bytes,
-        &SlicePosition::from_exit_event(context.events, context.index),
-    )
-    .as_str()
-    .parse()
-    .expect("expected list value up to u8");
-
-    if let Node::List(node) = context.tail_penultimate_mut() {
-        debug_assert!(node.ordered, "expected list to be ordered");
-        if node.start.is_none() {
-            node.start = Some(start);
-        }
-    } else {
-        unreachable!("expected list on stack");
-    }
-}
-
 /// Handle [`Exit`][Kind::Exit]:{[`MdxJsxFlowTag`][Name::MdxJsxFlowTag],[`MdxJsxTextTag`][Name::MdxJsxTextTag]}.
 fn on_exit_mdx_jsx_tag(context: &mut CompileContext) -> Result<(), message::Message> {
     let mut tag = context.jsx_tag.as_ref().expect("expected tag").clone();
@@ -1552,6 +1766,7 @@ fn on_exit_mdx_jsx_tag_name_primary(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.serialize();
     context.jsx_tag.as_mut().expect("expected tag").name = Some(value);
@@ -1562,6 +1777,7 @@ fn on_exit_mdx_jsx_tag_name_member(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1579,6 +1795,7 @@ fn on_exit_mdx_jsx_tag_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let name = context
         .jsx_tag
@@ -1603,6 +1820,7 @@ fn on_exit_mdx_jsx_tag_attribute_primary_name(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let value = slice.serialize();
 
@@ -1624,6 +1842,7 @@ fn on_exit_mdx_jsx_tag_attribute_name_local(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     if let Some(AttributeContent::Property(attribute)) = context
         .jsx_tag
@@ -1652,6 +1871,7 @@ fn on_exit_mdx_jsx_tag_attribute_value_literal(context: &mut CompileContext) {
     {
         node.value = Some(AttributeValue::Literal(parse_character_reference(
             &value.to_string(),
+            false,
         )));
     } else {
         unreachable!("expected property")
@@ -1669,6 +1889,7 @@ fn on_exit_reference_string(context: &mut CompileContext) {
     let slice = Slice::from_position(
         context.bytes,
         &SlicePosition::from_exit_event(context.events, context.index),
+        context.tab_size,
     );
     let identifier = normalize_identifier(slice.as_str()).to_lowercase();
     let reference = context
@@ -18,7 +18,7 @@ use crate::subtokenize::Subresult;
 #[cfg(feature = "log")]
 use crate::util::char::format_byte_opt;
 
-use crate::util::{constant::TAB_SIZE, edit_map::EditMap};
+use crate::util::edit_map::EditMap;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 
 /// Containers.
@@ -36,6 +36,15 @@ pub enum Container {
     ListItem,
     /// [GFM: Footnote definition][crate::construct::gfm_footnote_definition].
     GfmFootnoteDefinition,
+    /// [Directive (container)][crate::construct::directive_container].
+    ///
+    /// Its size is the number of markers in the opening fence sequence, which
+    /// is set to `0` when it is closed by a closing fence.
+    Directive,
+    /// [Definition list][crate::construct::definition_list] description.
+    ///
+    /// Continued like a list item.
+    DefinitionListDescription,
 }
 
 /// Info used to tokenize a container.
@@ -154,6 +163,11 @@ struct Attempt {
     /// `nok` of [`State::Nok`][], because that means it is used in *another*
     /// attempt, which will receive that `Nok`, and has to handle it.
     progress: Option<Progress>,
+    /// Construct that is attempted, where it started, and when.
+    ///
+    /// Set when the first state after the attempt is called.
+    #[cfg(feature = "profile")]
+    profile: Option<(crate::state::Name, usize, std::time::Instant)>,
 }
 
 /// The internal state of a tokenizer.
@@ -231,6 +245,11 @@ pub struct TokenizeState<'a> {
     pub definitions: Vec<String>,
     /// List of defined GFM footnote definition identifiers.
     pub gfm_footnote_definitions: Vec<String>,
+    /// List of defined abbreviation labels.
+    pub abbreviations: Vec<String>,
+    /// List of warnings, such as for references that do not match a
+    /// definition.
+    pub messages: Vec<message::Message>,
 
     // Last error message provided at an EOF of an expression.
     pub mdx_last_parse_error: Option<(String, String, String)>,
@@ -242,7 +261,7 @@ pub struct TokenizeState<'a> {
     /// Secondary marker.
     pub marker_b: u8,
     /// Several markers.
-    pub markers: &'static [u8],
+    pub markers: &'a [u8],
     /// Whether something was seen.
     pub seen: bool,
     /// Size.
@@ -351,6 +370,8 @@ impl<'a> Tokenizer<'a> {
                 document_at_first_paragraph_of_list_item: false,
                 definitions: vec![],
                 gfm_footnote_definitions: vec![],
+                abbreviations: vec![],
+                messages: vec![],
                 mdx_last_parse_error: None,
                 end: 0,
                 label_starts: vec![],
@@ -390,17 +411,26 @@ impl<'a> Tokenizer<'a> {
     }
 
     /// Register a resolver.
+    ///
+    /// Resolvers are kept sorted, so they run in the order in which their
+    /// names are defined in [`ResolveName`][], instead of the order in which
+    /// they are registered.
     pub fn register_resolver(&mut self, name: ResolveName) {
-        if !self.resolvers.contains(&name) {
-            self.resolvers.push(name);
-        }
-    }
+        let mut index = 0;
+
+        while index < self.resolvers.len() {
+            if self.resolvers[index] == name {
+                return;
+            }
+
+            if self.resolvers[index] > name {
+                break;
+            }
 
-    /// Register a resolver, before others.
-    pub fn register_resolver_before(&mut self, name: ResolveName) {
-        if !self.resolvers.contains(&name) {
-            self.resolvers.insert(0, name);
+            index += 1;
         }
+
+        self.resolvers.insert(index, name);
     }
 
     /// Define a jump between two places.
@@ -415,13 +445,7 @@ impl<'a> Tokenizer<'a> {
         log::trace!("position: define skip: {:?} -> ({:?})", point.line, info);
 
         let at = point.line - self.first_line;
-
-        if at >= self.column_start.len() {
-            self.column_start.push(info);
-        } else {
-            self.column_start[at] = info;
-        }
-
+        set_column_start(self, at, info, true);
         self.account_for_potential_skip();
     }
 
@@ -459,7 +483,11 @@ impl<'a> Tokenizer<'a> {
 
     /// Move to the next (virtual) byte.
     fn move_one(&mut self) {
-        match byte_action(self.parse_state.bytes, &self.point) {
+        match byte_action(
+            self.parse_state.bytes,
+            &self.point,
+            self.parse_state.tab_size,
+        ) {
             ByteAction::Ignore => {
                 self.point.index += 1;
             }
@@ -477,9 +505,9 @@ impl<'a> Tokenizer<'a> {
                     self.point.line += 1;
                     self.point.column = 1;
 
-                    if self.point.line - self.first_line + 1 > self.column_start.len() {
-                        self.column_start.push((self.point.index, self.point.vs));
-                    }
+                    let at = self.point.line - self.first_line;
+                    let info = (self.point.index, self.point.vs);
+                    set_column_start(self, at, info, false);
 
                     self.line_start = self.point.clone();
 
@@ -585,6 +613,9 @@ impl<'a> Tokenizer<'a> {
 
     /// Stack an attempt, moving to `ok` on [`State::Ok`][] and `nok` on
     /// [`State::Nok`][], reverting in both cases.
+    ///
+    /// Used to look ahead: the construct started next (typically with
+    /// [`State::Retry`][]) is parsed, but its events are dropped.
     pub fn check(&mut self, ok: State, nok: State) {
         // Always capture (and restore) when checking.
         // No need to capture (and restore) when `nok` is `State::Nok`, because the
@@ -595,12 +626,19 @@ impl<'a> Tokenizer<'a> {
             progress,
             ok,
             nok,
+            #[cfg(feature = "profile")]
+            profile: None,
         };
         self.attempts.push(attempt);
     }
 
     /// Stack an attempt, moving to `ok` on [`State::Ok`][] and `nok` on
     /// [`State::Nok`][], reverting in the latter case.
+    ///
+    /// The attempt applies to the construct started next (typically with
+    /// [`State::Retry`][]), and ends when that construct returns
+    /// [`State::Ok`][] or [`State::Nok`][].
+    /// Attempts can be nested.
     pub fn attempt(&mut self, ok: State, nok: State) {
         // Always capture (and restore) when checking.
         // No need to capture (and restore) when `nok` is `State::Nok`, because the
@@ -616,6 +654,8 @@ impl<'a> Tokenizer<'a> {
             progress,
             ok,
             nok,
+            #[cfg(feature = "profile")]
+            profile: None,
         };
         self.attempts.push(attempt);
     }
@@ -636,6 +676,8 @@ impl<'a> Tokenizer<'a> {
             done: false,
             gfm_footnote_definitions: self.tokenize_state.gfm_footnote_definitions.split_off(0),
             definitions: self.tokenize_state.definitions.split_off(0),
+            abbreviations: self.tokenize_state.abbreviations.split_off(0),
+            messages: self.tokenize_state.messages.split_off(0),
         };
 
         if resolve {
@@ -643,10 +685,22 @@ impl<'a> Tokenizer<'a> {
             let mut index = 0;
             let defs = &mut value.definitions;
             let fn_defs = &mut value.gfm_footnote_definitions;
+            let abbrs = &mut value.abbreviations;
+            let messages = &mut value.messages;
             while index < resolvers.len() {
-                if let Some(mut result) = call_resolve(self, resolvers[index])? {
+                #[cfg(feature = "profile")]
+                let start = std::time::Instant::now();
+                let result = call_resolve(self, resolvers[index])?;
+                #[cfg(feature = "profile")]
+                self.parse_state
+                    .profile
+                    .borrow_mut()
+                    .record_resolve(alloc::format!("{:?}", resolvers[index]), start.elapsed());
+                if let Some(mut result) = result {
                     fn_defs.append(&mut result.gfm_footnote_definitions);
                     defs.append(&mut result.definitions);
+                    abbrs.append(&mut result.abbreviations);
+                    messages.append(&mut result.messages);
                 }
                 index += 1;
             }
@@ -658,11 +712,33 @@ impl<'a> Tokenizer<'a> {
     }
 }
 
+/// Set where line `at` (relative to the first line) starts.
+///
+/// Lines are stored at their own index, also when earlier lines are not
+/// seen by this tokenizer, so that positions stay right when an attempt
+/// across several lines is reverted.
+/// Existing info is only replaced if `replace` is on.
+fn set_column_start(tokenizer: &mut Tokenizer, at: usize, info: (usize, usize), replace: bool) {
+    while tokenizer.column_start.len() < at {
+        tokenizer.column_start.push(info);
+    }
+
+    if at == tokenizer.column_start.len() {
+        tokenizer.column_start.push(info);
+    } else if replace {
+        tokenizer.column_start[at] = info;
+    }
+}
+
 /// Move back past ignored bytes.
 fn move_point_back(tokenizer: &mut Tokenizer, point: &mut Point) {
     while point.index > 0 {
         point.index -= 1;
-        let action = byte_action(tokenizer.parse_state.bytes, point);
+        let action = byte_action(
+            tokenizer.parse_state.bytes,
+            point,
+            tokenizer.parse_state.tab_size,
+        );
         if !matches!(action, ByteAction::Ignore) {
             point.index += 1;
             break;
@@ -708,6 +784,22 @@ fn push_impl(
             State::Error(_) => break,
             State::Ok | State::Nok => {
                 if let Some(attempt) = tokenizer.attempts.pop() {
+                    #[cfg(feature = "profile")]
+                    if let Some((name, start, instant)) = attempt.profile {
+                        let reverted = if attempt.kind == AttemptKind::Check || state == State::Nok
+                        {
+                            tokenizer.point.index.saturating_sub(start)
+                        } else {
+                            0
+                        };
+                        tokenizer.parse_state.profile.borrow_mut().record_attempt(
+                            alloc::format!("{:?}", name),
+                            state == State::Nok,
+                            reverted,
+                            instant.elapsed(),
+                        );
+                    }
+
                     if attempt.kind == AttemptKind::Check || state == State::Nok {
                         if let Some(progress) = attempt.progress {
                             tokenizer.free(progress);
@@ -734,7 +826,11 @@ fn push_impl(
                 let action = if tokenizer.point.index < to.0
                     || (tokenizer.point.index == to.0 && tokenizer.point.vs < to.1)
                 {
-                    Some(byte_action(tokenizer.parse_state.bytes, &tokenizer.point))
+                    Some(byte_action(
+                        tokenizer.parse_state.bytes,
+                        &tokenizer.point,
+                        tokenizer.parse_state.tab_size,
+                    ))
                 } else if flush {
                     None
                 } else {
@@ -755,13 +851,17 @@ fn push_impl(
                     log::trace!("feed:    {} to {:?}", format_byte_opt(byte), name);
 
                     tokenizer.expect(byte);
+                    #[cfg(feature = "profile")]
+                    profile_start(tokenizer, name);
                     state = call(tokenizer, name);
-                };
+                }
             }
             State::Retry(name) => {
                 #[cfg(feature = "log")]
                 log::trace!("retry:   `{:?}`", name);
 
+                #[cfg(feature = "profile")]
+                profile_start(tokenizer, name);
                 state = call(tokenizer, name);
             }
         }
@@ -781,8 +881,20 @@ fn push_impl(
     state
 }
 
+/// Note which construct the last attempt is for, if not yet known.
+#[cfg(feature = "profile")]
+fn profile_start(tokenizer: &mut Tokenizer, name: crate::state::Name) {
+    let index = tokenizer.point.index;
+
+    if let Some(attempt) = tokenizer.attempts.last_mut() {
+        if attempt.profile.is_none() {
+            attempt.profile = Some((name, index, std::time::Instant::now()));
+        }
+    }
+}
+
 /// Figure out how to handle a byte.
-fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
+fn byte_action(bytes: &[u8], point: &Point, tab_size: usize) -> ByteAction {
     if point.index < bytes.len() {
         let byte = bytes[point.index];
 
@@ -796,11 +908,11 @@ fn byte_action(bytes: &[u8], point: &Point) -> ByteAction {
                 ByteAction::Normal(b'\n')
             }
         } else if byte == b'\t' {
-            let remainder = point.column % TAB_SIZE;
+            let remainder = point.column % tab_size;
             let vs = if remainder == 0 {
                 0
             } else {
-                TAB_SIZE - remainder
+                tab_size - remainder
             };
 
             // On the tab itself, first send it.
@@ -53,9 +53,8 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
 /// `10` (decimal) or `16` (hexadecimal).
 ///
 /// This returns a `String` form of the associated character or a replacement
-/// character for C0 control characters (except for ASCII whitespace), C1
-/// control characters, lone surrogates, noncharacters, and out of range
-/// characters.
+/// character for code points that are not allowed (see
+/// [`numeric_allowed`][]).
 ///
 /// ## Examples
 ///
@@ -80,15 +79,10 @@ pub fn decode_named(value: &str, html5: bool) -> Option<String> {
 /// *   [`micromark-util-decode-numeric-character-reference` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-decode-numeric-character-reference)
 /// *   [*§ 2.5 Entity and numeric character references* in `CommonMark`](https://spec.commonmark.org/0.31/#entity-and-numeric-character-references)
 pub fn decode_numeric(value: &str, radix: u32) -> String {
-    if let Some(char) = char::from_u32(u32::from_str_radix(value, radix).unwrap()) {
-        if !matches!(char,
-            // C0 except for HT, LF, FF, CR, space.
-            '\0'..='\u{08}' | '\u{0B}' | '\u{0E}'..='\u{1F}' |
-            // Control character (DEL) of C0, and C1 controls.
-            '\u{7F}'..='\u{9F}'
-            // Lone surrogates, noncharacters, and out of range are handled by
-            // Rust.
-        ) {
+    let code = u32::from_str_radix(value, radix).unwrap();
+
+    if numeric_allowed(code) {
+        if let Some(char) = char::from_u32(code) {
             return char.into();
         }
     }
@@ -96,6 +90,43 @@ pub fn decode_numeric(value: &str, radix: u32) -> String {
     char::REPLACEMENT_CHARACTER.into()
 }
 
+/// Check whether a code point from a numeric character reference is allowed.
+///
+/// Not allowed are C0 control characters (except for ASCII whitespace), C1
+/// control characters, lone surrogates, noncharacters, and out of range
+/// code points.
+/// They are decoded as a replacement character (U+FFFD).
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::decode_character_reference::numeric_allowed;
+///
+/// assert!(numeric_allowed(0x7B));
+/// assert!(!numeric_allowed(0xD800));
+/// assert!(!numeric_allowed(0x11_0000));
+/// ```
+///
+/// ## References
+///
+/// *   [`micromark-util-decode-numeric-character-reference` in `micromark`](https://github.com/micromark/micromark/tree/main/packages/micromark-util-decode-numeric-character-reference)
+pub fn numeric_allowed(code: u32) -> bool {
+    !matches!(code,
+        // C0 except for HT, LF, FF, CR, space.
+        0..=0x08 | 0x0B | 0x0E..=0x1F |
+        // Control character (DEL) of C0, and C1 controls.
+        0x7F..=0x9F |
+        // Lone surrogates.
+        0xD800..=0xDFFF |
+        // Noncharacters.
+        0xFDD0..=0xFDEF
+    )
+        // Noncharacters at the end of each plane.
+        && code & 0xFFFF < 0xFFFE
+        // Out of range.
+        && code <= 0x0010_FFFF
+}
+
 /// Decode a character reference.
 ///
 /// This turns the number (in string form as either hexadecimal or decimal) or
@@ -155,12 +186,12 @@ pub fn value_test(marker: u8) -> fn(&u8) -> bool {
 
 /// Decode character references in a string.
 ///
-/// > 👉 **Note**: this currently only supports the 252 named character
-/// > references from HTML 4, as it’s only used for JSX.
-/// >
-/// > If it’s ever needed to support HTML 5 (which is what normal markdown
-/// > uses), a boolean parameter can be added here.
-pub fn parse(value: &str) -> String {
+/// The `html5` boolean is used for named character references, and specifies
+/// whether the 2125 names from HTML 5 or the 252 names from HTML 4 are
+/// supported.
+/// JSX uses HTML 4, directives use HTML 5 (which is what normal markdown
+/// uses).
+pub fn parse(value: &str, html5: bool) -> String {
     let bytes = value.as_bytes();
     let mut index = 0;
     let len = bytes.len();
@@ -193,11 +224,11 @@ pub fn parse(value: &str) -> String {
             let value_end = value_start + value_index;
 
             // Non empty and terminated.
-            if value_index > 0 && bytes[value_end] == b';' {
+            if value_index > 0 && value_end < len && bytes[value_end] == b';' {
                 if let Some(decoded) = decode(
                     str::from_utf8(&bytes[value_start..value_end]).unwrap(),
                     marker,
-                    false,
+                    html5,
                 ) {
                     result.push_str(&value[start..index]);
                     result.push_str(&decoded);
@@ -0,0 +1,45 @@
+//! Info on fenced code, passed to a function to highlight it.
+
+use alloc::string::String;
+
+/// Info on fenced code.
+///
+/// Passed to [`code_highlight`][crate::CompileOptions::code_highlight].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HighlightContext {
+    /// First word of the info string, with character escapes and references
+    /// decoded.
+    ///
+    /// This is not affected by
+    /// [`code_language_transform`][crate::CompileOptions::code_language_transform].
+    pub language: Option<String>,
+    /// Rest of the info string, after the first word and whitespace, with
+    /// character escapes and references decoded.
+    pub meta: Option<String>,
+    /// Code, as it is in the document, not encoded.
+    ///
+    /// Line endings are kept (or replaced by
+    /// [`line_ending`][crate::CompileOptions::line_ending], if configured),
+    /// including the final one if there is code.
+    pub value: String,
+}
+
+/// HTML of highlighted code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Highlight {
+    /// HTML for inside `<code>`, which is still wrapped in
+    /// `<pre><code class="language-x">` and `</code></pre>`.
+    Body(String),
+    /// HTML for the whole code, which replaces `<pre><code>` and
+    /// `</code></pre>` too.
+    Whole(String),
+}
+
+/// Signature of a function that highlights fenced code.
+///
+/// Can be passed as `code_highlight` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Return `Some` with HTML, which is not encoded, or `None` to compile the
+/// code as normal.
+pub type Highlighter = dyn Fn(HighlightContext) -> Option<Highlight>;
@@ -0,0 +1,32 @@
+//! Info on fenced code, passed to a function to choose its language.
+
+use alloc::string::String;
+
+/// Info on the info string of fenced code.
+///
+/// Passed to
+/// [`code_language_transform`][crate::CompileOptions::code_language_transform].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodeInfo {
+    /// First word of the info string, with character escapes and references
+    /// decoded.
+    ///
+    /// For example, `rust,no_run` in `` ```rust,no_run ``, or `js` in
+    /// `` ```js title="a.js" ``.
+    pub language: String,
+    /// Rest of the info string, after the first word and whitespace, with
+    /// character escapes and references decoded.
+    ///
+    /// For example, `title="a.js"` in `` ```js title="a.js" ``.
+    pub meta: Option<String>,
+}
+
+/// Signature of a function that chooses the language of fenced code.
+///
+/// Can be passed as `code_language_transform` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Return `Some` with a language, which is prefixed with
+/// [`code_language_prefix`][crate::CompileOptions::code_language_prefix] to
+/// form the `class` of `<code>`, or `None` to drop the `class` attribute.
+pub type Transform = dyn Fn(CodeInfo) -> Option<String>;
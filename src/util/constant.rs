@@ -67,6 +67,27 @@ pub const CHARACTER_REFERENCE_NAMED_SIZE_MAX: usize = 31;
 /// [raw_flow]: crate::construct::raw_flow
 pub const CODE_FENCED_SEQUENCE_SIZE_MIN: usize = 3;
 
+/// The number of markers needed for [directive (container)][directive_container]
+/// to form.
+///
+/// Like many things in markdown, the number is `3`.
+///
+/// [directive_container]: crate::construct::directive_container
+pub const DIRECTIVE_CONTAINER_SEQUENCE_SIZE_MIN: usize = 3;
+
+/// The number of markers needed for [directive (leaf)][directive_leaf] to
+/// form.
+///
+/// [directive_leaf]: crate::construct::directive_leaf
+pub const DIRECTIVE_LEAF_SEQUENCE_SIZE: usize = 2;
+
+/// The max depth of nodes in a syntax tree that
+/// [`to_markdown()`][crate::to_markdown()] formats.
+///
+/// Deeper trees (such as hundreds of nested block quotes) are kept as they
+/// are, as formatting them recursively could overflow the stack.
+pub const FORMAT_NESTING_DEPTH_MAX: usize = 256;
+
 /// The number of markers needed for [frontmatter][] to form.
 ///
 /// Like many things in markdown, the number is `3`.
@@ -266,6 +287,15 @@ pub const MATH_FLOW_SEQUENCE_SIZE_MIN: usize = 2;
 /// See: <https://github.com/remarkjs/react-markdown/issues/658#issuecomment-984345577>.
 pub const RESOURCE_DESTINATION_BALANCE_MAX: usize = 32;
 
+/// List of attributes allowed, when operating safely, in
+/// [attributes][attributes] on headings and paragraphs.
+///
+/// Event handlers (`on*`) and `style` are never allowed, not even when
+/// operating dangerously.
+///
+/// [attributes]: crate::construct::paragraph
+pub const SAFE_ATTRIBUTE_NAMES: [&str; 5] = ["class", "dir", "id", "lang", "title"];
+
 /// List of protocols allowed, when operating safely, as `href` on `a`.
 ///
 /// This list is based on what is allowed by GitHub.
@@ -276,13 +306,22 @@ pub const SAFE_PROTOCOL_HREF: [&str; 6] = ["http", "https", "irc", "ircs", "mail
 /// This list is based on what is allowed by GitHub.
 pub const SAFE_PROTOCOL_SRC: [&str; 2] = ["http", "https"];
 
+/// List of image types allowed, when operating safely, in `data:` URLs as
+/// `src` on `img`.
+///
+/// This list is based on what is allowed by `commonmark.js`.
+pub const SAFE_DATA_IMAGE_TYPES: [&str; 4] = ["gif", "jpeg", "png", "webp"];
+
 /// The number of characters that form a tab stop.
 ///
 /// This relates to the number of whitespace characters needed to form certain
 /// constructs in markdown, most notable the whitespace required to form
 /// [code (indented)][code_indented].
 ///
+/// This is the default of [`tab_size`][tab_size].
+///
 /// [code_indented]: crate::construct::code_indented
+/// [tab_size]: crate::ParseOptions::tab_size
 pub const TAB_SIZE: usize = 4;
 
 /// The number of markers needed for a [thematic break][thematic_break] to form.
@@ -306,7 +345,7 @@ pub const THEMATIC_BREAK_MARKER_COUNT_MIN: usize = 3;
 /// *   [*§ 2.5 Entity and numeric character references* in `CommonMark`](https://spec.commonmark.org/0.31/#entity-and-numeric-character-references)
 ///
 /// [character_reference]: crate::construct::character_reference
-pub const CHARACTER_REFERENCES: [(&str, &str); 2125] = [
+pub static CHARACTER_REFERENCES: [(&str, &str); 2125] = [
     ("AElig", "Æ"),
     ("AMP", "&"),
     ("Aacute", "Á"),
@@ -2447,7 +2486,7 @@ pub const CHARACTER_REFERENCES: [(&str, &str); 2125] = [
 /// ## References
 ///
 /// *   [*§ 1.5.2 HTML Character References* in `JSX`](https://facebook.github.io/jsx/#sec-HTMLCharacterReference)
-pub const CHARACTER_REFERENCES_HTML_4: [(&str, &str); 252] = [
+pub static CHARACTER_REFERENCES_HTML_4: [(&str, &str); 252] = [
     ("AElig", "Æ"),
     ("Aacute", "Á"),
     ("Acirc", "Â"),
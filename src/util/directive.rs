@@ -0,0 +1,85 @@
+//! Info on directives, passed to a custom handler when compiling to HTML.
+
+use alloc::{string::String, vec::Vec};
+
+/// Kind of directive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// Container directive.
+    ///
+    /// ```markdown
+    /// > | :::a
+    ///     ^^^^
+    /// > | b
+    ///     ^
+    /// > | :::
+    ///     ^^^
+    /// ```
+    Container,
+    /// Leaf directive.
+    ///
+    /// ```markdown
+    /// > | ::a[b]
+    ///     ^^^^^^
+    /// ```
+    Leaf,
+    /// Text directive.
+    ///
+    /// ```markdown
+    /// > | a :b[c] d
+    ///       ^^^^^
+    /// ```
+    Text,
+}
+
+/// A directive, as seen by a [`Handler`][].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Directive {
+    /// Kind of directive.
+    pub kind: Kind,
+    /// Name of the directive (`a` in `:a`).
+    pub name: String,
+    /// Label, compiled to HTML (`b` in `:a[b]`), if there was one.
+    pub label: Option<String>,
+    /// Attributes (`{#b .c d=e}`), in the order they were first seen.
+    ///
+    /// Character references in values are decoded.
+    /// The `#id` shortcut sets `id`, where the last one wins.
+    /// The `.class` shortcut and `class` attributes are joined with spaces.
+    /// Attributes without a value have an empty string as value.
+    pub attributes: Vec<(String, String)>,
+    /// Content, compiled to HTML, of container directives.
+    ///
+    /// Leaf and text directives have no content.
+    pub content: Option<String>,
+}
+
+/// Signature of a function that turns a directive into HTML.
+///
+/// Can be passed as `directive_handler` in
+/// [`CompileOptions`][crate::configuration::CompileOptions] to turn
+/// directives into HTML.
+///
+/// Return `None` to not handle a directive, in which case its label (leaf and
+/// text) or content (container) is rendered on its own, where the label of a
+/// leaf is in a paragraph.
+pub type Handler = dyn Fn(&Directive) -> Option<String>;
+
+/// Add an attribute to `attributes`.
+///
+/// Classes are merged, other attributes are overwritten.
+pub fn add_attribute(attributes: &mut Vec<(String, String)>, key: String, value: String) {
+    if let Some(existing) = attributes.iter_mut().find(|d| d.0 == key) {
+        if key == "class" {
+            if !existing.1.is_empty() && !value.is_empty() {
+                existing.1.push(' ');
+            }
+
+            existing.1.push_str(&value);
+        } else {
+            existing.1 = value;
+        }
+    } else {
+        attributes.push((key, value));
+    }
+}
@@ -9,7 +9,7 @@
 //! through another tokenizer and inject the result.
 
 use crate::event::Event;
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, vec::Vec};
 
 /// Shift `previous` and `next` links according to `jumps`.
 ///
@@ -57,14 +57,20 @@ fn shift_links(events: &mut [Event], jumps: &[(usize, usize, usize)]) {
 /// Tracks a bunch of edits.
 #[derive(Debug)]
 pub struct EditMap {
-    /// Record of changes.
-    map: Vec<(usize, usize, Vec<Event>)>,
+    /// Record of changes, by index: how many events to remove, and which
+    /// events to add.
+    ///
+    /// Kept sorted, as there can be many edits (such as one for every line
+    /// in a document), and they are applied in order.
+    map: BTreeMap<usize, (usize, Vec<Event>)>,
 }
 
 impl EditMap {
     /// Create a new edit map.
     pub fn new() -> EditMap {
-        EditMap { map: vec![] }
+        EditMap {
+            map: BTreeMap::new(),
+        }
     }
     /// Create an edit: a remove and/or add at a certain place.
     pub fn add(&mut self, index: usize, remove: usize, add: Vec<Event>) {
@@ -76,73 +82,59 @@ impl EditMap {
     }
     /// Done, change the events.
     pub fn consume(&mut self, events: &mut Vec<Event>) {
-        self.map
-            .sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-
         if self.map.is_empty() {
             return;
         }
 
         // Calculate jumps: where items in the current list move to.
         let mut jumps = Vec::with_capacity(self.map.len());
-        let mut index = 0;
         let mut add_acc = 0;
         let mut remove_acc = 0;
-        while index < self.map.len() {
-            let (at, remove, add) = &self.map[index];
+        for (at, (remove, add)) in &self.map {
             remove_acc += remove;
             add_acc += add.len();
             jumps.push((*at, remove_acc, add_acc));
-            index += 1;
         }
 
         shift_links(events, &jumps);
 
-        let len_before = events.len();
-        let mut index = self.map.len();
-        let mut vecs = Vec::with_capacity(index * 2 + 1);
-        while index > 0 {
-            index -= 1;
-            vecs.push(events.split_off(self.map[index].0 + self.map[index].1));
-            vecs.push(self.map[index].2.split_off(0));
-            events.truncate(self.map[index].0);
-        }
-        vecs.push(events.split_off(0));
+        // Move everything into a new list, in one go, instead of splitting
+        // and appending around each edit.
+        let len_after = events.len() + add_acc - remove_acc;
+        let mut old = core::mem::replace(events, Vec::with_capacity(len_after)).into_iter();
+        let mut index = 0;
 
-        events.reserve(len_before + add_acc - remove_acc);
+        for (at, (remove, mut add)) in core::mem::take(&mut self.map) {
+            events.extend(old.by_ref().take(at - index));
+
+            if remove > 0 {
+                old.nth(remove - 1);
+            }
 
-        while let Some(mut slice) = vecs.pop() {
-            events.append(&mut slice);
+            events.append(&mut add);
+            index = at + remove;
         }
 
-        self.map.truncate(0);
+        events.extend(old);
     }
 }
 
 /// Create an edit.
 fn add_impl(edit_map: &mut EditMap, at: usize, remove: usize, mut add: Vec<Event>, before: bool) {
-    let mut index = 0;
-
     if remove == 0 && add.is_empty() {
         return;
     }
 
-    while index < edit_map.map.len() {
-        if edit_map.map[index].0 == at {
-            edit_map.map[index].1 += remove;
-
-            if before {
-                add.append(&mut edit_map.map[index].2);
-                edit_map.map[index].2 = add;
-            } else {
-                edit_map.map[index].2.append(&mut add);
-            }
+    if let Some((existing_remove, existing_add)) = edit_map.map.get_mut(&at) {
+        *existing_remove += remove;
 
-            return;
+        if before {
+            add.append(existing_add);
+            *existing_add = add;
+        } else {
+            existing_add.append(&mut add);
         }
-
-        index += 1;
+    } else {
+        edit_map.map.insert(at, (remove, add));
     }
-
-    edit_map.map.push((at, remove, add));
 }
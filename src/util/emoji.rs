@@ -0,0 +1,5751 @@
+//! Info on emoji.
+
+/// List of emoji shortcodes (without colons) and the emoji they stand for,
+/// sorted by shortcode.
+///
+/// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
+/// > It is generate from the emoji data of `gemoji`.
+///
+/// Only included with the `emoji` feature.
+pub static EMOJI: [(&str, &str); 3526] = [
+    ("+1", "\u{1f44d}"),
+    ("-1", "\u{1f44e}"),
+    ("100", "\u{1f4af}"),
+    ("1234", "\u{1f522}"),
+    ("1st_place_medal", "\u{1f947}"),
+    ("2nd_place_medal", "\u{1f948}"),
+    ("3rd_place_medal", "\u{1f949}"),
+    ("8ball", "\u{1f3b1}"),
+    ("__1", "\u{1f44e}"),
+    ("a", "\u{1f170}"),
+    ("ab", "\u{1f18e}"),
+    ("abacus", "\u{1f9ee}"),
+    ("abc", "\u{1f524}"),
+    ("abcd", "\u{1f521}"),
+    ("accept", "\u{1f251}"),
+    ("adhesive_bandage", "\u{1fa79}"),
+    ("admission_tickets", "\u{1f39f}"),
+    ("adult", "\u{1f9d1}"),
+    ("adult_dark_skin_tone", "\u{1f9d1}\u{1f3ff}"),
+    ("adult_light_skin_tone", "\u{1f9d1}\u{1f3fb}"),
+    ("adult_medium-dark_skin_tone", "\u{1f9d1}\u{1f3fe}"),
+    ("adult_medium-light_skin_tone", "\u{1f9d1}\u{1f3fc}"),
+    ("adult_medium_skin_tone", "\u{1f9d1}\u{1f3fd}"),
+    ("aerial_tramway", "\u{1f6a1}"),
+    ("afghanistan", "\u{1f1e6}\u{1f1eb}"),
+    ("airplane", "\u{2708}"),
+    ("airplane_arrival", "\u{1f6ec}"),
+    ("airplane_arriving", "\u{1f6ec}"),
+    ("airplane_departure", "\u{1f6eb}"),
+    ("alarm_clock", "\u{23f0}"),
+    ("albania", "\u{1f1e6}\u{1f1f1}"),
+    ("alembic", "\u{2697}"),
+    ("algeria", "\u{1f1e9}\u{1f1ff}"),
+    ("alien", "\u{1f47d}"),
+    ("alien_monster", "\u{1f47e}"),
+    ("ambulance", "\u{1f691}"),
+    ("american_football", "\u{1f3c8}"),
+    ("american_samoa", "\u{1f1e6}\u{1f1f8}"),
+    ("amphora", "\u{1f3fa}"),
+    ("anchor", "\u{2693}"),
+    ("andorra", "\u{1f1e6}\u{1f1e9}"),
+    ("angel", "\u{1f47c}"),
+    ("anger", "\u{1f4a2}"),
+    ("anger_symbol", "\u{1f4a2}"),
+    ("angola", "\u{1f1e6}\u{1f1f4}"),
+    ("angry", "\u{1f620}"),
+    ("angry_face", "\u{1f620}"),
+    ("angry_face_with_horns", "\u{1f47f}"),
+    ("anguilla", "\u{1f1e6}\u{1f1ee}"),
+    ("anguished", "\u{1f627}"),
+    ("anguished_face", "\u{1f627}"),
+    ("ant", "\u{1f41c}"),
+    ("antarctica", "\u{1f1e6}\u{1f1f6}"),
+    ("antenna_bars", "\u{1f4f6}"),
+    ("anxious_face_with_sweat", "\u{1f630}"),
+    ("apple", "\u{1f34e}"),
+    ("aquarius", "\u{2652}"),
+    ("argentina", "\u{1f1e6}\u{1f1f7}"),
+    ("aries", "\u{2648}"),
+    ("armenia", "\u{1f1e6}\u{1f1f2}"),
+    ("arrow_backward", "\u{25c0}"),
+    ("arrow_double_down", "\u{23ec}"),
+    ("arrow_double_up", "\u{23eb}"),
+    ("arrow_down", "\u{2b07}"),
+    ("arrow_down_small", "\u{1f53d}"),
+    ("arrow_forward", "\u{25b6}"),
+    ("arrow_heading_down", "\u{2935}"),
+    ("arrow_heading_up", "\u{2934}"),
+    ("arrow_left", "\u{2b05}"),
+    ("arrow_lower_left", "\u{2199}"),
+    ("arrow_lower_right", "\u{2198}"),
+    ("arrow_right", "\u{27a1}"),
+    ("arrow_right_hook", "\u{21aa}"),
+    ("arrow_up", "\u{2b06}"),
+    ("arrow_up_down", "\u{2195}"),
+    ("arrow_up_small", "\u{1f53c}"),
+    ("arrow_upper_left", "\u{2196}"),
+    ("arrow_upper_right", "\u{2197}"),
+    ("arrows_clockwise", "\u{1f503}"),
+    ("arrows_counterclockwise", "\u{1f504}"),
+    ("art", "\u{1f3a8}"),
+    ("articulated_lorry", "\u{1f69b}"),
+    ("artist_palette", "\u{1f3a8}"),
+    ("aruba", "\u{1f1e6}\u{1f1fc}"),
+    ("ascension_island", "\u{1f1e6}\u{1f1e8}"),
+    ("astonished", "\u{1f632}"),
+    ("astonished_face", "\u{1f632}"),
+    ("athletic_shoe", "\u{1f45f}"),
+    ("atm", "\u{1f3e7}"),
+    ("atm_sign", "\u{1f3e7}"),
+    ("atom_symbol", "\u{269b}"),
+    ("australia", "\u{1f1e6}\u{1f1fa}"),
+    ("austria", "\u{1f1e6}\u{1f1f9}"),
+    ("auto_rickshaw", "\u{1f6fa}"),
+    ("automobile", "\u{1f697}"),
+    ("avocado", "\u{1f951}"),
+    ("axe", "\u{1fa93}"),
+    ("azerbaijan", "\u{1f1e6}\u{1f1ff}"),
+    ("b", "\u{1f171}"),
+    ("baby", "\u{1f476}"),
+    ("baby_angel", "\u{1f47c}"),
+    ("baby_angel_dark_skin_tone", "\u{1f47c}\u{1f3ff}"),
+    ("baby_angel_light_skin_tone", "\u{1f47c}\u{1f3fb}"),
+    ("baby_angel_medium-dark_skin_tone", "\u{1f47c}\u{1f3fe}"),
+    ("baby_angel_medium-light_skin_tone", "\u{1f47c}\u{1f3fc}"),
+    ("baby_angel_medium_skin_tone", "\u{1f47c}\u{1f3fd}"),
+    ("baby_bottle", "\u{1f37c}"),
+    ("baby_chick", "\u{1f424}"),
+    ("baby_dark_skin_tone", "\u{1f476}\u{1f3ff}"),
+    ("baby_light_skin_tone", "\u{1f476}\u{1f3fb}"),
+    ("baby_medium-dark_skin_tone", "\u{1f476}\u{1f3fe}"),
+    ("baby_medium-light_skin_tone", "\u{1f476}\u{1f3fc}"),
+    ("baby_medium_skin_tone", "\u{1f476}\u{1f3fd}"),
+    ("baby_symbol", "\u{1f6bc}"),
+    ("back", "\u{1f519}"),
+    ("back_arrow", "\u{1f519}"),
+    ("backhand_index_pointing_down", "\u{1f447}"),
+    (
+        "backhand_index_pointing_down_dark_skin_tone",
+        "\u{1f447}\u{1f3ff}",
+    ),
+    (
+        "backhand_index_pointing_down_light_skin_tone",
+        "\u{1f447}\u{1f3fb}",
+    ),
+    (
+        "backhand_index_pointing_down_medium-dark_skin_tone",
+        "\u{1f447}\u{1f3fe}",
+    ),
+    (
+        "backhand_index_pointing_down_medium-light_skin_tone",
+        "\u{1f447}\u{1f3fc}",
+    ),
+    (
+        "backhand_index_pointing_down_medium_skin_tone",
+        "\u{1f447}\u{1f3fd}",
+    ),
+    ("backhand_index_pointing_left", "\u{1f448}"),
+    (
+        "backhand_index_pointing_left_dark_skin_tone",
+        "\u{1f448}\u{1f3ff}",
+    ),
+    (
+        "backhand_index_pointing_left_light_skin_tone",
+        "\u{1f448}\u{1f3fb}",
+    ),
+    (
+        "backhand_index_pointing_left_medium-dark_skin_tone",
+        "\u{1f448}\u{1f3fe}",
+    ),
+    (
+        "backhand_index_pointing_left_medium-light_skin_tone",
+        "\u{1f448}\u{1f3fc}",
+    ),
+    (
+        "backhand_index_pointing_left_medium_skin_tone",
+        "\u{1f448}\u{1f3fd}",
+    ),
+    ("backhand_index_pointing_right", "\u{1f449}"),
+    (
+        "backhand_index_pointing_right_dark_skin_tone",
+        "\u{1f449}\u{1f3ff}",
+    ),
+    (
+        "backhand_index_pointing_right_light_skin_tone",
+        "\u{1f449}\u{1f3fb}",
+    ),
+    (
+        "backhand_index_pointing_right_medium-dark_skin_tone",
+        "\u{1f449}\u{1f3fe}",
+    ),
+    (
+        "backhand_index_pointing_right_medium-light_skin_tone",
+        "\u{1f449}\u{1f3fc}",
+    ),
+    (
+        "backhand_index_pointing_right_medium_skin_tone",
+        "\u{1f449}\u{1f3fd}",
+    ),
+    ("backhand_index_pointing_up", "\u{1f446}"),
+    (
+        "backhand_index_pointing_up_dark_skin_tone",
+        "\u{1f446}\u{1f3ff}",
+    ),
+    (
+        "backhand_index_pointing_up_light_skin_tone",
+        "\u{1f446}\u{1f3fb}",
+    ),
+    (
+        "backhand_index_pointing_up_medium-dark_skin_tone",
+        "\u{1f446}\u{1f3fe}",
+    ),
+    (
+        "backhand_index_pointing_up_medium-light_skin_tone",
+        "\u{1f446}\u{1f3fc}",
+    ),
+    (
+        "backhand_index_pointing_up_medium_skin_tone",
+        "\u{1f446}\u{1f3fd}",
+    ),
+    ("bacon", "\u{1f953}"),
+    ("badger", "\u{1f9a1}"),
+    ("badminton", "\u{1f3f8}"),
+    ("badminton_racquet_and_shuttlecock", "\u{1f3f8}"),
+    ("bagel", "\u{1f96f}"),
+    ("baggage_claim", "\u{1f6c4}"),
+    ("baguette_bread", "\u{1f956}"),
+    ("bahamas", "\u{1f1e7}\u{1f1f8}"),
+    ("bahrain", "\u{1f1e7}\u{1f1ed}"),
+    ("balance_scale", "\u{2696}"),
+    ("bald", "\u{1f9b2}"),
+    ("bald_man", "\u{1f468}\u{200d}\u{1f9b2}"),
+    ("bald_woman", "\u{1f469}\u{200d}\u{1f9b2}"),
+    ("ballet_shoes", "\u{1fa70}"),
+    ("balloon", "\u{1f388}"),
+    ("ballot_box_with_ballot", "\u{1f5f3}"),
+    ("ballot_box_with_check", "\u{2611}"),
+    ("bamboo", "\u{1f38d}"),
+    ("banana", "\u{1f34c}"),
+    ("bangbang", "\u{203c}"),
+    ("bangladesh", "\u{1f1e7}\u{1f1e9}"),
+    ("banjo", "\u{1fa95}"),
+    ("bank", "\u{1f3e6}"),
+    ("bar_chart", "\u{1f4ca}"),
+    ("barbados", "\u{1f1e7}\u{1f1e7}"),
+    ("barber", "\u{1f488}"),
+    ("barber_pole", "\u{1f488}"),
+    ("baseball", "\u{26be}"),
+    ("basket", "\u{1f9fa}"),
+    ("basketball", "\u{1f3c0}"),
+    ("bat", "\u{1f987}"),
+    ("bath", "\u{1f6c0}"),
+    ("bathtub", "\u{1f6c1}"),
+    ("battery", "\u{1f50b}"),
+    ("beach_with_umbrella", "\u{1f3d6}"),
+    ("beaming_face_with_smiling_eyes", "\u{1f601}"),
+    ("bear", "\u{1f43b}"),
+    ("bear_face", "\u{1f43b}"),
+    ("bearded_person", "\u{1f9d4}"),
+    ("bearded_person_dark_skin_tone", "\u{1f9d4}\u{1f3ff}"),
+    ("bearded_person_light_skin_tone", "\u{1f9d4}\u{1f3fb}"),
+    ("bearded_person_medium-dark_skin_tone", "\u{1f9d4}\u{1f3fe}"),
+    (
+        "bearded_person_medium-light_skin_tone",
+        "\u{1f9d4}\u{1f3fc}",
+    ),
+    ("bearded_person_medium_skin_tone", "\u{1f9d4}\u{1f3fd}"),
+    ("beating_heart", "\u{1f493}"),
+    ("bed", "\u{1f6cf}"),
+    ("bee", "\u{1f41d}"),
+    ("beer", "\u{1f37a}"),
+    ("beer_mug", "\u{1f37a}"),
+    ("beers", "\u{1f37b}"),
+    ("beetle", "\u{1f41e}"),
+    ("beginner", "\u{1f530}"),
+    ("belarus", "\u{1f1e7}\u{1f1fe}"),
+    ("belgium", "\u{1f1e7}\u{1f1ea}"),
+    ("belize", "\u{1f1e7}\u{1f1ff}"),
+    ("bell", "\u{1f514}"),
+    ("bell_with_slash", "\u{1f515}"),
+    ("bellhop_bell", "\u{1f6ce}"),
+    ("benin", "\u{1f1e7}\u{1f1ef}"),
+    ("bento", "\u{1f371}"),
+    ("bento_box", "\u{1f371}"),
+    ("bermuda", "\u{1f1e7}\u{1f1f2}"),
+    ("beverage_box", "\u{1f9c3}"),
+    ("bhutan", "\u{1f1e7}\u{1f1f9}"),
+    ("bicycle", "\u{1f6b2}"),
+    ("bicyclist", "\u{1f6b4}"),
+    ("bike", "\u{1f6b2}"),
+    ("bikini", "\u{1f459}"),
+    ("billed_cap", "\u{1f9e2}"),
+    ("biohazard", "\u{2623}"),
+    ("biohazard_sign", "\u{2623}"),
+    ("bird", "\u{1f426}"),
+    ("birthday", "\u{1f382}"),
+    ("birthday_cake", "\u{1f382}"),
+    ("black_circle", "\u{26ab}"),
+    ("black_circle_for_record", "\u{23fa}"),
+    ("black_flag", "\u{1f3f4}"),
+    ("black_heart", "\u{1f5a4}"),
+    ("black_joker", "\u{1f0cf}"),
+    ("black_large_square", "\u{2b1b}"),
+    (
+        "black_left__pointing_double_triangle_with_vertical_bar",
+        "\u{23ee}",
+    ),
+    ("black_medium-small_square", "\u{25fe}"),
+    ("black_medium_small_square", "\u{25fe}"),
+    ("black_medium_square", "\u{25fc}"),
+    ("black_nib", "\u{2712}"),
+    (
+        "black_right__pointing_double_triangle_with_vertical_bar",
+        "\u{23ed}",
+    ),
+    (
+        "black_right__pointing_triangle_with_double_vertical_bar",
+        "\u{23ef}",
+    ),
+    ("black_small_square", "\u{25aa}"),
+    ("black_square_button", "\u{1f532}"),
+    ("black_square_for_stop", "\u{23f9}"),
+    ("blond-haired_man", "\u{1f471}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "blond-haired_man_dark_skin_tone",
+        "\u{1f471}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "blond-haired_man_light_skin_tone",
+        "\u{1f471}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "blond-haired_man_medium-dark_skin_tone",
+        "\u{1f471}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "blond-haired_man_medium-light_skin_tone",
+        "\u{1f471}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "blond-haired_man_medium_skin_tone",
+        "\u{1f471}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("blond-haired_person", "\u{1f471}"),
+    ("blond-haired_person_dark_skin_tone", "\u{1f471}\u{1f3ff}"),
+    ("blond-haired_person_light_skin_tone", "\u{1f471}\u{1f3fb}"),
+    (
+        "blond-haired_person_medium-dark_skin_tone",
+        "\u{1f471}\u{1f3fe}",
+    ),
+    (
+        "blond-haired_person_medium-light_skin_tone",
+        "\u{1f471}\u{1f3fc}",
+    ),
+    ("blond-haired_person_medium_skin_tone", "\u{1f471}\u{1f3fd}"),
+    ("blond-haired_woman", "\u{1f471}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "blond-haired_woman_dark_skin_tone",
+        "\u{1f471}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "blond-haired_woman_light_skin_tone",
+        "\u{1f471}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "blond-haired_woman_medium-dark_skin_tone",
+        "\u{1f471}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "blond-haired_woman_medium-light_skin_tone",
+        "\u{1f471}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "blond-haired_woman_medium_skin_tone",
+        "\u{1f471}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("blossom", "\u{1f33c}"),
+    ("blowfish", "\u{1f421}"),
+    ("blue_book", "\u{1f4d8}"),
+    ("blue_car", "\u{1f699}"),
+    ("blue_circle", "\u{1f535}"),
+    ("blue_heart", "\u{1f499}"),
+    ("blue_square", "\u{1f7e6}"),
+    ("blush", "\u{1f60a}"),
+    ("boar", "\u{1f417}"),
+    ("boat", "\u{26f5}"),
+    ("bolivia", "\u{1f1e7}\u{1f1f4}"),
+    ("bomb", "\u{1f4a3}"),
+    ("bone", "\u{1f9b4}"),
+    ("book", "\u{1f4d6}"),
+    ("bookmark", "\u{1f516}"),
+    ("bookmark_tabs", "\u{1f4d1}"),
+    ("books", "\u{1f4da}"),
+    ("boom", "\u{1f4a5}"),
+    ("boot", "\u{1f462}"),
+    ("botswana", "\u{1f1e7}\u{1f1fc}"),
+    ("bottle_with_popping_cork", "\u{1f37e}"),
+    ("bouquet", "\u{1f490}"),
+    ("bouvet_island", "\u{1f1e7}\u{1f1fb}"),
+    ("bow", "\u{1f647}"),
+    ("bow_and_arrow", "\u{1f3f9}"),
+    ("bowl_with_spoon", "\u{1f963}"),
+    ("bowling", "\u{1f3b3}"),
+    ("boxing_glove", "\u{1f94a}"),
+    ("boy", "\u{1f466}"),
+    ("boy_dark_skin_tone", "\u{1f466}\u{1f3ff}"),
+    ("boy_light_skin_tone", "\u{1f466}\u{1f3fb}"),
+    ("boy_medium-dark_skin_tone", "\u{1f466}\u{1f3fe}"),
+    ("boy_medium-light_skin_tone", "\u{1f466}\u{1f3fc}"),
+    ("boy_medium_skin_tone", "\u{1f466}\u{1f3fd}"),
+    ("brain", "\u{1f9e0}"),
+    ("brazil", "\u{1f1e7}\u{1f1f7}"),
+    ("bread", "\u{1f35e}"),
+    ("breast-feeding", "\u{1f931}"),
+    ("breast-feeding_dark_skin_tone", "\u{1f931}\u{1f3ff}"),
+    ("breast-feeding_light_skin_tone", "\u{1f931}\u{1f3fb}"),
+    ("breast-feeding_medium-dark_skin_tone", "\u{1f931}\u{1f3fe}"),
+    (
+        "breast-feeding_medium-light_skin_tone",
+        "\u{1f931}\u{1f3fc}",
+    ),
+    ("breast-feeding_medium_skin_tone", "\u{1f931}\u{1f3fd}"),
+    ("brick", "\u{1f9f1}"),
+    ("bride_with_veil", "\u{1f470}"),
+    ("bride_with_veil_dark_skin_tone", "\u{1f470}\u{1f3ff}"),
+    ("bride_with_veil_light_skin_tone", "\u{1f470}\u{1f3fb}"),
+    (
+        "bride_with_veil_medium-dark_skin_tone",
+        "\u{1f470}\u{1f3fe}",
+    ),
+    (
+        "bride_with_veil_medium-light_skin_tone",
+        "\u{1f470}\u{1f3fc}",
+    ),
+    ("bride_with_veil_medium_skin_tone", "\u{1f470}\u{1f3fd}"),
+    ("bridge_at_night", "\u{1f309}"),
+    ("briefcase", "\u{1f4bc}"),
+    ("briefs", "\u{1fa72}"),
+    ("bright_button", "\u{1f506}"),
+    ("british_indian_ocean_territory", "\u{1f1ee}\u{1f1f4}"),
+    ("british_virgin_islands", "\u{1f1fb}\u{1f1ec}"),
+    ("broccoli", "\u{1f966}"),
+    ("broken_heart", "\u{1f494}"),
+    ("broom", "\u{1f9f9}"),
+    ("brown_circle", "\u{1f7e4}"),
+    ("brown_heart", "\u{1f90e}"),
+    ("brown_square", "\u{1f7eb}"),
+    ("brunei", "\u{1f1e7}\u{1f1f3}"),
+    ("bug", "\u{1f41b}"),
+    ("building_construction", "\u{1f3d7}"),
+    ("bulb", "\u{1f4a1}"),
+    ("bulgaria", "\u{1f1e7}\u{1f1ec}"),
+    ("bullet_train", "\u{1f685}"),
+    ("bullettrain_front", "\u{1f685}"),
+    ("bullettrain_side", "\u{1f684}"),
+    ("burkina_faso", "\u{1f1e7}\u{1f1eb}"),
+    ("burrito", "\u{1f32f}"),
+    ("burundi", "\u{1f1e7}\u{1f1ee}"),
+    ("bus", "\u{1f68c}"),
+    ("bus_stop", "\u{1f68f}"),
+    ("busstop", "\u{1f68f}"),
+    ("bust_in_silhouette", "\u{1f464}"),
+    ("busts_in_silhouette", "\u{1f465}"),
+    ("butter", "\u{1f9c8}"),
+    ("butterfly", "\u{1f98b}"),
+    ("cactus", "\u{1f335}"),
+    ("cake", "\u{1f370}"),
+    ("calendar", "\u{1f4c6}"),
+    ("call_me_hand", "\u{1f919}"),
+    ("call_me_hand_dark_skin_tone", "\u{1f919}\u{1f3ff}"),
+    ("call_me_hand_light_skin_tone", "\u{1f919}\u{1f3fb}"),
+    ("call_me_hand_medium-dark_skin_tone", "\u{1f919}\u{1f3fe}"),
+    ("call_me_hand_medium-light_skin_tone", "\u{1f919}\u{1f3fc}"),
+    ("call_me_hand_medium_skin_tone", "\u{1f919}\u{1f3fd}"),
+    ("calling", "\u{1f4f2}"),
+    ("cambodia", "\u{1f1f0}\u{1f1ed}"),
+    ("camel", "\u{1f42b}"),
+    ("camera", "\u{1f4f7}"),
+    ("camera_with_flash", "\u{1f4f8}"),
+    ("cameroon", "\u{1f1e8}\u{1f1f2}"),
+    ("camping", "\u{1f3d5}"),
+    ("canada", "\u{1f1e8}\u{1f1e6}"),
+    ("canary_islands", "\u{1f1ee}\u{1f1e8}"),
+    ("cancer", "\u{264b}"),
+    ("candle", "\u{1f56f}"),
+    ("candy", "\u{1f36c}"),
+    ("canned_food", "\u{1f96b}"),
+    ("canoe", "\u{1f6f6}"),
+    ("cape_verde", "\u{1f1e8}\u{1f1fb}"),
+    ("capital_abcd", "\u{1f520}"),
+    ("capricorn", "\u{2651}"),
+    ("car", "\u{1f697}"),
+    ("card_file_box", "\u{1f5c3}"),
+    ("card_index", "\u{1f4c7}"),
+    ("card_index_dividers", "\u{1f5c2}"),
+    ("caribbean_netherlands", "\u{1f1e7}\u{1f1f6}"),
+    ("carousel_horse", "\u{1f3a0}"),
+    ("carp_streamer", "\u{1f38f}"),
+    ("carrot", "\u{1f955}"),
+    ("castle", "\u{1f3f0}"),
+    ("cat", "\u{1f431}"),
+    ("cat2", "\u{1f408}"),
+    ("cat_face", "\u{1f431}"),
+    ("cat_face_with_tears_of_joy", "\u{1f639}"),
+    ("cat_face_with_wry_smile", "\u{1f63c}"),
+    ("cayman_islands", "\u{1f1f0}\u{1f1fe}"),
+    ("cd", "\u{1f4bf}"),
+    ("central_african_republic", "\u{1f1e8}\u{1f1eb}"),
+    ("chad", "\u{1f1f9}\u{1f1e9}"),
+    ("chains", "\u{26d3}"),
+    ("chair", "\u{1fa91}"),
+    ("chart", "\u{1f4b9}"),
+    ("chart_decreasing", "\u{1f4c9}"),
+    ("chart_increasing", "\u{1f4c8}"),
+    ("chart_increasing_with_yen", "\u{1f4b9}"),
+    ("chart_with_downwards_trend", "\u{1f4c9}"),
+    ("chart_with_upwards_trend", "\u{1f4c8}"),
+    ("checkered_flag", "\u{1f3c1}"),
+    ("cheese_wedge", "\u{1f9c0}"),
+    ("chequered_flag", "\u{1f3c1}"),
+    ("cherries", "\u{1f352}"),
+    ("cherry_blossom", "\u{1f338}"),
+    ("chess_pawn", "\u{265f}"),
+    ("chestnut", "\u{1f330}"),
+    ("chicken", "\u{1f414}"),
+    ("child", "\u{1f9d2}"),
+    ("child_dark_skin_tone", "\u{1f9d2}\u{1f3ff}"),
+    ("child_light_skin_tone", "\u{1f9d2}\u{1f3fb}"),
+    ("child_medium-dark_skin_tone", "\u{1f9d2}\u{1f3fe}"),
+    ("child_medium-light_skin_tone", "\u{1f9d2}\u{1f3fc}"),
+    ("child_medium_skin_tone", "\u{1f9d2}\u{1f3fd}"),
+    ("children_crossing", "\u{1f6b8}"),
+    ("chile", "\u{1f1e8}\u{1f1f1}"),
+    ("china", "\u{1f1e8}\u{1f1f3}"),
+    ("chipmunk", "\u{1f43f}"),
+    ("chocolate_bar", "\u{1f36b}"),
+    ("chopsticks", "\u{1f962}"),
+    ("christmas_island", "\u{1f1e8}\u{1f1fd}"),
+    ("christmas_tree", "\u{1f384}"),
+    ("church", "\u{26ea}"),
+    ("cigarette", "\u{1f6ac}"),
+    ("cinema", "\u{1f3a6}"),
+    ("circled_m", "\u{24c2}"),
+    ("circus_tent", "\u{1f3aa}"),
+    ("city_sunrise", "\u{1f307}"),
+    ("city_sunset", "\u{1f306}"),
+    ("cityscape", "\u{1f3d9}"),
+    ("cityscape_at_dusk", "\u{1f306}"),
+    ("cl", "\u{1f191}"),
+    ("cl_button", "\u{1f191}"),
+    ("clamp", "\u{1f5dc}"),
+    ("clap", "\u{1f44f}"),
+    ("clapper", "\u{1f3ac}"),
+    ("clapper_board", "\u{1f3ac}"),
+    ("clapping_hands", "\u{1f44f}"),
+    ("clapping_hands_dark_skin_tone", "\u{1f44f}\u{1f3ff}"),
+    ("clapping_hands_light_skin_tone", "\u{1f44f}\u{1f3fb}"),
+    ("clapping_hands_medium-dark_skin_tone", "\u{1f44f}\u{1f3fe}"),
+    (
+        "clapping_hands_medium-light_skin_tone",
+        "\u{1f44f}\u{1f3fc}",
+    ),
+    ("clapping_hands_medium_skin_tone", "\u{1f44f}\u{1f3fd}"),
+    ("classical_building", "\u{1f3db}"),
+    ("clinking_beer_mugs", "\u{1f37b}"),
+    ("clinking_glasses", "\u{1f942}"),
+    ("clipboard", "\u{1f4cb}"),
+    ("clipperton_island", "\u{1f1e8}\u{1f1f5}"),
+    ("clock1", "\u{1f550}"),
+    ("clock10", "\u{1f559}"),
+    ("clock1030", "\u{1f565}"),
+    ("clock11", "\u{1f55a}"),
+    ("clock1130", "\u{1f566}"),
+    ("clock12", "\u{1f55b}"),
+    ("clock1230", "\u{1f567}"),
+    ("clock130", "\u{1f55c}"),
+    ("clock2", "\u{1f551}"),
+    ("clock230", "\u{1f55d}"),
+    ("clock3", "\u{1f552}"),
+    ("clock330", "\u{1f55e}"),
+    ("clock4", "\u{1f553}"),
+    ("clock430", "\u{1f55f}"),
+    ("clock5", "\u{1f554}"),
+    ("clock530", "\u{1f560}"),
+    ("clock6", "\u{1f555}"),
+    ("clock630", "\u{1f561}"),
+    ("clock7", "\u{1f556}"),
+    ("clock730", "\u{1f562}"),
+    ("clock8", "\u{1f557}"),
+    ("clock830", "\u{1f563}"),
+    ("clock9", "\u{1f558}"),
+    ("clock930", "\u{1f564}"),
+    ("clockwise_vertical_arrows", "\u{1f503}"),
+    ("closed_book", "\u{1f4d5}"),
+    ("closed_lock_with_key", "\u{1f510}"),
+    ("closed_mailbox_with_lowered_flag", "\u{1f4ea}"),
+    ("closed_mailbox_with_raised_flag", "\u{1f4eb}"),
+    ("closed_umbrella", "\u{1f302}"),
+    ("cloud", "\u{2601}"),
+    ("cloud_with_lightning", "\u{1f329}"),
+    ("cloud_with_lightning_and_rain", "\u{26c8}"),
+    ("cloud_with_rain", "\u{1f327}"),
+    ("cloud_with_snow", "\u{1f328}"),
+    ("cloud_with_tornado", "\u{1f32a}"),
+    ("clown_face", "\u{1f921}"),
+    ("club_suit", "\u{2663}"),
+    ("clubs", "\u{2663}"),
+    ("clutch_bag", "\u{1f45d}"),
+    ("coat", "\u{1f9e5}"),
+    ("cocktail", "\u{1f378}"),
+    ("cocktail_glass", "\u{1f378}"),
+    ("coconut", "\u{1f965}"),
+    ("coffee", "\u{2615}"),
+    ("coffin", "\u{26b0}"),
+    ("cold_face", "\u{1f976}"),
+    ("cold_sweat", "\u{1f630}"),
+    ("collision", "\u{1f4a5}"),
+    ("colombia", "\u{1f1e8}\u{1f1f4}"),
+    ("comet", "\u{2604}"),
+    ("comoros", "\u{1f1f0}\u{1f1f2}"),
+    ("compass", "\u{1f9ed}"),
+    ("compression", "\u{1f5dc}"),
+    ("computer", "\u{1f4bb}"),
+    ("computer_disk", "\u{1f4bd}"),
+    ("computer_mouse", "\u{1f5b1}"),
+    ("confetti_ball", "\u{1f38a}"),
+    ("confounded", "\u{1f616}"),
+    ("confounded_face", "\u{1f616}"),
+    ("confused", "\u{1f615}"),
+    ("confused_face", "\u{1f615}"),
+    ("congo_-_brazzaville", "\u{1f1e8}\u{1f1ec}"),
+    ("congo_-_kinshasa", "\u{1f1e8}\u{1f1e9}"),
+    ("congratulations", "\u{3297}"),
+    ("construction", "\u{1f6a7}"),
+    ("construction_worker", "\u{1f477}"),
+    ("construction_worker_dark_skin_tone", "\u{1f477}\u{1f3ff}"),
+    ("construction_worker_light_skin_tone", "\u{1f477}\u{1f3fb}"),
+    (
+        "construction_worker_medium-dark_skin_tone",
+        "\u{1f477}\u{1f3fe}",
+    ),
+    (
+        "construction_worker_medium-light_skin_tone",
+        "\u{1f477}\u{1f3fc}",
+    ),
+    ("construction_worker_medium_skin_tone", "\u{1f477}\u{1f3fd}"),
+    ("control_knobs", "\u{1f39b}"),
+    ("convenience_store", "\u{1f3ea}"),
+    ("cook_islands", "\u{1f1e8}\u{1f1f0}"),
+    ("cooked_rice", "\u{1f35a}"),
+    ("cookie", "\u{1f36a}"),
+    ("cooking", "\u{1f373}"),
+    ("cool", "\u{1f192}"),
+    ("cool_button", "\u{1f192}"),
+    ("cop", "\u{1f46e}"),
+    ("copyright", "\u{a9}"),
+    ("corn", "\u{1f33d}"),
+    ("costa_rica", "\u{1f1e8}\u{1f1f7}"),
+    ("couch_and_lamp", "\u{1f6cb}"),
+    ("counterclockwise_arrows_button", "\u{1f504}"),
+    ("couple", "\u{1f46b}"),
+    ("couple_with_heart", "\u{1f491}"),
+    (
+        "couple_with_heart_man_man",
+        "\u{1f468}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f468}",
+    ),
+    (
+        "couple_with_heart_woman_man",
+        "\u{1f469}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f468}",
+    ),
+    (
+        "couple_with_heart_woman_woman",
+        "\u{1f469}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f469}",
+    ),
+    ("couplekiss", "\u{1f48f}"),
+    ("cow", "\u{1f42e}"),
+    ("cow2", "\u{1f404}"),
+    ("cow_face", "\u{1f42e}"),
+    ("cowboy_hat_face", "\u{1f920}"),
+    ("crab", "\u{1f980}"),
+    ("crayon", "\u{1f58d}"),
+    ("credit_card", "\u{1f4b3}"),
+    ("crescent_moon", "\u{1f319}"),
+    ("cricket", "\u{1f997}"),
+    ("cricket_bat_and_ball", "\u{1f3cf}"),
+    ("cricket_game", "\u{1f3cf}"),
+    ("croatia", "\u{1f1ed}\u{1f1f7}"),
+    ("crocodile", "\u{1f40a}"),
+    ("croissant", "\u{1f950}"),
+    ("cross_mark", "\u{274c}"),
+    ("cross_mark_button", "\u{274e}"),
+    ("crossed_fingers", "\u{1f91e}"),
+    ("crossed_fingers_dark_skin_tone", "\u{1f91e}\u{1f3ff}"),
+    ("crossed_fingers_light_skin_tone", "\u{1f91e}\u{1f3fb}"),
+    (
+        "crossed_fingers_medium-dark_skin_tone",
+        "\u{1f91e}\u{1f3fe}",
+    ),
+    (
+        "crossed_fingers_medium-light_skin_tone",
+        "\u{1f91e}\u{1f3fc}",
+    ),
+    ("crossed_fingers_medium_skin_tone", "\u{1f91e}\u{1f3fd}"),
+    ("crossed_flags", "\u{1f38c}"),
+    ("crossed_swords", "\u{2694}"),
+    ("crown", "\u{1f451}"),
+    ("cry", "\u{1f622}"),
+    ("crying_cat_face", "\u{1f63f}"),
+    ("crying_face", "\u{1f622}"),
+    ("crystal_ball", "\u{1f52e}"),
+    ("cuba", "\u{1f1e8}\u{1f1fa}"),
+    ("cucumber", "\u{1f952}"),
+    ("cup_with_straw", "\u{1f964}"),
+    ("cupcake", "\u{1f9c1}"),
+    ("cupid", "\u{1f498}"),
+    ("curling_stone", "\u{1f94c}"),
+    ("curly-haired_man", "\u{1f468}\u{200d}\u{1f9b1}"),
+    ("curly-haired_woman", "\u{1f469}\u{200d}\u{1f9b1}"),
+    ("curly_hair", "\u{1f9b1}"),
+    ("curly_loop", "\u{27b0}"),
+    ("currency_exchange", "\u{1f4b1}"),
+    ("curry", "\u{1f35b}"),
+    ("curry_rice", "\u{1f35b}"),
+    ("custard", "\u{1f36e}"),
+    ("customs", "\u{1f6c3}"),
+    ("cut_of_meat", "\u{1f969}"),
+    ("cyclone", "\u{1f300}"),
+    ("cyprus", "\u{1f1e8}\u{1f1fe}"),
+    ("czechia", "\u{1f1e8}\u{1f1ff}"),
+    ("dagger", "\u{1f5e1}"),
+    ("dagger_knife", "\u{1f5e1}"),
+    ("dancer", "\u{1f483}"),
+    ("dancers", "\u{1f46f}"),
+    ("dango", "\u{1f361}"),
+    ("dark_skin_tone", "\u{1f3ff}"),
+    ("dark_sunglasses", "\u{1f576}"),
+    ("dart", "\u{1f3af}"),
+    ("dash", "\u{1f4a8}"),
+    ("dashing_away", "\u{1f4a8}"),
+    ("date", "\u{1f4c5}"),
+    ("deaf_person", "\u{1f9cf}"),
+    ("deciduous_tree", "\u{1f333}"),
+    ("deer", "\u{1f98c}"),
+    ("delivery_truck", "\u{1f69a}"),
+    ("denmark", "\u{1f1e9}\u{1f1f0}"),
+    ("department_store", "\u{1f3ec}"),
+    ("derelict_house", "\u{1f3da}"),
+    ("derelict_house_building", "\u{1f3da}"),
+    ("desert", "\u{1f3dc}"),
+    ("desert_island", "\u{1f3dd}"),
+    ("desktop_computer", "\u{1f5a5}"),
+    ("detective", "\u{1f575}"),
+    ("detective_dark_skin_tone", "\u{1f575}\u{1f3ff}"),
+    ("detective_light_skin_tone", "\u{1f575}\u{1f3fb}"),
+    ("detective_medium-dark_skin_tone", "\u{1f575}\u{1f3fe}"),
+    ("detective_medium-light_skin_tone", "\u{1f575}\u{1f3fc}"),
+    ("detective_medium_skin_tone", "\u{1f575}\u{1f3fd}"),
+    ("diamond_shape_with_a_dot_inside", "\u{1f4a0}"),
+    ("diamond_suit", "\u{2666}"),
+    ("diamond_with_a_dot", "\u{1f4a0}"),
+    ("diamonds", "\u{2666}"),
+    ("diego_garcia", "\u{1f1e9}\u{1f1ec}"),
+    ("dim_button", "\u{1f505}"),
+    ("direct_hit", "\u{1f3af}"),
+    ("disappointed", "\u{1f61e}"),
+    ("disappointed_face", "\u{1f61e}"),
+    ("disappointed_relieved", "\u{1f625}"),
+    ("diving_mask", "\u{1f93f}"),
+    ("diya_lamp", "\u{1fa94}"),
+    ("dizzy", "\u{1f4ab}"),
+    ("dizzy_face", "\u{1f635}"),
+    ("djibouti", "\u{1f1e9}\u{1f1ef}"),
+    ("dna", "\u{1f9ec}"),
+    ("do_not_litter", "\u{1f6af}"),
+    ("dog", "\u{1f436}"),
+    ("dog2", "\u{1f415}"),
+    ("dog_face", "\u{1f436}"),
+    ("dollar", "\u{1f4b5}"),
+    ("dollar_banknote", "\u{1f4b5}"),
+    ("dolls", "\u{1f38e}"),
+    ("dolphin", "\u{1f42c}"),
+    ("dominica", "\u{1f1e9}\u{1f1f2}"),
+    ("dominican_republic", "\u{1f1e9}\u{1f1f4}"),
+    ("door", "\u{1f6aa}"),
+    ("dotted_six-pointed_star", "\u{1f52f}"),
+    ("double_curly_loop", "\u{27bf}"),
+    ("double_exclamation_mark", "\u{203c}"),
+    ("double_vertical_bar", "\u{23f8}"),
+    ("doughnut", "\u{1f369}"),
+    ("dove", "\u{1f54a}"),
+    ("dove_of_peace", "\u{1f54a}"),
+    ("down-left_arrow", "\u{2199}"),
+    ("down-right_arrow", "\u{2198}"),
+    ("down_arrow", "\u{2b07}"),
+    ("downcast_face_with_sweat", "\u{1f613}"),
+    ("downwards_button", "\u{1f53d}"),
+    ("dragon", "\u{1f409}"),
+    ("dragon_face", "\u{1f432}"),
+    ("dress", "\u{1f457}"),
+    ("dromedary_camel", "\u{1f42a}"),
+    ("drooling_face", "\u{1f924}"),
+    ("drop_of_blood", "\u{1fa78}"),
+    ("droplet", "\u{1f4a7}"),
+    ("drum", "\u{1f941}"),
+    ("duck", "\u{1f986}"),
+    ("dumpling", "\u{1f95f}"),
+    ("dvd", "\u{1f4c0}"),
+    ("e-mail", "\u{1f4e7}"),
+    ("e__mail", "\u{1f4e7}"),
+    ("eagle", "\u{1f985}"),
+    ("ear", "\u{1f442}"),
+    ("ear_dark_skin_tone", "\u{1f442}\u{1f3ff}"),
+    ("ear_light_skin_tone", "\u{1f442}\u{1f3fb}"),
+    ("ear_medium-dark_skin_tone", "\u{1f442}\u{1f3fe}"),
+    ("ear_medium-light_skin_tone", "\u{1f442}\u{1f3fc}"),
+    ("ear_medium_skin_tone", "\u{1f442}\u{1f3fd}"),
+    ("ear_of_corn", "\u{1f33d}"),
+    ("ear_of_rice", "\u{1f33e}"),
+    ("ear_with_hearing_aid", "\u{1f9bb}"),
+    ("earth_africa", "\u{1f30d}"),
+    ("earth_americas", "\u{1f30e}"),
+    ("earth_asia", "\u{1f30f}"),
+    ("ecuador", "\u{1f1ea}\u{1f1e8}"),
+    ("egg", "\u{1f373}"),
+    ("eggplant", "\u{1f346}"),
+    ("egypt", "\u{1f1ea}\u{1f1ec}"),
+    ("eight", "\u{38}\u{fe0f}\u{20e3}"),
+    ("eight-pointed_star", "\u{2734}"),
+    ("eight-spoked_asterisk", "\u{2733}"),
+    ("eight-thirty", "\u{1f563}"),
+    ("eight_pointed_black_star", "\u{2734}"),
+    ("eight_spoked_asterisk", "\u{2733}"),
+    ("eject_button", "\u{23cf}"),
+    ("eject_symbol", "\u{23cf}"),
+    ("el_salvador", "\u{1f1f8}\u{1f1fb}"),
+    ("electric_plug", "\u{1f50c}"),
+    ("elephant", "\u{1f418}"),
+    ("eleven-thirty", "\u{1f566}"),
+    ("elf", "\u{1f9dd}"),
+    ("elf_dark_skin_tone", "\u{1f9dd}\u{1f3ff}"),
+    ("elf_light_skin_tone", "\u{1f9dd}\u{1f3fb}"),
+    ("elf_medium-dark_skin_tone", "\u{1f9dd}\u{1f3fe}"),
+    ("elf_medium-light_skin_tone", "\u{1f9dd}\u{1f3fc}"),
+    ("elf_medium_skin_tone", "\u{1f9dd}\u{1f3fd}"),
+    ("email", "\u{2709}"),
+    ("emoji_modifier_fitzpatrick_type__1__2", "\u{1f3fb}"),
+    ("emoji_modifier_fitzpatrick_type__3", "\u{1f3fc}"),
+    ("emoji_modifier_fitzpatrick_type__4", "\u{1f3fd}"),
+    ("emoji_modifier_fitzpatrick_type__5", "\u{1f3fe}"),
+    ("emoji_modifier_fitzpatrick_type__6", "\u{1f3ff}"),
+    ("end", "\u{1f51a}"),
+    ("end_arrow", "\u{1f51a}"),
+    (
+        "england",
+        "\u{1f3f4}\u{e0067}\u{e0062}\u{e0065}\u{e006e}\u{e0067}\u{e007f}",
+    ),
+    ("envelope", "\u{2709}"),
+    ("envelope_with_arrow", "\u{1f4e9}"),
+    ("equatorial_guinea", "\u{1f1ec}\u{1f1f6}"),
+    ("eritrea", "\u{1f1ea}\u{1f1f7}"),
+    ("estonia", "\u{1f1ea}\u{1f1ea}"),
+    ("ethiopia", "\u{1f1ea}\u{1f1f9}"),
+    ("euro", "\u{1f4b6}"),
+    ("euro_banknote", "\u{1f4b6}"),
+    ("european_castle", "\u{1f3f0}"),
+    ("european_post_office", "\u{1f3e4}"),
+    ("european_union", "\u{1f1ea}\u{1f1fa}"),
+    ("evergreen_tree", "\u{1f332}"),
+    ("ewe", "\u{1f411}"),
+    ("exclamation", "\u{2757}"),
+    ("exclamation_mark", "\u{2757}"),
+    ("exclamation_question_mark", "\u{2049}"),
+    ("exploding_head", "\u{1f92f}"),
+    ("expressionless", "\u{1f611}"),
+    ("expressionless_face", "\u{1f611}"),
+    ("eye", "\u{1f441}"),
+    (
+        "eye_in_speech_bubble",
+        "\u{1f441}\u{fe0f}\u{200d}\u{1f5e8}\u{fe0f}",
+    ),
+    ("eyeglasses", "\u{1f453}"),
+    ("eyes", "\u{1f440}"),
+    ("face_blowing_a_kiss", "\u{1f618}"),
+    ("face_savoring_food", "\u{1f60b}"),
+    ("face_screaming_in_fear", "\u{1f631}"),
+    ("face_vomiting", "\u{1f92e}"),
+    ("face_with_hand_over_mouth", "\u{1f92d}"),
+    ("face_with_head-bandage", "\u{1f915}"),
+    ("face_with_head__bandage", "\u{1f915}"),
+    ("face_with_medical_mask", "\u{1f637}"),
+    ("face_with_monocle", "\u{1f9d0}"),
+    ("face_with_open_mouth", "\u{1f62e}"),
+    ("face_with_raised_eyebrow", "\u{1f928}"),
+    ("face_with_rolling_eyes", "\u{1f644}"),
+    ("face_with_steam_from_nose", "\u{1f624}"),
+    ("face_with_symbols_on_mouth", "\u{1f92c}"),
+    ("face_with_tears_of_joy", "\u{1f602}"),
+    ("face_with_thermometer", "\u{1f912}"),
+    ("face_with_tongue", "\u{1f61b}"),
+    ("face_without_mouth", "\u{1f636}"),
+    ("facepunch", "\u{1f44a}"),
+    ("factory", "\u{1f3ed}"),
+    ("fairy", "\u{1f9da}"),
+    ("fairy_dark_skin_tone", "\u{1f9da}\u{1f3ff}"),
+    ("fairy_light_skin_tone", "\u{1f9da}\u{1f3fb}"),
+    ("fairy_medium-dark_skin_tone", "\u{1f9da}\u{1f3fe}"),
+    ("fairy_medium-light_skin_tone", "\u{1f9da}\u{1f3fc}"),
+    ("fairy_medium_skin_tone", "\u{1f9da}\u{1f3fd}"),
+    ("falafel", "\u{1f9c6}"),
+    ("falkland_islands", "\u{1f1eb}\u{1f1f0}"),
+    ("fallen_leaf", "\u{1f342}"),
+    ("family", "\u{1f46a}"),
+    ("family_man_boy", "\u{1f468}\u{200d}\u{1f466}"),
+    (
+        "family_man_boy_boy",
+        "\u{1f468}\u{200d}\u{1f466}\u{200d}\u{1f466}",
+    ),
+    ("family_man_girl", "\u{1f468}\u{200d}\u{1f467}"),
+    (
+        "family_man_girl_boy",
+        "\u{1f468}\u{200d}\u{1f467}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_girl_girl",
+        "\u{1f468}\u{200d}\u{1f467}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_man_man_boy",
+        "\u{1f468}\u{200d}\u{1f468}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_man_boy_boy",
+        "\u{1f468}\u{200d}\u{1f468}\u{200d}\u{1f466}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_man_girl",
+        "\u{1f468}\u{200d}\u{1f468}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_man_man_girl_boy",
+        "\u{1f468}\u{200d}\u{1f468}\u{200d}\u{1f467}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_man_girl_girl",
+        "\u{1f468}\u{200d}\u{1f468}\u{200d}\u{1f467}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_man_woman_boy",
+        "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_woman_boy_boy",
+        "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f466}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_woman_girl",
+        "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_man_woman_girl_boy",
+        "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_man_woman_girl_girl",
+        "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f467}",
+    ),
+    ("family_woman_boy", "\u{1f469}\u{200d}\u{1f466}"),
+    (
+        "family_woman_boy_boy",
+        "\u{1f469}\u{200d}\u{1f466}\u{200d}\u{1f466}",
+    ),
+    ("family_woman_girl", "\u{1f469}\u{200d}\u{1f467}"),
+    (
+        "family_woman_girl_boy",
+        "\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_woman_girl_girl",
+        "\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_woman_woman_boy",
+        "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_woman_woman_boy_boy",
+        "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f466}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_woman_woman_girl",
+        "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}",
+    ),
+    (
+        "family_woman_woman_girl_boy",
+        "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f466}",
+    ),
+    (
+        "family_woman_woman_girl_girl",
+        "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}\u{200d}\u{1f467}",
+    ),
+    ("faroe_islands", "\u{1f1eb}\u{1f1f4}"),
+    ("fast-forward_button", "\u{23e9}"),
+    ("fast_down_button", "\u{23ec}"),
+    ("fast_forward", "\u{23e9}"),
+    ("fast_reverse_button", "\u{23ea}"),
+    ("fast_up_button", "\u{23eb}"),
+    ("fax", "\u{1f4e0}"),
+    ("fax_machine", "\u{1f4e0}"),
+    ("fearful", "\u{1f628}"),
+    ("fearful_face", "\u{1f628}"),
+    ("feet", "\u{1f43e}"),
+    ("female_sign", "\u{2640}"),
+    ("ferris_wheel", "\u{1f3a1}"),
+    ("ferry", "\u{26f4}"),
+    ("field_hockey", "\u{1f3d1}"),
+    ("field_hockey_stick_and_ball", "\u{1f3d1}"),
+    ("fiji", "\u{1f1eb}\u{1f1ef}"),
+    ("file_cabinet", "\u{1f5c4}"),
+    ("file_folder", "\u{1f4c1}"),
+    ("film_frames", "\u{1f39e}"),
+    ("film_projector", "\u{1f4fd}"),
+    ("finland", "\u{1f1eb}\u{1f1ee}"),
+    ("fire", "\u{1f525}"),
+    ("fire_engine", "\u{1f692}"),
+    ("fire_extinguisher", "\u{1f9ef}"),
+    ("firecracker", "\u{1f9e8}"),
+    ("fireworks", "\u{1f386}"),
+    ("first_quarter_moon", "\u{1f313}"),
+    ("first_quarter_moon_face", "\u{1f31b}"),
+    ("first_quarter_moon_with_face", "\u{1f31b}"),
+    ("fish", "\u{1f41f}"),
+    ("fish_cake", "\u{1f365}"),
+    ("fish_cake_with_swirl", "\u{1f365}"),
+    ("fishing_pole", "\u{1f3a3}"),
+    ("fishing_pole_and_fish", "\u{1f3a3}"),
+    ("fist", "\u{270a}"),
+    ("five", "\u{35}\u{fe0f}\u{20e3}"),
+    ("five-thirty", "\u{1f560}"),
+    ("flag_for_afghanistan", "\u{1f1e6}\u{1f1eb}"),
+    ("flag_for_albania", "\u{1f1e6}\u{1f1f1}"),
+    ("flag_for_algeria", "\u{1f1e9}\u{1f1ff}"),
+    ("flag_for_american_samoa", "\u{1f1e6}\u{1f1f8}"),
+    ("flag_for_andorra", "\u{1f1e6}\u{1f1e9}"),
+    ("flag_for_angola", "\u{1f1e6}\u{1f1f4}"),
+    ("flag_for_anguilla", "\u{1f1e6}\u{1f1ee}"),
+    ("flag_for_antarctica", "\u{1f1e6}\u{1f1f6}"),
+    ("flag_for_argentina", "\u{1f1e6}\u{1f1f7}"),
+    ("flag_for_armenia", "\u{1f1e6}\u{1f1f2}"),
+    ("flag_for_aruba", "\u{1f1e6}\u{1f1fc}"),
+    ("flag_for_ascension_island", "\u{1f1e6}\u{1f1e8}"),
+    ("flag_for_australia", "\u{1f1e6}\u{1f1fa}"),
+    ("flag_for_austria", "\u{1f1e6}\u{1f1f9}"),
+    ("flag_for_azerbaijan", "\u{1f1e6}\u{1f1ff}"),
+    ("flag_for_bahamas", "\u{1f1e7}\u{1f1f8}"),
+    ("flag_for_bahrain", "\u{1f1e7}\u{1f1ed}"),
+    ("flag_for_bangladesh", "\u{1f1e7}\u{1f1e9}"),
+    ("flag_for_barbados", "\u{1f1e7}\u{1f1e7}"),
+    ("flag_for_belarus", "\u{1f1e7}\u{1f1fe}"),
+    ("flag_for_belgium", "\u{1f1e7}\u{1f1ea}"),
+    ("flag_for_belize", "\u{1f1e7}\u{1f1ff}"),
+    ("flag_for_benin", "\u{1f1e7}\u{1f1ef}"),
+    ("flag_for_bermuda", "\u{1f1e7}\u{1f1f2}"),
+    ("flag_for_bhutan", "\u{1f1e7}\u{1f1f9}"),
+    ("flag_for_bolivia", "\u{1f1e7}\u{1f1f4}"),
+    ("flag_for_botswana", "\u{1f1e7}\u{1f1fc}"),
+    ("flag_for_bouvet_island", "\u{1f1e7}\u{1f1fb}"),
+    ("flag_for_brazil", "\u{1f1e7}\u{1f1f7}"),
+    (
+        "flag_for_british_indian_ocean_territory",
+        "\u{1f1ee}\u{1f1f4}",
+    ),
+    ("flag_for_british_virgin_islands", "\u{1f1fb}\u{1f1ec}"),
+    ("flag_for_brunei", "\u{1f1e7}\u{1f1f3}"),
+    ("flag_for_bulgaria", "\u{1f1e7}\u{1f1ec}"),
+    ("flag_for_burkina_faso", "\u{1f1e7}\u{1f1eb}"),
+    ("flag_for_burundi", "\u{1f1e7}\u{1f1ee}"),
+    ("flag_for_cambodia", "\u{1f1f0}\u{1f1ed}"),
+    ("flag_for_cameroon", "\u{1f1e8}\u{1f1f2}"),
+    ("flag_for_canada", "\u{1f1e8}\u{1f1e6}"),
+    ("flag_for_canary_islands", "\u{1f1ee}\u{1f1e8}"),
+    ("flag_for_cape_verde", "\u{1f1e8}\u{1f1fb}"),
+    ("flag_for_caribbean_netherlands", "\u{1f1e7}\u{1f1f6}"),
+    ("flag_for_cayman_islands", "\u{1f1f0}\u{1f1fe}"),
+    ("flag_for_central_african_republic", "\u{1f1e8}\u{1f1eb}"),
+    ("flag_for_chad", "\u{1f1f9}\u{1f1e9}"),
+    ("flag_for_chile", "\u{1f1e8}\u{1f1f1}"),
+    ("flag_for_china", "\u{1f1e8}\u{1f1f3}"),
+    ("flag_for_christmas_island", "\u{1f1e8}\u{1f1fd}"),
+    ("flag_for_clipperton_island", "\u{1f1e8}\u{1f1f5}"),
+    ("flag_for_cocos__islands", "\u{1f1e8}\u{1f1e8}"),
+    ("flag_for_colombia", "\u{1f1e8}\u{1f1f4}"),
+    ("flag_for_comoros", "\u{1f1f0}\u{1f1f2}"),
+    ("flag_for_congo____brazzaville", "\u{1f1e8}\u{1f1ec}"),
+    ("flag_for_congo____kinshasa", "\u{1f1e8}\u{1f1e9}"),
+    ("flag_for_cook_islands", "\u{1f1e8}\u{1f1f0}"),
+    ("flag_for_costa_rica", "\u{1f1e8}\u{1f1f7}"),
+    ("flag_for_croatia", "\u{1f1ed}\u{1f1f7}"),
+    ("flag_for_cuba", "\u{1f1e8}\u{1f1fa}"),
+    ("flag_for_cyprus", "\u{1f1e8}\u{1f1fe}"),
+    ("flag_for_czech_republic", "\u{1f1e8}\u{1f1ff}"),
+    ("flag_for_denmark", "\u{1f1e9}\u{1f1f0}"),
+    ("flag_for_diego_garcia", "\u{1f1e9}\u{1f1ec}"),
+    ("flag_for_djibouti", "\u{1f1e9}\u{1f1ef}"),
+    ("flag_for_dominica", "\u{1f1e9}\u{1f1f2}"),
+    ("flag_for_dominican_republic", "\u{1f1e9}\u{1f1f4}"),
+    ("flag_for_ecuador", "\u{1f1ea}\u{1f1e8}"),
+    ("flag_for_egypt", "\u{1f1ea}\u{1f1ec}"),
+    ("flag_for_el_salvador", "\u{1f1f8}\u{1f1fb}"),
+    ("flag_for_equatorial_guinea", "\u{1f1ec}\u{1f1f6}"),
+    ("flag_for_eritrea", "\u{1f1ea}\u{1f1f7}"),
+    ("flag_for_estonia", "\u{1f1ea}\u{1f1ea}"),
+    ("flag_for_ethiopia", "\u{1f1ea}\u{1f1f9}"),
+    ("flag_for_european_union", "\u{1f1ea}\u{1f1fa}"),
+    ("flag_for_falkland_islands", "\u{1f1eb}\u{1f1f0}"),
+    ("flag_for_faroe_islands", "\u{1f1eb}\u{1f1f4}"),
+    ("flag_for_fiji", "\u{1f1eb}\u{1f1ef}"),
+    ("flag_for_finland", "\u{1f1eb}\u{1f1ee}"),
+    ("flag_for_france", "\u{1f1eb}\u{1f1f7}"),
+    ("flag_for_french_guiana", "\u{1f1ec}\u{1f1eb}"),
+    ("flag_for_french_polynesia", "\u{1f1f5}\u{1f1eb}"),
+    ("flag_for_french_southern_territories", "\u{1f1f9}\u{1f1eb}"),
+    ("flag_for_gabon", "\u{1f1ec}\u{1f1e6}"),
+    ("flag_for_gambia", "\u{1f1ec}\u{1f1f2}"),
+    ("flag_for_georgia", "\u{1f1ec}\u{1f1ea}"),
+    ("flag_for_germany", "\u{1f1e9}\u{1f1ea}"),
+    ("flag_for_ghana", "\u{1f1ec}\u{1f1ed}"),
+    ("flag_for_gibraltar", "\u{1f1ec}\u{1f1ee}"),
+    ("flag_for_greece", "\u{1f1ec}\u{1f1f7}"),
+    ("flag_for_greenland", "\u{1f1ec}\u{1f1f1}"),
+    ("flag_for_grenada", "\u{1f1ec}\u{1f1e9}"),
+    ("flag_for_guadeloupe", "\u{1f1ec}\u{1f1f5}"),
+    ("flag_for_guam", "\u{1f1ec}\u{1f1fa}"),
+    ("flag_for_guatemala", "\u{1f1ec}\u{1f1f9}"),
+    ("flag_for_guernsey", "\u{1f1ec}\u{1f1ec}"),
+    ("flag_for_guinea", "\u{1f1ec}\u{1f1f3}"),
+    ("flag_for_guinea__bissau", "\u{1f1ec}\u{1f1fc}"),
+    ("flag_for_guyana", "\u{1f1ec}\u{1f1fe}"),
+    ("flag_for_haiti", "\u{1f1ed}\u{1f1f9}"),
+    ("flag_for_honduras", "\u{1f1ed}\u{1f1f3}"),
+    ("flag_for_hong_kong", "\u{1f1ed}\u{1f1f0}"),
+    ("flag_for_hungary", "\u{1f1ed}\u{1f1fa}"),
+    ("flag_for_iceland", "\u{1f1ee}\u{1f1f8}"),
+    ("flag_for_india", "\u{1f1ee}\u{1f1f3}"),
+    ("flag_for_indonesia", "\u{1f1ee}\u{1f1e9}"),
+    ("flag_for_iran", "\u{1f1ee}\u{1f1f7}"),
+    ("flag_for_iraq", "\u{1f1ee}\u{1f1f6}"),
+    ("flag_for_ireland", "\u{1f1ee}\u{1f1ea}"),
+    ("flag_for_isle_of_man", "\u{1f1ee}\u{1f1f2}"),
+    ("flag_for_israel", "\u{1f1ee}\u{1f1f1}"),
+    ("flag_for_italy", "\u{1f1ee}\u{1f1f9}"),
+    ("flag_for_jamaica", "\u{1f1ef}\u{1f1f2}"),
+    ("flag_for_japan", "\u{1f1ef}\u{1f1f5}"),
+    ("flag_for_jersey", "\u{1f1ef}\u{1f1ea}"),
+    ("flag_for_jordan", "\u{1f1ef}\u{1f1f4}"),
+    ("flag_for_kazakhstan", "\u{1f1f0}\u{1f1ff}"),
+    ("flag_for_kenya", "\u{1f1f0}\u{1f1ea}"),
+    ("flag_for_kiribati", "\u{1f1f0}\u{1f1ee}"),
+    ("flag_for_kosovo", "\u{1f1fd}\u{1f1f0}"),
+    ("flag_for_kuwait", "\u{1f1f0}\u{1f1fc}"),
+    ("flag_for_kyrgyzstan", "\u{1f1f0}\u{1f1ec}"),
+    ("flag_for_laos", "\u{1f1f1}\u{1f1e6}"),
+    ("flag_for_latvia", "\u{1f1f1}\u{1f1fb}"),
+    ("flag_for_lebanon", "\u{1f1f1}\u{1f1e7}"),
+    ("flag_for_lesotho", "\u{1f1f1}\u{1f1f8}"),
+    ("flag_for_liberia", "\u{1f1f1}\u{1f1f7}"),
+    ("flag_for_libya", "\u{1f1f1}\u{1f1fe}"),
+    ("flag_for_liechtenstein", "\u{1f1f1}\u{1f1ee}"),
+    ("flag_for_lithuania", "\u{1f1f1}\u{1f1f9}"),
+    ("flag_for_luxembourg", "\u{1f1f1}\u{1f1fa}"),
+    ("flag_for_macau", "\u{1f1f2}\u{1f1f4}"),
+    ("flag_for_macedonia", "\u{1f1f2}\u{1f1f0}"),
+    ("flag_for_madagascar", "\u{1f1f2}\u{1f1ec}"),
+    ("flag_for_malawi", "\u{1f1f2}\u{1f1fc}"),
+    ("flag_for_malaysia", "\u{1f1f2}\u{1f1fe}"),
+    ("flag_for_maldives", "\u{1f1f2}\u{1f1fb}"),
+    ("flag_for_mali", "\u{1f1f2}\u{1f1f1}"),
+    ("flag_for_malta", "\u{1f1f2}\u{1f1f9}"),
+    ("flag_for_marshall_islands", "\u{1f1f2}\u{1f1ed}"),
+    ("flag_for_martinique", "\u{1f1f2}\u{1f1f6}"),
+    ("flag_for_mauritania", "\u{1f1f2}\u{1f1f7}"),
+    ("flag_for_mauritius", "\u{1f1f2}\u{1f1fa}"),
+    ("flag_for_mayotte", "\u{1f1fe}\u{1f1f9}"),
+    ("flag_for_mexico", "\u{1f1f2}\u{1f1fd}"),
+    ("flag_for_micronesia", "\u{1f1eb}\u{1f1f2}"),
+    ("flag_for_moldova", "\u{1f1f2}\u{1f1e9}"),
+    ("flag_for_monaco", "\u{1f1f2}\u{1f1e8}"),
+    ("flag_for_mongolia", "\u{1f1f2}\u{1f1f3}"),
+    ("flag_for_montenegro", "\u{1f1f2}\u{1f1ea}"),
+    ("flag_for_montserrat", "\u{1f1f2}\u{1f1f8}"),
+    ("flag_for_morocco", "\u{1f1f2}\u{1f1e6}"),
+    ("flag_for_mozambique", "\u{1f1f2}\u{1f1ff}"),
+    ("flag_for_myanmar", "\u{1f1f2}\u{1f1f2}"),
+    ("flag_for_namibia", "\u{1f1f3}\u{1f1e6}"),
+    ("flag_for_nauru", "\u{1f1f3}\u{1f1f7}"),
+    ("flag_for_nepal", "\u{1f1f3}\u{1f1f5}"),
+    ("flag_for_netherlands", "\u{1f1f3}\u{1f1f1}"),
+    ("flag_for_new_caledonia", "\u{1f1f3}\u{1f1e8}"),
+    ("flag_for_new_zealand", "\u{1f1f3}\u{1f1ff}"),
+    ("flag_for_nicaragua", "\u{1f1f3}\u{1f1ee}"),
+    ("flag_for_niger", "\u{1f1f3}\u{1f1ea}"),
+    ("flag_for_nigeria", "\u{1f1f3}\u{1f1ec}"),
+    ("flag_for_niue", "\u{1f1f3}\u{1f1fa}"),
+    ("flag_for_norfolk_island", "\u{1f1f3}\u{1f1eb}"),
+    ("flag_for_north_korea", "\u{1f1f0}\u{1f1f5}"),
+    ("flag_for_northern_mariana_islands", "\u{1f1f2}\u{1f1f5}"),
+    ("flag_for_norway", "\u{1f1f3}\u{1f1f4}"),
+    ("flag_for_oman", "\u{1f1f4}\u{1f1f2}"),
+    ("flag_for_pakistan", "\u{1f1f5}\u{1f1f0}"),
+    ("flag_for_palau", "\u{1f1f5}\u{1f1fc}"),
+    ("flag_for_palestinian_territories", "\u{1f1f5}\u{1f1f8}"),
+    ("flag_for_panama", "\u{1f1f5}\u{1f1e6}"),
+    ("flag_for_papua_new_guinea", "\u{1f1f5}\u{1f1ec}"),
+    ("flag_for_paraguay", "\u{1f1f5}\u{1f1fe}"),
+    ("flag_for_peru", "\u{1f1f5}\u{1f1ea}"),
+    ("flag_for_philippines", "\u{1f1f5}\u{1f1ed}"),
+    ("flag_for_pitcairn_islands", "\u{1f1f5}\u{1f1f3}"),
+    ("flag_for_poland", "\u{1f1f5}\u{1f1f1}"),
+    ("flag_for_portugal", "\u{1f1f5}\u{1f1f9}"),
+    ("flag_for_puerto_rico", "\u{1f1f5}\u{1f1f7}"),
+    ("flag_for_qatar", "\u{1f1f6}\u{1f1e6}"),
+    ("flag_for_romania", "\u{1f1f7}\u{1f1f4}"),
+    ("flag_for_russia", "\u{1f1f7}\u{1f1fa}"),
+    ("flag_for_rwanda", "\u{1f1f7}\u{1f1fc}"),
+    ("flag_for_samoa", "\u{1f1fc}\u{1f1f8}"),
+    ("flag_for_san_marino", "\u{1f1f8}\u{1f1f2}"),
+    ("flag_for_saudi_arabia", "\u{1f1f8}\u{1f1e6}"),
+    ("flag_for_senegal", "\u{1f1f8}\u{1f1f3}"),
+    ("flag_for_serbia", "\u{1f1f7}\u{1f1f8}"),
+    ("flag_for_seychelles", "\u{1f1f8}\u{1f1e8}"),
+    ("flag_for_sierra_leone", "\u{1f1f8}\u{1f1f1}"),
+    ("flag_for_singapore", "\u{1f1f8}\u{1f1ec}"),
+    ("flag_for_sint_maarten", "\u{1f1f8}\u{1f1fd}"),
+    ("flag_for_slovakia", "\u{1f1f8}\u{1f1f0}"),
+    ("flag_for_slovenia", "\u{1f1f8}\u{1f1ee}"),
+    ("flag_for_solomon_islands", "\u{1f1f8}\u{1f1e7}"),
+    ("flag_for_somalia", "\u{1f1f8}\u{1f1f4}"),
+    ("flag_for_south_africa", "\u{1f1ff}\u{1f1e6}"),
+    ("flag_for_south_korea", "\u{1f1f0}\u{1f1f7}"),
+    ("flag_for_south_sudan", "\u{1f1f8}\u{1f1f8}"),
+    ("flag_for_spain", "\u{1f1ea}\u{1f1f8}"),
+    ("flag_for_sri_lanka", "\u{1f1f1}\u{1f1f0}"),
+    ("flag_for_sudan", "\u{1f1f8}\u{1f1e9}"),
+    ("flag_for_suriname", "\u{1f1f8}\u{1f1f7}"),
+    ("flag_for_swaziland", "\u{1f1f8}\u{1f1ff}"),
+    ("flag_for_sweden", "\u{1f1f8}\u{1f1ea}"),
+    ("flag_for_switzerland", "\u{1f1e8}\u{1f1ed}"),
+    ("flag_for_syria", "\u{1f1f8}\u{1f1fe}"),
+    ("flag_for_taiwan", "\u{1f1f9}\u{1f1fc}"),
+    ("flag_for_tajikistan", "\u{1f1f9}\u{1f1ef}"),
+    ("flag_for_tanzania", "\u{1f1f9}\u{1f1ff}"),
+    ("flag_for_thailand", "\u{1f1f9}\u{1f1ed}"),
+    ("flag_for_timor__leste", "\u{1f1f9}\u{1f1f1}"),
+    ("flag_for_togo", "\u{1f1f9}\u{1f1ec}"),
+    ("flag_for_tokelau", "\u{1f1f9}\u{1f1f0}"),
+    ("flag_for_tonga", "\u{1f1f9}\u{1f1f4}"),
+    ("flag_for_tristan_da_cunha", "\u{1f1f9}\u{1f1e6}"),
+    ("flag_for_tunisia", "\u{1f1f9}\u{1f1f3}"),
+    ("flag_for_turkey", "\u{1f1f9}\u{1f1f7}"),
+    ("flag_for_turkmenistan", "\u{1f1f9}\u{1f1f2}"),
+    ("flag_for_tuvalu", "\u{1f1f9}\u{1f1fb}"),
+    ("flag_for_uganda", "\u{1f1fa}\u{1f1ec}"),
+    ("flag_for_ukraine", "\u{1f1fa}\u{1f1e6}"),
+    ("flag_for_united_arab_emirates", "\u{1f1e6}\u{1f1ea}"),
+    ("flag_for_united_kingdom", "\u{1f1ec}\u{1f1e7}"),
+    ("flag_for_united_states", "\u{1f1fa}\u{1f1f8}"),
+    ("flag_for_uruguay", "\u{1f1fa}\u{1f1fe}"),
+    ("flag_for_uzbekistan", "\u{1f1fa}\u{1f1ff}"),
+    ("flag_for_vanuatu", "\u{1f1fb}\u{1f1fa}"),
+    ("flag_for_vatican_city", "\u{1f1fb}\u{1f1e6}"),
+    ("flag_for_venezuela", "\u{1f1fb}\u{1f1ea}"),
+    ("flag_for_vietnam", "\u{1f1fb}\u{1f1f3}"),
+    ("flag_for_western_sahara", "\u{1f1ea}\u{1f1ed}"),
+    ("flag_for_yemen", "\u{1f1fe}\u{1f1ea}"),
+    ("flag_for_zambia", "\u{1f1ff}\u{1f1f2}"),
+    ("flag_for_zimbabwe", "\u{1f1ff}\u{1f1fc}"),
+    ("flag_in_hole", "\u{26f3}"),
+    ("flags", "\u{1f38f}"),
+    ("flamingo", "\u{1f9a9}"),
+    ("flashlight", "\u{1f526}"),
+    ("flat_shoe", "\u{1f97f}"),
+    ("fleur-de-lis", "\u{269c}"),
+    ("fleur__de__lis", "\u{269c}"),
+    ("flexed_biceps", "\u{1f4aa}"),
+    ("flexed_biceps_dark_skin_tone", "\u{1f4aa}\u{1f3ff}"),
+    ("flexed_biceps_light_skin_tone", "\u{1f4aa}\u{1f3fb}"),
+    ("flexed_biceps_medium-dark_skin_tone", "\u{1f4aa}\u{1f3fe}"),
+    ("flexed_biceps_medium-light_skin_tone", "\u{1f4aa}\u{1f3fc}"),
+    ("flexed_biceps_medium_skin_tone", "\u{1f4aa}\u{1f3fd}"),
+    ("flipper", "\u{1f42c}"),
+    ("floppy_disk", "\u{1f4be}"),
+    ("flower_playing_cards", "\u{1f3b4}"),
+    ("flushed", "\u{1f633}"),
+    ("flushed_face", "\u{1f633}"),
+    ("flying_disc", "\u{1f94f}"),
+    ("flying_saucer", "\u{1f6f8}"),
+    ("fog", "\u{1f32b}"),
+    ("foggy", "\u{1f301}"),
+    ("folded_hands", "\u{1f64f}"),
+    ("folded_hands_dark_skin_tone", "\u{1f64f}\u{1f3ff}"),
+    ("folded_hands_light_skin_tone", "\u{1f64f}\u{1f3fb}"),
+    ("folded_hands_medium-dark_skin_tone", "\u{1f64f}\u{1f3fe}"),
+    ("folded_hands_medium-light_skin_tone", "\u{1f64f}\u{1f3fc}"),
+    ("folded_hands_medium_skin_tone", "\u{1f64f}\u{1f3fd}"),
+    ("foot", "\u{1f9b6}"),
+    ("football", "\u{1f3c8}"),
+    ("footprints", "\u{1f463}"),
+    ("fork_and_knife", "\u{1f374}"),
+    ("fork_and_knife_with_plate", "\u{1f37d}"),
+    ("fortune_cookie", "\u{1f960}"),
+    ("fountain", "\u{26f2}"),
+    ("fountain_pen", "\u{1f58b}"),
+    ("four", "\u{34}\u{fe0f}\u{20e3}"),
+    ("four-thirty", "\u{1f55f}"),
+    ("four_leaf_clover", "\u{1f340}"),
+    ("fox_face", "\u{1f98a}"),
+    ("frame_with_picture", "\u{1f5bc}"),
+    ("framed_picture", "\u{1f5bc}"),
+    ("france", "\u{1f1eb}\u{1f1f7}"),
+    ("free", "\u{1f193}"),
+    ("free_button", "\u{1f193}"),
+    ("french_fries", "\u{1f35f}"),
+    ("french_guiana", "\u{1f1ec}\u{1f1eb}"),
+    ("french_polynesia", "\u{1f1f5}\u{1f1eb}"),
+    ("french_southern_territories", "\u{1f1f9}\u{1f1eb}"),
+    ("fried_shrimp", "\u{1f364}"),
+    ("fries", "\u{1f35f}"),
+    ("frog", "\u{1f438}"),
+    ("frog_face", "\u{1f438}"),
+    ("front-facing_baby_chick", "\u{1f425}"),
+    ("frowning", "\u{1f626}"),
+    ("frowning_face", "\u{2639}"),
+    ("frowning_face_with_open_mouth", "\u{1f626}"),
+    ("fuel_pump", "\u{26fd}"),
+    ("fuelpump", "\u{26fd}"),
+    ("full_moon", "\u{1f315}"),
+    ("full_moon_face", "\u{1f31d}"),
+    ("full_moon_with_face", "\u{1f31d}"),
+    ("funeral_urn", "\u{26b1}"),
+    ("gabon", "\u{1f1ec}\u{1f1e6}"),
+    ("gambia", "\u{1f1ec}\u{1f1f2}"),
+    ("game_die", "\u{1f3b2}"),
+    ("garlic", "\u{1f9c4}"),
+    ("gear", "\u{2699}"),
+    ("gem", "\u{1f48e}"),
+    ("gem_stone", "\u{1f48e}"),
+    ("gemini", "\u{264a}"),
+    ("genie", "\u{1f9de}"),
+    ("georgia", "\u{1f1ec}\u{1f1ea}"),
+    ("germany", "\u{1f1e9}\u{1f1ea}"),
+    ("ghana", "\u{1f1ec}\u{1f1ed}"),
+    ("ghost", "\u{1f47b}"),
+    ("gibraltar", "\u{1f1ec}\u{1f1ee}"),
+    ("gift", "\u{1f381}"),
+    ("gift_heart", "\u{1f49d}"),
+    ("giraffe", "\u{1f992}"),
+    ("girl", "\u{1f467}"),
+    ("girl_dark_skin_tone", "\u{1f467}\u{1f3ff}"),
+    ("girl_light_skin_tone", "\u{1f467}\u{1f3fb}"),
+    ("girl_medium-dark_skin_tone", "\u{1f467}\u{1f3fe}"),
+    ("girl_medium-light_skin_tone", "\u{1f467}\u{1f3fc}"),
+    ("girl_medium_skin_tone", "\u{1f467}\u{1f3fd}"),
+    ("glass_of_milk", "\u{1f95b}"),
+    ("glasses", "\u{1f453}"),
+    ("globe_showing_americas", "\u{1f30e}"),
+    ("globe_showing_asia-australia", "\u{1f30f}"),
+    ("globe_showing_europe-africa", "\u{1f30d}"),
+    ("globe_with_meridians", "\u{1f310}"),
+    ("gloves", "\u{1f9e4}"),
+    ("glowing_star", "\u{1f31f}"),
+    ("goal_net", "\u{1f945}"),
+    ("goat", "\u{1f410}"),
+    ("goblin", "\u{1f47a}"),
+    ("goggles", "\u{1f97d}"),
+    ("golf", "\u{26f3}"),
+    ("golfer", "\u{1f3cc}"),
+    ("gorilla", "\u{1f98d}"),
+    ("graduation_cap", "\u{1f393}"),
+    ("grapes", "\u{1f347}"),
+    ("greece", "\u{1f1ec}\u{1f1f7}"),
+    ("green_apple", "\u{1f34f}"),
+    ("green_book", "\u{1f4d7}"),
+    ("green_circle", "\u{1f7e2}"),
+    ("green_heart", "\u{1f49a}"),
+    ("green_salad", "\u{1f957}"),
+    ("green_square", "\u{1f7e9}"),
+    ("greenland", "\u{1f1ec}\u{1f1f1}"),
+    ("grenada", "\u{1f1ec}\u{1f1e9}"),
+    ("grey_exclamation", "\u{2755}"),
+    ("grey_question", "\u{2754}"),
+    ("grimacing", "\u{1f62c}"),
+    ("grimacing_face", "\u{1f62c}"),
+    ("grin", "\u{1f601}"),
+    ("grinning", "\u{1f600}"),
+    ("grinning_cat_face", "\u{1f63a}"),
+    ("grinning_cat_face_with_smiling_eyes", "\u{1f638}"),
+    ("grinning_face", "\u{1f600}"),
+    ("grinning_face_with_big_eyes", "\u{1f603}"),
+    ("grinning_face_with_smiling_eyes", "\u{1f604}"),
+    ("grinning_face_with_sweat", "\u{1f605}"),
+    ("grinning_squinting_face", "\u{1f606}"),
+    ("growing_heart", "\u{1f497}"),
+    ("guadeloupe", "\u{1f1ec}\u{1f1f5}"),
+    ("guam", "\u{1f1ec}\u{1f1fa}"),
+    ("guard", "\u{1f482}"),
+    ("guard_dark_skin_tone", "\u{1f482}\u{1f3ff}"),
+    ("guard_light_skin_tone", "\u{1f482}\u{1f3fb}"),
+    ("guard_medium-dark_skin_tone", "\u{1f482}\u{1f3fe}"),
+    ("guard_medium-light_skin_tone", "\u{1f482}\u{1f3fc}"),
+    ("guard_medium_skin_tone", "\u{1f482}\u{1f3fd}"),
+    ("guardsman", "\u{1f482}"),
+    ("guatemala", "\u{1f1ec}\u{1f1f9}"),
+    ("guernsey", "\u{1f1ec}\u{1f1ec}"),
+    ("guide_dog", "\u{1f9ae}"),
+    ("guinea", "\u{1f1ec}\u{1f1f3}"),
+    ("guinea-bissau", "\u{1f1ec}\u{1f1fc}"),
+    ("guitar", "\u{1f3b8}"),
+    ("gun", "\u{1f52b}"),
+    ("guyana", "\u{1f1ec}\u{1f1fe}"),
+    ("haircut", "\u{1f487}"),
+    ("haiti", "\u{1f1ed}\u{1f1f9}"),
+    ("hamburger", "\u{1f354}"),
+    ("hammer", "\u{1f528}"),
+    ("hammer_and_pick", "\u{2692}"),
+    ("hammer_and_wrench", "\u{1f6e0}"),
+    ("hamster", "\u{1f439}"),
+    ("hamster_face", "\u{1f439}"),
+    ("hand", "\u{270b}"),
+    ("hand_with_fingers_splayed", "\u{1f590}"),
+    (
+        "hand_with_fingers_splayed_dark_skin_tone",
+        "\u{1f590}\u{1f3ff}",
+    ),
+    (
+        "hand_with_fingers_splayed_light_skin_tone",
+        "\u{1f590}\u{1f3fb}",
+    ),
+    (
+        "hand_with_fingers_splayed_medium-dark_skin_tone",
+        "\u{1f590}\u{1f3fe}",
+    ),
+    (
+        "hand_with_fingers_splayed_medium-light_skin_tone",
+        "\u{1f590}\u{1f3fc}",
+    ),
+    (
+        "hand_with_fingers_splayed_medium_skin_tone",
+        "\u{1f590}\u{1f3fd}",
+    ),
+    ("handbag", "\u{1f45c}"),
+    ("handshake", "\u{1f91d}"),
+    ("hankey", "\u{1f4a9}"),
+    ("hatched_chick", "\u{1f425}"),
+    ("hatching_chick", "\u{1f423}"),
+    ("headphone", "\u{1f3a7}"),
+    ("headphones", "\u{1f3a7}"),
+    ("hear-no-evil_monkey", "\u{1f649}"),
+    ("hear_no_evil", "\u{1f649}"),
+    ("heart", "\u{2764}"),
+    ("heart_decoration", "\u{1f49f}"),
+    ("heart_eyes", "\u{1f60d}"),
+    ("heart_eyes_cat", "\u{1f63b}"),
+    ("heart_suit", "\u{2665}"),
+    ("heart_with_arrow", "\u{1f498}"),
+    ("heart_with_ribbon", "\u{1f49d}"),
+    ("heartbeat", "\u{1f493}"),
+    ("heartpulse", "\u{1f497}"),
+    ("hearts", "\u{2665}"),
+    ("heavy_check_mark", "\u{2714}"),
+    ("heavy_division_sign", "\u{2797}"),
+    ("heavy_dollar_sign", "\u{1f4b2}"),
+    ("heavy_exclamation_mark", "\u{2757}"),
+    ("heavy_heart_exclamation", "\u{2763}"),
+    ("heavy_heart_exclamation_mark_ornament", "\u{2763}"),
+    ("heavy_large_circle", "\u{2b55}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_multiplication_x", "\u{2716}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("hedgehog", "\u{1f994}"),
+    ("helicopter", "\u{1f681}"),
+    ("helm_symbol", "\u{2388}"),
+    ("helmet_with_white_cross", "\u{26d1}"),
+    ("herb", "\u{1f33f}"),
+    ("hibiscus", "\u{1f33a}"),
+    ("high-heeled_shoe", "\u{1f460}"),
+    ("high-speed_train", "\u{1f684}"),
+    ("high_brightness", "\u{1f506}"),
+    ("high_heel", "\u{1f460}"),
+    ("high_voltage", "\u{26a1}"),
+    ("hiking_boot", "\u{1f97e}"),
+    ("hindu_temple", "\u{1f6d5}"),
+    ("hippopotamus", "\u{1f99b}"),
+    ("hocho", "\u{1f52a}"),
+    ("hole", "\u{1f573}"),
+    ("honduras", "\u{1f1ed}\u{1f1f3}"),
+    ("honey_pot", "\u{1f36f}"),
+    ("honeybee", "\u{1f41d}"),
+    ("hong_kong_sar_china", "\u{1f1ed}\u{1f1f0}"),
+    ("horizontal_traffic_light", "\u{1f6a5}"),
+    ("horse", "\u{1f434}"),
+    ("horse_face", "\u{1f434}"),
+    ("horse_racing", "\u{1f3c7}"),
+    ("horse_racing_dark_skin_tone", "\u{1f3c7}\u{1f3ff}"),
+    ("horse_racing_light_skin_tone", "\u{1f3c7}\u{1f3fb}"),
+    ("horse_racing_medium-dark_skin_tone", "\u{1f3c7}\u{1f3fe}"),
+    ("horse_racing_medium-light_skin_tone", "\u{1f3c7}\u{1f3fc}"),
+    ("horse_racing_medium_skin_tone", "\u{1f3c7}\u{1f3fd}"),
+    ("hospital", "\u{1f3e5}"),
+    ("hot_beverage", "\u{2615}"),
+    ("hot_dog", "\u{1f32d}"),
+    ("hot_face", "\u{1f975}"),
+    ("hot_pepper", "\u{1f336}"),
+    ("hot_springs", "\u{2668}"),
+    ("hotel", "\u{1f3e8}"),
+    ("hotsprings", "\u{2668}"),
+    ("hourglass", "\u{231b}"),
+    ("hourglass_done", "\u{231b}"),
+    ("hourglass_flowing_sand", "\u{23f3}"),
+    ("hourglass_not_done", "\u{23f3}"),
+    ("house", "\u{1f3e0}"),
+    ("house_buildings", "\u{1f3d8}"),
+    ("house_with_garden", "\u{1f3e1}"),
+    ("houses", "\u{1f3d8}"),
+    ("hugging_face", "\u{1f917}"),
+    ("hundred_points", "\u{1f4af}"),
+    ("hungary", "\u{1f1ed}\u{1f1fa}"),
+    ("hushed", "\u{1f62f}"),
+    ("hushed_face", "\u{1f62f}"),
+    ("ice", "\u{1f9ca}"),
+    ("ice_cream", "\u{1f368}"),
+    ("ice_hockey", "\u{1f3d2}"),
+    ("ice_hockey_stick_and_puck", "\u{1f3d2}"),
+    ("ice_skate", "\u{26f8}"),
+    ("icecream", "\u{1f366}"),
+    ("iceland", "\u{1f1ee}\u{1f1f8}"),
+    ("id", "\u{1f194}"),
+    ("id_button", "\u{1f194}"),
+    ("ideograph_advantage", "\u{1f250}"),
+    ("imp", "\u{1f47f}"),
+    ("inbox_tray", "\u{1f4e5}"),
+    ("incoming_envelope", "\u{1f4e8}"),
+    ("index_pointing_up", "\u{261d}"),
+    ("index_pointing_up_dark_skin_tone", "\u{261d}\u{1f3ff}"),
+    ("index_pointing_up_light_skin_tone", "\u{261d}\u{1f3fb}"),
+    (
+        "index_pointing_up_medium-dark_skin_tone",
+        "\u{261d}\u{1f3fe}",
+    ),
+    (
+        "index_pointing_up_medium-light_skin_tone",
+        "\u{261d}\u{1f3fc}",
+    ),
+    ("index_pointing_up_medium_skin_tone", "\u{261d}\u{1f3fd}"),
+    ("india", "\u{1f1ee}\u{1f1f3}"),
+    ("indonesia", "\u{1f1ee}\u{1f1e9}"),
+    ("infinity", "\u{267e}"),
+    ("information", "\u{2139}"),
+    ("information_desk_person", "\u{1f481}"),
+    ("information_source", "\u{2139}"),
+    ("innocent", "\u{1f607}"),
+    ("input_latin_letters", "\u{1f524}"),
+    ("input_latin_lowercase", "\u{1f521}"),
+    ("input_latin_uppercase", "\u{1f520}"),
+    ("input_numbers", "\u{1f522}"),
+    ("input_symbols", "\u{1f523}"),
+    ("interrobang", "\u{2049}"),
+    ("iphone", "\u{1f4f1}"),
+    ("iran", "\u{1f1ee}\u{1f1f7}"),
+    ("iraq", "\u{1f1ee}\u{1f1f6}"),
+    ("ireland", "\u{1f1ee}\u{1f1ea}"),
+    ("isle_of_man", "\u{1f1ee}\u{1f1f2}"),
+    ("israel", "\u{1f1ee}\u{1f1f1}"),
+    ("italy", "\u{1f1ee}\u{1f1f9}"),
+    ("izakaya_lantern", "\u{1f3ee}"),
+    ("jack-o-lantern", "\u{1f383}"),
+    ("jack_o_lantern", "\u{1f383}"),
+    ("jamaica", "\u{1f1ef}\u{1f1f2}"),
+    ("japan", "\u{1f5fe}"),
+    ("japanese_acceptable_button", "\u{1f251}"),
+    ("japanese_application_button", "\u{1f238}"),
+    ("japanese_bargain_button", "\u{1f250}"),
+    ("japanese_castle", "\u{1f3ef}"),
+    ("japanese_congratulations_button", "\u{3297}"),
+    ("japanese_discount_button", "\u{1f239}"),
+    ("japanese_dolls", "\u{1f38e}"),
+    ("japanese_free_of_charge_button", "\u{1f21a}"),
+    ("japanese_goblin", "\u{1f47a}"),
+    ("japanese_here_button", "\u{1f201}"),
+    ("japanese_monthly_amount_button", "\u{1f237}"),
+    ("japanese_no_vacancy_button", "\u{1f235}"),
+    ("japanese_not_free_of_charge_button", "\u{1f236}"),
+    ("japanese_ogre", "\u{1f479}"),
+    ("japanese_open_for_business_button", "\u{1f23a}"),
+    ("japanese_passing_grade_button", "\u{1f234}"),
+    ("japanese_post_office", "\u{1f3e3}"),
+    ("japanese_prohibited_button", "\u{1f232}"),
+    ("japanese_reserved_button", "\u{1f22f}"),
+    ("japanese_secret_button", "\u{3299}"),
+    ("japanese_service_charge_button", "\u{1f202}"),
+    ("japanese_symbol_for_beginner", "\u{1f530}"),
+    ("japanese_vacancy_button", "\u{1f233}"),
+    ("jeans", "\u{1f456}"),
+    ("jersey", "\u{1f1ef}\u{1f1ea}"),
+    ("jigsaw", "\u{1f9e9}"),
+    ("joker", "\u{1f0cf}"),
+    ("jordan", "\u{1f1ef}\u{1f1f4}"),
+    ("joy", "\u{1f602}"),
+    ("joy_cat", "\u{1f639}"),
+    ("joystick", "\u{1f579}"),
+    ("kaaba", "\u{1f54b}"),
+    ("kangaroo", "\u{1f998}"),
+    ("kazakhstan", "\u{1f1f0}\u{1f1ff}"),
+    ("kenya", "\u{1f1f0}\u{1f1ea}"),
+    ("key", "\u{1f511}"),
+    ("keyboard", "\u{2328}"),
+    ("keycap_0", "\u{30}\u{fe0f}\u{20e3}"),
+    ("keycap_1", "\u{31}\u{fe0f}\u{20e3}"),
+    ("keycap_10", "\u{1f51f}"),
+    ("keycap_2", "\u{32}\u{fe0f}\u{20e3}"),
+    ("keycap_3", "\u{33}\u{fe0f}\u{20e3}"),
+    ("keycap_4", "\u{34}\u{fe0f}\u{20e3}"),
+    ("keycap_5", "\u{35}\u{fe0f}\u{20e3}"),
+    ("keycap_6", "\u{36}\u{fe0f}\u{20e3}"),
+    ("keycap_7", "\u{37}\u{fe0f}\u{20e3}"),
+    ("keycap_8", "\u{38}\u{fe0f}\u{20e3}"),
+    ("keycap_9", "\u{39}\u{fe0f}\u{20e3}"),
+    ("keycap_asterisk", "\u{2a}\u{20e3}"),
+    ("keycap_digit_eight", "\u{38}\u{20e3}"),
+    ("keycap_digit_five", "\u{35}\u{20e3}"),
+    ("keycap_digit_four", "\u{34}\u{20e3}"),
+    ("keycap_digit_nine", "\u{39}\u{20e3}"),
+    ("keycap_digit_one", "\u{31}\u{20e3}"),
+    ("keycap_digit_seven", "\u{37}\u{20e3}"),
+    ("keycap_digit_six", "\u{36}\u{20e3}"),
+    ("keycap_digit_three", "\u{33}\u{20e3}"),
+    ("keycap_digit_two", "\u{32}\u{20e3}"),
+    ("keycap_digit_zero", "\u{30}\u{20e3}"),
+    ("keycap_number_sign", "\u{23}\u{20e3}"),
+    ("kick_scooter", "\u{1f6f4}"),
+    ("kimono", "\u{1f458}"),
+    ("kiribati", "\u{1f1f0}\u{1f1ee}"),
+    ("kiss", "\u{1f48b}"),
+    (
+        "kiss_man_man",
+        "\u{1f468}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f48b}\u{200d}\u{1f468}",
+    ),
+    ("kiss_mark", "\u{1f48b}"),
+    (
+        "kiss_woman_man",
+        "\u{1f469}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f48b}\u{200d}\u{1f468}",
+    ),
+    (
+        "kiss_woman_woman",
+        "\u{1f469}\u{200d}\u{2764}\u{fe0f}\u{200d}\u{1f48b}\u{200d}\u{1f469}",
+    ),
+    ("kissing", "\u{1f617}"),
+    ("kissing_cat", "\u{1f63d}"),
+    ("kissing_cat_face", "\u{1f63d}"),
+    ("kissing_closed_eyes", "\u{1f61a}"),
+    ("kissing_face", "\u{1f617}"),
+    ("kissing_face_with_closed_eyes", "\u{1f61a}"),
+    ("kissing_face_with_smiling_eyes", "\u{1f619}"),
+    ("kissing_heart", "\u{1f618}"),
+    ("kissing_smiling_eyes", "\u{1f619}"),
+    ("kitchen_knife", "\u{1f52a}"),
+    ("kite", "\u{1fa81}"),
+    ("kiwi_fruit", "\u{1f95d}"),
+    ("knife", "\u{1f52a}"),
+    ("koala", "\u{1f428}"),
+    ("koko", "\u{1f201}"),
+    ("kosovo", "\u{1f1fd}\u{1f1f0}"),
+    ("kuwait", "\u{1f1f0}\u{1f1fc}"),
+    ("kyrgyzstan", "\u{1f1f0}\u{1f1ec}"),
+    ("lab_coat", "\u{1f97c}"),
+    ("label", "\u{1f3f7}"),
+    ("lacrosse", "\u{1f94d}"),
+    ("lady_beetle", "\u{1f41e}"),
+    ("lantern", "\u{1f3ee}"),
+    ("laos", "\u{1f1f1}\u{1f1e6}"),
+    ("laptop_computer", "\u{1f4bb}"),
+    ("large_blue_circle", "\u{1f535}"),
+    ("large_blue_diamond", "\u{1f537}"),
+    ("large_orange_diamond", "\u{1f536}"),
+    ("last_quarter_moon", "\u{1f317}"),
+    ("last_quarter_moon_face", "\u{1f31c}"),
+    ("last_quarter_moon_with_face", "\u{1f31c}"),
+    ("last_track_button", "\u{23ee}"),
+    ("latin_cross", "\u{271d}"),
+    ("latvia", "\u{1f1f1}\u{1f1fb}"),
+    ("laughing", "\u{1f606}"),
+    ("leaf_fluttering_in_wind", "\u{1f343}"),
+    ("leafy_green", "\u{1f96c}"),
+    ("leaves", "\u{1f343}"),
+    ("lebanon", "\u{1f1f1}\u{1f1e7}"),
+    ("ledger", "\u{1f4d2}"),
+    ("left-facing_fist", "\u{1f91b}"),
+    ("left-facing_fist_dark_skin_tone", "\u{1f91b}\u{1f3ff}"),
+    ("left-facing_fist_light_skin_tone", "\u{1f91b}\u{1f3fb}"),
+    (
+        "left-facing_fist_medium-dark_skin_tone",
+        "\u{1f91b}\u{1f3fe}",
+    ),
+    (
+        "left-facing_fist_medium-light_skin_tone",
+        "\u{1f91b}\u{1f3fc}",
+    ),
+    ("left-facing_fist_medium_skin_tone", "\u{1f91b}\u{1f3fd}"),
+    ("left-right_arrow", "\u{2194}"),
+    ("left_arrow", "\u{2b05}"),
+    ("left_arrow_curving_right", "\u{21aa}"),
+    ("left_luggage", "\u{1f6c5}"),
+    ("left_right_arrow", "\u{2194}"),
+    ("left_speech_bubble", "\u{1f5e8}"),
+    ("leftwards_arrow_with_hook", "\u{21a9}"),
+    ("leg", "\u{1f9b5}"),
+    ("lemon", "\u{1f34b}"),
+    ("leo", "\u{264c}"),
+    ("leopard", "\u{1f406}"),
+    ("lesotho", "\u{1f1f1}\u{1f1f8}"),
+    ("level_slider", "\u{1f39a}"),
+    ("liberia", "\u{1f1f1}\u{1f1f7}"),
+    ("libra", "\u{264e}"),
+    ("libya", "\u{1f1f1}\u{1f1fe}"),
+    ("liechtenstein", "\u{1f1f1}\u{1f1ee}"),
+    ("light_bulb", "\u{1f4a1}"),
+    ("light_rail", "\u{1f688}"),
+    ("light_skin_tone", "\u{1f3fb}"),
+    ("link", "\u{1f517}"),
+    ("linked_paperclips", "\u{1f587}"),
+    ("lion_face", "\u{1f981}"),
+    ("lips", "\u{1f444}"),
+    ("lipstick", "\u{1f484}"),
+    ("lithuania", "\u{1f1f1}\u{1f1f9}"),
+    ("litter_in_bin_sign", "\u{1f6ae}"),
+    ("lizard", "\u{1f98e}"),
+    ("llama", "\u{1f999}"),
+    ("lobster", "\u{1f99e}"),
+    ("lock", "\u{1f512}"),
+    ("lock_with_ink_pen", "\u{1f50f}"),
+    ("locked", "\u{1f512}"),
+    ("locked_with_key", "\u{1f510}"),
+    ("locked_with_pen", "\u{1f50f}"),
+    ("locomotive", "\u{1f682}"),
+    ("lollipop", "\u{1f36d}"),
+    ("loop", "\u{27bf}"),
+    ("lotion_bottle", "\u{1f9f4}"),
+    ("loud_sound", "\u{1f50a}"),
+    ("loudly_crying_face", "\u{1f62d}"),
+    ("loudspeaker", "\u{1f4e2}"),
+    ("love-you_gesture", "\u{1f91f}"),
+    ("love-you_gesture_dark_skin_tone", "\u{1f91f}\u{1f3ff}"),
+    ("love-you_gesture_light_skin_tone", "\u{1f91f}\u{1f3fb}"),
+    (
+        "love-you_gesture_medium-dark_skin_tone",
+        "\u{1f91f}\u{1f3fe}",
+    ),
+    (
+        "love-you_gesture_medium-light_skin_tone",
+        "\u{1f91f}\u{1f3fc}",
+    ),
+    ("love-you_gesture_medium_skin_tone", "\u{1f91f}\u{1f3fd}"),
+    ("love_hotel", "\u{1f3e9}"),
+    ("love_letter", "\u{1f48c}"),
+    ("low_brightness", "\u{1f505}"),
+    ("lower_left_ballpoint_pen", "\u{1f58a}"),
+    ("lower_left_crayon", "\u{1f58d}"),
+    ("lower_left_fountain_pen", "\u{1f58b}"),
+    ("lower_left_paintbrush", "\u{1f58c}"),
+    ("luggage", "\u{1f9f3}"),
+    ("luxembourg", "\u{1f1f1}\u{1f1fa}"),
+    ("lying_face", "\u{1f925}"),
+    ("m", "\u{24c2}"),
+    ("macau_sar_china", "\u{1f1f2}\u{1f1f4}"),
+    ("macedonia", "\u{1f1f2}\u{1f1f0}"),
+    ("madagascar", "\u{1f1f2}\u{1f1ec}"),
+    ("mag", "\u{1f50d}"),
+    ("mag_right", "\u{1f50e}"),
+    ("mage", "\u{1f9d9}"),
+    ("mage_dark_skin_tone", "\u{1f9d9}\u{1f3ff}"),
+    ("mage_light_skin_tone", "\u{1f9d9}\u{1f3fb}"),
+    ("mage_medium-dark_skin_tone", "\u{1f9d9}\u{1f3fe}"),
+    ("mage_medium-light_skin_tone", "\u{1f9d9}\u{1f3fc}"),
+    ("mage_medium_skin_tone", "\u{1f9d9}\u{1f3fd}"),
+    ("magnet", "\u{1f9f2}"),
+    ("magnifying_glass_tilted_left", "\u{1f50d}"),
+    ("magnifying_glass_tilted_right", "\u{1f50e}"),
+    ("mahjong", "\u{1f004}"),
+    ("mahjong_red_dragon", "\u{1f004}"),
+    ("mailbox", "\u{1f4eb}"),
+    ("mailbox_closed", "\u{1f4ea}"),
+    ("mailbox_with_mail", "\u{1f4ec}"),
+    ("mailbox_with_no_mail", "\u{1f4ed}"),
+    ("malawi", "\u{1f1f2}\u{1f1fc}"),
+    ("malaysia", "\u{1f1f2}\u{1f1fe}"),
+    ("maldives", "\u{1f1f2}\u{1f1fb}"),
+    ("male_sign", "\u{2642}"),
+    ("mali", "\u{1f1f2}\u{1f1f1}"),
+    ("malta", "\u{1f1f2}\u{1f1f9}"),
+    ("man", "\u{1f468}"),
+    ("man_and_woman_holding_hands", "\u{1f46b}"),
+    ("man_artist", "\u{1f468}\u{200d}\u{1f3a8}"),
+    (
+        "man_artist_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "man_artist_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "man_artist_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "man_artist_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "man_artist_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f3a8}",
+    ),
+    ("man_astronaut", "\u{1f468}\u{200d}\u{1f680}"),
+    (
+        "man_astronaut_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f680}",
+    ),
+    (
+        "man_astronaut_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f680}",
+    ),
+    (
+        "man_astronaut_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f680}",
+    ),
+    (
+        "man_astronaut_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f680}",
+    ),
+    (
+        "man_astronaut_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f680}",
+    ),
+    ("man_biking", "\u{1f6b4}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_biking_dark_skin_tone",
+        "\u{1f6b4}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_biking_light_skin_tone",
+        "\u{1f6b4}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_biking_medium-dark_skin_tone",
+        "\u{1f6b4}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_biking_medium-light_skin_tone",
+        "\u{1f6b4}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_biking_medium_skin_tone",
+        "\u{1f6b4}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball",
+        "\u{26f9}\u{fe0f}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball_dark_skin_tone",
+        "\u{26f9}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball_light_skin_tone",
+        "\u{26f9}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball_medium-dark_skin_tone",
+        "\u{26f9}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball_medium-light_skin_tone",
+        "\u{26f9}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bouncing_ball_medium_skin_tone",
+        "\u{26f9}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_bowing", "\u{1f647}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_bowing_dark_skin_tone",
+        "\u{1f647}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bowing_light_skin_tone",
+        "\u{1f647}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bowing_medium-dark_skin_tone",
+        "\u{1f647}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bowing_medium-light_skin_tone",
+        "\u{1f647}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_bowing_medium_skin_tone",
+        "\u{1f647}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_cartwheeling", "\u{1f938}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_cartwheeling_dark_skin_tone",
+        "\u{1f938}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_cartwheeling_light_skin_tone",
+        "\u{1f938}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_cartwheeling_medium-dark_skin_tone",
+        "\u{1f938}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_cartwheeling_medium-light_skin_tone",
+        "\u{1f938}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_cartwheeling_medium_skin_tone",
+        "\u{1f938}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_climbing", "\u{1f9d7}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_climbing_dark_skin_tone",
+        "\u{1f9d7}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_climbing_light_skin_tone",
+        "\u{1f9d7}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_climbing_medium-dark_skin_tone",
+        "\u{1f9d7}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_climbing_medium-light_skin_tone",
+        "\u{1f9d7}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_climbing_medium_skin_tone",
+        "\u{1f9d7}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker",
+        "\u{1f477}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker_dark_skin_tone",
+        "\u{1f477}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker_light_skin_tone",
+        "\u{1f477}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker_medium-dark_skin_tone",
+        "\u{1f477}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker_medium-light_skin_tone",
+        "\u{1f477}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_construction_worker_medium_skin_tone",
+        "\u{1f477}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_cook", "\u{1f468}\u{200d}\u{1f373}"),
+    (
+        "man_cook_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f373}",
+    ),
+    (
+        "man_cook_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f373}",
+    ),
+    (
+        "man_cook_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f373}",
+    ),
+    (
+        "man_cook_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f373}",
+    ),
+    (
+        "man_cook_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f373}",
+    ),
+    ("man_dancing", "\u{1f57a}"),
+    ("man_dancing_dark_skin_tone", "\u{1f57a}\u{1f3ff}"),
+    ("man_dancing_light_skin_tone", "\u{1f57a}\u{1f3fb}"),
+    ("man_dancing_medium-dark_skin_tone", "\u{1f57a}\u{1f3fe}"),
+    ("man_dancing_medium-light_skin_tone", "\u{1f57a}\u{1f3fc}"),
+    ("man_dancing_medium_skin_tone", "\u{1f57a}\u{1f3fd}"),
+    ("man_dark_skin_tone", "\u{1f468}\u{1f3ff}"),
+    ("man_detective", "\u{1f575}\u{fe0f}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_detective_dark_skin_tone",
+        "\u{1f575}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_detective_light_skin_tone",
+        "\u{1f575}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_detective_medium-dark_skin_tone",
+        "\u{1f575}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_detective_medium-light_skin_tone",
+        "\u{1f575}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_detective_medium_skin_tone",
+        "\u{1f575}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_elf", "\u{1f9dd}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_elf_dark_skin_tone",
+        "\u{1f9dd}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_elf_light_skin_tone",
+        "\u{1f9dd}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_elf_medium-dark_skin_tone",
+        "\u{1f9dd}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_elf_medium-light_skin_tone",
+        "\u{1f9dd}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_elf_medium_skin_tone",
+        "\u{1f9dd}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_facepalming", "\u{1f926}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_facepalming_dark_skin_tone",
+        "\u{1f926}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_facepalming_light_skin_tone",
+        "\u{1f926}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_facepalming_medium-dark_skin_tone",
+        "\u{1f926}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_facepalming_medium-light_skin_tone",
+        "\u{1f926}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_facepalming_medium_skin_tone",
+        "\u{1f926}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_factory_worker", "\u{1f468}\u{200d}\u{1f3ed}"),
+    (
+        "man_factory_worker_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "man_factory_worker_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "man_factory_worker_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "man_factory_worker_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "man_factory_worker_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f3ed}",
+    ),
+    ("man_fairy", "\u{1f9da}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_fairy_dark_skin_tone",
+        "\u{1f9da}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_fairy_light_skin_tone",
+        "\u{1f9da}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_fairy_medium-dark_skin_tone",
+        "\u{1f9da}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_fairy_medium-light_skin_tone",
+        "\u{1f9da}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_fairy_medium_skin_tone",
+        "\u{1f9da}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_farmer", "\u{1f468}\u{200d}\u{1f33e}"),
+    (
+        "man_farmer_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f33e}",
+    ),
+    (
+        "man_farmer_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f33e}",
+    ),
+    (
+        "man_farmer_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f33e}",
+    ),
+    (
+        "man_farmer_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f33e}",
+    ),
+    (
+        "man_farmer_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f33e}",
+    ),
+    ("man_firefighter", "\u{1f468}\u{200d}\u{1f692}"),
+    (
+        "man_firefighter_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f692}",
+    ),
+    (
+        "man_firefighter_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f692}",
+    ),
+    (
+        "man_firefighter_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f692}",
+    ),
+    (
+        "man_firefighter_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f692}",
+    ),
+    (
+        "man_firefighter_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f692}",
+    ),
+    ("man_frowning", "\u{1f64d}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_frowning_dark_skin_tone",
+        "\u{1f64d}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_frowning_light_skin_tone",
+        "\u{1f64d}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_frowning_medium-dark_skin_tone",
+        "\u{1f64d}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_frowning_medium-light_skin_tone",
+        "\u{1f64d}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_frowning_medium_skin_tone",
+        "\u{1f64d}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_genie", "\u{1f9de}\u{200d}\u{2642}\u{fe0f}"),
+    ("man_gesturing_no", "\u{1f645}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_gesturing_no_dark_skin_tone",
+        "\u{1f645}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_no_light_skin_tone",
+        "\u{1f645}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_no_medium-dark_skin_tone",
+        "\u{1f645}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_no_medium-light_skin_tone",
+        "\u{1f645}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_no_medium_skin_tone",
+        "\u{1f645}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_gesturing_ok", "\u{1f646}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_gesturing_ok_dark_skin_tone",
+        "\u{1f646}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_ok_light_skin_tone",
+        "\u{1f646}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_ok_medium-dark_skin_tone",
+        "\u{1f646}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_ok_medium-light_skin_tone",
+        "\u{1f646}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_gesturing_ok_medium_skin_tone",
+        "\u{1f646}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_getting_haircut", "\u{1f487}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_getting_haircut_dark_skin_tone",
+        "\u{1f487}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_haircut_light_skin_tone",
+        "\u{1f487}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_haircut_medium-dark_skin_tone",
+        "\u{1f487}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_haircut_medium-light_skin_tone",
+        "\u{1f487}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_haircut_medium_skin_tone",
+        "\u{1f487}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_getting_massage", "\u{1f486}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_getting_massage_dark_skin_tone",
+        "\u{1f486}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_massage_light_skin_tone",
+        "\u{1f486}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_massage_medium-dark_skin_tone",
+        "\u{1f486}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_massage_medium-light_skin_tone",
+        "\u{1f486}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_getting_massage_medium_skin_tone",
+        "\u{1f486}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_golfing", "\u{1f3cc}\u{fe0f}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_golfing_dark_skin_tone",
+        "\u{1f3cc}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_golfing_light_skin_tone",
+        "\u{1f3cc}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_golfing_medium-dark_skin_tone",
+        "\u{1f3cc}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_golfing_medium-light_skin_tone",
+        "\u{1f3cc}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_golfing_medium_skin_tone",
+        "\u{1f3cc}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_guard", "\u{1f482}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_guard_dark_skin_tone",
+        "\u{1f482}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_guard_light_skin_tone",
+        "\u{1f482}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_guard_medium-dark_skin_tone",
+        "\u{1f482}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_guard_medium-light_skin_tone",
+        "\u{1f482}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_guard_medium_skin_tone",
+        "\u{1f482}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_health_worker", "\u{1f468}\u{200d}\u{2695}\u{fe0f}"),
+    (
+        "man_health_worker_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "man_health_worker_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "man_health_worker_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "man_health_worker_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "man_health_worker_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    ("man_in_business_suit_levitating", "\u{1f574}"),
+    ("man_in_lotus_position", "\u{1f9d8}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_in_lotus_position_dark_skin_tone",
+        "\u{1f9d8}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_lotus_position_light_skin_tone",
+        "\u{1f9d8}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_lotus_position_medium-dark_skin_tone",
+        "\u{1f9d8}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_lotus_position_medium-light_skin_tone",
+        "\u{1f9d8}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_lotus_position_medium_skin_tone",
+        "\u{1f9d8}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_in_manual_wheelchair", "\u{1f468}\u{200d}\u{1f9bd}"),
+    ("man_in_motorized_wheelchair", "\u{1f468}\u{200d}\u{1f9bc}"),
+    ("man_in_steamy_room", "\u{1f9d6}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_in_steamy_room_dark_skin_tone",
+        "\u{1f9d6}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_steamy_room_light_skin_tone",
+        "\u{1f9d6}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_steamy_room_medium-dark_skin_tone",
+        "\u{1f9d6}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_steamy_room_medium-light_skin_tone",
+        "\u{1f9d6}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_in_steamy_room_medium_skin_tone",
+        "\u{1f9d6}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_in_suit_levitating", "\u{1f574}"),
+    (
+        "man_in_suit_levitating_dark_skin_tone",
+        "\u{1f574}\u{1f3ff}",
+    ),
+    (
+        "man_in_suit_levitating_light_skin_tone",
+        "\u{1f574}\u{1f3fb}",
+    ),
+    (
+        "man_in_suit_levitating_medium-dark_skin_tone",
+        "\u{1f574}\u{1f3fe}",
+    ),
+    (
+        "man_in_suit_levitating_medium-light_skin_tone",
+        "\u{1f574}\u{1f3fc}",
+    ),
+    (
+        "man_in_suit_levitating_medium_skin_tone",
+        "\u{1f574}\u{1f3fd}",
+    ),
+    ("man_in_tuxedo", "\u{1f935}"),
+    ("man_in_tuxedo_dark_skin_tone", "\u{1f935}\u{1f3ff}"),
+    ("man_in_tuxedo_light_skin_tone", "\u{1f935}\u{1f3fb}"),
+    ("man_in_tuxedo_medium-dark_skin_tone", "\u{1f935}\u{1f3fe}"),
+    ("man_in_tuxedo_medium-light_skin_tone", "\u{1f935}\u{1f3fc}"),
+    ("man_in_tuxedo_medium_skin_tone", "\u{1f935}\u{1f3fd}"),
+    ("man_judge", "\u{1f468}\u{200d}\u{2696}\u{fe0f}"),
+    (
+        "man_judge_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "man_judge_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "man_judge_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "man_judge_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "man_judge_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    ("man_juggling", "\u{1f939}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_juggling_dark_skin_tone",
+        "\u{1f939}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_juggling_light_skin_tone",
+        "\u{1f939}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_juggling_medium-dark_skin_tone",
+        "\u{1f939}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_juggling_medium-light_skin_tone",
+        "\u{1f939}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_juggling_medium_skin_tone",
+        "\u{1f939}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights",
+        "\u{1f3cb}\u{fe0f}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights_dark_skin_tone",
+        "\u{1f3cb}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights_light_skin_tone",
+        "\u{1f3cb}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights_medium-dark_skin_tone",
+        "\u{1f3cb}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights_medium-light_skin_tone",
+        "\u{1f3cb}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_lifting_weights_medium_skin_tone",
+        "\u{1f3cb}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_light_skin_tone", "\u{1f468}\u{1f3fb}"),
+    ("man_mage", "\u{1f9d9}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_mage_dark_skin_tone",
+        "\u{1f9d9}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mage_light_skin_tone",
+        "\u{1f9d9}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mage_medium-dark_skin_tone",
+        "\u{1f9d9}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mage_medium-light_skin_tone",
+        "\u{1f9d9}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mage_medium_skin_tone",
+        "\u{1f9d9}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_mechanic", "\u{1f468}\u{200d}\u{1f527}"),
+    (
+        "man_mechanic_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f527}",
+    ),
+    (
+        "man_mechanic_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f527}",
+    ),
+    (
+        "man_mechanic_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f527}",
+    ),
+    (
+        "man_mechanic_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f527}",
+    ),
+    (
+        "man_mechanic_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f527}",
+    ),
+    ("man_medium-dark_skin_tone", "\u{1f468}\u{1f3fe}"),
+    ("man_medium-light_skin_tone", "\u{1f468}\u{1f3fc}"),
+    ("man_medium_skin_tone", "\u{1f468}\u{1f3fd}"),
+    ("man_mountain_biking", "\u{1f6b5}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_mountain_biking_dark_skin_tone",
+        "\u{1f6b5}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mountain_biking_light_skin_tone",
+        "\u{1f6b5}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mountain_biking_medium-dark_skin_tone",
+        "\u{1f6b5}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mountain_biking_medium-light_skin_tone",
+        "\u{1f6b5}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_mountain_biking_medium_skin_tone",
+        "\u{1f6b5}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_office_worker", "\u{1f468}\u{200d}\u{1f4bc}"),
+    (
+        "man_office_worker_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "man_office_worker_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "man_office_worker_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "man_office_worker_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "man_office_worker_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f4bc}",
+    ),
+    ("man_pilot", "\u{1f468}\u{200d}\u{2708}\u{fe0f}"),
+    (
+        "man_pilot_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "man_pilot_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "man_pilot_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "man_pilot_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "man_pilot_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    ("man_playing_handball", "\u{1f93e}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_playing_handball_dark_skin_tone",
+        "\u{1f93e}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_handball_light_skin_tone",
+        "\u{1f93e}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_handball_medium-dark_skin_tone",
+        "\u{1f93e}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_handball_medium-light_skin_tone",
+        "\u{1f93e}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_handball_medium_skin_tone",
+        "\u{1f93e}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo",
+        "\u{1f93d}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo_dark_skin_tone",
+        "\u{1f93d}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo_light_skin_tone",
+        "\u{1f93d}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo_medium-dark_skin_tone",
+        "\u{1f93d}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo_medium-light_skin_tone",
+        "\u{1f93d}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_playing_water_polo_medium_skin_tone",
+        "\u{1f93d}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_police_officer", "\u{1f46e}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_police_officer_dark_skin_tone",
+        "\u{1f46e}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_police_officer_light_skin_tone",
+        "\u{1f46e}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_police_officer_medium-dark_skin_tone",
+        "\u{1f46e}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_police_officer_medium-light_skin_tone",
+        "\u{1f46e}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_police_officer_medium_skin_tone",
+        "\u{1f46e}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_pouting", "\u{1f64e}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_pouting_dark_skin_tone",
+        "\u{1f64e}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_pouting_light_skin_tone",
+        "\u{1f64e}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_pouting_medium-dark_skin_tone",
+        "\u{1f64e}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_pouting_medium-light_skin_tone",
+        "\u{1f64e}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_pouting_medium_skin_tone",
+        "\u{1f64e}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_raising_hand", "\u{1f64b}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_raising_hand_dark_skin_tone",
+        "\u{1f64b}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_raising_hand_light_skin_tone",
+        "\u{1f64b}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_raising_hand_medium-dark_skin_tone",
+        "\u{1f64b}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_raising_hand_medium-light_skin_tone",
+        "\u{1f64b}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_raising_hand_medium_skin_tone",
+        "\u{1f64b}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_rowing_boat", "\u{1f6a3}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_rowing_boat_dark_skin_tone",
+        "\u{1f6a3}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_rowing_boat_light_skin_tone",
+        "\u{1f6a3}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_rowing_boat_medium-dark_skin_tone",
+        "\u{1f6a3}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_rowing_boat_medium-light_skin_tone",
+        "\u{1f6a3}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_rowing_boat_medium_skin_tone",
+        "\u{1f6a3}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_running", "\u{1f3c3}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_running_dark_skin_tone",
+        "\u{1f3c3}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_running_light_skin_tone",
+        "\u{1f3c3}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_running_medium-dark_skin_tone",
+        "\u{1f3c3}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_running_medium-light_skin_tone",
+        "\u{1f3c3}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_running_medium_skin_tone",
+        "\u{1f3c3}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_scientist", "\u{1f468}\u{200d}\u{1f52c}"),
+    (
+        "man_scientist_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f52c}",
+    ),
+    (
+        "man_scientist_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f52c}",
+    ),
+    (
+        "man_scientist_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f52c}",
+    ),
+    (
+        "man_scientist_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f52c}",
+    ),
+    (
+        "man_scientist_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f52c}",
+    ),
+    ("man_shrugging", "\u{1f937}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_shrugging_dark_skin_tone",
+        "\u{1f937}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_shrugging_light_skin_tone",
+        "\u{1f937}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_shrugging_medium-dark_skin_tone",
+        "\u{1f937}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_shrugging_medium-light_skin_tone",
+        "\u{1f937}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_shrugging_medium_skin_tone",
+        "\u{1f937}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_singer", "\u{1f468}\u{200d}\u{1f3a4}"),
+    (
+        "man_singer_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "man_singer_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "man_singer_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "man_singer_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "man_singer_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f3a4}",
+    ),
+    ("man_student", "\u{1f468}\u{200d}\u{1f393}"),
+    (
+        "man_student_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f393}",
+    ),
+    (
+        "man_student_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f393}",
+    ),
+    (
+        "man_student_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f393}",
+    ),
+    (
+        "man_student_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f393}",
+    ),
+    (
+        "man_student_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f393}",
+    ),
+    ("man_surfing", "\u{1f3c4}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_surfing_dark_skin_tone",
+        "\u{1f3c4}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_surfing_light_skin_tone",
+        "\u{1f3c4}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_surfing_medium-dark_skin_tone",
+        "\u{1f3c4}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_surfing_medium-light_skin_tone",
+        "\u{1f3c4}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_surfing_medium_skin_tone",
+        "\u{1f3c4}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_swimming", "\u{1f3ca}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_swimming_dark_skin_tone",
+        "\u{1f3ca}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_swimming_light_skin_tone",
+        "\u{1f3ca}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_swimming_medium-dark_skin_tone",
+        "\u{1f3ca}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_swimming_medium-light_skin_tone",
+        "\u{1f3ca}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_swimming_medium_skin_tone",
+        "\u{1f3ca}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_teacher", "\u{1f468}\u{200d}\u{1f3eb}"),
+    (
+        "man_teacher_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "man_teacher_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "man_teacher_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "man_teacher_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "man_teacher_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f3eb}",
+    ),
+    ("man_technologist", "\u{1f468}\u{200d}\u{1f4bb}"),
+    (
+        "man_technologist_dark_skin_tone",
+        "\u{1f468}\u{1f3ff}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "man_technologist_light_skin_tone",
+        "\u{1f468}\u{1f3fb}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "man_technologist_medium-dark_skin_tone",
+        "\u{1f468}\u{1f3fe}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "man_technologist_medium-light_skin_tone",
+        "\u{1f468}\u{1f3fc}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "man_technologist_medium_skin_tone",
+        "\u{1f468}\u{1f3fd}\u{200d}\u{1f4bb}",
+    ),
+    ("man_tipping_hand", "\u{1f481}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_tipping_hand_dark_skin_tone",
+        "\u{1f481}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_tipping_hand_light_skin_tone",
+        "\u{1f481}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_tipping_hand_medium-dark_skin_tone",
+        "\u{1f481}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_tipping_hand_medium-light_skin_tone",
+        "\u{1f481}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_tipping_hand_medium_skin_tone",
+        "\u{1f481}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_vampire", "\u{1f9db}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_vampire_dark_skin_tone",
+        "\u{1f9db}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_vampire_light_skin_tone",
+        "\u{1f9db}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_vampire_medium-dark_skin_tone",
+        "\u{1f9db}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_vampire_medium-light_skin_tone",
+        "\u{1f9db}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_vampire_medium_skin_tone",
+        "\u{1f9db}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_walking", "\u{1f6b6}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_walking_dark_skin_tone",
+        "\u{1f6b6}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_walking_light_skin_tone",
+        "\u{1f6b6}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_walking_medium-dark_skin_tone",
+        "\u{1f6b6}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_walking_medium-light_skin_tone",
+        "\u{1f6b6}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_walking_medium_skin_tone",
+        "\u{1f6b6}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_wearing_turban", "\u{1f473}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "man_wearing_turban_dark_skin_tone",
+        "\u{1f473}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_wearing_turban_light_skin_tone",
+        "\u{1f473}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_wearing_turban_medium-dark_skin_tone",
+        "\u{1f473}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_wearing_turban_medium-light_skin_tone",
+        "\u{1f473}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "man_wearing_turban_medium_skin_tone",
+        "\u{1f473}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("man_with_chinese_cap", "\u{1f472}"),
+    ("man_with_chinese_cap_dark_skin_tone", "\u{1f472}\u{1f3ff}"),
+    ("man_with_chinese_cap_light_skin_tone", "\u{1f472}\u{1f3fb}"),
+    (
+        "man_with_chinese_cap_medium-dark_skin_tone",
+        "\u{1f472}\u{1f3fe}",
+    ),
+    (
+        "man_with_chinese_cap_medium-light_skin_tone",
+        "\u{1f472}\u{1f3fc}",
+    ),
+    (
+        "man_with_chinese_cap_medium_skin_tone",
+        "\u{1f472}\u{1f3fd}",
+    ),
+    ("man_with_gua_pi_mao", "\u{1f472}"),
+    ("man_with_probing_cane", "\u{1f468}\u{200d}\u{1f9af}"),
+    ("man_with_turban", "\u{1f473}"),
+    ("man_zombie", "\u{1f9df}\u{200d}\u{2642}\u{fe0f}"),
+    ("mango", "\u{1f96d}"),
+    ("mans_shoe", "\u{1f45e}"),
+    ("mantelpiece_clock", "\u{1f570}"),
+    ("manual_wheelchair", "\u{1f9bd}"),
+    ("map_of_japan", "\u{1f5fe}"),
+    ("maple_leaf", "\u{1f341}"),
+    ("marshall_islands", "\u{1f1f2}\u{1f1ed}"),
+    ("martial_arts_uniform", "\u{1f94b}"),
+    ("martinique", "\u{1f1f2}\u{1f1f6}"),
+    ("mask", "\u{1f637}"),
+    ("massage", "\u{1f486}"),
+    ("mate", "\u{1f9c9}"),
+    ("mauritania", "\u{1f1f2}\u{1f1f7}"),
+    ("mauritius", "\u{1f1f2}\u{1f1fa}"),
+    ("mayotte", "\u{1f1fe}\u{1f1f9}"),
+    ("meat_on_bone", "\u{1f356}"),
+    ("mechanical_arm", "\u{1f9be}"),
+    ("mechanical_leg", "\u{1f9bf}"),
+    ("medical_symbol", "\u{2695}"),
+    ("medium_dark_skin_tone", "\u{1f3fe}"),
+    ("medium_light_skin_tone", "\u{1f3fc}"),
+    ("medium_skin_tone", "\u{1f3fd}"),
+    ("mega", "\u{1f4e3}"),
+    ("megaphone", "\u{1f4e3}"),
+    ("melon", "\u{1f348}"),
+    ("memo", "\u{1f4dd}"),
+    ("men_with_bunny_ears", "\u{1f46f}\u{200d}\u{2642}\u{fe0f}"),
+    ("men_wrestling", "\u{1f93c}\u{200d}\u{2642}\u{fe0f}"),
+    ("menorah", "\u{1f54e}"),
+    ("menorah_with_nine_branches", "\u{1f54e}"),
+    ("mens", "\u{1f6b9}"),
+    ("mermaid", "\u{1f9dc}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "mermaid_dark_skin_tone",
+        "\u{1f9dc}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "mermaid_light_skin_tone",
+        "\u{1f9dc}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "mermaid_medium-dark_skin_tone",
+        "\u{1f9dc}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "mermaid_medium-light_skin_tone",
+        "\u{1f9dc}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "mermaid_medium_skin_tone",
+        "\u{1f9dc}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("merman", "\u{1f9dc}\u{200d}\u{2642}\u{fe0f}"),
+    (
+        "merman_dark_skin_tone",
+        "\u{1f9dc}\u{1f3ff}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "merman_light_skin_tone",
+        "\u{1f9dc}\u{1f3fb}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "merman_medium-dark_skin_tone",
+        "\u{1f9dc}\u{1f3fe}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "merman_medium-light_skin_tone",
+        "\u{1f9dc}\u{1f3fc}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    (
+        "merman_medium_skin_tone",
+        "\u{1f9dc}\u{1f3fd}\u{200d}\u{2642}\u{fe0f}",
+    ),
+    ("merperson", "\u{1f9dc}"),
+    ("merperson_dark_skin_tone", "\u{1f9dc}\u{1f3ff}"),
+    ("merperson_light_skin_tone", "\u{1f9dc}\u{1f3fb}"),
+    ("merperson_medium-dark_skin_tone", "\u{1f9dc}\u{1f3fe}"),
+    ("merperson_medium-light_skin_tone", "\u{1f9dc}\u{1f3fc}"),
+    ("merperson_medium_skin_tone", "\u{1f9dc}\u{1f3fd}"),
+    ("metro", "\u{1f687}"),
+    ("mexico", "\u{1f1f2}\u{1f1fd}"),
+    ("microbe", "\u{1f9a0}"),
+    ("micronesia", "\u{1f1eb}\u{1f1f2}"),
+    ("microphone", "\u{1f3a4}"),
+    ("microscope", "\u{1f52c}"),
+    ("middle_finger", "\u{1f595}"),
+    ("middle_finger_dark_skin_tone", "\u{1f595}\u{1f3ff}"),
+    ("middle_finger_light_skin_tone", "\u{1f595}\u{1f3fb}"),
+    ("middle_finger_medium-dark_skin_tone", "\u{1f595}\u{1f3fe}"),
+    ("middle_finger_medium-light_skin_tone", "\u{1f595}\u{1f3fc}"),
+    ("middle_finger_medium_skin_tone", "\u{1f595}\u{1f3fd}"),
+    ("military_medal", "\u{1f396}"),
+    ("milky_way", "\u{1f30c}"),
+    ("minibus", "\u{1f690}"),
+    ("minidisc", "\u{1f4bd}"),
+    ("moai", "\u{1f5ff}"),
+    ("mobile_phone", "\u{1f4f1}"),
+    ("mobile_phone_off", "\u{1f4f4}"),
+    ("mobile_phone_with_arrow", "\u{1f4f2}"),
+    ("moldova", "\u{1f1f2}\u{1f1e9}"),
+    ("monaco", "\u{1f1f2}\u{1f1e8}"),
+    ("money-mouth_face", "\u{1f911}"),
+    ("money__mouth_face", "\u{1f911}"),
+    ("money_bag", "\u{1f4b0}"),
+    ("money_with_wings", "\u{1f4b8}"),
+    ("moneybag", "\u{1f4b0}"),
+    ("mongolia", "\u{1f1f2}\u{1f1f3}"),
+    ("monkey", "\u{1f412}"),
+    ("monkey_face", "\u{1f435}"),
+    ("monorail", "\u{1f69d}"),
+    ("montenegro", "\u{1f1f2}\u{1f1ea}"),
+    ("montserrat", "\u{1f1f2}\u{1f1f8}"),
+    ("moon", "\u{1f314}"),
+    ("moon_cake", "\u{1f96e}"),
+    ("moon_viewing_ceremony", "\u{1f391}"),
+    ("morocco", "\u{1f1f2}\u{1f1e6}"),
+    ("mortar_board", "\u{1f393}"),
+    ("mosque", "\u{1f54c}"),
+    ("mosquito", "\u{1f99f}"),
+    ("motor_boat", "\u{1f6e5}"),
+    ("motor_scooter", "\u{1f6f5}"),
+    ("motorcycle", "\u{1f3cd}"),
+    ("motorized_wheelchair", "\u{1f9bc}"),
+    ("motorway", "\u{1f6e3}"),
+    ("mount_fuji", "\u{1f5fb}"),
+    ("mountain", "\u{26f0}"),
+    ("mountain_bicyclist", "\u{1f6b5}"),
+    ("mountain_cableway", "\u{1f6a0}"),
+    ("mountain_railway", "\u{1f69e}"),
+    ("mouse", "\u{1f42d}"),
+    ("mouse2", "\u{1f401}"),
+    ("mouse_face", "\u{1f42d}"),
+    ("mouth", "\u{1f444}"),
+    ("movie_camera", "\u{1f3a5}"),
+    ("moyai", "\u{1f5ff}"),
+    ("mozambique", "\u{1f1f2}\u{1f1ff}"),
+    ("muscle", "\u{1f4aa}"),
+    ("mushroom", "\u{1f344}"),
+    ("musical_keyboard", "\u{1f3b9}"),
+    ("musical_note", "\u{1f3b5}"),
+    ("musical_notes", "\u{1f3b6}"),
+    ("musical_score", "\u{1f3bc}"),
+    ("mute", "\u{1f507}"),
+    ("muted_speaker", "\u{1f507}"),
+    ("nail_care", "\u{1f485}"),
+    ("nail_polish", "\u{1f485}"),
+    ("nail_polish_dark_skin_tone", "\u{1f485}\u{1f3ff}"),
+    ("nail_polish_light_skin_tone", "\u{1f485}\u{1f3fb}"),
+    ("nail_polish_medium-dark_skin_tone", "\u{1f485}\u{1f3fe}"),
+    ("nail_polish_medium-light_skin_tone", "\u{1f485}\u{1f3fc}"),
+    ("nail_polish_medium_skin_tone", "\u{1f485}\u{1f3fd}"),
+    ("name_badge", "\u{1f4db}"),
+    ("namibia", "\u{1f1f3}\u{1f1e6}"),
+    ("national_park", "\u{1f3de}"),
+    ("nauru", "\u{1f1f3}\u{1f1f7}"),
+    ("nauseated_face", "\u{1f922}"),
+    ("nazar_amulet", "\u{1f9ff}"),
+    ("necktie", "\u{1f454}"),
+    ("negative_squared_cross_mark", "\u{274e}"),
+    ("nepal", "\u{1f1f3}\u{1f1f5}"),
+    ("nerd_face", "\u{1f913}"),
+    ("netherlands", "\u{1f1f3}\u{1f1f1}"),
+    ("neutral_face", "\u{1f610}"),
+    ("new", "\u{1f195}"),
+    ("new_button", "\u{1f195}"),
+    ("new_caledonia", "\u{1f1f3}\u{1f1e8}"),
+    ("new_moon", "\u{1f311}"),
+    ("new_moon_face", "\u{1f31a}"),
+    ("new_moon_with_face", "\u{1f31a}"),
+    ("new_zealand", "\u{1f1f3}\u{1f1ff}"),
+    ("newspaper", "\u{1f4f0}"),
+    ("next_track_button", "\u{23ed}"),
+    ("ng", "\u{1f196}"),
+    ("ng_button", "\u{1f196}"),
+    ("nicaragua", "\u{1f1f3}\u{1f1ee}"),
+    ("niger", "\u{1f1f3}\u{1f1ea}"),
+    ("nigeria", "\u{1f1f3}\u{1f1ec}"),
+    ("night_with_stars", "\u{1f303}"),
+    ("nine", "\u{39}\u{fe0f}\u{20e3}"),
+    ("nine-thirty", "\u{1f564}"),
+    ("niue", "\u{1f1f3}\u{1f1fa}"),
+    ("no_bell", "\u{1f515}"),
+    ("no_bicycles", "\u{1f6b3}"),
+    ("no_entry", "\u{26d4}"),
+    ("no_entry_sign", "\u{1f6ab}"),
+    ("no_good", "\u{1f645}"),
+    ("no_littering", "\u{1f6af}"),
+    ("no_mobile_phones", "\u{1f4f5}"),
+    ("no_mouth", "\u{1f636}"),
+    ("no_one_under_eighteen", "\u{1f51e}"),
+    ("no_pedestrians", "\u{1f6b7}"),
+    ("no_smoking", "\u{1f6ad}"),
+    ("non-potable_water", "\u{1f6b1}"),
+    ("non__potable_water", "\u{1f6b1}"),
+    ("norfolk_island", "\u{1f1f3}\u{1f1eb}"),
+    ("north_korea", "\u{1f1f0}\u{1f1f5}"),
+    ("northern_mariana_islands", "\u{1f1f2}\u{1f1f5}"),
+    ("norway", "\u{1f1f3}\u{1f1f4}"),
+    ("nose", "\u{1f443}"),
+    ("nose_dark_skin_tone", "\u{1f443}\u{1f3ff}"),
+    ("nose_light_skin_tone", "\u{1f443}\u{1f3fb}"),
+    ("nose_medium-dark_skin_tone", "\u{1f443}\u{1f3fe}"),
+    ("nose_medium-light_skin_tone", "\u{1f443}\u{1f3fc}"),
+    ("nose_medium_skin_tone", "\u{1f443}\u{1f3fd}"),
+    ("notebook", "\u{1f4d3}"),
+    ("notebook_with_decorative_cover", "\u{1f4d4}"),
+    ("notes", "\u{1f3b6}"),
+    ("nut_and_bolt", "\u{1f529}"),
+    ("o", "\u{2b55}"),
+    ("o2", "\u{1f17e}"),
+    ("ocean", "\u{1f30a}"),
+    ("octopus", "\u{1f419}"),
+    ("oden", "\u{1f362}"),
+    ("office", "\u{1f3e2}"),
+    ("office_building", "\u{1f3e2}"),
+    ("ogre", "\u{1f479}"),
+    ("oil_drum", "\u{1f6e2}"),
+    ("ok", "\u{1f197}"),
+    ("ok_button", "\u{1f197}"),
+    ("ok_hand", "\u{1f44c}"),
+    ("ok_hand_dark_skin_tone", "\u{1f44c}\u{1f3ff}"),
+    ("ok_hand_light_skin_tone", "\u{1f44c}\u{1f3fb}"),
+    ("ok_hand_medium-dark_skin_tone", "\u{1f44c}\u{1f3fe}"),
+    ("ok_hand_medium-light_skin_tone", "\u{1f44c}\u{1f3fc}"),
+    ("ok_hand_medium_skin_tone", "\u{1f44c}\u{1f3fd}"),
+    ("ok_woman", "\u{1f646}"),
+    ("old_key", "\u{1f5dd}"),
+    ("old_man", "\u{1f474}"),
+    ("old_man_dark_skin_tone", "\u{1f474}\u{1f3ff}"),
+    ("old_man_light_skin_tone", "\u{1f474}\u{1f3fb}"),
+    ("old_man_medium-dark_skin_tone", "\u{1f474}\u{1f3fe}"),
+    ("old_man_medium-light_skin_tone", "\u{1f474}\u{1f3fc}"),
+    ("old_man_medium_skin_tone", "\u{1f474}\u{1f3fd}"),
+    ("old_woman", "\u{1f475}"),
+    ("old_woman_dark_skin_tone", "\u{1f475}\u{1f3ff}"),
+    ("old_woman_light_skin_tone", "\u{1f475}\u{1f3fb}"),
+    ("old_woman_medium-dark_skin_tone", "\u{1f475}\u{1f3fe}"),
+    ("old_woman_medium-light_skin_tone", "\u{1f475}\u{1f3fc}"),
+    ("old_woman_medium_skin_tone", "\u{1f475}\u{1f3fd}"),
+    ("older_adult", "\u{1f9d3}"),
+    ("older_adult_dark_skin_tone", "\u{1f9d3}\u{1f3ff}"),
+    ("older_adult_light_skin_tone", "\u{1f9d3}\u{1f3fb}"),
+    ("older_adult_medium-dark_skin_tone", "\u{1f9d3}\u{1f3fe}"),
+    ("older_adult_medium-light_skin_tone", "\u{1f9d3}\u{1f3fc}"),
+    ("older_adult_medium_skin_tone", "\u{1f9d3}\u{1f3fd}"),
+    ("older_man", "\u{1f474}"),
+    ("older_woman", "\u{1f475}"),
+    ("om", "\u{1f549}"),
+    ("om_symbol", "\u{1f549}"),
+    ("oman", "\u{1f1f4}\u{1f1f2}"),
+    ("on", "\u{1f51b}"),
+    ("oncoming_automobile", "\u{1f698}"),
+    ("oncoming_bus", "\u{1f68d}"),
+    ("oncoming_fist", "\u{1f44a}"),
+    ("oncoming_fist_dark_skin_tone", "\u{1f44a}\u{1f3ff}"),
+    ("oncoming_fist_light_skin_tone", "\u{1f44a}\u{1f3fb}"),
+    ("oncoming_fist_medium-dark_skin_tone", "\u{1f44a}\u{1f3fe}"),
+    ("oncoming_fist_medium-light_skin_tone", "\u{1f44a}\u{1f3fc}"),
+    ("oncoming_fist_medium_skin_tone", "\u{1f44a}\u{1f3fd}"),
+    ("oncoming_police_car", "\u{1f694}"),
+    ("oncoming_taxi", "\u{1f696}"),
+    ("one", "\u{31}\u{fe0f}\u{20e3}"),
+    ("one-piece_swimsuit", "\u{1fa71}"),
+    ("one-thirty", "\u{1f55c}"),
+    ("onion", "\u{1f9c5}"),
+    ("open_book", "\u{1f4d6}"),
+    ("open_file_folder", "\u{1f4c2}"),
+    ("open_hands", "\u{1f450}"),
+    ("open_hands_dark_skin_tone", "\u{1f450}\u{1f3ff}"),
+    ("open_hands_light_skin_tone", "\u{1f450}\u{1f3fb}"),
+    ("open_hands_medium-dark_skin_tone", "\u{1f450}\u{1f3fe}"),
+    ("open_hands_medium-light_skin_tone", "\u{1f450}\u{1f3fc}"),
+    ("open_hands_medium_skin_tone", "\u{1f450}\u{1f3fd}"),
+    ("open_mailbox_with_lowered_flag", "\u{1f4ed}"),
+    ("open_mailbox_with_raised_flag", "\u{1f4ec}"),
+    ("open_mouth", "\u{1f62e}"),
+    ("ophiuchus", "\u{26ce}"),
+    ("optical_disk", "\u{1f4bf}"),
+    ("orange_book", "\u{1f4d9}"),
+    ("orange_circle", "\u{1f7e0}"),
+    ("orange_heart", "\u{1f9e1}"),
+    ("orange_square", "\u{1f7e7}"),
+    ("orangutan", "\u{1f9a7}"),
+    ("orthodox_cross", "\u{2626}"),
+    ("otter", "\u{1f9a6}"),
+    ("outbox_tray", "\u{1f4e4}"),
+    ("owl", "\u{1f989}"),
+    ("ox", "\u{1f402}"),
+    ("oyster", "\u{1f9aa}"),
+    ("p_button", "\u{1f17f}"),
+    ("package", "\u{1f4e6}"),
+    ("page_facing_up", "\u{1f4c4}"),
+    ("page_with_curl", "\u{1f4c3}"),
+    ("pager", "\u{1f4df}"),
+    ("paintbrush", "\u{1f58c}"),
+    ("pakistan", "\u{1f1f5}\u{1f1f0}"),
+    ("palau", "\u{1f1f5}\u{1f1fc}"),
+    ("palestinian_territories", "\u{1f1f5}\u{1f1f8}"),
+    ("palm_tree", "\u{1f334}"),
+    ("palms_up_together", "\u{1f932}"),
+    ("palms_up_together_dark_skin_tone", "\u{1f932}\u{1f3ff}"),
+    ("palms_up_together_light_skin_tone", "\u{1f932}\u{1f3fb}"),
+    (
+        "palms_up_together_medium-dark_skin_tone",
+        "\u{1f932}\u{1f3fe}",
+    ),
+    (
+        "palms_up_together_medium-light_skin_tone",
+        "\u{1f932}\u{1f3fc}",
+    ),
+    ("palms_up_together_medium_skin_tone", "\u{1f932}\u{1f3fd}"),
+    ("panama", "\u{1f1f5}\u{1f1e6}"),
+    ("pancakes", "\u{1f95e}"),
+    ("panda_face", "\u{1f43c}"),
+    ("paperclip", "\u{1f4ce}"),
+    ("papua_new_guinea", "\u{1f1f5}\u{1f1ec}"),
+    ("paraguay", "\u{1f1f5}\u{1f1fe}"),
+    ("parking", "\u{1f17f}"),
+    ("parrot", "\u{1f99c}"),
+    ("part_alternation_mark", "\u{303d}"),
+    ("partly_sunny", "\u{26c5}"),
+    ("party_popper", "\u{1f389}"),
+    ("partying_face", "\u{1f973}"),
+    ("passenger_ship", "\u{1f6f3}"),
+    ("passport_control", "\u{1f6c2}"),
+    ("pause_button", "\u{23f8}"),
+    ("paw_prints", "\u{1f43e}"),
+    ("peace_symbol", "\u{262e}"),
+    ("peach", "\u{1f351}"),
+    ("peacock", "\u{1f99a}"),
+    ("peanuts", "\u{1f95c}"),
+    ("pear", "\u{1f350}"),
+    ("pen", "\u{1f58a}"),
+    ("pencil", "\u{1f4dd}"),
+    ("pencil2", "\u{270f}"),
+    ("penguin", "\u{1f427}"),
+    ("pensive", "\u{1f614}"),
+    ("pensive_face", "\u{1f614}"),
+    (
+        "people_holding_hands",
+        "\u{1f9d1}\u{200d}\u{1f91d}\u{200d}\u{1f9d1}",
+    ),
+    ("people_with_bunny_ears", "\u{1f46f}"),
+    ("people_wrestling", "\u{1f93c}"),
+    ("performing_arts", "\u{1f3ad}"),
+    ("persevere", "\u{1f623}"),
+    ("persevering_face", "\u{1f623}"),
+    ("person_biking", "\u{1f6b4}"),
+    ("person_biking_dark_skin_tone", "\u{1f6b4}\u{1f3ff}"),
+    ("person_biking_light_skin_tone", "\u{1f6b4}\u{1f3fb}"),
+    ("person_biking_medium-dark_skin_tone", "\u{1f6b4}\u{1f3fe}"),
+    ("person_biking_medium-light_skin_tone", "\u{1f6b4}\u{1f3fc}"),
+    ("person_biking_medium_skin_tone", "\u{1f6b4}\u{1f3fd}"),
+    ("person_bouncing_ball", "\u{26f9}"),
+    ("person_bouncing_ball_dark_skin_tone", "\u{26f9}\u{1f3ff}"),
+    ("person_bouncing_ball_light_skin_tone", "\u{26f9}\u{1f3fb}"),
+    (
+        "person_bouncing_ball_medium-dark_skin_tone",
+        "\u{26f9}\u{1f3fe}",
+    ),
+    (
+        "person_bouncing_ball_medium-light_skin_tone",
+        "\u{26f9}\u{1f3fc}",
+    ),
+    ("person_bouncing_ball_medium_skin_tone", "\u{26f9}\u{1f3fd}"),
+    ("person_bowing", "\u{1f647}"),
+    ("person_bowing_dark_skin_tone", "\u{1f647}\u{1f3ff}"),
+    ("person_bowing_light_skin_tone", "\u{1f647}\u{1f3fb}"),
+    ("person_bowing_medium-dark_skin_tone", "\u{1f647}\u{1f3fe}"),
+    ("person_bowing_medium-light_skin_tone", "\u{1f647}\u{1f3fc}"),
+    ("person_bowing_medium_skin_tone", "\u{1f647}\u{1f3fd}"),
+    ("person_cartwheeling", "\u{1f938}"),
+    ("person_cartwheeling_dark_skin_tone", "\u{1f938}\u{1f3ff}"),
+    ("person_cartwheeling_light_skin_tone", "\u{1f938}\u{1f3fb}"),
+    (
+        "person_cartwheeling_medium-dark_skin_tone",
+        "\u{1f938}\u{1f3fe}",
+    ),
+    (
+        "person_cartwheeling_medium-light_skin_tone",
+        "\u{1f938}\u{1f3fc}",
+    ),
+    ("person_cartwheeling_medium_skin_tone", "\u{1f938}\u{1f3fd}"),
+    ("person_climbing", "\u{1f9d7}"),
+    ("person_climbing_dark_skin_tone", "\u{1f9d7}\u{1f3ff}"),
+    ("person_climbing_light_skin_tone", "\u{1f9d7}\u{1f3fb}"),
+    (
+        "person_climbing_medium-dark_skin_tone",
+        "\u{1f9d7}\u{1f3fe}",
+    ),
+    (
+        "person_climbing_medium-light_skin_tone",
+        "\u{1f9d7}\u{1f3fc}",
+    ),
+    ("person_climbing_medium_skin_tone", "\u{1f9d7}\u{1f3fd}"),
+    ("person_facepalming", "\u{1f926}"),
+    ("person_facepalming_dark_skin_tone", "\u{1f926}\u{1f3ff}"),
+    ("person_facepalming_light_skin_tone", "\u{1f926}\u{1f3fb}"),
+    (
+        "person_facepalming_medium-dark_skin_tone",
+        "\u{1f926}\u{1f3fe}",
+    ),
+    (
+        "person_facepalming_medium-light_skin_tone",
+        "\u{1f926}\u{1f3fc}",
+    ),
+    ("person_facepalming_medium_skin_tone", "\u{1f926}\u{1f3fd}"),
+    ("person_fencing", "\u{1f93a}"),
+    ("person_frowning", "\u{1f64d}"),
+    ("person_frowning_dark_skin_tone", "\u{1f64d}\u{1f3ff}"),
+    ("person_frowning_light_skin_tone", "\u{1f64d}\u{1f3fb}"),
+    (
+        "person_frowning_medium-dark_skin_tone",
+        "\u{1f64d}\u{1f3fe}",
+    ),
+    (
+        "person_frowning_medium-light_skin_tone",
+        "\u{1f64d}\u{1f3fc}",
+    ),
+    ("person_frowning_medium_skin_tone", "\u{1f64d}\u{1f3fd}"),
+    ("person_gesturing_no", "\u{1f645}"),
+    ("person_gesturing_no_dark_skin_tone", "\u{1f645}\u{1f3ff}"),
+    ("person_gesturing_no_light_skin_tone", "\u{1f645}\u{1f3fb}"),
+    (
+        "person_gesturing_no_medium-dark_skin_tone",
+        "\u{1f645}\u{1f3fe}",
+    ),
+    (
+        "person_gesturing_no_medium-light_skin_tone",
+        "\u{1f645}\u{1f3fc}",
+    ),
+    ("person_gesturing_no_medium_skin_tone", "\u{1f645}\u{1f3fd}"),
+    ("person_gesturing_ok", "\u{1f646}"),
+    ("person_gesturing_ok_dark_skin_tone", "\u{1f646}\u{1f3ff}"),
+    ("person_gesturing_ok_light_skin_tone", "\u{1f646}\u{1f3fb}"),
+    (
+        "person_gesturing_ok_medium-dark_skin_tone",
+        "\u{1f646}\u{1f3fe}",
+    ),
+    (
+        "person_gesturing_ok_medium-light_skin_tone",
+        "\u{1f646}\u{1f3fc}",
+    ),
+    ("person_gesturing_ok_medium_skin_tone", "\u{1f646}\u{1f3fd}"),
+    ("person_getting_haircut", "\u{1f487}"),
+    (
+        "person_getting_haircut_dark_skin_tone",
+        "\u{1f487}\u{1f3ff}",
+    ),
+    (
+        "person_getting_haircut_light_skin_tone",
+        "\u{1f487}\u{1f3fb}",
+    ),
+    (
+        "person_getting_haircut_medium-dark_skin_tone",
+        "\u{1f487}\u{1f3fe}",
+    ),
+    (
+        "person_getting_haircut_medium-light_skin_tone",
+        "\u{1f487}\u{1f3fc}",
+    ),
+    (
+        "person_getting_haircut_medium_skin_tone",
+        "\u{1f487}\u{1f3fd}",
+    ),
+    ("person_getting_massage", "\u{1f486}"),
+    (
+        "person_getting_massage_dark_skin_tone",
+        "\u{1f486}\u{1f3ff}",
+    ),
+    (
+        "person_getting_massage_light_skin_tone",
+        "\u{1f486}\u{1f3fb}",
+    ),
+    (
+        "person_getting_massage_medium-dark_skin_tone",
+        "\u{1f486}\u{1f3fe}",
+    ),
+    (
+        "person_getting_massage_medium-light_skin_tone",
+        "\u{1f486}\u{1f3fc}",
+    ),
+    (
+        "person_getting_massage_medium_skin_tone",
+        "\u{1f486}\u{1f3fd}",
+    ),
+    ("person_golfing", "\u{1f3cc}"),
+    ("person_golfing_dark_skin_tone", "\u{1f3cc}\u{1f3ff}"),
+    ("person_golfing_light_skin_tone", "\u{1f3cc}\u{1f3fb}"),
+    ("person_golfing_medium-dark_skin_tone", "\u{1f3cc}\u{1f3fe}"),
+    (
+        "person_golfing_medium-light_skin_tone",
+        "\u{1f3cc}\u{1f3fc}",
+    ),
+    ("person_golfing_medium_skin_tone", "\u{1f3cc}\u{1f3fd}"),
+    ("person_in_bed", "\u{1f6cc}"),
+    ("person_in_bed_dark_skin_tone", "\u{1f6cc}\u{1f3ff}"),
+    ("person_in_bed_light_skin_tone", "\u{1f6cc}\u{1f3fb}"),
+    ("person_in_bed_medium-dark_skin_tone", "\u{1f6cc}\u{1f3fe}"),
+    ("person_in_bed_medium-light_skin_tone", "\u{1f6cc}\u{1f3fc}"),
+    ("person_in_bed_medium_skin_tone", "\u{1f6cc}\u{1f3fd}"),
+    ("person_in_lotus_position", "\u{1f9d8}"),
+    (
+        "person_in_lotus_position_dark_skin_tone",
+        "\u{1f9d8}\u{1f3ff}",
+    ),
+    (
+        "person_in_lotus_position_light_skin_tone",
+        "\u{1f9d8}\u{1f3fb}",
+    ),
+    (
+        "person_in_lotus_position_medium-dark_skin_tone",
+        "\u{1f9d8}\u{1f3fe}",
+    ),
+    (
+        "person_in_lotus_position_medium-light_skin_tone",
+        "\u{1f9d8}\u{1f3fc}",
+    ),
+    (
+        "person_in_lotus_position_medium_skin_tone",
+        "\u{1f9d8}\u{1f3fd}",
+    ),
+    ("person_in_steamy_room", "\u{1f9d6}"),
+    ("person_in_steamy_room_dark_skin_tone", "\u{1f9d6}\u{1f3ff}"),
+    (
+        "person_in_steamy_room_light_skin_tone",
+        "\u{1f9d6}\u{1f3fb}",
+    ),
+    (
+        "person_in_steamy_room_medium-dark_skin_tone",
+        "\u{1f9d6}\u{1f3fe}",
+    ),
+    (
+        "person_in_steamy_room_medium-light_skin_tone",
+        "\u{1f9d6}\u{1f3fc}",
+    ),
+    (
+        "person_in_steamy_room_medium_skin_tone",
+        "\u{1f9d6}\u{1f3fd}",
+    ),
+    ("person_juggling", "\u{1f939}"),
+    ("person_juggling_dark_skin_tone", "\u{1f939}\u{1f3ff}"),
+    ("person_juggling_light_skin_tone", "\u{1f939}\u{1f3fb}"),
+    (
+        "person_juggling_medium-dark_skin_tone",
+        "\u{1f939}\u{1f3fe}",
+    ),
+    (
+        "person_juggling_medium-light_skin_tone",
+        "\u{1f939}\u{1f3fc}",
+    ),
+    ("person_juggling_medium_skin_tone", "\u{1f939}\u{1f3fd}"),
+    ("person_kneeling", "\u{1f9ce}"),
+    ("person_lifting_weights", "\u{1f3cb}"),
+    (
+        "person_lifting_weights_dark_skin_tone",
+        "\u{1f3cb}\u{1f3ff}",
+    ),
+    (
+        "person_lifting_weights_light_skin_tone",
+        "\u{1f3cb}\u{1f3fb}",
+    ),
+    (
+        "person_lifting_weights_medium-dark_skin_tone",
+        "\u{1f3cb}\u{1f3fe}",
+    ),
+    (
+        "person_lifting_weights_medium-light_skin_tone",
+        "\u{1f3cb}\u{1f3fc}",
+    ),
+    (
+        "person_lifting_weights_medium_skin_tone",
+        "\u{1f3cb}\u{1f3fd}",
+    ),
+    ("person_mountain_biking", "\u{1f6b5}"),
+    (
+        "person_mountain_biking_dark_skin_tone",
+        "\u{1f6b5}\u{1f3ff}",
+    ),
+    (
+        "person_mountain_biking_light_skin_tone",
+        "\u{1f6b5}\u{1f3fb}",
+    ),
+    (
+        "person_mountain_biking_medium-dark_skin_tone",
+        "\u{1f6b5}\u{1f3fe}",
+    ),
+    (
+        "person_mountain_biking_medium-light_skin_tone",
+        "\u{1f6b5}\u{1f3fc}",
+    ),
+    (
+        "person_mountain_biking_medium_skin_tone",
+        "\u{1f6b5}\u{1f3fd}",
+    ),
+    ("person_playing_handball", "\u{1f93e}"),
+    (
+        "person_playing_handball_dark_skin_tone",
+        "\u{1f93e}\u{1f3ff}",
+    ),
+    (
+        "person_playing_handball_light_skin_tone",
+        "\u{1f93e}\u{1f3fb}",
+    ),
+    (
+        "person_playing_handball_medium-dark_skin_tone",
+        "\u{1f93e}\u{1f3fe}",
+    ),
+    (
+        "person_playing_handball_medium-light_skin_tone",
+        "\u{1f93e}\u{1f3fc}",
+    ),
+    (
+        "person_playing_handball_medium_skin_tone",
+        "\u{1f93e}\u{1f3fd}",
+    ),
+    ("person_playing_water_polo", "\u{1f93d}"),
+    (
+        "person_playing_water_polo_dark_skin_tone",
+        "\u{1f93d}\u{1f3ff}",
+    ),
+    (
+        "person_playing_water_polo_light_skin_tone",
+        "\u{1f93d}\u{1f3fb}",
+    ),
+    (
+        "person_playing_water_polo_medium-dark_skin_tone",
+        "\u{1f93d}\u{1f3fe}",
+    ),
+    (
+        "person_playing_water_polo_medium-light_skin_tone",
+        "\u{1f93d}\u{1f3fc}",
+    ),
+    (
+        "person_playing_water_polo_medium_skin_tone",
+        "\u{1f93d}\u{1f3fd}",
+    ),
+    ("person_pouting", "\u{1f64e}"),
+    ("person_pouting_dark_skin_tone", "\u{1f64e}\u{1f3ff}"),
+    ("person_pouting_light_skin_tone", "\u{1f64e}\u{1f3fb}"),
+    ("person_pouting_medium-dark_skin_tone", "\u{1f64e}\u{1f3fe}"),
+    (
+        "person_pouting_medium-light_skin_tone",
+        "\u{1f64e}\u{1f3fc}",
+    ),
+    ("person_pouting_medium_skin_tone", "\u{1f64e}\u{1f3fd}"),
+    ("person_raising_hand", "\u{1f64b}"),
+    ("person_raising_hand_dark_skin_tone", "\u{1f64b}\u{1f3ff}"),
+    ("person_raising_hand_light_skin_tone", "\u{1f64b}\u{1f3fb}"),
+    (
+        "person_raising_hand_medium-dark_skin_tone",
+        "\u{1f64b}\u{1f3fe}",
+    ),
+    (
+        "person_raising_hand_medium-light_skin_tone",
+        "\u{1f64b}\u{1f3fc}",
+    ),
+    ("person_raising_hand_medium_skin_tone", "\u{1f64b}\u{1f3fd}"),
+    ("person_rowing_boat", "\u{1f6a3}"),
+    ("person_rowing_boat_dark_skin_tone", "\u{1f6a3}\u{1f3ff}"),
+    ("person_rowing_boat_light_skin_tone", "\u{1f6a3}\u{1f3fb}"),
+    (
+        "person_rowing_boat_medium-dark_skin_tone",
+        "\u{1f6a3}\u{1f3fe}",
+    ),
+    (
+        "person_rowing_boat_medium-light_skin_tone",
+        "\u{1f6a3}\u{1f3fc}",
+    ),
+    ("person_rowing_boat_medium_skin_tone", "\u{1f6a3}\u{1f3fd}"),
+    ("person_running", "\u{1f3c3}"),
+    ("person_running_dark_skin_tone", "\u{1f3c3}\u{1f3ff}"),
+    ("person_running_light_skin_tone", "\u{1f3c3}\u{1f3fb}"),
+    ("person_running_medium-dark_skin_tone", "\u{1f3c3}\u{1f3fe}"),
+    (
+        "person_running_medium-light_skin_tone",
+        "\u{1f3c3}\u{1f3fc}",
+    ),
+    ("person_running_medium_skin_tone", "\u{1f3c3}\u{1f3fd}"),
+    ("person_shrugging", "\u{1f937}"),
+    ("person_shrugging_dark_skin_tone", "\u{1f937}\u{1f3ff}"),
+    ("person_shrugging_light_skin_tone", "\u{1f937}\u{1f3fb}"),
+    (
+        "person_shrugging_medium-dark_skin_tone",
+        "\u{1f937}\u{1f3fe}",
+    ),
+    (
+        "person_shrugging_medium-light_skin_tone",
+        "\u{1f937}\u{1f3fc}",
+    ),
+    ("person_shrugging_medium_skin_tone", "\u{1f937}\u{1f3fd}"),
+    ("person_standing", "\u{1f9cd}"),
+    ("person_surfing", "\u{1f3c4}"),
+    ("person_surfing_dark_skin_tone", "\u{1f3c4}\u{1f3ff}"),
+    ("person_surfing_light_skin_tone", "\u{1f3c4}\u{1f3fb}"),
+    ("person_surfing_medium-dark_skin_tone", "\u{1f3c4}\u{1f3fe}"),
+    (
+        "person_surfing_medium-light_skin_tone",
+        "\u{1f3c4}\u{1f3fc}",
+    ),
+    ("person_surfing_medium_skin_tone", "\u{1f3c4}\u{1f3fd}"),
+    ("person_swimming", "\u{1f3ca}"),
+    ("person_swimming_dark_skin_tone", "\u{1f3ca}\u{1f3ff}"),
+    ("person_swimming_light_skin_tone", "\u{1f3ca}\u{1f3fb}"),
+    (
+        "person_swimming_medium-dark_skin_tone",
+        "\u{1f3ca}\u{1f3fe}",
+    ),
+    (
+        "person_swimming_medium-light_skin_tone",
+        "\u{1f3ca}\u{1f3fc}",
+    ),
+    ("person_swimming_medium_skin_tone", "\u{1f3ca}\u{1f3fd}"),
+    ("person_taking_bath", "\u{1f6c0}"),
+    ("person_taking_bath_dark_skin_tone", "\u{1f6c0}\u{1f3ff}"),
+    ("person_taking_bath_light_skin_tone", "\u{1f6c0}\u{1f3fb}"),
+    (
+        "person_taking_bath_medium-dark_skin_tone",
+        "\u{1f6c0}\u{1f3fe}",
+    ),
+    (
+        "person_taking_bath_medium-light_skin_tone",
+        "\u{1f6c0}\u{1f3fc}",
+    ),
+    ("person_taking_bath_medium_skin_tone", "\u{1f6c0}\u{1f3fd}"),
+    ("person_tipping_hand", "\u{1f481}"),
+    ("person_tipping_hand_dark_skin_tone", "\u{1f481}\u{1f3ff}"),
+    ("person_tipping_hand_light_skin_tone", "\u{1f481}\u{1f3fb}"),
+    (
+        "person_tipping_hand_medium-dark_skin_tone",
+        "\u{1f481}\u{1f3fe}",
+    ),
+    (
+        "person_tipping_hand_medium-light_skin_tone",
+        "\u{1f481}\u{1f3fc}",
+    ),
+    ("person_tipping_hand_medium_skin_tone", "\u{1f481}\u{1f3fd}"),
+    ("person_walking", "\u{1f6b6}"),
+    ("person_walking_dark_skin_tone", "\u{1f6b6}\u{1f3ff}"),
+    ("person_walking_light_skin_tone", "\u{1f6b6}\u{1f3fb}"),
+    ("person_walking_medium-dark_skin_tone", "\u{1f6b6}\u{1f3fe}"),
+    (
+        "person_walking_medium-light_skin_tone",
+        "\u{1f6b6}\u{1f3fc}",
+    ),
+    ("person_walking_medium_skin_tone", "\u{1f6b6}\u{1f3fd}"),
+    ("person_wearing_turban", "\u{1f473}"),
+    ("person_wearing_turban_dark_skin_tone", "\u{1f473}\u{1f3ff}"),
+    (
+        "person_wearing_turban_light_skin_tone",
+        "\u{1f473}\u{1f3fb}",
+    ),
+    (
+        "person_wearing_turban_medium-dark_skin_tone",
+        "\u{1f473}\u{1f3fe}",
+    ),
+    (
+        "person_wearing_turban_medium-light_skin_tone",
+        "\u{1f473}\u{1f3fc}",
+    ),
+    (
+        "person_wearing_turban_medium_skin_tone",
+        "\u{1f473}\u{1f3fd}",
+    ),
+    ("person_with_ball", "\u{26f9}"),
+    ("person_with_blond_hair", "\u{1f471}"),
+    ("person_with_pouting_face", "\u{1f64e}"),
+    ("peru", "\u{1f1f5}\u{1f1ea}"),
+    ("petri_dish", "\u{1f9eb}"),
+    ("philippines", "\u{1f1f5}\u{1f1ed}"),
+    ("phone", "\u{260e}"),
+    ("pick", "\u{26cf}"),
+    ("pie", "\u{1f967}"),
+    ("pig", "\u{1f437}"),
+    ("pig2", "\u{1f416}"),
+    ("pig_face", "\u{1f437}"),
+    ("pig_nose", "\u{1f43d}"),
+    ("pile_of_poo", "\u{1f4a9}"),
+    ("pill", "\u{1f48a}"),
+    ("pinching_hand", "\u{1f90f}"),
+    ("pine_decoration", "\u{1f38d}"),
+    ("pineapple", "\u{1f34d}"),
+    ("ping_pong", "\u{1f3d3}"),
+    ("pirate_flag", "\u{1f3f4}\u{200d}\u{2620}\u{fe0f}"),
+    ("pisces", "\u{2653}"),
+    ("pistol", "\u{1f52b}"),
+    ("pitcairn_islands", "\u{1f1f5}\u{1f1f3}"),
+    ("pizza", "\u{1f355}"),
+    ("place_of_worship", "\u{1f6d0}"),
+    ("play_button", "\u{25b6}"),
+    ("play_or_pause_button", "\u{23ef}"),
+    ("pleading_face", "\u{1f97a}"),
+    ("point_down", "\u{1f447}"),
+    ("point_left", "\u{1f448}"),
+    ("point_right", "\u{1f449}"),
+    ("point_up", "\u{261d}"),
+    ("point_up_2", "\u{1f446}"),
+    ("poland", "\u{1f1f5}\u{1f1f1}"),
+    ("police_car", "\u{1f693}"),
+    ("police_car_light", "\u{1f6a8}"),
+    ("police_officer", "\u{1f46e}"),
+    ("police_officer_dark_skin_tone", "\u{1f46e}\u{1f3ff}"),
+    ("police_officer_light_skin_tone", "\u{1f46e}\u{1f3fb}"),
+    ("police_officer_medium-dark_skin_tone", "\u{1f46e}\u{1f3fe}"),
+    (
+        "police_officer_medium-light_skin_tone",
+        "\u{1f46e}\u{1f3fc}",
+    ),
+    ("police_officer_medium_skin_tone", "\u{1f46e}\u{1f3fd}"),
+    ("poodle", "\u{1f429}"),
+    ("pool_8_ball", "\u{1f3b1}"),
+    ("poop", "\u{1f4a9}"),
+    ("popcorn", "\u{1f37f}"),
+    ("portugal", "\u{1f1f5}\u{1f1f9}"),
+    ("post_office", "\u{1f3e3}"),
+    ("postal_horn", "\u{1f4ef}"),
+    ("postbox", "\u{1f4ee}"),
+    ("pot_of_food", "\u{1f372}"),
+    ("potable_water", "\u{1f6b0}"),
+    ("potato", "\u{1f954}"),
+    ("pouch", "\u{1f45d}"),
+    ("poultry_leg", "\u{1f357}"),
+    ("pound", "\u{1f4b7}"),
+    ("pound_banknote", "\u{1f4b7}"),
+    ("pouting_cat", "\u{1f63e}"),
+    ("pouting_cat_face", "\u{1f63e}"),
+    ("pouting_face", "\u{1f621}"),
+    ("pray", "\u{1f64f}"),
+    ("prayer_beads", "\u{1f4ff}"),
+    ("pregnant_woman", "\u{1f930}"),
+    ("pregnant_woman_dark_skin_tone", "\u{1f930}\u{1f3ff}"),
+    ("pregnant_woman_light_skin_tone", "\u{1f930}\u{1f3fb}"),
+    ("pregnant_woman_medium-dark_skin_tone", "\u{1f930}\u{1f3fe}"),
+    (
+        "pregnant_woman_medium-light_skin_tone",
+        "\u{1f930}\u{1f3fc}",
+    ),
+    ("pregnant_woman_medium_skin_tone", "\u{1f930}\u{1f3fd}"),
+    ("pretzel", "\u{1f968}"),
+    ("prince", "\u{1f934}"),
+    ("prince_dark_skin_tone", "\u{1f934}\u{1f3ff}"),
+    ("prince_light_skin_tone", "\u{1f934}\u{1f3fb}"),
+    ("prince_medium-dark_skin_tone", "\u{1f934}\u{1f3fe}"),
+    ("prince_medium-light_skin_tone", "\u{1f934}\u{1f3fc}"),
+    ("prince_medium_skin_tone", "\u{1f934}\u{1f3fd}"),
+    ("princess", "\u{1f478}"),
+    ("princess_dark_skin_tone", "\u{1f478}\u{1f3ff}"),
+    ("princess_light_skin_tone", "\u{1f478}\u{1f3fb}"),
+    ("princess_medium-dark_skin_tone", "\u{1f478}\u{1f3fe}"),
+    ("princess_medium-light_skin_tone", "\u{1f478}\u{1f3fc}"),
+    ("princess_medium_skin_tone", "\u{1f478}\u{1f3fd}"),
+    ("printer", "\u{1f5a8}"),
+    ("probing_cane", "\u{1f9af}"),
+    ("prohibited", "\u{1f6ab}"),
+    ("puerto_rico", "\u{1f1f5}\u{1f1f7}"),
+    ("punch", "\u{1f44a}"),
+    ("purple_circle", "\u{1f7e3}"),
+    ("purple_heart", "\u{1f49c}"),
+    ("purple_square", "\u{1f7ea}"),
+    ("purse", "\u{1f45b}"),
+    ("pushpin", "\u{1f4cc}"),
+    ("put_litter_in_its_place", "\u{1f6ae}"),
+    ("qatar", "\u{1f1f6}\u{1f1e6}"),
+    ("question", "\u{2753}"),
+    ("question_mark", "\u{2753}"),
+    ("rabbit", "\u{1f430}"),
+    ("rabbit2", "\u{1f407}"),
+    ("rabbit_face", "\u{1f430}"),
+    ("raccoon", "\u{1f99d}"),
+    ("racehorse", "\u{1f40e}"),
+    ("racing_car", "\u{1f3ce}"),
+    ("racing_motorcycle", "\u{1f3cd}"),
+    ("radio", "\u{1f4fb}"),
+    ("radio_button", "\u{1f518}"),
+    ("radioactive", "\u{2622}"),
+    ("radioactive_sign", "\u{2622}"),
+    ("rage", "\u{1f621}"),
+    ("railway_car", "\u{1f683}"),
+    ("railway_track", "\u{1f6e4}"),
+    ("rainbow", "\u{1f308}"),
+    ("rainbow_flag", "\u{1f3f3}\u{fe0f}\u{200d}\u{1f308}"),
+    ("raised_back_of_hand", "\u{1f91a}"),
+    ("raised_back_of_hand_dark_skin_tone", "\u{1f91a}\u{1f3ff}"),
+    ("raised_back_of_hand_light_skin_tone", "\u{1f91a}\u{1f3fb}"),
+    (
+        "raised_back_of_hand_medium-dark_skin_tone",
+        "\u{1f91a}\u{1f3fe}",
+    ),
+    (
+        "raised_back_of_hand_medium-light_skin_tone",
+        "\u{1f91a}\u{1f3fc}",
+    ),
+    ("raised_back_of_hand_medium_skin_tone", "\u{1f91a}\u{1f3fd}"),
+    ("raised_fist", "\u{270a}"),
+    ("raised_fist_dark_skin_tone", "\u{270a}\u{1f3ff}"),
+    ("raised_fist_light_skin_tone", "\u{270a}\u{1f3fb}"),
+    ("raised_fist_medium-dark_skin_tone", "\u{270a}\u{1f3fe}"),
+    ("raised_fist_medium-light_skin_tone", "\u{270a}\u{1f3fc}"),
+    ("raised_fist_medium_skin_tone", "\u{270a}\u{1f3fd}"),
+    ("raised_hand", "\u{270b}"),
+    ("raised_hand_dark_skin_tone", "\u{270b}\u{1f3ff}"),
+    ("raised_hand_light_skin_tone", "\u{270b}\u{1f3fb}"),
+    ("raised_hand_medium-dark_skin_tone", "\u{270b}\u{1f3fe}"),
+    ("raised_hand_medium-light_skin_tone", "\u{270b}\u{1f3fc}"),
+    ("raised_hand_medium_skin_tone", "\u{270b}\u{1f3fd}"),
+    ("raised_hand_with_fingers_splayed", "\u{1f590}"),
+    (
+        "raised_hand_with_part_between_middle_and_ring_fingers",
+        "\u{1f596}",
+    ),
+    ("raised_hands", "\u{1f64c}"),
+    ("raising_hand", "\u{1f64b}"),
+    ("raising_hands", "\u{1f64c}"),
+    ("raising_hands_dark_skin_tone", "\u{1f64c}\u{1f3ff}"),
+    ("raising_hands_light_skin_tone", "\u{1f64c}\u{1f3fb}"),
+    ("raising_hands_medium-dark_skin_tone", "\u{1f64c}\u{1f3fe}"),
+    ("raising_hands_medium-light_skin_tone", "\u{1f64c}\u{1f3fc}"),
+    ("raising_hands_medium_skin_tone", "\u{1f64c}\u{1f3fd}"),
+    ("ram", "\u{1f40f}"),
+    ("ramen", "\u{1f35c}"),
+    ("rat", "\u{1f400}"),
+    ("razor", "\u{1fa92}"),
+    ("receipt", "\u{1f9fe}"),
+    ("record_button", "\u{23fa}"),
+    ("recycle", "\u{267b}"),
+    ("recycling_symbol", "\u{267b}"),
+    ("red-haired_man", "\u{1f468}\u{200d}\u{1f9b0}"),
+    ("red-haired_woman", "\u{1f469}\u{200d}\u{1f9b0}"),
+    ("red_apple", "\u{1f34e}"),
+    ("red_car", "\u{1f697}"),
+    ("red_circle", "\u{1f534}"),
+    ("red_envelope", "\u{1f9e7}"),
+    ("red_hair", "\u{1f9b0}"),
+    ("red_heart", "\u{2764}"),
+    ("red_paper_lantern", "\u{1f3ee}"),
+    ("red_square", "\u{1f7e5}"),
+    ("red_triangle_pointed_down", "\u{1f53b}"),
+    ("red_triangle_pointed_up", "\u{1f53a}"),
+    ("regional_indicator_a", "\u{1f1e6}"),
+    ("regional_indicator_b", "\u{1f1e7}"),
+    ("regional_indicator_c", "\u{1f1e8}"),
+    ("regional_indicator_d", "\u{1f1e9}"),
+    ("regional_indicator_e", "\u{1f1ea}"),
+    ("regional_indicator_f", "\u{1f1eb}"),
+    ("regional_indicator_g", "\u{1f1ec}"),
+    ("regional_indicator_h", "\u{1f1ed}"),
+    ("regional_indicator_i", "\u{1f1ee}"),
+    ("regional_indicator_j", "\u{1f1ef}"),
+    ("regional_indicator_k", "\u{1f1f0}"),
+    ("regional_indicator_l", "\u{1f1f1}"),
+    ("regional_indicator_m", "\u{1f1f2}"),
+    ("regional_indicator_n", "\u{1f1f3}"),
+    ("regional_indicator_o", "\u{1f1f4}"),
+    ("regional_indicator_p", "\u{1f1f5}"),
+    ("regional_indicator_q", "\u{1f1f6}"),
+    ("regional_indicator_r", "\u{1f1f7}"),
+    ("regional_indicator_s", "\u{1f1f8}"),
+    ("regional_indicator_symbol_letter_a", "\u{1f1e6}"),
+    ("regional_indicator_symbol_letter_b", "\u{1f1e7}"),
+    ("regional_indicator_symbol_letter_c", "\u{1f1e8}"),
+    ("regional_indicator_symbol_letter_d", "\u{1f1e9}"),
+    ("regional_indicator_symbol_letter_e", "\u{1f1ea}"),
+    ("regional_indicator_symbol_letter_f", "\u{1f1eb}"),
+    ("regional_indicator_symbol_letter_g", "\u{1f1ec}"),
+    ("regional_indicator_symbol_letter_h", "\u{1f1ed}"),
+    ("regional_indicator_symbol_letter_i", "\u{1f1ee}"),
+    ("regional_indicator_symbol_letter_j", "\u{1f1ef}"),
+    ("regional_indicator_symbol_letter_k", "\u{1f1f0}"),
+    ("regional_indicator_symbol_letter_l", "\u{1f1f1}"),
+    ("regional_indicator_symbol_letter_m", "\u{1f1f2}"),
+    ("regional_indicator_symbol_letter_n", "\u{1f1f3}"),
+    ("regional_indicator_symbol_letter_o", "\u{1f1f4}"),
+    ("regional_indicator_symbol_letter_p", "\u{1f1f5}"),
+    ("regional_indicator_symbol_letter_q", "\u{1f1f6}"),
+    ("regional_indicator_symbol_letter_r", "\u{1f1f7}"),
+    ("regional_indicator_symbol_letter_s", "\u{1f1f8}"),
+    ("regional_indicator_symbol_letter_t", "\u{1f1f9}"),
+    ("regional_indicator_symbol_letter_u", "\u{1f1fa}"),
+    ("regional_indicator_symbol_letter_v", "\u{1f1fb}"),
+    ("regional_indicator_symbol_letter_w", "\u{1f1fc}"),
+    ("regional_indicator_symbol_letter_x", "\u{1f1fd}"),
+    ("regional_indicator_symbol_letter_y", "\u{1f1fe}"),
+    ("regional_indicator_symbol_letter_z", "\u{1f1ff}"),
+    ("regional_indicator_t", "\u{1f1f9}"),
+    ("regional_indicator_u", "\u{1f1fa}"),
+    ("regional_indicator_v", "\u{1f1fb}"),
+    ("regional_indicator_w", "\u{1f1fc}"),
+    ("regional_indicator_x", "\u{1f1fd}"),
+    ("regional_indicator_y", "\u{1f1fe}"),
+    ("regional_indicator_z", "\u{1f1ff}"),
+    ("registered", "\u{ae}"),
+    ("relaxed", "\u{263a}"),
+    ("relieved", "\u{1f60c}"),
+    ("relieved_face", "\u{1f60c}"),
+    ("reminder_ribbon", "\u{1f397}"),
+    ("repeat", "\u{1f501}"),
+    ("repeat_button", "\u{1f501}"),
+    ("repeat_one", "\u{1f502}"),
+    ("repeat_single_button", "\u{1f502}"),
+    ("restroom", "\u{1f6bb}"),
+    ("reverse_button", "\u{25c0}"),
+    ("reversed_hand_with_middle_finger_extended", "\u{1f595}"),
+    ("revolving_hearts", "\u{1f49e}"),
+    ("rewind", "\u{23ea}"),
+    ("rhinoceros", "\u{1f98f}"),
+    ("ribbon", "\u{1f380}"),
+    ("rice", "\u{1f35a}"),
+    ("rice_ball", "\u{1f359}"),
+    ("rice_cracker", "\u{1f358}"),
+    ("rice_scene", "\u{1f391}"),
+    ("right-facing_fist", "\u{1f91c}"),
+    ("right-facing_fist_dark_skin_tone", "\u{1f91c}\u{1f3ff}"),
+    ("right-facing_fist_light_skin_tone", "\u{1f91c}\u{1f3fb}"),
+    (
+        "right-facing_fist_medium-dark_skin_tone",
+        "\u{1f91c}\u{1f3fe}",
+    ),
+    (
+        "right-facing_fist_medium-light_skin_tone",
+        "\u{1f91c}\u{1f3fc}",
+    ),
+    ("right-facing_fist_medium_skin_tone", "\u{1f91c}\u{1f3fd}"),
+    ("right_anger_bubble", "\u{1f5ef}"),
+    ("right_arrow", "\u{27a1}"),
+    ("right_arrow_curving_down", "\u{2935}"),
+    ("right_arrow_curving_left", "\u{21a9}"),
+    ("right_arrow_curving_up", "\u{2934}"),
+    ("ring", "\u{1f48d}"),
+    ("ringed_planet", "\u{1fa90}"),
+    ("roasted_sweet_potato", "\u{1f360}"),
+    ("robot", "\u{1f916}"),
+    ("robot_face", "\u{1f916}"),
+    ("rocket", "\u{1f680}"),
+    ("roll_of_paper", "\u{1f9fb}"),
+    ("rolled-up_newspaper", "\u{1f5de}"),
+    ("rolled__up_newspaper", "\u{1f5de}"),
+    ("roller_coaster", "\u{1f3a2}"),
+    ("rolling_on_the_floor_laughing", "\u{1f923}"),
+    ("romania", "\u{1f1f7}\u{1f1f4}"),
+    ("rooster", "\u{1f413}"),
+    ("rose", "\u{1f339}"),
+    ("rosette", "\u{1f3f5}"),
+    ("rotating_light", "\u{1f6a8}"),
+    ("round_pushpin", "\u{1f4cd}"),
+    ("rowboat", "\u{1f6a3}"),
+    ("rugby_football", "\u{1f3c9}"),
+    ("runner", "\u{1f3c3}"),
+    ("running", "\u{1f3c3}"),
+    ("running_shirt", "\u{1f3bd}"),
+    ("running_shirt_with_sash", "\u{1f3bd}"),
+    ("running_shoe", "\u{1f45f}"),
+    ("russia", "\u{1f1f7}\u{1f1fa}"),
+    ("rwanda", "\u{1f1f7}\u{1f1fc}"),
+    ("sa", "\u{1f202}"),
+    ("sad_but_relieved_face", "\u{1f625}"),
+    ("safety_pin", "\u{1f9f7}"),
+    ("safety_vest", "\u{1f9ba}"),
+    ("sagittarius", "\u{2650}"),
+    ("sailboat", "\u{26f5}"),
+    ("sake", "\u{1f376}"),
+    ("salt", "\u{1f9c2}"),
+    ("samoa", "\u{1f1fc}\u{1f1f8}"),
+    ("san_marino", "\u{1f1f8}\u{1f1f2}"),
+    ("sandal", "\u{1f461}"),
+    ("sandwich", "\u{1f96a}"),
+    ("santa", "\u{1f385}"),
+    ("santa_claus", "\u{1f385}"),
+    ("santa_claus_dark_skin_tone", "\u{1f385}\u{1f3ff}"),
+    ("santa_claus_light_skin_tone", "\u{1f385}\u{1f3fb}"),
+    ("santa_claus_medium-dark_skin_tone", "\u{1f385}\u{1f3fe}"),
+    ("santa_claus_medium-light_skin_tone", "\u{1f385}\u{1f3fc}"),
+    ("santa_claus_medium_skin_tone", "\u{1f385}\u{1f3fd}"),
+    ("sari", "\u{1f97b}"),
+    ("satellite", "\u{1f4e1}"),
+    ("satellite_antenna", "\u{1f4e1}"),
+    ("satisfied", "\u{1f606}"),
+    ("saudi_arabia", "\u{1f1f8}\u{1f1e6}"),
+    ("sauropod", "\u{1f995}"),
+    ("saxophone", "\u{1f3b7}"),
+    ("scales", "\u{2696}"),
+    ("scarf", "\u{1f9e3}"),
+    ("school", "\u{1f3eb}"),
+    ("school_backpack", "\u{1f392}"),
+    ("school_satchel", "\u{1f392}"),
+    ("scissors", "\u{2702}"),
+    ("scorpio", "\u{264f}"),
+    ("scorpion", "\u{1f982}"),
+    ("scorpius", "\u{264f}"),
+    (
+        "scotland",
+        "\u{1f3f4}\u{e0067}\u{e0062}\u{e0073}\u{e0063}\u{e0074}\u{e007f}",
+    ),
+    ("scream", "\u{1f631}"),
+    ("scream_cat", "\u{1f640}"),
+    ("scroll", "\u{1f4dc}"),
+    ("seat", "\u{1f4ba}"),
+    ("secret", "\u{3299}"),
+    ("see-no-evil_monkey", "\u{1f648}"),
+    ("see_no_evil", "\u{1f648}"),
+    ("seedling", "\u{1f331}"),
+    ("selfie", "\u{1f933}"),
+    ("selfie_dark_skin_tone", "\u{1f933}\u{1f3ff}"),
+    ("selfie_light_skin_tone", "\u{1f933}\u{1f3fb}"),
+    ("selfie_medium-dark_skin_tone", "\u{1f933}\u{1f3fe}"),
+    ("selfie_medium-light_skin_tone", "\u{1f933}\u{1f3fc}"),
+    ("selfie_medium_skin_tone", "\u{1f933}\u{1f3fd}"),
+    ("senegal", "\u{1f1f8}\u{1f1f3}"),
+    ("serbia", "\u{1f1f7}\u{1f1f8}"),
+    ("service_dog", "\u{1f415}\u{200d}\u{1f9ba}"),
+    ("seven", "\u{37}\u{fe0f}\u{20e3}"),
+    ("seven-thirty", "\u{1f562}"),
+    ("seychelles", "\u{1f1f8}\u{1f1e8}"),
+    ("shallow_pan_of_food", "\u{1f958}"),
+    ("shamrock", "\u{2618}"),
+    ("shark", "\u{1f988}"),
+    ("shaved_ice", "\u{1f367}"),
+    ("sheaf_of_rice", "\u{1f33e}"),
+    ("sheep", "\u{1f411}"),
+    ("shell", "\u{1f41a}"),
+    ("shield", "\u{1f6e1}"),
+    ("shinto_shrine", "\u{26e9}"),
+    ("ship", "\u{1f6a2}"),
+    ("shirt", "\u{1f455}"),
+    ("shit", "\u{1f4a9}"),
+    ("shoe", "\u{1f45e}"),
+    ("shooting_star", "\u{1f320}"),
+    ("shopping_bags", "\u{1f6cd}"),
+    ("shopping_cart", "\u{1f6d2}"),
+    ("shortcake", "\u{1f370}"),
+    ("shorts", "\u{1fa73}"),
+    ("shower", "\u{1f6bf}"),
+    ("shrimp", "\u{1f990}"),
+    ("shuffle_tracks_button", "\u{1f500}"),
+    ("shushing_face", "\u{1f92b}"),
+    ("sierra_leone", "\u{1f1f8}\u{1f1f1}"),
+    ("sign_of_the_horns", "\u{1f918}"),
+    ("sign_of_the_horns_dark_skin_tone", "\u{1f918}\u{1f3ff}"),
+    ("sign_of_the_horns_light_skin_tone", "\u{1f918}\u{1f3fb}"),
+    (
+        "sign_of_the_horns_medium-dark_skin_tone",
+        "\u{1f918}\u{1f3fe}",
+    ),
+    (
+        "sign_of_the_horns_medium-light_skin_tone",
+        "\u{1f918}\u{1f3fc}",
+    ),
+    ("sign_of_the_horns_medium_skin_tone", "\u{1f918}\u{1f3fd}"),
+    ("signal_strength", "\u{1f4f6}"),
+    ("singapore", "\u{1f1f8}\u{1f1ec}"),
+    ("sint_maarten", "\u{1f1f8}\u{1f1fd}"),
+    ("six", "\u{36}\u{fe0f}\u{20e3}"),
+    ("six-thirty", "\u{1f561}"),
+    ("six_pointed_star", "\u{1f52f}"),
+    ("skateboard", "\u{1f6f9}"),
+    ("ski", "\u{1f3bf}"),
+    ("skier", "\u{26f7}"),
+    ("skis", "\u{1f3bf}"),
+    ("skull", "\u{1f480}"),
+    ("skull_and_crossbones", "\u{2620}"),
+    ("skunk", "\u{1f9a8}"),
+    ("sled", "\u{1f6f7}"),
+    ("sleeping", "\u{1f634}"),
+    ("sleeping_accommodation", "\u{1f6cc}"),
+    ("sleeping_face", "\u{1f634}"),
+    ("sleepy", "\u{1f62a}"),
+    ("sleepy_face", "\u{1f62a}"),
+    ("sleuth_or_spy", "\u{1f575}"),
+    ("slightly_frowning_face", "\u{1f641}"),
+    ("slightly_smiling_face", "\u{1f642}"),
+    ("slot_machine", "\u{1f3b0}"),
+    ("sloth", "\u{1f9a5}"),
+    ("slovakia", "\u{1f1f8}\u{1f1f0}"),
+    ("slovenia", "\u{1f1f8}\u{1f1ee}"),
+    ("small_airplane", "\u{1f6e9}"),
+    ("small_blue_diamond", "\u{1f539}"),
+    ("small_orange_diamond", "\u{1f538}"),
+    ("small_red_triangle", "\u{1f53a}"),
+    ("small_red_triangle_down", "\u{1f53b}"),
+    ("smile", "\u{1f604}"),
+    ("smile_cat", "\u{1f638}"),
+    ("smiley", "\u{1f603}"),
+    ("smiley_cat", "\u{1f63a}"),
+    ("smiling_cat_face_with_heart-eyes", "\u{1f63b}"),
+    ("smiling_face", "\u{263a}"),
+    ("smiling_face_with_3_hearts", "\u{1f970}"),
+    ("smiling_face_with_halo", "\u{1f607}"),
+    ("smiling_face_with_heart-eyes", "\u{1f60d}"),
+    ("smiling_face_with_horns", "\u{1f608}"),
+    ("smiling_face_with_smiling_eyes", "\u{1f60a}"),
+    ("smiling_face_with_sunglasses", "\u{1f60e}"),
+    ("smiling_imp", "\u{1f608}"),
+    ("smirk", "\u{1f60f}"),
+    ("smirk_cat", "\u{1f63c}"),
+    ("smirking_face", "\u{1f60f}"),
+    ("smoking", "\u{1f6ac}"),
+    ("snail", "\u{1f40c}"),
+    ("snake", "\u{1f40d}"),
+    ("sneezing_face", "\u{1f927}"),
+    ("snow-capped_mountain", "\u{1f3d4}"),
+    ("snow_capped_mountain", "\u{1f3d4}"),
+    ("snowboarder", "\u{1f3c2}"),
+    ("snowboarder_dark_skin_tone", "\u{1f3c2}\u{1f3ff}"),
+    ("snowboarder_light_skin_tone", "\u{1f3c2}\u{1f3fb}"),
+    ("snowboarder_medium-dark_skin_tone", "\u{1f3c2}\u{1f3fe}"),
+    ("snowboarder_medium-light_skin_tone", "\u{1f3c2}\u{1f3fc}"),
+    ("snowboarder_medium_skin_tone", "\u{1f3c2}\u{1f3fd}"),
+    ("snowflake", "\u{2744}"),
+    ("snowman", "\u{2603}"),
+    ("snowman_without_snow", "\u{26c4}"),
+    ("soap", "\u{1f9fc}"),
+    ("sob", "\u{1f62d}"),
+    ("soccer", "\u{26bd}"),
+    ("soccer_ball", "\u{26bd}"),
+    ("socks", "\u{1f9e6}"),
+    ("soft_ice_cream", "\u{1f366}"),
+    ("softball", "\u{1f94e}"),
+    ("solomon_islands", "\u{1f1f8}\u{1f1e7}"),
+    ("somalia", "\u{1f1f8}\u{1f1f4}"),
+    ("soon", "\u{1f51c}"),
+    ("soon_arrow", "\u{1f51c}"),
+    ("sos", "\u{1f198}"),
+    ("sos_button", "\u{1f198}"),
+    ("sound", "\u{1f509}"),
+    ("south_africa", "\u{1f1ff}\u{1f1e6}"),
+    ("south_korea", "\u{1f1f0}\u{1f1f7}"),
+    ("south_sudan", "\u{1f1f8}\u{1f1f8}"),
+    ("space_invader", "\u{1f47e}"),
+    ("spade_suit", "\u{2660}"),
+    ("spades", "\u{2660}"),
+    ("spaghetti", "\u{1f35d}"),
+    ("spain", "\u{1f1ea}\u{1f1f8}"),
+    ("sparkle", "\u{2747}"),
+    ("sparkler", "\u{1f387}"),
+    ("sparkles", "\u{2728}"),
+    ("sparkling_heart", "\u{1f496}"),
+    ("speak-no-evil_monkey", "\u{1f64a}"),
+    ("speak_no_evil", "\u{1f64a}"),
+    ("speaker", "\u{1f508}"),
+    ("speaker_high_volume", "\u{1f50a}"),
+    ("speaker_low_volume", "\u{1f508}"),
+    ("speaker_medium_volume", "\u{1f509}"),
+    ("speaking_head", "\u{1f5e3}"),
+    ("speaking_head_in_silhouette", "\u{1f5e3}"),
+    ("speech_balloon", "\u{1f4ac}"),
+    ("speedboat", "\u{1f6a4}"),
+    ("spider", "\u{1f577}"),
+    ("spider_web", "\u{1f578}"),
+    ("spiral_calendar", "\u{1f5d3}"),
+    ("spiral_calendar_pad", "\u{1f5d3}"),
+    ("spiral_note_pad", "\u{1f5d2}"),
+    ("spiral_notepad", "\u{1f5d2}"),
+    ("spiral_shell", "\u{1f41a}"),
+    ("sponge", "\u{1f9fd}"),
+    ("spoon", "\u{1f944}"),
+    ("sport_utility_vehicle", "\u{1f699}"),
+    ("sports_medal", "\u{1f3c5}"),
+    ("spouting_whale", "\u{1f433}"),
+    ("squid", "\u{1f991}"),
+    ("squinting_face_with_tongue", "\u{1f61d}"),
+    ("sri_lanka", "\u{1f1f1}\u{1f1f0}"),
+    ("stadium", "\u{1f3df}"),
+    ("star", "\u{2b50}"),
+    ("star-struck", "\u{1f929}"),
+    ("star2", "\u{1f31f}"),
+    ("star_and_crescent", "\u{262a}"),
+    ("star_of_david", "\u{2721}"),
+    ("stars", "\u{1f320}"),
+    ("station", "\u{1f689}"),
+    ("statue_of_liberty", "\u{1f5fd}"),
+    ("steam_locomotive", "\u{1f682}"),
+    ("steaming_bowl", "\u{1f35c}"),
+    ("stethoscope", "\u{1fa7a}"),
+    ("stew", "\u{1f372}"),
+    ("stop_button", "\u{23f9}"),
+    ("stop_sign", "\u{1f6d1}"),
+    ("stopwatch", "\u{23f1}"),
+    ("straight_ruler", "\u{1f4cf}"),
+    ("strawberry", "\u{1f353}"),
+    ("stuck_out_tongue", "\u{1f61b}"),
+    ("stuck_out_tongue_closed_eyes", "\u{1f61d}"),
+    ("stuck_out_tongue_winking_eye", "\u{1f61c}"),
+    ("studio_microphone", "\u{1f399}"),
+    ("stuffed_flatbread", "\u{1f959}"),
+    ("sudan", "\u{1f1f8}\u{1f1e9}"),
+    ("sun", "\u{2600}"),
+    ("sun_behind_cloud", "\u{26c5}"),
+    ("sun_behind_large_cloud", "\u{1f325}"),
+    ("sun_behind_rain_cloud", "\u{1f326}"),
+    ("sun_behind_small_cloud", "\u{1f324}"),
+    ("sun_with_face", "\u{1f31e}"),
+    ("sunflower", "\u{1f33b}"),
+    ("sunglasses", "\u{1f60e}"),
+    ("sunny", "\u{2600}"),
+    ("sunrise", "\u{1f305}"),
+    ("sunrise_over_mountains", "\u{1f304}"),
+    ("sunset", "\u{1f307}"),
+    ("superhero", "\u{1f9b8}"),
+    ("supervillain", "\u{1f9b9}"),
+    ("surfer", "\u{1f3c4}"),
+    ("suriname", "\u{1f1f8}\u{1f1f7}"),
+    ("sushi", "\u{1f363}"),
+    ("suspension_railway", "\u{1f69f}"),
+    ("swan", "\u{1f9a2}"),
+    ("swaziland", "\u{1f1f8}\u{1f1ff}"),
+    ("sweat", "\u{1f613}"),
+    ("sweat_droplets", "\u{1f4a6}"),
+    ("sweat_drops", "\u{1f4a6}"),
+    ("sweat_smile", "\u{1f605}"),
+    ("sweden", "\u{1f1f8}\u{1f1ea}"),
+    ("sweet_potato", "\u{1f360}"),
+    ("swimmer", "\u{1f3ca}"),
+    ("switzerland", "\u{1f1e8}\u{1f1ed}"),
+    ("symbols", "\u{1f523}"),
+    ("synagogue", "\u{1f54d}"),
+    ("syria", "\u{1f1f8}\u{1f1fe}"),
+    ("syringe", "\u{1f489}"),
+    ("t-rex", "\u{1f996}"),
+    ("t-shirt", "\u{1f455}"),
+    ("table_tennis_paddle_and_ball", "\u{1f3d3}"),
+    ("taco", "\u{1f32e}"),
+    ("tada", "\u{1f389}"),
+    ("taiwan", "\u{1f1f9}\u{1f1fc}"),
+    ("tajikistan", "\u{1f1f9}\u{1f1ef}"),
+    ("takeout_box", "\u{1f961}"),
+    ("tanabata_tree", "\u{1f38b}"),
+    ("tangerine", "\u{1f34a}"),
+    ("tanzania", "\u{1f1f9}\u{1f1ff}"),
+    ("taurus", "\u{2649}"),
+    ("taxi", "\u{1f695}"),
+    ("tea", "\u{1f375}"),
+    ("teacup_without_handle", "\u{1f375}"),
+    ("tear-off_calendar", "\u{1f4c6}"),
+    ("teddy_bear", "\u{1f9f8}"),
+    ("telephone", "\u{260e}"),
+    ("telephone_receiver", "\u{1f4de}"),
+    ("telescope", "\u{1f52d}"),
+    ("television", "\u{1f4fa}"),
+    ("ten", "\u{1f51f}"),
+    ("ten-thirty", "\u{1f565}"),
+    ("tennis", "\u{1f3be}"),
+    ("tent", "\u{26fa}"),
+    ("test_tube", "\u{1f9ea}"),
+    ("thailand", "\u{1f1f9}\u{1f1ed}"),
+    ("thermometer", "\u{1f321}"),
+    ("thinking_face", "\u{1f914}"),
+    ("thought_balloon", "\u{1f4ad}"),
+    ("thread", "\u{1f9f5}"),
+    ("three", "\u{33}\u{fe0f}\u{20e3}"),
+    ("three-thirty", "\u{1f55e}"),
+    ("three_button_mouse", "\u{1f5b1}"),
+    ("thumbs_down", "\u{1f44e}"),
+    ("thumbs_down_dark_skin_tone", "\u{1f44e}\u{1f3ff}"),
+    ("thumbs_down_light_skin_tone", "\u{1f44e}\u{1f3fb}"),
+    ("thumbs_down_medium-dark_skin_tone", "\u{1f44e}\u{1f3fe}"),
+    ("thumbs_down_medium-light_skin_tone", "\u{1f44e}\u{1f3fc}"),
+    ("thumbs_down_medium_skin_tone", "\u{1f44e}\u{1f3fd}"),
+    ("thumbs_up", "\u{1f44d}"),
+    ("thumbs_up_dark_skin_tone", "\u{1f44d}\u{1f3ff}"),
+    ("thumbs_up_light_skin_tone", "\u{1f44d}\u{1f3fb}"),
+    ("thumbs_up_medium-dark_skin_tone", "\u{1f44d}\u{1f3fe}"),
+    ("thumbs_up_medium-light_skin_tone", "\u{1f44d}\u{1f3fc}"),
+    ("thumbs_up_medium_skin_tone", "\u{1f44d}\u{1f3fd}"),
+    ("thumbsdown", "\u{1f44e}"),
+    ("thumbsup", "\u{1f44d}"),
+    ("thunder_cloud_and_rain", "\u{26c8}"),
+    ("ticket", "\u{1f3ab}"),
+    ("tiger", "\u{1f42f}"),
+    ("tiger2", "\u{1f405}"),
+    ("tiger_face", "\u{1f42f}"),
+    ("timer_clock", "\u{23f2}"),
+    ("timor-leste", "\u{1f1f9}\u{1f1f1}"),
+    ("tired_face", "\u{1f62b}"),
+    ("tm", "\u{2122}"),
+    ("togo", "\u{1f1f9}\u{1f1ec}"),
+    ("toilet", "\u{1f6bd}"),
+    ("tokelau", "\u{1f1f9}\u{1f1f0}"),
+    ("tokyo_tower", "\u{1f5fc}"),
+    ("tomato", "\u{1f345}"),
+    ("tonga", "\u{1f1f9}\u{1f1f4}"),
+    ("tongue", "\u{1f445}"),
+    ("toolbox", "\u{1f9f0}"),
+    ("tooth", "\u{1f9b7}"),
+    ("top", "\u{1f51d}"),
+    ("top_arrow", "\u{1f51d}"),
+    ("top_hat", "\u{1f3a9}"),
+    ("tophat", "\u{1f3a9}"),
+    ("tornado", "\u{1f32a}"),
+    ("trackball", "\u{1f5b2}"),
+    ("tractor", "\u{1f69c}"),
+    ("trade_mark", "\u{2122}"),
+    ("traffic_light", "\u{1f6a5}"),
+    ("train", "\u{1f68b}"),
+    ("train2", "\u{1f686}"),
+    ("tram", "\u{1f68a}"),
+    ("tram_car", "\u{1f68b}"),
+    ("triangular_flag", "\u{1f6a9}"),
+    ("triangular_flag_on_post", "\u{1f6a9}"),
+    ("triangular_ruler", "\u{1f4d0}"),
+    ("trident", "\u{1f531}"),
+    ("trident_emblem", "\u{1f531}"),
+    ("tristan_da_cunha", "\u{1f1f9}\u{1f1e6}"),
+    ("triumph", "\u{1f624}"),
+    ("trolleybus", "\u{1f68e}"),
+    ("trophy", "\u{1f3c6}"),
+    ("tropical_drink", "\u{1f379}"),
+    ("tropical_fish", "\u{1f420}"),
+    ("truck", "\u{1f69a}"),
+    ("trumpet", "\u{1f3ba}"),
+    ("tshirt", "\u{1f455}"),
+    ("tulip", "\u{1f337}"),
+    ("tumbler_glass", "\u{1f943}"),
+    ("tunisia", "\u{1f1f9}\u{1f1f3}"),
+    ("turkey", "\u{1f983}"),
+    ("turkmenistan", "\u{1f1f9}\u{1f1f2}"),
+    ("turtle", "\u{1f422}"),
+    ("tuvalu", "\u{1f1f9}\u{1f1fb}"),
+    ("tv", "\u{1f4fa}"),
+    ("twelve-thirty", "\u{1f567}"),
+    ("twisted_rightwards_arrows", "\u{1f500}"),
+    ("two", "\u{32}\u{fe0f}\u{20e3}"),
+    ("two-hump_camel", "\u{1f42b}"),
+    ("two-thirty", "\u{1f55d}"),
+    ("two_hearts", "\u{1f495}"),
+    ("two_men_holding_hands", "\u{1f46c}"),
+    ("two_women_holding_hands", "\u{1f46d}"),
+    ("u5272", "\u{1f239}"),
+    ("u5408", "\u{1f234}"),
+    ("u55b6", "\u{1f23a}"),
+    ("u6307", "\u{1f22f}"),
+    ("u6708", "\u{1f237}"),
+    ("u6709", "\u{1f236}"),
+    ("u6e80", "\u{1f235}"),
+    ("u7121", "\u{1f21a}"),
+    ("u7533", "\u{1f238}"),
+    ("u7981", "\u{1f232}"),
+    ("u7a7a", "\u{1f233}"),
+    ("uganda", "\u{1f1fa}\u{1f1ec}"),
+    ("ukraine", "\u{1f1fa}\u{1f1e6}"),
+    ("umbrella", "\u{2602}"),
+    ("umbrella_on_ground", "\u{26f1}"),
+    ("umbrella_with_rain_drops", "\u{2614}"),
+    ("unamused", "\u{1f612}"),
+    ("unamused_face", "\u{1f612}"),
+    ("underage", "\u{1f51e}"),
+    ("unicorn_face", "\u{1f984}"),
+    ("united_arab_emirates", "\u{1f1e6}\u{1f1ea}"),
+    ("united_kingdom", "\u{1f1ec}\u{1f1e7}"),
+    ("united_nations", "\u{1f1fa}\u{1f1f3}"),
+    ("united_states", "\u{1f1fa}\u{1f1f8}"),
+    ("unlock", "\u{1f513}"),
+    ("unlocked", "\u{1f513}"),
+    ("up", "\u{1f199}"),
+    ("up-down_arrow", "\u{2195}"),
+    ("up-left_arrow", "\u{2196}"),
+    ("up-right_arrow", "\u{2197}"),
+    ("up_arrow", "\u{2b06}"),
+    ("upside-down_face", "\u{1f643}"),
+    ("upside__down_face", "\u{1f643}"),
+    ("upwards_button", "\u{1f53c}"),
+    ("uruguay", "\u{1f1fa}\u{1f1fe}"),
+    ("uzbekistan", "\u{1f1fa}\u{1f1ff}"),
+    ("v", "\u{270c}"),
+    ("vampire", "\u{1f9db}"),
+    ("vampire_dark_skin_tone", "\u{1f9db}\u{1f3ff}"),
+    ("vampire_light_skin_tone", "\u{1f9db}\u{1f3fb}"),
+    ("vampire_medium-dark_skin_tone", "\u{1f9db}\u{1f3fe}"),
+    ("vampire_medium-light_skin_tone", "\u{1f9db}\u{1f3fc}"),
+    ("vampire_medium_skin_tone", "\u{1f9db}\u{1f3fd}"),
+    ("vanuatu", "\u{1f1fb}\u{1f1fa}"),
+    ("vatican_city", "\u{1f1fb}\u{1f1e6}"),
+    ("venezuela", "\u{1f1fb}\u{1f1ea}"),
+    ("vertical_traffic_light", "\u{1f6a6}"),
+    ("vhs", "\u{1f4fc}"),
+    ("vibration_mode", "\u{1f4f3}"),
+    ("victory_hand", "\u{270c}"),
+    ("victory_hand_dark_skin_tone", "\u{270c}\u{1f3ff}"),
+    ("victory_hand_light_skin_tone", "\u{270c}\u{1f3fb}"),
+    ("victory_hand_medium-dark_skin_tone", "\u{270c}\u{1f3fe}"),
+    ("victory_hand_medium-light_skin_tone", "\u{270c}\u{1f3fc}"),
+    ("victory_hand_medium_skin_tone", "\u{270c}\u{1f3fd}"),
+    ("video_camera", "\u{1f4f9}"),
+    ("video_game", "\u{1f3ae}"),
+    ("videocassette", "\u{1f4fc}"),
+    ("vietnam", "\u{1f1fb}\u{1f1f3}"),
+    ("violin", "\u{1f3bb}"),
+    ("virgo", "\u{264d}"),
+    ("volcano", "\u{1f30b}"),
+    ("volleyball", "\u{1f3d0}"),
+    ("vs", "\u{1f19a}"),
+    ("vs_button", "\u{1f19a}"),
+    ("vulcan_salute", "\u{1f596}"),
+    ("vulcan_salute_dark_skin_tone", "\u{1f596}\u{1f3ff}"),
+    ("vulcan_salute_light_skin_tone", "\u{1f596}\u{1f3fb}"),
+    ("vulcan_salute_medium-dark_skin_tone", "\u{1f596}\u{1f3fe}"),
+    ("vulcan_salute_medium-light_skin_tone", "\u{1f596}\u{1f3fc}"),
+    ("vulcan_salute_medium_skin_tone", "\u{1f596}\u{1f3fd}"),
+    ("waffle", "\u{1f9c7}"),
+    (
+        "wales",
+        "\u{1f3f4}\u{e0067}\u{e0062}\u{e0077}\u{e006c}\u{e0073}\u{e007f}",
+    ),
+    ("walking", "\u{1f6b6}"),
+    ("waning_crescent_moon", "\u{1f318}"),
+    ("waning_gibbous_moon", "\u{1f316}"),
+    ("warning", "\u{26a0}"),
+    ("wastebasket", "\u{1f5d1}"),
+    ("watch", "\u{231a}"),
+    ("water_buffalo", "\u{1f403}"),
+    ("water_closet", "\u{1f6be}"),
+    ("water_wave", "\u{1f30a}"),
+    ("watermelon", "\u{1f349}"),
+    ("wave", "\u{1f44b}"),
+    ("waving_black_flag", "\u{1f3f4}"),
+    ("waving_hand", "\u{1f44b}"),
+    ("waving_hand_dark_skin_tone", "\u{1f44b}\u{1f3ff}"),
+    ("waving_hand_light_skin_tone", "\u{1f44b}\u{1f3fb}"),
+    ("waving_hand_medium-dark_skin_tone", "\u{1f44b}\u{1f3fe}"),
+    ("waving_hand_medium-light_skin_tone", "\u{1f44b}\u{1f3fc}"),
+    ("waving_hand_medium_skin_tone", "\u{1f44b}\u{1f3fd}"),
+    ("waving_white_flag", "\u{1f3f3}"),
+    ("wavy_dash", "\u{3030}"),
+    ("waxing_crescent_moon", "\u{1f312}"),
+    ("waxing_gibbous_moon", "\u{1f314}"),
+    ("wc", "\u{1f6be}"),
+    ("weary", "\u{1f629}"),
+    ("weary_cat_face", "\u{1f640}"),
+    ("weary_face", "\u{1f629}"),
+    ("wedding", "\u{1f492}"),
+    ("weight_lifter", "\u{1f3cb}"),
+    ("western_sahara", "\u{1f1ea}\u{1f1ed}"),
+    ("whale", "\u{1f433}"),
+    ("whale2", "\u{1f40b}"),
+    ("wheel_of_dharma", "\u{2638}"),
+    ("wheelchair", "\u{267f}"),
+    ("wheelchair_symbol", "\u{267f}"),
+    ("white-haired_man", "\u{1f468}\u{200d}\u{1f9b3}"),
+    ("white-haired_woman", "\u{1f469}\u{200d}\u{1f9b3}"),
+    ("white_check_mark", "\u{2705}"),
+    ("white_circle", "\u{26aa}"),
+    ("white_exclamation_mark", "\u{2755}"),
+    ("white_flag", "\u{1f3f3}"),
+    ("white_flower", "\u{1f4ae}"),
+    ("white_frowning_face", "\u{2639}"),
+    ("white_hair", "\u{1f9b3}"),
+    ("white_heart", "\u{1f90d}"),
+    ("white_heavy_check_mark", "\u{2705}"),
+    ("white_large_square", "\u{2b1c}"),
+    ("white_medium-small_square", "\u{25fd}"),
+    ("white_medium_small_square", "\u{25fd}"),
+    ("white_medium_square", "\u{25fb}"),
+    ("white_medium_star", "\u{2b50}"),
+    ("white_question_mark", "\u{2754}"),
+    ("white_small_square", "\u{25ab}"),
+    ("white_square_button", "\u{1f533}"),
+    ("white_sun_behind_cloud", "\u{1f325}"),
+    ("white_sun_behind_cloud_with_rain", "\u{1f326}"),
+    ("white_sun_with_small_cloud", "\u{1f324}"),
+    ("wilted_flower", "\u{1f940}"),
+    ("wind_blowing_face", "\u{1f32c}"),
+    ("wind_chime", "\u{1f390}"),
+    ("wind_face", "\u{1f32c}"),
+    ("wine_glass", "\u{1f377}"),
+    ("wink", "\u{1f609}"),
+    ("winking_face", "\u{1f609}"),
+    ("winking_face_with_tongue", "\u{1f61c}"),
+    ("wolf", "\u{1f43a}"),
+    ("wolf_face", "\u{1f43a}"),
+    ("woman", "\u{1f469}"),
+    ("woman_artist", "\u{1f469}\u{200d}\u{1f3a8}"),
+    (
+        "woman_artist_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "woman_artist_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "woman_artist_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "woman_artist_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f3a8}",
+    ),
+    (
+        "woman_artist_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f3a8}",
+    ),
+    ("woman_astronaut", "\u{1f469}\u{200d}\u{1f680}"),
+    (
+        "woman_astronaut_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f680}",
+    ),
+    (
+        "woman_astronaut_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f680}",
+    ),
+    (
+        "woman_astronaut_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f680}",
+    ),
+    (
+        "woman_astronaut_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f680}",
+    ),
+    (
+        "woman_astronaut_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f680}",
+    ),
+    ("woman_biking", "\u{1f6b4}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_biking_dark_skin_tone",
+        "\u{1f6b4}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_biking_light_skin_tone",
+        "\u{1f6b4}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_biking_medium-dark_skin_tone",
+        "\u{1f6b4}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_biking_medium-light_skin_tone",
+        "\u{1f6b4}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_biking_medium_skin_tone",
+        "\u{1f6b4}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball",
+        "\u{26f9}\u{fe0f}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball_dark_skin_tone",
+        "\u{26f9}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball_light_skin_tone",
+        "\u{26f9}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball_medium-dark_skin_tone",
+        "\u{26f9}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball_medium-light_skin_tone",
+        "\u{26f9}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bouncing_ball_medium_skin_tone",
+        "\u{26f9}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_bowing", "\u{1f647}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_bowing_dark_skin_tone",
+        "\u{1f647}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bowing_light_skin_tone",
+        "\u{1f647}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bowing_medium-dark_skin_tone",
+        "\u{1f647}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bowing_medium-light_skin_tone",
+        "\u{1f647}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_bowing_medium_skin_tone",
+        "\u{1f647}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_cartwheeling", "\u{1f938}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_cartwheeling_dark_skin_tone",
+        "\u{1f938}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_cartwheeling_light_skin_tone",
+        "\u{1f938}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_cartwheeling_medium-dark_skin_tone",
+        "\u{1f938}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_cartwheeling_medium-light_skin_tone",
+        "\u{1f938}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_cartwheeling_medium_skin_tone",
+        "\u{1f938}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_climbing", "\u{1f9d7}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_climbing_dark_skin_tone",
+        "\u{1f9d7}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_climbing_light_skin_tone",
+        "\u{1f9d7}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_climbing_medium-dark_skin_tone",
+        "\u{1f9d7}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_climbing_medium-light_skin_tone",
+        "\u{1f9d7}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_climbing_medium_skin_tone",
+        "\u{1f9d7}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker",
+        "\u{1f477}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker_dark_skin_tone",
+        "\u{1f477}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker_light_skin_tone",
+        "\u{1f477}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker_medium-dark_skin_tone",
+        "\u{1f477}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker_medium-light_skin_tone",
+        "\u{1f477}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_construction_worker_medium_skin_tone",
+        "\u{1f477}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_cook", "\u{1f469}\u{200d}\u{1f373}"),
+    (
+        "woman_cook_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f373}",
+    ),
+    (
+        "woman_cook_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f373}",
+    ),
+    (
+        "woman_cook_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f373}",
+    ),
+    (
+        "woman_cook_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f373}",
+    ),
+    (
+        "woman_cook_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f373}",
+    ),
+    ("woman_dancing", "\u{1f483}"),
+    ("woman_dancing_dark_skin_tone", "\u{1f483}\u{1f3ff}"),
+    ("woman_dancing_light_skin_tone", "\u{1f483}\u{1f3fb}"),
+    ("woman_dancing_medium-dark_skin_tone", "\u{1f483}\u{1f3fe}"),
+    ("woman_dancing_medium-light_skin_tone", "\u{1f483}\u{1f3fc}"),
+    ("woman_dancing_medium_skin_tone", "\u{1f483}\u{1f3fd}"),
+    ("woman_dark_skin_tone", "\u{1f469}\u{1f3ff}"),
+    (
+        "woman_detective",
+        "\u{1f575}\u{fe0f}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_detective_dark_skin_tone",
+        "\u{1f575}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_detective_light_skin_tone",
+        "\u{1f575}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_detective_medium-dark_skin_tone",
+        "\u{1f575}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_detective_medium-light_skin_tone",
+        "\u{1f575}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_detective_medium_skin_tone",
+        "\u{1f575}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_elf", "\u{1f9dd}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_elf_dark_skin_tone",
+        "\u{1f9dd}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_elf_light_skin_tone",
+        "\u{1f9dd}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_elf_medium-dark_skin_tone",
+        "\u{1f9dd}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_elf_medium-light_skin_tone",
+        "\u{1f9dd}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_elf_medium_skin_tone",
+        "\u{1f9dd}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_facepalming", "\u{1f926}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_facepalming_dark_skin_tone",
+        "\u{1f926}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_facepalming_light_skin_tone",
+        "\u{1f926}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_facepalming_medium-dark_skin_tone",
+        "\u{1f926}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_facepalming_medium-light_skin_tone",
+        "\u{1f926}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_facepalming_medium_skin_tone",
+        "\u{1f926}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_factory_worker", "\u{1f469}\u{200d}\u{1f3ed}"),
+    (
+        "woman_factory_worker_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "woman_factory_worker_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "woman_factory_worker_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "woman_factory_worker_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f3ed}",
+    ),
+    (
+        "woman_factory_worker_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f3ed}",
+    ),
+    ("woman_fairy", "\u{1f9da}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_fairy_dark_skin_tone",
+        "\u{1f9da}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_fairy_light_skin_tone",
+        "\u{1f9da}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_fairy_medium-dark_skin_tone",
+        "\u{1f9da}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_fairy_medium-light_skin_tone",
+        "\u{1f9da}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_fairy_medium_skin_tone",
+        "\u{1f9da}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_farmer", "\u{1f469}\u{200d}\u{1f33e}"),
+    (
+        "woman_farmer_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f33e}",
+    ),
+    (
+        "woman_farmer_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f33e}",
+    ),
+    (
+        "woman_farmer_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f33e}",
+    ),
+    (
+        "woman_farmer_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f33e}",
+    ),
+    (
+        "woman_farmer_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f33e}",
+    ),
+    ("woman_firefighter", "\u{1f469}\u{200d}\u{1f692}"),
+    (
+        "woman_firefighter_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f692}",
+    ),
+    (
+        "woman_firefighter_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f692}",
+    ),
+    (
+        "woman_firefighter_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f692}",
+    ),
+    (
+        "woman_firefighter_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f692}",
+    ),
+    (
+        "woman_firefighter_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f692}",
+    ),
+    ("woman_frowning", "\u{1f64d}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_frowning_dark_skin_tone",
+        "\u{1f64d}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_frowning_light_skin_tone",
+        "\u{1f64d}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_frowning_medium-dark_skin_tone",
+        "\u{1f64d}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_frowning_medium-light_skin_tone",
+        "\u{1f64d}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_frowning_medium_skin_tone",
+        "\u{1f64d}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_genie", "\u{1f9de}\u{200d}\u{2640}\u{fe0f}"),
+    ("woman_gesturing_no", "\u{1f645}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_gesturing_no_dark_skin_tone",
+        "\u{1f645}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_no_light_skin_tone",
+        "\u{1f645}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_no_medium-dark_skin_tone",
+        "\u{1f645}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_no_medium-light_skin_tone",
+        "\u{1f645}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_no_medium_skin_tone",
+        "\u{1f645}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_gesturing_ok", "\u{1f646}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_gesturing_ok_dark_skin_tone",
+        "\u{1f646}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_ok_light_skin_tone",
+        "\u{1f646}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_ok_medium-dark_skin_tone",
+        "\u{1f646}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_ok_medium-light_skin_tone",
+        "\u{1f646}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_gesturing_ok_medium_skin_tone",
+        "\u{1f646}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_getting_haircut", "\u{1f487}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_getting_haircut_dark_skin_tone",
+        "\u{1f487}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_haircut_light_skin_tone",
+        "\u{1f487}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_haircut_medium-dark_skin_tone",
+        "\u{1f487}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_haircut_medium-light_skin_tone",
+        "\u{1f487}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_haircut_medium_skin_tone",
+        "\u{1f487}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_getting_massage", "\u{1f486}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_getting_massage_dark_skin_tone",
+        "\u{1f486}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_massage_light_skin_tone",
+        "\u{1f486}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_massage_medium-dark_skin_tone",
+        "\u{1f486}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_massage_medium-light_skin_tone",
+        "\u{1f486}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_getting_massage_medium_skin_tone",
+        "\u{1f486}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_golfing", "\u{1f3cc}\u{fe0f}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_golfing_dark_skin_tone",
+        "\u{1f3cc}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_golfing_light_skin_tone",
+        "\u{1f3cc}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_golfing_medium-dark_skin_tone",
+        "\u{1f3cc}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_golfing_medium-light_skin_tone",
+        "\u{1f3cc}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_golfing_medium_skin_tone",
+        "\u{1f3cc}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_guard", "\u{1f482}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_guard_dark_skin_tone",
+        "\u{1f482}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_guard_light_skin_tone",
+        "\u{1f482}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_guard_medium-dark_skin_tone",
+        "\u{1f482}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_guard_medium-light_skin_tone",
+        "\u{1f482}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_guard_medium_skin_tone",
+        "\u{1f482}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_health_worker", "\u{1f469}\u{200d}\u{2695}\u{fe0f}"),
+    (
+        "woman_health_worker_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "woman_health_worker_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "woman_health_worker_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "woman_health_worker_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "woman_health_worker_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{2695}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position",
+        "\u{1f9d8}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position_dark_skin_tone",
+        "\u{1f9d8}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position_light_skin_tone",
+        "\u{1f9d8}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position_medium-dark_skin_tone",
+        "\u{1f9d8}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position_medium-light_skin_tone",
+        "\u{1f9d8}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_lotus_position_medium_skin_tone",
+        "\u{1f9d8}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_in_manual_wheelchair", "\u{1f469}\u{200d}\u{1f9bd}"),
+    (
+        "woman_in_motorized_wheelchair",
+        "\u{1f469}\u{200d}\u{1f9bc}",
+    ),
+    ("woman_in_steamy_room", "\u{1f9d6}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_in_steamy_room_dark_skin_tone",
+        "\u{1f9d6}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_steamy_room_light_skin_tone",
+        "\u{1f9d6}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_steamy_room_medium-dark_skin_tone",
+        "\u{1f9d6}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_steamy_room_medium-light_skin_tone",
+        "\u{1f9d6}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_in_steamy_room_medium_skin_tone",
+        "\u{1f9d6}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_judge", "\u{1f469}\u{200d}\u{2696}\u{fe0f}"),
+    (
+        "woman_judge_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "woman_judge_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "woman_judge_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "woman_judge_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    (
+        "woman_judge_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{2696}\u{fe0f}",
+    ),
+    ("woman_juggling", "\u{1f939}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_juggling_dark_skin_tone",
+        "\u{1f939}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_juggling_light_skin_tone",
+        "\u{1f939}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_juggling_medium-dark_skin_tone",
+        "\u{1f939}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_juggling_medium-light_skin_tone",
+        "\u{1f939}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_juggling_medium_skin_tone",
+        "\u{1f939}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights",
+        "\u{1f3cb}\u{fe0f}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights_dark_skin_tone",
+        "\u{1f3cb}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights_light_skin_tone",
+        "\u{1f3cb}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights_medium-dark_skin_tone",
+        "\u{1f3cb}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights_medium-light_skin_tone",
+        "\u{1f3cb}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_lifting_weights_medium_skin_tone",
+        "\u{1f3cb}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_light_skin_tone", "\u{1f469}\u{1f3fb}"),
+    ("woman_mage", "\u{1f9d9}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_mage_dark_skin_tone",
+        "\u{1f9d9}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mage_light_skin_tone",
+        "\u{1f9d9}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mage_medium-dark_skin_tone",
+        "\u{1f9d9}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mage_medium-light_skin_tone",
+        "\u{1f9d9}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mage_medium_skin_tone",
+        "\u{1f9d9}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_mechanic", "\u{1f469}\u{200d}\u{1f527}"),
+    (
+        "woman_mechanic_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f527}",
+    ),
+    (
+        "woman_mechanic_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f527}",
+    ),
+    (
+        "woman_mechanic_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f527}",
+    ),
+    (
+        "woman_mechanic_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f527}",
+    ),
+    (
+        "woman_mechanic_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f527}",
+    ),
+    ("woman_medium-dark_skin_tone", "\u{1f469}\u{1f3fe}"),
+    ("woman_medium-light_skin_tone", "\u{1f469}\u{1f3fc}"),
+    ("woman_medium_skin_tone", "\u{1f469}\u{1f3fd}"),
+    ("woman_mountain_biking", "\u{1f6b5}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_mountain_biking_dark_skin_tone",
+        "\u{1f6b5}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mountain_biking_light_skin_tone",
+        "\u{1f6b5}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mountain_biking_medium-dark_skin_tone",
+        "\u{1f6b5}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mountain_biking_medium-light_skin_tone",
+        "\u{1f6b5}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_mountain_biking_medium_skin_tone",
+        "\u{1f6b5}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_office_worker", "\u{1f469}\u{200d}\u{1f4bc}"),
+    (
+        "woman_office_worker_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "woman_office_worker_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "woman_office_worker_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "woman_office_worker_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f4bc}",
+    ),
+    (
+        "woman_office_worker_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f4bc}",
+    ),
+    ("woman_pilot", "\u{1f469}\u{200d}\u{2708}\u{fe0f}"),
+    (
+        "woman_pilot_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "woman_pilot_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "woman_pilot_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "woman_pilot_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "woman_pilot_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{2708}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball",
+        "\u{1f93e}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball_dark_skin_tone",
+        "\u{1f93e}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball_light_skin_tone",
+        "\u{1f93e}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball_medium-dark_skin_tone",
+        "\u{1f93e}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball_medium-light_skin_tone",
+        "\u{1f93e}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_handball_medium_skin_tone",
+        "\u{1f93e}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo",
+        "\u{1f93d}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo_dark_skin_tone",
+        "\u{1f93d}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo_light_skin_tone",
+        "\u{1f93d}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo_medium-dark_skin_tone",
+        "\u{1f93d}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo_medium-light_skin_tone",
+        "\u{1f93d}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_playing_water_polo_medium_skin_tone",
+        "\u{1f93d}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_police_officer", "\u{1f46e}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_police_officer_dark_skin_tone",
+        "\u{1f46e}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_police_officer_light_skin_tone",
+        "\u{1f46e}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_police_officer_medium-dark_skin_tone",
+        "\u{1f46e}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_police_officer_medium-light_skin_tone",
+        "\u{1f46e}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_police_officer_medium_skin_tone",
+        "\u{1f46e}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_pouting", "\u{1f64e}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_pouting_dark_skin_tone",
+        "\u{1f64e}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_pouting_light_skin_tone",
+        "\u{1f64e}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_pouting_medium-dark_skin_tone",
+        "\u{1f64e}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_pouting_medium-light_skin_tone",
+        "\u{1f64e}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_pouting_medium_skin_tone",
+        "\u{1f64e}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_raising_hand", "\u{1f64b}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_raising_hand_dark_skin_tone",
+        "\u{1f64b}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_raising_hand_light_skin_tone",
+        "\u{1f64b}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_raising_hand_medium-dark_skin_tone",
+        "\u{1f64b}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_raising_hand_medium-light_skin_tone",
+        "\u{1f64b}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_raising_hand_medium_skin_tone",
+        "\u{1f64b}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_rowing_boat", "\u{1f6a3}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_rowing_boat_dark_skin_tone",
+        "\u{1f6a3}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_rowing_boat_light_skin_tone",
+        "\u{1f6a3}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_rowing_boat_medium-dark_skin_tone",
+        "\u{1f6a3}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_rowing_boat_medium-light_skin_tone",
+        "\u{1f6a3}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_rowing_boat_medium_skin_tone",
+        "\u{1f6a3}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_running", "\u{1f3c3}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_running_dark_skin_tone",
+        "\u{1f3c3}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_running_light_skin_tone",
+        "\u{1f3c3}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_running_medium-dark_skin_tone",
+        "\u{1f3c3}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_running_medium-light_skin_tone",
+        "\u{1f3c3}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_running_medium_skin_tone",
+        "\u{1f3c3}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_scientist", "\u{1f469}\u{200d}\u{1f52c}"),
+    (
+        "woman_scientist_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f52c}",
+    ),
+    (
+        "woman_scientist_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f52c}",
+    ),
+    (
+        "woman_scientist_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f52c}",
+    ),
+    (
+        "woman_scientist_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f52c}",
+    ),
+    (
+        "woman_scientist_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f52c}",
+    ),
+    ("woman_shrugging", "\u{1f937}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_shrugging_dark_skin_tone",
+        "\u{1f937}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_shrugging_light_skin_tone",
+        "\u{1f937}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_shrugging_medium-dark_skin_tone",
+        "\u{1f937}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_shrugging_medium-light_skin_tone",
+        "\u{1f937}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_shrugging_medium_skin_tone",
+        "\u{1f937}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_singer", "\u{1f469}\u{200d}\u{1f3a4}"),
+    (
+        "woman_singer_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "woman_singer_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "woman_singer_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "woman_singer_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f3a4}",
+    ),
+    (
+        "woman_singer_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f3a4}",
+    ),
+    ("woman_student", "\u{1f469}\u{200d}\u{1f393}"),
+    (
+        "woman_student_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f393}",
+    ),
+    (
+        "woman_student_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f393}",
+    ),
+    (
+        "woman_student_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f393}",
+    ),
+    (
+        "woman_student_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f393}",
+    ),
+    (
+        "woman_student_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f393}",
+    ),
+    ("woman_surfing", "\u{1f3c4}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_surfing_dark_skin_tone",
+        "\u{1f3c4}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_surfing_light_skin_tone",
+        "\u{1f3c4}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_surfing_medium-dark_skin_tone",
+        "\u{1f3c4}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_surfing_medium-light_skin_tone",
+        "\u{1f3c4}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_surfing_medium_skin_tone",
+        "\u{1f3c4}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_swimming", "\u{1f3ca}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_swimming_dark_skin_tone",
+        "\u{1f3ca}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_swimming_light_skin_tone",
+        "\u{1f3ca}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_swimming_medium-dark_skin_tone",
+        "\u{1f3ca}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_swimming_medium-light_skin_tone",
+        "\u{1f3ca}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_swimming_medium_skin_tone",
+        "\u{1f3ca}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_teacher", "\u{1f469}\u{200d}\u{1f3eb}"),
+    (
+        "woman_teacher_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "woman_teacher_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "woman_teacher_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "woman_teacher_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f3eb}",
+    ),
+    (
+        "woman_teacher_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f3eb}",
+    ),
+    ("woman_technologist", "\u{1f469}\u{200d}\u{1f4bb}"),
+    (
+        "woman_technologist_dark_skin_tone",
+        "\u{1f469}\u{1f3ff}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "woman_technologist_light_skin_tone",
+        "\u{1f469}\u{1f3fb}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "woman_technologist_medium-dark_skin_tone",
+        "\u{1f469}\u{1f3fe}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "woman_technologist_medium-light_skin_tone",
+        "\u{1f469}\u{1f3fc}\u{200d}\u{1f4bb}",
+    ),
+    (
+        "woman_technologist_medium_skin_tone",
+        "\u{1f469}\u{1f3fd}\u{200d}\u{1f4bb}",
+    ),
+    ("woman_tipping_hand", "\u{1f481}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_tipping_hand_dark_skin_tone",
+        "\u{1f481}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_tipping_hand_light_skin_tone",
+        "\u{1f481}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_tipping_hand_medium-dark_skin_tone",
+        "\u{1f481}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_tipping_hand_medium-light_skin_tone",
+        "\u{1f481}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_tipping_hand_medium_skin_tone",
+        "\u{1f481}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_vampire", "\u{1f9db}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_vampire_dark_skin_tone",
+        "\u{1f9db}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_vampire_light_skin_tone",
+        "\u{1f9db}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_vampire_medium-dark_skin_tone",
+        "\u{1f9db}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_vampire_medium-light_skin_tone",
+        "\u{1f9db}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_vampire_medium_skin_tone",
+        "\u{1f9db}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_walking", "\u{1f6b6}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_walking_dark_skin_tone",
+        "\u{1f6b6}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_walking_light_skin_tone",
+        "\u{1f6b6}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_walking_medium-dark_skin_tone",
+        "\u{1f6b6}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_walking_medium-light_skin_tone",
+        "\u{1f6b6}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_walking_medium_skin_tone",
+        "\u{1f6b6}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_wearing_turban", "\u{1f473}\u{200d}\u{2640}\u{fe0f}"),
+    (
+        "woman_wearing_turban_dark_skin_tone",
+        "\u{1f473}\u{1f3ff}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_wearing_turban_light_skin_tone",
+        "\u{1f473}\u{1f3fb}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_wearing_turban_medium-dark_skin_tone",
+        "\u{1f473}\u{1f3fe}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_wearing_turban_medium-light_skin_tone",
+        "\u{1f473}\u{1f3fc}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    (
+        "woman_wearing_turban_medium_skin_tone",
+        "\u{1f473}\u{1f3fd}\u{200d}\u{2640}\u{fe0f}",
+    ),
+    ("woman_with_headscarf", "\u{1f9d5}"),
+    ("woman_with_headscarf_dark_skin_tone", "\u{1f9d5}\u{1f3ff}"),
+    ("woman_with_headscarf_light_skin_tone", "\u{1f9d5}\u{1f3fb}"),
+    (
+        "woman_with_headscarf_medium-dark_skin_tone",
+        "\u{1f9d5}\u{1f3fe}",
+    ),
+    (
+        "woman_with_headscarf_medium-light_skin_tone",
+        "\u{1f9d5}\u{1f3fc}",
+    ),
+    (
+        "woman_with_headscarf_medium_skin_tone",
+        "\u{1f9d5}\u{1f3fd}",
+    ),
+    ("woman_with_probing_cane", "\u{1f469}\u{200d}\u{1f9af}"),
+    ("woman_zombie", "\u{1f9df}\u{200d}\u{2640}\u{fe0f}"),
+    ("womans_clothes", "\u{1f45a}"),
+    ("womans_hat", "\u{1f452}"),
+    ("women_with_bunny_ears", "\u{1f46f}\u{200d}\u{2640}\u{fe0f}"),
+    ("women_wrestling", "\u{1f93c}\u{200d}\u{2640}\u{fe0f}"),
+    ("womens", "\u{1f6ba}"),
+    ("woozy_face", "\u{1f974}"),
+    ("world_map", "\u{1f5fa}"),
+    ("worried", "\u{1f61f}"),
+    ("worried_face", "\u{1f61f}"),
+    ("wrapped_gift", "\u{1f381}"),
+    ("wrench", "\u{1f527}"),
+    ("writing_hand", "\u{270d}"),
+    ("writing_hand_dark_skin_tone", "\u{270d}\u{1f3ff}"),
+    ("writing_hand_light_skin_tone", "\u{270d}\u{1f3fb}"),
+    ("writing_hand_medium-dark_skin_tone", "\u{270d}\u{1f3fe}"),
+    ("writing_hand_medium-light_skin_tone", "\u{270d}\u{1f3fc}"),
+    ("writing_hand_medium_skin_tone", "\u{270d}\u{1f3fd}"),
+    ("x", "\u{274c}"),
+    ("yarn", "\u{1f9f6}"),
+    ("yawning_face", "\u{1f971}"),
+    ("yellow_circle", "\u{1f7e1}"),
+    ("yellow_heart", "\u{1f49b}"),
+    ("yellow_square", "\u{1f7e8}"),
+    ("yemen", "\u{1f1fe}\u{1f1ea}"),
+    ("yen", "\u{1f4b4}"),
+    ("yen_banknote", "\u{1f4b4}"),
+    ("yin_yang", "\u{262f}"),
+    ("yo-yo", "\u{1fa80}"),
+    ("yum", "\u{1f60b}"),
+    ("zambia", "\u{1f1ff}\u{1f1f2}"),
+    ("zany_face", "\u{1f92a}"),
+    ("zap", "\u{26a1}"),
+    ("zebra", "\u{1f993}"),
+    ("zero", "\u{30}\u{fe0f}\u{20e3}"),
+    ("zimbabwe", "\u{1f1ff}\u{1f1fc}"),
+    ("zipper-mouth_face", "\u{1f910}"),
+    ("zipper__mouth_face", "\u{1f910}"),
+    ("zombie", "\u{1f9df}"),
+    ("zzz", "\u{1f4a4}"),
+];
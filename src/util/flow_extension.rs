@@ -0,0 +1,104 @@
+//! Info on custom flow constructs, passed when parsing and compiling.
+
+use alloc::{boxed::Box, fmt, string::String};
+
+/// When to try a custom flow construct.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Priority {
+    /// Try before all built-in flow constructs.
+    ///
+    /// The construct sees lines before, for example, code (indented) or
+    /// headings do, so it can take lines that would otherwise be those.
+    First,
+    /// Try after all built-in flow constructs, right before falling back to
+    /// a paragraph.
+    ///
+    /// The construct only sees lines that are not something else.
+    #[default]
+    Last,
+}
+
+/// Signature of a function that checks whether a line starts a custom flow
+/// construct.
+///
+/// Gets the line, without container prefixes (such as `> ` of block quotes)
+/// but with the indent, and without the line ending.
+pub type Start = dyn Fn(&str) -> bool;
+
+/// Signature of a function that checks whether a line closes a custom flow
+/// construct.
+///
+/// Gets the opening line and the current line, both as with [`Start`][].
+/// The closing line is part of the construct.
+pub type Close = dyn Fn(&str, &str) -> bool;
+
+/// Signature of a function that turns a custom flow construct into HTML.
+///
+/// Can be passed as `flow_extension_handler` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Gets the name of the construct and its value: its lines, without container
+/// prefixes, joined by their line endings.
+///
+/// Return `None` to not handle a construct, in which case its value is
+/// rendered as code.
+pub type Handler = dyn Fn(&str, &str) -> Option<String>;
+
+/// Custom flow construct.
+///
+/// Custom flow constructs are line based: `start` decides whether a line
+/// starts the construct, and if there is a `close`, following lines are part
+/// of it until `close` decides a line closes it, the containers it is in
+/// end, or the document ends.
+/// Like code (fenced), lazy lines are not part of it, and the lines after its
+/// first line are concrete: new containers (such as block quotes) cannot
+/// start in them.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::{FlowExtension, FlowExtensionPriority};
+///
+/// let extension = FlowExtension {
+///     name: "note".into(),
+///     priority: FlowExtensionPriority::First,
+///     interrupt: false,
+///     start: Box::new(|line| line.trim_start() == "%%%"),
+///     close: Some(Box::new(|_opening, line| line.trim_start() == "%%%")),
+/// };
+/// # assert_eq!(extension.name, "note");
+/// ```
+pub struct FlowExtension {
+    /// Name of the construct.
+    ///
+    /// Used in events (as `FlowExtension(name)`) and passed to
+    /// `flow_extension_handler` when compiling to HTML.
+    pub name: String,
+    /// When to try this construct, compared to built-in constructs.
+    ///
+    /// Constructs with the same priority are tried in the order they are
+    /// given.
+    pub priority: Priority,
+    /// Whether the construct can interrupt a paragraph.
+    ///
+    /// When `false`, a line right after a paragraph is not tried.
+    pub interrupt: bool,
+    /// Check whether a line starts the construct.
+    pub start: Box<Start>,
+    /// Check whether a line closes the construct.
+    ///
+    /// When `None`, the construct is one line.
+    pub close: Option<Box<Close>>,
+}
+
+impl fmt::Debug for FlowExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlowExtension")
+            .field("name", &self.name)
+            .field("priority", &self.priority)
+            .field("interrupt", &self.interrupt)
+            .field("start", &"[Function]")
+            .field("close", &self.close.as_ref().map(|_d| "[Function]"))
+            .finish()
+    }
+}
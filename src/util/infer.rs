@@ -4,7 +4,372 @@
 
 use crate::event::{Event, Kind, Name};
 use crate::mdast::AlignKind;
-use alloc::{vec, vec::Vec};
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    constant::{HTML_BLOCK_NAMES, HTML_RAW_NAMES},
+    skip,
+    slice::{Position, Slice},
+};
+use alloc::{string::String, vec, vec::Vec};
+
+/// Info on a list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListInfo {
+    /// Whether the list is ordered (`1.`) or unordered (`*`).
+    pub ordered: bool,
+    /// Start number of an ordered list (the value of its first item).
+    ///
+    /// `None` for unordered lists.
+    pub start: Option<u32>,
+    /// Marker of the items: `*`, `+`, or `-` for unordered lists, `.` or
+    /// `)` for ordered lists.
+    pub marker: char,
+    /// Whether the list is “loose” (its items are wrapped in paragraphs in
+    /// HTML), as opposed to “tight”.
+    ///
+    /// A list is loose if any of its items are separated by blank lines, or
+    /// if any of its items directly contain two block elements with a blank
+    /// line between them.
+    pub loose: bool,
+}
+
+/// Get info on a list.
+///
+/// `index` must point to the enter of a list (`ListOrdered` or
+/// `ListUnordered`).
+/// A list interrupted by something else (such as a paragraph) and then
+/// resumed, is actually two lists: each has its own info, such as a start
+/// number.
+pub fn list_info(bytes: &[u8], events: &[Event], mut index: usize) -> ListInfo {
+    let ordered = events[index].name == Name::ListOrdered;
+    let loose = list_loose(events, index, true);
+    let mut balance = 0;
+    let mut start = None;
+    let mut marker = None;
+
+    debug_assert!(
+        ordered || events[index].name == Name::ListUnordered,
+        "expected list"
+    );
+
+    // The value and marker of the first item.
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+
+            if balance == 4 && event.name == Name::ListItemMarker {
+                marker = Some(char::from(bytes[event.point.index]));
+                break;
+            }
+        } else {
+            if balance == 4 && event.name == Name::ListItemValue {
+                // A value consists of digits, so it cannot have virtual
+                // spaces.
+                let (from, to) = Position::from_exit_event(events, index).to_indices();
+                start = Some(
+                    Slice::from_indices(bytes, from, to)
+                        .as_str()
+                        .parse()
+                        .expect("expected list value up to u32"),
+                );
+            }
+
+            balance -= 1;
+        }
+
+        index += 1;
+    }
+
+    ListInfo {
+        ordered,
+        start,
+        marker: marker.expect("expected list item marker"),
+        loose,
+    }
+}
+
+/// Info on a thematic break.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThematicBreakInfo {
+    /// Marker: `*`, `-`, or `_`.
+    pub marker: char,
+    /// Number of markers, not including whitespace between them.
+    pub size: usize,
+}
+
+/// Get info on a thematic break.
+///
+/// `index` must point to the enter of a thematic break.
+pub fn thematic_break_info(bytes: &[u8], events: &[Event], mut index: usize) -> ThematicBreakInfo {
+    debug_assert_eq!(
+        events[index].name,
+        Name::ThematicBreak,
+        "expected thematic break"
+    );
+    let mut marker = None;
+    let mut size = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == Name::ThematicBreak {
+                break;
+            }
+
+            if event.name == Name::ThematicBreakSequence {
+                // A sequence consists of markers, so it cannot have virtual
+                // spaces.
+                let (from, to) = Position::from_exit_event(events, index).to_indices();
+                marker = Some(char::from(bytes[from]));
+                size += to - from;
+            }
+        }
+
+        index += 1;
+    }
+
+    ThematicBreakInfo {
+        marker: marker.expect("expected thematic break sequence"),
+        size,
+    }
+}
+
+/// Info on the fences of code (fenced) or math (flow).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FenceInfo {
+    /// Marker: `` ` `` or `~` for code, `$` for math.
+    pub marker: char,
+    /// Number of markers in the opening fence.
+    pub size: usize,
+    /// Number of markers in the closing fence, which can be more than in the
+    /// opening fence.
+    ///
+    /// `None` if there is no closing fence (the code runs to the end of its
+    /// container or the document).
+    pub closing_size: Option<usize>,
+}
+
+/// Get info on the fences of code (fenced) or math (flow).
+///
+/// `index` must point to the enter of code (fenced) or math (flow).
+pub fn fence_info(bytes: &[u8], events: &[Event], mut index: usize) -> FenceInfo {
+    let name = events[index].name.clone();
+    debug_assert!(
+        matches!(name, Name::CodeFenced | Name::MathFlow),
+        "expected code (fenced) or math (flow)"
+    );
+    let mut marker = None;
+    let mut size = 0;
+    let mut closing_size = None;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Exit {
+            if event.name == name {
+                break;
+            }
+
+            if matches!(
+                event.name,
+                Name::CodeFencedFenceSequence | Name::MathFlowFenceSequence
+            ) {
+                // A sequence consists of markers, so it cannot have virtual
+                // spaces.
+                let (from, to) = Position::from_exit_event(events, index).to_indices();
+
+                if marker.is_none() {
+                    marker = Some(char::from(bytes[from]));
+                    size = to - from;
+                } else {
+                    closing_size = Some(to - from);
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    FenceInfo {
+        marker: marker.expect("expected fence sequence"),
+        size,
+        closing_size,
+    }
+}
+
+/// Kind of HTML (flow).
+///
+/// Each kind corresponds to one of the seven start conditions of HTML blocks
+/// in `CommonMark`, and determines how the HTML ends.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HtmlFlowKind {
+    /// Raw (`<pre>`, `<script>`, `<style>`, or `<textarea>`), ending at a
+    /// line containing a closing tag of one of those names (condition 1).
+    Raw,
+    /// Comment (`<!--`), ending at a line containing `-->` (condition 2).
+    Comment,
+    /// Instruction (`<?`), ending at a line containing `?>` (condition 3).
+    Instruction,
+    /// Declaration (`<!doctype`), ending at a line containing `>`
+    /// (condition 4).
+    Declaration,
+    /// CDATA (`<![CDATA[`), ending at a line containing `]]>` (condition 5).
+    Cdata,
+    /// Basic (such as `<div>` or `</table>`), ending at a blank line
+    /// (condition 6).
+    Basic,
+    /// Complete (any other complete opening or closing tag, alone on its
+    /// line), ending at a blank line (condition 7).
+    Complete,
+}
+
+/// Get the kind of HTML (flow).
+///
+/// `index` must point to the enter of HTML (flow).
+pub fn html_flow_kind(bytes: &[u8], events: &[Event], mut index: usize) -> HtmlFlowKind {
+    debug_assert_eq!(events[index].name, Name::HtmlFlow, "expected html (flow)");
+
+    // Move to the first data.
+    while !(events[index].kind == Kind::Enter && events[index].name == Name::HtmlFlowData) {
+        index += 1;
+    }
+
+    let mut at = events[index].point.index;
+
+    // Initial whitespace is also data.
+    while matches!(bytes[at], b'\t' | b' ') {
+        at += 1;
+    }
+
+    // After `<`.
+    at += 1;
+
+    match bytes.get(at) {
+        Some(b'!') => match bytes.get(at + 1) {
+            Some(b'-') => HtmlFlowKind::Comment,
+            Some(b'[') => HtmlFlowKind::Cdata,
+            _ => HtmlFlowKind::Declaration,
+        },
+        Some(b'?') => HtmlFlowKind::Instruction,
+        _ => {
+            let closing_tag = bytes.get(at) == Some(&b'/');
+
+            if closing_tag {
+                at += 1;
+            }
+
+            let start = at;
+
+            while at < bytes.len()
+                && matches!(bytes[at], b'-' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
+            {
+                at += 1;
+            }
+
+            // Guaranteed to be valid ASCII bytes.
+            let name = Slice::from_indices(bytes, start, at)
+                .as_str()
+                .to_ascii_lowercase();
+
+            if !closing_tag
+                && bytes.get(at) != Some(&b'/')
+                && HTML_RAW_NAMES.contains(&name.as_str())
+            {
+                HtmlFlowKind::Raw
+            } else if HTML_BLOCK_NAMES.contains(&name.as_str()) {
+                HtmlFlowKind::Basic
+            } else {
+                HtmlFlowKind::Complete
+            }
+        }
+    }
+}
+
+/// Info on a resource: the `(…)` after the label of a link or image.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourceInfo {
+    /// Destination, with character escapes and character references
+    /// decoded.
+    ///
+    /// Empty if there is no destination (`()` or `(<>)`).
+    pub destination: String,
+    /// Whether the destination is wrapped in angle brackets (`<a b>`), which
+    /// allows spaces in it.
+    pub literal: bool,
+    /// Title, with character escapes and character references decoded.
+    ///
+    /// `None` if there is no title.
+    pub title: Option<String>,
+}
+
+/// Get info on a resource.
+///
+/// `index` must point to the enter of a resource.
+pub fn resource_info(bytes: &[u8], events: &[Event], mut index: usize) -> ResourceInfo {
+    debug_assert_eq!(events[index].name, Name::Resource, "expected resource");
+    let mut destination = String::new();
+    let mut literal = false;
+    let mut title = None;
+    let mut buffer = None;
+    let mut marker = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            match event.name {
+                Name::ResourceDestinationLiteral => literal = true,
+                Name::ResourceDestinationString | Name::ResourceTitleString => {
+                    buffer = Some(String::new());
+                }
+                _ => {}
+            }
+        } else {
+            match event.name {
+                Name::Resource => break,
+                Name::ResourceDestinationString => {
+                    destination = buffer.take().expect("expected buffer");
+                }
+                Name::ResourceTitleString => title = buffer.take(),
+                Name::CharacterReferenceMarker => marker = b'&',
+                Name::CharacterReferenceMarkerNumeric => marker = b'#',
+                Name::CharacterReferenceMarkerHexadecimal => marker = b'x',
+                Name::CharacterEscapeValue
+                | Name::CharacterReferenceValue
+                | Name::Data
+                | Name::LineEnding => {
+                    if let Some(buffer) = buffer.as_mut() {
+                        // Strings have no virtual spaces.
+                        let (from, to) = Position::from_exit_event(events, index).to_indices();
+                        let slice = Slice::from_indices(bytes, from, to);
+                        let value = slice.as_str();
+
+                        if event.name == Name::CharacterReferenceValue {
+                            buffer.push_str(
+                                &decode_character_reference(value, marker, true)
+                                    .expect("expected to parse only valid named references"),
+                            );
+                        } else {
+                            buffer.push_str(value);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        index += 1;
+    }
+
+    ResourceInfo {
+        destination,
+        literal,
+        title,
+    }
+}
 
 /// Figure out if a list is spread or not.
 ///
@@ -51,6 +416,16 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                 //   | * a
                 // ```
                 let mut at_empty_block_quote = false;
+                // Rest of a closing directive (container) fence:
+                //
+                // ```markdown
+                //   | * :::a
+                //   |   b
+                // > |   :::␊
+                //          ^
+                //   | * c
+                // ```
+                let mut at_directive_container_close = false;
 
                 // List.
                 let mut before = index - 2;
@@ -58,6 +433,10 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                 if events[before].name == Name::ListItem {
                     before -= 1;
 
+                    if events[before].name == Name::DirectiveContainer {
+                        at_directive_container_close = true;
+                    }
+
                     if events[before].name == Name::SpaceOrTab {
                         before -= 2;
                     }
@@ -71,7 +450,11 @@ pub fn list_loose(events: &[Event], mut index: usize, include_items: bool) -> bo
                     }
                 }
 
-                if !at_empty_list_item && !at_empty_block_quote {
+                if !at_empty_list_item
+                    && !at_empty_block_quote
+                    && !at_directive_container_close
+                    && followed_by_content(events, index)
+                {
                     return true;
                 }
             }
@@ -121,11 +504,21 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
                     before -= 2;
                 }
 
-                if events[before].name == Name::ListItemPrefix {
+                // Rest of a closing directive (container) fence:
+                //
+                // ```markdown
+                //   | * :::a
+                //   |   b
+                // > |   :::␊
+                //          ^
+                // ```
+                if events[before].name == Name::ListItemPrefix
+                    || events[before].name == Name::DirectiveContainer
+                {
                     at_prefix = true;
                 }
 
-                if !at_prefix {
+                if !at_prefix && followed_by_content(events, index) {
                     return true;
                 }
             }
@@ -142,6 +535,148 @@ pub fn list_item_loose(events: &[Event], mut index: usize) -> bool {
     false
 }
 
+/// Figure out if a definition list is loose or not.
+///
+/// A definition list is loose if there is a blank line before one of its
+/// descriptions, or if one of its descriptions is loose.
+pub fn definition_list_loose(events: &[Event], mut index: usize) -> bool {
+    debug_assert_eq!(
+        events[index].name,
+        Name::DefinitionList,
+        "expected definition list"
+    );
+    let mut balance = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+
+            if balance == 2
+                && event.name == Name::DefinitionListDescription
+                && definition_list_description_loose(events, index)
+            {
+                return true;
+            }
+        } else {
+            balance -= 1;
+
+            // Blank line before a description, but not before a term:
+            //
+            // ```markdown
+            //   | a
+            // > | ␊
+            //     ^
+            //   | : b
+            // ```
+            if balance == 1 && event.name == Name::BlankLineEnding {
+                let next = skip::opt(
+                    events,
+                    index + 1,
+                    &[
+                        Name::BlankLineEnding,
+                        Name::BlockQuotePrefix,
+                        Name::LineEnding,
+                        Name::SpaceOrTab,
+                    ],
+                );
+
+                if next < events.len() && events[next].name == Name::DefinitionListDescription {
+                    return true;
+                }
+            }
+
+            // Done.
+            if balance == 0 && event.name == Name::DefinitionList {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
+/// Figure out if a definition list description is spread or not.
+pub fn definition_list_description_loose(events: &[Event], mut index: usize) -> bool {
+    debug_assert_eq!(
+        events[index].name,
+        Name::DefinitionListDescription,
+        "expected definition list description"
+    );
+    let mut balance = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if event.kind == Kind::Enter {
+            balance += 1;
+        } else {
+            balance -= 1;
+
+            // Rest of a closing directive (container) fence:
+            //
+            // ```markdown
+            //   | a
+            //   | : :::b
+            //   |     c
+            // > |   :::␊
+            //          ^
+            // ```
+            if balance == 1
+                && event.name == Name::BlankLineEnding
+                && events[index - 2].name != Name::DirectiveContainer
+                && followed_by_content(events, index)
+            {
+                return true;
+            }
+
+            // Done.
+            if balance == 0 && event.name == Name::DefinitionListDescription {
+                break;
+            }
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
+/// Check if there is something other than whitespace after the event at
+/// `index`, before its parent exits.
+///
+/// Blank lines at the end of a list (item) are not between two things:
+///
+/// ```markdown
+///   | * a
+/// > | ␊
+///     ^
+/// > | ␊
+///     ^
+///   | b
+/// ```
+fn followed_by_content(events: &[Event], mut index: usize) -> bool {
+    index += 1;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if !matches!(
+            event.name,
+            Name::BlankLineEnding | Name::LineEnding | Name::SpaceOrTab
+        ) {
+            return event.kind == Kind::Enter;
+        }
+
+        index += 1;
+    }
+
+    false
+}
+
 /// Figure out the alignment of a GFM table.
 pub fn gfm_table_align(events: &[Event], mut index: usize) -> Vec<AlignKind> {
     debug_assert!(
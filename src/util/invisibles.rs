@@ -0,0 +1,44 @@
+//! Deal with invisible characters in text.
+
+use crate::configuration::Invisibles;
+use alloc::string::String;
+
+/// Strip invisible characters from `value`, or replace them with character
+/// references.
+///
+/// Invisible characters are zero width spaces (U+200B), word joiners
+/// (U+2060), and zero width no-break spaces, also known as byte order marks
+/// (U+FEFF).
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::invisibles::invisibles;
+/// use markdown::Invisibles;
+///
+/// assert_eq!(invisibles("a\u{200B}b", &Invisibles::Strip), "ab");
+/// assert_eq!(invisibles("a\u{200B}b", &Invisibles::Replace), "a&#x200B;b");
+/// ```
+pub fn invisibles(value: &str, kind: &Invisibles) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.chars() {
+        let reference = match char {
+            '\u{200B}' => "&#x200B;",
+            '\u{2060}' => "&#x2060;",
+            '\u{FEFF}' => "&#xFEFF;",
+            _ => {
+                result.push(char);
+                continue;
+            }
+        };
+
+        match kind {
+            Invisibles::Keep => result.push(char),
+            Invisibles::Strip => {}
+            Invisibles::Replace => result.push_str(reference),
+        }
+    }
+
+    result
+}
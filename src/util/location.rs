@@ -49,6 +49,9 @@ impl Location {
 
     /// Get the line and column-based `point` for `offset` in the bound indices.
     ///
+    /// `column` counts bytes, and a tab counts as 1 (unlike the columns in
+    /// [`EventPoint`][crate::EventPoint]s, which expand tabs).
+    ///
     /// Returns `None` when given out of bounds input.
     ///
     /// Port of <https://github.com/vfile/vfile-location/blob/main/index.js>
@@ -78,6 +81,55 @@ impl Location {
         None
     }
 
+    /// Get the `offset` for a line and column-based point in the bound
+    /// indices: the opposite of `to_point`.
+    ///
+    /// `line` and `column` are 1-indexed, and `column` counts bytes.
+    ///
+    /// Returns `None` when given out of bounds input, such as a column past
+    /// the end of its line.
+    #[must_use]
+    pub fn to_offset(&self, line: usize, column: usize) -> Option<usize> {
+        if line > 0 && line <= self.indices.len() && column > 0 {
+            let start = if line > 1 { self.indices[line - 2] } else { 0 };
+            let offset = start + column - 1;
+
+            if offset < self.indices[line - 1] {
+                return Some(offset);
+            }
+        }
+
+        None
+    }
+
+    /// Like `to_point`, but with a `column` that counts UTF-16 code units
+    /// instead of bytes, as the language server protocol does.
+    ///
+    /// `value` must be the document that the indices were made for.
+    /// An `offset` in the middle of a character gets the column of that
+    /// character.
+    /// The language server protocol is 0-indexed, so subtract `1` from `line`
+    /// and `column` for it.
+    ///
+    /// Returns `None` when given out of bounds input.
+    #[must_use]
+    pub fn to_utf16_point(&self, value: &str, offset: usize) -> Option<Point> {
+        self.to_point(offset).map(|point| {
+            let start = offset + 1 - point.column;
+            let mut column = 1;
+
+            for (index, char) in value[start..].char_indices() {
+                if start + index + char.len_utf8() > offset {
+                    break;
+                }
+
+                column += char.len_utf16();
+            }
+
+            Point::new(point.line, column, offset)
+        })
+    }
+
     /// Like `to_point`, but takes a relative offset from a certain string
     /// instead of an absolute offset into the whole document.
     ///
@@ -192,6 +244,83 @@ mod tests {
             "should support some points (4)"
         );
     }
+    #[test]
+    fn test_location_to_offset() {
+        let location = Location::new("ab\r\nc".as_bytes());
+        assert_eq!(location.to_offset(1, 1), Some(0), "should support `a`");
+        assert_eq!(location.to_offset(1, 4), Some(3), "should support `\\n`");
+        assert_eq!(location.to_offset(2, 1), Some(4), "should support `c`");
+        assert_eq!(location.to_offset(2, 2), Some(5), "should support EOF");
+        assert_eq!(
+            location.to_offset(1, 5),
+            None,
+            "should not support columns past lines"
+        );
+        assert_eq!(
+            location.to_offset(2, 3),
+            None,
+            "should not support past EOF"
+        );
+        assert_eq!(
+            location.to_offset(3, 1),
+            None,
+            "should not support past lines"
+        );
+        assert_eq!(location.to_offset(0, 1), None, "should not support line 0");
+        assert_eq!(
+            location.to_offset(1, 0),
+            None,
+            "should not support column 0"
+        );
+
+        let mut offset = 0;
+        while offset < 6 {
+            let point = location.to_point(offset).unwrap();
+            assert_eq!(
+                location.to_offset(point.line, point.column),
+                Some(offset),
+                "should be the opposite of `to_point`"
+            );
+            offset += 1;
+        }
+    }
+
+    #[test]
+    fn test_location_to_utf16_point() {
+        let value = "a\n\u{e9}\u{1f600}b";
+        let location = Location::new(value.as_bytes());
+        assert_eq!(
+            location.to_utf16_point(value, 0),
+            Some(Point::new(1, 1, 0)),
+            "should support ASCII"
+        );
+        assert_eq!(
+            location.to_utf16_point(value, 4),
+            Some(Point::new(2, 2, 4)),
+            "should count 2 bytes as 1 code unit"
+        );
+        assert_eq!(
+            location.to_utf16_point(value, 8),
+            Some(Point::new(2, 4, 8)),
+            "should count 4 bytes as 2 code units"
+        );
+        assert_eq!(
+            location.to_utf16_point(value, 9),
+            Some(Point::new(2, 5, 9)),
+            "should support EOF"
+        );
+        assert_eq!(
+            location.to_utf16_point(value, 6),
+            Some(Point::new(2, 2, 6)),
+            "should use the column of a character in its middle"
+        );
+        assert_eq!(
+            location.to_utf16_point(value, 10),
+            None,
+            "should not support out of bounds"
+        );
+    }
+
     #[test]
     fn test_empty() {
         let location = Location::new("".as_bytes());
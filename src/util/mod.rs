@@ -2,18 +2,35 @@
 
 pub mod char;
 pub mod character_reference;
+pub mod code_highlight;
+pub mod code_language;
 pub mod constant;
+pub mod directive;
 pub mod edit_map;
+#[cfg(feature = "emoji")]
+pub mod emoji;
 pub mod encode;
+pub mod flow_extension;
 pub mod gfm_tagfilter;
 pub mod identifier;
 pub mod infer;
+pub mod invisibles;
 pub mod line_ending;
 pub mod location;
 pub mod mdx;
 pub mod mdx_collect;
 pub mod normalize_identifier;
+#[cfg(feature = "profile")]
+pub mod profile;
+pub mod resolve_uri;
 pub mod sanitize_uri;
 pub mod skip;
 pub mod slice;
+pub mod slug;
+pub mod smart_punctuation;
+pub mod stats;
+pub mod text_extension;
+pub mod toc;
 pub mod unicode;
+pub mod url_transform;
+pub mod visible_text;
@@ -5,10 +5,13 @@ use alloc::string::String;
 /// Normalize an identifier, as found in [references][label_end] and
 /// [definitions][definition], so it can be compared when matching.
 ///
-/// This collapsed whitespace found in markdown (`\t`, `\r`, `\n`, and ` `)
+/// This collapses whitespace found in markdown (`\t`, `\r`, `\n`, and ` `)
 /// into one space, trims it (as in, dropping the first and last space), and
 /// then performs unicode case folding twice: first by lowercasing uppercase
 /// characters, and then uppercasing lowercase characters.
+/// Other whitespace (such as U+00A0 NO-BREAK SPACE) is kept as it is.
+/// Character escapes and character references are not decoded: pass the
+/// label as it is written in the source.
 ///
 /// Some characters are considered “uppercase”, such as U+03F4 (`ϴ`), but if
 /// their lowercase counterpart (U+03B8 (`θ`)) is uppercased will result in a
@@ -16,23 +19,29 @@ use alloc::string::String;
 /// Hence, to get that form, we perform both lower- and uppercase.
 ///
 /// Performing these steps in that order works, but the inverse does not work.
-/// To illustrate, say the source markdown containes two identifiers
+/// To illustrate, say the source markdown contains two identifiers
 /// `SS` (U+0053 U+0053) and `ẞ` (U+1E9E), which would be lowercased to
 /// `ss` (U+0073 U+0073) and `ß` (U+00DF), and those in turn would both
 /// uppercase to `SS` (U+0053 U+0053).
 /// If we’d inverse the steps, for `ẞ`, we’d first uppercase without a
 /// change, and then lowercase to `ß`, which would not match `ss`.
 ///
+/// A reference matches a definition when their normalized identifiers are
+/// equal.
+/// The `identifier` fields in [mdast][crate::mdast] (and returned by
+/// [`definitions()`][crate::definitions()]) are this value, lowercased.
+///
 /// ## Examples
 ///
-/// ```rust ignore
-/// markdown::util::normalize_identifier::normalize_identifier;
+/// ```
+/// use markdown::normalize_identifier;
 ///
-/// assert_eq!(normalize_identifier(" a "), "a");
-/// assert_eq!(normalize_identifier("a\t\r\nb"), "a b");
-/// assert_eq!(normalize_identifier("ПРИВЕТ"), "привет");
-/// assert_eq!(normalize_identifier("Привет"), "привет");
-/// assert_eq!(normalize_identifier("привет"), "привет");
+/// assert_eq!(normalize_identifier(" a "), "A");
+/// assert_eq!(normalize_identifier("a\t\r\nb"), "A B");
+/// assert_eq!(normalize_identifier("ПРИВЕТ"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("Привет"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("привет"), "ПРИВЕТ");
+/// assert_eq!(normalize_identifier("ẞ"), normalize_identifier("ss"));
 /// ```
 ///
 /// ## References
@@ -59,7 +68,7 @@ pub fn normalize_identifier(value: &str) -> String {
         }
         // First non-whitespace we see after whitespace.
         else if in_whitespace {
-            if start != 0 {
+            if !result.is_empty() {
                 result.push(' ');
             }
 
@@ -0,0 +1,131 @@
+//! Profile of how much work the constructs and resolvers do on a document.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+use std::time::Duration;
+
+/// Profile of parsing a document.
+///
+/// Made with [`profile()`][crate::profile].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Profile {
+    /// Entries, sorted by `duration`, the slowest first.
+    pub entries: Vec<ProfileEntry>,
+}
+
+/// Work done by a construct or resolver.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileEntry {
+    /// Name of the state a construct was attempted with (such as
+    /// `HeadingAtxStart`, `LabelEndStart`), or of a resolver.
+    ///
+    /// The names of states start with the name of their construct.
+    pub name: String,
+    /// Whether this is a resolver.
+    pub resolver: bool,
+    /// Number of times this construct was attempted or checked, or this
+    /// resolver ran.
+    pub count: usize,
+    /// Number of times this construct was not found.
+    pub nok: usize,
+    /// Number of bytes that were tokenized by this construct but then
+    /// reverted, because it was not found, or because it was only checked.
+    ///
+    /// This is work that is done again by something else, so a lot of it
+    /// points to backtracking.
+    pub reverted: usize,
+    /// Total time spent, including in constructs attempted inside this one.
+    pub duration: Duration,
+}
+
+impl Profile {
+    /// Record that a construct was attempted.
+    pub(crate) fn record_attempt(
+        &mut self,
+        name: String,
+        nok: bool,
+        reverted: usize,
+        duration: Duration,
+    ) {
+        let entry = self.entry(name, false);
+        entry.count += 1;
+        entry.reverted += reverted;
+        entry.duration += duration;
+
+        if nok {
+            entry.nok += 1;
+        }
+    }
+
+    /// Record that a resolver ran.
+    pub(crate) fn record_resolve(&mut self, name: String, duration: Duration) {
+        let entry = self.entry(name, true);
+        entry.count += 1;
+        entry.duration += duration;
+    }
+
+    /// Sort entries, the slowest first.
+    pub(crate) fn sort(&mut self) {
+        self.entries.sort_by(|a, b| {
+            b.duration
+                .cmp(&a.duration)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+
+    /// Get the entry for `name`, adding it if needed.
+    fn entry(&mut self, name: String, resolver: bool) -> &mut ProfileEntry {
+        let index = if let Some(index) = self
+            .entries
+            .iter()
+            .position(|d| d.resolver == resolver && d.name == name)
+        {
+            index
+        } else {
+            self.entries.push(ProfileEntry {
+                name,
+                resolver,
+                count: 0,
+                nok: 0,
+                reverted: 0,
+                duration: Duration::default(),
+            });
+            self.entries.len() - 1
+        };
+
+        &mut self.entries[index]
+    }
+}
+
+/// Show a profile as a small report: one line for each entry.
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        lines.push(format!(
+            "{:<40} {:>10} {:>10} {:>10} {:>12}",
+            "name", "count", "nok", "reverted", "time"
+        ));
+
+        let mut index = 0;
+
+        while index < self.entries.len() {
+            let entry = &self.entries[index];
+            let name = if entry.resolver {
+                format!("{} (resolver)", entry.name)
+            } else {
+                entry.name.clone()
+            };
+            lines.push(format!(
+                "{:<40} {:>10} {:>10} {:>10} {:>12}",
+                name,
+                entry.count,
+                entry.nok,
+                entry.reverted,
+                format!("{:?}", entry.duration)
+            ));
+            index += 1;
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
@@ -0,0 +1,244 @@
+//! Resolve relative urls against a base.
+
+use alloc::{format, string::String, vec::Vec};
+
+/// Resolve a URL against a base URL.
+///
+/// URLs with a protocol (such as `https:` or `mailto:`) are absolute and
+/// returned as they are.
+/// Other URLs are resolved like a browser would: protocol-relative URLs
+/// (`//example.com`) get the protocol of `base`, root-relative URLs (`/a`)
+/// its protocol and host, query-only (`?a`) and fragment-only (`#a`) URLs
+/// its path, empty URLs are `base` itself (without fragment), and all other
+/// URLs are resolved against the “directory” of `base`, where `.` and `..`
+/// segments are removed.
+///
+/// `base` itself can be relative too (such as `/docs/`), in which case the
+/// result is relative to the same thing.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::resolve_uri::resolve;
+///
+/// assert_eq!(resolve("https://a.b/c/d", "e"), "https://a.b/c/e");
+/// assert_eq!(resolve("https://a.b/c/d", "../e"), "https://a.b/e");
+/// assert_eq!(resolve("https://a.b/c/d", "/e"), "https://a.b/e");
+/// assert_eq!(resolve("https://a.b/c/d", "//e.f/g"), "https://e.f/g");
+/// assert_eq!(resolve("https://a.b/c/d", "#e"), "https://a.b/c/d#e");
+/// assert_eq!(resolve("https://a.b/c/d", "mailto:e@f.g"), "mailto:e@f.g");
+/// ```
+///
+/// ## References
+///
+/// *   [*§ 5.2 Relative Resolution* in RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-5.2)
+pub fn resolve(base: &str, value: &str) -> String {
+    if protocol_end(value).is_some() {
+        return value.into();
+    }
+
+    let base = Parts::from(base);
+
+    if value.starts_with("//") {
+        format!("{}{}", base.protocol, value)
+    } else if value.starts_with('/') {
+        format!(
+            "{}{}{}",
+            base.protocol,
+            base.host,
+            remove_dot_segments(value)
+        )
+    } else if value.starts_with('?') {
+        format!("{}{}{}{}", base.protocol, base.host, base.path, value)
+    } else if value.is_empty() || value.starts_with('#') {
+        format!(
+            "{}{}{}{}{}",
+            base.protocol, base.host, base.path, base.query, value
+        )
+    } else {
+        let path = if !base.host.is_empty() && base.path.is_empty() {
+            "/"
+        } else if let Some(index) = base.path.rfind('/') {
+            &base.path[0..=index]
+        } else {
+            ""
+        };
+
+        format!(
+            "{}{}{}",
+            base.protocol,
+            base.host,
+            remove_dot_segments(&format!("{}{}", path, value))
+        )
+    }
+}
+
+/// Lowercase the protocol and host of a URL.
+///
+/// The user info (`user@`), path, query, and fragment are case-sensitive and
+/// left as they are.
+/// URLs without a protocol or host, such as `/a` or `mailto:a@b.c`, only
+/// have their protocol, if any, lowercased.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::resolve_uri::normalize_case;
+///
+/// assert_eq!(normalize_case("HTTP://Example.COM/Path"), "http://example.com/Path");
+/// assert_eq!(normalize_case("HTTPS://User@Example.COM:80/?Q#F"), "https://User@example.com:80/?Q#F");
+/// assert_eq!(normalize_case("MAILTO:A@B.C"), "mailto:A@B.C");
+/// assert_eq!(normalize_case("/A/B"), "/A/B");
+/// ```
+///
+/// ## References
+///
+/// *   [*§ 6.2.2.1 Case Normalization* in RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-6.2.2.1)
+pub fn normalize_case(value: &str) -> String {
+    let protocol_end = protocol_end(value).map_or(0, |index| index + 1);
+    let rest = &value[protocol_end..];
+    let (host_start, host_end) = rest.strip_prefix("//").map_or((0, 0), |host| {
+        let end = host
+            .find(|char| matches!(char, '/' | '?' | '#'))
+            .unwrap_or(host.len());
+        let start = host[0..end].rfind('@').map_or(0, |index| index + 1);
+        (start + 2, end + 2)
+    });
+
+    format!(
+        "{}{}{}{}",
+        value[0..protocol_end].to_ascii_lowercase(),
+        &rest[0..host_start],
+        rest[host_start..host_end].to_ascii_lowercase(),
+        &rest[host_end..]
+    )
+}
+
+/// Check whether a URL is to another host than `base`.
+///
+/// URLs without a host (such as `a`, `/a`, `#a`, or `mailto:a@b.c`) are not
+/// external.
+/// URLs with a host are external if `base` is `None`, or if `base` has
+/// another host (compared case-insensitively, without user info).
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::resolve_uri::is_external;
+///
+/// assert!(is_external("https://a.b/c", None));
+/// assert!(is_external("https://a.b/c", Some("https://d.e/")));
+/// assert!(!is_external("https://A.B/c", Some("https://a.b/")));
+/// assert!(!is_external("/c", None));
+/// ```
+pub fn is_external(value: &str, base: Option<&str>) -> bool {
+    match (host(value), base.map(host)) {
+        (Some(host), Some(Some(base))) => !host.eq_ignore_ascii_case(base),
+        (Some(_), _) => true,
+        (None, _) => false,
+    }
+}
+
+/// Get the host (and port) of a URL, without user info, if there is one.
+fn host(value: &str) -> Option<&str> {
+    let protocol_end = protocol_end(value).map_or(0, |index| index + 1);
+    let host = value[protocol_end..].strip_prefix("//")?;
+    let end = host
+        .find(|char| matches!(char, '/' | '?' | '#'))
+        .unwrap_or(host.len());
+    let start = host[0..end].rfind('@').map_or(0, |index| index + 1);
+    let host = &host[start..end];
+
+    if host.is_empty() {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Parts of a (base) URL.
+struct Parts<'a> {
+    /// Protocol, including its colon (`https:`), or an empty string.
+    protocol: &'a str,
+    /// Host, including its slashes (`//example.com`), or an empty string.
+    host: &'a str,
+    /// Path (`/a/b`).
+    path: &'a str,
+    /// Query, including its question mark (`?a`), or an empty string.
+    query: &'a str,
+}
+
+impl<'a> Parts<'a> {
+    /// Split a URL into its parts, dropping its fragment.
+    fn from(value: &'a str) -> Self {
+        let value = value.find('#').map_or(value, |index| &value[0..index]);
+        let protocol_end = protocol_end(value).map_or(0, |index| index + 1);
+        let rest = &value[protocol_end..];
+        let host_end = rest.strip_prefix("//").map_or(0, |host| {
+            host.find(|char| matches!(char, '/' | '?'))
+                .map_or(rest.len(), |index| index + 2)
+        });
+        let query_start = rest[host_end..]
+            .find('?')
+            .map_or(rest.len(), |index| index + host_end);
+
+        Parts {
+            protocol: &value[0..protocol_end],
+            host: &rest[0..host_end],
+            path: &rest[host_end..query_start],
+            query: &rest[query_start..],
+        }
+    }
+}
+
+/// Get the index of the colon after the protocol of a URL, if there is one.
+///
+/// Like [`sanitize_with_protocols`][crate::util::sanitize_uri::sanitize_with_protocols],
+/// a colon after `?`, `#`, or `/` does not end a protocol.
+fn protocol_end(value: &str) -> Option<usize> {
+    let colon = value.find(':')?;
+    let end = value.find(|char| matches!(char, '?' | '#' | '/'));
+
+    if end.map_or(false, |end| colon > end) {
+        None
+    } else {
+        Some(colon)
+    }
+}
+
+/// Remove `.` and `..` segments from the path of a URL.
+///
+/// The query and fragment, if there are any, are kept as they are.
+fn remove_dot_segments(value: &str) -> String {
+    let path_end = value
+        .find(|char| matches!(char, '?' | '#'))
+        .unwrap_or(value.len());
+    let segments = value[0..path_end].split('/').collect::<Vec<_>>();
+    let mut result: Vec<&str> = Vec::with_capacity(segments.len());
+    let mut index = 0;
+
+    while index < segments.len() {
+        let segment = segments[index];
+        let last = index == segments.len() - 1;
+
+        if segment == "." || segment == ".." {
+            // Keep the empty segment before the first slash of absolute paths.
+            if segment == ".."
+                && (result.len() > 1 || result.first().map_or(false, |d| !d.is_empty()))
+            {
+                result.pop();
+            }
+
+            // Keep the trailing slash.
+            if last {
+                result.push("");
+            }
+        } else {
+            result.push(segment);
+        }
+
+        index += 1;
+    }
+
+    format!("{}{}", result.join("/"), &value[path_end..])
+}
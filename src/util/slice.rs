@@ -1,7 +1,6 @@
 //! Deal with bytes.
 
 use crate::event::{Event, Kind, Point};
-use crate::util::constant::TAB_SIZE;
 use alloc::{format, string::String, vec};
 use core::str;
 
@@ -64,7 +63,10 @@ pub struct Slice<'a> {
 
 impl<'a> Slice<'a> {
     /// Get a slice for a position.
-    pub fn from_position(bytes: &'a [u8], position: &Position) -> Slice<'a> {
+    ///
+    /// Virtual spaces (parts of tabs) at the edges are counted with
+    /// `tab_size`.
+    pub fn from_position(bytes: &'a [u8], position: &Position, tab_size: usize) -> Slice<'a> {
         let mut before = position.start.vs;
         let mut after = position.end.vs;
         let mut start = position.start.index;
@@ -72,10 +74,11 @@ impl<'a> Slice<'a> {
 
         // If we have virtual spaces before, it means we are past the actual
         // character at that index, and those virtual spaces.
+        // What is left of the tab are the columns up to the next tab stop.
         if before > 0 {
-            before = TAB_SIZE - before;
+            before = tab_size - (position.start.column - 1) % tab_size;
             start += 1;
-        };
+        }
 
         // If we have virtual spaces after, it means that character is included,
         // and one less virtual space.
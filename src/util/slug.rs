@@ -0,0 +1,159 @@
+//! Generate slugs, used as heading ids.
+
+use crate::event::{Event, Kind, Name};
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    slice::{Position, Slice},
+};
+use alloc::{format, string::String, vec::Vec};
+
+/// Turn the plain text of a heading into a slug, as done on GitHub.
+///
+/// This lowercases the value, drops everything other than letters, numbers,
+/// `-`, `_`, and spaces, and then turns each space into a `-`.
+/// Non-ASCII letters and numbers are kept.
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::slug::slug;
+///
+/// assert_eq!(slug("Hello, world!"), "hello-world");
+/// assert_eq!(slug("  a  b  "), "--a--b--");
+/// assert_eq!(slug("Привет мир"), "привет-мир");
+/// ```
+///
+/// ## References
+///
+/// *   [`github-slugger`](https://github.com/Flet/github-slugger)
+pub fn slug(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for char in value.to_lowercase().chars() {
+        if char == ' ' {
+            result.push('-');
+        } else if char.is_alphanumeric() || char == '-' || char == '_' {
+            result.push(char);
+        }
+    }
+
+    result
+}
+
+/// Track slugs, to make sure each one is used only once.
+#[derive(Debug, Default)]
+pub struct Slugger {
+    /// Slugs that are taken.
+    seen: Vec<String>,
+}
+
+impl Slugger {
+    /// Create a unique slug for `value`.
+    ///
+    /// When the slug of `value` is already taken, `-1`, `-2`, etc are
+    /// appended until it isn’t.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust ignore
+    /// use markdown::util::slug::Slugger;
+    ///
+    /// let mut slugger = Slugger::default();
+    ///
+    /// assert_eq!(slugger.slug("a"), "a");
+    /// assert_eq!(slugger.slug("A"), "a-1");
+    /// assert_eq!(slugger.slug("a-1"), "a-1-1");
+    /// ```
+    pub fn slug(&mut self, value: &str) -> String {
+        let base = slug(value);
+        let mut result = base.clone();
+        let mut count = 0;
+
+        while self.seen.contains(&result) {
+            count += 1;
+            result = format!("{}-{}", base, count);
+        }
+
+        self.seen.push(result.clone());
+        result
+    }
+}
+
+/// Get the plain text of a heading, used to generate its slug.
+///
+/// `index` is the exit of a [`HeadingAtxText`][Name::HeadingAtxText] or
+/// [`HeadingSetextText`][Name::HeadingSetextText].
+///
+/// The plain text includes data, code, math, character escapes and
+/// references, emoji, and autolinks, but not images, HTML, footnote calls,
+/// the destinations and titles of links, or the names and attributes of
+/// directives.
+pub fn heading_text(events: &[Event], bytes: &[u8], tab_size: usize, index: usize) -> String {
+    let name = &events[index].name;
+    let end = index;
+    let mut index = index;
+
+    while !(events[index].kind == Kind::Enter && &events[index].name == name) {
+        index -= 1;
+    }
+
+    let mut value = String::new();
+    let mut skip_depth = 0;
+    let mut marker = b'&';
+
+    while index < end {
+        let event = &events[index];
+
+        if matches!(
+            event.name,
+            Name::Attributes
+                | Name::DirectiveAttributes
+                | Name::DirectiveName
+                | Name::GfmFootnoteCall
+                | Name::HtmlText
+                | Name::Image
+                | Name::Reference
+                | Name::Resource
+        ) {
+            if event.kind == Kind::Enter {
+                skip_depth += 1;
+            } else {
+                skip_depth -= 1;
+            }
+        } else if skip_depth == 0 && event.kind == Kind::Exit {
+            let slice =
+                Slice::from_position(bytes, &Position::from_exit_event(events, index), tab_size);
+
+            match event.name {
+                Name::AutolinkEmail
+                | Name::AutolinkProtocol
+                | Name::CharacterEscapeValue
+                | Name::CodeTextData
+                | Name::Data
+                | Name::GfmAutolinkLiteralEmail
+                | Name::GfmAutolinkLiteralMailto
+                | Name::GfmAutolinkLiteralProtocol
+                | Name::GfmAutolinkLiteralWww
+                | Name::GfmAutolinkLiteralXmpp
+                | Name::LineEnding
+                | Name::MathTextData
+                | Name::TextExtension(_) => value.push_str(slice.as_str()),
+                Name::Emoji(ref emoji) => value.push_str(emoji),
+                Name::CharacterReferenceMarkerHexadecimal => marker = b'x',
+                Name::CharacterReferenceMarkerNumeric => marker = b'#',
+                Name::CharacterReferenceValue => {
+                    value.push_str(
+                        &decode_character_reference(slice.as_str(), marker, true)
+                            .expect("expected to parse only valid named references"),
+                    );
+                    marker = b'&';
+                }
+                _ => {}
+            }
+        }
+
+        index += 1;
+    }
+
+    value
+}
@@ -0,0 +1,136 @@
+//! Turn straight quotes, dashes, and dots into their typographic forms.
+
+use crate::util::char::{classify_opt, Kind as CharacterKind};
+use alloc::{string::String, vec::Vec};
+
+/// Turn straight quotes into curly quotes, `--` and `---` into en and em
+/// dashes, and `...` into an ellipsis.
+///
+/// `before` and `after` are the characters around `value` in the document,
+/// used to figure out whether quotes at its edges open or close.
+/// Pass `None` for the start or end of a line (or the document).
+///
+/// Whether a quote opens or closes is decided by the characters around it,
+/// like attention (emphasis, strong) does: a quote that can only open is an
+/// opening quote, a quote that can only close is a closing quote.
+/// A single quote right after a letter or number is an apostrophe, as in
+/// contractions (`don’t`) and possessives (`planets’`).
+///
+/// Runs of dashes are turned into em dashes if they are divisible by three,
+/// into en dashes if they are divisible by two, and into a mix otherwise
+/// (em dashes first).
+///
+/// ## Examples
+///
+/// ```rust ignore
+/// use markdown::util::smart_punctuation::smart_punctuation;
+///
+/// assert_eq!(smart_punctuation("\"a\" -- b's...", None, None), "“a” – b’s…");
+/// ```
+///
+/// ## References
+///
+/// *   [*§ Smart punctuation* in `commonmark.js`](https://github.com/commonmark/commonmark.js#usage)
+pub fn smart_punctuation(value: &str, before: Option<char>, after: Option<char>) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(value.len());
+    let mut index = 0;
+
+    while index < chars.len() {
+        let char = chars[index];
+
+        match char {
+            '"' | '\'' => {
+                let previous = if index == 0 {
+                    before
+                } else {
+                    Some(chars[index - 1])
+                };
+                let next = if index + 1 == chars.len() {
+                    after
+                } else {
+                    Some(chars[index + 1])
+                };
+                result.push(quote(char, previous, next));
+                index += 1;
+            }
+            '-' | '.' => {
+                let start = index;
+
+                while index < chars.len() && chars[index] == char {
+                    index += 1;
+                }
+
+                let size = index - start;
+
+                if char == '.' {
+                    let mut count = 0;
+                    while count < size / 3 {
+                        result.push('…');
+                        count += 1;
+                    }
+                    count = 0;
+                    while count < size % 3 {
+                        result.push('.');
+                        count += 1;
+                    }
+                } else if size == 1 {
+                    result.push('-');
+                } else {
+                    let (em, en) = if size % 3 == 0 {
+                        (size / 3, 0)
+                    } else if size % 2 == 0 {
+                        (0, size / 2)
+                    } else if size % 3 == 2 {
+                        ((size - 2) / 3, 1)
+                    } else {
+                        ((size - 4) / 3, 2)
+                    };
+                    let mut count = 0;
+                    while count < em {
+                        result.push('—');
+                        count += 1;
+                    }
+                    count = 0;
+                    while count < en {
+                        result.push('–');
+                        count += 1;
+                    }
+                }
+            }
+            _ => {
+                result.push(char);
+                index += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// Get the curly form of a straight quote (`marker`), based on the
+/// characters around it.
+fn quote(marker: char, previous: Option<char>, next: Option<char>) -> char {
+    let before = classify_opt(previous);
+    let after = classify_opt(next);
+    let open = after != CharacterKind::Whitespace
+        && (after != CharacterKind::Punctuation || before != CharacterKind::Other);
+    let close = before != CharacterKind::Whitespace
+        && (before != CharacterKind::Punctuation || after != CharacterKind::Other);
+    let opening = if marker == '"' {
+        if open == close {
+            before == CharacterKind::Whitespace || matches!(previous, Some('(' | '[' | '{'))
+        } else {
+            open
+        }
+    } else {
+        before != CharacterKind::Other && open
+    };
+
+    match (marker, opening) {
+        ('"', true) => '“',
+        ('"', false) => '”',
+        (_, true) => '‘',
+        (_, false) => '’',
+    }
+}
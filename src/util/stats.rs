@@ -0,0 +1,213 @@
+//! Statistics of a document, such as how many words it has.
+
+use crate::event::{Event, Kind, Name};
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    slice::{Position, Slice},
+};
+use alloc::string::String;
+
+/// Statistics of a document.
+///
+/// Words and characters are counted in the text of the document, as it is
+/// rendered: `**a**b` is one word (`ab`) of two characters.
+///
+/// Text is data (including in headings, links, emphasis, tables, footnote
+/// definitions, and so on), character escapes, character references, and
+/// the URLs of autolinks and GFM autolink literals.
+///
+/// Not text is code (fenced, indented, and text), math (flow and text), HTML
+/// (flow and text), definitions and abbreviation definitions, the
+/// destinations and titles of links, the labels of references and footnote
+/// calls, the alt of images, frontmatter, MDX (ESM, expressions, and JSX
+/// tags), and the attributes of directives, headings, and paragraphs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DocumentStats {
+    /// Number of words.
+    ///
+    /// A word is a run of characters that are not whitespace, which includes
+    /// at least one alphanumeric character: `-` is not a word.
+    /// Chinese and Japanese are written without spaces, so each CJK
+    /// ideograph, hiragana, and katakana is counted as a word.
+    /// Korean (hangul) is written with spaces, so it is counted like other
+    /// scripts.
+    pub words: usize,
+    /// Number of characters in text that are not whitespace.
+    pub characters: usize,
+    /// Number of headings (atx and setext).
+    pub headings: usize,
+    /// Number of links, including autolinks and GFM autolink literals, but
+    /// not images.
+    pub links: usize,
+    /// Number of code blocks (fenced and indented), not including math
+    /// (flow).
+    pub code_blocks: usize,
+}
+
+/// Collect statistics from events.
+pub fn collect(events: &[Event], bytes: &[u8], tab_size: usize) -> DocumentStats {
+    let mut stats = DocumentStats::default();
+    let mut text = String::new();
+    let mut ignore = 0;
+    let mut marker = b'&';
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        if matches!(
+            event.name,
+            Name::AbbreviationDefinition
+                | Name::Attributes
+                | Name::CodeFenced
+                | Name::CodeIndented
+                | Name::CodeText
+                | Name::Definition
+                | Name::DirectiveAttributes
+                | Name::Frontmatter
+                | Name::GfmFootnoteCall
+                | Name::GfmFootnoteDefinitionPrefix
+                | Name::HtmlFlow
+                | Name::HtmlText
+                | Name::Image
+                | Name::MathFlow
+                | Name::MathText
+                | Name::MdxEsm
+                | Name::MdxFlowExpression
+                | Name::MdxJsxFlowTag
+                | Name::MdxJsxTextTag
+                | Name::MdxTextExpression
+                | Name::Reference
+                | Name::Resource
+        ) {
+            if event.kind == Kind::Enter {
+                if ignore == 0 && matches!(event.name, Name::CodeFenced | Name::CodeIndented) {
+                    stats.code_blocks += 1;
+                }
+
+                ignore += 1;
+            } else {
+                ignore -= 1;
+            }
+        } else if ignore == 0 {
+            if event.kind == Kind::Enter {
+                match event.name {
+                    Name::HeadingAtx | Name::HeadingSetext => stats.headings += 1,
+                    Name::Autolink
+                    | Name::GfmAutolinkLiteralEmail
+                    | Name::GfmAutolinkLiteralMailto
+                    | Name::GfmAutolinkLiteralProtocol
+                    | Name::GfmAutolinkLiteralWww
+                    | Name::GfmAutolinkLiteralXmpp
+                    | Name::Link => stats.links += 1,
+                    _ => {}
+                }
+            } else {
+                match event.name {
+                    Name::AutolinkEmail
+                    | Name::AutolinkProtocol
+                    | Name::CharacterEscapeValue
+                    | Name::Data
+                    | Name::GfmAutolinkLiteralEmail
+                    | Name::GfmAutolinkLiteralMailto
+                    | Name::GfmAutolinkLiteralProtocol
+                    | Name::GfmAutolinkLiteralWww
+                    | Name::GfmAutolinkLiteralXmpp
+                    | Name::TextExtension(_) => {
+                        let slice = Slice::from_position(
+                            bytes,
+                            &Position::from_exit_event(events, index),
+                            tab_size,
+                        );
+                        text.push_str(slice.as_str());
+                    }
+                    Name::Emoji(ref emoji) => text.push_str(emoji),
+                    Name::CharacterReferenceMarker => marker = b'&',
+                    Name::CharacterReferenceMarkerHexadecimal => marker = b'x',
+                    Name::CharacterReferenceMarkerNumeric => marker = b'#',
+                    Name::CharacterReferenceValue => {
+                        let slice = Slice::from_position(
+                            bytes,
+                            &Position::from_exit_event(events, index),
+                            tab_size,
+                        );
+                        if let Some(value) =
+                            decode_character_reference(slice.as_str(), marker, true)
+                        {
+                            text.push_str(&value);
+                        }
+                    }
+                    // Separate words in different blocks, cells, and lines.
+                    Name::DefinitionListTerm
+                    | Name::GfmTableCell
+                    | Name::HeadingAtx
+                    | Name::HeadingSetext
+                    | Name::LineEnding
+                    | Name::Paragraph => text.push('\n'),
+                    _ => {}
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    let mut word = false;
+    let mut alphanumeric = false;
+
+    for char in text.chars() {
+        if char.is_whitespace() {
+            if word && alphanumeric {
+                stats.words += 1;
+            }
+            word = false;
+            alphanumeric = false;
+        } else {
+            stats.characters += 1;
+
+            if cjk(char) {
+                if word && alphanumeric {
+                    stats.words += 1;
+                }
+                stats.words += 1;
+                word = false;
+                alphanumeric = false;
+            } else {
+                word = true;
+                if char.is_alphanumeric() {
+                    alphanumeric = true;
+                }
+            }
+        }
+    }
+
+    if word && alphanumeric {
+        stats.words += 1;
+    }
+
+    stats
+}
+
+/// Check whether a character is a CJK ideograph, hiragana, or katakana.
+fn cjk(char: char) -> bool {
+    matches!(
+        char,
+        // Hiragana, katakana.
+        '\u{3040}'..='\u{30FF}'
+        // Katakana phonetic extensions.
+        | '\u{31F0}'..='\u{31FF}'
+        // CJK unified ideographs extension A.
+        | '\u{3400}'..='\u{4DBF}'
+        // CJK unified ideographs.
+        | '\u{4E00}'..='\u{9FFF}'
+        // CJK compatibility ideographs.
+        | '\u{F900}'..='\u{FAFF}'
+        // Halfwidth katakana.
+        | '\u{FF66}'..='\u{FF9F}'
+        // CJK unified ideographs extension B and later, compatibility
+        // ideographs supplement.
+        | '\u{20000}'..='\u{2FA1F}'
+        // CJK unified ideographs extension G and later.
+        | '\u{30000}'..='\u{323AF}'
+    )
+}
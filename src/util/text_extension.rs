@@ -0,0 +1,78 @@
+//! Info on custom text constructs, passed when parsing and compiling.
+
+use alloc::{boxed::Box, fmt, string::String};
+
+/// Signature of a function that checks whether a custom text construct
+/// starts at a place.
+///
+/// Gets the rest of the line, starting at the marker of the extension, and
+/// without the line ending.
+/// Returns the size of the construct in bytes, or `None` if it does not start
+/// there.
+pub type Start = dyn Fn(&str) -> Option<usize>;
+
+/// Signature of a function that turns a custom text construct into HTML.
+///
+/// Can be passed as `text_extension_handler` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Gets the name of the construct and its value.
+///
+/// Return `None` to not handle a construct, in which case its value is
+/// rendered as text.
+pub type Handler = dyn Fn(&str, &str) -> Option<String>;
+
+/// Custom text construct.
+///
+/// Custom text constructs are tried where `marker` occurs in text (such as
+/// in paragraphs and headings), before the built-in constructs that start
+/// with that byte.
+/// `start` decides whether the construct starts there, and how many bytes
+/// it spans.
+/// The construct cannot span lines, and its value is not parsed further.
+///
+/// ## Examples
+///
+/// ```
+/// use markdown::TextExtension;
+///
+/// // Emoji shortcodes, such as `:tada:`.
+/// let extension = TextExtension {
+///     name: "emoji".into(),
+///     marker: b':',
+///     start: Box::new(|rest| {
+///         let size = rest[1..].find(':')?;
+///         let name = &rest[1..=size];
+///         if !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_lowercase() || byte == b'_') {
+///             Some(size + 2)
+///         } else {
+///             None
+///         }
+///     }),
+/// };
+/// # assert_eq!((extension.start)(":tada: a"), Some(6));
+/// # assert_eq!((extension.start)(":a b:"), None);
+/// ```
+pub struct TextExtension {
+    /// Name of the construct.
+    ///
+    /// Used in events (as `TextExtension(name)`) and passed to
+    /// `text_extension_handler` when compiling to HTML.
+    pub name: String,
+    /// Byte that starts the construct, such as `b':'`, `b'@'`, or `b'#'`.
+    ///
+    /// Extensions with the same marker are tried in the order they are given.
+    pub marker: u8,
+    /// Check whether the construct starts at a marker, and how big it is.
+    pub start: Box<Start>,
+}
+
+impl fmt::Debug for TextExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextExtension")
+            .field("name", &self.name)
+            .field("marker", &self.marker)
+            .field("start", &"[Function]")
+            .finish()
+    }
+}
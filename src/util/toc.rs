@@ -0,0 +1,111 @@
+//! Table of contents of a document: its headings.
+
+use crate::event::{Event, Kind, Name};
+use crate::unist;
+use crate::util::{
+    character_reference::parse as parse_character_reference,
+    slice::{Position, Slice},
+    slug::{heading_text, Slugger},
+};
+use alloc::{string::String, vec, vec::Vec};
+
+/// Entry in a table of contents: a heading.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TocEntry {
+    /// Rank of the heading, from `1` to `6`, the same as `N` in the `<hN>`
+    /// it compiles to.
+    pub depth: u8,
+    /// Plain text of the heading.
+    ///
+    /// This is the same text that slugs are made from: data, code, math,
+    /// character escapes and references, emoji, and autolinks, but not
+    /// images, HTML, or footnote calls.
+    pub text: String,
+    /// Slug of the heading, the same as the `id` it gets with
+    /// [`heading_ids`][crate::CompileOptions::heading_ids].
+    ///
+    /// Empty for headings without text (such as `#`), which do not get an
+    /// `id`.
+    /// When the heading has an `id` in its attributes (such as `## a {#b}`,
+    /// with [`attributes`][crate::Constructs::attributes]), that is used
+    /// instead.
+    pub id: String,
+    /// Place of the whole heading in the document.
+    pub position: unist::Position,
+}
+
+/// Collect the headings from events.
+pub fn collect(events: &[Event], bytes: &[u8], tab_size: usize) -> Vec<TocEntry> {
+    let mut result = vec![];
+    let mut slugger = Slugger::default();
+    let mut start = 0;
+    let mut depth = 0;
+    let mut text = String::new();
+    let mut id = String::new();
+    let mut attribute_id = None;
+    let mut id_marker = false;
+    let mut index = 0;
+
+    while index < events.len() {
+        let event = &events[index];
+
+        match (&event.kind, &event.name) {
+            (Kind::Enter, Name::HeadingAtx | Name::HeadingSetext) => {
+                start = index;
+                depth = 0;
+                text = String::new();
+                id = String::new();
+                attribute_id = None;
+            }
+            // Only the opening sequence decides the rank.
+            (Kind::Exit, Name::HeadingAtxSequence) if depth == 0 => {
+                #[allow(clippy::cast_possible_truncation)]
+                let size = (event.point.index - events[index - 1].point.index) as u8;
+                depth = size;
+            }
+            (Kind::Exit, Name::HeadingSetextUnderlineSequence) => {
+                depth = if bytes[events[index - 1].point.index] == b'-' {
+                    2
+                } else {
+                    1
+                };
+            }
+            (Kind::Exit, Name::HeadingAtxText | Name::HeadingSetextText) => {
+                text = heading_text(events, bytes, tab_size, index);
+                id = slugger.slug(&text);
+            }
+            (Kind::Exit, Name::AttributeIdMarker) => id_marker = true,
+            (Kind::Exit, Name::AttributeValue) if id_marker => {
+                id_marker = false;
+                attribute_id = Some(parse_character_reference(
+                    Slice::from_position(
+                        bytes,
+                        &Position::from_exit_event(events, index),
+                        tab_size,
+                    )
+                    .as_str(),
+                    true,
+                ));
+            }
+            (Kind::Exit, Name::AttributeClassMarker | Name::AttributeName) => id_marker = false,
+            (Kind::Exit, Name::HeadingAtx | Name::HeadingSetext) => {
+                result.push(TocEntry {
+                    depth,
+                    text: core::mem::take(&mut text),
+                    id: attribute_id
+                        .take()
+                        .unwrap_or_else(|| core::mem::take(&mut id)),
+                    position: unist::Position {
+                        start: events[start].point.to_unist(),
+                        end: event.point.to_unist(),
+                    },
+                });
+            }
+            _ => {}
+        }
+
+        index += 1;
+    }
+
+    result
+}
@@ -2,6 +2,8 @@
 
 /// List of characters that are considered punctuation.
 ///
+/// Sorted by code point, so that it can be searched quickly.
+///
 /// > 👉 **Important**: this module is generated by `generate/src/main.rs`.
 /// > It is generate from the latest Unicode data.
 ///
@@ -15,7 +17,7 @@
 /// ## References
 ///
 /// *   [*§ 2.1 Characters and lines* in `CommonMark`](https://spec.commonmark.org/0.31.2/#unicode-punctuation-character)
-pub const PUNCTUATION: [char; 8617] = [
+pub static PUNCTUATION: [char; 8617] = [
     '\u{0021}',
     '\u{0022}',
     '\u{0023}',
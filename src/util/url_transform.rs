@@ -0,0 +1,41 @@
+//! Info on URLs of links and images, passed to a function to change them.
+
+use alloc::string::String;
+
+/// Info on the URL of a link or image.
+///
+/// Passed to [`url_transform`][crate::CompileOptions::url_transform].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UrlContext {
+    /// Destination, with character escapes and references decoded, and
+    /// definitions resolved.
+    ///
+    /// For example, `https://a&amp;b` in `[x](https://a&amp;b)`, or in
+    /// `[x]` with `[x]: https://a&amp;b`, is `https://a&b`.
+    pub destination: String,
+    /// Whether the URL is the `src` of an image (`true`) or the `href` of a
+    /// link (`false`).
+    pub image: bool,
+    /// Title, with character escapes and references decoded.
+    ///
+    /// Autolinks have no title.
+    pub title: Option<String>,
+}
+
+/// Signature of a function that changes the URL of a link or image.
+///
+/// Can be passed as `url_transform` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Return `Some` with a new destination, or `None` to drop the `href` or
+/// `src` attribute.
+pub type Transform = dyn Fn(UrlContext) -> Option<String>;
+
+/// Signature of a function that checks whether a link goes to another site.
+///
+/// Can be passed as `is_external_link` in
+/// [`CompileOptions`][crate::configuration::CompileOptions].
+///
+/// Gets the final URL of the link (after `url_transform`, `base_url`, and
+/// `normalize_url_case`).
+pub type IsExternal = dyn Fn(&str) -> bool;
@@ -0,0 +1,260 @@
+//! Visible text of a document, such as to index it for search.
+
+use crate::event::{Event, Kind, Name};
+use crate::unist;
+use crate::util::{
+    character_reference::decode as decode_character_reference,
+    slice::{Position, Slice},
+};
+use crate::ParseOptions;
+use alloc::{string::String, vec::Vec};
+
+/// Visible text of a block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TextRun {
+    /// Visible text of the block.
+    ///
+    /// Line endings in the block are kept as `\n`, except for those in code
+    /// (text) and math (text), which are spaces.
+    pub text: String,
+    /// Place of the whole block in the document.
+    pub position: unist::Position,
+}
+
+/// Iterator over the visible text of a document, one [`TextRun`][] per
+/// block, in order.
+///
+/// Made with [`text_runs()`][crate::text_runs].
+///
+/// Blocks are paragraphs, headings (atx and setext), GFM table cells, terms
+/// of definition lists, leaf directives, the fences of container directives,
+/// code (fenced and indented), and math (flow).
+/// Whitespace around the text of blocks is removed, except for the indent of
+/// code and math.
+/// Blocks without visible text are skipped.
+///
+/// Visible text is data (including in emphasis, links, and so on),
+/// character escapes, decoded character references, emoji, the text of
+/// autolinks and GFM autolink literals, the alt of images, the labels of
+/// directives, and the content of code and math.
+///
+/// Not visible text is markup, the destinations and titles of links and
+/// images, the labels of references and footnote calls, the info of code
+/// (fenced), HTML (flow and text), definitions and abbreviation definitions,
+/// frontmatter, MDX (ESM, expressions, and JSX tags), the checks of GFM task
+/// list items, and the names and attributes of directives, headings, and
+/// paragraphs.
+#[derive(Debug)]
+pub struct TextRuns<'a> {
+    /// Events of the document.
+    events: Vec<Event>,
+    /// Bytes of the document.
+    bytes: &'a [u8],
+    /// Size of a tab stop.
+    tab_size: usize,
+    /// Whether to strip one space from both sides of code (text).
+    code_text_strip_space: bool,
+    /// Index of the next event to look at.
+    index: usize,
+}
+
+impl<'a> TextRuns<'a> {
+    /// Create an iterator over runs from events, parsed with `options`.
+    pub fn new(events: Vec<Event>, bytes: &'a [u8], options: &ParseOptions) -> TextRuns<'a> {
+        TextRuns {
+            events,
+            bytes,
+            tab_size: options.tab_size.max(1),
+            code_text_strip_space: options.code_text_strip_space,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for TextRuns<'_> {
+    type Item = TextRun;
+
+    fn next(&mut self) -> Option<TextRun> {
+        while self.index < self.events.len() {
+            let start = self.index;
+            self.index += 1;
+
+            if self.events[start].kind == Kind::Enter && is_block(&self.events[start].name) {
+                let mut balance = 0;
+
+                while self.events[self.index].kind == Kind::Enter || balance > 0 {
+                    if self.events[self.index].kind == Kind::Enter {
+                        balance += 1;
+                    } else {
+                        balance -= 1;
+                    }
+
+                    self.index += 1;
+                }
+
+                let end = self.index;
+                self.index += 1;
+                let text = self.text(start + 1, end);
+
+                if !text.is_empty() {
+                    return Some(TextRun {
+                        text,
+                        position: unist::Position {
+                            start: self.events[start].point.to_unist(),
+                            end: self.events[end].point.to_unist(),
+                        },
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> TextRuns<'a> {
+    /// Get the visible text of the events from `start` to `end`.
+    fn text(&self, start: usize, end: usize) -> String {
+        let table = self.events[end].name == Name::GfmTableCell;
+        let mut text = String::new();
+        let mut raw: Option<String> = None;
+        let mut ignore = 0;
+        let mut marker = b'&';
+        let mut index = start;
+
+        while index < end {
+            let event = &self.events[index];
+
+            if matches!(
+                event.name,
+                Name::Attributes
+                    | Name::CodeFencedFence
+                    | Name::DirectiveAttributes
+                    | Name::GfmFootnoteCall
+                    | Name::HtmlText
+                    | Name::MathFlowFence
+                    | Name::MdxJsxTextTag
+                    | Name::MdxTextExpression
+                    | Name::Reference
+                    | Name::Resource
+            ) {
+                if event.kind == Kind::Enter {
+                    ignore += 1;
+                } else {
+                    ignore -= 1;
+                }
+            } else if ignore == 0 {
+                if event.kind == Kind::Enter {
+                    if matches!(event.name, Name::CodeText | Name::MathText) {
+                        raw = Some(String::new());
+                    }
+                } else {
+                    let in_raw = raw.is_some();
+                    let buffer = raw.as_mut().unwrap_or(&mut text);
+
+                    match event.name {
+                        Name::AutolinkEmail
+                        | Name::AutolinkProtocol
+                        | Name::CharacterEscapeValue
+                        | Name::CodeTextData
+                        | Name::Data
+                        | Name::GfmAutolinkLiteralEmail
+                        | Name::GfmAutolinkLiteralMailto
+                        | Name::GfmAutolinkLiteralProtocol
+                        | Name::GfmAutolinkLiteralWww
+                        | Name::GfmAutolinkLiteralXmpp
+                        | Name::MathTextData
+                        | Name::TextExtension(_) => {
+                            buffer.push_str(self.slice(index).as_str());
+                        }
+                        Name::CodeFlowChunk | Name::MathFlowChunk => {
+                            // Must serialize to get virtual spaces.
+                            buffer.push_str(&self.slice(index).serialize());
+                        }
+                        Name::Emoji(ref emoji) => buffer.push_str(emoji),
+                        Name::CharacterReferenceMarker => marker = b'&',
+                        Name::CharacterReferenceMarkerHexadecimal => marker = b'x',
+                        Name::CharacterReferenceMarkerNumeric => marker = b'#',
+                        Name::CharacterReferenceValue => {
+                            if let Some(value) =
+                                decode_character_reference(self.slice(index).as_str(), marker, true)
+                            {
+                                buffer.push_str(&value);
+                            }
+                        }
+                        Name::LineEnding => {
+                            buffer.push(if in_raw { ' ' } else { '\n' });
+                        }
+                        Name::CodeText | Name::MathText => {
+                            let value = raw.take().unwrap();
+                            text.push_str(&raw_text(&value, self.code_text_strip_space, table));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        // Keep the indent of code and math, but not other whitespace around
+        // blocks, such as before a dropped footnote call.
+        if matches!(
+            self.events[end].name,
+            Name::CodeFenced | Name::CodeIndented | Name::MathFlow
+        ) {
+            String::from(text.trim_matches('\n'))
+        } else {
+            String::from(text.trim())
+        }
+    }
+
+    /// Get the slice of the exit event at `index`.
+    fn slice(&self, index: usize) -> Slice<'a> {
+        Slice::from_position(
+            self.bytes,
+            &Position::from_exit_event(&self.events, index),
+            self.tab_size,
+        )
+    }
+}
+
+/// Check whether a construct is a block that contains visible text.
+fn is_block(name: &Name) -> bool {
+    matches!(
+        name,
+        Name::CodeFenced
+            | Name::CodeIndented
+            | Name::DefinitionListTerm
+            | Name::DirectiveContainerFence
+            | Name::DirectiveLeaf
+            | Name::GfmTableCell
+            | Name::HeadingAtx
+            | Name::HeadingSetext
+            | Name::MathFlow
+            | Name::Paragraph
+    )
+}
+
+/// Get the visible text of code (text) or math (text), the same as how it
+/// compiles to HTML.
+fn raw_text(value: &str, strip: bool, table: bool) -> String {
+    // Escaped pipes in GFM tables are pipes.
+    let value = if table {
+        value.replace("\\|", "|")
+    } else {
+        String::from(value)
+    };
+    let bytes = value.as_bytes();
+
+    if strip
+        && bytes.len() > 2
+        && bytes[0] == b' '
+        && bytes[bytes.len() - 1] == b' '
+        && bytes.iter().any(|d| *d != b' ')
+    {
+        String::from(&value[1..value.len() - 1])
+    } else {
+        value
+    }
+}
@@ -0,0 +1,214 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn abbreviation() -> Result<(), message::Message> {
+    let abbreviation = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                abbreviation: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("*[HTML]: a\n\nHTML"),
+        "<p>*[HTML]: a</p>\n<p>HTML</p>",
+        "should not support abbreviations by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[HTML]: Hyper Text Markup Language\n\nHTML b",
+            &abbreviation
+        )?,
+        "<p><abbr title=\"Hyper Text Markup Language\">HTML</abbr> b</p>",
+        "should support abbreviations"
+    );
+
+    assert_eq!(
+        to_html_with_options("a HTML\n\n*[HTML]: b", &abbreviation)?,
+        "<p>a <abbr title=\"b\">HTML</abbr></p>\n",
+        "should support definitions after abbreviations"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n*[HTML]: b\n\nHTML", &abbreviation)?,
+        "<p><abbr title=\"a\">HTML</abbr></p>",
+        "should prefer the first definition"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\nHTML, XHTML, HTML5, _HTML_", &abbreviation)?,
+        "<p><abbr title=\"a\">HTML</abbr>, XHTML, HTML5, <em><abbr title=\"a\">HTML</abbr></em></p>",
+        "should match whole words"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[é]: a\n\né, éé, aé", &abbreviation)?,
+        "<p><abbr title=\"a\">é</abbr>, éé, aé</p>",
+        "should match whole words (unicode)"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[C++]: a\n\nC++, C+++", &abbreviation)?,
+        "<p><abbr title=\"a\">C++</abbr>, <abbr title=\"a\">C++</abbr>+</p>",
+        "should not need word boundaries next to non-word characters"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n*[HTML 5]: b\n\nHTML 5, HTML", &abbreviation)?,
+        "<p><abbr title=\"b\">HTML 5</abbr>, <abbr title=\"a\">HTML</abbr></p>",
+        "should prefer the longest match"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\nHTML html Html", &abbreviation)?,
+        "<p><abbr title=\"a\">HTML</abbr> html Html</p>",
+        "should match case-sensitively by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[HTML]: a\n*[html]: b\n\nHTML html Html",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        abbreviation: true,
+                        ..Constructs::default()
+                    },
+                    abbreviation_ignore_case: true,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><abbr title=\"a\">HTML</abbr> <abbr title=\"b\">html</abbr> <abbr title=\"a\">Html</abbr></p>",
+        "should support `abbreviation_ignore_case`, preferring exact matches"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[HTML]: a\n\n`HTML` <span title=\"HTML\">x</span> [HTML](b) ![HTML](c) <https://HTML.com> www.HTML.com",
+            &abbreviation
+        )?,
+        "<p><code>HTML</code> &lt;span title=&quot;HTML&quot;&gt;x&lt;/span&gt; <a href=\"b\">HTML</a> <img src=\"c\" alt=\"HTML\" /> <a href=\"https://HTML.com\">https://HTML.com</a> <a href=\"http://www.HTML.com\">www.HTML.com</a></p>",
+        "should not match in code, HTML, links, images, and autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\n[b HTML][]\n\n[b HTML]: c", &abbreviation)?,
+        "<p><a href=\"c\">b HTML</a></p>\n",
+        "should not match in references"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\n*HTML* **HTML** # HTML\n# HTML", &abbreviation)?,
+        "<p><em><abbr title=\"a\">HTML</abbr></em> <strong><abbr title=\"a\">HTML</abbr></strong> # <abbr title=\"a\">HTML</abbr></p>\n<h1><abbr title=\"a\">HTML</abbr></h1>",
+        "should match in other text"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\n&#72;TML H&#84;ML", &abbreviation)?,
+        "<p>HTML HTML</p>",
+        "should not match character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]: b &amp; \\\"c\\\"  \n\na", &abbreviation)?,
+        "<p><abbr title=\"b &amp; &quot;c&quot;\">a</abbr></p>",
+        "should support character escapes and references in titles, and ignore trailing whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]:\n\na", &abbreviation)?,
+        "<p><abbr>a</abbr></p>",
+        "should support definitions w/o title"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[a]:b\n\na", &abbreviation)?,
+        "<p><abbr title=\"b\">a</abbr></p>",
+        "should support definitions w/o whitespace after the marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[]: a\n*[ b]: c\n*[b ]: c\n*[b] : c", &abbreviation)?,
+        "<p>*[]: a\n*[ b]: c\n*[b ]: c\n*[b] : c</p>",
+        "should not support empty labels, whitespace around labels, or whitespace around markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("   *[a]: b\n\na", &abbreviation)?,
+        "<p><abbr title=\"b\">a</abbr></p>",
+        "should support up to three spaces of indent"
+    );
+
+    assert_eq!(
+        to_html_with_options("    *[a]: b\n\na", &abbreviation)?,
+        "<pre><code>*[a]: b\n</code></pre>\n<p>a</p>",
+        "should not support four spaces of indent"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n*[a]: b", &abbreviation)?,
+        "<p>a\n*[a]: b</p>",
+        "should not support a definition interrupting a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("> *[a]: b\n\n- *[c]: d\n\na c", &abbreviation)?,
+        "<blockquote>\n</blockquote>\n<ul>\n<li></li>\n</ul>\n<p><abbr title=\"b\">a</abbr> <abbr title=\"d\">c</abbr></p>",
+        "should support definitions in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("*[HTML]: a\n\nHTML[^1]\n\n[^1]: HTML", &abbreviation)?,
+        "<p><abbr title=\"a\">HTML</abbr><sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-1\">\n<p><abbr title=\"a\">HTML</abbr> <a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should match in footnote definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "*[HTML]: a\n\nHTML",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        abbreviation: true,
+                        ..Constructs::default()
+                    },
+                    plain_text: true,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>HTML</p>",
+        "should not match w/ `plain_text`"
+    );
+
+    assert_eq!(
+        to_mdast("*[HTML]: a\n\nb HTML c", &abbreviation.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "b HTML c".into(),
+                    position: Some(Position::new(3, 1, 12, 3, 9, 20))
+                }),],
+                position: Some(Position::new(3, 1, 12, 3, 9, 20))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 9, 20))
+        }),
+        "should support abbreviations as text in mdast"
+    );
+
+    Ok(())
+}
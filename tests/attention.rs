@@ -878,3 +878,126 @@ fn attention() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn attention_underscore() -> Result<(), message::Message> {
+    let asterisks = Options {
+        parse: ParseOptions {
+            attention_underscore: false,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("_a_ __b__ ___c___", &asterisks)?,
+        "<p>_a_ __b__ ___c___</p>",
+        "should not support emphasis or strong w/ underscores w/o `attention_underscore`"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a* **b** ***c***", &asterisks)?,
+        "<p><em>a</em> <strong>b</strong> <em><strong>c</strong></em></p>",
+        "should support emphasis and strong w/ asterisks w/o `attention_underscore`"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a_b* _c*d*_", &asterisks)?,
+        "<p><em>a_b</em> _c<em>d</em>_</p>",
+        "should treat underscores as data in and around asterisks w/o `attention_underscore`"
+    );
+
+    assert_eq!(
+        to_html_with_options("snake_case_words _snake_ case", &asterisks)?,
+        "<p>snake_case_words _snake_ case</p>",
+        "should not support underscores at the edges of words w/o `attention_underscore`"
+    );
+
+    assert_eq!(
+        to_html("snake_case_words _snake_ case"),
+        "<p>snake_case_words <em>snake</em> case</p>",
+        "should support underscores at the edges of words, but not inside them, by default"
+    );
+
+    assert_eq!(
+        to_mdast("_a_", &asterisks.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "_a_".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should support underscores as text in mdast w/o `attention_underscore`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn attention_underscore_intraword() -> Result<(), message::Message> {
+    let intraword = Options {
+        parse: ParseOptions {
+            attention_underscore_intraword: true,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("foo_bar_baz foo__bar__baz 5_6_78", &Options::gfm())?,
+        "<p>foo_bar_baz foo__bar__baz 5_6_78</p>",
+        "should not support underscores inside words by default (GFM)"
+    );
+
+    assert_eq!(
+        to_html_with_options("_foo_ __bar__ _foo_bar_baz_", &Options::gfm())?,
+        "<p><em>foo</em> <strong>bar</strong> <em>foo_bar_baz</em></p>",
+        "should support underscores at the edges of words by default (GFM)"
+    );
+
+    assert_eq!(
+        to_html_with_options("foo_bar_baz foo__bar__baz 5_6_78", &intraword)?,
+        "<p>foo<em>bar</em>baz foo<strong>bar</strong>baz 5<em>6</em>78</p>",
+        "should support underscores inside words w/ `attention_underscore_intraword`"
+    );
+
+    assert_eq!(
+        to_html_with_options("_foo_ __bar__ _foo_bar_baz_", &intraword)?,
+        "<p><em>foo</em> <strong>bar</strong> <em>foo</em>bar<em>baz</em></p>",
+        "should match underscores like asterisks w/ `attention_underscore_intraword`"
+    );
+
+    assert_eq!(
+        to_html_with_options("пристаням_стремятся_", &intraword)?,
+        "<p>пристаням<em>стремятся</em></p>",
+        "should support underscores after non-ASCII letters w/ `attention_underscore_intraword`"
+    );
+
+    assert_eq!(
+        to_html_with_options("foo*bar*baz *foo*bar*baz*", &Options::gfm())?,
+        to_html_with_options("foo*bar*baz *foo*bar*baz*", &intraword)?,
+        "should not change asterisks w/ `attention_underscore_intraword`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "foo_bar_baz",
+            &Options {
+                parse: ParseOptions {
+                    attention_underscore: false,
+                    attention_underscore_intraword: true,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>foo_bar_baz</p>",
+        "should not support underscores w/o `attention_underscore`"
+    );
+
+    Ok(())
+}
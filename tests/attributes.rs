@@ -0,0 +1,267 @@
+use markdown::{
+    mdast::{Heading, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast, toc,
+    unist::Position,
+    CompileOptions, Constructs, EventName, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+
+#[test]
+fn attributes() -> Result<(), message::Message> {
+    let attributes = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("## a {#b .c}\n\nd {.e}"),
+        "<h2>a {#b .c}</h2>\n<p>d {.e}</p>",
+        "should not support attributes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("## Heading {#custom-id .note}", &attributes)?,
+        "<h2 id=\"custom-id\" class=\"note\">Heading</h2>",
+        "should support attributes on headings (atx)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {.b}", &attributes)?,
+        "<p class=\"b\">a</p>",
+        "should support attributes on paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb {.c}\n===", &attributes)?,
+        "<h1 class=\"c\">a\nb</h1>",
+        "should support attributes on headings (setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {#b .c .d lang=f title=\"h i\" dir='k}'}", &attributes)?,
+        "<p id=\"b\" class=\"c d\" lang=\"f\" title=\"h i\" dir=\"k}\">a</p>",
+        "should support ids, classes, and other attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {#b #c lang=f lang=g}", &attributes)?,
+        "<p id=\"c\" lang=\"g\">a</p>",
+        "should prefer the last of other attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {title=\"&amp; &lt;\"}", &attributes)?,
+        "<p title=\"&amp; &lt;\">a</p>",
+        "should decode and encode character references in values"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a {onclick=alert(1)}\n\n## b {#c style=\"color:red\" data-d=e}",
+            &attributes
+        )?,
+        "<p>a</p>\n<h2 id=\"c\">b</h2>",
+        "should drop unsafe attributes by default"
+    );
+
+    let dangerous = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                attributes: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::default()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("a {#b e=f g=\"h i\"}", &dangerous)?,
+        "<p id=\"b\" e=\"f\" g=\"h i\">a</p>",
+        "should support other attributes w/ `allow_dangerous_html`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {onclick=alert(1) ONMOUSEOVER=b style=c}", &dangerous)?,
+        "<p>a</p>",
+        "should drop event handlers and styles, even w/ `allow_dangerous_html`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {b} c\n\n## a {b} c", &attributes)?,
+        "<p>a {b} c</p>\n<h2>a {b} c</h2>",
+        "should not support attributes followed by other text"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "Hello {name}\n\nf(x) {return}\n\nThe set {a b}\n\n## a {b}\n\nc {}\n\nd { }",
+            &attributes
+        )?,
+        "<p>Hello {name}</p>\n<p>f(x) {return}</p>\n<p>The set {a b}</p>\n<h2>a {b}</h2>\n<p>c {}</p>\n<p>d { }</p>",
+        "should not support braces in prose (keys w/o values, empty)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {lang=b c}\n\nd {e .f}", &attributes)?,
+        "<p>a {lang=b c}</p>\n<p>d {e .f}</p>",
+        "should not support keys w/o values next to valid attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {.b}\nc", &attributes)?,
+        "<p>a {.b}\nc</p>",
+        "should not support attributes followed by more lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a{.b}\n\n## a{.b}", &attributes)?,
+        "<p>a{.b}</p>\n<h2>a{.b}</h2>",
+        "should not support attributes w/o whitespace before"
+    );
+
+    assert_eq!(
+        to_html_with_options("{.b}\n\na\n{.b}", &attributes)?,
+        "<p>{.b}</p>\n<p>a\n{.b}</p>",
+        "should not support attributes at the start of a line in paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {<}\n\na {.b c}d}\n\na { b\n\na {.}", &attributes)?,
+        "<p>a {&lt;}</p>\n<p>a {.b c}d}</p>\n<p>a { b</p>\n<p>a {.}</p>",
+        "should not support invalid attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {.b}  \n\n## c {.d}  ", &attributes)?,
+        "<p class=\"b\">a</p>\n<h2 class=\"d\">c</h2>",
+        "should support whitespace after attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a {.b} {.c}", &attributes)?,
+        "<p class=\"c\">a {.b}</p>",
+        "should only use the last attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("## a {.b} ##\n\n## a {.b}##\n\n## a {.b} # #", &attributes)?,
+        "<h2 class=\"b\">a</h2>\n<h2>a {.b}##</h2>\n<h2>a {.b}</h2>",
+        "should support attributes before a closing sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("## {#a}", &attributes)?,
+        "<h2 id=\"a\"></h2>",
+        "should support attributes w/o heading text"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `{.b}`\n\na *b* {.c}", &attributes)?,
+        "<p>a <code>{.b}</code></p>\n<p class=\"c\">a <em>b</em></p>",
+        "should support text constructs before attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a {.b}\n\n- c {.d}\n\n- e\n\n  f {.g}", &attributes)?,
+        "<blockquote>\n<p class=\"b\">a</p>\n</blockquote>\n<ul>\n<li>\n<p class=\"d\">c</p>\n</li>\n<li>\n<p>e</p>\n<p class=\"g\">f</p>\n</li>\n</ul>",
+        "should support attributes in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a {.b}", &attributes)?,
+        "<ul>\n<li>a</li>\n</ul>",
+        "should drop attributes of paragraphs in tight lists"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "## a {#b}\n\n## a\n\n## c {.d}",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<h2 id=\"b\">a</h2>\n<h2 id=\"a-1\">a</h2>\n<h2 id=\"c\" class=\"d\">c</h2>",
+        "should prefer ids from attributes over `heading_ids`"
+    );
+
+    let mut class_map = BTreeMap::new();
+    class_map.insert(EventName::Paragraph, "a".into());
+
+    assert_eq!(
+        to_html_with_options(
+            "b {.c}",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    class_map,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<p class=\"a c\">b</p>",
+        "should merge classes with `class_map`"
+    );
+
+    assert_eq!(
+        toc("## a {#b}\n\nc {#d}\n-", &attributes.parse)?
+            .into_iter()
+            .map(|d| d.id)
+            .collect::<Vec<_>>(),
+        vec!["b".to_string(), "d".to_string()],
+        "should use ids from attributes in `toc`"
+    );
+
+    assert_eq!(
+        to_mdast("## a {#b}\n\nc {.d}", &attributes.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Heading(Heading {
+                    depth: 2,
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 4, 3, 1, 5, 4))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 10, 9))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "c".into(),
+                        position: Some(Position::new(3, 1, 11, 3, 2, 12))
+                    })],
+                    position: Some(Position::new(3, 1, 11, 3, 7, 17))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 3, 7, 17))
+        }),
+        "should drop attributes in mdast"
+    );
+
+    Ok(())
+}
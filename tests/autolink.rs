@@ -323,3 +323,144 @@ fn autolink() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn autolink_protocols() -> Result<(), message::Message> {
+    let web = Options {
+        parse: ParseOptions {
+            autolink_protocols: Some(vec!["http".into(), "https".into(), "mailto".into()]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("<tel:123> <x-y:z>"),
+        "<p><a href=\"\">tel:123</a> <a href=\"\">x-y:z</a></p>",
+        "should allow all protocols by default (but not in `href`s, unless dangerous)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b> <http://c.d> <mailto:e@f.g>", &web)?,
+        "<p><a href=\"https://a.b\">https://a.b</a> <a href=\"http://c.d\">http://c.d</a> <a href=\"mailto:e@f.g\">mailto:e@f.g</a></p>",
+        "should support allowed protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("<tel:123>", &web)?,
+        "<p>&lt;tel:123&gt;</p>",
+        "should not support other protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("<HTTPS://a.b>", &web)?,
+        "<p><a href=\"HTTPS://a.b\">HTTPS://a.b</a></p>",
+        "should compare protocols case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options("<httpss://a.b> <http:>", &web)?,
+        "<p>&lt;httpss://a.b&gt; <a href=\"http:\">http:</a></p>",
+        "should compare whole protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("<a@b.c>", &web)?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a></p>",
+        "should support email autolinks, which are not affected"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<a@b.c>",
+            &Options {
+                parse: ParseOptions {
+                    autolink_protocols: Some(vec![]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a></p>",
+        "should support email autolinks w/o any allowed protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]: <tel:1>\n\n[a] *<tel:1>*", &web)?,
+        "<p><a href=\"\">a</a> <em>&lt;tel:1&gt;</em></p>",
+        "should not affect other links"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn autolink_non_ascii() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("<https://例え.jp>"),
+        "<p><a href=\"https://%E4%BE%8B%E3%81%88.jp\">https://例え.jp</a></p>",
+        "should support internationalized domain names"
+    );
+
+    assert_eq!(
+        to_html("<https://例え.jp/パス?q=値#片>"),
+        "<p><a href=\"https://%E4%BE%8B%E3%81%88.jp/%E3%83%91%E3%82%B9?q=%E5%80%A4#%E7%89%87\">https://例え.jp/パス?q=値#片</a></p>",
+        "should support non-ASCII paths, queries, and fragments"
+    );
+
+    assert_eq!(
+        to_html("<https://例え.jp/\u{3000}>"),
+        "<p><a href=\"https://%E4%BE%8B%E3%81%88.jp/%E3%80%80\">https://例え.jp/\u{3000}</a></p>",
+        "should support non-ASCII whitespace"
+    );
+
+    assert_eq!(
+        to_html("<https://例え.jp/ a>"),
+        "<p>&lt;https://例え.jp/ a&gt;</p>",
+        "should not support ASCII whitespace"
+    );
+
+    assert_eq!(
+        to_html("<例://a>"),
+        "<p>&lt;例://a&gt;</p>",
+        "should not support non-ASCII in protocols"
+    );
+
+    assert_eq!(
+        to_html("<ü@b.c>"),
+        "<p>&lt;ü@b.c&gt;</p>",
+        "should not support non-ASCII in email autolinks"
+    );
+
+    assert_eq!(
+        to_mdast("a <https://例え.jp> b", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Link(Link {
+                        url: "https://例え.jp".into(),
+                        title: None,
+                        children: vec![Node::Text(Text {
+                            value: "https://例え.jp".into(),
+                            position: Some(Position::new(1, 4, 3, 1, 21, 20))
+                        }),],
+                        position: Some(Position::new(1, 3, 2, 1, 22, 21))
+                    }),
+                    Node::Text(Text {
+                        value: " b".into(),
+                        position: Some(Position::new(1, 22, 21, 1, 24, 23))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 24, 23))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 24, 23))
+        }),
+        "should support positions (in bytes) around non-ASCII autolinks"
+    );
+
+    Ok(())
+}
@@ -238,3 +238,213 @@ fn block_quote() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn block_quote_lazy() -> Result<(), message::Message> {
+    let strict = Options {
+        parse: ParseOptions {
+            block_quote_lazy: false,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("> a\nb", &strict)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>",
+        "should not support lazy paragraph lines w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> b\nc\n> d", &strict)?,
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>\n<p>c</p>\n<blockquote>\n<p>d</p>\n</blockquote>",
+        "should still support non-lazy paragraph lines w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n===", &strict)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>===</p>",
+        "should not turn a lazy line into a setext underline w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n---", &strict)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<hr />",
+        "should support a thematic break after a block quote w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("* > a\n  ---", &strict)?,
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\n<hr />\n</li>\n</ul>",
+        "should support a thematic break after a block quote in a list item w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n    b", &strict)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<pre><code>b\n</code></pre>",
+        "should support indented code after a block quote w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options(">> a\n> b\nc", &strict)?,
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>\n</blockquote>\n<p>c</p>",
+        "should close nested block quotes w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\nb", &strict)?,
+        "<ul>\n<li>a\nb</li>\n</ul>",
+        "should still support lazy lines in list items w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> * a\n> b", &strict)?,
+        "<blockquote>\n<ul>\n<li>a\nb</li>\n</ul>\n</blockquote>",
+        "should still support lazy lines in list items in block quotes w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("* > a\nb", &strict)?,
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\n</li>\n</ul>\n<p>b</p>",
+        "should not support lazy lines in block quotes in list items w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("* > a\n  b", &strict)?,
+        "<ul>\n<li>\n<blockquote>\n<p>a</p>\n</blockquote>\nb</li>\n</ul>",
+        "should keep list items that continue, around block quotes that do not, w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\nb", &strict)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>b</p>",
+        "should support blank lines after block quotes w/o `block_quote_lazy`"
+    );
+
+    assert_eq!(
+        to_mdast("> a\nb", &strict.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::BlockQuote(BlockQuote {
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "a".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        }),],
+                        position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(2, 1, 4, 2, 2, 5))
+                    }),],
+                    position: Some(Position::new(2, 1, 4, 2, 2, 5))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 2, 2, 5))
+        }),
+        "should support positional info w/o `block_quote_lazy`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn block_quote_nested_lazy() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("> > a\nb"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> > b\n> c\nd"),
+        "<blockquote>\n<blockquote>\n<p>a\nb\nc\nd</p>\n</blockquote>\n</blockquote>",
+        "should support lazy lines that continue fewer block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\nb\n> > c"),
+        "<blockquote>\n<blockquote>\n<p>a\nb\nc</p>\n</blockquote>\n</blockquote>",
+        "should support non-lazy lines after lazy lines"
+    );
+
+    assert_eq!(
+        to_html("> a\n> > b\nc\n> d"),
+        "<blockquote>\n<p>a</p>\n<blockquote>\n<p>b\nc\nd</p>\n</blockquote>\n</blockquote>",
+        "should attach lazy lines to the deepest paragraph"
+    );
+
+    assert_eq!(
+        to_html("> > - a\nb"),
+        "<blockquote>\n<blockquote>\n<ul>\n<li>a\nb</li>\n</ul>\n</blockquote>\n</blockquote>",
+        "should support lazy lines in list items in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n    b"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n</blockquote>",
+        "should support indented lazy lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\nb\n==="),
+        "<blockquote>\n<blockquote>\n<p>a\nb\n===</p>\n</blockquote>\n</blockquote>",
+        "should not support lazy setext heading underlines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> b\n> ---"),
+        "<blockquote>\n<blockquote>\n<p>a\nb</p>\n</blockquote>\n<hr />\n</blockquote>",
+        "should not support setext heading underlines that continue fewer block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n> ***\n\n> > c\n> # d"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n<hr />\n</blockquote>\n<blockquote>\n<blockquote>\n<p>c</p>\n</blockquote>\n<h1>d</h1>\n</blockquote>",
+        "should close inner block quotes before other constructs in outer block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n- b"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n<ul>\n<li>b</li>\n</ul>",
+        "should not support lazy lists after nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > ```\na"),
+        "<blockquote>\n<blockquote>\n<pre><code></code></pre>\n</blockquote>\n</blockquote>\n<p>a</p>",
+        "should not support lazy lines in fenced code in nested block quotes"
+    );
+
+    assert_eq!(
+        to_html("> > a\n>\nb\n\n> > c\n\n> d"),
+        "<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n<p>b</p>\n<blockquote>\n<blockquote>\n<p>c</p>\n</blockquote>\n</blockquote>\n<blockquote>\n<p>d</p>\n</blockquote>",
+        "should not support lazy lines after blank lines in nested block quotes"
+    );
+
+    assert_eq!(
+        to_mdast("> > a\nb", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::BlockQuote(BlockQuote {
+                children: vec![Node::BlockQuote(BlockQuote {
+                    children: vec![Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "a\nb".into(),
+                            position: Some(Position::new(1, 5, 4, 2, 2, 7))
+                        }),],
+                        position: Some(Position::new(1, 5, 4, 2, 2, 7))
+                    })],
+                    position: Some(Position::new(1, 3, 2, 2, 2, 7))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 2, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 2, 7))
+        }),
+        "should include lazy lines in the positions of nested block quotes"
+    );
+
+    Ok(())
+}
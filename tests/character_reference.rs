@@ -231,3 +231,121 @@ fn character_reference() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn character_reference_boundaries() {
+    assert_eq!(
+        to_html("&#xD800; &#xDBFF; &#xDC00; &#xDFFF;"),
+        "<p>\u{FFFD} \u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace lone surrogates"
+    );
+
+    assert_eq!(
+        to_html("&#xD7FF; &#xE000;"),
+        "<p>\u{D7FF} \u{E000}</p>",
+        "should support code points around surrogates"
+    );
+
+    assert_eq!(
+        to_html("&#x10FFFD; &#1114109; &#x110000; &#1114112;"),
+        "<p>\u{10FFFD} \u{10FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace code points above U+10FFFF"
+    );
+
+    assert_eq!(
+        to_html("&#xFDD0; &#xFDEF; &#xFFFE; &#xFFFF; &#x1FFFE; &#x10FFFF;"),
+        "<p>\u{FFFD} \u{FFFD} \u{FFFD} \u{FFFD} \u{FFFD} \u{FFFD}</p>",
+        "should replace noncharacters"
+    );
+
+    assert_eq!(
+        to_html("&#xFDCF; &#xFDF0; &#xFFFD;"),
+        "<p>\u{FDCF} \u{FDF0} \u{FFFD}</p>",
+        "should support code points around noncharacters"
+    );
+
+    assert_eq!(
+        to_html("&#8; &#9; &#31; &#127; &#159; &#160;"),
+        "<p>\u{FFFD} \t \u{FFFD} \u{FFFD} \u{FFFD} \u{A0}</p>",
+        "should replace control characters, except for whitespace"
+    );
+
+    assert_eq!(
+        to_html("&#0000035; &#x00023;"),
+        "<p># #</p>",
+        "should support leading zeros, up to the size limit"
+    );
+
+    assert_eq!(
+        to_html("&AElig; &zwnj;"),
+        "<p>\u{C6} \u{200C}</p>",
+        "should support the first and last named references"
+    );
+
+    assert_eq!(
+        to_html("&CounterClockwiseContourIntegral;"),
+        "<p>\u{2233}</p>",
+        "should support the longest named reference"
+    );
+
+    assert_eq!(
+        to_html("&aelig &amp &copy &#35 &#x23"),
+        "<p>&amp;aelig &amp;amp &amp;copy &amp;#35 &amp;#x23</p>",
+        "should not support references w/o semicolon"
+    );
+
+    assert_eq!(
+        to_html("&#99999999; &#x1234567;"),
+        "<p>&amp;#99999999; &amp;#x1234567;</p>",
+        "should not support numeric references that are too long"
+    );
+}
+
+#[test]
+fn character_reference_preserve() -> Result<(), message::Message> {
+    let preserve = Options {
+        compile: CompileOptions {
+            preserve_character_references: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("&copy; &AElig; &amp; &lt;", &preserve)?,
+        "<p>&copy; &AElig; &amp; &lt;</p>",
+        "should preserve named character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("&#169; &#0; &#X22; &#xcab;", &preserve)?,
+        "<p>&#169; &#0; &#X22; &#xcab;</p>",
+        "should preserve numeric character references as written"
+    );
+
+    assert_eq!(
+        to_html_with_options("&x; &#; & < \\&copy;", &preserve)?,
+        "<p>&amp;x; &amp;#; &amp; &lt; &amp;copy;</p>",
+        "should still encode other ampersands"
+    );
+
+    assert_eq!(
+        to_html_with_options("*&copy;* [&copy;](a \"&copy;\") ![&copy;](b)", &preserve)?,
+        "<p><em>&copy;</em> <a href=\"a\" title=\"©\">&copy;</a> <img src=\"b\" alt=\"&copy;\" /></p>",
+        "should preserve character references in text and image alts, but not titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](&#x2F;b)", &preserve)?,
+        "<p><a href=\"/b\">a</a></p>",
+        "should decode character references in destinations"
+    );
+
+    assert_eq!(
+        to_html_with_options("`&copy;`\n\n    &copy;", &preserve)?,
+        "<p><code>&amp;copy;</code></p>\n<pre><code>&amp;copy;\n</code></pre>",
+        "should not affect code"
+    );
+
+    Ok(())
+}
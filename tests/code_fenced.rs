@@ -1,10 +1,13 @@
 use markdown::{
+    fence_info,
     mdast::{Code, Node, Root},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    CodeInfo, CompileOptions, Constructs, EventKind, EventName, Highlight, HighlightContext,
+    LineEnding, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
+use std::rc::Rc;
 
 #[test]
 fn code_fenced() -> Result<(), message::Message> {
@@ -183,6 +186,30 @@ fn code_fenced() -> Result<(), message::Message> {
     );
 
     // Our own:
+    assert_eq!(
+        to_html("```js`x\nfoo\n```"),
+        "<p>```js`x\nfoo</p>\n<pre><code></code></pre>\n",
+        "should not support a grave accent in the info string of grave accent fenced code"
+    );
+
+    assert_eq!(
+        to_html("````js a`b\nfoo\n````"),
+        "<p>````js a`b\nfoo</p>\n<pre><code></code></pre>\n",
+        "should not support a grave accent in the meta string of grave accent fenced code"
+    );
+
+    assert_eq!(
+        to_html("~~~js`x\nfoo\n~~~"),
+        "<pre><code class=\"language-js`x\">foo\n</code></pre>",
+        "should support a grave accent in the info string of tilde fenced code"
+    );
+
+    assert_eq!(
+        to_html("```\n```js`x\n```"),
+        "<pre><code>```js`x\n</code></pre>",
+        "should support grave accents in the content of grave accent fenced code"
+    );
+
     assert_eq!(
         to_html("```  "),
         "<pre><code></code></pre>\n",
@@ -340,3 +367,325 @@ fn code_fenced() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn code_fenced_language() -> Result<(), message::Message> {
+    let lang = Options {
+        compile: CompileOptions {
+            code_language_prefix: Some("lang-".into()),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    let bare = Options {
+        compile: CompileOptions {
+            code_language_prefix: Some(String::new()),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    let whole = Options {
+        compile: CompileOptions {
+            code_language_transform: Some(Rc::new(|info: CodeInfo| {
+                Some(if let Some(meta) = info.meta {
+                    format!("{} {}", info.language, meta)
+                } else {
+                    info.language
+                })
+            })),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    let drop = Options {
+        compile: CompileOptions {
+            code_language_transform: Some(Rc::new(|info: CodeInfo| {
+                if info.language == "text" {
+                    None
+                } else {
+                    Some(info.language)
+                }
+            })),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("```rust,no_run extra\na\n```"),
+        "<pre><code class=\"language-rust,no_run\">a\n</code></pre>",
+        "should use the first word of the info string as the language by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```rust\na\n```", &lang)?,
+        "<pre><code class=\"lang-rust\">a\n</code></pre>",
+        "should support `code_language_prefix`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```rust\na\n```", &bare)?,
+        "<pre><code class=\"rust\">a\n</code></pre>",
+        "should support an empty `code_language_prefix`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\n```", &lang)?,
+        "<pre><code>a\n</code></pre>",
+        "should not add a class w/o info string"
+    );
+
+    assert_eq!(
+        to_html_with_options("``` \na\n```", &whole)?,
+        "<pre><code>a\n</code></pre>",
+        "should not call `code_language_transform` w/o info string"
+    );
+
+    assert_eq!(
+        to_html_with_options("```rust no_run\na\n```", &whole)?,
+        "<pre><code class=\"language-rust no_run\">a\n</code></pre>",
+        "should pass the meta to `code_language_transform`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```a&amp;b c&quot;d\n```", &whole)?,
+        "<pre><code class=\"language-a&amp;b c&quot;d\"></code></pre>",
+        "should pass decoded values to `code_language_transform`, and encode the result"
+    );
+
+    assert_eq!(
+        to_html_with_options("```text\na\n```\n\n```js\nb\n```", &drop)?,
+        "<pre><code>a\n</code></pre>\n<pre><code class=\"language-js\">b\n</code></pre>",
+        "should support dropping the class from `code_language_transform`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$$\na\n$$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        math_flow: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..lang
+            }
+        )?,
+        "<pre><code class=\"language-math math-display\">a\n</code></pre>",
+        "should not affect math (flow)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_highlight() -> Result<(), message::Message> {
+    let debug = Options {
+        compile: CompileOptions {
+            code_highlight: Some(Rc::new(|code: HighlightContext| {
+                Some(Highlight::Body(format!("{:?}", code)))
+            })),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    let whole = Options {
+        compile: CompileOptions {
+            code_highlight: Some(Rc::new(|code: HighlightContext| {
+                if code.language.as_deref() == Some("x") {
+                    Some(Highlight::Whole(format!("<x-code>{}</x-code>", code.value)))
+                } else {
+                    None
+                }
+            })),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("```rust no_run\na < b && c\r\n  d\n```", &debug)?,
+        "<pre><code class=\"language-rust\">HighlightContext { language: Some(\"rust\"), meta: Some(\"no_run\"), value: \"a < b && c\\r\\n  d\\n\" }</code></pre>",
+        "should pass the language, meta, and raw code w/ line endings to `code_highlight`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```", &debug)?,
+        "<pre><code>HighlightContext { language: None, meta: None, value: \"\" }</code></pre>",
+        "should call `code_highlight` w/o info string and w/o code"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n```x\n<b>\n```\n\nc", &whole)?,
+        "<p>a</p>\n<x-code><b>\n</x-code>\n<p>c</p>",
+        "should support replacing the whole code with `Highlight::Whole`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```y\n<b>\n```", &whole)?,
+        "<pre><code class=\"language-y\">&lt;b&gt;\n</code></pre>",
+        "should compile code as normal when `code_highlight` returns `None`"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```x\n> a\n\nb", &whole)?,
+        "<blockquote>\n<x-code>a\n</x-code>\n</blockquote>\n<p>b</p>",
+        "should support `code_highlight` in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("    <b>", &whole)?,
+        "<pre><code>&lt;b&gt;\n</code></pre>",
+        "should not call `code_highlight` for code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```x\na\nb\n```",
+            &Options {
+                compile: CompileOptions {
+                    line_ending: Some(LineEnding::CarriageReturnLineFeed),
+                    ..whole.compile.clone()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<x-code>a\r\nb\r\n</x-code>",
+        "should pass configured line endings to `code_highlight`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_trim_final_line_ending() -> Result<(), message::Message> {
+    let trim = Options {
+        compile: CompileOptions {
+            code_trim_final_line_ending: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("```\na\n\n```"),
+        "<pre><code>a\n\n</code></pre>",
+        "should keep the final line ending and trailing blank lines by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\nb\n```", &trim)?,
+        "<pre><code>a\nb</code></pre>",
+        "should support `code_trim_final_line_ending`"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\n\n```", &trim)?,
+        "<pre><code>a\n</code></pre>",
+        "should drop only one line ending w/ trailing blank lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\r\na\r\n```", &trim)?,
+        "<pre><code>a</code></pre>",
+        "should drop a final carriage return + line feed"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na", &trim)?,
+        "<pre><code>a</code></pre>\n",
+        "should support `code_trim_final_line_ending` w/o closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\n```", &trim)?,
+        "<pre><code></code></pre>",
+        "should support `code_trim_final_line_ending` w/o code"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ```\n> a\n\nb", &trim)?,
+        "<blockquote>\n<pre><code>a</code></pre>\n</blockquote>\n<p>b</p>",
+        "should support `code_trim_final_line_ending` in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```x\na\n```",
+            &Options {
+                compile: CompileOptions {
+                    code_trim_final_line_ending: true,
+                    code_highlight: Some(Rc::new(|code: HighlightContext| {
+                        Some(Highlight::Body(format!("{:?}", code.value)))
+                    })),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<pre><code class=\"language-x\">\"a\"</code></pre>",
+        "should pass trimmed code to `code_highlight`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn code_fenced_fence_info() -> Result<(), message::Message> {
+    let infos = |value: &str| -> Result<Vec<(char, usize, Option<usize>)>, message::Message> {
+        let events = to_events(
+            value,
+            &ParseOptions {
+                constructs: Constructs {
+                    math_flow: true,
+                    ..Constructs::default()
+                },
+                ..ParseOptions::default()
+            },
+        )?;
+        let mut infos = vec![];
+        let mut index = 0;
+
+        while index < events.len() {
+            if events[index].kind == EventKind::Enter
+                && matches!(
+                    events[index].name,
+                    EventName::CodeFenced | EventName::MathFlow
+                )
+            {
+                let info = fence_info(value, &events, index);
+                infos.push((info.marker, info.size, info.closing_size));
+            }
+
+            index += 1;
+        }
+
+        Ok(infos)
+    };
+
+    assert_eq!(
+        infos("```js\na\n```\n\n~~~~\nb\n~~~~~~\n\n$$\nc\n$$")?,
+        vec![('`', 3, Some(3)), ('~', 4, Some(6)), ('$', 2, Some(2))],
+        "should support the markers and sizes of fences"
+    );
+
+    assert_eq!(
+        infos("> ```\n> a\n\n`````\n````")?,
+        vec![('`', 3, None), ('`', 5, None)],
+        "should not have a closing size w/o closing fence"
+    );
+
+    assert_eq!(
+        infos("```js`x\n```")?,
+        vec![('`', 3, None)],
+        "should not treat invalid opening fences as code"
+    );
+
+    Ok(())
+}
@@ -202,6 +202,78 @@ fn code_indented() -> Result<(), message::Message> {
         "should support turning off code (indented, 9)"
     );
 
+    assert_eq!(
+        to_html_with_options("a\n    b", &off)?,
+        "<p>a\nb</p>",
+        "should support turning off code (indented, 10)"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n    b\n\n    c", &off)?,
+        "<p>a\nb</p>\n<p>c</p>",
+        "should support turning off code (indented, 11)"
+    );
+
+    assert_eq!(
+        to_html_with_options("\ta", &off)?,
+        "<p>a</p>",
+        "should support turning off code (indented, 12)"
+    );
+
+    assert_eq!(
+        to_html_with_options("    # a\n    > b\n    ---", &off)?,
+        "<h1>a</h1>\n<blockquote>\n<p>b</p>\n</blockquote>\n<hr />",
+        "should support turning off code (indented, 13)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n        b", &off)?,
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should support turning off code (indented, 14)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n\t\tb", &off)?,
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should support turning off code (indented, 15)"
+    );
+
+    assert_eq!(
+        to_html_with_options("1. a\n\n            b", &off)?,
+        "<ol>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>",
+        "should support turning off code (indented, 16)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n  - b\n    - c\n\n              d", &off)?,
+        "<ul>\n<li>a\n<ul>\n<li>b\n<ul>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ul>\n</li>\n</ul>\n</li>\n</ul>",
+        "should support turning off code (indented, 17)"
+    );
+
+    assert_eq!(
+        to_html_with_options("-     a", &off)?,
+        "<ul>\n<li>a</li>\n</ul>",
+        "should support turning off code (indented, 18)"
+    );
+
+    assert_eq!(
+        to_html_with_options("-\n        a", &off)?,
+        "<ul>\n<li>a</li>\n</ul>",
+        "should support turning off code (indented, 19)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n      - b", &off)?,
+        "<ul>\n<li>\n<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n</li>\n</ul>",
+        "should support turning off code (indented, 20)"
+    );
+
+    assert_eq!(
+        to_html_with_options(">     a", &off)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>",
+        "should support turning off code (indented, 21)"
+    );
+
     assert_eq!(
         to_mdast(
             "\tconsole.log(1)\n    console.log(2)\n",
@@ -221,3 +293,40 @@ fn code_indented() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn code_indented_trim_final_line_ending() -> Result<(), message::Message> {
+    let trim = Options {
+        compile: CompileOptions {
+            code_trim_final_line_ending: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("    a\n    \n\nb"),
+        "<pre><code>a\n</code></pre>\n<p>b</p>",
+        "should keep the final line ending, but not trailing blank lines, by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n    b", &trim)?,
+        "<pre><code>a\nb</code></pre>",
+        "should support `code_trim_final_line_ending`"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n    \n\nb", &trim)?,
+        "<pre><code>a</code></pre>\n<p>b</p>",
+        "should support `code_trim_final_line_ending` w/ trailing blank lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("    a\n\n    b\n", &trim)?,
+        "<pre><code>a\n\nb</code></pre>\n",
+        "should keep blank lines inside code"
+    );
+
+    Ok(())
+}
@@ -237,3 +237,91 @@ fn code_text() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn code_text_strip_space() -> Result<(), message::Message> {
+    let keep = Options {
+        parse: ParseOptions {
+            code_text_strip_space: false,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("` `` `"),
+        "<p><code>``</code></p>",
+        "should strip a space around a backtick run of a different size"
+    );
+
+    assert_eq!(
+        to_html("`` ` ``"),
+        "<p><code>`</code></p>",
+        "should strip a space around a single backtick"
+    );
+
+    assert_eq!(
+        to_html("``` `` ` ```"),
+        "<p><code>`` `</code></p>",
+        "should strip a space around several backtick runs"
+    );
+
+    assert_eq!(
+        to_html("` `"),
+        "<p><code> </code></p>",
+        "should not strip a single space"
+    );
+
+    assert_eq!(
+        to_html("`  `"),
+        "<p><code>  </code></p>",
+        "should not strip two spaces"
+    );
+
+    assert_eq!(
+        to_html("`  a  `"),
+        "<p><code> a </code></p>",
+        "should strip only one space from each side"
+    );
+
+    assert_eq!(
+        to_html("`\ta\t`"),
+        "<p><code>\ta\t</code></p>",
+        "should not strip tabs"
+    );
+
+    assert_eq!(
+        to_html("`\na\n`"),
+        "<p><code>a</code></p>",
+        "should strip line endings, as they are turned into spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("` `` `", &keep)?,
+        "<p><code> `` </code></p>",
+        "should not strip spaces w/ `code_text_strip_space: false`"
+    );
+
+    assert_eq!(
+        to_html_with_options("`\na\n`", &keep)?,
+        "<p><code> a </code></p>",
+        "should not strip line endings w/ `code_text_strip_space: false`"
+    );
+
+    assert_eq!(
+        to_mdast("` `` `", &keep.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::InlineCode(InlineCode {
+                    value: " `` ".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 7, 6))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 7, 6))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 7, 6))
+        }),
+        "should not strip spaces from `InlineCode` w/ `code_text_strip_space: false`"
+    );
+
+    Ok(())
+}
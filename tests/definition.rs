@@ -1,10 +1,12 @@
 use markdown::{
+    definition_labels, definitions, definitions_with_usage,
     mdast::{Definition, Node, Root},
-    message, to_html, to_html_with_options, to_mdast,
+    message, normalize_identifier, to_html, to_html_with_options, to_mdast,
     unist::Position,
     CompileOptions, Constructs, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
 
 #[test]
 fn definition() -> Result<(), message::Message> {
@@ -489,6 +491,66 @@ fn definition() -> Result<(), message::Message> {
         "should prefer code (fenced) over definition titles"
     );
 
+    assert_eq!(
+        to_html("[a]: b\n\"c\"\n\n[a]"),
+        "<p><a href=\"b\" title=\"c\">a</a></p>",
+        "should support a title on the next line"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n\"c\nd\"\n\n[a]"),
+        "<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a multiline title on the next line (double quotes)"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n'c\nd'\n\n[a]"),
+        "<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a multiline title on the next line (single quotes)"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n(c\nd)\n\n[a]"),
+        "<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a multiline title on the next line (parens)"
+    );
+
+    assert_eq!(
+        to_html("[a]:\nb\n  \"c\n  d\"\n\n[a]"),
+        "<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a destination and an indented multiline title on next lines"
+    );
+
+    assert_eq!(
+        to_html("> [a]: b\n> \"c\n> d\"\n\n[a]"),
+        "<blockquote>\n</blockquote>\n<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a multiline title on the next line in a block quote"
+    );
+
+    assert_eq!(
+        to_html("* [a]: b\n  \"c\n  d\"\n\n[a]"),
+        "<ul>\n<li></li>\n</ul>\n<p><a href=\"b\" title=\"c\nd\">a</a></p>",
+        "should support a multiline title on the next line in a list item"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n\"c\nd\n\n[a]"),
+        "<p>&quot;c\nd</p>\n<p><a href=\"b\">a</a></p>",
+        "should support a definition w/o title if a title on the next line is not closed"
+    );
+
+    assert_eq!(
+        to_html("[a]: b\n\"c\n\nd\"\n\n[a]"),
+        "<p>&quot;c</p>\n<p>d&quot;</p>\n<p><a href=\"b\">a</a></p>",
+        "should support a definition w/o title if a title on the next line contains a blank line"
+    );
+
+    assert_eq!(
+        to_html("[a]: b \"c\n\nd\"\n\n[a]"),
+        "<p>[a]: b &quot;c</p>\n<p>d&quot;</p>\n<p>[a]</p>",
+        "should not support a definition if a title on the same line contains a blank line"
+    );
+
     assert_eq!(
         to_html_with_options(
             "[foo]: /url \"title\"",
@@ -522,5 +584,215 @@ fn definition() -> Result<(), message::Message> {
         "should support definitions as `Definition`s in mdast"
     );
 
+    assert_eq!(
+        to_mdast("[a]: b\n\"c\nd\"", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Definition(Definition {
+                url: "b".into(),
+                identifier: "a".into(),
+                label: Some("a".into()),
+                title: Some("c\nd".into()),
+                position: Some(Position::new(1, 1, 0, 3, 3, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 3, 12))
+        }),
+        "should support multiline titles on the next line in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definitions_api() -> Result<(), message::Message> {
+    assert_eq!(
+        definitions("a", &ParseOptions::default())?,
+        vec![],
+        "should support no definitions"
+    );
+
+    assert_eq!(
+        definitions("[a]: <b> 'c'\n\n> [d]: e", &ParseOptions::default())?,
+        vec![
+            Definition {
+                url: "b".into(),
+                identifier: "a".into(),
+                label: Some("a".into()),
+                title: Some("c".into()),
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            },
+            Definition {
+                url: "e".into(),
+                identifier: "d".into(),
+                label: Some("d".into()),
+                title: None,
+                position: Some(Position::new(3, 3, 16, 3, 9, 22))
+            }
+        ],
+        "should support definitions, also in containers"
+    );
+
+    assert_eq!(
+        definitions("[a]: b\n[A]: c\n[ a ]: d", &ParseOptions::default())?
+            .iter()
+            .map(|d| d.url.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b"],
+        "should keep the first of several definitions with the same identifier"
+    );
+
+    assert_eq!(
+        definitions("[Foo\n  *Bar*&amp;]: a", &ParseOptions::default())?
+            .iter()
+            .map(|d| (d.identifier.as_str(), d.label.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("foo *bar*&amp;", Some("Foo\n*Bar*&"))],
+        "should support normalized identifiers and raw labels"
+    );
+
+    assert_eq!(
+        definitions("[a]: <b&amp;c> '\\'d'", &ParseOptions::default())?
+            .iter()
+            .map(|d| (d.url.as_str(), d.title.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![("b&c", Some("'d"))],
+        "should decode character references and escapes in destinations and titles"
+    );
+
+    assert_eq!(
+        definitions(
+            "[a]\n\n[a]: b\n\n    [c]: d\n\n```\n[e]: f\n```",
+            &ParseOptions::default()
+        )?
+        .iter()
+        .map(|d| d.identifier.as_str())
+        .collect::<Vec<_>>(),
+        vec!["a"],
+        "should not see definitions in code"
+    );
+
+    let gfm = Options::gfm();
+
+    assert_eq!(
+        to_html_with_options("[a]: b\n\n[^c]: d", &gfm)?,
+        "",
+        "should support definitions in a first document"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a] [^c]", &gfm)?,
+        "<p>[a] [^c]</p>",
+        "should not leak definitions into later documents w/ the same options"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definitions_with_usage_api() -> Result<(), message::Message> {
+    let usage = |value: &str| -> Result<Vec<(String, usize)>, message::Message> {
+        Ok(definitions_with_usage(value, &ParseOptions::default())?
+            .into_iter()
+            .map(|(definition, count)| (definition.identifier, count))
+            .collect())
+    };
+
+    assert_eq!(
+        usage("[a]: b")?,
+        vec![("a".into(), 0)],
+        "should support unused definitions"
+    );
+
+    assert_eq!(
+        usage("[a] [b][a] [c][] ![a] ![A]\n\n[a]: b\n[c]: d")?,
+        vec![("a".into(), 4), ("c".into(), 1)],
+        "should count full, collapsed, and shortcut references, of links and images"
+    );
+
+    assert_eq!(
+        usage("[A  B]\n\n[a b]: c")?,
+        vec![("a b".into(), 1)],
+        "should match references by normalized identifier"
+    );
+
+    assert_eq!(
+        usage("[a](b) [c]\n\n[a]: d")?,
+        vec![("a".into(), 0)],
+        "should not count resources or references w/o definition"
+    );
+
+    assert_eq!(
+        usage("> * [a]\n\n[a]: b\n[a]: c")?,
+        vec![("a".into(), 1)],
+        "should count references in containers, for the first definition"
+    );
+
+    assert_eq!(
+        usage("[*[a]*][b]\n\n[a]: c\n[b]: d")?,
+        vec![("a".into(), 1), ("b".into(), 1)],
+        "should count the inner link (as links cannot contain links)"
+    );
+
+    assert_eq!(
+        usage("![*[a]*][b]\n\n[a]: c\n[b]: d")?,
+        vec![("a".into(), 0), ("b".into(), 1)],
+        "should not count references in the alt of images (as that is text)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definition_labels_api() -> Result<(), message::Message> {
+    assert_eq!(
+        definition_labels("a", &ParseOptions::default())?,
+        BTreeMap::new(),
+        "should support no definitions"
+    );
+
+    assert_eq!(
+        definition_labels(
+            "[Glossary\n  Term]: a\n[glossary term]: b\n\n> [ẞ]: c",
+            &ParseOptions::default()
+        )?,
+        BTreeMap::from([
+            ("glossary term".into(), "Glossary\nTerm".into()),
+            ("ss".into(), "ẞ".into()),
+        ]),
+        "should map identifiers to the labels of the first definitions"
+    );
+
+    assert_eq!(
+        definition_labels("[a\\*b &amp; c]: d", &ParseOptions::default())?,
+        BTreeMap::from([("a\\*b &amp; c".into(), "a*b & c".into())]),
+        "should decode escapes and references in labels, but not identifiers"
+    );
+
+    let labels = definition_labels("[Ἀ  ΣΑ]: a\n[ϴ]: b", &ParseOptions::default())?;
+
+    assert_eq!(
+        ["ἀ σα", "ἈΣΑ", "Ἀ\tΣΑ ", "θ", "Θ"]
+            .iter()
+            .map(|term| labels.get(&normalize_identifier(term).to_lowercase()))
+            .collect::<Vec<_>>(),
+        vec![
+            Some(&"Ἀ  ΣΑ".into()),
+            None,
+            Some(&"Ἀ  ΣΑ".into()),
+            Some(&"ϴ".into()),
+            Some(&"ϴ".into())
+        ],
+        "should match terms normalized w/ `normalize_identifier`"
+    );
+
+    assert_eq!(
+        (
+            normalize_identifier(" a\t\r\n b "),
+            normalize_identifier("ẞ"),
+            normalize_identifier("a\u{a0}b")
+        ),
+        ("A B".into(), "SS".into(), "A\u{a0}B".into()),
+        "should collapse markdown whitespace and case fold in `normalize_identifier`"
+    );
+
     Ok(())
 }
@@ -0,0 +1,297 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn definition_list() -> Result<(), message::Message> {
+    let definition_list = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                definition_list: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a\n: b"),
+        "<p>a\n: b</p>",
+        "should ignore definition lists by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>",
+        "should support a term and a description"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n: c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n<dd>c</dd>\n</dl>",
+        "should support several descriptions for a term"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\nc\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n<dt>c</dt>\n<dd>d</dd>\n</dl>",
+        "should support several terms in one list, w/ blank lines before terms in a tight list"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n: b", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n</dl>",
+        "should support a blank line between a term and a description (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\n: c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n<dd>\n<p>c</p>\n</dd>\n</dl>",
+        "should support a blank line between descriptions (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\n  c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n<p>c</p>\n</dd>\n</dl>",
+        "should support a blank line between things in a description (loose)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\nc\n\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n<dt>c</dt>\n<dd>\n<p>d</p>\n</dd>\n</dl>",
+        "should make the whole list loose if one description is"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\n\n: c\n\n\nd", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n<dd>\n<p>c</p>\n</dd>\n</dl>\n<p>d</p>",
+        "should support several blank lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n  c\n\n  ```\n  d\n  ```", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<p>b\nc</p>\n<pre><code>d\n</code></pre>\n</dd>\n</dl>",
+        "should support indented continuation lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\nc\n: d", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b\nc</dd>\n<dd>d</dd>\n</dl>",
+        "should support lazy lines in descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:\tb\n\tc", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b\nc</dd>\n</dl>",
+        "should support tabs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n   : b", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>",
+        "should support up to three spaces of indent before a marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n    : b", &definition_list)?,
+        "<p>a\n: b</p>",
+        "should not support four spaces of indent before a marker"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:b", &definition_list)?,
+        "<p>a\n:b</p>",
+        "should not support a marker w/o whitespace after it"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:\n: b", &definition_list)?,
+        "<dl>\n<dt>a\n:</dt>\n<dd>b</dd>\n</dl>",
+        "should not support empty descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n: c", &definition_list)?,
+        "<dl>\n<dt>a\nb</dt>\n<dd>c</dd>\n</dl>",
+        "should use a whole paragraph as the term"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a*\n: b", &definition_list)?,
+        "<dl>\n<dt><em>a</em></dt>\n<dd>b</dd>\n</dl>",
+        "should support text in terms"
+    );
+
+    assert_eq!(
+        to_html_with_options(": a", &definition_list)?,
+        "<p>: a</p>",
+        "should not support a description w/o term"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n: b", &definition_list)?,
+        "<h1>a</h1>\n<p>: b</p>",
+        "should not support a heading as a term"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---\n: b", &definition_list)?,
+        "<h2>a</h2>\n<p>: b</p>",
+        "should not support a setext heading as a term"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n***", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>\n<hr />",
+        "should support things interrupting descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n: b", &definition_list)?,
+        "<blockquote>\n<p>a\n: b</p>\n</blockquote>",
+        "should not support a term in a block quote before it (lazy)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n: b", &definition_list)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<p>: b</p>",
+        "should not support a term in a block quote before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n: b", &definition_list)?,
+        "<ul>\n<li>a\n: b</li>\n</ul>",
+        "should not support a term in a list item before it"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> : b\n>\n> : c", &definition_list)?,
+        "<blockquote>\n<dl>\n<dt>a</dt>\n<dd>\n<p>b</p>\n</dd>\n<dd>\n<p>c</p>\n</dd>\n</dl>\n</blockquote>",
+        "should support definition lists in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n  : b\n* c", &definition_list)?,
+        "<ul>\n<li>\n<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>\n</li>\n<li>c</li>\n</ul>",
+        "should support definition lists in list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n  * c\n  * d\n: e", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b\n<ul>\n<li>c</li>\n<li>d</li>\n</ul>\n</dd>\n<dd>e</dd>\n</dl>",
+        "should support lists in descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n  : c", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>\n<dl>\n<dt>b</dt>\n<dd>c</dd>\n</dl>\n</dd>\n</dl>",
+        "should support definition lists in descriptions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n: b\n\nc\n\nd\n: e", &definition_list)?,
+        "<dl>\n<dt>a</dt>\n<dd>b</dd>\n</dl>\n<p>c</p>\n<dl>\n<dt>d</dt>\n<dd>e</dd>\n</dl>",
+        "should not join definition lists w/ a paragraph between them"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\n: b",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        definition_list: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    source_positions: true,
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<dl data-sourcepos=\"1:1-2:4\">\n<dt data-sourcepos=\"1:1-1:2\">a</dt>\n<dd data-sourcepos=\"2:1-2:4\">b</dd>\n</dl>",
+        "should support source positions"
+    );
+
+    assert_eq!(
+        to_mdast("a\n: b", &definition_list.parse)?,
+        Node::Root(Root {
+            children: vec![
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "a".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),],
+                    position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                }),
+                Node::Paragraph(Paragraph {
+                    children: vec![Node::Text(Text {
+                        value: "b".into(),
+                        position: Some(Position::new(2, 3, 4, 2, 4, 5))
+                    }),],
+                    position: Some(Position::new(2, 3, 4, 2, 4, 5))
+                })
+            ],
+            position: Some(Position::new(1, 1, 0, 2, 4, 5))
+        }),
+        "should support definition lists as paragraphs in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn definition_list_gfm() -> Result<(), message::Message> {
+    let gfm = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                definition_list: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("Term\n: def", &gfm)?,
+        "<dl>\n<dt>Term</dt>\n<dd>def</dd>\n</dl>",
+        "should support a tight definition list w/ GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("Term\n:\tdef", &gfm)?,
+        "<dl>\n<dt>Term</dt>\n<dd>def</dd>\n</dl>",
+        "should support a tab after the marker w/ GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "Mercury\n: The first planet.\n: Named after a Roman god.\n\nVenus\n: The second planet.",
+            &gfm
+        )?,
+        "<dl>\n<dt>Mercury</dt>\n<dd>The first planet.</dd>\n<dd>Named after a Roman god.</dd>\n<dt>Venus</dt>\n<dd>The second planet.</dd>\n</dl>",
+        "should support several terms and descriptions w/ GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n: c\n\n| d |\n: e", &gfm)?,
+        "<dl>\n<dt>a\nb</dt>\n<dd>c</dd>\n<dt>| d |</dt>\n<dd>e</dd>\n</dl>",
+        "should support terms that could have been table head rows"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |\n: c\n\nd\n:-", &gfm)?,
+        "<table>\n<thead>\n<tr>\n<th>a</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b</td>\n</tr>\n<tr>\n<td>: c</td>\n</tr>\n</tbody>\n</table>\n<table>\n<thead>\n<tr>\n<th align=\"left\">d</th>\n</tr>\n</thead>\n</table>",
+        "should not support descriptions after table rows, or delimiter rows as descriptions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use markdown::{
+    mdast::{ContainerDirective, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Directive, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn directive_container() -> Result<(), message::Message> {
+    let directive = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_container: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let handled = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_container: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            directive_handler: Some(Rc::new(|directive: &Directive| {
+                let mut value = format!("<{}", directive.name);
+
+                for (key, value_) in &directive.attributes {
+                    value.push_str(&format!(" {}=\"{}\"", key, value_));
+                }
+
+                if let Some(label) = &directive.label {
+                    value.push_str(&format!(" label=\"{}\"", label));
+                }
+
+                value.push('>');
+                value.push_str(directive.content.as_deref().unwrap_or_default());
+                value.push_str(&format!("</{}>", directive.name));
+                Some(value)
+            })),
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html(":::a\nb\n:::"),
+        "<p>:::a\nb\n:::</p>",
+        "should not support directives (container) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n:::", &handled)?,
+        "<a><p>b</p></a>",
+        "should support a container"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a[b *c*]{#d .e f=g}\nh\n:::", &handled)?,
+        "<a id=\"d\" class=\"e\" f=\"g\" label=\"b <em>c</em>\"><p>h</p></a>",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n:::", &handled)?,
+        "<a></a>",
+        "should support an empty container"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb", &handled)?,
+        "<a><p>b</p></a>",
+        "should support a container without closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n:::\nc", &handled)?,
+        "<a><p>b</p></a>\n<p>c</p>",
+        "should support content after a container"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n:::b\nc\n:::\nd", &handled)?,
+        "<p>a</p>\n<b><p>c</p></b>\n<p>d</p>",
+        "should support a container interrupting a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a\nb", &handled)?,
+        "<p>::a\nb</p>",
+        "should not support a container with two markers"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a b", &handled)?,
+        "<p>:::a b</p>",
+        "should not support other characters after an opening fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a[b\nc]\n:::", &handled)?,
+        "<p>:::a[b\nc]\n:::</p>",
+        "should not support a line ending in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n::::", &handled)?,
+        "<a><p>b</p></a>",
+        "should support a longer closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("::::a\nb\n:::", &handled)?,
+        "<a><p>b\n:::</p></a>",
+        "should not support a shorter closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\nb\n::: c", &handled)?,
+        "<a><p>b\n::: c</p></a>",
+        "should not support other characters after a closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("   :::a\nb\n   :::  ", &handled)?,
+        "<a><p>b</p></a>",
+        "should support indented fences and trailing whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("    :::a\nb\n:::", &handled)?,
+        "<pre><code>:::a\n</code></pre>\n<p>b\n:::</p>",
+        "should not support an opening fence indented four spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("::::a\n:::b\nc\n:::\n::::", &handled)?,
+        "<a><b><p>c</p></b></a>",
+        "should support nested containers if the outer one has more markers"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n:::b\nc\n:::\n:::", &handled)?,
+        "<a><b><p>c</p></b></a>\n<p>:::</p>",
+        "should close the inner container first if they have the same number of markers"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n> b\n- c\n:::\nd", &handled)?,
+        "<a><blockquote>\n<p>b</p>\n</blockquote>\n<ul>\n<li>c</li>\n</ul></a>\n<p>d</p>",
+        "should support containers in a container"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n```\nb\n:::\nc", &handled)?,
+        "<a><pre><code>b\n\n</code></pre></a>\n<p>c</p>",
+        "should close flow in a container at the closing fence"
+    );
+
+    assert_eq!(
+        to_html_with_options("> :::a\n> b\n> :::\nc", &handled)?,
+        "<blockquote>\n<a><p>b</p></a>\n</blockquote>\n<p>c</p>",
+        "should support a container in a block quote"
+    );
+
+    assert_eq!(
+        to_html_with_options("> :::a\n> b\n\nc", &handled)?,
+        "<blockquote>\n<a><p>b</p></a>\n</blockquote>\n<p>c</p>",
+        "should close a container when its parent closes"
+    );
+
+    assert_eq!(
+        to_html_with_options("* :::a\n  b\n  :::\n* c", &handled)?,
+        "<ul>\n<li>\n<a><p>b</p></a>\n</li>\n<li>c</li>\n</ul>",
+        "should not treat the rest of a closing fence as a blank line in a list"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a\n[b]: c\n:::\n[b]", &handled)?,
+        "<a></a>\n<p><a href=\"c\">b</a></p>",
+        "should support definitions in a container"
+    );
+
+    assert_eq!(
+        to_html_with_options(":::a[b]{c}\nd\n:::", &directive)?,
+        "<p>d</p>",
+        "should render the content of directives without a handler"
+    );
+
+    assert_eq!(
+        to_mdast(":::a[b]{#c .d}\ne\n:::", &directive.parse)?,
+        Node::Root(Root {
+            children: vec![Node::ContainerDirective(ContainerDirective {
+                children: vec![
+                    Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                        })],
+                        position: Some(Position::new(1, 5, 4, 1, 8, 7))
+                    }),
+                    Node::Paragraph(Paragraph {
+                        children: vec![Node::Text(Text {
+                            value: "e".into(),
+                            position: Some(Position::new(2, 1, 15, 2, 2, 16))
+                        })],
+                        position: Some(Position::new(2, 1, 15, 2, 2, 16))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 3, 4, 20)),
+                name: "a".into(),
+                attributes: vec![("id".into(), "c".into()), ("class".into(), "d".into())]
+            })],
+            position: Some(Position::new(1, 1, 0, 3, 4, 20))
+        }),
+        "should support container directives as `ContainerDirective`s in mdast"
+    );
+
+    Ok(())
+}
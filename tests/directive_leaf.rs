@@ -0,0 +1,165 @@
+use markdown::{
+    mdast::{LeafDirective, Node, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Directive, DirectiveKind, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn directive_leaf() -> Result<(), message::Message> {
+    let directive = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_leaf: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let handled = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_leaf: true,
+                directive_text: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            directive_handler: Some(Rc::new(|directive: &Directive| {
+                let mut value = format!(
+                    "<{}{}",
+                    directive.name,
+                    if directive.kind == DirectiveKind::Leaf {
+                        " leaf"
+                    } else {
+                        ""
+                    }
+                );
+
+                for (key, value_) in &directive.attributes {
+                    value.push_str(&format!(" {}=\"{}\"", key, value_));
+                }
+
+                value.push('>');
+                value.push_str(directive.label.as_deref().unwrap_or_default());
+                value.push_str(&format!("</{}>", directive.name));
+                Some(value)
+            })),
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html("::a[b]{c}"),
+        "<p>::a[b]{c}</p>",
+        "should not support directives (leaf) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a", &handled)?,
+        "<a leaf></a>",
+        "should support a name"
+    );
+
+    assert_eq!(
+        to_html_with_options("::1", &handled)?,
+        "<p>::1</p>",
+        "should not support a name starting with a digit"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b *c*]{#d .e f=g}", &handled)?,
+        "<a leaf id=\"d\" class=\"e\" f=\"g\">b <em>c</em></a>",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[:b[c]]", &handled)?,
+        "<a leaf><b>c</b></a>",
+        "should support a directive (text) in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a b", &handled)?,
+        "<p>::a b</p>",
+        "should not support other characters after a leaf"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a  ", &handled)?,
+        "<a leaf></a>",
+        "should support trailing whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("   ::a", &handled)?,
+        "<a leaf></a>",
+        "should support an indent of up to three spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("    ::a", &handled)?,
+        "<pre><code>::a\n</code></pre>",
+        "should not support an indent of four spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b\nc]", &handled)?,
+        "<p>::a[b\nc]</p>",
+        "should not support a line ending in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a{b\nc}", &handled)?,
+        "<p>::a{b\nc}</p>",
+        "should not support a line ending in attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n::b\nc", &handled)?,
+        "<p>a</p>\n<b leaf></b>\n<p>c</p>",
+        "should support a leaf between paragraphs, interrupting"
+    );
+
+    assert_eq!(
+        to_html_with_options("> ::a[b]", &handled)?,
+        "<blockquote>\n<a leaf>b</a>\n</blockquote>",
+        "should support a leaf in a block quote"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a[b]{c}", &directive)?,
+        "<p>b</p>",
+        "should render the label of directives without a handler in a paragraph"
+    );
+
+    assert_eq!(
+        to_html_with_options("::a{b}", &directive)?,
+        "",
+        "should render nothing for directives without a handler or label"
+    );
+
+    assert_eq!(
+        to_mdast("::a[b]{c=\"&amp;\"}", &directive.parse)?,
+        Node::Root(Root {
+            children: vec![Node::LeafDirective(LeafDirective {
+                children: vec![Node::Text(Text {
+                    value: "b".into(),
+                    position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 18, 17)),
+                name: "a".into(),
+                attributes: vec![("c".into(), "&".into())]
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 18, 17))
+        }),
+        "should support leaf directives as `LeafDirective`s in mdast"
+    );
+
+    Ok(())
+}
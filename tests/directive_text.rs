@@ -0,0 +1,280 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text, TextDirective},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Directive, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn directive_text() -> Result<(), message::Message> {
+    let directive = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_text: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let handled = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                directive_text: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile: CompileOptions {
+            directive_handler: Some(Rc::new(|directive: &Directive| {
+                let mut value = format!("<{}", directive.name);
+
+                for (key, value_) in &directive.attributes {
+                    value.push_str(&format!(" {}=\"{}\"", key, value_));
+                }
+
+                value.push('>');
+                value.push_str(directive.label.as_deref().unwrap_or_default());
+                value.push_str(&format!("</{}>", directive.name));
+                Some(value)
+            })),
+            ..Default::default()
+        },
+    };
+
+    assert_eq!(
+        to_html("a :b[c]{d} e"),
+        "<p>a :b[c]{d} e</p>",
+        "should not support directives (text) by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b c", &handled)?,
+        "<p>a <b></b> c</p>",
+        "should support a name"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b-c_d e", &handled)?,
+        "<p>a <b-c_d></b-c_d> e</p>",
+        "should support dashes and underscores in a name"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :1 b", &handled)?,
+        "<p>a :1 b</p>",
+        "should not support a name starting with a digit"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b- c", &handled)?,
+        "<p>a :b- c</p>",
+        "should not support a name ending in a dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b_ c", &handled)?,
+        "<p>a :b_ c</p>",
+        "should not support a name ending in an underscore"
+    );
+
+    assert_eq!(
+        to_html_with_options("a::b", &handled)?,
+        "<p>a::b</p>",
+        "should not support a directive after a colon"
+    );
+
+    assert_eq!(
+        to_html_with_options("a:b", &handled)?,
+        "<p>a<b></b></p>",
+        "should support a directive after other characters"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\:a", &handled)?,
+        "<p>:a</p>",
+        "should not support an escaped colon"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b[c *d*] e", &handled)?,
+        "<p>a <b>c <em>d</em></b> e</p>",
+        "should support a label with text content"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[]", &handled)?,
+        "<p><a></a></p>",
+        "should support an empty label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b [c] d]", &handled)?,
+        "<p><a>b [c] d</a></p>",
+        "should support balanced brackets in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b \\] c]", &handled)?,
+        "<p><a>b ] c</a></p>",
+        "should support escaped brackets in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b", &handled)?,
+        "<p><a></a>[b</p>",
+        "should not support an unclosed label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b\nc]", &handled)?,
+        "<p><a>b\nc</a></p>",
+        "should support a line ending in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[:b[c]]", &handled)?,
+        "<p><a><b>c</b></a></p>",
+        "should support a directive in a label"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{}", &handled)?,
+        "<p><a></a></p>",
+        "should support empty attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{#b .c .d e=f g=\"h\" i='j' k}", &handled)?,
+        "<p><a id=\"b\" class=\"c d\" e=\"f\" g=\"h\" i=\"j\" k=\"\"></a></p>",
+        "should support attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{#b #c}", &handled)?,
+        "<p><a id=\"c\"></a></p>",
+        "should use the last id"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{.b class=c .d}", &handled)?,
+        "<p><a class=\"b c d\"></a></p>",
+        "should merge classes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=c b=d}", &handled)?,
+        "<p><a b=\"d\"></a></p>",
+        "should use the last value of other attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{#b.c}", &handled)?,
+        "<p><a id=\"b\" class=\"c\"></a></p>",
+        "should support shortcuts without whitespace between them"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=\"&amp;&#x20;&copy;\"}", &handled)?,
+        "<p><a b=\"& ©\"></a></p>",
+        "should decode character references in attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=\"c\nd\" \ne\n}", &handled)?,
+        "<p><a b=\"c\nd\" e=\"\"></a></p>",
+        "should support line endings in attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b<c}", &handled)?,
+        "<p><a></a>{b&lt;c}</p>",
+        "should not support invalid attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a{b=\"c}", &handled)?,
+        "<p><a></a>{b=&quot;c}</p>",
+        "should not support unclosed quoted attribute values"
+    );
+
+    assert_eq!(
+        to_html_with_options(":a[b]{c}d", &handled)?,
+        "<p><a c=\"\">b</a>d</p>",
+        "should support a label and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("[:a[b]](c)", &handled)?,
+        "<p><a href=\"c\"><a>b</a></a></p>",
+        "should support a directive in a link"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b[c]{d} e", &directive)?,
+        "<p>a c e</p>",
+        "should render the label of directives without a handler"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :b[c] :d[e]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        directive_text: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    directive_handler: Some(Rc::new(|directive: &Directive| {
+                        if directive.name == "b" {
+                            Some("B".into())
+                        } else {
+                            None
+                        }
+                    })),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<p>a B e</p>",
+        "should render the label of directives not handled by the handler"
+    );
+
+    assert_eq!(
+        to_mdast("a :b[c]{d} e", &directive.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::TextDirective(TextDirective {
+                        children: vec![Node::Text(Text {
+                            value: "c".into(),
+                            position: Some(Position::new(1, 6, 5, 1, 7, 6))
+                        })],
+                        position: Some(Position::new(1, 3, 2, 1, 11, 10)),
+                        name: "b".into(),
+                        attributes: vec![("d".into(), "".into())]
+                    }),
+                    Node::Text(Text {
+                        value: " e".into(),
+                        position: Some(Position::new(1, 11, 10, 1, 13, 12))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should support text directives as `TextDirective`s in mdast"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn options(shortcodes: Vec<(String, String)>) -> Options {
+    Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                emoji: true,
+                ..Constructs::gfm()
+            },
+            emoji_shortcodes: shortcodes,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    }
+}
+
+#[test]
+fn emoji() -> Result<(), message::Message> {
+    let custom = options(vec![
+        ("ferris".into(), "🦀".into()),
+        ("a_b".into(), "🅰".into()),
+        ("+1".into(), "👍".into()),
+        ("lt".into(), "<".into()),
+    ]);
+
+    assert_eq!(
+        to_html(":ferris:"),
+        "<p>:ferris:</p>",
+        "should not support emoji by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :ferris: b", &custom)?,
+        "<p>a 🦀 b</p>",
+        "should support a shortcode"
+    );
+
+    assert_eq!(
+        to_html_with_options(":ferris::ferris: :+1: :a_b:", &custom)?,
+        "<p>🦀🦀 👍 🅰</p>",
+        "should support adjacent shortcodes, and `+`, `-`, and `_`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :unknown: b :Ferris: c ::", &custom)?,
+        "<p>a :unknown: b :Ferris: c ::</p>",
+        "should not support unknown shortcodes"
+    );
+
+    assert_eq!(
+        to_html_with_options("Note: at 10:ferris:30, a:ferris: and :ferris:b", &custom)?,
+        "<p>Note: at 10:ferris:30, a:ferris: and :ferris:b</p>",
+        "should not support shortcodes next to alphanumericals"
+    );
+
+    assert_eq!(
+        to_html_with_options("(:ferris:) ,:ferris:. :a:ferris:", &custom)?,
+        "<p>(🦀) ,🦀. :a:ferris:</p>",
+        "should support shortcodes next to punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options(":ferris\n: :fer\nris:", &custom)?,
+        "<p>:ferris\n: :fer\nris:</p>",
+        "should not support shortcodes across lines"
+    );
+
+    assert_eq!(
+        to_html_with_options(":lt:", &custom)?,
+        "<p>&lt;</p>",
+        "should encode the emoji"
+    );
+
+    assert_eq!(
+        to_html_with_options("*:ferris:* **a :a_b:**", &custom)?,
+        "<p><em>🦀</em> <strong>a 🅰</strong></p>",
+        "should support shortcodes in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("[:ferris:](a) ![:ferris:](b)", &custom)?,
+        "<p><a href=\"a\">🦀</a> <img src=\"b\" alt=\"🦀\" /></p>",
+        "should support shortcodes in links and image alts"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "`:ferris:` <a title=\":ferris:\"> <https://a.b/:ferris:>",
+            &Options {
+                compile: markdown::CompileOptions {
+                    allow_dangerous_html: true,
+                    ..markdown::CompileOptions::gfm()
+                },
+                ..options(vec![("ferris".into(), "🦀".into())])
+            }
+        )?,
+        "<p><code>:ferris:</code> <a title=\":ferris:\"> <a href=\"https://a.b/:ferris:\">https://a.b/:ferris:</a></p>",
+        "should not support shortcodes in code, HTML, or autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "\\:ferris: :&#102;erris: :ferr&#105;s:",
+            &options(vec![("ferris".into(), "🦀".into())])
+        )?,
+        "<p>:ferris: :ferris: :ferris:</p>",
+        "should not support character escapes or references in shortcodes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :ferris: b",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        directive_text: true,
+                        emoji: true,
+                        ..Constructs::default()
+                    },
+                    emoji_shortcodes: vec![("ferris".into(), "🦀".into())],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a : b</p>",
+        "should prefer directives (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :ferris: b",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        emoji: true,
+                        ..Constructs::default()
+                    },
+                    emoji_shortcodes: vec![("ferris".into(), "🦀".into())],
+                    plain_text: true,
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a :ferris: b</p>",
+        "should not support shortcodes in plain text"
+    );
+
+    assert_eq!(
+        to_mdast("a :ferris: b", &custom.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a 🦀 b".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 13, 12))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 13, 12))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 13, 12))
+        }),
+        "should support shortcodes as text in mdast"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "emoji")]
+#[test]
+fn emoji_bundled() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_with_options(":smile: :tada: :+1: :-1: :100:", &options(vec![]))?,
+        "<p>😄 🎉 👍 👎 💯</p>",
+        "should support the shortcodes used on GitHub"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":smile: :ferris:",
+            &options(vec![("smile".into(), "🙂".into())])
+        )?,
+        "<p>🙂 :ferris:</p>",
+        "should prefer shortcodes passed in options"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            ":smile: :tada:",
+            &options(vec![("smile".into(), ":smile:".into())])
+        )?,
+        "<p>:smile: 🎉</p>",
+        "should support turning off a shortcode by passing it as its own value"
+    );
+
+    Ok(())
+}
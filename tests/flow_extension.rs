@@ -0,0 +1,243 @@
+use markdown::{
+    message, to_events, to_html, to_html_with_options, CompileOptions, EventKind, EventName,
+    FlowExtension, FlowExtensionPriority, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+fn fence(priority: FlowExtensionPriority, interrupt: bool) -> FlowExtension {
+    FlowExtension {
+        name: "fence".into(),
+        priority,
+        interrupt,
+        start: Box::new(|line| line.trim_start().starts_with("%%%")),
+        close: Some(Box::new(|_opening, line| line.trim() == "%%%")),
+    }
+}
+
+fn options(extensions: Vec<FlowExtension>) -> Options {
+    Options {
+        parse: ParseOptions {
+            flow_extensions: extensions,
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            flow_extension_handler: Some(Rc::new(|name, value| {
+                if name == "fence" {
+                    Some(format!("<fence>{}</fence>", value))
+                } else {
+                    None
+                }
+            })),
+            ..CompileOptions::gfm()
+        },
+    }
+}
+
+#[test]
+fn flow_extension() -> Result<(), message::Message> {
+    let first = options(vec![fence(FlowExtensionPriority::First, false)]);
+    let last = options(vec![fence(FlowExtensionPriority::Last, false)]);
+    let interrupt = options(vec![fence(FlowExtensionPriority::First, true)]);
+    let single = options(vec![FlowExtension {
+        name: "bang".into(),
+        priority: FlowExtensionPriority::First,
+        interrupt: true,
+        start: Box::new(|line| line.starts_with("!!")),
+        close: None,
+    }]);
+
+    assert_eq!(
+        to_html("%%%\na\n%%%"),
+        "<p>%%%\na\n%%%</p>",
+        "should not support custom flow constructs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\n*a*\n%%%\nb", &first)?,
+        "<fence>%%%\n*a*\n%%%</fence>\n<p>b</p>",
+        "should support a custom flow construct"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\n*a*\n%%%\nb", &last)?,
+        "<fence>%%%\n*a*\n%%%</fence>\n<p>b</p>",
+        "should support a custom flow construct that goes last"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\na\n%%%%\nb", &first)?,
+        "<fence>%%%\na\n%%%%\nb</fence>",
+        "should continue until the end if not closed"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\n\n  a\n\n%%%", &first)?,
+        "<fence>%%%\n\n  a\n\n%%%</fence>",
+        "should include blank lines and indent"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\na\n%%%", &options(vec![]))?,
+        "<p>%%%\na\n%%%</p>",
+        "should support no extensions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "%%%\na\n%%%",
+            &Options {
+                parse: ParseOptions {
+                    flow_extensions: vec![fence(FlowExtensionPriority::First, false)],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<pre><code>%%%\na\n%%%\n</code></pre>",
+        "should render a custom flow construct as code without a handler"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "%%%\n<a>&amp;\n%%%",
+            &Options {
+                parse: ParseOptions {
+                    flow_extensions: vec![fence(FlowExtensionPriority::First, false)],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<pre><code>%%%\n&lt;a&gt;&amp;amp;\n%%%\n</code></pre>",
+        "should encode the value when rendering as code"
+    );
+
+    assert_eq!(
+        to_html_with_options("    %%%\n    a", &first)?,
+        "<fence>    %%%\n    a</fence>",
+        "should try extensions that go first before code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options("    %%%\n    a", &last)?,
+        "<pre><code>%%%\na\n</code></pre>",
+        "should try extensions that go last after code (indented)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# %%%", &single)?,
+        "<h1>%%%</h1>",
+        "should not try extensions in the middle of a line"
+    );
+
+    assert_eq!(
+        to_html_with_options("!! a\n!!b\nc", &single)?,
+        "<pre><code>!! a\n</code></pre>\n<pre><code>!!b\n</code></pre>\n<p>c</p>",
+        "should support one line constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n%%%\nb\n%%%", &first)?,
+        "<p>a\n%%%\nb\n%%%</p>",
+        "should not interrupt a paragraph by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n%%%\nb\n%%%", &interrupt)?,
+        "<p>a</p>\n<fence>%%%\nb\n%%%</fence>",
+        "should interrupt a paragraph if `interrupt` is set"
+    );
+
+    assert_eq!(
+        to_html_with_options("> %%%\n> a\n> %%%\nb", &first)?,
+        "<blockquote>\n<fence>%%%\na\n%%%</fence>\n</blockquote>\n<p>b</p>",
+        "should support custom flow constructs in containers, without prefixes"
+    );
+
+    assert_eq!(
+        to_html_with_options("> %%%\n> a\nb", &first)?,
+        "<blockquote>\n<fence>%%%\na</fence>\n</blockquote>\n<p>b</p>",
+        "should not support lazy lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("- %%%\n  a\n\n  b\n  %%%\n- c", &first)?,
+        "<ul>\n<li>\n<fence>%%%\na\n\nb\n%%%</fence>\n</li>\n<li>c</li>\n</ul>",
+        "should support custom flow constructs in list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\n> a\n- b\n%%%", &first)?,
+        "<fence>%%%\n> a\n- b\n%%%</fence>",
+        "should be concrete (containers cannot pierce into it)"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%%\n> a\n- b\n%%%", &last)?,
+        "<fence>%%%\n> a\n- b\n%%%</fence>",
+        "should be concrete when going last too (before GFM tables)"
+    );
+
+    assert_eq!(
+        to_html_with_options("%%% | a\n--- | -", &last)?,
+        "<fence>%%% | a\n--- | -</fence>",
+        "should try extensions that go last before GFM tables"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "%%%\na\n%%%",
+            &options(vec![
+                FlowExtension {
+                    name: "other".into(),
+                    priority: FlowExtensionPriority::Last,
+                    interrupt: false,
+                    start: Box::new(|line| line.starts_with('%')),
+                    close: None,
+                },
+                fence(FlowExtensionPriority::First, false)
+            ])
+        )?,
+        "<fence>%%%\na\n%%%</fence>",
+        "should try extensions that go first before those that go last"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "%%%\na\n%%%",
+            &options(vec![
+                FlowExtension {
+                    name: "other".into(),
+                    priority: FlowExtensionPriority::First,
+                    interrupt: false,
+                    start: Box::new(|line| line.starts_with('%')),
+                    close: None,
+                },
+                fence(FlowExtensionPriority::First, false)
+            ])
+        )?,
+        "<pre><code>%%%\n</code></pre>\n<p>a\n%%%</p>",
+        "should try extensions with the same priority in order"
+    );
+
+    let events = to_events("%%%\na\n%%%", &first.parse)?;
+    assert_eq!(
+        events
+            .iter()
+            .filter(|d| d.kind == EventKind::Enter)
+            .map(|d| d.name.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            EventName::FlowExtension("fence".into()),
+            EventName::FlowExtensionChunk,
+            EventName::LineEnding,
+            EventName::FlowExtensionChunk,
+            EventName::LineEnding,
+            EventName::FlowExtensionChunk
+        ],
+        "should expose the name in events"
+    );
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Node, Root, Toml, Yaml},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    Constructs, FrontmatterKind, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -133,6 +133,60 @@ fn frontmatter() -> Result<(), message::Message> {
         "should support blank lines in frontmatter"
     );
 
+    assert_eq!(
+        to_html_with_options("+++\ntitle = \"Jupyter\"\n---", &frontmatter)?,
+        "<h2>+++\ntitle = &quot;Jupyter&quot;</h2>",
+        "should not support a closing fence that does not match the opening fence"
+    );
+
+    let yaml = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                frontmatter: true,
+                ..Default::default()
+            },
+            frontmatter_kind: FrontmatterKind::Yaml,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: Jupyter\n---", &yaml)?,
+        "",
+        "should support yaml if `frontmatter_kind: Yaml`"
+    );
+
+    assert_eq!(
+        to_html_with_options("+++\ntitle = \"Jupyter\"\n+++", &yaml)?,
+        "<p>+++\ntitle = &quot;Jupyter&quot;\n+++</p>",
+        "should not support toml if `frontmatter_kind: Yaml`"
+    );
+
+    let toml = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                frontmatter: true,
+                ..Default::default()
+            },
+            frontmatter_kind: FrontmatterKind::Toml,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("+++\ntitle = \"Jupyter\"\n+++", &toml)?,
+        "",
+        "should support toml if `frontmatter_kind: Toml`"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\ntitle: Jupyter\n---", &toml)?,
+        "<hr />\n<h2>title: Jupyter</h2>",
+        "should not support yaml if `frontmatter_kind: Toml`"
+    );
+
     assert_eq!(
         to_mdast("---\na: b\n---", &frontmatter.parse)?,
         Node::Root(Root {
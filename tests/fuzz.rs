@@ -129,5 +129,149 @@ fn fuzz() -> Result<(), message::Message> {
         "12: mdx: handle invalid mdx without panic (GH-26)"
     );
 
+    assert_eq!(
+        to_html("- ~~~\n * "),
+        "<ul>\n<li>\n<pre><code>\n</code></pre>\n</li>\n</ul>\n<ul>\n<li></li>\n</ul>",
+        "13: list items directly following each other in different lists"
+    );
+
+    let mut frontmatter = Options::default();
+    frontmatter.parse.constructs.frontmatter = true;
+    assert_eq!(
+        to_html_with_options("---\na\n> b", &frontmatter)?,
+        "<hr />\n<p>a</p>\n<blockquote>\n<p>b</p>\n</blockquote>",
+        "14: containers after an unclosed frontmatter"
+    );
+
+    assert!(
+        matches!(
+            to_mdast("[a]::\n-\n\\\n-", &Default::default()),
+            Ok(mdast::Node::Root(_))
+        ),
+        "15: setext underline in a paragraph that becomes a heading"
+    );
+
+    let mut mdx = Options::default();
+    mdx.parse = markdown::ParseOptions::mdx();
+    assert_eq!(
+        to_mdast("# a <b> #", &mdx.parse)
+            .err()
+            .map(|message| message.rule_id),
+        Some(Box::new("end-tag-mismatch".into())),
+        "16: mdx: unclosed JSX in a heading"
+    );
+
+    assert_eq!(
+        to_mdast("[<a>b](c)", &mdx.parse)
+            .err()
+            .map(|message| message.rule_id),
+        Some(Box::new("end-tag-mismatch".into())),
+        "17: mdx: unclosed JSX in a label"
+    );
+
+    assert!(
+        matches!(
+            to_mdast("{a}<a></a>", &mdx.parse)?
+                .children()
+                .map(|children| children.as_slice()),
+            Some([
+                mdast::Node::MdxFlowExpression(_),
+                mdast::Node::MdxJsxFlowElement(_)
+            ])
+        ),
+        "18: mdx: JSX after an expression"
+    );
+
+    let mut directive = Options::default();
+    directive.parse.constructs.directive_text = true;
+    assert_eq!(
+        to_html_with_options(":a[b  \nc]", &directive)?,
+        "<p>b<br />\nc</p>",
+        "19: directives: trailing whitespace in a label"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn fuzz_exhaustive() -> Result<(), message::Message> {
+    // Every combination of a few container markers, indents, line endings,
+    // and flow that stays open, which must not crash or error.
+    // These are what open, continue, and close containers around flow, where
+    // the `unexpected-flow-end` and `unexpected-container-exit` errors are.
+    let parts = ["> ", "- ", "1. ", "  ", "\n", "a", "```", "| a |\n| - |"];
+    let options = [
+        Options::gfm(),
+        Options {
+            parse: markdown::ParseOptions {
+                block_quote_lazy: false,
+                ..markdown::ParseOptions::gfm()
+            },
+            ..Options::gfm()
+        },
+    ];
+    let mut indices = [0; 3];
+
+    loop {
+        let value = indices.iter().map(|d| parts[*d]).collect::<String>();
+
+        for options in &options {
+            to_html_with_options(&value, options)?;
+            to_mdast(&value, &options.parse)?;
+        }
+
+        let mut index = 0;
+        while index < indices.len() && indices[index] == parts.len() - 1 {
+            indices[index] = 0;
+            index += 1;
+        }
+
+        if index == indices.len() {
+            break;
+        }
+
+        indices[index] += 1;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fuzz_pathological() -> Result<(), message::Message> {
+    // Inputs that used to take quadratic time, because of walking back over
+    // the same brackets or attention sequences again and again.
+    // The bound is generous, as this also runs in debug builds, but it is
+    // far below what the quadratic behavior took.
+    let values = [
+        "[".repeat(50000),
+        "*a".repeat(50000),
+        "a*b_ ".repeat(20000),
+        "[*a*](b)".repeat(20000),
+        format!("{}{}", "*[".repeat(20000), "]*".repeat(20000)),
+    ];
+    let mut index = 0;
+
+    while index < values.len() {
+        let value = &values[index];
+        let start = std::time::Instant::now();
+        to_html_with_options(value, &Options::gfm())?;
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "should parse `{}…` ({} bytes) in near-linear time, took {:?}",
+            &value[0..8],
+            value.len(),
+            elapsed
+        );
+
+        index += 1;
+    }
+
+    assert!(
+        to_html(&"*a".repeat(50000)).starts_with("<p><em>a</em>a<em>a</em>a<em>a</em>"),
+        "should still match attention in many sequences"
+    );
+
     Ok(())
 }
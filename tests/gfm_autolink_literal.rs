@@ -5,9 +5,9 @@
 
 use markdown::{
     mdast::{Link, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Options, ParseOptions,
+    CompileOptions, Constructs, EventKind, EventName, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -2826,3 +2826,162 @@ www.a/~
 
     Ok(())
 }
+
+#[test]
+fn gfm_autolink_literal_autolink() -> Result<(), message::Message> {
+    let gfm = Options::gfm();
+
+    assert_eq!(
+        to_html_with_options("<https://a.b>https://c.d", &gfm)?,
+        "<p><a href=\"https://a.b\">https://a.b</a><a href=\"https://c.d\">https://c.d</a></p>",
+        "should support a literal directly after an autolink"
+    );
+
+    assert_eq!(
+        to_html_with_options("https://a.b<https://c.d> https://e.f/<https://g.h>", &gfm)?,
+        "<p><a href=\"https://a.b\">https://a.b</a><a href=\"https://c.d\">https://c.d</a> <a href=\"https://e.f/\">https://e.f/</a><a href=\"https://g.h\">https://g.h</a></p>",
+        "should support an autolink directly after a literal"
+    );
+
+    assert_eq!(
+        to_html_with_options("<a@b.c>d@e.f <https://a.b>www.c.d <https://a.b>.https://c.d", &gfm)?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a><a href=\"mailto:d@e.f\">d@e.f</a> <a href=\"https://a.b\">https://a.b</a>www.c.d <a href=\"https://a.b\">https://a.b</a>.<a href=\"https://c.d\">https://c.d</a></p>",
+        "should support email literals, but not www literals, directly after autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b/https://c.d> <www.a.b>", &gfm)?,
+        "<p><a href=\"https://a.b/https://c.d\">https://a.b/https://c.d</a> &lt;www.a.b&gt;</p>",
+        "should not support literals in autolinks, or www literals after `<`"
+    );
+
+    assert_eq!(
+        to_events("<https://a.b>https://c.d", &gfm.parse)?
+            .iter()
+            .filter(|d| d.kind == EventKind::Enter)
+            .map(|d| d.name.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            EventName::Paragraph,
+            EventName::Autolink,
+            EventName::AutolinkMarker,
+            EventName::AutolinkProtocol,
+            EventName::AutolinkMarker,
+            EventName::GfmAutolinkLiteralProtocol
+        ],
+        "should not overlap autolinks and literals"
+    );
+
+    assert_eq!(
+        to_html_with_options("[<https://a.b> https://c.d](e)", &gfm)?,
+        "<p><a href=\"e\"><a href=\"https://a.b\">https://a.b</a> https://c.d</a></p>",
+        "should nest autolinks, but not literals, in links by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[<https://a.b> <c@d.e> https://f.g](h) ![<https://i.j>](k) <https://l.m>",
+            &Options {
+                compile: CompileOptions {
+                    flatten_autolinks: true,
+                    ..CompileOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<p><a href=\"h\">https://a.b c@d.e https://f.g</a> <img src=\"k\" alt=\"https://i.j\" /> <a href=\"https://l.m\">https://l.m</a></p>",
+        "should compile autolinks in links as text w/ `flatten_autolinks`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn gfm_autolink_literal_kinds() -> Result<(), message::Message> {
+    let kinds = |email: bool, protocol: bool, www: bool| Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                gfm_autolink_literal_email: email,
+                gfm_autolink_literal_protocol: protocol,
+                gfm_autolink_literal_www: www,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "a@b.c mailto:d@e.f https://g.h www.i.j",
+            &kinds(false, true, true)
+        )?,
+        "<p>a@b.c mailto:d@e.f <a href=\"https://g.h\">https://g.h</a> <a href=\"http://www.i.j\">www.i.j</a></p>",
+        "should support turning off email literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a@b.c mailto:d@e.f https://g.h www.i.j",
+            &kinds(true, false, true)
+        )?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a> <a href=\"mailto:d@e.f\">mailto:d@e.f</a> https://g.h <a href=\"http://www.i.j\">www.i.j</a></p>",
+        "should support turning off protocol literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a@b.c mailto:d@e.f https://g.h www.i.j",
+            &kinds(true, true, false)
+        )?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a> <a href=\"mailto:d@e.f\">mailto:d@e.f</a> <a href=\"https://g.h\">https://g.h</a> www.i.j</p>",
+        "should support turning off www literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<a@b.c> <mailto:d@e.f> <https://g.h>",
+            &kinds(false, false, false)
+        )?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a> <a href=\"mailto:d@e.f\">mailto:d@e.f</a> <a href=\"https://g.h\">https://g.h</a></p>",
+        "should support autolinks without literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a@b.c mailto:d@e.f https://g.h www.i.j",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        gfm_autolink_literal: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><a href=\"mailto:a@b.c\">a@b.c</a> <a href=\"mailto:d@e.f\">mailto:d@e.f</a> <a href=\"https://g.h\">https://g.h</a> <a href=\"http://www.i.j\">www.i.j</a></p>",
+        "should support turning on all kinds of literals with `gfm_autolink_literal`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a@b.c mailto:d@e.f https://g.h www.i.j",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        gfm_autolink_literal: false,
+                        ..Constructs::gfm()
+                    },
+                    ..ParseOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<p>a@b.c mailto:d@e.f https://g.h www.i.j</p>",
+        "should support turning off all kinds of literals with `gfm_autolink_literal`"
+    );
+
+    Ok(())
+}
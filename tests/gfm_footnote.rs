@@ -2,7 +2,7 @@ use markdown::{
     mdast::{FootnoteDefinition, FootnoteReference, Node, Paragraph, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Options, ParseOptions,
+    CompileOptions, FootnoteStyle, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -1663,3 +1663,108 @@ multi-paragraph list items. <a href="#user-content-fnref-longnote" data-footnote
 
     Ok(())
 }
+
+#[test]
+fn gfm_footnote_inline() -> Result<(), message::Message> {
+    let inline = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            gfm_footnote_style: FootnoteStyle::Inline,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\nc\n\n[^b]: d", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup></p>
+<aside id=\"user-content-fn-b\" class=\"footnote\" data-footnote-number=\"1\">
+<p>d <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>
+<p>c</p>
+",
+        "should support footnotes after the block where they are first referenced"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n# c[^d] [^b]\n\n[^b]: e\n[^d]: f", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup></p>
+<aside id=\"user-content-fn-b\" class=\"footnote\" data-footnote-number=\"1\">
+<p>e <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a> <a href=\"#user-content-fnref-b-2\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩<sup>2</sup></a></p>
+</aside>
+<h1>c<sup><a href=\"#user-content-fn-d\" id=\"user-content-fnref-d\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-d\">2</a></sup> <sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b-2\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup></h1>
+<aside id=\"user-content-fn-d\" class=\"footnote\" data-footnote-number=\"2\">
+<p>f <a href=\"#user-content-fnref-d\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>
+",
+        "should place footnotes referenced several times once, numbered like in a section"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a[^b]\n\n| c |\n| - |\n| d[^e] |\n\n[^b]: f\n[^e]: g", &inline)?,
+        "<ul>
+<li>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup>
+<aside id=\"user-content-fn-b\" class=\"footnote\" data-footnote-number=\"1\">
+<p>f <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside></li>
+</ul>
+<table>
+<thead>
+<tr>
+<th>c</th>
+</tr>
+</thead>
+<tbody>
+<tr>
+<td>d<sup><a href=\"#user-content-fn-e\" id=\"user-content-fnref-e\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-e\">2</a></sup></td>
+</tr>
+</tbody>
+</table>
+<aside id=\"user-content-fn-e\" class=\"footnote\" data-footnote-number=\"2\">
+<p>g <a href=\"#user-content-fnref-e\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>
+",
+        "should place footnotes in list items, and after tables"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c[^d]\n\n[^d]: e", &inline)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-b\">1</a></sup></p>
+<aside id=\"user-content-fn-b\" class=\"footnote\" data-footnote-number=\"1\">
+<p>c<sup><a href=\"#user-content-fn-d\" id=\"user-content-fnref-d\" data-footnote-ref=\"\" aria-describedby=\"user-content-fn-d\">2</a></sup> <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>
+<aside id=\"user-content-fn-d\" class=\"footnote\" data-footnote-number=\"2\">
+<p>e <a href=\"#user-content-fnref-d\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>",
+        "should place footnotes referenced in footnotes at the end"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a[^b]\n\n[^b]: c",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    gfm_footnote_style: FootnoteStyle::Inline,
+                    gfm_footnote_clobber_prefix: Some("".into()),
+                    wrap: Some("article".into()),
+                    ..CompileOptions::gfm()
+                },
+            }
+        )?,
+        "<article>
+<p>a<sup><a href=\"#fn-b\" id=\"fnref-b\" data-footnote-ref=\"\" aria-describedby=\"fn-b\">1</a></sup></p>
+<aside id=\"fn-b\" class=\"footnote\" data-footnote-number=\"1\">
+<p>c <a href=\"#fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>
+</aside>
+</article>",
+        "should support `gfm_footnote_clobber_prefix` and `wrap`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n[^b]: c", &inline)?,
+        "<p>a</p>\n",
+        "should not generate unreferenced footnotes"
+    );
+
+    Ok(())
+}
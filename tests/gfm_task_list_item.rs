@@ -261,9 +261,11 @@ Text.</li>
                 ordered: false,
                 spread: false,
                 start: None,
+                delimiter: None,
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -276,6 +278,7 @@ Text.</li>
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(false),
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -288,6 +291,7 @@ Text.</li>
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -316,9 +320,11 @@ Text.</li>
                 ordered: false,
                 spread: false,
                 start: None,
+                delimiter: None,
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -331,6 +337,7 @@ Text.</li>
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(false),
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -343,6 +350,7 @@ Text.</li>
                     }),
                     Node::ListItem(ListItem {
                         checked: Some(true),
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Emphasis(Emphasis {
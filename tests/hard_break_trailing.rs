@@ -154,3 +154,59 @@ fn hard_break_trailing() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn hard_break_trailing_size_min() -> Result<(), message::Message> {
+    let size = |size: usize| Options {
+        parse: ParseOptions {
+            hard_break_trailing_size_min: size,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a  \nb   \nc", &size(3))?,
+        "<p>a\nb<br />\nc</p>",
+        "should support a higher `hard_break_trailing_size_min`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \nb", &size(1))?,
+        "<p>a<br />\nb</p>",
+        "should support a lower `hard_break_trailing_size_min`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb \nc", &size(0))?,
+        "<p>a\nb<br />\nc</p>",
+        "should treat `hard_break_trailing_size_min: 0` as `1`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \t\nb", &size(1))?,
+        "<p>a\nb</p>",
+        "should not support tabs w/ `hard_break_trailing_size_min`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a     \nb\\\nc",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        hard_break_trailing: false,
+                        ..Default::default()
+                    },
+                    hard_break_trailing_size_min: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a\nb<br />\nc</p>",
+        "should support only escapes w/ `hard_break_trailing: false`"
+    );
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Heading, Node, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    Constructs, HeadingAtxOptions, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -242,3 +242,133 @@ fn heading_atx() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn heading_atx_options() -> Result<(), message::Message> {
+    let relaxed = Options {
+        parse: ParseOptions {
+            heading_atx: HeadingAtxOptions {
+                space_required: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let no_closing = Options {
+        parse: ParseOptions {
+            heading_atx: HeadingAtxOptions {
+                closing_sequence: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("####text"),
+        "<p>####text</p>",
+        "should require whitespace after the opening sequence by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("####text", &relaxed)?,
+        "<h4>text</h4>",
+        "should support no whitespace after the opening sequence w/ `space_required: false`"
+    );
+
+    assert_eq!(
+        to_html_with_options("#a #\n## b\n###\n#5 bolt", &relaxed)?,
+        "<h1>a</h1>\n<h2>b</h2>\n<h3></h3>\n<h1>5 bolt</h1>",
+        "should support other headings w/ `space_required: false`"
+    );
+
+    assert_eq!(
+        to_html_with_options("#######a", &relaxed)?,
+        "<p>#######a</p>",
+        "should not support more than six hashes w/ `space_required: false`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n#b", &relaxed)?,
+        "<p>a</p>\n<h1>b</h1>",
+        "should support interrupting w/ `space_required: false`"
+    );
+
+    assert_eq!(
+        to_html("## a ##"),
+        "<h2>a</h2>",
+        "should support a closing sequence by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("## a ##\n# b #c #\n#\t#  \n# #", &no_closing)?,
+        "<h2>a ##</h2>\n<h1>b #c #</h1>\n<h1>#</h1>\n<h1>#</h1>",
+        "should not support a closing sequence w/ `closing_sequence: false`"
+    );
+
+    assert_eq!(
+        to_mdast("# a #", &no_closing.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Heading(Heading {
+                depth: 1,
+                children: vec![Node::Text(Text {
+                    value: "a #".into(),
+                    position: Some(Position::new(1, 3, 2, 1, 6, 5))
+                }),],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should include the hashes in the text in mdast w/ `closing_sequence: false`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn heading_atx_max_depth() -> Result<(), message::Message> {
+    let three = Options {
+        parse: ParseOptions {
+            max_heading_depth: 3,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("# a\n## b\n### c", &three)?,
+        "<h1>a</h1>\n<h2>b</h2>\n<h3>c</h3>",
+        "should support headings up to `max_heading_depth`"
+    );
+
+    assert_eq!(
+        to_html_with_options("#### a\n###### b", &three)?,
+        "<p>#### a\n###### b</p>",
+        "should not support headings past `max_heading_depth`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n#### b", &three)?,
+        "<p>a\n#### b</p>",
+        "should not interrupt paragraphs with headings past `max_heading_depth`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a",
+            &Options {
+                parse: ParseOptions {
+                    max_heading_depth: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p># a</p>",
+        "should support `max_heading_depth: 0`"
+    );
+
+    Ok(())
+}
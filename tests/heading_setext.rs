@@ -315,3 +315,168 @@ fn heading_setext() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn heading_setext_precedence() -> Result<(), message::Message> {
+    let off = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                heading_setext: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("a\n-"),
+        "<h2>a</h2>",
+        "should prefer an underline over an empty list item (`-`)"
+    );
+
+    assert_eq!(
+        to_html("a\n- b"),
+        "<p>a</p>\n<ul>\n<li>b</li>\n</ul>",
+        "should prefer a list item w/ content over an underline"
+    );
+
+    assert_eq!(
+        to_html("a\n---"),
+        "<h2>a</h2>",
+        "should prefer an underline over a thematic break"
+    );
+
+    assert_eq!(
+        to_html("a\n- - -"),
+        "<p>a</p>\n<hr />",
+        "should prefer a thematic break w/ inner whitespace over an underline"
+    );
+
+    assert_eq!(
+        to_html("a\n-\n-"),
+        "<h2>a</h2>\n<ul>\n<li></li>\n</ul>",
+        "should support an empty list item after an underline"
+    );
+
+    assert_eq!(
+        to_html("- a\n-"),
+        "<ul>\n<li>a</li>\n<li></li>\n</ul>",
+        "should prefer a list item over an underline outside the paragraph’s container"
+    );
+
+    assert_eq!(
+        to_html("- a\n  -"),
+        "<ul>\n<li>\n<h2>a</h2>\n</li>\n</ul>",
+        "should support an underline inside the paragraph’s container"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---", &off)?,
+        "<p>a</p>\n<hr />",
+        "should form a thematic break, not consume `---`, w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===", &off)?,
+        "<p>a\n===</p>",
+        "should not consume `===` w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n- b", &off)?,
+        "<p>a</p>\n<ul>\n<li>b</li>\n</ul>",
+        "should support list items w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---\nb", &off)?,
+        "<p>a</p>\n<hr />\n<p>b</p>",
+        "should split a paragraph with a thematic break w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===\nb", &off)?,
+        "<p>a\n===\nb</p>",
+        "should not split a paragraph at `===` w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n===\n---", &off)?,
+        "<p>a\n===</p>\n<hr />",
+        "should support a thematic break after `===` w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n***\n\nb\n___", &off)?,
+        "<p>a</p>\n<hr />\n<p>b</p>\n<hr />",
+        "should support other thematic breaks w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n---\n\n- b\n---\n\n- c\n  ---", &off)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<hr />\n<ul>\n<li>b</li>\n</ul>\n<hr />\n<ul>\n<li>c\n<hr />\n</li>\n</ul>",
+        "should support thematic breaks after paragraphs in containers w/o setext underlines"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\n---",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        heading_setext: false,
+                        thematic_break: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a\n---</p>",
+        "should not consume `---` w/o setext underlines and thematic breaks"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn heading_setext_max_depth() -> Result<(), message::Message> {
+    let one = Options {
+        parse: ParseOptions {
+            max_heading_depth: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\n===", &one)?,
+        "<h1>a</h1>",
+        "should support `=` w/ `max_heading_depth: 1`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n---\n\nb\n-", &one)?,
+        "<p>a</p>\n<hr />\n<p>b\n-</p>",
+        "should not support `-` w/ `max_heading_depth: 1`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\n===",
+            &Options {
+                parse: ParseOptions {
+                    max_heading_depth: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p>a\n===</p>",
+        "should not support `=` w/ `max_heading_depth: 0`"
+    );
+
+    Ok(())
+}
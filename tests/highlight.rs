@@ -0,0 +1,204 @@
+use markdown::{
+    mdast::{Highlight, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn highlight() -> Result<(), message::Message> {
+    let highlight = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                highlight: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a ==b== c"),
+        "<p>a ==b== c</p>",
+        "should ignore highlight by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b== c", &Options::gfm())?,
+        "<p>a ==b== c</p>",
+        "should ignore highlight in GFM"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b== c", &highlight)?,
+        "<p>a <mark>b</mark> c</p>",
+        "should support highlight w/ two equals signs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a =b= c", &highlight)?,
+        "<p>a =b= c</p>",
+        "should not support highlight w/ one equals sign"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ===b=== c", &highlight)?,
+        "<p>a ===b=== c</p>",
+        "should not support highlight w/ three equals signs"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b=== c", &highlight)?,
+        "<p>a ==b=== c</p>",
+        "should not support highlight w/ mismatched sequences"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b c", &highlight)?,
+        "<p>a ==b c</p>",
+        "should not support an unmatched opening sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b=== c ===d== e", &highlight)?,
+        "<p>a <mark>b=== c ===d</mark> e</p>",
+        "should support highlight around mismatched sequences"
+    );
+
+    assert_eq!(
+        to_html_with_options("a b== c", &highlight)?,
+        "<p>a b== c</p>",
+        "should not support an unmatched closing sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("a == b == c", &highlight)?,
+        "<p>a == b == c</p>",
+        "should not support sequences surrounded by whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b\nc== d", &highlight)?,
+        "<p>a <mark>b\nc</mark> d</p>",
+        "should support highlight across line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==*b*== c", &highlight)?,
+        "<p>a <mark><em>b</em></mark> c</p>",
+        "should support emphasis in highlight"
+    );
+
+    assert_eq!(
+        to_html_with_options("a *==b==* c", &highlight)?,
+        "<p>a <em><mark>b</mark></em> c</p>",
+        "should support highlight in emphasis"
+    );
+
+    assert_eq!(
+        to_html_with_options("a**==b==**c", &highlight)?,
+        "<p>a<strong><mark>b</mark></strong>c</p>",
+        "should support highlight in strong, in words"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==~~b~~== c", &highlight)?,
+        "<p>a <mark><del>b</del></mark> c</p>",
+        "should support strikethrough in highlight"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ==b *c== d*", &highlight)?,
+        "<p>a <mark>b *c</mark> d*</p>",
+        "should not support misnested highlight and emphasis"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `==b==` c", &highlight)?,
+        "<p>a <code>==b==</code> c</p>",
+        "should not support highlight in code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a <span title=\"==b==\">c</span>",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        highlight: true,
+                        ..Constructs::gfm()
+                    },
+                    ..ParseOptions::gfm()
+                },
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::gfm()
+                }
+            }
+        )?,
+        "<p>a <span title=\"==b==\">c</span></p>",
+        "should not support highlight in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \\==b== c", &highlight)?,
+        "<p>a ==b== c</p>",
+        "should not support highlight w/ an escaped equals sign"
+    );
+
+    assert_eq!(
+        to_html_with_options("a = b", &highlight)?,
+        "<p>a = b</p>",
+        "should support equals signs as data"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n==", &highlight)?,
+        "<h1>a</h1>",
+        "should not interfere with setext headings"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a ==b== c](d)", &highlight)?,
+        "<p><img src=\"d\" alt=\"a b c\" /></p>",
+        "should not add tags in image alts"
+    );
+
+    assert_eq!(
+        to_html_with_options("[==a==](b)", &highlight)?,
+        "<p><a href=\"b\"><mark>a</mark></a></p>",
+        "should support highlight in links"
+    );
+
+    assert_eq!(
+        to_mdast("a ==b== c", &highlight.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "a ".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 3, 2))
+                    }),
+                    Node::Highlight(Highlight {
+                        children: vec![Node::Text(Text {
+                            value: "b".into(),
+                            position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                        })],
+                        position: Some(Position::new(1, 3, 2, 1, 8, 7))
+                    }),
+                    Node::Text(Text {
+                        value: " c".into(),
+                        position: Some(Position::new(1, 8, 7, 1, 10, 9))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 10, 9))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 10, 9))
+        }),
+        "should support highlight as `Highlight`s in mdast"
+    );
+
+    Ok(())
+}
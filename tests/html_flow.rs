@@ -1,8 +1,9 @@
 use markdown::{
+    html_flow_kind,
     mdast::{Html, Node, Root},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, EventKind, EventName, HtmlFlowKind, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -1139,3 +1140,106 @@ fn html_flow_7_complete() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn html_flow_kind_api() -> Result<(), message::Message> {
+    let kinds = |value: &str| -> Result<Vec<(HtmlFlowKind, String)>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        let mut kinds = vec![];
+        let mut index = 0;
+
+        while index < events.len() {
+            if events[index].kind == EventKind::Enter && events[index].name == EventName::HtmlFlow {
+                let kind = html_flow_kind(value, &events, index);
+                let start = events[index].point.index;
+
+                while !(events[index].kind == EventKind::Exit
+                    && events[index].name == EventName::HtmlFlow)
+                {
+                    index += 1;
+                }
+
+                kinds.push((kind, value[start..events[index].point.index].into()));
+            }
+
+            index += 1;
+        }
+
+        Ok(kinds)
+    };
+
+    assert_eq!(
+        kinds("<pre>\na\n\nb</PRE>c\nd")?,
+        vec![(HtmlFlowKind::Raw, "<pre>\na\n\nb</PRE>c".into())],
+        "should support raw, ending at a closing tag"
+    );
+
+    assert_eq!(
+        kinds("<!--\na\n\nb-->c\nd")?,
+        vec![(HtmlFlowKind::Comment, "<!--\na\n\nb-->c".into())],
+        "should support comments, ending at `-->`"
+    );
+
+    assert_eq!(
+        kinds("<?\na\n\nb?>c\nd")?,
+        vec![(HtmlFlowKind::Instruction, "<?\na\n\nb?>c".into())],
+        "should support instructions, ending at `?>`"
+    );
+
+    assert_eq!(
+        kinds("<!A\na\n\nb>c\nd")?,
+        vec![(HtmlFlowKind::Declaration, "<!A\na\n\nb>c".into())],
+        "should support declarations, ending at `>`"
+    );
+
+    assert_eq!(
+        kinds("<![CDATA[\na\n\nb]]>c\nd")?,
+        vec![(HtmlFlowKind::Cdata, "<![CDATA[\na\n\nb]]>c".into())],
+        "should support CDATA, ending at `]]>`"
+    );
+
+    assert_eq!(
+        kinds("<table>\n<tr><td>a</td></tr>\n</table>\n\n*b*")?,
+        vec![(
+            HtmlFlowKind::Basic,
+            "<table>\n<tr><td>a</td></tr>\n</table>".into()
+        )],
+        "should support basic, ending at a blank line"
+    );
+
+    assert_eq!(
+        kinds("</TABLE>\n\t\n<table/>\r\n\r\na")?,
+        vec![
+            (HtmlFlowKind::Basic, "</TABLE>".into()),
+            (HtmlFlowKind::Basic, "<table/>".into())
+        ],
+        "should support basic closing and self-closing tags, ending at blank lines with whitespace or CRLF"
+    );
+
+    assert_eq!(
+        kinds("<x>\na\n\n</pre>\na\n\n<pre/>")?,
+        vec![
+            (HtmlFlowKind::Complete, "<x>\na".into()),
+            (HtmlFlowKind::Complete, "</pre>\na".into()),
+            (HtmlFlowKind::Complete, "<pre/>".into())
+        ],
+        "should support complete, ending at a blank line"
+    );
+
+    assert_eq!(
+        kinds("> <table>\n> a\n>\n> b\n- <div>\n\n  c")?,
+        vec![
+            (HtmlFlowKind::Basic, "<table>\n> a".into()),
+            (HtmlFlowKind::Basic, "<div>".into())
+        ],
+        "should support basic in containers, ending at a blank line"
+    );
+
+    assert_eq!(
+        kinds("  <table>\na")?,
+        vec![(HtmlFlowKind::Basic, "  <table>\na".into())],
+        "should support initial whitespace"
+    );
+
+    Ok(())
+}
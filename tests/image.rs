@@ -2,7 +2,7 @@ use markdown::{
     mdast::{Definition, Image, ImageReference, Node, Paragraph, ReferenceKind, Root, Text},
     message, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, FigureCaption, ImageLoading, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -338,3 +338,192 @@ fn image() -> Result<(), message::Message> {
     );
     Ok(())
 }
+
+#[test]
+fn image_loading_and_dimensions() -> Result<(), message::Message> {
+    let dimensions = Options {
+        compile: CompileOptions {
+            image_dimensions: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "![a](b)",
+            &Options {
+                compile: CompileOptions {
+                    image_loading: Some(ImageLoading::Lazy),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><img src=\"b\" alt=\"a\" loading=\"lazy\" /></p>",
+        "should support `image_loading: Some(ImageLoading::Lazy)`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "![a](b \"c\") and [d](e)",
+            &Options {
+                compile: CompileOptions {
+                    image_loading: Some(ImageLoading::Eager),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><img src=\"b\" alt=\"a\" title=\"c\" loading=\"eager\" /> and <a href=\"e\">d</a></p>",
+        "should support `image_loading: Some(ImageLoading::Eager)`, but not on links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"c =100x50\")", &dimensions)?,
+        "<p><img src=\"b\" alt=\"a\" title=\"c\" width=\"100\" height=\"50\" /></p>",
+        "should support a `=WxH` suffix in titles w/ `image_dimensions`"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"=100x50\")", &dimensions)?,
+        "<p><img src=\"b\" alt=\"a\" width=\"100\" height=\"50\" /></p>",
+        "should support a `=WxH` suffix as the whole title"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"c =100x\") ![d](e 'f\t=x50')", &dimensions)?,
+        "<p><img src=\"b\" alt=\"a\" title=\"c\" width=\"100\" /> <img src=\"e\" alt=\"d\" title=\"f\" height=\"50\" /></p>",
+        "should support a width or a height on its own"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"c\")", &dimensions)?,
+        "<p><img src=\"b\" alt=\"a\" title=\"c\" /></p>",
+        "should not change titles w/o suffix"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "![a](b \"c =100\") ![a](b \"c=1x2\") ![a](b \"c =x\") ![a](b \"c =1x2x3\") ![a](b \"c =1ax2\") ![a](b \"c =1x2 \")",
+            &dimensions
+        )?,
+        "<p><img src=\"b\" alt=\"a\" title=\"c =100\" /> <img src=\"b\" alt=\"a\" title=\"c=1x2\" /> <img src=\"b\" alt=\"a\" title=\"c =x\" /> <img src=\"b\" alt=\"a\" title=\"c =1x2x3\" /> <img src=\"b\" alt=\"a\" title=\"c =1ax2\" /> <img src=\"b\" alt=\"a\" title=\"c =1x2 \" /></p>",
+        "should keep malformed suffixes in titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a][b]\n\n[b]: c \"d =1x2\"", &dimensions)?,
+        "<p><img src=\"c\" alt=\"a\" title=\"d\" width=\"1\" height=\"2\" /></p>\n",
+        "should support a suffix in titles of definitions used by images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b \"c =1x2\")", &dimensions)?,
+        "<p><a href=\"b\" title=\"c =1x2\">a</a></p>",
+        "should not change titles of links"
+    );
+
+    assert_eq!(
+        to_html("![a](b \"c =1x2\")"),
+        "<p><img src=\"b\" alt=\"a\" title=\"c =1x2\" /></p>",
+        "should not change titles by default"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn image_figure() -> Result<(), message::Message> {
+    let alt = Options {
+        compile: CompileOptions {
+            image_figure: Some(FigureCaption::Alt),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let title = Options {
+        compile: CompileOptions {
+            image_figure: Some(FigureCaption::Title),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("![a](b \"c\")"),
+        "<p><img src=\"b\" alt=\"a\" title=\"c\" /></p>",
+        "should not turn images into figures by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a *b*](c \"d\")", &alt)?,
+        "<figure><img src=\"c\" alt=\"a b\" title=\"d\" /><figcaption>a b</figcaption></figure>",
+        "should support figures with the alternative text as caption"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b \"c<d\")", &title)?,
+        "<figure><img src=\"b\" alt=\"a\" title=\"c&lt;d\" /><figcaption>c&lt;d</figcaption></figure>",
+        "should support figures with the title as caption"
+    );
+
+    assert_eq!(
+        to_html_with_options("![](a)\n\n![b](c)", &alt)?,
+        "<figure><img src=\"a\" alt=\"\" /></figure>\n<figure><img src=\"c\" alt=\"b\" /><figcaption>b</figcaption></figure>",
+        "should not add an empty caption (alt)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b)", &title)?,
+        "<figure><img src=\"b\" alt=\"a\" /></figure>",
+        "should not add a missing caption (title)"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a][b]\n\n[b]: c \"d\"", &title)?,
+        "<figure><img src=\"c\" alt=\"a\" title=\"d\" /><figcaption>d</figcaption></figure>\n",
+        "should support figures for image references"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ![b](c)\n\n![d](e) f\n\n![g](h)![i](j)", &alt)?,
+        "<p>a <img src=\"c\" alt=\"b\" /></p>\n<p><img src=\"e\" alt=\"d\" /> f</p>\n<p><img src=\"h\" alt=\"g\" /><img src=\"j\" alt=\"i\" /></p>",
+        "should not turn images next to other content into figures"
+    );
+
+    assert_eq!(
+        to_html_with_options("[![a](b)](c)", &alt)?,
+        "<p><a href=\"c\"><img src=\"b\" alt=\"a\" /></a></p>",
+        "should not turn images in links into figures"
+    );
+
+    assert_eq!(
+        to_html_with_options("- ![a](b)\n- c", &alt)?,
+        "<ul>\n<li><figure><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure></li>\n<li>c</li>\n</ul>",
+        "should support figures in tight list items"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "![a](b) {.c}",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        attributes: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                compile: CompileOptions {
+                    image_figure: Some(FigureCaption::Alt),
+                    ..Default::default()
+                },
+            }
+        )?,
+        "<figure class=\"c\"><img src=\"b\" alt=\"a\" /><figcaption>a</figcaption></figure>",
+        "should add attributes of the paragraph to the figure"
+    );
+
+    Ok(())
+}
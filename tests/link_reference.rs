@@ -302,6 +302,12 @@ fn link_reference() -> Result<(), message::Message> {
         "should not support whitespace-only full references"
     );
 
+    assert_eq!(
+        to_html("[a b]: /url\n\n[ab], [a\tb], [ a\n b ]"),
+        "<p>[ab], <a href=\"/url\">a\tb</a>, <a href=\"/url\"> a\nb </a></p>",
+        "should not match labels that differ in whether there is whitespace"
+    );
+
     // See also: <https://github.com/commonmark/commonmark-spec/issues/616>
     assert_eq!(
         to_html("[+]: example.com\n[\\;]: example.com\n\nWill it link? [\\+], [;]"),
@@ -383,6 +389,23 @@ fn link_reference() -> Result<(), message::Message> {
         "should not support 1000 characters in a reference"
     );
 
+    assert_eq!(
+        to_html(
+            format!(
+                "[a b]: c\n\n[a{}b] [a{}b]",
+                " ".repeat(997),
+                " ".repeat(998)
+            )
+            .as_str()
+        ),
+        format!(
+            "<p><a href=\"c\">a{}b</a> [a{}b]</p>",
+            " ".repeat(997),
+            " ".repeat(998)
+        ),
+        "should not support shortcut references w/ more than 999 characters in the label"
+    );
+
     assert_eq!(
         to_html("[x] missing-colon\n\nWill it link? [x]"),
         "<p>[x] missing-colon</p>\n<p>Will it link? [x]</p>",
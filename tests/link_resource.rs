@@ -1,8 +1,8 @@
 use markdown::{
     mdast::{Image, Link, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    message, resource_info, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Options,
+    CompileOptions, EventKind, EventName, Options, ParseOptions, ResourceInfo,
 };
 use pretty_assertions::assert_eq;
 
@@ -535,3 +535,113 @@ fn link_resource() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn link_resource_info() -> Result<(), message::Message> {
+    let info = |value: &str| -> Result<Vec<ResourceInfo>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        let mut infos = vec![];
+        let mut index = 0;
+
+        while index < events.len() {
+            if events[index].kind == EventKind::Enter && events[index].name == EventName::Resource {
+                infos.push(resource_info(value, &events, index));
+            }
+
+            index += 1;
+        }
+
+        Ok(infos)
+    };
+
+    assert_eq!(
+        to_html("[a](<b\\>c d>)"),
+        "<p><a href=\"b%3Ec%20d\">a</a></p>",
+        "should support escaped angle brackets and spaces in enclosed destinations"
+    );
+
+    assert_eq!(
+        to_html("[a](b 'c\\\nd')"),
+        "<p><a href=\"b\" title=\"c\\\nd\">a</a></p>",
+        "should not support a backslash before a line ending in titles as an escape"
+    );
+
+    assert_eq!(
+        info("[a](\\(b\\))")?,
+        vec![ResourceInfo {
+            destination: "(b)".into(),
+            literal: false,
+            title: None
+        }],
+        "should decode escaped parens in destinations"
+    );
+
+    assert_eq!(
+        info("[a](</my uri> \"b\")")?,
+        vec![ResourceInfo {
+            destination: "/my uri".into(),
+            literal: true,
+            title: Some("b".into())
+        }],
+        "should support enclosed destinations w/ spaces"
+    );
+
+    assert_eq!(
+        info("[a](<b\\>c&gt;>)")?,
+        vec![ResourceInfo {
+            destination: "b>c>".into(),
+            literal: true,
+            title: None
+        }],
+        "should decode escapes and character references in enclosed destinations"
+    );
+
+    assert_eq!(
+        info("[a](b%20&auml;\\bc)")?,
+        vec![ResourceInfo {
+            destination: "b%20ä\\bc".into(),
+            literal: false,
+            title: None
+        }],
+        "should not decode percent-encoding or backslashes before other characters"
+    );
+
+    assert_eq!(
+        info("[a](b \"c \\\"&#x41;&#66;\nd\")")?,
+        vec![ResourceInfo {
+            destination: "b".into(),
+            literal: false,
+            title: Some("c \"AB\nd".into())
+        }],
+        "should decode titles"
+    );
+
+    assert_eq!(
+        info("> [a](b 'c\n> d')")?,
+        vec![ResourceInfo {
+            destination: "b".into(),
+            literal: false,
+            title: Some("c\nd".into())
+        }],
+        "should not include container prefixes in titles"
+    );
+
+    assert_eq!(
+        info("[a]() ![b](<>)")?,
+        vec![
+            ResourceInfo {
+                destination: "".into(),
+                literal: false,
+                title: None
+            },
+            ResourceInfo {
+                destination: "".into(),
+                literal: true,
+                title: None
+            }
+        ],
+        "should support empty destinations, in links and images"
+    );
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use markdown::{
+    list_info,
     mdast::{List, ListItem, Node, Paragraph, Root, Text},
-    message, to_html, to_html_with_options, to_mdast,
+    message, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    CompileOptions, Constructs, Options, ParseOptions,
+    CompileOptions, Constructs, EventKind, EventName, ListInfo, ListItemOptions, Options,
+    ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -102,6 +104,24 @@ fn list() -> Result<(), message::Message> {
         "should support ordered item values of `0`"
     );
 
+    assert_eq!(
+        to_html("999999999. a\n1. b"),
+        "<ol start=\"999999999\">\n<li>a</li>\n<li>b</li>\n</ol>",
+        "should support the biggest start (9 digits)"
+    );
+
+    assert_eq!(
+        to_html("9999999999. a"),
+        "<p>9999999999. a</p>",
+        "should not support a start of 10 digits (instead of resetting it to `1`)"
+    );
+
+    assert_eq!(
+        to_html("01. a\n\n000. b"),
+        "<ol>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ol>",
+        "should not add `start` for a start of `1` w/ leading `0`s"
+    );
+
     assert_eq!(
         to_html("003. ok"),
         "<ol start=\"3\">\n<li>ok</li>\n</ol>",
@@ -596,8 +616,10 @@ fn list() -> Result<(), message::Message> {
                 ordered: false,
                 spread: false,
                 start: None,
+                delimiter: None,
                 children: vec![Node::ListItem(ListItem {
                     checked: None,
+                    number: None,
                     spread: false,
                     children: vec![Node::Paragraph(Paragraph {
                         children: vec![Node::Text(Text {
@@ -622,9 +644,11 @@ fn list() -> Result<(), message::Message> {
                 ordered: true,
                 spread: false,
                 start: Some(3),
+                delimiter: Some('.'),
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: None,
+                        number: Some(3),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -637,6 +661,7 @@ fn list() -> Result<(), message::Message> {
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        number: Some(4),
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -662,9 +687,11 @@ fn list() -> Result<(), message::Message> {
                 ordered: false,
                 spread: false,
                 start: None,
+                delimiter: None,
                 children: vec![
                     Node::ListItem(ListItem {
                         checked: None,
+                        number: None,
                         spread: true,
                         children: vec![
                             Node::Paragraph(Paragraph {
@@ -686,6 +713,7 @@ fn list() -> Result<(), message::Message> {
                     }),
                     Node::ListItem(ListItem {
                         checked: None,
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -704,5 +732,554 @@ fn list() -> Result<(), message::Message> {
         "should support `spread` fields on `List`, `ListItem`s in mdast"
     );
 
+    let infos = |value: &str| -> Result<Vec<ListInfo>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        let mut infos = vec![];
+        let mut index = 0;
+
+        while index < events.len() {
+            if events[index].kind == EventKind::Enter
+                && matches!(
+                    events[index].name,
+                    EventName::ListOrdered | EventName::ListUnordered
+                )
+            {
+                infos.push(list_info(value, &events, index));
+            }
+
+            index += 1;
+        }
+
+        Ok(infos)
+    };
+
+    assert_eq!(
+        infos("5. a\n6. b")?,
+        vec![ListInfo {
+            ordered: true,
+            start: Some(5),
+            marker: '.',
+            loose: false
+        }],
+        "should expose info on ordered lists"
+    );
+
+    assert_eq!(
+        infos("0) a")?,
+        vec![ListInfo {
+            ordered: true,
+            start: Some(0),
+            marker: ')',
+            loose: false
+        }],
+        "should expose the start number of ordered lists, even if `0`"
+    );
+
+    assert_eq!(
+        infos("999999999. a\n\n- b\n\n9999999999. c")?,
+        vec![
+            ListInfo {
+                ordered: true,
+                start: Some(999_999_999),
+                marker: '.',
+                loose: false
+            },
+            ListInfo {
+                ordered: false,
+                start: None,
+                marker: '-',
+                loose: false
+            }
+        ],
+        "should expose big start numbers of ordered lists"
+    );
+
+    assert_eq!(
+        infos("+ a\n+ b")?,
+        vec![ListInfo {
+            ordered: false,
+            start: None,
+            marker: '+',
+            loose: false
+        }],
+        "should expose info on unordered lists"
+    );
+
+    assert_eq!(
+        infos("* a\n\n* b")?,
+        vec![ListInfo {
+            ordered: false,
+            start: None,
+            marker: '*',
+            loose: true
+        }],
+        "should expose whether lists are loose (blank line between items)"
+    );
+
+    assert_eq!(
+        infos("- a\n\n  b")?,
+        vec![ListInfo {
+            ordered: false,
+            start: None,
+            marker: '-',
+            loose: true
+        }],
+        "should expose whether lists are loose (blank line in item)"
+    );
+
+    assert_eq!(
+        infos("- a\n- b\n\n")?,
+        vec![ListInfo {
+            ordered: false,
+            start: None,
+            marker: '-',
+            loose: false
+        }],
+        "should expose whether lists are loose (not w/ a blank line after the list)"
+    );
+
+    assert_eq!(
+        infos("- a\n  - b\n\n    c\n- d")?,
+        vec![
+            ListInfo {
+                ordered: false,
+                start: None,
+                marker: '-',
+                loose: false
+            },
+            ListInfo {
+                ordered: false,
+                start: None,
+                marker: '-',
+                loose: true
+            }
+        ],
+        "should expose info on nested lists separately"
+    );
+
+    assert_eq!(
+        infos("1. a\n\nb\n\n3. c")?,
+        vec![
+            ListInfo {
+                ordered: true,
+                start: Some(1),
+                marker: '.',
+                loose: false
+            },
+            ListInfo {
+                ordered: true,
+                start: Some(3),
+                marker: '.',
+                loose: false
+            }
+        ],
+        "should expose info on lists interrupted and resumed across blank lines as separate lists"
+    );
+
+    assert_eq!(
+        infos("- a\n- b\n* c")?,
+        vec![
+            ListInfo {
+                ordered: false,
+                start: None,
+                marker: '-',
+                loose: false
+            },
+            ListInfo {
+                ordered: false,
+                start: None,
+                marker: '*',
+                loose: false
+            }
+        ],
+        "should expose info on lists split by a different marker separately"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_loose() -> Result<(), message::Message> {
+    let list_loose = |value: bool| Options {
+        compile: CompileOptions {
+            list_loose: Some(value),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("- a\n- b\n\n- c"),
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should be loose w/ a blank line between items"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n  b\n- c"),
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should be loose w/ a blank line between things in an item"
+    );
+
+    assert_eq!(
+        to_html("- a\n  - b\n\n    c\n- d"),
+        "<ul>\n<li>a\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>\n</li>\n<li>d</li>\n</ul>",
+        "should be tight w/ a blank line in a sublist"
+    );
+
+    assert_eq!(
+        to_html("- a\n  ```\n  b\n\n\n  ```\n- c"),
+        "<ul>\n<li>a\n<pre><code>b\n\n\n</code></pre>\n</li>\n<li>c</li>\n</ul>",
+        "should be tight w/ blank lines in fenced code"
+    );
+
+    assert_eq!(
+        to_html("-\n  a\n- b"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should be tight w/ a blank line right after a marker"
+    );
+
+    assert_eq!(
+        to_html("* >\n* b"),
+        "<ul>\n<li>\n<blockquote>\n</blockquote>\n</li>\n<li>b</li>\n</ul>",
+        "should be tight w/ a blank line at an empty block quote"
+    );
+
+    assert_eq!(
+        to_html("* a\n\nb"),
+        "<ul>\n<li>a</li>\n</ul>\n<p>b</p>",
+        "should be tight w/ a blank line after a list"
+    );
+
+    assert_eq!(
+        to_html("* a\n\n\nb\n\n* c\n\n\n"),
+        "<ul>\n<li>a</li>\n</ul>\n<p>b</p>\n<ul>\n<li>c</li>\n</ul>\n",
+        "should be tight w/ several blank lines after a list"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n",
+        "should be tight w/ a blank line at the end of the document"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n \n\t\n"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n",
+        "should be tight w/ whitespace-only lines at the end of the document"
+    );
+
+    assert_eq!(
+        to_html("- a\n-\n\n"),
+        "<ul>\n<li>a</li>\n<li></li>\n</ul>\n",
+        "should be tight w/ a blank line after an empty last item"
+    );
+
+    assert_eq!(
+        to_html("- a\n  - b\n\n\n"),
+        "<ul>\n<li>a\n<ul>\n<li>b</li>\n</ul>\n</li>\n</ul>\n",
+        "should be tight w/ blank lines after a sublist at the end of the document"
+    );
+
+    assert_eq!(
+        to_html("> - a\n> - b\n>\n> c"),
+        "<blockquote>\n<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<p>c</p>\n</blockquote>",
+        "should be tight w/ a blank line after a list in a block quote"
+    );
+
+    assert_eq!(
+        to_html("- a\n- b\n\n[c]: d"),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should be tight w/ a blank line after a list, before a definition"
+    );
+
+    assert_eq!(
+        to_html("* a\n\n\n* b"),
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ul>",
+        "should be loose w/ several blank lines between items"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* <div>\n\n  a\n* b",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<ul>\n<li>\n<div>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n</li>\n</ul>",
+        "should be loose w/ a blank line after HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* <!--\n\n  -->\n* b",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<ul>\n<li>\n<!--\n\n-->\n</li>\n<li>b</li>\n</ul>",
+        "should be tight w/ a blank line in HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n- b\n\n- c", &list_loose(false))?,
+        "<ul>\n<li>a</li>\n<li>b</li>\n<li>c</li>\n</ul>",
+        "should support `list_loose: Some(false)`"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n  - b\n1. c", &list_loose(true))?,
+        "<ul>\n<li>\n<p>a</p>\n<ul>\n<li>\n<p>b</p>\n</li>\n</ul>\n</li>\n</ul>\n<ol>\n<li>\n<p>c</p>\n</li>\n</ol>",
+        "should support `list_loose: Some(true)`"
+    );
+
+    Ok(())
+}
+
+/// Delimiter of a list, and numbers of its items.
+type Numbers = (Option<char>, Vec<Option<u32>>);
+
+#[test]
+fn list_numbers() -> Result<(), message::Message> {
+    let numbers = |value: &str| -> Result<Vec<Numbers>, message::Message> {
+        let tree = to_mdast(value, &Default::default())?;
+        let mut lists = vec![];
+        let mut stack = vec![&tree];
+
+        while let Some(node) = stack.pop() {
+            if let Node::List(list) = node {
+                let mut numbers = vec![];
+                for child in &list.children {
+                    if let Node::ListItem(item) = child {
+                        numbers.push(item.number);
+                    }
+                }
+                lists.push((list.delimiter, numbers));
+            }
+
+            if let Some(children) = node.children() {
+                let mut index = children.len();
+                while index > 0 {
+                    index -= 1;
+                    stack.push(&children[index]);
+                }
+            }
+        }
+
+        Ok(lists)
+    };
+
+    assert_eq!(
+        numbers("1. a\n1. b\n1. c")?,
+        vec![(Some('.'), vec![Some(1), Some(2), Some(3)])],
+        "should number items sequentially, ignoring their values"
+    );
+
+    assert_eq!(
+        numbers("3) a\n9) b")?,
+        vec![(Some(')'), vec![Some(3), Some(4)])],
+        "should number items from the start of the list, and expose `)`"
+    );
+
+    assert_eq!(
+        numbers("0. a\n\n\n5. b")?,
+        vec![(Some('.'), vec![Some(0), Some(1)])],
+        "should support a start of `0`, and blank lines between items"
+    );
+
+    assert_eq!(
+        numbers("* a\n* b")?,
+        vec![(None, vec![None, None])],
+        "should not number unordered items"
+    );
+
+    assert_eq!(
+        numbers("2. a\n   - b\n   - c\n3. d\n   1. e")?,
+        vec![
+            (Some('.'), vec![Some(2), Some(3)]),
+            (None, vec![None, None]),
+            (Some('.'), vec![Some(1)])
+        ],
+        "should number items in nested lists by their own list"
+    );
+
+    assert_eq!(
+        numbers("1. a\n1) b")?,
+        vec![(Some('.'), vec![Some(1)]), (Some(')'), vec![Some(1)])],
+        "should start a new list (and numbering) when the delimiter changes"
+    );
+
+    assert_eq!(
+        numbers("999999999. a\n1. b")?,
+        vec![(Some('.'), vec![Some(999_999_999), Some(1_000_000_000)])],
+        "should support big numbers"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_content_column() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("1. a\n\n   b\n\n10. c\n\n    d"),
+        "<ol>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ol>",
+        "should continue items at the content column of each item, for markers of different widths"
+    );
+
+    assert_eq!(
+        to_html("100. a\n\n     b\n\n123456789. c\n\n           d"),
+        "<ol start=\"100\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ol>",
+        "should continue items at the content column, for wide markers"
+    );
+
+    assert_eq!(
+        to_html("10. a\n\n     b"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>",
+        "should support text indented a bit more than the content column"
+    );
+
+    assert_eq!(
+        to_html("10. a\n\n        b\n\n1. c\n\n       d"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n</li>\n<li>\n<p>c</p>\n<pre><code>d\n</code></pre>\n</li>\n</ol>",
+        "should support code (indented) at 4 spaces after the content column"
+    );
+
+    assert_eq!(
+        to_html("9. a\n10. b\n\n    c"),
+        "<ol start=\"9\">\n<li>\n<p>a</p>\n</li>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ol>",
+        "should use the width of each marker, not of the first"
+    );
+
+    assert_eq!(
+        to_html("10.  a\n\n     b\n\n1.   c\n\n     d"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ol>",
+        "should include extra spaces after the marker in the content column"
+    );
+
+    assert_eq!(
+        to_html("10.     a\n\n    b"),
+        "<ol start=\"10\">\n<li>\n<pre><code>a\n</code></pre>\n<p>b</p>\n</li>\n</ol>",
+        "should use 1 space after the marker for the content column if the item starts with code (indented)"
+    );
+
+    assert_eq!(
+        to_html("10.\ta\n\n\tb\n\n1.\tc\n\n\td"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ol>",
+        "should support tabs after markers of different widths"
+    );
+
+    assert_eq!(
+        to_html("10.\n    a\n\n    b"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>",
+        "should support items starting with a blank line"
+    );
+
+    assert_eq!(
+        to_html("10. a\n\n    10. b\n\n        c\n\n1. d\n\n   10. e\n\n       f"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<ol start=\"10\">\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ol>\n</li>\n<li>\n<p>d</p>\n<ol start=\"10\">\n<li>\n<p>e</p>\n<p>f</p>\n</li>\n</ol>\n</li>\n</ol>",
+        "should support nested ordered lists with markers of different widths"
+    );
+
+    assert_eq!(
+        to_html("10. a\n\n\t1. b\n\n\t   c"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<ol>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ol>\n</li>\n</ol>",
+        "should support nested ordered lists indented with tabs"
+    );
+
+    assert_eq!(
+        to_html("> 10. a\n>\n>     b\n\n- 10. c\n\n      d"),
+        "<blockquote>\n<ol start=\"10\">\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ol>\n</blockquote>\n<ul>\n<li>\n<ol start=\"10\">\n<li>\n<p>c</p>\n<p>d</p>\n</li>\n</ol>\n</li>\n</ul>",
+        "should support ordered lists with wide markers in other containers"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_item_options() -> Result<(), message::Message> {
+    let only_dash = Options {
+        parse: ParseOptions {
+            list_item: ListItemOptions {
+                bullet_asterisk: false,
+                bullet_plus: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let no_dash_period = Options {
+        parse: ParseOptions {
+            list_item: ListItemOptions {
+                bullet_dash: false,
+                delimiter_period: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("- a\n\n* b\n\n+ c", &only_dash)?,
+        "<ul>\n<li>a</li>\n</ul>\n<p>* b</p>\n<p>+ c</p>",
+        "should not support disallowed bullets"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n+ b", &only_dash)?,
+        "<p>a\n+ b</p>",
+        "should not interrupt a paragraph with a disallowed bullet"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n+ b\n  - c", &only_dash)?,
+        "<ul>\n<li>a\n+ b\n<ul>\n<li>c</li>\n</ul>\n</li>\n</ul>",
+        "should support a disallowed bullet as lazy content of an item"
+    );
+
+    assert_eq!(
+        to_html_with_options("***\n* * *\n___", &only_dash)?,
+        "<hr />\n<hr />\n<hr />",
+        "should support thematic breaks w/ disallowed bullets"
+    );
+
+    assert_eq!(
+        to_html_with_options("---\n- - -\n\na\n---", &no_dash_period)?,
+        "<hr />\n<hr />\n<h2>a</h2>",
+        "should support thematic breaks and setext underlines w/ a disallowed dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n* b", &no_dash_period)?,
+        "<p>- a</p>\n<ul>\n<li>b</li>\n</ul>",
+        "should support allowed bullets w/ a disallowed dash"
+    );
+
+    assert_eq!(
+        to_html_with_options("1. a\n\n1) b", &no_dash_period)?,
+        "<p>1. a</p>\n<ol>\n<li>b</li>\n</ol>",
+        "should not support disallowed delimiters"
+    );
+
+    assert_eq!(
+        to_html_with_options("1. a\n\n1) b", &only_dash)?,
+        "<ol>\n<li>a</li>\n</ol>\n<ol>\n<li>b</li>\n</ol>",
+        "should configure delimiters separately from bullets"
+    );
+
+    assert_eq!(
+        to_html_with_options("> + a\n\n- > * b", &only_dash)?,
+        "<blockquote>\n<p>+ a</p>\n</blockquote>\n<ul>\n<li>\n<blockquote>\n<p>* b</p>\n</blockquote>\n</li>\n</ul>",
+        "should not support disallowed bullets in containers"
+    );
+
     Ok(())
 }
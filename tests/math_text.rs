@@ -234,3 +234,65 @@ fn math_text() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn math_wrapper() -> Result<(), message::Message> {
+    let math = |compile: CompileOptions| Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                math_text: true,
+                math_flow: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        compile,
+    };
+    let value = "$a$ `b`\n\n$$\nc\n$$\n\n```\nd\n```";
+
+    assert_eq!(
+        to_html_with_options(value, &math(CompileOptions::default()))?,
+        "<p><code class=\"language-math math-inline\">a</code> <code>b</code></p>\n<pre><code class=\"language-math math-display\">c\n</code></pre>\n<pre><code>d\n</code></pre>",
+        "should wrap math in `code` w/ `language-math` by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            value,
+            &math(CompileOptions {
+                math_tag_name: Some("span".into()),
+                math_class_name: Some("math".into()),
+                ..Default::default()
+            })
+        )?,
+        "<p><span class=\"math math-inline\">a</span> <code>b</code></p>\n<pre><span class=\"math math-display\">c\n</span></pre>\n<pre><code>d\n</code></pre>",
+        "should support `math_tag_name` and `math_class_name`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$a$\n\n$$\nb",
+            &math(CompileOptions {
+                math_class_name: Some(String::new()),
+                ..Default::default()
+            })
+        )?,
+        "<p><code class=\"math-inline\">a</code></p>\n<pre><code class=\"math-display\">b\n</code></pre>\n",
+        "should support an empty `math_class_name`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$a$\n\n$$\nb\n$$",
+            &math(CompileOptions {
+                math_tag_name: Some("x\"y".into()),
+                math_class_name: Some("<z>".into()),
+                ..Default::default()
+            })
+        )?,
+        "<p><x&quot;y class=\"&lt;z&gt; math-inline\">a</x&quot;y></p>\n<pre><x&quot;y class=\"&lt;z&gt; math-display\">b\n</x&quot;y></pre>",
+        "should encode `math_tag_name` and `math_class_name`"
+    );
+
+    Ok(())
+}
@@ -205,8 +205,10 @@ fn mdx_jsx_flow_essence() -> Result<(), message::Message> {
                     ordered: false,
                     spread: false,
                     start: None,
+                    delimiter: None,
                     children: vec![Node::ListItem(ListItem {
                         checked: None,
+                        number: None,
                         spread: false,
                         children: vec![Node::Paragraph(Paragraph {
                             children: vec![Node::Text(Text {
@@ -0,0 +1,124 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn base_url() -> Result<(), message::Message> {
+    let base = |url: &str| Options {
+        compile: CompileOptions {
+            base_url: Some(url.into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let docs = &base("https://example.com/docs/guide/?x=1#y");
+
+    assert_eq!(
+        to_html("[a](b)"),
+        "<p><a href=\"b\">a</a></p>",
+        "should not resolve URLs by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b.html)", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/b.html\">a</a></p>",
+        "should resolve relative URLs in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](b.png)", docs)?,
+        "<p><img src=\"https://example.com/docs/guide/b.png\" alt=\"a\" /></p>",
+        "should resolve relative URLs in images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]\n\n[a]: b.html", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/b.html\">a</a></p>\n",
+        "should resolve relative URLs in definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](./b) [c](../d) [e](../../../f) [g](h/./i/../j)", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/b\">a</a> <a href=\"https://example.com/docs/d\">c</a> <a href=\"https://example.com/f\">e</a> <a href=\"https://example.com/docs/guide/h/j\">g</a></p>",
+        "should remove dot segments"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](..)", docs)?,
+        "<p><a href=\"https://example.com/docs/\">a</a></p>",
+        "should keep a trailing slash after dot segments"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](/b?c#d)", docs)?,
+        "<p><a href=\"https://example.com/b?c#d\">a</a></p>",
+        "should resolve root-relative URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](//b.c/d)", docs)?,
+        "<p><a href=\"https://b.c/d\">a</a></p>",
+        "should resolve protocol-relative URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](#b)", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/?x=1#b\">a</a></p>",
+        "should resolve fragment-only URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](?b)", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/?b\">a</a></p>",
+        "should resolve query-only URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](<>)", docs)?,
+        "<p><a href=\"https://example.com/docs/guide/?x=1\">a</a></p>",
+        "should resolve empty URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](http://b.c/d) [e](mailto:f@g.h)", docs)?,
+        "<p><a href=\"http://b.c/d\">a</a> <a href=\"mailto:f@g.h\">e</a></p>",
+        "should not resolve absolute URLs"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://b.c/d> <e@f.g>", docs)?,
+        "<p><a href=\"https://b.c/d\">https://b.c/d</a> <a href=\"mailto:e@f.g\">e@f.g</a></p>",
+        "should not resolve autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b)", &base("https://example.com/docs"))?,
+        "<p><a href=\"https://example.com/b\">a</a></p>",
+        "should drop the last segment of the base if it does not end in a slash"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b)", &base("https://example.com"))?,
+        "<p><a href=\"https://example.com/b\">a</a></p>",
+        "should support a base w/o path"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b) [c](/d) [e](//f.g)", &base("/docs/"))?,
+        "<p><a href=\"/docs/b\">a</a> <a href=\"/d\">c</a> <a href=\"//f.g\">e</a></p>",
+        "should support a relative base"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b c)", docs)?,
+        "<p>[a](b c)</p>",
+        "should not affect things that are not links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](b)", &base("javascript:alert(1)//"))?,
+        "<p><a href=\"\">a</a></p>",
+        "should sanitize resolved URLs"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, LineEnding, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn block_whitespace() -> Result<(), message::Message> {
+    let normalize = Options {
+        compile: CompileOptions {
+            normalize_block_whitespace: true,
+            allow_dangerous_html: true,
+            ..CompileOptions::default()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("a\n\nb\r\n\r\n\r\nc"),
+        "<p>a</p>\n<p>b</p>\r\n<p>c</p>",
+        "should keep line endings between blocks by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb\r\n\r\n\r\nc", &normalize)?,
+        "<p>a</p>\n<p>b</p>\n<p>c</p>\n",
+        "should use one line ending between blocks and at the end w/ `normalize_block_whitespace`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a", &normalize)?,
+        to_html_with_options("\n\na\n\n\n", &normalize)?,
+        "should not depend on blank lines around the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("", &normalize)?,
+        "",
+        "should not add a line ending to empty output"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]: b\n\n\n", &normalize)?,
+        "",
+        "should not add a line ending to output that is only whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>\n>\n> - b\n>\n>\n>   c\n\n\n***", &normalize)?,
+        "<blockquote>\n<p>a</p>\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>\n</blockquote>\n<hr />\n",
+        "should normalize in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "```\na\r\n\r\n\nb\n```\n\n<!--\n\n\n-->\n\nc  \r\nd",
+            &normalize
+        )?,
+        "<pre><code>a\r\n\r\n\nb\n</code></pre>\n<!--\n\n\n-->\n<p>c<br />\r\nd</p>\n",
+        "should not change whitespace in code, HTML, or text"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\r\n\r\nb\n\nc",
+            &Options {
+                compile: CompileOptions {
+                    normalize_block_whitespace: true,
+                    line_ending: Some(LineEnding::LineFeed),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a</p>\n<p>b</p>\n<p>c</p>\n",
+        "should support `line_ending`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c", &normalize)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>c <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should support footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                compile: CompileOptions {
+                    normalize_block_whitespace: true,
+                    wrap: Some("article".into()),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<article>\n<p>a</p>\n</article>\n",
+        "should support `wrap`"
+    );
+
+    Ok(())
+}
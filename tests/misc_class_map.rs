@@ -0,0 +1,126 @@
+use markdown::{message, to_html_with_options, CompileOptions, EventName, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+
+#[test]
+fn class_map() -> Result<(), message::Message> {
+    let classes = |names: &[(EventName, &str)]| -> Options {
+        let mut class_map = BTreeMap::new();
+        let mut index = 0;
+
+        while index < names.len() {
+            class_map.insert(names[index].0.clone(), names[index].1.into());
+            index += 1;
+        }
+
+        Options {
+            parse: ParseOptions::gfm(),
+            compile: CompileOptions {
+                class_map,
+                ..CompileOptions::gfm()
+            },
+        }
+    };
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n=\n\nc\n\n***", &classes(&[]))?,
+        "<h1>a</h1>\n<h1>b</h1>\n<p>c</p>\n<hr />",
+        "should not add classes by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a\n\nb\n=\n\nc\n\n***",
+            &classes(&[
+                (EventName::HeadingAtx, "atx"),
+                (EventName::HeadingSetext, "setext"),
+                (EventName::Paragraph, "para"),
+                (EventName::ThematicBreak, "rule")
+            ])
+        )?,
+        "<h1 class=\"atx\">a</h1>\n<h1 class=\"setext\">b</h1>\n<p class=\"para\">c</p>\n<hr class=\"rule\" />",
+        "should add classes to headings, paragraphs, and thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> * a\n>\n>   b\n\n1. c",
+            &classes(&[
+                (EventName::BlockQuote, "quote"),
+                (EventName::ListUnordered, "ul"),
+                (EventName::ListOrdered, "ol"),
+                (EventName::ListItem, "item")
+            ])
+        )?,
+        "<blockquote class=\"quote\">\n<ul class=\"ul\">\n<li class=\"item\">\n<p>a</p>\n<p>b</p>\n</li>\n</ul>\n</blockquote>\n<ol class=\"ol\">\n<li class=\"item\">c</li>\n</ol>",
+        "should add classes to block quotes, lists, and list items"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "    a\n\n```js\nb\n```",
+            &classes(&[
+                (EventName::CodeIndented, "indented"),
+                (EventName::CodeFenced, "fenced")
+            ])
+        )?,
+        "<pre class=\"indented\"><code>a\n</code></pre>\n<pre class=\"fenced\"><code class=\"language-js\">b\n</code></pre>",
+        "should add classes to code"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "| a |\n| - |\n| b |",
+            &classes(&[
+                (EventName::GfmTable, "table"),
+                (EventName::GfmTableRow, "row"),
+                (EventName::GfmTableCell, "cell")
+            ])
+        )?,
+        "<table class=\"table\">\n<thead>\n<tr class=\"row\">\n<th class=\"cell\">a</th>\n</tr>\n</thead>\n<tbody>\n<tr class=\"row\">\n<td class=\"cell\">b</td>\n</tr>\n</tbody>\n</table>",
+        "should add classes to tables, rows, and cells"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a *b*",
+            &classes(&[(EventName::Paragraph, "x"), (EventName::Emphasis, "y")])
+        )?,
+        "<p class=\"x\">a <em>b</em></p>",
+        "should ignore other names"
+    );
+
+    assert_eq!(
+        to_html_with_options("a", &classes(&[(EventName::Paragraph, "a\"b<c")]))?,
+        "<p class=\"a&quot;b&lt;c\">a</p>",
+        "should encode classes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a", &classes(&[(EventName::Paragraph, "")]))?,
+        "<p>a</p>",
+        "should not add a `class` attribute for an empty string"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a\n\nb",
+            &Options {
+                compile: CompileOptions {
+                    source_positions: true,
+                    heading_ids: true,
+                    ..classes(&[
+                        (EventName::HeadingAtx, "title"),
+                        (EventName::Paragraph, "para")
+                    ])
+                    .compile
+                },
+                ..Options::default()
+            }
+        )?,
+        "<h1 data-sourcepos=\"1:1-1:4\" class=\"title\" id=\"a\">a</h1>\n<p data-sourcepos=\"3:1-3:2\" class=\"para\">b</p>",
+        "should add classes w/ positions and ids"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{message, to_html, to_html_with_options, CompileOptions, HtmlFilter, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -30,5 +30,152 @@ fn dangerous_html() -> Result<(), message::Message> {
         "should be unsafe w/ `allowDangerousHtml`"
     );
 
+    let only = |filter: HtmlFilter| Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            html_filter: filter,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let comments = &only(HtmlFilter {
+        comment: true,
+        ..HtmlFilter::none()
+    });
+
+    assert_eq!(
+        to_html_with_options("<!-- a -->\n\n<script>b</script>", comments)?,
+        "<!-- a -->\n&lt;script&gt;b&lt;/script&gt;",
+        "should support `html_filter` to only allow some kinds of HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <!-- b --> <script>c</script>", comments)?,
+        "<p>a <!-- b --> &lt;script&gt;c&lt;/script&gt;</p>",
+        "should support `html_filter` to only allow some kinds of HTML (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<!-- a -->",
+            &Options {
+                compile: CompileOptions {
+                    html_filter: HtmlFilter::default(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "&lt;!-- a --&gt;",
+        "should not allow HTML w/o `allow_dangerous_html`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<script>a</script>\n\n<!-- b -->\n\n<?c?>\n\n<!D>\n\n<![CDATA[e]]>\n\n<div>\n\n<x-y>",
+            danger
+        )?,
+        "<script>a</script>\n<!-- b -->\n<?c?>\n<!D>\n<![CDATA[e]]>\n<div>\n<x-y>",
+        "should allow all kinds of HTML by default"
+    );
+
+    let kinds = [
+        (
+            "<script>a</script>",
+            "&lt;script&gt;a&lt;/script&gt;",
+            "raw",
+        ),
+        ("  <!-- a -->", "  &lt;!-- a --&gt;", "comment"),
+        ("<?a?>", "&lt;?a?&gt;", "instruction"),
+        ("<!DOCTYPE html>", "&lt;!DOCTYPE html&gt;", "declaration"),
+        ("<![CDATA[a]]>", "&lt;![CDATA[a]]&gt;", "cdata"),
+        ("<DIV>", "&lt;DIV&gt;", "basic"),
+        ("<x-y>", "&lt;x-y&gt;", "complete"),
+    ];
+    let mut index = 0;
+
+    while index < kinds.len() {
+        let (value, expected, kind) = kinds[index];
+        let mut filter = HtmlFilter::default();
+        let mut other = 0;
+
+        match kind {
+            "raw" => filter.raw = false,
+            "comment" => filter.comment = false,
+            "instruction" => filter.instruction = false,
+            "declaration" => filter.declaration = false,
+            "cdata" => filter.cdata = false,
+            "basic" => filter.basic = false,
+            _ => filter.complete = false,
+        }
+
+        assert_eq!(
+            to_html_with_options(value, &only(filter.clone()))?,
+            expected,
+            "should support turning off {} HTML",
+            kind
+        );
+
+        while other < kinds.len() {
+            if other != index {
+                assert_eq!(
+                    to_html_with_options(kinds[other].0, &only(filter.clone()))?,
+                    kinds[other].0,
+                    "should not turn off {} HTML when turning off {} HTML",
+                    kinds[other].2,
+                    kind
+                );
+            }
+
+            other += 1;
+        }
+
+        index += 1;
+    }
+
+    assert_eq!(
+        to_html_with_options(
+            "<pre>\na\n</pre>",
+            &only(HtmlFilter {
+                raw: false,
+                ..HtmlFilter::default()
+            })
+        )?,
+        "&lt;pre&gt;\na\n&lt;/pre&gt;",
+        "should filter all lines of HTML (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "</script>",
+            &only(HtmlFilter {
+                raw: false,
+                ..HtmlFilter::default()
+            })
+        )?,
+        "</script>",
+        "should classify closing raw tags as complete"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "<title>a</title>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    gfm_tagfilter: true,
+                    html_filter: HtmlFilter {
+                        basic: false,
+                        ..HtmlFilter::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "&lt;title&gt;a&lt;/title&gt;",
+        "should not apply the GFM tag filter to HTML that is not allowed"
+    );
+
     Ok(())
 }
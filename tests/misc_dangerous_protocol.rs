@@ -113,6 +113,24 @@ fn dangerous_protocol_image() {
         "<p><img src=\"a/b:c\" alt=\"\" /></p>",
         "should allow a colon in a path"
     );
+
+    assert_eq!(
+        to_html("![](data:image/png;base64,a) ![](DATA:IMAGE/GIF,a) ![](data:image/jpeg) ![](data:image/webp;a)"),
+        "<p><img src=\"data:image/png;base64,a\" alt=\"\" /> <img src=\"DATA:IMAGE/GIF,a\" alt=\"\" /> <img src=\"data:image/jpeg\" alt=\"\" /> <img src=\"data:image/webp;a\" alt=\"\" /></p>",
+        "should allow `data:` of safe image types"
+    );
+
+    assert_eq!(
+        to_html("![](data:image/svg+xml,a) ![](data:image/pngx,a) ![](data:text/html,a)"),
+        "<p><img src=\"\" alt=\"\" /> <img src=\"\" alt=\"\" /> <img src=\"\" alt=\"\" /></p>",
+        "should not allow `data:` of other types"
+    );
+
+    assert_eq!(
+        to_html("![](&#x6A;avascript:alert(1))"),
+        "<p><img src=\"\" alt=\"\" /></p>",
+        "should not allow dangerous protocols obfuscated with character references"
+    );
 }
 
 #[test]
@@ -194,4 +212,24 @@ fn dangerous_protocol_link() {
         "<p><a href=\"a/b:c\"></a></p>",
         "should allow a colon in a path"
     );
+
+    assert_eq!(
+        to_html("[](vbscript:a) [](file:///a) [](data:text/html,a) [](data:image/png,a)"),
+        "<p><a href=\"\"></a> <a href=\"\"></a> <a href=\"\"></a> <a href=\"\"></a></p>",
+        "should not allow `vbscript:`, `file:`, or `data:`"
+    );
+
+    assert_eq!(
+        to_html(
+            "[](&#x6A;avascript:alert(1)) [](JaVaScRiPt:alert(1)) [](<java&#9;script:alert(1)>)"
+        ),
+        "<p><a href=\"\"></a> <a href=\"\"></a> <a href=\"\"></a></p>",
+        "should not allow dangerous protocols obfuscated with character references or case"
+    );
+
+    assert_eq!(
+        to_html("[a]\n\n[a]: &#x6A;avascript:alert(1)"),
+        "<p><a href=\"\">a</a></p>\n",
+        "should not allow dangerous protocols in definitions"
+    );
 }
@@ -59,5 +59,27 @@ fn default_line_ending() -> Result<(), message::Message> {
         "should support the given line ending, even if line endings exist"
     );
 
+    assert_eq!(
+        to_html("# a\r\n\r\n> b\r\n> c\r\n\r\n* d\r\n\r\n  e\r\n\r\n```\r\nf\r\n```\r\n"),
+        "<h1>a</h1>\r\n<blockquote>\r\n<p>b\r\nc</p>\r\n</blockquote>\r\n<ul>\r\n<li>\r\n<p>d</p>\r\n<p>e</p>\r\n</li>\r\n</ul>\r\n<pre><code>f\r\n</code></pre>\r\n",
+        "should use the first line ending for all extra line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> a\r\n",
+            &Options {
+                compile: CompileOptions {
+                    line_ending: Some(LineEnding::LineFeed),
+                    default_line_ending: LineEnding::CarriageReturn,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n",
+        "should prefer `line_ending` over the first line ending and `default_line_ending`"
+    );
+
     Ok(())
 }
@@ -0,0 +1,254 @@
+use markdown::{
+    message::{self, Message, Place},
+    to_html_with_diagnostics,
+    unist::Position,
+    CompileOptions, HtmlFilter, Options,
+};
+use pretty_assertions::assert_eq;
+
+/// Get the `rule_id` and place of each message.
+fn places(value: &str, options: &Options) -> Result<Vec<(String, String)>, message::Message> {
+    let (_, messages) = to_html_with_diagnostics(value, options)?;
+    Ok(messages
+        .iter()
+        .map(|d| {
+            (
+                d.rule_id.to_string(),
+                d.place.as_ref().map_or(String::new(), |d| d.to_string()),
+            )
+        })
+        .collect())
+}
+
+#[test]
+fn diagnostics() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_with_diagnostics("[a]", &Options::default())?,
+        (
+            "<p>[a]</p>".into(),
+            vec![Message {
+                place: Some(Box::new(Place::Position(Position::new(1, 1, 0, 1, 4, 3)))),
+                reason: "Unexpected reference to undefined definition `a`, expected a definition (such as `[a]: https://example.com`)".into(),
+                rule_id: Box::new("undefined-reference".into()),
+                source: Box::new("markdown-rs".into()),
+            }]
+        ),
+        "should warn about undefined shortcut references"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("[a]\n\n[a]: b", &Options::default())?,
+        ("<p><a href=\"b\">a</a></p>\n".into(), vec![]),
+        "should not warn about defined references"
+    );
+
+    assert_eq!(
+        places("a [b][] c", &Options::default())?,
+        vec![("undefined-reference".into(), "1:3-1:6".into())],
+        "should warn about undefined collapsed references"
+    );
+
+    assert_eq!(
+        places("[a][b]", &Options::default())?,
+        vec![("undefined-reference".into(), "1:4-1:7".into())],
+        "should warn about the reference of an undefined full reference"
+    );
+
+    assert_eq!(
+        places("[a][b]\n\n[b]: c", &Options::default())?,
+        vec![],
+        "should not warn about the label of a defined full reference"
+    );
+
+    assert_eq!(
+        places("[a](b), [c](d", &Options::default())?,
+        vec![],
+        "should not warn about resources"
+    );
+
+    assert_eq!(
+        places("![a]", &Options::default())?,
+        vec![("undefined-reference".into(), "1:1-1:5".into())],
+        "should warn about undefined image references"
+    );
+
+    assert_eq!(
+        places("[], [ ], \\[a], `[b]`\n\n    [c]", &Options::default())?,
+        vec![],
+        "should not warn about empty labels, escaped brackets, or code"
+    );
+
+    assert_eq!(
+        places("> a [b\n> c]", &Options::default())?,
+        vec![("undefined-reference".into(), "1:5-2:5".into())],
+        "should warn about references spanning lines in containers"
+    );
+
+    assert_eq!(
+        places("[a]\n\n# [b]\n\n[c]", &Options::default())?,
+        vec![
+            ("undefined-reference".into(), "1:1-1:4".into()),
+            ("undefined-reference".into(), "3:3-3:6".into()),
+            ("undefined-reference".into(), "5:1-5:4".into())
+        ],
+        "should sort warnings by where they start"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("[^a]", &Options::gfm())?.1[0].reason,
+        "Unexpected reference to undefined definition `^a`, expected a definition (such as `[^a]: https://example.com`)",
+        "should warn about undefined footnote calls"
+    );
+
+    assert_eq!(
+        places("[^a]\n\n[^a]: b", &Options::gfm())?,
+        vec![],
+        "should not warn about defined footnote calls"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diagnostics_character_reference() -> Result<(), message::Message> {
+    assert_eq!(
+        places(
+            "&#0; &#xD800; &#x110000; &#1114112; &#xFFFE; &#x9F;",
+            &Options::default()
+        )?,
+        vec![
+            ("invalid-character-reference".into(), "1:1-1:5".into()),
+            ("invalid-character-reference".into(), "1:6-1:14".into()),
+            ("invalid-character-reference".into(), "1:15-1:25".into()),
+            ("invalid-character-reference".into(), "1:26-1:36".into()),
+            ("invalid-character-reference".into(), "1:37-1:45".into()),
+            ("invalid-character-reference".into(), "1:46-1:52".into())
+        ],
+        "should warn about disallowed numeric character references"
+    );
+
+    assert_eq!(
+        to_html_with_diagnostics("a &#0; b", &Options::default())?,
+        (
+            "<p>a \u{FFFD} b</p>".into(),
+            vec![Message {
+                place: Some(Box::new(Place::Position(Position::new(1, 3, 2, 1, 7, 6)))),
+                reason: "Unexpected character reference `&#0;` to a disallowed code point, expected an allowed character (it is replaced by U+FFFD REPLACEMENT CHARACTER)".into(),
+                rule_id: Box::new("invalid-character-reference".into()),
+                source: Box::new("markdown-rs".into()),
+            }]
+        ),
+        "should warn about a disallowed numeric character reference"
+    );
+
+    assert_eq!(
+        places(
+            "&amp; &#123; &#x9; &#xFFFD; &#99999999; &#0",
+            &Options::default()
+        )?,
+        vec![],
+        "should not warn about allowed references, or things that are not references"
+    );
+
+    assert_eq!(
+        places("[a](b&#0;) `&#0;`\n\n```c&#0;\n```", &Options::default())?,
+        vec![
+            ("invalid-character-reference".into(), "1:6-1:10".into()),
+            ("invalid-character-reference".into(), "3:5-3:9".into())
+        ],
+        "should warn in strings (such as destinations and info), but not in code"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn diagnostics_escaped_html() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html_with_diagnostics("a <b>c</b>", &Options::default())?,
+        (
+            "<p>a &lt;b&gt;c&lt;/b&gt;</p>".into(),
+            vec![
+                Message {
+                    place: Some(Box::new(Place::Position(Position::new(1, 3, 2, 1, 6, 5)))),
+                    reason: "Unexpected HTML, expected markdown (it is escaped, and shown as text, because `allow_dangerous_html` is off)".into(),
+                    rule_id: Box::new("escaped-html".into()),
+                    source: Box::new("markdown-rs".into()),
+                },
+                Message {
+                    place: Some(Box::new(Place::Position(Position::new(1, 7, 6, 1, 11, 10)))),
+                    reason: "Unexpected HTML, expected markdown (it is escaped, and shown as text, because `allow_dangerous_html` is off)".into(),
+                    rule_id: Box::new("escaped-html".into()),
+                    source: Box::new("markdown-rs".into()),
+                }
+            ]
+        ),
+        "should warn about escaped HTML (text)"
+    );
+
+    assert_eq!(
+        places("<div>\na\n\n[b]\n\n<!--c-->", &Options::default())?,
+        vec![
+            ("escaped-html".into(), "1:1-2:2".into()),
+            ("undefined-reference".into(), "4:1-4:4".into()),
+            ("escaped-html".into(), "6:1-6:9".into())
+        ],
+        "should warn about escaped HTML (flow), in order with other warnings"
+    );
+
+    assert_eq!(
+        places(
+            "<div>\n\na <b>c</b>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        vec![],
+        "should not warn about allowed HTML"
+    );
+
+    assert_eq!(
+        places(
+            "<div>\n\na <!--b--> <i>c</i>",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    html_filter: HtmlFilter {
+                        comment: true,
+                        ..HtmlFilter::none()
+                    },
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        vec![
+            ("escaped-html".into(), "1:1-1:6".into()),
+            ("escaped-html".into(), "3:12-3:15".into()),
+            ("escaped-html".into(), "3:16-3:20".into())
+        ],
+        "should warn about HTML that is not allowed by `html_filter`"
+    );
+
+    assert_eq!(
+        places("a `<b>` \\<c> &lt;d&gt;\n\n    <e>", &Options::default())?,
+        vec![],
+        "should not warn about things that are not HTML"
+    );
+
+    let (_, messages) = to_html_with_diagnostics("<a>\n\n<b> <c>", &Options::default())?;
+    assert_eq!(
+        messages
+            .iter()
+            .filter(|d| d.rule_id.as_str() == "escaped-html")
+            .count(),
+        3,
+        "should support counting escaped HTML"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Invisibles, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn drop_empty_blocks() -> Result<(), message::Message> {
+    let drop = Options {
+        compile: CompileOptions {
+            drop_empty_blocks: true,
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("- \n- \n"),
+        "<ul>\n<li></li>\n<li></li>\n</ul>\n",
+        "should keep empty list items by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n-\n- b", &drop)?,
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should drop empty list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n*\n\n* c", &drop)?,
+        "<ul>\n<li>\n<p>a</p>\n</li>\n<li>\n<p>c</p>\n</li>\n</ul>",
+        "should drop empty list items in loose lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\n- \n- \n\nb", &drop)?,
+        "<p>a</p>\n<p>b</p>",
+        "should drop lists w/ only empty items"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n  * \n  *\n- -", &drop)?,
+        "<ul>\n<li>\n<p>a</p>\n</li>\n</ul>",
+        "should drop list items w/ only empty lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("3. a\n4.\n5. b", &drop)?,
+        "<ol start=\"3\">\n<li>a</li>\n<li>b</li>\n</ol>",
+        "should not change the start of ordered lists"
+    );
+
+    assert_eq!(
+        to_html_with_options("- [ ]\n- [x] a\n-", &drop)?,
+        "<ul>\n<li>[ ]</li>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a</li>\n</ul>",
+        "should not drop list items w/ content"
+    );
+
+    assert_eq!(
+        to_html_with_options("> \n\n>\n> a", &drop)?,
+        "<blockquote>\n</blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>",
+        "should not drop other blocks"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\n\n\u{200B}\n\n- \u{200B}\n- b\n\n> \u{FEFF}",
+            &Options {
+                compile: CompileOptions {
+                    drop_empty_blocks: true,
+                    invisibles: Invisibles::Strip,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a</p>\n<ul>\n<li>b</li>\n</ul>\n<blockquote>\n</blockquote>",
+        "should drop paragraphs and list items that are empty when compiled"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "- a\n-\n\n  b",
+            &Options {
+                compile: CompileOptions {
+                    drop_empty_blocks: true,
+                    source_positions: true,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<ul data-sourcepos=\"1:1-2:2\">\n<li data-sourcepos=\"1:1-1:4\">a</li>\n</ul>\n<p data-sourcepos=\"4:1-4:4\">b</p>",
+        "should support source positions"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^1]\n\n[^1]: -\n    - b", &drop)?,
+        "<p>a<sup><a href=\"#user-content-fn-1\" id=\"user-content-fnref-1\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-1\">\n<ul>\n<li>b</li>\n</ul>\n<a href=\"#user-content-fnref-1\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a>\n</li>\n</ol>\n</section>\n",
+        "should drop empty list items in footnote definitions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use markdown::{event_source, message, to_events, EventKind, EventName, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn events() -> Result<(), message::Message> {
+    let value = "> *a **b *c* d** e*\n\n\tf";
+    let events = to_events(value, &ParseOptions::default())?;
+    let names = events
+        .iter()
+        .filter(|d| d.kind == EventKind::Enter)
+        .map(|d| d.name.clone())
+        .take(4)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        vec![
+            EventName::BlockQuote,
+            EventName::BlockQuotePrefix,
+            EventName::BlockQuoteMarker,
+            EventName::SpaceOrTab,
+        ],
+        "should expose events"
+    );
+
+    let mut sources = vec![];
+
+    for (index, event) in events.iter().enumerate() {
+        if event.kind == EventKind::Exit && event.name == EventName::Emphasis {
+            sources.push(event_source(
+                value,
+                &events,
+                index,
+                &ParseOptions::default(),
+            ));
+        }
+    }
+
+    assert_eq!(
+        sources,
+        vec!["*c*", "*a **b *c* d** e*"],
+        "should get the source of events, including nested events of the same name"
+    );
+
+    let mut sources = vec![];
+
+    for (index, event) in events.iter().enumerate() {
+        if event.kind == EventKind::Enter && event.name == EventName::Emphasis {
+            sources.push(event_source(
+                value,
+                &events,
+                index,
+                &ParseOptions::default(),
+            ));
+        }
+    }
+
+    assert_eq!(
+        sources,
+        vec!["*a **b *c* d** e*", "*c*"],
+        "should get the source of enter events, including nested events of the same name"
+    );
+
+    let index = events
+        .iter()
+        .position(|d| d.kind == EventKind::Exit && d.name == EventName::CodeFlowChunk)
+        .unwrap();
+
+    assert_eq!(
+        event_source(value, &events, index, &ParseOptions::default()),
+        "f",
+        "should get the source of events after tabs"
+    );
+
+    assert_eq!(
+        to_events("~a~", &Options::gfm().parse)?
+            .iter()
+            .any(|d| d.name == EventName::GfmStrikethrough),
+        true,
+        "should support options"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn events_whitespace() -> Result<(), message::Message> {
+    // Get the `line:column (index)` range of each whitespace event.
+    let whitespace = |value: &str| -> Result<Vec<String>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        let mut result = vec![];
+        let mut index = 0;
+        while index < events.len() {
+            let event = &events[index];
+            if event.kind == EventKind::Enter
+                && matches!(
+                    event.name,
+                    EventName::SpaceOrTab | EventName::HardBreakTrailing
+                )
+            {
+                let exit = &events[index + 1];
+                result.push(format!(
+                    "{:?} {}:{} ({})-{}:{} ({})",
+                    event.name,
+                    event.point.line,
+                    event.point.column,
+                    event.point.index,
+                    exit.point.line,
+                    exit.point.column,
+                    exit.point.index
+                ));
+            }
+            index += 1;
+        }
+        Ok(result)
+    };
+
+    assert_eq!(
+        whitespace("a   \n  b")?,
+        vec![
+            "HardBreakTrailing 1:2 (1)-1:5 (4)",
+            "SpaceOrTab 2:1 (5)-2:3 (7)"
+        ],
+        "should keep the positions of whitespace around line endings"
+    );
+
+    assert_eq!(
+        whitespace("ab\t\t\n\t\tc \t")?,
+        vec![
+            "SpaceOrTab 1:3 (2)-1:9 (4)",
+            "SpaceOrTab 2:1 (5)-2:9 (7)",
+            "SpaceOrTab 2:10 (8)-2:13 (10)"
+        ],
+        "should expand tabs in the columns of whitespace"
+    );
+
+    let value = "a \t \nb";
+    let events = to_events(value, &ParseOptions::default())?;
+    assert_eq!(
+        event_source(value, &events, 4, &ParseOptions::default()),
+        " \t ",
+        "should support getting the source of whitespace that is not a hard break"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn events_points() -> Result<(), message::Message> {
+    let events = to_events("-\t\ta", &ParseOptions::default())?;
+    let point = |name: EventName, kind: EventKind| {
+        let event = events
+            .iter()
+            .find(|d| d.name == name && d.kind == kind)
+            .unwrap();
+        (
+            event.point.to_byte_offset(),
+            event.point.column,
+            event.point.vs,
+        )
+    };
+
+    assert_eq!(
+        point(EventName::ListItemPrefix, EventKind::Exit),
+        (1, 3, 1),
+        "should use a virtual step for the part of a tab in the list item prefix"
+    );
+
+    assert_eq!(
+        point(EventName::CodeIndented, EventKind::Enter),
+        (1, 3, 1),
+        "should start code (indented) in the middle of that tab"
+    );
+
+    assert_eq!(
+        point(EventName::CodeFlowChunk, EventKind::Enter),
+        (2, 7, 2),
+        "should start the code itself in the middle of the next tab"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+#[test]
+fn external_link() -> Result<(), message::Message> {
+    let rel = Options {
+        compile: CompileOptions {
+            external_link_rel: Some("nofollow noopener".into()),
+            external_link_target: Some("_blank".into()),
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+    let base = Options {
+        compile: CompileOptions {
+            base_url: Some("https://example.com/docs/".into()),
+            external_link_rel: Some("nofollow noopener".into()),
+            ..CompileOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("[a](https://b.c)"),
+        "<p><a href=\"https://b.c\">a</a></p>",
+        "should not add `rel` or `target` by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](https://b.c \"d\")", &rel)?,
+        "<p><a href=\"https://b.c\" title=\"d\" rel=\"nofollow noopener\" target=\"_blank\">a</a></p>",
+        "should add `rel` and `target` to external links"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a](//b.c/d) <https://e.f> www.g.h https://i.j", &rel)?,
+        "<p><a href=\"//b.c/d\" rel=\"nofollow noopener\" target=\"_blank\">a</a> <a href=\"https://e.f\" rel=\"nofollow noopener\" target=\"_blank\">https://e.f</a> <a href=\"http://www.g.h\" rel=\"nofollow noopener\" target=\"_blank\">www.g.h</a> <a href=\"https://i.j\" rel=\"nofollow noopener\" target=\"_blank\">https://i.j</a></p>",
+        "should support protocol-relative links, autolinks, and GFM autolink literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b) [c](/d) [e](#f) [g](?h) <mailto:i@j.k> l@m.n",
+            &rel
+        )?,
+        "<p><a href=\"b\">a</a> <a href=\"/d\">c</a> <a href=\"#f\">e</a> <a href=\"?h\">g</a> <a href=\"mailto:i@j.k\">mailto:i@j.k</a> <a href=\"mailto:l@m.n\">l@m.n</a></p>",
+        "should not add `rel` or `target` to links w/o host"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a](https://b.c)", &rel)?,
+        "<p><img src=\"https://b.c\" alt=\"a\" /></p>",
+        "should not add `rel` or `target` to images"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a]\n\n[a]: https://b.c", &rel)?,
+        "<p><a href=\"https://b.c\" rel=\"nofollow noopener\" target=\"_blank\">a</a></p>\n",
+        "should support references"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b) [c](https://example.com/d) [e](https://EXAMPLE.com) [f](https://other.com)",
+            &base
+        )?,
+        "<p><a href=\"https://example.com/docs/b\">a</a> <a href=\"https://example.com/d\">c</a> <a href=\"https://EXAMPLE.com\">e</a> <a href=\"https://other.com\" rel=\"nofollow noopener\">f</a></p>",
+        "should compare hosts to the host of `base_url`, case-insensitively"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](https://user@example.com) [b](https://example.com:8080)",
+            &base
+        )?,
+        "<p><a href=\"https://user@example.com\">a</a> <a href=\"https://example.com:8080\" rel=\"nofollow noopener\">b</a></p>",
+        "should ignore user info, but not ports"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](https://b.c) [d](/e)",
+            &Options {
+                compile: CompileOptions {
+                    external_link_rel: Some("external".into()),
+                    is_external_link: Some(Rc::new(|url: &str| url.starts_with('/'))),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><a href=\"https://b.c\">a</a> <a href=\"/e\" rel=\"external\">d</a></p>",
+        "should support `is_external_link`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](https://b.c)",
+            &Options {
+                compile: CompileOptions {
+                    external_link_target: Some("\"<x>\"".into()),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><a href=\"https://b.c\" target=\"&quot;&lt;x&gt;&quot;\">a</a></p>",
+        "should encode the values"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](https://b.c)",
+            &Options {
+                compile: CompileOptions {
+                    external_link_rel: Some("nofollow".into()),
+                    url_transform: Some(Rc::new(|_url| None)),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><a>a</a></p>",
+        "should not add `rel` to links w/o `href`"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn heading_ids() -> Result<(), message::Message> {
+    let ids = Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("# a"),
+        "<h1>a</h1>",
+        "should not add ids by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a", &ids)?,
+        "<h1 id=\"a\">a</h1>",
+        "should add an id to a heading (atx)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n=", &ids)?,
+        "<h1 id=\"a\">a</h1>",
+        "should add an id to a heading (setext)"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Hello World", &ids)?,
+        "<h1 id=\"hello-world\">Hello World</h1>",
+        "should lowercase and turn spaces into dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a, b! (c) 'd' \"e\" f.g", &ids)?,
+        "<h1 id=\"a-b-c-d-e-fg\">a, b! (c) 'd' &quot;e&quot; f.g</h1>",
+        "should drop punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a-b_c", &ids)?,
+        "<h1 id=\"a-b_c\">a-b_c</h1>",
+        "should keep dashes and underscores"
+    );
+
+    assert_eq!(
+        to_html_with_options("#  a  b ", &ids)?,
+        "<h1 id=\"a--b\">a  b</h1>",
+        "should not collapse inner spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("# Über Ça va? 日本語 Привет", &ids)?,
+        "<h1 id=\"über-ça-va-日本語-привет\">Über Ça va? 日本語 Привет</h1>",
+        "should keep and lowercase non-ASCII letters"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a 😀 b", &ids)?,
+        "<h1 id=\"a--b\">a 😀 b</h1>",
+        "should drop emoji"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n# a\n# a", &ids)?,
+        "<h1 id=\"a\">a</h1>\n<h1 id=\"a-1\">a</h1>\n<h1 id=\"a-2\">a</h1>",
+        "should deduplicate ids"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n# a\n# a-1", &ids)?,
+        "<h1 id=\"a\">a</h1>\n<h1 id=\"a-1\">a</h1>\n<h1 id=\"a-1-1\">a-1</h1>",
+        "should deduplicate ids that look like deduplicated ids"
+    );
+
+    assert_eq!(
+        to_html_with_options("# A\n\nb\n-", &ids)?,
+        "<h1 id=\"a\">A</h1>\n<h2 id=\"b\">b</h2>",
+        "should support different ranks"
+    );
+
+    assert_eq!(
+        to_html_with_options("# *a* **b** `C d`", &ids)?,
+        "<h1 id=\"a-b-c-d\"><em>a</em> <strong>b</strong> <code>C d</code></h1>",
+        "should use the text of emphasis, strong, and code"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a &amp; &#66; &#x43; \\*", &ids)?,
+        "<h1 id=\"a--b-c-\">a &amp; B C *</h1>",
+        "should use the value of character references and escapes"
+    );
+
+    assert_eq!(
+        to_html_with_options("# [a](b \"c\") [d][] ![e](f)\n\n[d]: g", &ids)?,
+        "<h1 id=\"a-d-\"><a href=\"b\" title=\"c\">a</a> <a href=\"g\">d</a> <img src=\"f\" alt=\"e\" /></h1>\n",
+        "should use the text of links, but not destinations, titles, or images"
+    );
+
+    assert_eq!(
+        to_html_with_options("# <https://a.b> <c@d.e>", &ids)?,
+        "<h1 id=\"httpsab-cde\"><a href=\"https://a.b\">https://a.b</a> <a href=\"mailto:c@d.e\">c@d.e</a></h1>",
+        "should use the text of autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a <b>c</b>", &ids)?,
+        "<h1 id=\"a-c\">a &lt;b&gt;c&lt;/b&gt;</h1>",
+        "should not use HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n=", &ids)?,
+        "<h1 id=\"ab\">a\nb</h1>",
+        "should drop line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("#", &ids)?,
+        "<h1></h1>",
+        "should not add ids to empty headings"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a ~~b~~ www.c.com",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..CompileOptions::gfm()
+                }
+            }
+        )?,
+        "<h1 id=\"a-b-wwwccom\">a <del>b</del> <a href=\"http://www.c.com\">www.c.com</a></h1>",
+        "should support GFM"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Invisibles, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn invisibles() -> Result<(), message::Message> {
+    let strip = Options {
+        compile: CompileOptions {
+            invisibles: Invisibles::Strip,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+    let replace = Options {
+        compile: CompileOptions {
+            invisibles: Invisibles::Replace,
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("a\u{200B}b\u{FEFF}c"),
+        "<p>a\u{200B}b\u{FEFF}c</p>",
+        "should keep invisible characters by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\u{200B}b\u{2060}c\u{FEFF}d", &strip)?,
+        "<p>abcd</p>",
+        "should strip invisible characters w/ `Strip`"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\u{200B}b\u{2060}c\u{FEFF}d", &replace)?,
+        "<p>a&#x200B;b&#x2060;c&#xFEFF;d</p>",
+        "should replace invisible characters w/ `Replace`"
+    );
+
+    assert_eq!(
+        to_html_with_options("\u{FEFF}# a\u{FEFF}b", &replace)?,
+        "<h1>a&#xFEFF;b</h1>",
+        "should still drop a bom at the start of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("\u{200B}\n\n*\u{200B}a\u{200B}*", &strip)?,
+        "<p></p>\n<p><em>a</em></p>",
+        "should strip in other constructs, even if nothing is left"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a\u{200B}](b \"c\u{200B}\") ![d\u{200B}](e)", &strip)?,
+        "<p><a href=\"b\" title=\"c\">a</a> <img src=\"e\" alt=\"d\" /></p>",
+        "should strip in links, titles, and image alts"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "`a\u{200B}b` &#x200B; <i title=\"\u{200B}\">\n\n    c\u{200B}",
+            &Options {
+                compile: CompileOptions {
+                    allow_dangerous_html: true,
+                    invisibles: Invisibles::Strip,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p><code>a\u{200B}b</code> \u{200B} <i title=\"\u{200B}\"></p>\n<pre><code>c\u{200B}\n</code></pre>",
+        "should not strip in code, character references, or HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("👨\u{200D}👩\u{200D}👧 a\u{200C}b", &strip)?,
+        "<p>👨\u{200D}👩\u{200D}👧 a\u{200C}b</p>",
+        "should not strip zero width joiners and non-joiners"
+    );
+
+    Ok(())
+}
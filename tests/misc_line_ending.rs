@@ -1,4 +1,4 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{message, to_html, to_html_with_options, CompileOptions, LineEnding, Options};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -186,5 +186,57 @@ fn line_ending() -> Result<(), message::Message> {
         "should support a non-blank line w/ carriage return + line feed in html"
     );
 
+    let lf = &Options {
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            line_ending: Some(LineEnding::LineFeed),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let crlf = &Options {
+        compile: CompileOptions {
+            line_ending: Some(LineEnding::CarriageReturnLineFeed),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("a\r\nb\rc\nd\r\n\r\n> e", lf)?,
+        "<p>a\nb\nc\nd</p>\n<blockquote>\n<p>e</p>\n</blockquote>",
+        "should support `line_ending` to normalize mixed line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n\n> c", crlf)?,
+        "<p>a\r\nb</p>\r\n<blockquote>\r\n<p>c</p>\r\n</blockquote>",
+        "should use `line_ending` over the line ending in the document"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \r\nb\\\rc", lf)?,
+        "<p>a<br />\nb<br />\nc</p>",
+        "should support `line_ending` after hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\r\na\rb\r\n```\r\n\r\n    c\r    d", lf)?,
+        "<pre><code>a\nb\n</code></pre>\n<pre><code>c\nd\n</code></pre>",
+        "should support `line_ending` in code"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\r\na\r\n\r\nb <i\rc>", lf)?,
+        "<div>\na\n<p>b <i\nc></p>",
+        "should support `line_ending` in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("\r\n\r\na", lf)?,
+        "<p>a</p>",
+        "should support `line_ending` w/ initial blank lines"
+    );
+
     Ok(())
 }
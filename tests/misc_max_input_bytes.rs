@@ -0,0 +1,109 @@
+use markdown::{
+    message, reparse, to_events, to_html, to_html_with_options, to_mdast, Edit, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+fn options(max: usize, truncate: bool) -> Options {
+    Options {
+        parse: ParseOptions {
+            max_input_bytes: Some(max),
+            truncate_input: truncate,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    }
+}
+
+#[test]
+fn max_input_bytes() -> Result<(), message::Message> {
+    let error = options(4, false);
+    let truncate = options(4, true);
+
+    assert_eq!(
+        to_html(&"a".repeat(1024)),
+        format!("<p>{}</p>", "a".repeat(1024)),
+        "should not limit input by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("*ab*", &error)?,
+        "<p><em>ab</em></p>",
+        "should support input up to the limit"
+    );
+
+    let result = to_html_with_options("*abc*", &error).unwrap_err();
+
+    assert_eq!(
+        (
+            result.place,
+            result.reason.as_str(),
+            result.rule_id.as_str(),
+            result.source.as_str()
+        ),
+        (
+            None,
+            "Unexpected input of 5 bytes, expected at most 4 bytes",
+            "max-input-bytes",
+            "markdown-rs"
+        ),
+        "should error on input over the limit"
+    );
+
+    assert_eq!(
+        (
+            to_mdast("*abc*", &error.parse).is_err(),
+            to_events("*abc*", &error.parse).is_err()
+        ),
+        (true, true),
+        "should error in other APIs too"
+    );
+
+    assert_eq!(
+        to_html_with_options("*abc*", &truncate)?,
+        "<p>*abc</p>",
+        "should truncate input w/ `truncate_input`"
+    );
+
+    assert_eq!(
+        to_html_with_options("abcé", &truncate)?,
+        "<p>abc</p>",
+        "should not split characters when truncating"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\na\nb\n```", &options(8, true))?,
+        "<pre><code>a\nb\n</code></pre>\n",
+        "should close constructs that are cut off"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\r\nb", &options(2, true))?,
+        "<p>a</p>\r",
+        "should support cutting off line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a", &options(0, true))?,
+        "",
+        "should support a limit of `0`"
+    );
+
+    let events = to_events("a\n\nb", &ParseOptions::default())?;
+    let edit = Edit {
+        start: 3,
+        old_end: 4,
+        new_end: 7,
+    };
+
+    assert_eq!(
+        reparse("a\n\nbcde", &events, &edit, &error.parse)
+            .unwrap_err()
+            .rule_id
+            .as_str(),
+        "max-input-bytes",
+        "should check the whole document when reparsing"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use markdown::{message, to_html, to_html_with_options, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_nesting_depth() -> Result<(), message::Message> {
+    let two = Options {
+        parse: ParseOptions {
+            max_nesting_depth: Some(2),
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("> > > a"),
+        "<blockquote>\n<blockquote>\n<blockquote>\n<p>a</p>\n</blockquote>\n</blockquote>\n</blockquote>",
+        "should not limit nesting by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(">>>a\n>>>b", &two)?,
+        "<blockquote>\n<blockquote>\n<p>&gt;a\n&gt;b</p>\n</blockquote>\n</blockquote>",
+        "should not open more block quotes than allowed"
+    );
+
+    assert_eq!(
+        to_html_with_options("> - a\n>   - b\n> c", &two)?,
+        "<blockquote>\n<ul>\n<li>a\n- b\nc</li>\n</ul>\n</blockquote>",
+        "should count different containers together"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n\n> > b\n\n- > c", &two)?,
+        "<blockquote>\n<p>a</p>\n</blockquote>\n<blockquote>\n<blockquote>\n<p>b</p>\n</blockquote>\n</blockquote>\n<ul>\n<li>\n<blockquote>\n<p>c</p>\n</blockquote>\n</li>\n</ul>",
+        "should support containers up to the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "> a",
+            &Options {
+                parse: ParseOptions {
+                    max_nesting_depth: Some(0),
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>&gt; a</p>",
+        "should support a limit of `0`"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a [b](c)](d)", &two)?,
+        "<p>[a <a href=\"c\">b</a>](d)</p>",
+        "should support labels up to the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a ![b](c)](d)", &two)?,
+        "<p><img src=\"d\" alt=\"a b\" /></p>",
+        "should support nested images up to the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a ![b ![c](d)](e)](f)", &two)?,
+        "<p>![a <img src=\"e\" alt=\"b c\" />](f)</p>",
+        "should turn the outermost label into text when nested too deeply"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a [b [c [d](/x)", &two)?,
+        "<p>[a [b [c <a href=\"/x\">d</a></p>",
+        "should not let unclosed brackets affect a later link"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a [b [c](d)](e)](f)", &two)?,
+        "<p>[a [b <a href=\"d\">c</a>](e)](f)</p>",
+        "should match the unlimited output for links within the limit"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            &format!("{}a{}", ">".repeat(10_000), "[".repeat(10_000)),
+            &two
+        )?,
+        format!(
+            "<blockquote>\n<blockquote>\n<p>{}a{}</p>\n</blockquote>\n</blockquote>",
+            "&gt;".repeat(9_998),
+            "[".repeat(10_000)
+        ),
+        "should parse deeply nested input with a limit"
+    );
+
+    Ok(())
+}
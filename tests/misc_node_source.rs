@@ -0,0 +1,83 @@
+use markdown::{mdast, message, node_source, to_mdast, unist::Position, ParseOptions};
+use pretty_assertions::assert_eq;
+
+/// Collect the sources of all nodes in a tree, depth-first.
+fn sources<'a>(value: &'a str, node: &mdast::Node) -> Vec<Option<&'a str>> {
+    let mut result = vec![node_source(value, node)];
+    if let Some(children) = node.children() {
+        for child in children {
+            result.append(&mut sources(value, child));
+        }
+    }
+    result
+}
+
+#[test]
+fn misc_node_source() -> Result<(), message::Message> {
+    let value = "# a *b*\n\n> * [c](d \"e\")\n>   ![f][g]\n\n[g]: h";
+    let tree = to_mdast(value, &ParseOptions::default())?;
+
+    assert_eq!(
+        sources(value, &tree),
+        vec![
+            Some(value),
+            Some("# a *b*"),
+            Some("a "),
+            Some("*b*"),
+            Some("b"),
+            Some("> * [c](d \"e\")\n>   ![f][g]"),
+            Some("* [c](d \"e\")\n>   ![f][g]"),
+            Some("* [c](d \"e\")\n>   ![f][g]"),
+            Some("[c](d \"e\")\n>   ![f][g]"),
+            Some("[c](d \"e\")"),
+            Some("c"),
+            Some("\n"),
+            Some("![f][g]"),
+            Some("[g]: h"),
+        ],
+        "should get the exact source of each node"
+    );
+
+    let value = ">\t\ta\n\n-\tb";
+    let tree = to_mdast(value, &ParseOptions::default())?;
+
+    assert_eq!(
+        sources(value, &tree),
+        vec![
+            Some(value),
+            Some(">\t\ta"),
+            Some("\t\ta"),
+            Some("-\tb"),
+            Some("-\tb"),
+            Some("b"),
+            Some("b"),
+        ],
+        "should include whole tabs, not virtual spaces"
+    );
+
+    assert_eq!(
+        node_source(
+            "a",
+            &mdast::Node::Text(mdast::Text {
+                value: "a".into(),
+                position: None
+            })
+        ),
+        None,
+        "should return `None` for nodes w/o position"
+    );
+
+    assert_eq!(
+        node_source(
+            "abc",
+            &mdast::Node::Text(mdast::Text {
+                value: "b".into(),
+                position: Some(Position::new(1, 2, 1, 1, 3, 2))
+            })
+        ),
+        Some("b"),
+        "should support nodes created by hand"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Text},
+    message, to_html_with_options, to_mdast,
+    unist::Position,
+    Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn plain_text() -> Result<(), message::Message> {
+    let plain = Options {
+        parse: ParseOptions {
+            plain_text: true,
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+    let plain_gfm = Options {
+        parse: ParseOptions {
+            plain_text: true,
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("*a* _b_ `c` [d](e) ![f](g) <h> <i@j.k> \\l &amp;", &plain)?,
+        "<p>*a* _b_ `c` [d](e) ![f](g) &lt;h&gt; &lt;i@j.k&gt; \\l &amp;amp;</p>",
+        "should not parse phrasing, but still encode"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &plain)?,
+        "<p>a\nb\\\nc</p>",
+        "should not form hard breaks, but still trim whitespace"
+    );
+
+    assert_eq!(
+        to_html_with_options("# *a* #\n\nb\n=\n\n> c\nd\n\n* e\n\n1. f", &plain)?,
+        "<h1>*a*</h1>\n<h1>b</h1>\n<blockquote>\n<p>c\nd</p>\n</blockquote>\n<ul>\n<li>e</li>\n</ul>\n<ol>\n<li>f</li>\n</ol>",
+        "should still parse block constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("```*a*\nb\n```\n\n[c]: <d> \"&amp;\"\n\n[c]", &plain)?,
+        "<pre><code class=\"language-*a*\">b\n</code></pre>\n<p>[c]</p>",
+        "should still parse strings and definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "* [x] a www.b.com ~c~ [^d]\n\n| e |\n| - |\n| *f* |\n\n[^d]: g",
+            &plain_gfm
+        )?,
+        "<ul>\n<li>[x] a www.b.com ~c~ [^d]</li>\n</ul>\n<table>\n<thead>\n<tr>\n<th>e</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>*f*</td>\n</tr>\n</tbody>\n</table>\n",
+        "should not parse GFM phrasing"
+    );
+
+    assert_eq!(
+        to_mdast("a *b*\nc", &plain.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a *b*\nc".into(),
+                    position: Some(Position::new(1, 1, 0, 2, 2, 7))
+                })],
+                position: Some(Position::new(1, 1, 0, 2, 2, 7))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 2, 7))
+        }),
+        "should support plain text in mdast"
+    );
+
+    Ok(())
+}
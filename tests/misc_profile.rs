@@ -0,0 +1,87 @@
+#![cfg(feature = "profile")]
+
+use markdown::{message, profile, Constructs, ParseOptions, Profile, ProfileEntry};
+use pretty_assertions::assert_eq;
+
+/// Get the entry for a construct or resolver, without the time.
+fn entry(profile: &Profile, name: &str, resolver: bool) -> Option<(usize, usize, usize)> {
+    profile
+        .entries
+        .iter()
+        .find(|d: &&ProfileEntry| d.name == name && d.resolver == resolver)
+        .map(|d| (d.count, d.nok, d.reverted))
+}
+
+#[test]
+fn misc_profile() -> Result<(), message::Message> {
+    let result = profile("# a\n\nb", &ParseOptions::default())?;
+
+    assert_eq!(
+        entry(&result, "HeadingAtxStart", false),
+        Some((2, 1, 0)),
+        "should count attempts of constructs"
+    );
+
+    assert_eq!(
+        entry(
+            &profile("**a", &ParseOptions::default())?,
+            "ThematicBreakStart",
+            false
+        ),
+        Some((2, 2, 4)),
+        "should count constructs that are not found, and the bytes they revert"
+    );
+
+    assert_eq!(
+        entry(&result, "HeadingAtx", true),
+        Some((1, 0, 0)),
+        "should count resolvers"
+    );
+
+    assert_eq!(
+        entry(&result, "LabelEndStart", false),
+        None,
+        "should not include constructs that are not attempted"
+    );
+
+    assert_eq!(
+        entry(
+            &profile(
+                &"[".repeat(100),
+                &ParseOptions {
+                    constructs: Constructs::gfm(),
+                    ..ParseOptions::default()
+                }
+            )?,
+            "GfmLabelStartFootnoteStart",
+            false
+        ),
+        Some((100, 100, 100)),
+        "should point to backtracking"
+    );
+
+    let durations = result
+        .entries
+        .iter()
+        .map(|d| d.duration)
+        .collect::<Vec<_>>();
+    let mut sorted = durations.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+
+    assert_eq!(durations, sorted, "should sort entries, slowest first");
+
+    let report = result.to_string();
+
+    assert_eq!(
+        report.lines().count(),
+        result.entries.len() + 1,
+        "should show a report, with a line for each entry"
+    );
+
+    assert!(
+        report.contains("HeadingAtx (resolver)"),
+        "should show resolvers in the report"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use markdown::{message, reparse, to_events, Constructs, Edit, Event, ParseOptions};
+use pretty_assertions::assert_eq;
+
+/// Apply `edit` (replacing with `insert`) to `value`, and check that
+/// reparsing gives the same events as parsing the result.
+fn check(
+    value: &str,
+    edit: &Edit,
+    insert: &str,
+    options: &ParseOptions,
+) -> Result<(), message::Message> {
+    let events = to_events(value, options)?;
+    let new_value = format!(
+        "{}{}{}",
+        &value[0..edit.start],
+        insert,
+        &value[edit.old_end..]
+    );
+    let expected = to_events(&new_value, options)?;
+    let actual: Vec<Event> = reparse(&new_value, &events, edit, options)?;
+
+    assert_eq!(
+        format!("{:#?}", actual),
+        format!("{:#?}", expected),
+        "should reparse {:?} to {:?}",
+        value,
+        new_value
+    );
+
+    Ok(())
+}
+
+#[test]
+fn reparse_paragraph() -> Result<(), message::Message> {
+    let options = ParseOptions::default();
+
+    check(
+        "# a\n\nb *c*\n\nd",
+        &Edit {
+            start: 5,
+            old_end: 6,
+            new_end: 8,
+        },
+        "xyz",
+        &options,
+    )?;
+
+    check(
+        "a\n\nb\nc\n\nd",
+        &Edit {
+            start: 5,
+            old_end: 5,
+            new_end: 7,
+        },
+        "\ne",
+        &options,
+    )?;
+
+    check(
+        "a\n\nb\nc\n\nd",
+        &Edit {
+            start: 4,
+            old_end: 6,
+            new_end: 4,
+        },
+        "",
+        &options,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn reparse_fallback() -> Result<(), message::Message> {
+    let options = ParseOptions::default();
+    let cases = [
+        ("a\n\nb\n\nc", 3, 4, "# b"),
+        ("a\n\nb\n\nc", 3, 4, "b\n==="),
+        ("a\n\nb\n\nc", 3, 4, "b\n\nd"),
+        ("a\n\nb\n\nc", 3, 4, "  b"),
+        ("- a\n\nb\n\nc", 5, 6, "  b"),
+        ("a\n\nb\n\nc", 3, 4, "[b]"),
+        ("a\n\nb\n\n[b]: c", 3, 4, "[b]"),
+        ("> a\n> b\n\nc", 4, 5, "d"),
+        ("a\nb\n\nc", 0, 1, "d"),
+        ("a\n\nb", 1, 2, ""),
+        ("a\n\nb", 0, 4, "c"),
+        ("a\n\nb\n\nc", 3, 4, "```"),
+        ("a\n\nb\n\nc", 3, 4, "<div>"),
+    ];
+    let mut index = 0;
+
+    while index < cases.len() {
+        let (value, start, old_end, insert) = cases[index];
+        check(
+            value,
+            &Edit {
+                start,
+                old_end,
+                new_end: start + insert.len(),
+            },
+            insert,
+            &options,
+        )?;
+        index += 1;
+    }
+
+    check(
+        "a\n\nb\n\nc",
+        &Edit {
+            start: 6,
+            old_end: 7,
+            new_end: 9,
+        },
+        ": c",
+        &ParseOptions {
+            constructs: Constructs {
+                definition_list: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+    )?;
+
+    check(
+        "*[HTML]: Hyper\n\nfoo",
+        &Edit {
+            start: 16,
+            old_end: 19,
+            new_end: 20,
+        },
+        "HTML",
+        &ParseOptions {
+            constructs: Constructs {
+                abbreviation: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn reparse_every_edit() -> Result<(), message::Message> {
+    let gfm = ParseOptions {
+        constructs: Constructs {
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    let values = [
+        "# a\n\nb *c* d\ne `f`\n\n- g\n\n  h\n\ni\n",
+        "a | b\n\nc\t~d~ $e$\r\nf\r\n\r\n> g\n\nhttps://i.j\n",
+        "\ta\n\nb\n\n    c\n\nd **e**\n\n1. f\n",
+    ];
+    let inserts = ["", "x", " ", "\n", "*", "-", "|", "#", "> ", "\t"];
+    let mut value_index = 0;
+
+    while value_index < values.len() {
+        let value = values[value_index];
+        let mut start = 0;
+
+        while start <= value.len() {
+            let mut insert_index = 0;
+
+            while insert_index < inserts.len() {
+                let insert = inserts[insert_index];
+                let mut old_end = start;
+
+                while old_end <= (start + 1).min(value.len()) {
+                    check(
+                        value,
+                        &Edit {
+                            start,
+                            old_end,
+                            new_end: start + insert.len(),
+                        },
+                        insert,
+                        &gfm,
+                    )?;
+                    old_end += 1;
+                }
+
+                insert_index += 1;
+            }
+
+            start += 1;
+        }
+
+        value_index += 1;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn smart_punctuation() -> Result<(), message::Message> {
+    let smart = Options {
+        compile: CompileOptions {
+            smart_punctuation: true,
+            allow_dangerous_html: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html("\"a\" 'b' c--d---e..."),
+        "<p>&quot;a&quot; 'b' c--d---e...</p>",
+        "should not support smart punctuation by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"a\" 'b'", &smart)?,
+        "<p>“a” ‘b’</p>",
+        "should support quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("Don't, it's the planets' moon", &smart)?,
+        "<p>Don’t, it’s the planets’ moon</p>",
+        "should support apostrophes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \"b 'c' d\" e", &smart)?,
+        "<p>a “b ‘c’ d” e</p>",
+        "should support nested quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("(\"a\") [\"b\"], \"c\".", &smart)?,
+        "<p>(“a”) [“b”], “c”.</p>",
+        "should support quotes next to punctuation"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"*a*\" *\"b\"*", &smart)?,
+        "<p>“<em>a</em>” <em>“b”</em></p>",
+        "should support quotes around and in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("\"a\nb\"", &smart)?,
+        "<p>“a\nb”</p>",
+        "should support quotes across lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a - b -- c --- d", &smart)?,
+        "<p>a - b – c — d</p>",
+        "should support dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a----b-----c------d-------e", &smart)?,
+        "<p>a––b—–c——d—––e</p>",
+        "should support long runs of dashes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a... b.... c......", &smart)?,
+        "<p>a… b…. c……</p>",
+        "should support ellipses"
+    );
+
+    assert_eq!(
+        to_html_with_options("`\"a\" -- b...`", &smart)?,
+        "<p><code>&quot;a&quot; -- b...</code></p>",
+        "should not support smart punctuation in code (text)"
+    );
+
+    assert_eq!(
+        to_html_with_options("```\"a\"--\n\"b\"...\n```", &smart)?,
+        "<pre><code class=\"language-&quot;a&quot;--\">&quot;b&quot;...\n</code></pre>",
+        "should not support smart punctuation in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a <b c=\"d\"> <!--e-->", &smart)?,
+        "<p>a <b c=\"d\"> <!--e--></p>",
+        "should not support smart punctuation in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("<https://a.b/c--d...>", &smart)?,
+        "<p><a href=\"https://a.b/c--d...\">https://a.b/c--d...</a></p>",
+        "should not support smart punctuation in autolinks"
+    );
+
+    assert_eq!(
+        to_html_with_options("\\\"a\\\" b\\-\\-c \\.\\.\\.", &smart)?,
+        "<p>&quot;a&quot; b--c ...</p>",
+        "should not support smart punctuation in character escapes"
+    );
+
+    assert_eq!(
+        to_html_with_options("&quot;a&quot; &#45;&#45;", &smart)?,
+        "<p>&quot;a&quot; --</p>",
+        "should not support smart punctuation in character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("[\"a\"](b--c \"d--e\") ![\"f\"](g)", &smart)?,
+        "<p><a href=\"b--c\" title=\"d--e\">“a”</a> <img src=\"g\" alt=\"“f”\" /></p>",
+        "should support smart punctuation in labels, but not in destinations and titles"
+    );
+
+    assert_eq!(
+        to_html_with_options("[\"a\"]\n\n[\"a\"]: b--c", &smart)?,
+        "<p><a href=\"b--c\">“a”</a></p>\n",
+        "should support smart punctuation in references, but not in definitions"
+    );
+
+    assert_eq!(
+        to_html_with_options("# \"a\" -- b", &smart)?,
+        "<h1>“a” – b</h1>",
+        "should support smart punctuation in headings"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use markdown::to_html;
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options, ParseOptions};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -15,3 +15,77 @@ fn soft_break() {
         "should trim spaces around line endings"
     );
 }
+
+#[test]
+fn hard_line_breaks() -> Result<(), message::Message> {
+    let breaks = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            hard_line_breaks: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("a\nb\r\nc", &breaks)?,
+        "<p>a<br />\nb<br />\r\nc</p>",
+        "should turn line endings in paragraphs into hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \n b", &breaks)?,
+        "<p>a<br />\nb</p>",
+        "should trim spaces around line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a  \nb\\\nc", &breaks)?,
+        "<p>a<br />\nb<br />\nc</p>",
+        "should not double hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a\nb* [c\nd](e)", &breaks)?,
+        "<p><em>a<br />\nb</em> <a href=\"e\">c<br />\nd</a></p>",
+        "should turn line endings in phrasing into hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb\n===", &breaks)?,
+        "<h1>a<br />\nb</h1>",
+        "should turn line endings in heading (setext) text into hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n> b\n\n* c\n  d", &breaks)?,
+        "<blockquote>\n<p>a<br />\nb</p>\n</blockquote>\n<ul>\n<li>c<br />\nd</li>\n</ul>",
+        "should turn line endings in paragraphs in containers into hard breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a\n\nb\n\n    c\n    d\n\n```\ne\nf\n```", &breaks)?,
+        "<h1>a</h1>\n<p>b</p>\n<pre><code>c\nd\n</code></pre>\n<pre><code>e\nf\n</code></pre>",
+        "should not add hard breaks between blocks or in code (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("<div>\na\n</div>\n\nb <i\nc>d", &breaks)?,
+        "<div>\na\n</div>\n<p>b <i\nc>d</p>",
+        "should not add hard breaks in HTML"
+    );
+
+    assert_eq!(
+        to_html_with_options("`a\nb` ![c\nd](e)", &breaks)?,
+        "<p><code>a b</code> <img src=\"e\" alt=\"c\nd\" /></p>",
+        "should not add hard breaks in code (text) or image alt text"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\nb", &Options::gfm())?,
+        "<p>a\nb</p>",
+        "should not add hard breaks by default"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use markdown::{message, to_html_with_options, CompileOptions, Constructs, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn source_positions() -> Result<(), message::Message> {
+    let positions = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            source_positions: true,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options("a\nb\n\nc", &positions)?,
+        "<p data-sourcepos=\"1:1-2:2\">a\nb</p>\n<p data-sourcepos=\"4:1-4:2\">c</p>",
+        "should add positions to paragraphs"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a #\n\nb\n=", &positions)?,
+        "<h1 data-sourcepos=\"1:1-1:6\">a</h1>\n<h1 data-sourcepos=\"3:1-4:2\">b</h1>",
+        "should add positions to headings"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "# a",
+            &Options {
+                compile: CompileOptions {
+                    heading_ids: true,
+                    ..positions.compile.clone()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<h1 data-sourcepos=\"1:1-1:4\" id=\"a\">a</h1>",
+        "should add positions to headings w/ ids"
+    );
+
+    assert_eq!(
+        to_html_with_options("***", &positions)?,
+        "<hr data-sourcepos=\"1:1-1:4\" />",
+        "should add positions to thematic breaks"
+    );
+
+    assert_eq!(
+        to_html_with_options("```js\na\n```\n\n    b", &positions)?,
+        "<pre data-sourcepos=\"1:1-3:4\"><code class=\"language-js\">a\n</code></pre>\n<pre data-sourcepos=\"5:1-5:6\"><code>b\n</code></pre>",
+        "should add positions to code (on `pre`)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "$$\na\n$$",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        math_flow: true,
+                        ..Constructs::default()
+                    },
+                    ..ParseOptions::default()
+                },
+                compile: positions.compile.clone(),
+            }
+        )?,
+        "<pre data-sourcepos=\"1:1-3:3\"><code class=\"language-math math-display\">a\n</code></pre>",
+        "should add positions to math (flow)"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n* b\n\n3. c", &positions)?,
+        "<ul data-sourcepos=\"1:1-3:1\">\n<li data-sourcepos=\"1:1-1:4\">a</li>\n<li data-sourcepos=\"2:1-3:1\">b</li>\n</ul>\n<ol start=\"3\" data-sourcepos=\"4:1-4:5\">\n<li data-sourcepos=\"4:1-4:5\">c</li>\n</ol>",
+        "should add positions to lists and list items (which include trailing blank lines, like in mdast)"
+    );
+
+    assert_eq!(
+        to_html_with_options("> * a\n>\n>   > b\n>   c", &positions)?,
+        "<blockquote data-sourcepos=\"1:1-4:6\">\n<ul data-sourcepos=\"1:3-4:6\">\n<li data-sourcepos=\"1:3-4:6\">\n<p data-sourcepos=\"1:5-1:6\">a</p>\n<blockquote data-sourcepos=\"3:5-4:6\">\n<p data-sourcepos=\"3:7-4:6\">b\nc</p>\n</blockquote>\n</li>\n</ul>\n</blockquote>",
+        "should add positions in containers"
+    );
+
+    assert_eq!(
+        to_html_with_options("| a |\n| - |\n| b |", &positions)?,
+        "<table data-sourcepos=\"1:1-3:6\">\n<thead>\n<tr data-sourcepos=\"1:1-1:6\">\n<th data-sourcepos=\"1:1-1:6\">a</th>\n</tr>\n</thead>\n<tbody>\n<tr data-sourcepos=\"3:1-3:6\">\n<td data-sourcepos=\"3:1-3:6\">b</td>\n</tr>\n</tbody>\n</table>",
+        "should add positions to tables, rows, and cells"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a* [b](c) `d`", &positions)?,
+        "<p data-sourcepos=\"1:1-1:15\"><em>a</em> <a href=\"c\">b</a> <code>d</code></p>",
+        "should not add positions to inline elements"
+    );
+
+    Ok(())
+}
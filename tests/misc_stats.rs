@@ -0,0 +1,117 @@
+use markdown::{message, stats, Constructs, DocumentStats, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn misc_stats() -> Result<(), message::Message> {
+    let words = |value: &str| -> Result<(usize, usize), message::Message> {
+        let result = stats(value, &ParseOptions::gfm())?;
+        Ok((result.words, result.characters))
+    };
+
+    assert_eq!(
+        stats("", &ParseOptions::default())?,
+        DocumentStats::default(),
+        "should support empty documents"
+    );
+
+    assert_eq!(
+        words("a b\nc")?,
+        (3, 3),
+        "should count words and characters, separated by whitespace"
+    );
+
+    assert_eq!(
+        words("**bold** a*b*c")?,
+        (2, 7),
+        "should count words as rendered"
+    );
+
+    assert_eq!(
+        words("# a\nb\n\nc\n-\n\n* d\n> e")?,
+        (5, 5),
+        "should separate words in different blocks"
+    );
+
+    assert_eq!(
+        words("| a | b |\n| - | - |\n| c|d |")?,
+        (4, 4),
+        "should separate words in different table cells"
+    );
+
+    assert_eq!(
+        words("a - b — c")?,
+        (3, 5),
+        "should not count punctuation as words, but as characters"
+    );
+
+    assert_eq!(
+        words("a&amp;b \\*c &#x44;")?,
+        (3, 6),
+        "should count character escapes and references as rendered"
+    );
+
+    assert_eq!(
+        words("a `b c` d\n\n```\ne f\n```\n\n    g h")?,
+        (2, 2),
+        "should not count code"
+    );
+
+    assert_eq!(
+        words("a <b c=\"d\"> e\n\n<div>\nf g\n</div>")?,
+        (2, 2),
+        "should not count HTML"
+    );
+
+    assert_eq!(
+        words("[a b](c \"d\") [e][f] ![g h](i)\n\n[f]: j \"k\"")?,
+        (3, 3),
+        "should count the text of links, but not destinations, titles, references, definitions, or images"
+    );
+
+    assert_eq!(
+        words("a[^b]\n\n[^b]: c d")?,
+        (3, 3),
+        "should count footnote definitions, but not calls"
+    );
+
+    assert_eq!(
+        words("<https://a.b> www.c.d")?,
+        (2, 18),
+        "should count autolinks and GFM autolink literals"
+    );
+
+    assert_eq!(
+        words("日本語のテキスト")?,
+        (8, 8),
+        "should count each CJK ideograph, hiragana, and katakana as a word"
+    );
+
+    assert_eq!(
+        words("a日b 한국어 텍스트")?,
+        (5, 9),
+        "should count other scripts, such as hangul, by whitespace"
+    );
+
+    assert_eq!(
+        stats(
+            "# a\n\nb\n=\n\n[c](d) <ee:f> www.g.h ![i](j)\n\n```\nk\n```\n\n    l\n\n$$\nm\n$$",
+            &ParseOptions {
+                constructs: Constructs {
+                    math_flow: true,
+                    ..Constructs::gfm()
+                },
+                ..ParseOptions::gfm()
+            }
+        )?,
+        DocumentStats {
+            words: 5,
+            characters: 14,
+            headings: 2,
+            links: 3,
+            code_blocks: 2
+        },
+        "should count headings, links, and code blocks"
+    );
+
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use markdown::{message, to_html, to_html_with_options, CompileOptions, Options};
+use markdown::{
+    mdast::{Code, Node, Paragraph, Root, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Options, ParseOptions,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -284,9 +289,280 @@ fn tabs_virtual_spaces() {
 
     assert_eq!(
         to_html("-\ta\n\n\tb"),
-        "<ul>\n<li>\n<p>a</p>\n<p>\tb</p>\n</li>\n</ul>",
-        // To do: CM.js does not output the tab before `b`. See if that makes sense?
-        // "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
         "should support a part of a tab as a container, and the rest of a tab as flow"
     );
 }
+
+#[test]
+fn tabs_containers() {
+    assert_eq!(
+        to_html("> a\n>\tb"),
+        "<blockquote>\n<p>a\nb</p>\n</blockquote>",
+        "should support a tab after a block quote continuation marker"
+    );
+
+    assert_eq!(
+        to_html(">\t  a"),
+        "<blockquote>\n<pre><code>a\n</code></pre>\n</blockquote>",
+        "should use the rest of a tab after a block quote marker as indent"
+    );
+
+    assert_eq!(
+        to_html(" >\t\ta"),
+        "<blockquote>\n<pre><code> a\n</code></pre>\n</blockquote>",
+        "should use the rest of a tab after an indented block quote marker as indent (1)"
+    );
+
+    assert_eq!(
+        to_html("   >\t\ta"),
+        "<blockquote>\n<pre><code>   a\n</code></pre>\n</blockquote>",
+        "should use the rest of a tab after an indented block quote marker as indent (3)"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n\t\tb"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>  b\n</code></pre>\n</li>\n</ul>",
+        "should use the rest of a tab after a list item continuation as indent"
+    );
+
+    assert_eq!(
+        to_html("10.\ta\n\n\t\tb"),
+        "<ol start=\"10\">\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n</li>\n</ol>",
+        "should measure a tab after a wide list item marker in columns"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n \t b"),
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should support spaces and tabs as list item continuation"
+    );
+
+    assert_eq!(
+        to_html("- a\n\t- b\n\n\t\tc"),
+        "<ul>\n<li>a\n<ul>\n<li>\n<p>b</p>\n<p>c</p>\n</li>\n</ul>\n</li>\n</ul>",
+        "should support tabs as continuation of nested list items"
+    );
+
+    assert_eq!(
+        to_html("> - a\n>\n>\tb"),
+        "<blockquote>\n<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>\n</blockquote>",
+        "should split a tab over a block quote and a list item"
+    );
+
+    assert_eq!(
+        to_html("> - a\n>\n>\t\tb"),
+        "<blockquote>\n<ul>\n<li>\n<p>a</p>\n<pre><code>b\n</code></pre>\n</li>\n</ul>\n</blockquote>",
+        "should split tabs over a block quote, a list item, and code (indented)"
+    );
+}
+
+#[test]
+fn tabs_code_data() -> Result<(), message::Message> {
+    assert_eq!(
+        to_html("\tall:\n\t\tcc -o a a.c\n\t\t./a"),
+        "<pre><code>all:\n\tcc -o a a.c\n\t./a\n</code></pre>",
+        "should keep tabs after the indent of code (indented)"
+    );
+
+    assert_eq!(
+        to_html("    a\t\tb\t"),
+        "<pre><code>a\t\tb\t\n</code></pre>",
+        "should keep tabs inside and after code (indented)"
+    );
+
+    assert_eq!(
+        to_html("\t\t\ta"),
+        "<pre><code>\t\ta\n</code></pre>",
+        "should keep several tabs after the indent of code (indented)"
+    );
+
+    assert_eq!(
+        to_html("    a\n\t\t\n    b"),
+        "<pre><code>a\n\t\nb\n</code></pre>",
+        "should keep tabs after the indent of blank lines in code (indented)"
+    );
+
+    assert_eq!(
+        to_html("```make\nall:\n\tcc\t-o a\n```"),
+        "<pre><code class=\"language-make\">all:\n\tcc\t-o a\n</code></pre>",
+        "should keep tabs in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html("~~~\n\t\t\n~~~"),
+        "<pre><code>\t\t\n</code></pre>",
+        "should keep tabs on otherwise blank lines in code (fenced)"
+    );
+
+    assert_eq!(
+        to_html("- a\n\n      \tb\n      \t\n      c"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>\tb\n\t\nc\n</code></pre>\n</li>\n</ul>",
+        "should keep tabs after the indent of code (indented) in list items"
+    );
+
+    assert_eq!(
+        to_html(">     \ta"),
+        "<blockquote>\n<pre><code>\ta\n</code></pre>\n</blockquote>",
+        "should keep tabs after the indent of code (indented) in block quotes"
+    );
+
+    assert_eq!(
+        to_html("1. ```\n   \tx\n   ```\n\n> ```\n> \ty\n> ```"),
+        "<ol>\n<li>\n<pre><code>\tx\n</code></pre>\n</li>\n</ol>\n<blockquote>\n<pre><code>\ty\n</code></pre>\n</blockquote>",
+        "should keep tabs after the indent of code (fenced) in containers"
+    );
+
+    assert_eq!(
+        to_html("-\ta\n\n\t\tb"),
+        "<ul>\n<li>\n<p>a</p>\n<pre><code>  b\n</code></pre>\n</li>\n</ul>",
+        "should use spaces for the rest of a tab that is partly used by the indent (list item)"
+    );
+
+    assert_eq!(
+        to_html("> \t\tfoo"),
+        "<blockquote>\n<pre><code>  foo\n</code></pre>\n</blockquote>",
+        "should use spaces for the rest of a tab that is partly used by the indent (block quote)"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "  \ta\n\t\tb",
+            &Options {
+                parse: ParseOptions {
+                    tab_size: 2,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code>\ta\n\tb\n</code></pre>",
+        "should keep tabs after the indent of code (indented) w/ the tab size"
+    );
+
+    assert_eq!(
+        to_mdast("\tall:\n\t\tcc", &Default::default())?,
+        Node::Root(Root {
+            children: vec![Node::Code(Code {
+                value: "all:\n\tcc".into(),
+                lang: None,
+                meta: None,
+                position: Some(Position::new(1, 1, 0, 2, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 2, 11, 10))
+        }),
+        "should keep tabs after the indent of code (indented) in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tabs_size() -> Result<(), message::Message> {
+    let two = Options {
+        parse: ParseOptions {
+            tab_size: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let eight = Options {
+        parse: ParseOptions {
+            tab_size: 8,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options("  a", &two)?,
+        "<pre><code>a\n</code></pre>",
+        "should support code (indented) w/ the tab size (2)"
+    );
+
+    assert_eq!(
+        to_html_with_options("       a", &eight)?,
+        "<p>a</p>",
+        "should not support code (indented) w/ less than the tab size (8)"
+    );
+
+    assert_eq!(
+        to_html_with_options("        a", &eight)?,
+        "<pre><code>a\n</code></pre>",
+        "should support code (indented) w/ the tab size (8)"
+    );
+
+    assert_eq!(
+        to_html_with_options(" # a", &two)?,
+        "<h1>a</h1>",
+        "should support one less than the tab size before flow constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("  # a", &two)?,
+        "<pre><code># a\n</code></pre>",
+        "should not support the tab size before flow constructs"
+    );
+
+    assert_eq!(
+        to_html_with_options("  > a", &two)?,
+        "<pre><code>&gt; a\n</code></pre>",
+        "should not support the tab size before container prefixes"
+    );
+
+    assert_eq!(
+        to_html_with_options(">\t\tb", &two)?,
+        "<blockquote>\n<pre><code>b\n</code></pre>\n</blockquote>",
+        "should expand tabs to the tab size (2)"
+    );
+
+    assert_eq!(
+        to_html_with_options(">\t\tb", &eight)?,
+        "<blockquote>\n<pre><code>      b\n</code></pre>\n</blockquote>",
+        "should expand tabs to the tab size (8)"
+    );
+
+    assert_eq!(
+        to_html_with_options("-\t\tb", &eight)?,
+        "<ul>\n<li>\n<pre><code>      b\n</code></pre>\n</li>\n</ul>",
+        "should measure list item prefixes w/ the tab size"
+    );
+
+    assert_eq!(
+        to_html_with_options("- a\n\n  b", &two)?,
+        "<ul>\n<li>\n<p>a</p>\n<p>b</p>\n</li>\n</ul>",
+        "should support list item continuation w/ the tab size"
+    );
+
+    assert_eq!(
+        to_mdast("a\tb", &two.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Text(Text {
+                    value: "a\tb".into(),
+                    position: Some(Position::new(1, 1, 0, 1, 4, 3))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 4, 3))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 4, 3))
+        }),
+        "should use the tab size in columns"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "\ta",
+            &Options {
+                parse: ParseOptions {
+                    tab_size: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<pre><code>a\n</code></pre>",
+        "should treat a tab size of `0` as `1`"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use markdown::{
+    message, text_runs, unist::Position, visible_text, Constructs, ParseOptions, TextRun,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn misc_text_runs() -> Result<(), message::Message> {
+    let gfm = ParseOptions {
+        constructs: Constructs {
+            math_flow: true,
+            math_text: true,
+            ..Constructs::gfm()
+        },
+        ..ParseOptions::gfm()
+    };
+    let text = |value: &str| visible_text(value, &gfm);
+
+    assert_eq!(text("")?, "", "should support empty documents");
+
+    assert_eq!(
+        text("# a #\n\nb\n===\n\n- c\n- d\n\n> e\n> f")?,
+        "a\n\nb\n\nc\n\nd\n\ne\nf",
+        "should separate blocks with a blank line, and keep line endings in them"
+    );
+
+    assert_eq!(
+        text("a  \nb\\\nc\n  d")?,
+        "a\nb\nc\nd",
+        "should turn breaks into line endings"
+    );
+
+    assert_eq!(
+        text("*a* **b** ~~c~~ \\* &amp; &#35;")?,
+        "a b c * & #",
+        "should drop markup, and decode escapes and character references"
+    );
+
+    assert_eq!(
+        text("[a](b 'c') [d][e] [e] ![f *g*](h) <https://i.j> <k@l.m> www.n.o\n\n[e]: p")?,
+        "a d e f g https://i.j k@l.m www.n.o",
+        "should keep the text of links and the alt of images, but not destinations, titles, references, or definitions"
+    );
+
+    assert_eq!(
+        text("```js a\n\n  b\n\tc\n\n```\n\n    d\n    \n    e")?,
+        "  b\n\tc\n\nd\n\ne",
+        "should keep the content of code, but not the info"
+    );
+
+    assert_eq!(
+        text("a `` b\nc `` d ` e `")?,
+        "a b c d e",
+        "should keep the content of code (text) as it compiles"
+    );
+
+    assert_eq!(
+        text("$$\nx^2\n$$\n\na $y$ b")?,
+        "x^2\n\na y b",
+        "should keep the content of math"
+    );
+
+    assert_eq!(
+        text("| a | `b\\|c` |\n| - | - |\n| d *e* | f |")?,
+        "a\n\nb|c\n\nd e\n\nf",
+        "should separate table cells"
+    );
+
+    assert_eq!(
+        text("a<b>c</b>\n\n<div>\nd\n</div>\n\n---")?,
+        "ac",
+        "should drop HTML and thematic breaks"
+    );
+
+    assert_eq!(
+        text("- [ ] a\n- [x] b[^c]\n\n[^c]: d")?,
+        "a\n\nb\n\nd",
+        "should drop task list item checks and footnote calls, but keep footnote definitions"
+    );
+
+    assert_eq!(
+        text("#\n\n```\n```\n\n![](a)")?,
+        "",
+        "should skip blocks without visible text"
+    );
+
+    assert_eq!(
+        text_runs("# a\n\n> b *c*", &ParseOptions::default())?.collect::<Vec<_>>(),
+        vec![
+            TextRun {
+                text: "a".into(),
+                position: Position::new(1, 1, 0, 1, 4, 3)
+            },
+            TextRun {
+                text: "b c".into(),
+                position: Position::new(3, 3, 7, 3, 8, 12)
+            }
+        ],
+        "should support runs with positions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,487 @@
+use markdown::{
+    message, to_html_with_options, to_markdown, Constructs, FormatOptions, ListItemOptions,
+    Options, ParseOptions, ProseWrap,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn to_markdown_flow() -> Result<(), message::Message> {
+    let format =
+        |value: &str| to_markdown(value, &ParseOptions::default(), &FormatOptions::default());
+
+    assert_eq!(format("")?, "", "should support an empty document");
+
+    assert_eq!(
+        format("a\n\n\n\nb")?,
+        "a\n\nb\n",
+        "should put one blank line between blocks"
+    );
+
+    assert_eq!(
+        format("a\n===\n\nb\n-\n\n### c ###\n\n#")?,
+        "# a\n\n## b\n\n### c\n\n#\n",
+        "should use atx headings"
+    );
+
+    assert_eq!(
+        format("# a \\#\n\n# b#")?,
+        "# a \\#\n\n# b\\#\n",
+        "should escape a closing sequence in headings"
+    );
+
+    assert_eq!(
+        format("a\\\nb\n===")?,
+        "a\\\nb\n===\n",
+        "should use setext headings for hard breaks in headings"
+    );
+
+    assert_eq!(
+        format("* * *\n\n___")?,
+        "***\n\n***\n",
+        "should use `***` for thematic breaks"
+    );
+
+    assert_eq!(
+        format("    a\n\n\n    b")?,
+        "```\na\n\n\nb\n```\n",
+        "should use fenced code for indented code"
+    );
+
+    assert_eq!(
+        format("~~~js  a  b\n  *c*\n\n~~~")?,
+        "```js a  b\n  *c*\n\n```\n",
+        "should keep code verbatim"
+    );
+
+    assert_eq!(
+        format("````\n```\n````\n\n~~~a`b\n~~~")?,
+        "````\n```\n````\n\n~~~a`b\n~~~\n",
+        "should use fences that are not in the code"
+    );
+
+    assert_eq!(
+        format("~~~ ~a`b\nc\n~~~")?,
+        "~~~ ~a`b\nc\n~~~\n",
+        "should not merge info starting with the marker into the fence"
+    );
+
+    assert_eq!(
+        format("```\n```")?,
+        "```\n```\n",
+        "should support empty code"
+    );
+
+    assert_eq!(
+        format("<div>\n*a*\n</div>")?,
+        "<div>\n*a*\n</div>\n",
+        "should keep HTML verbatim"
+    );
+
+    assert_eq!(
+        format(">a\n> > b\n>\n>c")?,
+        "> a\n>\n> > b\n>\n> c\n",
+        "should support block quotes"
+    );
+
+    let deep = "> ".repeat(5000) + "a";
+
+    assert_eq!(
+        format(&deep)?,
+        format!("{}\n", deep),
+        "should keep very deep trees as they are"
+    );
+
+    assert_eq!(
+        format("[A]:\n/b 'c\"d'\n\n[e]: <f g>")?,
+        "[A]: /b \"c\\\"d\"\n\n[e]: <f g>\n",
+        "should support definitions"
+    );
+
+    assert_eq!(
+        format("[a\\]b]: c\n\n[d][a\\]b] [e\\\\f\\[]\n\n[e\\\\f\\[]: g")?,
+        "[a\\]b]: c\n\n[d][a\\]b] [e\\\\f\\[]\n\n[e\\\\f\\[]: g\n",
+        "should escape brackets and backslashes in labels"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_list() -> Result<(), message::Message> {
+    let format =
+        |value: &str| to_markdown(value, &ParseOptions::default(), &FormatOptions::default());
+
+    assert_eq!(
+        format("* a\n* b\n\n+ c")?,
+        "- a\n- b\n\n* c\n",
+        "should use `-` for bullets, and another bullet for a list after a list"
+    );
+
+    assert_eq!(
+        format("- a\n\n- b")?,
+        "- a\n\n- b\n",
+        "should keep loose lists loose"
+    );
+
+    assert_eq!(
+        format("- a\n  b\n\n      c\n- > d\n  e")?,
+        "- a\n  b\n\n  ```\n  c\n  ```\n\n- > d\n  > e\n",
+        "should indent the content of list items"
+    );
+
+    assert_eq!(
+        format("3) a\n3) b\n\n10. c\n11. d\n* e\n\n1. f")?,
+        "3) a\n4) b\n\n10. c\n11. d\n\n- e\n\n1. f\n",
+        "should number ordered lists from their start"
+    );
+
+    assert_eq!(
+        format("10. a\n\n    b")?,
+        "10. a\n\n    b\n",
+        "should indent by the size of the marker"
+    );
+
+    assert_eq!(
+        format("- * *\n\n- a\n  - b\n    - c")?,
+        "- * -\n\n- a\n\n  * b\n    - c\n",
+        "should use another bullet for lists in list items"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "- ***",
+            &ParseOptions::default(),
+            &FormatOptions {
+                bullet: '*',
+                ..FormatOptions::default()
+            }
+        )?,
+        "* ---\n",
+        "should not use `***` for a thematic break at the start of a `*` list item"
+    );
+
+    assert_eq!(
+        format("-\n- a\n-")?,
+        "-\n- a\n-\n",
+        "should support empty list items"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "- [ ] a\n- [x]\n  b",
+            &ParseOptions::gfm(),
+            &FormatOptions::default()
+        )?,
+        "- [ ] a\n- [x] b\n",
+        "should support tasklist items"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "- a\n\n- b",
+            &ParseOptions::default(),
+            &FormatOptions {
+                bullet: '+',
+                ..FormatOptions::default()
+            }
+        )?,
+        "+ a\n\n+ b\n",
+        "should support `bullet`"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "+ a\n* b\n+ c\n\n1. d",
+            &ParseOptions {
+                list_item: ListItemOptions {
+                    bullet_dash: false,
+                    delimiter_parenthesis: false,
+                    ..ListItemOptions::default()
+                },
+                ..ParseOptions::default()
+            },
+            &FormatOptions::default()
+        )?,
+        "* a\n\n+ b\n\n* c\n\n1. d\n",
+        "should only use markers allowed by `list_item`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_phrasing() -> Result<(), message::Message> {
+    let format =
+        |value: &str| to_markdown(value, &ParseOptions::default(), &FormatOptions::default());
+
+    assert_eq!(
+        format("a  \nb\\\nc")?,
+        "a\\\nb\\\nc\n",
+        "should use backslashes for hard breaks"
+    );
+
+    assert_eq!(
+        format("_a_ __b__ ***c***")?,
+        "*a* **b** ***c***\n",
+        "should normalize attention markers"
+    );
+
+    assert_eq!(
+        format("*a _b_ c*")?,
+        "*a _b_ c*\n",
+        "should use the other marker for nested emphasis"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "*a* **b**",
+            &ParseOptions::default(),
+            &FormatOptions {
+                emphasis: '_',
+                strong: '_',
+                ..FormatOptions::default()
+            }
+        )?,
+        "_a_ __b__\n",
+        "should support `emphasis` and `strong`"
+    );
+
+    assert_eq!(
+        format("`` a`b `` ` `` ` `a`")?,
+        "``a`b`` ` `` ` `a`\n",
+        "should support code (text)"
+    );
+
+    assert_eq!(
+        format("\\*a\\* \\_b\\_ c_d \\[e\\] \\` \\\\ &amp;amp; \\<f> 1\\. g")?,
+        "\\*a\\* \\_b\\_ c_d \\[e\\] \\` \\\\ \\&amp; \\<f> 1. g\n",
+        "should escape text that would otherwise be syntax"
+    );
+
+    assert_eq!(
+        format("\\# a\n\\> b\n\\- c\n1\\. d\n\\+ e")?,
+        "\\# a\n\\> b\n\\- c\n1\\. d\n\\+ e\n",
+        "should escape text at the start of lines"
+    );
+
+    assert_eq!(
+        format("[a](b 'c') ![d](<e f>) [g](<>) <https://h> <i@j.k>")?,
+        "[a](b \"c\") ![d](<e f>) [g](<>) <https://h> <i@j.k>\n",
+        "should support links, images, and autolinks"
+    );
+
+    assert_eq!(
+        format("[a][B] [c][] [d] ![e][] [f][]\\(g)\n\n[b]: h\n[c]: i\n[d]: j\n[e]: k\n[f]: l")?,
+        "[a][B] [c][] [d] ![e][] [f][](g)\n\n[b]: h\n\n[c]: i\n\n[d]: j\n\n[e]: k\n\n[f]: l\n",
+        "should keep references by default"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "[a][b] ![c] [d]\n\n[b]: e 'f'\n[c]: <g h>",
+            &ParseOptions::default(),
+            &FormatOptions {
+                inline_links: true,
+                ..FormatOptions::default()
+            }
+        )?,
+        "[a](e \"f\") ![c](<g h>) \\[d\\]\n",
+        "should support `inline_links`"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "www.a.com_, https://b.c) ~~d~~ [^e]\n\n[^e]: f\n\n    g",
+            &ParseOptions::gfm(),
+            &FormatOptions::default()
+        )?,
+        "www.a.com_, https://b.c) ~~d~~ [^e]\n\n[^e]: f\n\n    g\n",
+        "should support GFM autolink literals, strikethrough, and footnotes"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "| a | b | c |\n|:-|:-:|-:|\n| dd | `e\\|f` |",
+            &ParseOptions::gfm(),
+            &FormatOptions::default()
+        )?,
+        "| a   |   b    |   c |\n| :-- | :----: | --: |\n| dd  | `e\\|f` |     |\n",
+        "should support GFM tables"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_prose_wrap() -> Result<(), message::Message> {
+    let wrap = |value: &str, prose_wrap: ProseWrap| {
+        to_markdown(
+            value,
+            &ParseOptions::default(),
+            &FormatOptions {
+                prose_wrap,
+                print_width: 10,
+                ..FormatOptions::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        wrap("aaa bbb\nccc ddd eee fff", ProseWrap::Preserve)?,
+        "aaa bbb\nccc ddd eee fff\n",
+        "should keep line endings with `ProseWrap::Preserve`"
+    );
+
+    assert_eq!(
+        wrap("aaa bbb\nccc ddd eee fff", ProseWrap::Never)?,
+        "aaa bbb ccc ddd eee fff\n",
+        "should join lines with `ProseWrap::Never`"
+    );
+
+    assert_eq!(
+        wrap("aaa bbb\nccc ddd eee fff", ProseWrap::Always)?,
+        "aaa bbb\nccc ddd\neee fff\n",
+        "should wrap lines with `ProseWrap::Always`"
+    );
+
+    assert_eq!(
+        wrap("aaaaaaaaaaaa b", ProseWrap::Always)?,
+        "aaaaaaaaaaaa\nb\n",
+        "should not break long words"
+    );
+
+    assert_eq!(
+        wrap("aaa bbb\\\nccc", ProseWrap::Never)?,
+        "aaa bbb\\\nccc\n",
+        "should keep hard breaks"
+    );
+
+    assert_eq!(
+        wrap("aaa `b c d e f g`", ProseWrap::Always)?,
+        "aaa\n`b c d e f g`\n",
+        "should not wrap in code (text)"
+    );
+
+    assert_eq!(
+        wrap("> - aaa bbb ccc", ProseWrap::Always)?,
+        "> - aaa\n>   bbb\n>   ccc\n",
+        "should wrap in containers"
+    );
+
+    assert_eq!(
+        wrap("aaaaaaaaa # b aaaaaaa - c", ProseWrap::Always)?,
+        "aaaaaaaaa\n\\# b\naaaaaaa -\nc\n",
+        "should escape text that would start a block when wrapping"
+    );
+
+    assert_eq!(
+        wrap("aaaaaaaaa <div> b", ProseWrap::Always)?,
+        "aaaaaaaaa <div>\nb\n",
+        "should not put HTML at the start of lines"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_unformatted() -> Result<(), message::Message> {
+    let parse = ParseOptions {
+        constructs: Constructs {
+            abbreviation: true,
+            attributes: true,
+            definition_list: true,
+            directive_container: true,
+            directive_leaf: true,
+            directive_text: true,
+            highlight: true,
+            subscript: true,
+            superscript: true,
+            ..Constructs::gfm()
+        },
+        gfm_strikethrough_single_tilde: false,
+        ..ParseOptions::gfm()
+    };
+    let options = FormatOptions::default();
+
+    assert_eq!(
+        to_markdown("==a== ~b~ ^c^", &parse, &options)?,
+        "==a== ~b~ ^c^\n",
+        "should keep highlight, subscript, and superscript"
+    );
+
+    assert_eq!(
+        to_markdown(
+            ":::note\nhi\n:::\n\n::a[b]{c}\n\nd :e[f]{g} h",
+            &parse,
+            &options
+        )?,
+        ":::note\nhi\n:::\n\n::a[b]{c}\n\nd :e[f]{g} h\n",
+        "should keep directives"
+    );
+
+    assert_eq!(
+        to_markdown("> :::note\n> hi\n> :::", &parse, &options)?,
+        "> :::note\n> hi\n> :::\n",
+        "should keep directives in containers"
+    );
+
+    assert_eq!(
+        to_markdown("a\n: b\n\n  c\n  : d\n\n*e*", &parse, &options)?,
+        "a\n: b\n\n  c\n  : d\n\n*e*\n",
+        "should keep definition lists"
+    );
+
+    assert_eq!(
+        to_markdown("* a\n\n  b\n  : c\n\n* d", &parse, &options)?,
+        "- a\n\n  b\n  : c\n\n- d\n",
+        "should keep definition lists in list items"
+    );
+
+    assert_eq!(
+        to_markdown(
+            "*[HTML]: Hyper\n\n> HTML\n>\n> *[CSS]: Style",
+            &parse,
+            &options
+        )?,
+        "*[HTML]: Hyper\n\n> HTML\n>\n> *[CSS]: Style\n",
+        "should keep abbreviation definitions"
+    );
+
+    assert_eq!(
+        to_markdown("# a {#b}\n\nc {.d}\n\n_e_", &parse, &options)?,
+        "# a {#b}\n\nc {.d}\n\n*e*\n",
+        "should keep headings and paragraphs with attributes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn to_markdown_round_trip() -> Result<(), message::Message> {
+    let options = Options::gfm();
+    let value = "# a *b*\n\nc **d** `e` [f](g) ![h][i]\nj\\\nk\n\n[i]: l\n\n> - m\n>\n>   n\n\n1. [ ] o\n2. p\n\n```js\nq\n```\n\n| r | s |\n| - | - |\n| t | u |\n";
+    let formatted = to_markdown(value, &options.parse, &FormatOptions::default())?;
+
+    assert_eq!(
+        to_html_with_options(&formatted, &options)?,
+        to_html_with_options(value, &options)?,
+        "should format markdown without changing what it means"
+    );
+
+    assert_eq!(
+        to_markdown(&formatted, &options.parse, &FormatOptions::default())?,
+        formatted,
+        "should format formatted markdown to itself"
+    );
+
+    let value = "<!b  ')ba@b.c\u{e9}2) ";
+    let formatted = to_markdown(value, &options.parse, &FormatOptions::default())?;
+
+    assert_eq!(
+        to_markdown(&formatted, &options.parse, &FormatOptions::default())?,
+        formatted,
+        "should format HTML at the end of the document to itself"
+    );
+
+    Ok(())
+}
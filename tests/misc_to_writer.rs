@@ -0,0 +1,151 @@
+#![cfg(feature = "std")]
+
+use markdown::{
+    message, to_html_to_writer, to_html_with_options, CompileOptions, FootnoteStyle, Options,
+    ParseOptions,
+};
+use pretty_assertions::assert_eq;
+use std::io;
+
+/// Writer that keeps the chunks it gets.
+#[derive(Default)]
+struct Chunks(Vec<String>);
+
+impl io::Write for Chunks {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.push(String::from_utf8(buf.to_vec()).unwrap());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writer that always fails.
+struct Broken;
+
+impl io::Write for Broken {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn to_string(value: &str, options: &Options) -> io::Result<String> {
+    let mut html = vec![];
+    to_html_to_writer(value, options, &mut html)?;
+    Ok(String::from_utf8(html).unwrap())
+}
+
+#[test]
+fn to_writer() -> Result<(), message::Message> {
+    let gfm = Options::gfm();
+    let ids = Options {
+        compile: CompileOptions {
+            heading_ids: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let cases = [
+        "# a\n\nb *c*\n\n> d\n> - e",
+        "[a]\n\n[a]: b",
+        "a[^b] c[^d]\n\n[^d]: e\n[^b]: f\n\n- g[^b]",
+        "a\r\nb\r\n\r\n***",
+        "* a\n\n\n  b\n* c\n\n    d",
+        "| a |\n| - |\n| b |",
+        "",
+    ];
+    let mut index = 0;
+
+    while index < cases.len() {
+        assert_eq!(
+            to_string(cases[index], &gfm).unwrap(),
+            to_html_with_options(cases[index], &gfm)?,
+            "should write the same as `to_html_with_options` ({})",
+            index
+        );
+        index += 1;
+    }
+
+    assert_eq!(
+        to_string("# a\n\n# a", &ids).unwrap(),
+        to_html_with_options("# a\n\n# a", &ids)?,
+        "should write the same as `to_html_with_options` w/ heading ids"
+    );
+
+    let inline = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            gfm_footnote_style: FootnoteStyle::Inline,
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_string(cases[2], &inline).unwrap(),
+        to_html_with_options(cases[2], &inline)?,
+        "should write the same as `to_html_with_options` w/ inline footnotes"
+    );
+
+    let mut chunks = Chunks::default();
+    to_html_to_writer("a\n\nb\n\nc", &Options::default(), &mut chunks).unwrap();
+
+    assert!(
+        chunks.0.len() > 1,
+        "should write in chunks, while compiling"
+    );
+
+    assert_eq!(
+        chunks.0.concat(),
+        "<p>a</p>\n<p>b</p>\n<p>c</p>",
+        "should write everything"
+    );
+
+    let mut chunks = Chunks::default();
+    to_html_to_writer("a[^b]\n\n[^b]: c\n\nd", &gfm, &mut chunks).unwrap();
+
+    assert!(
+        chunks.0[chunks.0.len() - 1].starts_with("\n<section data-footnotes"),
+        "should write the footnote section last"
+    );
+
+    assert_eq!(
+        to_html_to_writer("a", &Options::default(), &mut Broken)
+            .unwrap_err()
+            .kind(),
+        io::ErrorKind::BrokenPipe,
+        "should pass errors from the writer through"
+    );
+
+    let error = to_html_to_writer(
+        "{a",
+        &Options {
+            parse: ParseOptions::mdx(),
+            ..Default::default()
+        },
+        &mut Vec::new(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error.kind(),
+        io::ErrorKind::InvalidData,
+        "should turn syntax errors into `InvalidData` errors"
+    );
+
+    assert!(
+        error
+            .into_inner()
+            .unwrap()
+            .downcast::<message::Message>()
+            .is_ok(),
+        "should keep the message in syntax errors"
+    );
+
+    Ok(())
+}
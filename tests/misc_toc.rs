@@ -0,0 +1,109 @@
+use markdown::{
+    message, to_html_with_options, toc, unist::Position, CompileOptions, Options, ParseOptions,
+    TocEntry,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn misc_toc() -> Result<(), message::Message> {
+    let entries = |value: &str| -> Result<Vec<(u8, String, String)>, message::Message> {
+        Ok(toc(value, &ParseOptions::gfm())?
+            .into_iter()
+            .map(|d| (d.depth, d.text, d.id))
+            .collect())
+    };
+
+    assert_eq!(
+        toc("a\n\n    # b", &ParseOptions::default())?,
+        vec![],
+        "should support documents w/o headings"
+    );
+
+    assert_eq!(
+        toc("# a\n\nb\n-", &ParseOptions::default())?,
+        vec![
+            TocEntry {
+                depth: 1,
+                text: "a".into(),
+                id: "a".into(),
+                position: Position::new(1, 1, 0, 1, 4, 3)
+            },
+            TocEntry {
+                depth: 2,
+                text: "b".into(),
+                id: "b".into(),
+                position: Position::new(3, 1, 5, 4, 2, 8)
+            }
+        ],
+        "should support atx and setext headings"
+    );
+
+    assert_eq!(
+        entries("# 1\n## 2\n### 3 ###\n#### 4\n##### 5\n###### 6\n####### 7\n\na\n=\nb\n---")?
+            .into_iter()
+            .map(|d| d.0)
+            .collect::<Vec<_>>(),
+        vec![1, 2, 3, 4, 5, 6, 1, 2],
+        "should support all depths"
+    );
+
+    assert_eq!(
+        entries("# *a* `b` [c](d) ![e](f) <g> &amp; \\* h[^i]\n\n[^i]: j")?,
+        vec![(1, "a b c   & * h".into(), "a-b-c-----h".into())],
+        "should use the plain text of headings"
+    );
+
+    assert_eq!(
+        entries("# a\n\na\n=\n\n# A\n\n#\n\n# a-1")?,
+        vec![
+            (1, "a".into(), "a".into()),
+            (1, "a".into(), "a-1".into()),
+            (1, "A".into(), "a-2".into()),
+            (1, "".into(), "".into()),
+            (1, "a-1".into(), "a-1-1".into())
+        ],
+        "should make ids unique, and not for headings w/o text"
+    );
+
+    assert_eq!(
+        entries("> # a\n\n* b\n  ---\n\n```\n# c\n```\n\n<div>\n# d\n</div>")?,
+        vec![(1, "a".into(), "a".into()), (2, "b".into(), "b".into())],
+        "should support headings in containers, but not in code or HTML"
+    );
+
+    assert_eq!(
+        toc(
+            "### a",
+            &ParseOptions {
+                max_heading_depth: 2,
+                ..ParseOptions::default()
+            }
+        )?,
+        vec![],
+        "should not include what `max_heading_depth` turns into paragraphs"
+    );
+
+    let value = "# a *b*\n\n## a b\n\nc\n---\n\n# `d` &copy;";
+    let html = to_html_with_options(
+        value,
+        &Options {
+            compile: CompileOptions {
+                heading_ids: true,
+                ..CompileOptions::default()
+            },
+            ..Options::default()
+        },
+    )?;
+
+    assert_eq!(
+        toc(value, &ParseOptions::default())?
+            .into_iter()
+            .map(|d| format!("<h{} id=\"{}\">", d.depth, d.id))
+            .filter(|d| html.contains(d))
+            .count(),
+        4,
+        "should match the ranks and ids in HTML"
+    );
+
+    Ok(())
+}
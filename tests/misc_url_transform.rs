@@ -0,0 +1,133 @@
+use markdown::{message, to_html_with_options, CompileOptions, Options, UrlContext};
+use pretty_assertions::assert_eq;
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn url_transform() -> Result<(), message::Message> {
+    let seen = Rc::new(RefCell::new(vec![]));
+    let seen_in_transform = Rc::clone(&seen);
+    let record = Options {
+        compile: CompileOptions {
+            url_transform: Some(Rc::new(move |url: UrlContext| {
+                seen_in_transform.borrow_mut().push(url.clone());
+                Some(url.destination)
+            })),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b&amp;c \"d&amp;e\") ![f](g) [h] <ij:k>\n\n[h]: k\\*l",
+            &record
+        )?,
+        "<p><a href=\"b&amp;c\" title=\"d&amp;e\">a</a> <img src=\"g\" alt=\"f\" /> <a href=\"k*l\">h</a> <a href=\"\">ij:k</a></p>\n",
+        "should call `url_transform` for links, images, references, and autolinks"
+    );
+
+    assert_eq!(
+        seen.borrow().clone(),
+        vec![
+            UrlContext {
+                destination: "b&c".into(),
+                image: false,
+                title: Some("d&e".into())
+            },
+            UrlContext {
+                destination: "g".into(),
+                image: true,
+                title: None
+            },
+            UrlContext {
+                destination: "k*l".into(),
+                image: false,
+                title: None
+            },
+            UrlContext {
+                destination: "ij:k".into(),
+                image: false,
+                title: None
+            }
+        ],
+        "should pass decoded destinations and titles to `url_transform`"
+    );
+
+    let allowlist = Options {
+        compile: CompileOptions {
+            allow_dangerous_protocol: true,
+            url_transform: Some(Rc::new(|url: UrlContext| {
+                if url.destination.starts_with("https:") || url.destination.starts_with("mailto:") {
+                    Some(url.destination)
+                } else {
+                    None
+                }
+            })),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](https://b) [c](javascript:alert(1)) <mailto:d@e> <f@g> ![h](data:i)",
+            &allowlist
+        )?,
+        "<p><a href=\"https://b\">a</a> <a>c</a> <a href=\"mailto:d@e\">mailto:d@e</a> <a href=\"mailto:f@g\">f@g</a> <img alt=\"h\" /></p>",
+        "should drop `href` and `src` if `url_transform` returns `None`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b)",
+            &Options {
+                compile: CompileOptions {
+                    url_transform: Some(Rc::new(|_url: UrlContext| {
+                        Some("javascript:alert(1)".into())
+                    })),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"\">a</a></p>",
+        "should check the result of `url_transform` for dangerous protocols"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[a](b)",
+            &Options {
+                compile: CompileOptions {
+                    base_url: Some("https://example.com/".into()),
+                    url_transform: Some(Rc::new(|url: UrlContext| {
+                        Some(format!("{}.html\"<", url.destination))
+                    })),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><a href=\"https://example.com/b.html%22%3C\">a</a></p>",
+        "should resolve and encode the result of `url_transform`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "![a ![b](c \"d\")](e \"f\")",
+            &Options {
+                compile: CompileOptions {
+                    url_transform: Some(Rc::new(|url: UrlContext| {
+                        Some(url.destination.to_uppercase())
+                    })),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        )?,
+        "<p><img src=\"E\" alt=\"a b\" title=\"f\" /></p>",
+        "should not call `url_transform` for images in images"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn wrap() -> Result<(), message::Message> {
+    let article = Options {
+        compile: CompileOptions {
+            wrap: Some("article".into()),
+            ..CompileOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(to_html("a"), "<p>a</p>", "should not wrap by default");
+
+    assert_eq!(
+        to_html_with_options("a\n\n> b", &article)?,
+        "<article>\n<p>a</p>\n<blockquote>\n<p>b</p>\n</blockquote>\n</article>",
+        "should wrap the output"
+    );
+
+    assert_eq!(
+        to_html_with_options("", &article)?,
+        "<article>\n</article>",
+        "should wrap empty output"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\r\nb", &article)?,
+        "<article>\r\n<p>a\r\nb</p>\r\n</article>",
+        "should use the line ending of the document"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "[^a]\n\n[^a]: b",
+            &Options {
+                parse: ParseOptions::gfm(),
+                compile: CompileOptions {
+                    wrap: Some("article".into()),
+                    ..CompileOptions::gfm()
+                }
+            }
+        )?,
+        "<article>\n<p><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<p>b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n</article>",
+        "should wrap the footnote section"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn paragraph_wrapper() -> Result<(), message::Message> {
+    let prose = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            paragraph_wrapper: Some("div".into()),
+            paragraph_attributes: Some("class=\"prose-paragraph\"".into()),
+            ..CompileOptions::gfm()
+        },
+    };
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                compile: CompileOptions {
+                    paragraph_wrapper: Some("div".into()),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<div>a</div>",
+        "should support a tag name"
+    );
+
+    assert_eq!(
+        to_html_with_options("a\n\nb", &prose)?,
+        "<div class=\"prose-paragraph\">a</div>\n<div class=\"prose-paragraph\">b</div>",
+        "should support a tag name and attributes"
+    );
+
+    assert_eq!(
+        to_html_with_options("> a\n>\n> > b", &prose)?,
+        "<blockquote>\n<div class=\"prose-paragraph\">a</div>\n<blockquote>\n<div class=\"prose-paragraph\">b</div>\n</blockquote>\n</blockquote>",
+        "should support paragraphs in block quotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n\n* b", &prose)?,
+        "<ul>\n<li>\n<div class=\"prose-paragraph\">a</div>\n</li>\n<li>\n<div class=\"prose-paragraph\">b</div>\n</li>\n</ul>",
+        "should support paragraphs in loose list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("* a\n* b", &prose)?,
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>",
+        "should not show paragraphs in tight list items"
+    );
+
+    assert_eq!(
+        to_html_with_options("[^a]\n\n[^a]: b", &prose)?,
+        "<div class=\"prose-paragraph\"><sup><a href=\"#user-content-fn-a\" id=\"user-content-fnref-a\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></div>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-a\">\n<div class=\"prose-paragraph\">b <a href=\"#user-content-fnref-a\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></div>\n</li>\n</ol>\n</section>\n",
+        "should support paragraphs in footnote definitions, with backreferences in them"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a",
+            &Options {
+                compile: CompileOptions {
+                    source_positions: true,
+                    paragraph_attributes: Some("class=\"prose-paragraph\"".into()),
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p data-sourcepos=\"1:1-1:2\" class=\"prose-paragraph\">a</p>",
+        "should support attributes w/ positions"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use markdown::{message, to_html, to_html_with_options, CompileOptions, Options, ParseOptions};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn xhtml() -> Result<(), message::Message> {
+    let html = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            xhtml: false,
+            ..CompileOptions::gfm()
+        },
+    };
+    let xhtml = Options {
+        parse: ParseOptions::gfm(),
+        compile: CompileOptions {
+            allow_dangerous_html: true,
+            ..CompileOptions::gfm()
+        },
+    };
+    let value = "* [x] a\\\n  b  \n  c\n* [ ] ![d](e \"f\")\n\n***";
+
+    assert_eq!(
+        to_html_with_options(value, &xhtml)?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\" /> a<br />\nb<br />\nc</li>\n<li><input type=\"checkbox\" disabled=\"\" /> <img src=\"e\" alt=\"d\" title=\"f\" /></li>\n</ul>\n<hr />",
+        "should self-close void elements by default"
+    );
+
+    assert_eq!(
+        to_html_with_options(value, &html)?,
+        "<ul>\n<li><input type=\"checkbox\" disabled=\"\" checked=\"\"> a<br>\nb<br>\nc</li>\n<li><input type=\"checkbox\" disabled=\"\"> <img src=\"e\" alt=\"d\" title=\"f\"></li>\n</ul>\n<hr>",
+        "should not self-close void elements w/o `xhtml`"
+    );
+
+    assert_eq!(
+        to_html("***"),
+        "<hr />",
+        "should self-close void elements w/o options"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a ![b](c)](d)", &html)?,
+        "<p><img src=\"d\" alt=\"a b\"></p>",
+        "should support nested images w/o `xhtml`"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a\nb",
+            &Options {
+                compile: CompileOptions {
+                    hard_line_breaks: true,
+                    xhtml: false,
+                    ..CompileOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a<br>\nb</p>",
+        "should support `hard_line_breaks` w/o `xhtml`"
+    );
+
+    assert_eq!(
+        to_html_with_options("<br/> a <br />\n\n<hr/>", &html)?,
+        "<p><br/> a <br /></p>\n<hr/>",
+        "should not change void elements in HTML"
+    );
+
+    Ok(())
+}
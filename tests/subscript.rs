@@ -0,0 +1,194 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Subscript, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn subscript() -> Result<(), message::Message> {
+    let subscript = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                subscript: true,
+                ..Constructs::default()
+            },
+            ..ParseOptions::default()
+        },
+        ..Options::default()
+    };
+
+    assert_eq!(
+        to_html("H~2~O"),
+        "<p>H~2~O</p>",
+        "should ignore subscript by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("H~2~O", &subscript)?,
+        "<p>H<sub>2</sub>O</p>",
+        "should support subscript w/ one tilde"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b~ c", &subscript)?,
+        "<p>a <sub>b</sub> c</p>",
+        "should support subscript between words"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~b~~ c", &subscript)?,
+        "<p>a ~~b~~ c</p>",
+        "should not support two tildes w/o strikethrough"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b~~ c", &subscript)?,
+        "<p>a ~b~~ c</p>",
+        "should not support mismatched sequences"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b c~ d", &subscript)?,
+        "<p>a ~b c~ d</p>",
+        "should not support subscript w/ spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b\nc~ d", &subscript)?,
+        "<p>a ~b\nc~ d</p>",
+        "should not support subscript w/ line endings"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b\\ c~ d", &subscript)?,
+        "<p>a ~b\\ c~ d</p>",
+        "should not support subscript w/ backslash-escaped spaces (not an escape in `CommonMark`)"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b&#32;c~ d", &subscript)?,
+        "<p>a <sub>b c</sub> d</p>",
+        "should support subscript w/ spaces as character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~*b*~ c", &subscript)?,
+        "<p>a <sub><em>b</em></sub> c</p>",
+        "should support emphasis in subscript"
+    );
+
+    assert_eq!(
+        to_html_with_options("a `~b~` c", &subscript)?,
+        "<p>a <code>~b~</code> c</p>",
+        "should not support subscript in code"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a~b~](c)", &subscript)?,
+        "<p><img src=\"c\" alt=\"ab\" /></p>",
+        "should not add tags in image alts"
+    );
+
+    assert_eq!(
+        to_mdast("H~2~O", &subscript.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "H".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::Subscript(Subscript {
+                        children: vec![Node::Text(Text {
+                            value: "2".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 4, 3))
+                        })],
+                        position: Some(Position::new(1, 2, 1, 1, 5, 4))
+                    }),
+                    Node::Text(Text {
+                        value: "O".into(),
+                        position: Some(Position::new(1, 5, 4, 1, 6, 5))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should support subscript as `Subscript`s in mdast"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn subscript_strikethrough() -> Result<(), message::Message> {
+    let both = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                subscript: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html_with_options("a ~b~ c", &Options::gfm())?,
+        "<p>a <del>b</del> c</p>",
+        "should support one tilde as strikethrough w/o subscript"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b~ c", &both)?,
+        "<p>a <sub>b</sub> c</p>",
+        "should support one tilde as subscript w/ both"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~b~~ c", &both)?,
+        "<p>a <del>b</del> c</p>",
+        "should support two tildes as strikethrough w/ both"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b c~ d", &both)?,
+        "<p>a ~b c~ d</p>",
+        "should not fall back to strikethrough for one tilde w/ spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~H~2~O~~ b", &both)?,
+        "<p>a <del>H<sub>2</sub>O</del> b</p>",
+        "should support subscript in strikethrough"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~~~b~~~ c", &both)?,
+        "<p>a ~~~b~~~ c</p>",
+        "should not support three tildes w/ both"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a ~b~ c",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        subscript: true,
+                        ..Constructs::gfm()
+                    },
+                    gfm_strikethrough_single_tilde: false,
+                    ..ParseOptions::gfm()
+                },
+                ..Options::gfm()
+            }
+        )?,
+        "<p>a <sub>b</sub> c</p>",
+        "should support subscript w/ `gfm_strikethrough_single_tilde: false`"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use markdown::{
+    mdast::{Node, Paragraph, Root, Superscript, Text},
+    message, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    Constructs, Options, ParseOptions,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn superscript() -> Result<(), message::Message> {
+    let superscript = Options {
+        parse: ParseOptions {
+            constructs: Constructs {
+                superscript: true,
+                ..Constructs::gfm()
+            },
+            ..ParseOptions::gfm()
+        },
+        ..Options::gfm()
+    };
+
+    assert_eq!(
+        to_html("2^10^"),
+        "<p>2^10^</p>",
+        "should ignore superscript by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("2^10^", &superscript)?,
+        "<p>2<sup>10</sup></p>",
+        "should support superscript w/ one caret"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b^ c", &superscript)?,
+        "<p>a <sup>b</sup> c</p>",
+        "should support superscript between words"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^^b^^ c", &superscript)?,
+        "<p>a ^^b^^ c</p>",
+        "should not support superscript w/ two carets"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b c^ d", &superscript)?,
+        "<p>a ^b c^ d</p>",
+        "should not support superscript w/ spaces"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b&#32;c^ d", &superscript)?,
+        "<p>a <sup>b c</sup> d</p>",
+        "should support superscript w/ spaces as character references"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^b c", &superscript)?,
+        "<p>a ^b c</p>",
+        "should not support an unmatched sequence"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ^*b*^ c *^d^*", &superscript)?,
+        "<p>a <sup><em>b</em></sup> c <em><sup>d</sup></em></p>",
+        "should support superscript and emphasis in each other"
+    );
+
+    assert_eq!(
+        to_html_with_options("a \\^b^ c", &superscript)?,
+        "<p>a ^b^ c</p>",
+        "should not support superscript w/ an escaped caret"
+    );
+
+    assert_eq!(
+        to_html_with_options("a[^b]\n\n[^b]: c^d^", &superscript)?,
+        "<p>a<sup><a href=\"#user-content-fn-b\" id=\"user-content-fnref-b\" data-footnote-ref=\"\" aria-describedby=\"footnote-label\">1</a></sup></p>\n<section data-footnotes=\"\" class=\"footnotes\"><h2 id=\"footnote-label\" class=\"sr-only\">Footnotes</h2>\n<ol>\n<li id=\"user-content-fn-b\">\n<p>c<sup>d</sup> <a href=\"#user-content-fnref-b\" data-footnote-backref=\"\" aria-label=\"Back to content\" class=\"data-footnote-backref\">↩</a></p>\n</li>\n</ol>\n</section>\n",
+        "should not interfere with footnotes"
+    );
+
+    assert_eq!(
+        to_html_with_options("a ~b~ ^c^", &superscript)?,
+        "<p>a <del>b</del> <sup>c</sup></p>",
+        "should support superscript next to strikethrough"
+    );
+
+    assert_eq!(
+        to_mdast("2^10^", &superscript.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![
+                    Node::Text(Text {
+                        value: "2".into(),
+                        position: Some(Position::new(1, 1, 0, 1, 2, 1))
+                    }),
+                    Node::Superscript(Superscript {
+                        children: vec![Node::Text(Text {
+                            value: "10".into(),
+                            position: Some(Position::new(1, 3, 2, 1, 5, 4))
+                        })],
+                        position: Some(Position::new(1, 2, 1, 1, 6, 5))
+                    })
+                ],
+                position: Some(Position::new(1, 1, 0, 1, 6, 5))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 6, 5))
+        }),
+        "should support superscript as `Superscript`s in mdast"
+    );
+
+    Ok(())
+}
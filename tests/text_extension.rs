@@ -0,0 +1,283 @@
+use markdown::{
+    mdast::{Emphasis, Node, Paragraph, Root, Text},
+    message, to_events, to_html, to_html_with_options, to_mdast,
+    unist::Position,
+    CompileOptions, Constructs, EventKind, EventName, Options, ParseOptions, TextExtension,
+};
+use pretty_assertions::assert_eq;
+use std::rc::Rc;
+
+/// Emoji shortcodes, such as `:tada:`.
+fn emoji() -> TextExtension {
+    TextExtension {
+        name: "emoji".into(),
+        marker: b':',
+        start: Box::new(|rest| {
+            let size = rest[1..].find(':')?;
+            let name = &rest[1..=size];
+
+            if !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_lowercase() || byte == b'_' || byte == b'*')
+            {
+                Some(size + 2)
+            } else {
+                None
+            }
+        }),
+    }
+}
+
+/// Mentions, such as `@venus`.
+fn mention() -> TextExtension {
+    TextExtension {
+        name: "mention".into(),
+        marker: b'@',
+        start: Box::new(|rest| {
+            let size = rest[1..]
+                .find(|char: char| !char.is_ascii_alphanumeric())
+                .unwrap_or(rest.len() - 1);
+
+            if size > 0 {
+                Some(size + 1)
+            } else {
+                None
+            }
+        }),
+    }
+}
+
+fn options(extensions: Vec<TextExtension>) -> Options {
+    Options {
+        parse: ParseOptions {
+            text_extensions: extensions,
+            ..ParseOptions::gfm()
+        },
+        compile: CompileOptions {
+            text_extension_handler: Some(Rc::new(|name, value| match (name, value) {
+                ("emoji", ":tada:") => Some("🎉".into()),
+                ("emoji", ":a*b:") => Some("<i>ab</i>".into()),
+                ("mention", _) => Some(format!("<a href=\"/{}\">{}</a>", &value[1..], value)),
+                _ => None,
+            })),
+            ..CompileOptions::gfm()
+        },
+    }
+}
+
+#[test]
+fn text_extension() -> Result<(), message::Message> {
+    let emoji_only = options(vec![emoji()]);
+    let both = options(vec![emoji(), mention()]);
+
+    assert_eq!(
+        to_html("a :tada: b"),
+        "<p>a :tada: b</p>",
+        "should not support text extensions by default"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :tada: b", &emoji_only)?,
+        "<p>a 🎉 b</p>",
+        "should support an emoji shortcode"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :smile: b", &emoji_only)?,
+        "<p>a :smile: b</p>",
+        "should render a construct as text if the handler does not handle it"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :tada: <b>",
+            &Options {
+                parse: ParseOptions {
+                    text_extensions: vec![emoji()],
+                    ..ParseOptions::default()
+                },
+                ..Options::default()
+            }
+        )?,
+        "<p>a :tada: &lt;b&gt;</p>",
+        "should render a construct as text w/o handler"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :b c: d :: e :", &emoji_only)?,
+        "<p>a :b c: d :: e :</p>",
+        "should not form constructs that `start` does not accept"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :tada:\n:tada: b", &emoji_only)?,
+        "<p>a 🎉\n🎉 b</p>",
+        "should support constructs at the end and start of lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("a :ta\nda: b", &emoji_only)?,
+        "<p>a :ta\nda: b</p>",
+        "should not support constructs across lines"
+    );
+
+    assert_eq!(
+        to_html_with_options("Hi @venus and @mars, :tada:!", &both)?,
+        "<p>Hi <a href=\"/venus\">@venus</a> and <a href=\"/mars\">@mars</a>, 🎉!</p>",
+        "should support several extensions"
+    );
+
+    assert_eq!(
+        to_html_with_options("*a :tada:* **@b**", &both)?,
+        "<p><em>a 🎉</em> <strong><a href=\"/b\">@b</a></strong></p>",
+        "should support constructs in attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("*x :a*b: y*", &emoji_only)?,
+        "<p><em>x <i>ab</i> y</em></p>",
+        "should hide the value of constructs from attention"
+    );
+
+    assert_eq!(
+        to_html_with_options("[a :tada:](b)", &emoji_only)?,
+        "<p><a href=\"b\">a 🎉</a></p>",
+        "should support constructs in links"
+    );
+
+    assert_eq!(
+        to_html_with_options("![a :tada:](b)", &emoji_only)?,
+        "<p><img src=\"b\" alt=\"a :tada:\" /></p>",
+        "should render constructs as text in image alts"
+    );
+
+    assert_eq!(
+        to_html_with_options("`:tada:` \\:tada:", &emoji_only)?,
+        "<p><code>:tada:</code> :tada:</p>",
+        "should not support constructs in code or escaped markers"
+    );
+
+    assert_eq!(
+        to_html_with_options("# a :tada: #\n\n# b :ta #da:", &emoji_only)?,
+        "<h1>a 🎉</h1>\n<h1>b :ta #da:</h1>",
+        "should support constructs in headings, but not past their text"
+    );
+
+    assert_eq!(
+        to_html_with_options("a@venus.com https://a.b/@c", &both)?,
+        "<p>a<a href=\"/venus\">@venus</a>.com <a href=\"https://a.b/@c\">https://a.b/@c</a></p>",
+        "should hide bytes from GFM email autolink literals, but not start inside protocol literals"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :tada: :b[c]",
+            &Options {
+                parse: ParseOptions {
+                    constructs: Constructs {
+                        directive_text: true,
+                        ..Constructs::default()
+                    },
+                    text_extensions: vec![emoji()],
+                    ..ParseOptions::default()
+                },
+                compile: CompileOptions {
+                    text_extension_handler: Some(Rc::new(|_name, _value| Some("🎉".into()))),
+                    ..CompileOptions::default()
+                }
+            }
+        )?,
+        "<p>a 🎉 c</p>",
+        "should go before built-in constructs w/ the same marker"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :b:",
+            &options(vec![
+                TextExtension {
+                    name: "first".into(),
+                    marker: b':',
+                    start: Box::new(|_rest| None),
+                },
+                TextExtension {
+                    name: "second".into(),
+                    marker: b':',
+                    start: Box::new(|_rest| Some(3)),
+                },
+                TextExtension {
+                    name: "third".into(),
+                    marker: b':',
+                    start: Box::new(|_rest| Some(2)),
+                },
+            ])
+        )?,
+        "<p>a :b:</p>",
+        "should try extensions w/ the same marker in order"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :b",
+            &options(vec![TextExtension {
+                name: "broken".into(),
+                marker: b':',
+                start: Box::new(|_rest| Some(100)),
+            }])
+        )?,
+        "<p>a :b</p>",
+        "should ignore sizes past the end of the line"
+    );
+
+    assert_eq!(
+        to_html_with_options(
+            "a :tada:",
+            &Options {
+                parse: ParseOptions {
+                    plain_text: true,
+                    text_extensions: vec![emoji()],
+                    ..ParseOptions::default()
+                },
+                ..options(vec![])
+            }
+        )?,
+        "<p>a :tada:</p>",
+        "should not support constructs in plain text"
+    );
+
+    let events = to_events("a :tada:", &emoji_only.parse)?;
+    assert_eq!(
+        events
+            .iter()
+            .filter(|d| d.kind == EventKind::Enter)
+            .map(|d| d.name.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            EventName::Paragraph,
+            EventName::Data,
+            EventName::TextExtension("emoji".into())
+        ],
+        "should emit `TextExtension` events"
+    );
+
+    assert_eq!(
+        to_mdast("*a :tada:*", &emoji_only.parse)?,
+        Node::Root(Root {
+            children: vec![Node::Paragraph(Paragraph {
+                children: vec![Node::Emphasis(Emphasis {
+                    children: vec![Node::Text(Text {
+                        value: "a :tada:".into(),
+                        position: Some(Position::new(1, 2, 1, 1, 10, 9))
+                    })],
+                    position: Some(Position::new(1, 1, 0, 1, 11, 10))
+                })],
+                position: Some(Position::new(1, 1, 0, 1, 11, 10))
+            })],
+            position: Some(Position::new(1, 1, 0, 1, 11, 10))
+        }),
+        "should support constructs as text in mdast"
+    );
+
+    Ok(())
+}
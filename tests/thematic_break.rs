@@ -1,8 +1,8 @@
 use markdown::{
     mdast::{Node, Root, ThematicBreak},
-    message, to_html, to_html_with_options, to_mdast,
+    message, thematic_break_info, to_events, to_html, to_html_with_options, to_mdast,
     unist::Position,
-    Constructs, Options, ParseOptions,
+    Constructs, EventKind, EventName, Options, ParseOptions,
 };
 use pretty_assertions::assert_eq;
 
@@ -201,3 +201,51 @@ fn thematic_break() -> Result<(), message::Message> {
 
     Ok(())
 }
+
+#[test]
+fn thematic_break_info_api() -> Result<(), message::Message> {
+    let infos = |value: &str| -> Result<Vec<(char, usize)>, message::Message> {
+        let events = to_events(value, &ParseOptions::default())?;
+        let mut infos = vec![];
+        let mut index = 0;
+
+        while index < events.len() {
+            if events[index].kind == EventKind::Enter
+                && events[index].name == EventName::ThematicBreak
+            {
+                let info = thematic_break_info(value, &events, index);
+                infos.push((info.marker, info.size));
+            }
+
+            index += 1;
+        }
+
+        Ok(infos)
+    };
+
+    assert_eq!(
+        infos("***\n---\n___")?,
+        vec![('*', 3), ('-', 3), ('_', 3)],
+        "should support the markers"
+    );
+
+    assert_eq!(
+        infos(" - - -- \n_\t_\t_\t_")?,
+        vec![('-', 4), ('_', 4)],
+        "should count markers, not whitespace"
+    );
+
+    assert_eq!(
+        infos("> ****\n* * * *\n- ___")?,
+        vec![('*', 4), ('*', 4), ('_', 3)],
+        "should support thematic breaks in containers, and thematic breaks over list items"
+    );
+
+    assert_eq!(
+        infos("a\n---\n\nb\n***")?,
+        vec![('*', 3)],
+        "should not treat setext heading underlines as thematic breaks"
+    );
+
+    Ok(())
+}